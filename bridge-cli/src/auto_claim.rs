@@ -0,0 +1,442 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `bridge-cli client auto-claim`: claims approved Eth -> Starcoin transfers
+//! the bridge nodes left unclaimed.
+//!
+//! Each round scans seq nums upwards from the first one not known to be done,
+//! through `ClaimSource` so the scan can be tested without a chain. Progress
+//! is kept in a small JSON state file, so a restart resumes where the last run
+//! stopped instead of scanning from zero.
+
+use crate::{claim_on_starcoin, LoadedBridgeCliConfig};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::metrics::BridgeMetrics;
+use starcoin_bridge::retry_with_max_elapsed_time;
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge::types::{BridgeActionStatus, ParsedTokenTransferMessage};
+use starcoin_bridge_types::base_types::StarcoinAddress;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// Seq nums past the last recorded one that are still checked each round, as
+// transfers are not always approved in order
+const LOOKAHEAD: u64 = 16;
+// Each RPC attempt gets this long, and is retried with backoff for up to
+// `MAX_RPC_RETRY` before the round is given up
+const RPC_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_RPC_RETRY: Duration = Duration::from_secs(300);
+const CLAIM_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AutoClaimState {
+    pub source_chain_id: u8,
+    // Every transfer below this one is claimed or not for us
+    pub next_seq_num: u64,
+}
+
+impl AutoClaimState {
+    // Resumes from `path` if it exists. `start_seq_num` only takes effect when
+    // it is past the recorded progress.
+    pub fn load_or_new(
+        path: &Path,
+        source_chain_id: u8,
+        start_seq_num: u64,
+    ) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                source_chain_id,
+                next_seq_num: start_seq_num,
+            });
+        }
+        let state: Self = serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Invalid auto-claim state file {:?}: {:?}", path, e))?;
+        if state.source_chain_id != source_chain_id {
+            return Err(anyhow!(
+                "Auto-claim state file {:?} is for source chain {}, not {}",
+                path,
+                state.source_chain_id,
+                source_chain_id
+            ));
+        }
+        Ok(Self {
+            next_seq_num: state.next_seq_num.max(start_seq_num),
+            ..state
+        })
+    }
+
+    // Writes through a temporary file, so a crash never leaves a torn state
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+// Where the auto-claimer reads transfers from and claims them. Implemented
+// over RPC by `RpcClaimSource`.
+#[async_trait]
+pub trait ClaimSource: Send + Sync {
+    async fn status(&self, seq_num: u64) -> anyhow::Result<BridgeActionStatus>;
+    async fn message(&self, seq_num: u64) -> anyhow::Result<Option<ParsedTokenTransferMessage>>;
+    async fn claim(&self, seq_num: u64) -> anyhow::Result<()>;
+}
+
+pub struct AutoClaimer<S> {
+    source: S,
+    state: AutoClaimState,
+    state_file: PathBuf,
+    // Only transfers to this account are claimed, any if None
+    recipient: Option<StarcoinAddress>,
+    dry_run: bool,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<S: ClaimSource> AutoClaimer<S> {
+    pub fn new(
+        source: S,
+        state: AutoClaimState,
+        state_file: PathBuf,
+        recipient: Option<StarcoinAddress>,
+        dry_run: bool,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            source,
+            state,
+            state_file,
+            recipient,
+            dry_run,
+            metrics,
+        }
+    }
+
+    pub fn state(&self) -> &AutoClaimState {
+        &self.state
+    }
+
+    pub async fn run(mut self, poll_interval: Duration) -> anyhow::Result<()> {
+        let mut interval = tokio::time::interval(poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("Auto-claim round stopped early: {:?}", e);
+            }
+        }
+    }
+
+    // Claims what can be claimed from `next_seq_num` on, and moves
+    // `next_seq_num` past every transfer that needs nothing more. Progress is
+    // not saved in dry runs, so a real run still claims what was only logged.
+    pub async fn poll(&mut self) -> anyhow::Result<()> {
+        let source_chain_id = self.state.source_chain_id;
+        let mut seq_num = self.state.next_seq_num;
+        let mut done_so_far = true;
+        let mut unrecorded = 0;
+        while unrecorded < LOOKAHEAD {
+            let status = retry_with_max_elapsed_time!(self.source.status(seq_num), MAX_RPC_RETRY)
+                .map_err(|e| anyhow!("{:?}", e))?
+                .map_err(|e| anyhow!("Failed to get status of transfer {seq_num}: {:?}", e))?;
+            let done = match status {
+                BridgeActionStatus::NotFound => {
+                    unrecorded += 1;
+                    false
+                }
+                BridgeActionStatus::Pending => {
+                    unrecorded = 0;
+                    false
+                }
+                BridgeActionStatus::Claimed => {
+                    unrecorded = 0;
+                    true
+                }
+                BridgeActionStatus::Approved => {
+                    unrecorded = 0;
+                    self.claim_if_ours(seq_num).await?
+                }
+            };
+            done_so_far &= done;
+            if done_so_far {
+                self.state.next_seq_num = seq_num + 1;
+                if !self.dry_run {
+                    self.state.save(&self.state_file)?;
+                }
+            }
+            seq_num += 1;
+        }
+        info!(
+            source_chain_id,
+            next_seq_num = self.state.next_seq_num,
+            "Auto-claim round finished"
+        );
+        Ok(())
+    }
+
+    // Whether the approved transfer `seq_num` needs nothing more from us
+    async fn claim_if_ours(&self, seq_num: u64) -> anyhow::Result<bool> {
+        let message = retry_with_max_elapsed_time!(self.source.message(seq_num), MAX_RPC_RETRY)
+            .map_err(|e| anyhow!("{:?}", e))?
+            .map_err(|e| anyhow!("Failed to get transfer {seq_num}: {:?}", e))?
+            .ok_or_else(|| anyhow!("Transfer {seq_num} is approved but has no record"))?;
+        let target_address = &message.parsed_payload.target_address;
+        if let Some(recipient) = &self.recipient {
+            if target_address != &recipient.to_vec() {
+                return Ok(true);
+            }
+        }
+        let source_chain_id = self.state.source_chain_id;
+        let recipient = format!("0x{}", hex::encode(target_address));
+        if self.dry_run {
+            info!(
+                source_chain_id,
+                seq_num,
+                recipient,
+                amount = message.parsed_payload.amount,
+                "Would claim transfer"
+            );
+            return Ok(false);
+        }
+        self.metrics.auto_claim_attempted.inc();
+        match self.source.claim(seq_num).await {
+            Ok(()) => {
+                self.metrics.auto_claim_succeeded.inc();
+                info!(source_chain_id, seq_num, recipient, "Claimed transfer");
+                Ok(true)
+            }
+            Err(e) => {
+                // Retried in the next round
+                self.metrics.auto_claim_failed.inc();
+                warn!(
+                    source_chain_id,
+                    seq_num, recipient, "Failed to claim transfer: {:?}", e
+                );
+                Ok(false)
+            }
+        }
+    }
+}
+
+pub struct RpcClaimSource<'a> {
+    pub source_chain_id: u8,
+    pub config: &'a LoadedBridgeCliConfig,
+    pub starcoin_bridge_client: &'a StarcoinBridgeClient,
+}
+
+#[async_trait]
+impl ClaimSource for RpcClaimSource<'_> {
+    async fn status(&self, seq_num: u64) -> anyhow::Result<BridgeActionStatus> {
+        self.starcoin_bridge_client
+            .get_token_transfer_action_onchain_status(
+                self.source_chain_id,
+                seq_num,
+                Deadline::after(RPC_ATTEMPT_TIMEOUT),
+            )
+            .await
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+
+    async fn message(&self, seq_num: u64) -> anyhow::Result<Option<ParsedTokenTransferMessage>> {
+        self.starcoin_bridge_client
+            .get_parsed_token_transfer_message(
+                self.source_chain_id,
+                seq_num,
+                Deadline::after(RPC_ATTEMPT_TIMEOUT),
+            )
+            .await
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+
+    async fn claim(&self, seq_num: u64) -> anyhow::Result<()> {
+        claim_on_starcoin(
+            self.source_chain_id,
+            seq_num,
+            None,
+            self.config,
+            self.starcoin_bridge_client,
+            false,
+            Deadline::after(CLAIM_TIMEOUT),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| anyhow!("{:?}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_bridge_types::bridge::{BridgeChainId, MoveTypeTokenTransferPayload};
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeSource {
+        statuses: Mutex<BTreeMap<u64, BridgeActionStatus>>,
+        recipients: BTreeMap<u64, StarcoinAddress>,
+        failing_claims: BTreeSet<u64>,
+        claims: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl ClaimSource for FakeSource {
+        async fn status(&self, seq_num: u64) -> anyhow::Result<BridgeActionStatus> {
+            Ok(self
+                .statuses
+                .lock()
+                .unwrap()
+                .get(&seq_num)
+                .cloned()
+                .unwrap_or(BridgeActionStatus::NotFound))
+        }
+
+        async fn message(
+            &self,
+            seq_num: u64,
+        ) -> anyhow::Result<Option<ParsedTokenTransferMessage>> {
+            Ok(self
+                .recipients
+                .get(&seq_num)
+                .map(|recipient| ParsedTokenTransferMessage {
+                    message_version: 1,
+                    seq_num,
+                    source_chain: BridgeChainId::EthCustom,
+                    payload: vec![],
+                    parsed_payload: MoveTypeTokenTransferPayload {
+                        sender_address: vec![1; 20],
+                        target_chain: BridgeChainId::StarcoinCustom as u8,
+                        target_address: recipient.to_vec(),
+                        token_type: 1,
+                        amount: 100,
+                    },
+                }))
+        }
+
+        async fn claim(&self, seq_num: u64) -> anyhow::Result<()> {
+            self.claims.lock().unwrap().push(seq_num);
+            if self.failing_claims.contains(&seq_num) {
+                return Err(anyhow!("claim reverted"));
+            }
+            self.statuses
+                .lock()
+                .unwrap()
+                .insert(seq_num, BridgeActionStatus::Claimed);
+            Ok(())
+        }
+    }
+
+    fn state_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("auto-claim-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_state_file_round_trip() {
+        let path = state_file("round-trip");
+        let state = AutoClaimState::load_or_new(&path, 12, 3).unwrap();
+        assert_eq!(state.next_seq_num, 3);
+        AutoClaimState {
+            next_seq_num: 9,
+            ..state
+        }
+        .save(&path)
+        .unwrap();
+
+        // Resumes from the file, unless told to start further on
+        assert_eq!(
+            AutoClaimState::load_or_new(&path, 12, 0)
+                .unwrap()
+                .next_seq_num,
+            9
+        );
+        assert_eq!(
+            AutoClaimState::load_or_new(&path, 12, 20)
+                .unwrap()
+                .next_seq_num,
+            20
+        );
+        assert!(AutoClaimState::load_or_new(&path, 11, 0).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_poll_claims_our_approved_transfers() {
+        use BridgeActionStatus::*;
+        let ours = StarcoinAddress::new([1; 16]);
+        let other = StarcoinAddress::new([2; 16]);
+        let source = FakeSource {
+            statuses: Mutex::new(BTreeMap::from([
+                (0, Claimed),
+                (1, Approved),
+                (2, Approved),
+                (3, Approved),
+                (4, Pending),
+                (5, Approved),
+                // Approved out of order, past a gap
+                (8, Approved),
+            ])),
+            recipients: BTreeMap::from([(1, ours), (2, other), (3, ours), (5, ours), (8, ours)]),
+            failing_claims: BTreeSet::from([5]),
+            ..Default::default()
+        };
+        let path = state_file("poll");
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let mut claimer = AutoClaimer::new(
+            source,
+            AutoClaimState::load_or_new(&path, 12, 0).unwrap(),
+            path.clone(),
+            Some(ours),
+            false,
+            metrics.clone(),
+        );
+
+        claimer.poll().await.unwrap();
+        assert_eq!(*claimer.source.claims.lock().unwrap(), vec![1, 3, 5, 8]);
+        // Stops at the pending transfer, the failed claim is retried later
+        assert_eq!(claimer.state().next_seq_num, 4);
+        assert_eq!(
+            AutoClaimState::load_or_new(&path, 12, 0).unwrap(),
+            *claimer.state()
+        );
+        assert_eq!(metrics.auto_claim_attempted.get(), 4);
+        assert_eq!(metrics.auto_claim_succeeded.get(), 3);
+        assert_eq!(metrics.auto_claim_failed.get(), 1);
+
+        // Once the pending one is claimed by the nodes, only the failed claim
+        // is tried again
+        claimer.source.statuses.lock().unwrap().insert(4, Claimed);
+        claimer.poll().await.unwrap();
+        assert_eq!(*claimer.source.claims.lock().unwrap(), vec![1, 3, 5, 8, 5]);
+        assert_eq!(claimer.state().next_seq_num, 5);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_claims_nothing() {
+        let source = FakeSource {
+            statuses: Mutex::new(BTreeMap::from([(0, BridgeActionStatus::Approved)])),
+            recipients: BTreeMap::from([(0, StarcoinAddress::new([1; 16]))]),
+            ..Default::default()
+        };
+        let path = state_file("dry-run");
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let mut claimer = AutoClaimer::new(
+            source,
+            AutoClaimState::load_or_new(&path, 12, 0).unwrap(),
+            path.clone(),
+            None,
+            true,
+            metrics.clone(),
+        );
+        claimer.poll().await.unwrap();
+        assert!(claimer.source.claims.lock().unwrap().is_empty());
+        assert_eq!(metrics.auto_claim_attempted.get(), 0);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Certified governance actions kept on disk once their committee signatures
+// are collected, so that a `governance` command whose transaction failed can
+// be rerun without asking every validator to sign again. Entries are keyed by
+// action digest, checked against the current committee when they are read,
+// and removed once their action executed or they expired.
+
+use crate::signed_actions::SignedAction;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use starcoin_bridge::types::{BridgeAction, BridgeCommittee, VerifiedCertifiedBridgeAction};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+#[derive(Clone, Debug)]
+pub struct CertificateCache {
+    dir: PathBuf,
+    // How long after certification an entry is used
+    ttl: Duration,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CacheEntry {
+    pub(crate) certified_at_ms: u64,
+    pub(crate) certificate: SignedAction,
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl CertificateCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, action: &BridgeAction) -> anyhow::Result<PathBuf> {
+        let digest = action.encode()?.digest;
+        Ok(self.dir.join(format!("{}.json", hex::encode(digest))))
+    }
+
+    // The cached certificate of `action`, if it is younger than the ttl at
+    // `now_ms` and still verifies against `committee`. Entries that don't are
+    // deleted, and so are unreadable ones.
+    pub fn get(
+        &self,
+        action: &BridgeAction,
+        committee: &BridgeCommittee,
+        now_ms: u64,
+    ) -> anyhow::Result<Option<VerifiedCertifiedBridgeAction>> {
+        let path = self.path(action)?;
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read cached certificate {:?}", path))
+            }
+        };
+        let entry = match serde_json::from_str::<CacheEntry>(&content) {
+            Ok(entry) if entry.certificate.action == *action => entry,
+            Ok(_) => {
+                warn!("Cached certificate {:?} is for another action", path);
+                return self.discard(&path);
+            }
+            Err(e) => {
+                warn!("Invalid cached certificate {:?}: {}", path, e);
+                return self.discard(&path);
+            }
+        };
+        let age = Duration::from_millis(now_ms.saturating_sub(entry.certified_at_ms));
+        if age > self.ttl {
+            info!(
+                "Cached certificate {:?} expired {:?} ago",
+                path,
+                age - self.ttl
+            );
+            return self.discard(&path);
+        }
+        // The committee may have rotated since the signatures were collected
+        match entry.certificate.verify(committee) {
+            Ok(certified_action) => {
+                info!(
+                    "Using the certificate of {:?} collected {:?} ago",
+                    action, age
+                );
+                Ok(Some(certified_action))
+            }
+            Err(e) => {
+                warn!(
+                    "Cached certificate {:?} no longer verifies against the committee: {}",
+                    path, e
+                );
+                self.discard(&path)
+            }
+        }
+    }
+
+    fn discard(&self, path: &Path) -> anyhow::Result<Option<VerifiedCertifiedBridgeAction>> {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to delete cached certificate {:?}", path))?;
+        Ok(None)
+    }
+
+    // Writes through a temporary file, so an interrupted write never leaves
+    // a torn entry
+    pub fn put(
+        &self,
+        certified_action: &VerifiedCertifiedBridgeAction,
+        now_ms: u64,
+    ) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create certificate cache {:?}", self.dir))?;
+        let path = self.path(certified_action.data())?;
+        let entry = CacheEntry {
+            certified_at_ms: now_ms,
+            certificate: SignedAction::from(certified_action),
+        };
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&entry)?)
+            .with_context(|| format!("Failed to write cached certificate {:?}", tmp))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to write cached certificate {:?}", path))?;
+        Ok(())
+    }
+
+    // Deletes the entry of an action that executed
+    pub fn remove(&self, action: &BridgeAction) -> anyhow::Result<()> {
+        let path = self.path(action)?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to delete cached certificate {:?}", path))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::KeyPair;
+    use starcoin_bridge::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+    use starcoin_bridge::types::{
+        BridgeAuthority, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction, EmergencyAction,
+        EmergencyActionType,
+    };
+    use starcoin_bridge_types::base_types::StarcoinAddress;
+    use starcoin_bridge_types::bridge::{BridgeChainId, BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER};
+    use std::collections::BTreeMap;
+
+    const TTL: Duration = Duration::from_secs(3600);
+    const NOW_MS: u64 = 1_700_000_000_000;
+
+    fn test_committee() -> (BridgeCommittee, BridgeAuthorityKeyPair) {
+        let (_, key): (_, Secp256k1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+        let committee = BridgeCommittee::new(vec![BridgeAuthority {
+            starcoin_bridge_address: StarcoinAddress::ZERO,
+            pubkey: key.public().clone(),
+            voting_power: BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER,
+            base_url: "http://127.0.0.1:9191".to_string(),
+            is_blocklisted: false,
+        }])
+        .unwrap();
+        (committee, key)
+    }
+
+    fn action(nonce: u64) -> BridgeAction {
+        BridgeAction::EmergencyAction(EmergencyAction {
+            nonce,
+            chain_id: BridgeChainId::EthCustom,
+            action_type: EmergencyActionType::Pause,
+        })
+    }
+
+    fn certify(
+        action: BridgeAction,
+        key: &BridgeAuthorityKeyPair,
+    ) -> VerifiedCertifiedBridgeAction {
+        let sig = BridgeAuthoritySignInfo::new(&action, key);
+        VerifiedCertifiedBridgeAction::new_from_verified(
+            CertifiedBridgeAction::new_from_data_and_sig(
+                action,
+                BridgeCommitteeValiditySignInfo {
+                    signatures: BTreeMap::from([(sig.authority_pub_key_bytes(), sig.signature)]),
+                },
+            ),
+        )
+    }
+
+    fn entries(cache: &CertificateCache) -> usize {
+        std::fs::read_dir(cache.dir()).unwrap().count()
+    }
+
+    #[test]
+    fn test_cache_hit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CertificateCache::new(dir.path().join("certificates"), TTL);
+        let (committee, key) = test_committee();
+        let certified_action = certify(action(3), &key);
+
+        // Nothing cached yet, the directory is only created by `put`
+        assert!(cache.get(&action(3), &committee, NOW_MS).unwrap().is_none());
+        cache.put(&certified_action, NOW_MS).unwrap();
+
+        let cached = cache
+            .get(&action(3), &committee, NOW_MS + 60_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.data(), certified_action.data());
+        assert_eq!(
+            cached.auth_sig().signatures,
+            certified_action.auth_sig().signatures
+        );
+        // Another nonce is another action
+        assert!(cache.get(&action(4), &committee, NOW_MS).unwrap().is_none());
+
+        // Executed actions are removed
+        cache.remove(&action(3)).unwrap();
+        assert_eq!(entries(&cache), 0);
+        cache.remove(&action(3)).unwrap();
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CertificateCache::new(dir.path().to_path_buf(), TTL);
+        let (committee, key) = test_committee();
+        cache.put(&certify(action(3), &key), NOW_MS).unwrap();
+
+        let expiry_ms = NOW_MS + TTL.as_millis() as u64;
+        assert!(cache
+            .get(&action(3), &committee, expiry_ms)
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .get(&action(3), &committee, expiry_ms + 1)
+            .unwrap()
+            .is_none());
+        // Expired entries are deleted
+        assert_eq!(entries(&cache), 0);
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_committee_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CertificateCache::new(dir.path().to_path_buf(), TTL);
+        let (_, key) = test_committee();
+        cache.put(&certify(action(3), &key), NOW_MS).unwrap();
+
+        // The signer is no longer a member
+        let (rotated, _) = test_committee();
+        assert!(cache.get(&action(3), &rotated, NOW_MS).unwrap().is_none());
+        assert_eq!(entries(&cache), 0);
+    }
+}
@@ -0,0 +1,1575 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The deposit, claim and governance flows of `bridge-cli` as a library, so
+//! another service can drive the bridge without shelling out to the CLI.
+//! Each `BridgeOps` method returns the result the matching command prints.
+//!
+//! ```no_run
+//! use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+//! use starcoin_bridge_cli::client::BridgeOps;
+//! use starcoin_bridge_cli::output::ClaimStatus;
+//! use starcoin_bridge_cli::{BridgeCliConfig, LoadedBridgeCliConfig};
+//! use starcoin_bridge_config::Config;
+//!
+//! # async fn claim() -> anyhow::Result<()> {
+//! let config = BridgeCliConfig::load("bridge-cli.yaml")?;
+//! let config = LoadedBridgeCliConfig::load(config).await?;
+//! let starcoin_bridge_client = StarcoinBridgeClient::new(
+//!     &config.starcoin_bridge_rpc_url,
+//!     &config.starcoin_bridge_proxy_address,
+//! );
+//! let ops = BridgeOps::new(config, starcoin_bridge_client);
+//! // Estimate first, then claim for real
+//! let claim = ops.claim_on_eth(42, true).await?;
+//! if claim.status == ClaimStatus::DryRun {
+//!     let claim = ops.claim_on_eth(42, false).await?;
+//!     println!("{claim}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::certificate_cache::{now_ms, CertificateCache};
+use crate::committee_snapshot::{CommitteeSnapshot, CommitteeSource};
+use crate::committee_sync::{
+    check_online_quorum, diff_committees, eth_committee_members, online_signing_stake,
+    starcoin_members,
+};
+use crate::output::{
+    ClaimOutput, CommitteeSyncOutput, DepositOutput, EtherDepositDryRunOutput, EtherDepositOutput,
+    GovernanceOutput, RegisteredTokensOutput, TxOutput, UsdChange,
+};
+use crate::pause_check::{ensure_not_paused, BridgeSide, RpcPauseSource};
+use crate::signed_actions::SignedActionsFile;
+use crate::transfer_budget::check_deposit_budget;
+use crate::{
+    certified_action_output, certify_actions, check_new_tokens, check_starcoin_deposit_recipient,
+    check_usd_change, claim_on_eth, claim_on_starcoin, deposit_erc20_on_eth, deposit_on_starcoin,
+    eth_bridge_units, eth_current_usd_values, eth_deposit_nonce, eth_next_nonces,
+    eth_registered_token_ids, execute_actions_on_eth, execute_actions_on_starcoin,
+    governance_action_output, make_actions, new_usd_value, ping_urls_with_timeout,
+    record_claim_latency, registered_tokens, resolve_nonces, simulate_actions_on_eth,
+    starcoin_current_usd_value, starcoin_next_nonces, GovernanceClientCommands,
+    LoadedBridgeCliConfig,
+};
+use anyhow::{anyhow, Context};
+use ethers::signers::Signer;
+use ethers::types::{Address as EthAddress, TxHash, U256};
+use starcoin_bridge::abi::EthStarcoinBridge;
+use starcoin_bridge::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::error::BridgeError;
+use starcoin_bridge::lifecycle::BridgeActionLifecycle;
+use starcoin_bridge::metrics::BridgeMetrics;
+use starcoin_bridge::recipient::{check_eth_recipient, check_eth_recipient_has_no_code};
+use starcoin_bridge::starcoin_bridge_client::{
+    bridge_committee_from_summary, StarcoinBridgeClient,
+};
+use starcoin_bridge::startup::{finish_steps, timed_step};
+use starcoin_bridge::tx_digest::TxDigest;
+use starcoin_bridge::types::{
+    BridgeAction, BridgeActionType, BridgeCommittee, VerifiedCertifiedBridgeAction,
+};
+use starcoin_bridge_types::base_types::StarcoinAddress;
+use starcoin_bridge_types::bridge::{
+    BridgeChainId, BridgeSummary, APPROVAL_THRESHOLD_TOKEN_TRANSFER, TOKEN_ID_ETH,
+};
+use starcoin_bridge_types::TypeTag;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const ETHER_DECIMALS: u8 = 18;
+// Wei per ether
+const ETHER_DECIMAL_MULTIPLIER: u128 = 10u128.pow(ETHER_DECIMALS as u32);
+
+pub struct BridgeOps {
+    config: LoadedBridgeCliConfig,
+    starcoin_bridge_client: StarcoinBridgeClient,
+    // Bounds every flow, from its first RPC to the last transaction it waits for
+    deadline: Deadline,
+    metrics: Arc<BridgeMetrics>,
+}
+
+// How `execute_governance_action` collects signatures and submits the actions
+#[derive(Clone, Debug)]
+pub struct GovernanceOptions {
+    // Only collect the signatures
+    pub dry_run: bool,
+    // Write the certified actions to this file, see `execute_signed_actions`
+    pub export_signatures: Option<PathBuf>,
+    // Use the given nonces even if they don't match the next nonces on chain
+    pub force: bool,
+    // How long to wait for the committee signatures of each action
+    pub sig_timeout: Duration,
+    // Log which authorities signed even when quorum is reached
+    pub min_quorum_report: bool,
+    // Don't check the tokens of `add-tokens-on-*` actions against the target
+    // chain, see `check_new_tokens`
+    pub skip_onchain_validation: bool,
+    // Let limit and price updates move the value on chain by more than
+    // `max_change_factor`, see `check_usd_change`
+    pub allow_large_change: bool,
+    pub max_change_factor: f64,
+    // Keeps certified actions until they execute, so a rerun after a failed
+    // submission reuses their signatures, see `certificate_cache`
+    pub certificate_cache: Option<CertificateCache>,
+    // Collect new signatures even for actions in the certificate cache
+    pub fresh_signatures: bool,
+}
+
+impl Default for GovernanceOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            export_signatures: None,
+            force: false,
+            sig_timeout: Duration::from_secs(5),
+            min_quorum_report: false,
+            skip_onchain_validation: false,
+            allow_large_change: false,
+            max_change_factor: 10.0,
+            certificate_cache: None,
+            fresh_signatures: false,
+        }
+    }
+}
+
+impl BridgeOps {
+    pub fn new(
+        config: LoadedBridgeCliConfig,
+        starcoin_bridge_client: StarcoinBridgeClient,
+    ) -> Self {
+        Self {
+            config,
+            starcoin_bridge_client,
+            deadline: Deadline::none(),
+            metrics: Arc::new(BridgeMetrics::new_for_testing()),
+        }
+    }
+
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    // Claim latencies and transaction submissions are recorded here. Defaults
+    // to metrics on a registry of their own.
+    pub fn with_metrics(mut self, metrics: Arc<BridgeMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn config(&self) -> &LoadedBridgeCliConfig {
+        &self.config
+    }
+
+    pub fn starcoin_bridge_client(&self) -> &StarcoinBridgeClient {
+        &self.starcoin_bridge_client
+    }
+
+    pub fn metrics(&self) -> &Arc<BridgeMetrics> {
+        &self.metrics
+    }
+
+    // Fails if the bridge is paused on any of `sides`, see `pause_check`
+    pub async fn ensure_not_paused(&self, sides: &[BridgeSide]) -> anyhow::Result<()> {
+        let source = RpcPauseSource {
+            config: &self.config,
+            starcoin_bridge_client: &self.starcoin_bridge_client,
+        };
+        self.deadline
+            .run("pause check", async {
+                Ok::<_, BridgeError>(ensure_not_paused(&source, sides).await)
+            })
+            .await
+            .map_err(|e| anyhow!("{:?}", e))?
+    }
+
+    // Deposits `amount` wei of native ether from the EVM chain `eth_chain`,
+    // the default one if None. With `dry_run` only the gas is estimated.
+    // Unless `force`, fails when the deposit is over the transfer limit left
+    // on the target chain, see `transfer_budget`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deposit_native_ether(
+        &self,
+        amount: U256,
+        target_chain: u8,
+        recipient: StarcoinAddress,
+        allow_unknown_account: bool,
+        eth_chain: Option<BridgeChainId>,
+        dry_run: bool,
+        force: bool,
+    ) -> anyhow::Result<EtherDepositOutput> {
+        let evm_chain = self.config.evm_chain(eth_chain)?;
+        check_starcoin_deposit_recipient(&recipient, &self.config, allow_unknown_account).await?;
+        self.starcoin_bridge_client
+            .ensure_token_not_paused(TOKEN_ID_ETH)
+            .await
+            .map_err(|e| anyhow!("{:?}", e))?;
+        eth_bridge_units(evm_chain, TOKEN_ID_ETH, amount, ETHER_DECIMALS).await?;
+        let target_chain_id = BridgeChainId::try_from(target_chain)
+            .map_err(|e| anyhow!("Invalid target chain {target_chain}: {:?}", e))?;
+        // A dry run only reports what the deposit would run into
+        check_deposit_budget(
+            &self.config,
+            &self.starcoin_bridge_client,
+            (evm_chain.chain_id, target_chain_id),
+            TOKEN_ID_ETH,
+            u128::try_from(amount).map_err(|_| anyhow!("Deposit amount {amount} is too large"))?,
+            ETHER_DECIMAL_MULTIPLIER,
+            force || dry_run,
+        )
+        .await?;
+        let eth_signer = Arc::new(self.config.eth_signer(Some(evm_chain.chain_id)).await?);
+        let sender = eth_signer.address();
+        let eth_starcoin_bridge =
+            EthStarcoinBridge::new(evm_chain.eth_bridge_proxy_address, eth_signer);
+        // Starcoin address is 16 bytes, Solidity contract expects exactly 16 bytes
+        let eth_tx = eth_starcoin_bridge
+            .bridge_eth(recipient.to_vec().into(), target_chain)
+            .value(amount)
+            .from(sender);
+        if dry_run {
+            let estimate = eth_tx.estimate_gas().await;
+            return Ok(EtherDepositOutput::DryRun(EtherDepositDryRunOutput {
+                amount_wei: amount.to_string(),
+                target_chain,
+                recipient: recipient.to_hex_literal(),
+                estimated_gas: estimate.as_ref().ok().map(|gas| gas.as_u64()),
+                error: estimate.err().map(|e| format!("{:?}", e)),
+            }));
+        }
+        let mut lifecycle = BridgeActionLifecycle::without_nonce(
+            BridgeActionType::TokenTransfer,
+            evm_chain.chain_id as u8,
+        );
+        lifecycle.tx_built(evm_chain.chain_id, eth_tx.tx.gas().map(|gas| gas.as_u64()));
+        let pending_tx = eth_tx
+            .send()
+            .await
+            .context("Failed to submit the deposit transaction")?;
+        let tx_hash = TxDigest::eth(pending_tx.tx_hash());
+        lifecycle.tx_submitted(&tx_hash.to_string());
+        let tx_receipt = self
+            .deadline
+            .run("deposit receipt", pending_tx)
+            .await
+            .map_err(|e| anyhow!("Failed to confirm deposit transaction {tx_hash}: {:?}", e))?
+            .ok_or_else(|| anyhow!("Deposit transaction {tx_hash} was dropped"))?;
+        let deposit_tx = TxOutput::from_eth_receipt(&tx_receipt);
+        let nonce = eth_deposit_nonce(&tx_receipt);
+        if let Some(nonce) = nonce {
+            lifecycle.record_nonce(nonce);
+        }
+        lifecycle.tx_confirmed(deposit_tx.status.as_deref().unwrap_or("unknown"));
+        if tx_receipt.status != Some(1u64.into()) {
+            return Err(anyhow!("Deposit transaction {tx_hash} reverted"));
+        }
+        Ok(EtherDepositOutput::Deposited(DepositOutput {
+            approve_tx: None,
+            deposit_tx,
+            nonce,
+            claim_command: None,
+        }))
+    }
+
+    // Deposits `amount` of the ERC20 token at `token_address`, a decimal in
+    // token units. With `approve`, the bridge is approved to spend the amount
+    // first when the allowance doesn't cover it. `force` as for
+    // `deposit_native_ether`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deposit_erc20_on_eth(
+        &self,
+        token_address: EthAddress,
+        amount: &str,
+        token_decimals: Option<u8>,
+        target_chain: u8,
+        recipient: StarcoinAddress,
+        approve: bool,
+        allow_unknown_account: bool,
+        eth_chain: Option<BridgeChainId>,
+        force: bool,
+    ) -> anyhow::Result<DepositOutput> {
+        let evm_chain = self.config.evm_chain(eth_chain)?;
+        check_starcoin_deposit_recipient(&recipient, &self.config, allow_unknown_account).await?;
+        deposit_erc20_on_eth(
+            token_address,
+            amount,
+            token_decimals,
+            target_chain,
+            recipient,
+            approve,
+            evm_chain,
+            &self.config,
+            &self.starcoin_bridge_client,
+            force,
+        )
+        .await
+    }
+
+    // Deposits `amount` base units of `coin_type` from the configured
+    // Starcoin account to `recipient` on the EVM chain `target_chain`.
+    // `force` as for `deposit_native_ether`.
+    pub async fn deposit_on_starcoin(
+        &self,
+        coin_type: TypeTag,
+        target_chain: BridgeChainId,
+        recipient: EthAddress,
+        amount: u128,
+        allow_contract_recipient: bool,
+        force: bool,
+    ) -> anyhow::Result<DepositOutput> {
+        let evm_chain = self.config.evm_chain(Some(target_chain))?;
+        check_eth_recipient(&recipient, &evm_chain.eth_bridge_contracts())
+            .map_err(|e| anyhow!("{:?}", e))?;
+        check_eth_recipient_has_no_code(
+            evm_chain.provider().as_ref(),
+            &recipient,
+            allow_contract_recipient,
+        )
+        .await
+        .map_err(|e| anyhow!("{:?}", e))?;
+        deposit_on_starcoin(
+            coin_type,
+            target_chain,
+            recipient,
+            amount,
+            &self.config,
+            &self.starcoin_bridge_client,
+            force,
+        )
+        .await
+    }
+
+    // Tokens `deposit_on_starcoin` accepts
+    pub async fn registered_starcoin_tokens(&self) -> anyhow::Result<RegisteredTokensOutput> {
+        let token_id_map = self
+            .starcoin_bridge_client
+            .get_token_id_map()
+            .await
+            .map_err(|e| anyhow!("{:?}", e))?;
+        let summary = self
+            .starcoin_bridge_client
+            .get_bridge_summary()
+            .await
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(RegisteredTokensOutput {
+            tokens: registered_tokens(&token_id_map, &summary.treasury),
+        })
+    }
+
+    // Claims the Starcoin -> Eth transfer `seq_num` on its target chain. With
+    // `dry_run` only the gas is estimated.
+    pub async fn claim_on_eth(&self, seq_num: u64, dry_run: bool) -> anyhow::Result<ClaimOutput> {
+        let started = std::time::Instant::now();
+        let claim = claim_on_eth(
+            seq_num,
+            &self.config,
+            &self.starcoin_bridge_client,
+            dry_run,
+            self.deadline,
+        )
+        .await;
+        record_claim_latency(&self.metrics, "eth", &claim, started);
+        claim.map_err(|e| anyhow!("{:?}", e))
+    }
+
+    // Approves, if needed, and claims the transfer `seq_num` from
+    // `source_chain_id` on Starcoin. Approving needs the Eth deposit
+    // transaction and the index of its deposit event.
+    pub async fn claim_on_starcoin(
+        &self,
+        source_chain_id: u8,
+        seq_num: u64,
+        eth_deposit: Option<(TxHash, u16)>,
+        dry_run: bool,
+    ) -> anyhow::Result<ClaimOutput> {
+        let started = std::time::Instant::now();
+        let claim = claim_on_starcoin(
+            source_chain_id,
+            seq_num,
+            eth_deposit,
+            &self.config,
+            &self.starcoin_bridge_client,
+            dry_run,
+            self.deadline,
+        )
+        .await;
+        record_claim_latency(&self.metrics, "starcoin", &claim, started);
+        claim.map_err(|e| anyhow!("{:?}", e))
+    }
+
+    // Collects committee signatures for `cmds` and executes them on
+    // `chain_id` in order. Nothing is submitted unless every action is
+    // certified.
+    pub async fn execute_governance_action(
+        &self,
+        chain_id: BridgeChainId,
+        cmds: Vec<GovernanceClientCommands>,
+        options: &GovernanceOptions,
+    ) -> anyhow::Result<GovernanceOutput> {
+        // The nonces of an EVM chain don't depend on the Starcoin summary, so
+        // both are read together
+        let (bridge_summary, eth_nonces) = tokio::join!(
+            timed_step(
+                "starcoin bridge summary",
+                self.deadline.run(
+                    "starcoin bridge summary",
+                    self.starcoin_bridge_client.latest_bridge_summary(),
+                ),
+            ),
+            timed_step("eth nonces", async {
+                if chain_id.is_starcoin_bridge_chain() {
+                    return Ok(None);
+                }
+                self.read_eth_nonces(chain_id, &cmds).await.map(Some)
+            }),
+        );
+        finish_steps("Governance setup", &[&bridge_summary, &eth_nonces])?;
+        self.execute_governance_action_with_summary(
+            chain_id,
+            bridge_summary.into_result()?,
+            eth_nonces.into_result()?,
+            cmds,
+            options,
+        )
+        .await
+    }
+
+    // The next nonce of each action of `cmds` on the EVM chain `chain_id`.
+    // The chain id of each configured EVM chain is read from its BridgeConfig
+    // contract, so this also rejects chains that are not configured.
+    async fn read_eth_nonces(
+        &self,
+        chain_id: BridgeChainId,
+        cmds: &[GovernanceClientCommands],
+    ) -> anyhow::Result<Vec<((EthAddress, u8), u64)>> {
+        let evm_chain = self.config.evm_chain(Some(chain_id))?;
+        self.deadline
+            .run("eth nonces", eth_next_nonces(evm_chain, cmds))
+            .await
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+
+    // `execute_governance_action` with a bridge summary the caller already
+    // read, and for an EVM chain the nonces if they were read along with it.
+    // Every action is signed by its committee, see `committee_snapshot`.
+    async fn execute_governance_action_with_summary(
+        &self,
+        chain_id: BridgeChainId,
+        bridge_summary: BridgeSummary,
+        eth_nonces: Option<Vec<((EthAddress, u8), u64)>>,
+        mut cmds: Vec<GovernanceClientCommands>,
+        options: &GovernanceOptions,
+    ) -> anyhow::Result<GovernanceOutput> {
+        let deadline = self.deadline;
+        let snapshot = CommitteeSnapshot::new(&bridge_summary.committee);
+        info!(
+            "Signing with the committee of epoch {} ({})",
+            snapshot.last_committee_update_epoch, snapshot.committee_hash
+        );
+        let bridge_committee = Arc::new(
+            bridge_committee_from_summary(bridge_summary.committee.clone())
+                .map_err(|e| anyhow!("{:?}", e))?,
+        );
+        let agg = BridgeAuthorityAggregator::new(
+            bridge_committee.clone(),
+            self.metrics.clone(),
+            Arc::new(BTreeMap::new()),
+        )
+        .with_timeout(options.sig_timeout);
+
+        let next_nonces = if chain_id.is_starcoin_bridge_chain() {
+            check_starcoin_chain_id(chain_id, bridge_summary.chain_id)?;
+            starcoin_next_nonces(&cmds, &bridge_summary.sequence_nums)
+        } else {
+            match eth_nonces {
+                Some(eth_nonces) => eth_nonces,
+                None => self.read_eth_nonces(chain_id, &cmds).await?,
+            }
+        };
+        resolve_nonces(&mut cmds, next_nonces, options.force)?;
+        let actions = make_actions(chain_id, &cmds)?;
+        for action in &actions {
+            info!("Action to execute on {:?}: {:?}", chain_id, action);
+        }
+        if !options.skip_onchain_validation {
+            for action in &actions {
+                self.check_new_tokens_on_chain(chain_id, action).await?;
+            }
+        }
+        let usd_changes = self
+            .usd_changes(chain_id, &bridge_summary, &actions)
+            .await?;
+        for (action, usd_change) in actions.iter().zip(&usd_changes) {
+            let Some(usd_change) = usd_change else {
+                continue;
+            };
+            info!(
+                "USD value set by {:?} on {:?}: {usd_change}",
+                action.action_type(),
+                chain_id
+            );
+            if !options.allow_large_change {
+                check_usd_change(action, usd_change, options.max_change_factor)?;
+            }
+        }
+        // TODO if a validator is blocklisted on eth, ignore their signatures?
+        let certified_actions = self
+            .certify_or_resume(&agg, &bridge_committee, actions, options)
+            .await?;
+        let mut output = GovernanceOutput {
+            chain_id: chain_id as u8,
+            dry_run: options.dry_run,
+            committee: Some(snapshot.clone()),
+            actions: cmds
+                .iter()
+                .zip(&certified_actions)
+                .map(|(cmd, certified_action)| governance_action_output(cmd, certified_action))
+                .collect::<anyhow::Result<_>>()?,
+        };
+        for (action_output, usd_change) in output.actions.iter_mut().zip(usd_changes) {
+            action_output.usd_change = usd_change;
+        }
+        if let Some(path) = &options.export_signatures {
+            SignedActionsFile::new(chain_id, &certified_actions).write(path)?;
+            info!("Signed actions written to {}", path.display());
+        }
+        if options.dry_run {
+            // Eth actions are simulated, Starcoin ones have no such check
+            if !chain_id.is_starcoin_bridge_chain() {
+                simulate_actions_on_eth(
+                    &self.config,
+                    chain_id,
+                    &certified_actions,
+                    deadline,
+                    &mut output,
+                )
+                .await?;
+            }
+            return Ok(output);
+        }
+        let actions = certified_actions
+            .iter()
+            .map(|certified_action| certified_action.data().clone())
+            .collect::<Vec<_>>();
+        let executed = self
+            .execute_certified_actions(chain_id, certified_actions, &snapshot, &mut output)
+            .await;
+        // Certificates of the actions that executed are never needed again,
+        // those of the others are kept for a rerun
+        if let Some(cache) = &options.certificate_cache {
+            for (action, action_output) in actions.iter().zip(&output.actions) {
+                let status = action_output
+                    .tx
+                    .as_ref()
+                    .and_then(|tx| tx.status.as_deref());
+                if status == Some("success") {
+                    if let Err(e) = cache.remove(action) {
+                        warn!("{:?}", e);
+                    }
+                }
+            }
+        }
+        executed?;
+        Ok(output)
+    }
+
+    // Certifies `actions` in order. With a certificate cache, actions
+    // certified by an earlier run are taken from it when they still verify
+    // against `committee`, and only the others are sent to the committee.
+    async fn certify_or_resume(
+        &self,
+        agg: &BridgeAuthorityAggregator,
+        committee: &BridgeCommittee,
+        actions: Vec<BridgeAction>,
+        options: &GovernanceOptions,
+    ) -> anyhow::Result<Vec<VerifiedCertifiedBridgeAction>> {
+        let Some(cache) = &options.certificate_cache else {
+            return certify_actions(agg, actions, self.deadline, options.min_quorum_report).await;
+        };
+        let now = now_ms();
+        let mut cached = Vec::with_capacity(actions.len());
+        for action in &actions {
+            cached.push(if options.fresh_signatures {
+                None
+            } else {
+                cache.get(action, committee, now)?
+            });
+        }
+        let to_sign = actions
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(action, _)| action.clone())
+            .collect();
+        let mut signed = certify_actions(agg, to_sign, self.deadline, options.min_quorum_report)
+            .await?
+            .into_iter();
+        let now = now_ms();
+        let mut certified_actions = Vec::with_capacity(actions.len());
+        for cached in cached {
+            let certified_action = match cached {
+                Some(certified_action) => certified_action,
+                None => {
+                    let certified_action = signed
+                        .next()
+                        .expect("One certified action per action to sign");
+                    if let Err(e) = cache.put(&certified_action, now) {
+                        warn!("{:?}", e);
+                    }
+                    certified_action
+                }
+            };
+            certified_actions.push(certified_action);
+        }
+        if !certified_actions.is_empty() {
+            info!(
+                "Certified actions are kept in {} until they execute",
+                cache.dir().display()
+            );
+        }
+        Ok(certified_actions)
+    }
+
+    // The value each limit or price update replaces on `chain_id`, with the
+    // value it sets. None for other actions.
+    async fn usd_changes(
+        &self,
+        chain_id: BridgeChainId,
+        bridge_summary: &BridgeSummary,
+        actions: &[BridgeAction],
+    ) -> anyhow::Result<Vec<Option<UsdChange>>> {
+        let current = if chain_id.is_starcoin_bridge_chain() {
+            actions
+                .iter()
+                .map(|action| {
+                    starcoin_current_usd_value(
+                        &bridge_summary.limiter,
+                        &bridge_summary.treasury,
+                        action,
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        } else if actions.iter().any(|action| new_usd_value(action).is_some()) {
+            let evm_chain = self.config.evm_chain(Some(chain_id))?;
+            self.deadline
+                .run("eth usd values", eth_current_usd_values(evm_chain, actions))
+                .await
+                .map_err(|e| anyhow!("{:?}", e))?
+        } else {
+            vec![None; actions.len()]
+        };
+        Ok(actions
+            .iter()
+            .zip(current)
+            .map(|(action, current)| Some(UsdChange::new(current?, new_usd_value(action)?)))
+            .collect())
+    }
+
+    // Plans the blocklist actions that bring the committee of the EVM chain
+    // `chain_id` in line with Starcoin, see `committee_sync`. With `execute`
+    // they are executed like `execute_governance_action`, with auto nonces,
+    // unless they would leave Eth without an online quorum. Members that
+    // don't answer a ping within `ping_timeout` count as offline.
+    pub async fn sync_committee_state(
+        &self,
+        chain_id: BridgeChainId,
+        execute: bool,
+        ping_timeout: Duration,
+        options: &GovernanceOptions,
+    ) -> anyhow::Result<CommitteeSyncOutput> {
+        let deadline = self.deadline;
+        let evm_chain = self.config.evm_chain(Some(chain_id))?;
+        let bridge_summary = deadline
+            .run(
+                "starcoin bridge summary",
+                self.starcoin_bridge_client.latest_bridge_summary(),
+            )
+            .await
+            .map_err(|e| anyhow!("{:?}", e))?;
+        let members = starcoin_members(&bridge_summary.committee)?;
+        let addresses = members
+            .iter()
+            .map(|member| member.eth_address)
+            .collect::<Vec<_>>();
+        let urls = members
+            .iter()
+            .map(|member| member.url.clone())
+            .collect::<Vec<_>>();
+        let (eth_members, pings) = tokio::join!(
+            deadline.run(
+                "eth committee",
+                eth_committee_members(evm_chain, &addresses)
+            ),
+            ping_urls_with_timeout(&urls, ping_timeout),
+        );
+        let eth_members = eth_members.map_err(|e| anyhow!("{:?}", e))?;
+        let online = addresses
+            .iter()
+            .zip(&pings)
+            .filter(|(_, ping)| ping.online)
+            .map(|(address, _)| *address)
+            .collect::<HashSet<_>>();
+
+        let diff = diff_committees(&members, &eth_members);
+        let (online_stake_before, online_stake_after) =
+            online_signing_stake(&members, &eth_members, &online);
+        let eth_address = |address: &EthAddress| format!("{:?}", address);
+        let mut output = CommitteeSyncOutput {
+            chain_id: chain_id as u8,
+            to_blocklist: diff
+                .to_blocklist
+                .iter()
+                .map(|pubkey| eth_address(&pubkey.to_eth_address()))
+                .collect(),
+            to_unblocklist: diff
+                .to_unblocklist
+                .iter()
+                .map(|pubkey| eth_address(&pubkey.to_eth_address()))
+                .collect(),
+            missing_on_eth: diff.missing_on_eth.iter().map(eth_address).collect(),
+            online_stake_before,
+            online_stake_after,
+            quorum: APPROVAL_THRESHOLD_TOKEN_TRANSFER,
+            governance: None,
+        };
+        if !execute || diff.is_empty() {
+            return Ok(output);
+        }
+        check_online_quorum(online_stake_before, online_stake_after)?;
+        // The actions are signed by the committee the diff was made against
+        output.governance = Some(
+            self.execute_governance_action_with_summary(
+                chain_id,
+                bridge_summary,
+                None,
+                diff.commands(),
+                options,
+            )
+            .await?,
+        );
+        Ok(output)
+    }
+
+    // Reads what `check_new_tokens` needs from the target chain. Other
+    // actions pass without any RPC.
+    async fn check_new_tokens_on_chain(
+        &self,
+        chain_id: BridgeChainId,
+        action: &BridgeAction,
+    ) -> anyhow::Result<()> {
+        let deadline = self.deadline;
+        match action {
+            BridgeAction::AddTokensOnStarcoinAction(add_tokens) => {
+                let registered = deadline
+                    .run(
+                        "starcoin token ids",
+                        self.starcoin_bridge_client.get_token_id_map(),
+                    )
+                    .await
+                    .map_err(|e| anyhow!("{:?}", e))?;
+                let mut published = HashSet::new();
+                for type_name in &add_tokens.token_type_names {
+                    let module = deadline
+                        .run(
+                            "starcoin token module",
+                            self.starcoin_bridge_client
+                                .get_token_module_bytes(type_name),
+                        )
+                        .await
+                        .map_err(|e| anyhow!("{:?}", e))?;
+                    if module.is_some() {
+                        published.insert(type_name.clone());
+                    }
+                }
+                check_new_tokens(
+                    action,
+                    |id| registered.contains_key(&id),
+                    |type_name| published.contains(type_name),
+                )
+            }
+            BridgeAction::AddTokensOnEvmAction(add_tokens) => {
+                let evm_chain = self.config.evm_chain(Some(chain_id))?;
+                let registered = deadline
+                    .run(
+                        "eth token ids",
+                        eth_registered_token_ids(evm_chain, &add_tokens.token_ids),
+                    )
+                    .await
+                    .map_err(|e| anyhow!("{:?}", e))?;
+                check_new_tokens(action, |id| registered.contains(&id), |_| true)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Executes actions certified by `execute_governance_action` with
+    // `export_signatures`, after checking their signatures against the
+    // current committee
+    pub async fn execute_signed_actions(
+        &self,
+        chain_id: BridgeChainId,
+        signed_actions: &SignedActionsFile,
+    ) -> anyhow::Result<GovernanceOutput> {
+        let bridge_summary = timed_step(
+            "starcoin bridge summary",
+            self.deadline.run(
+                "starcoin bridge summary",
+                self.starcoin_bridge_client.latest_bridge_summary(),
+            ),
+        )
+        .await;
+        finish_steps("Signed action setup", &[&bridge_summary])?;
+        let bridge_summary = bridge_summary.into_result()?;
+        let snapshot = CommitteeSnapshot::new(&bridge_summary.committee);
+        let bridge_committee = bridge_committee_from_summary(bridge_summary.committee.clone())
+            .map_err(|e| anyhow!("{:?}", e))?;
+        // The committee may have changed since the signatures were collected,
+        // so they are checked against the current one
+        let certified_actions = signed_actions.verify(chain_id, &bridge_committee)?;
+        for action in &certified_actions {
+            info!("Signed action to execute: {:?}", action.data());
+        }
+        if chain_id.is_starcoin_bridge_chain() {
+            check_starcoin_chain_id(chain_id, bridge_summary.chain_id)?;
+        }
+        let mut output = GovernanceOutput {
+            chain_id: chain_id as u8,
+            dry_run: false,
+            committee: Some(snapshot.clone()),
+            actions: certified_actions
+                .iter()
+                .map(certified_action_output)
+                .collect(),
+        };
+        self.execute_certified_actions(chain_id, certified_actions, &snapshot, &mut output)
+            .await?;
+        Ok(output)
+    }
+
+    // Executes `certified_actions`, signed by the committee of `snapshot`,
+    // until one fails or the committee changes
+    async fn execute_certified_actions(
+        &self,
+        chain_id: BridgeChainId,
+        certified_actions: Vec<VerifiedCertifiedBridgeAction>,
+        snapshot: &CommitteeSnapshot,
+        output: &mut GovernanceOutput,
+    ) -> anyhow::Result<()> {
+        if chain_id.is_starcoin_bridge_chain() {
+            execute_actions_on_starcoin(
+                &self.config,
+                &self.starcoin_bridge_client,
+                certified_actions,
+                snapshot,
+                self.deadline,
+                &self.metrics,
+                output,
+            )
+            .await
+        } else {
+            execute_actions_on_eth(
+                &self.config,
+                &self.starcoin_bridge_client,
+                chain_id,
+                certified_actions,
+                snapshot,
+                self.deadline,
+                &self.metrics,
+                output,
+            )
+            .await
+        }
+    }
+}
+
+// Starcoin actions must target the chain the RPC url serves
+fn check_starcoin_chain_id(chain_id: BridgeChainId, rpc_chain_id: u8) -> anyhow::Result<()> {
+    if rpc_chain_id != chain_id as u8 {
+        return Err(anyhow!(
+            "Chain ID mismatch, expected: {:?}, got from url: {}",
+            chain_id,
+            rpc_chain_id
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::ClaimStatus;
+    use crate::tests::{test_config, test_evm_chain};
+    use crate::GovernanceNonce;
+    use ethers::abi::Token;
+    use ethers::contract::{EthCall, EthEvent};
+    use ethers::types::{Block, Bytes, FeeHistory, Log, H256};
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::ToFromBytes;
+    use move_core_types::language_storage::StructTag;
+    use serde_json::json;
+    use starcoin_bridge::abi::{
+        eth_bridge_committee, eth_bridge_config, eth_bridge_limiter, eth_starcoin_bridge,
+    };
+    use starcoin_bridge::eth_mock_provider::EthMockProvider;
+    use starcoin_bridge::events::MoveTokenDepositedEvent;
+    use starcoin_bridge::fault_injection::FaultInjector;
+    use starcoin_bridge::server::mock_handler::BridgeRequestMockHandler;
+    use starcoin_bridge::starcoin_mock_node::{StarcoinMockNode, MOCK_NODE_BRIDGE_ADDRESS};
+    use starcoin_bridge::test_utils::{
+        bridge_committee_to_bridge_committee_summary,
+        get_test_authorities_and_run_mock_bridge_server, get_test_authority_and_key,
+    };
+    use starcoin_bridge::types::{EmergencyActionType, USD_MULTIPLIER};
+    use starcoin_bridge_json_rpc_types::{EventID, StarcoinEvent};
+    use starcoin_bridge_types::bridge::{
+        BridgeCommitteeSummary, BridgeLimiterSummary, BridgeTokenMetadata, BridgeTreasurySummary,
+        MoveTypeCommitteeMember, BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER,
+    };
+    use starcoin_bridge_types::crypto::{get_key_pair, StarcoinKeyPair};
+    use std::str::FromStr;
+
+    const BRIDGE_ADDRESS: &str = "0x246b237c16c761e9478783dd83f7004a";
+    // EVM chain id the mock Eth node reports
+    const MOCK_EVM_CHAIN_ID: u64 = 31337;
+
+    // Keyless ops over endpoints that refuse connections, so every flow has
+    // to fail before it sends anything
+    fn offline_ops() -> BridgeOps {
+        let evm_chain = test_evm_chain(BridgeChainId::EthCustom, "http://127.0.0.1:1");
+        let config = test_config("http://127.0.0.1:1", BRIDGE_ADDRESS, Some(evm_chain));
+        let starcoin_bridge_client =
+            StarcoinBridgeClient::new("http://127.0.0.1:1", BRIDGE_ADDRESS);
+        BridgeOps::new(config, starcoin_bridge_client)
+            .with_deadline(Deadline::after(Duration::from_secs(5)))
+    }
+
+    // Ops keyed for both chains over a mock Starcoin node and a mock Eth node
+    // serving `test_evm_chain(EthCustom)`. The Starcoin bridge has ETH
+    // registered, and both limiters have room for any deposit. Eth
+    // transactions are mined at once, and Starcoin ones execute.
+    struct MockBridge {
+        node: StarcoinMockNode,
+        eth: EthMockProvider,
+        ops: BridgeOps,
+    }
+
+    // Type name of ETH on the mock Starcoin bridge, as the treasury lists it
+    fn eth_type_name() -> String {
+        format!(
+            "{}::ETH::ETH",
+            MOCK_NODE_BRIDGE_ADDRESS.trim_start_matches("0x")
+        )
+    }
+
+    async fn mock_bridge() -> MockBridge {
+        let node = StarcoinMockNode::start().await;
+        node.set_bridge_summary(BridgeSummary {
+            bridge_version: 1,
+            message_version: 1,
+            chain_id: BridgeChainId::StarcoinCustom as u8,
+            sequence_nums: vec![],
+            committee: BridgeCommitteeSummary::default(),
+            treasury: BridgeTreasurySummary {
+                supported_tokens: vec![(
+                    eth_type_name(),
+                    BridgeTokenMetadata {
+                        id: TOKEN_ID_ETH,
+                        decimal_multiplier: 100_000_000,
+                        notional_value: 2_000 * USD_MULTIPLIER,
+                        native_token: false,
+                    },
+                )],
+                id_token_type_map: vec![(TOKEN_ID_ETH, eth_type_name())],
+                paused_token_ids: vec![],
+            },
+            bridge_records_id: [0; 32],
+            limiter: BridgeLimiterSummary {
+                transfer_limit: vec![(
+                    BridgeChainId::EthCustom,
+                    BridgeChainId::StarcoinCustom,
+                    u64::MAX,
+                )],
+                transfer_records: vec![],
+            },
+            is_frozen: false,
+        });
+
+        let eth = EthMockProvider::new();
+        for (method, response) in [
+            ("eth_chainId", json!(U256::from(MOCK_EVM_CHAIN_ID))),
+            ("eth_blockNumber", json!(U256::one())),
+            ("eth_getTransactionCount", json!(U256::zero())),
+            ("eth_estimateGas", json!(U256::from(100_000))),
+            ("eth_gasPrice", json!(U256::one())),
+            (
+                "eth_getBlockByNumber",
+                json!(Block::<TxHash> {
+                    number: Some(1.into()),
+                    base_fee_per_gas: Some(U256::one()),
+                    ..Default::default()
+                }),
+            ),
+            (
+                "eth_feeHistory",
+                json!(FeeHistory {
+                    base_fee_per_gas: vec![U256::one()],
+                    gas_used_ratio: vec![0.5],
+                    oldest_block: U256::one(),
+                    reward: vec![],
+                }),
+            ),
+            // No recipient is a contract
+            ("eth_getCode", json!(Bytes::new())),
+        ] {
+            eth.add_method_response(method, response).unwrap();
+        }
+        let evm_chain = test_evm_chain(BridgeChainId::EthCustom, &eth.serve().await);
+        let uint = |value: u64| vec![Token::Uint(value.into())];
+        add_call::<eth_bridge_config::TokenStarcoinDecimalOfCall>(&eth, 3, uint(8));
+        add_call::<eth_bridge_limiter::ChainLimitsCall>(&eth, 4, uint(u64::MAX));
+        add_call::<eth_bridge_limiter::CurrentHourCall>(&eth, 4, uint(1_000));
+        add_call::<eth_bridge_limiter::ChainHourlyTransferAmountCall>(&eth, 4, uint(0));
+
+        let mut config = test_config(node.url(), MOCK_NODE_BRIDGE_ADDRESS, Some(evm_chain));
+        let (_, starcoin_bridge_key): (_, Ed25519KeyPair) = get_key_pair();
+        let (_, eth_key): (_, Secp256k1KeyPair) = get_key_pair();
+        config.starcoin_bridge_key = Some(StarcoinKeyPair::Ed25519(starcoin_bridge_key));
+        config.eth_key = Some(StarcoinKeyPair::Secp256k1(eth_key));
+        let starcoin_bridge_client =
+            StarcoinBridgeClient::new(node.url(), MOCK_NODE_BRIDGE_ADDRESS);
+        let ops = BridgeOps::new(config, starcoin_bridge_client)
+            .with_deadline(Deadline::after(Duration::from_secs(30)));
+        MockBridge { node, eth, ops }
+    }
+
+    // Every call of `C` on the contract at 0x0101..01 times `contract_byte`,
+    // see `test_evm_chain`, returns `output`
+    fn add_call<C: EthCall>(eth: &EthMockProvider, contract_byte: u8, output: Vec<Token>) {
+        eth.add_call_response(
+            EthAddress::repeat_byte(contract_byte),
+            C::selector(),
+            ethers::abi::encode(&output).into(),
+        );
+    }
+
+    // `Bridge::test_get_parsed_token_transfer_message` of transfer `seq_num`
+    // of 100 ETH units from `source_chain` to `target_chain`
+    fn parsed_message(
+        source_chain: BridgeChainId,
+        target_chain: BridgeChainId,
+        seq_num: u64,
+    ) -> serde_json::Value {
+        let (sender_address, target_address) = if source_chain.is_starcoin_bridge_chain() {
+            (hex::encode([7u8; 16]), hex::encode([9u8; 20]))
+        } else {
+            (hex::encode([9u8; 20]), hex::encode([7u8; 16]))
+        };
+        json!([{
+            "type": "option",
+            "value": {
+                "message_version": 1,
+                "seq_num": seq_num,
+                "source_chain": source_chain as u8,
+                "payload": "0x00",
+                "parsed_payload": {
+                    "sender_address": format!("0x{sender_address}"),
+                    "target_chain": target_chain as u8,
+                    "target_address": format!("0x{target_address}"),
+                    "token_type": TOKEN_ID_ETH,
+                    "amount": 100,
+                },
+            },
+        }])
+    }
+
+    // Result of the Starcoin bridge function `function`, as `Module::function`,
+    // for transfer `seq_num` from `source_chain`
+    fn set_transfer_call(
+        node: &StarcoinMockNode,
+        function: &str,
+        source_chain: BridgeChainId,
+        seq_num: u64,
+        result: serde_json::Value,
+    ) {
+        let source_chain = format!("{}u8", source_chain as u8);
+        let seq_num = format!("{seq_num}u64");
+        node.set_call_result(function, &[&source_chain, &seq_num], result);
+    }
+
+    #[tokio::test]
+    async fn test_deposits_reject_bad_recipients() {
+        let ops = offline_ops();
+        let bridge_address = StarcoinAddress::from_str(BRIDGE_ADDRESS).unwrap();
+
+        let err = ops
+            .deposit_native_ether(U256::one(), 2, bridge_address, true, None, true, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is the bridge address"), "{err}");
+
+        let err = ops
+            .deposit_erc20_on_eth(
+                EthAddress::repeat_byte(9),
+                "1",
+                Some(6),
+                2,
+                StarcoinAddress::ZERO,
+                false,
+                true,
+                None,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("zero address"), "{err}");
+
+        // Unconfigured and non-EVM source chains
+        let err = ops
+            .deposit_native_ether(
+                U256::one(),
+                2,
+                StarcoinAddress::new([7; 16]),
+                true,
+                Some(BridgeChainId::EthSepolia),
+                true,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not configured"), "{err}");
+        let err = ops
+            .deposit_native_ether(
+                U256::one(),
+                2,
+                StarcoinAddress::new([7; 16]),
+                true,
+                Some(BridgeChainId::StarcoinCustom),
+                true,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not an EVM chain"), "{err}");
+
+        let err = ops
+            .deposit_on_starcoin(
+                TypeTag::from_str(&format!("{BRIDGE_ADDRESS}::USDT::USDT")).unwrap(),
+                BridgeChainId::EthCustom,
+                EthAddress::repeat_byte(1),
+                1,
+                false,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is a bridge contract"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_claims_record_latency() {
+        let ops = offline_ops();
+        let latency = |chain: &str| {
+            ops.metrics()
+                .cli_claim_latency
+                .with_label_values(&[chain, "error"])
+                .get_sample_count()
+        };
+
+        // Claiming for real needs an Eth key
+        let err = ops.claim_on_eth(1, false).await.unwrap_err();
+        assert!(err.to_string().contains("No Eth key configured"), "{err}");
+        assert_eq!(latency("eth"), 1);
+
+        let err = ops.claim_on_starcoin(99, 1, None, true).await.unwrap_err();
+        assert!(err.to_string().contains("Invalid source chain"), "{err}");
+        assert_eq!(latency("starcoin"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_governance_fails_without_bridge_state() {
+        let ops = offline_ops();
+        let pause = GovernanceClientCommands::EmergencyButton {
+            nonce: crate::GovernanceNonce::Auto,
+            action_type: EmergencyActionType::Pause,
+        };
+        let err = ops
+            .execute_governance_action(
+                BridgeChainId::StarcoinCustom,
+                vec![pause],
+                &GovernanceOptions {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Governance setup"), "{err}");
+
+        let signed_actions = SignedActionsFile::new(BridgeChainId::StarcoinCustom, &[]);
+        let err = ops
+            .execute_signed_actions(BridgeChainId::StarcoinCustom, &signed_actions)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Signed action setup"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_pause_check_needs_the_bridge_state() {
+        let ops = offline_ops();
+        ops.ensure_not_paused(&[]).await.unwrap();
+        let err = ops
+            .ensure_not_paused(&[BridgeSide::Starcoin])
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Failed to read the Starcoin bridge state"),
+            "{err}"
+        );
+        let err = ops
+            .ensure_not_paused(&[BridgeSide::Evm(BridgeChainId::EthSepolia)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not configured"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_sync_committee_state_needs_an_evm_chain() {
+        let ops = offline_ops();
+        let options = GovernanceOptions::default();
+        let ping_timeout = Duration::from_secs(1);
+        let err = ops
+            .sync_committee_state(BridgeChainId::StarcoinCustom, false, ping_timeout, &options)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not an EVM chain"), "{err}");
+
+        // The committee is read from Starcoin first
+        let err = ops
+            .sync_committee_state(BridgeChainId::EthCustom, false, ping_timeout, &options)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bridge summary"), "{err}");
+    }
+
+    #[test]
+    fn test_check_starcoin_chain_id() {
+        check_starcoin_chain_id(BridgeChainId::StarcoinCustom, 2).unwrap();
+        let err = check_starcoin_chain_id(BridgeChainId::StarcoinCustom, 1).unwrap_err();
+        assert!(err.to_string().contains("Chain ID mismatch"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_deposit_native_ether() {
+        let bridge = mock_bridge().await;
+        let recipient = StarcoinAddress::new([7; 16]);
+        let deposit_log = Log {
+            address: EthAddress::repeat_byte(1),
+            topics: vec![
+                eth_starcoin_bridge::TokensDepositedFilter::signature(),
+                H256::from_low_u64_be(BridgeChainId::EthCustom as u64),
+                H256::from_low_u64_be(7),
+                H256::from_low_u64_be(BridgeChainId::StarcoinCustom as u64),
+            ],
+            data: ethers::abi::encode(&[
+                Token::Uint(TOKEN_ID_ETH.into()),
+                Token::Uint(100_000_000u64.into()),
+                Token::Address(EthAddress::repeat_byte(9)),
+                Token::Bytes(recipient.to_vec()),
+            ])
+            .into(),
+            ..Default::default()
+        };
+        bridge
+            .eth
+            .set_transaction_logs(EthAddress::repeat_byte(1), vec![deposit_log]);
+
+        let output = bridge
+            .ops
+            .deposit_native_ether(
+                U256::exp10(18),
+                BridgeChainId::StarcoinCustom as u8,
+                recipient,
+                true,
+                None,
+                false,
+                false,
+            )
+            .await
+            .unwrap();
+        let EtherDepositOutput::Deposited(deposit) = output else {
+            panic!("Not deposited: {output:?}");
+        };
+        assert_eq!(deposit.nonce, Some(7));
+        assert!(deposit.approve_tx.is_none());
+        assert_eq!(deposit.deposit_tx.status.as_deref(), Some("success"));
+        assert_eq!(deposit.deposit_tx.gas_used, Some(100_000));
+        let receipts = bridge.eth.mined_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(
+            deposit.deposit_tx.tx_hash,
+            TxDigest::eth(receipts[0].transaction_hash).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deposit_on_starcoin() {
+        let bridge = mock_bridge().await;
+        let sender = bridge
+            .ops
+            .config()
+            .starcoin_bridge_key()
+            .unwrap()
+            .starcoin_address();
+        let coin_type = TypeTag::from_str(&format!("0x{}", eth_type_name())).unwrap();
+        bridge.node.set_resource(
+            sender,
+            &format!("0x00000000000000000000000000000001::Account::Balance<{coin_type}>"),
+            json!({"json": {"token": {"value": 1_000}}}),
+        );
+        let recipient = EthAddress::repeat_byte(9);
+        bridge.node.set_transaction_events(vec![StarcoinEvent {
+            id: EventID {
+                tx_digest: [0; 32],
+                event_seq: 0,
+                block_number: 0,
+            },
+            type_: StructTag::from_str(&format!(
+                "{MOCK_NODE_BRIDGE_ADDRESS}::Bridge::TokenDepositedEvent"
+            ))
+            .unwrap(),
+            bcs: bcs::to_bytes(&MoveTokenDepositedEvent {
+                seq_num: 5,
+                source_chain: BridgeChainId::StarcoinCustom as u8,
+                sender_address: sender.to_vec(),
+                target_chain: BridgeChainId::EthCustom as u8,
+                target_address: recipient.as_bytes().to_vec(),
+                token_type: TOKEN_ID_ETH,
+                amount_starcoin_bridge_adjusted: 100,
+            })
+            .unwrap(),
+        }]);
+
+        let deposit = bridge
+            .ops
+            .deposit_on_starcoin(
+                coin_type,
+                BridgeChainId::EthCustom,
+                recipient,
+                100,
+                false,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(deposit.nonce, Some(5));
+        assert_eq!(
+            deposit.claim_command.as_deref(),
+            Some("client claim-on-eth --seq-num 5")
+        );
+        assert_eq!(deposit.deposit_tx.status.as_deref(), Some("Executed"));
+        assert_eq!(bridge.node.submitted_transactions(), vec![(sender, 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_claim_on_eth() {
+        let bridge = mock_bridge().await;
+        let source_chain = BridgeChainId::StarcoinCustom;
+        set_transfer_call(
+            &bridge.node,
+            "Bridge::test_get_parsed_token_transfer_message",
+            source_chain,
+            3,
+            parsed_message(source_chain, BridgeChainId::EthCustom, 3),
+        );
+        set_transfer_call(
+            &bridge.node,
+            "Bridge::query_token_transfer_signatures",
+            source_chain,
+            3,
+            json!([{
+                "type": "option",
+                "value": {"type": "vector", "value": [{"value": vec![1u8; 65]}]},
+            }]),
+        );
+        add_call::<eth_starcoin_bridge::IsTransferProcessedCall>(
+            &bridge.eth,
+            1,
+            vec![Token::Bool(false)],
+        );
+        add_call::<eth_bridge_committee::VerifySignaturesCall>(&bridge.eth, 2, vec![]);
+        add_call::<eth_starcoin_bridge::TransferBridgedTokensWithSignaturesCall>(
+            &bridge.eth,
+            1,
+            vec![],
+        );
+
+        let claim = bridge.ops.claim_on_eth(3, false).await.unwrap();
+        assert_eq!(claim.source_chain_id, source_chain as u8);
+        assert_eq!(claim.seq_num, 3);
+        assert_eq!(claim.status, ClaimStatus::Claimed);
+        assert!(claim.approve_tx.is_none());
+        assert_eq!(claim.claim_tx.unwrap().status.as_deref(), Some("success"));
+        assert!(claim.message_digest.is_some());
+        assert_eq!(bridge.eth.mined_receipts().len(), 1);
+        assert_eq!(
+            bridge
+                .ops
+                .metrics()
+                .cli_claim_latency
+                .with_label_values(&["eth", "claimed"])
+                .get_sample_count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claim_on_starcoin() {
+        let bridge = mock_bridge().await;
+        let source_chain = BridgeChainId::EthCustom;
+        // Approved, so only the claim is submitted
+        set_transfer_call(
+            &bridge.node,
+            "Bridge::query_token_transfer_status",
+            source_chain,
+            4,
+            json!([1]),
+        );
+        set_transfer_call(
+            &bridge.node,
+            "Bridge::test_get_parsed_token_transfer_message",
+            source_chain,
+            4,
+            parsed_message(source_chain, BridgeChainId::StarcoinCustom, 4),
+        );
+
+        let claim = bridge
+            .ops
+            .claim_on_starcoin(source_chain as u8, 4, None, false)
+            .await
+            .unwrap();
+        assert_eq!(claim.source_chain_id, source_chain as u8);
+        assert_eq!(claim.seq_num, 4);
+        assert_eq!(claim.status, ClaimStatus::Claimed);
+        assert!(claim.approve_tx.is_none());
+        assert_eq!(claim.claim_tx.unwrap().status.as_deref(), Some("Executed"));
+        assert_eq!(bridge.node.submitted_transactions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_governance_action_on_eth() {
+        let bridge = mock_bridge().await;
+        let handler = BridgeRequestMockHandler::new();
+        let (_handles, authorities, mut secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER],
+            vec![handler.clone()],
+        );
+        handler.set_signer(secrets.remove(0));
+        let authority = &authorities[0];
+        let pubkey = authority.pubkey.as_bytes().to_vec();
+        bridge.node.set_bridge_committee(BridgeCommitteeSummary {
+            members: vec![(
+                pubkey.clone(),
+                MoveTypeCommitteeMember {
+                    starcoin_bridge_address: authority.starcoin_bridge_address,
+                    bridge_pubkey_bytes: pubkey,
+                    voting_power: authority.voting_power,
+                    http_rest_url: authority.base_url.as_bytes().to_vec(),
+                    blocklisted: false,
+                },
+            )],
+            member_registration: vec![],
+            last_committee_update_epoch: 1,
+        });
+        add_call::<eth_starcoin_bridge::NoncesCall>(&bridge.eth, 1, vec![Token::Uint(4.into())]);
+        add_call::<eth_starcoin_bridge::ExecuteEmergencyOpWithSignaturesCall>(
+            &bridge.eth,
+            1,
+            vec![],
+        );
+
+        let pause = GovernanceClientCommands::EmergencyButton {
+            nonce: GovernanceNonce::Auto,
+            action_type: EmergencyActionType::Pause,
+        };
+        let output = bridge
+            .ops
+            .execute_governance_action(
+                BridgeChainId::EthCustom,
+                vec![pause],
+                &GovernanceOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.chain_id, BridgeChainId::EthCustom as u8);
+        assert!(!output.dry_run);
+        assert_eq!(output.committee.unwrap().last_committee_update_epoch, 1);
+        assert_eq!(output.actions.len(), 1);
+        let action = &output.actions[0];
+        assert_eq!(action.action_type, BridgeActionType::EmergencyButton);
+        assert_eq!(action.nonce, 4);
+        assert_eq!(action.signers.len(), 1);
+        assert!(action.error.is_none());
+        assert_eq!(
+            action.tx.as_ref().unwrap().status.as_deref(),
+            Some("success")
+        );
+        assert_eq!(bridge.eth.mined_receipts().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_governance_setup_reads_summary_and_nonces_concurrently() {
+        let latency = Duration::from_millis(400);
+        let bridge = mock_bridge().await;
+        let (authority, _, _) =
+            get_test_authority_and_key(BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, 9999);
+        bridge
+            .node
+            .set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+                BridgeCommittee::new(vec![authority]).unwrap(),
+            ));
+        add_call::<eth_starcoin_bridge::NoncesCall>(&bridge.eth, 1, vec![Token::Uint(4.into())]);
+        // The summary takes one latency to read, and so do the nonces: half
+        // for the failed Multicall3 read and half for its fallback
+        bridge.node.set_fault_injector(
+            FaultInjector::builder(0)
+                .latency("state.get_resource", latency, latency)
+                .build(),
+        );
+        bridge.eth.set_fault_injector(
+            FaultInjector::builder(0)
+                .latency("eth_call", latency / 2, latency / 2)
+                .build(),
+        );
+
+        // A stale nonce fails right after the setup
+        let pause = GovernanceClientCommands::EmergencyButton {
+            nonce: GovernanceNonce::Value(3),
+            action_type: EmergencyActionType::Pause,
+        };
+        let start = std::time::Instant::now();
+        let err = bridge
+            .ops
+            .execute_governance_action(
+                BridgeChainId::EthCustom,
+                vec![pause],
+                &GovernanceOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        let elapsed = start.elapsed();
+        assert!(
+            err.to_string().contains("expected on-chain nonce 4"),
+            "{err}"
+        );
+        assert!(elapsed >= latency);
+        assert!(elapsed < latency * 3 / 2, "setup took {:?}", elapsed);
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The committee a governance invocation is signed against. The committee is
+//! read once, and every action of the invocation is certified by the same
+//! aggregator. A rotation in the middle of a batch would otherwise produce
+//! certificates signed by different sets, so before each transaction is
+//! submitted the committee on chain is checked against the snapshot, and the
+//! batch is aborted if it changed.
+//!
+//! The committee is read through `CommitteeSource`, so the check can be
+//! tested with the mock client.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use fastcrypto::hash::{HashFunction, Keccak256};
+use serde::Serialize;
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::error::BridgeResult;
+use starcoin_bridge::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use starcoin_bridge_types::bridge::{BridgeCommitteeSummary, BridgeSummary};
+
+#[async_trait]
+pub trait CommitteeSource {
+    // The bridge summary on chain now, never a cached one
+    async fn latest_bridge_summary(&self) -> BridgeResult<BridgeSummary>;
+}
+
+#[async_trait]
+impl<P: StarcoinClientInner> CommitteeSource for StarcoinClient<P> {
+    async fn latest_bridge_summary(&self) -> BridgeResult<BridgeSummary> {
+        self.invalidate_bridge_summary_cache().await;
+        self.get_bridge_summary().await
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeSnapshot {
+    pub last_committee_update_epoch: u64,
+    // Keccak256 of the members with their voting power and blocklist state,
+    // which change without a new epoch
+    pub committee_hash: String,
+    pub members: usize,
+}
+
+impl CommitteeSnapshot {
+    pub fn new(committee: &BridgeCommitteeSummary) -> Self {
+        let mut members = committee
+            .members
+            .iter()
+            .map(|(_, member)| member)
+            .collect::<Vec<_>>();
+        members.sort_by(|a, b| a.bridge_pubkey_bytes.cmp(&b.bridge_pubkey_bytes));
+        let mut hasher = Keccak256::default();
+        for member in &members {
+            hasher.update((member.bridge_pubkey_bytes.len() as u64).to_le_bytes());
+            hasher.update(&member.bridge_pubkey_bytes);
+            hasher.update(member.voting_power.to_le_bytes());
+            hasher.update([member.blocklisted as u8]);
+        }
+        Self {
+            last_committee_update_epoch: committee.last_committee_update_epoch,
+            committee_hash: format!("0x{}", hex::encode(hasher.finalize().digest)),
+            members: members.len(),
+        }
+    }
+
+    // Fails if `committee` is not the committee of the snapshot
+    pub fn check(&self, committee: &BridgeCommitteeSummary) -> anyhow::Result<()> {
+        let current = Self::new(committee);
+        if current.last_committee_update_epoch != self.last_committee_update_epoch {
+            return Err(anyhow!(
+                "The bridge committee rotated from epoch {} to epoch {} after the actions were \
+                 signed, so their certificates may no longer verify. Nothing more is submitted, \
+                 run the command again to sign with the new committee",
+                self.last_committee_update_epoch,
+                current.last_committee_update_epoch
+            ));
+        }
+        if current.committee_hash != self.committee_hash {
+            return Err(anyhow!(
+                "The bridge committee of epoch {} changed after the actions were signed, e.g. a \
+                 member was blocklisted, so their certificates may no longer verify. Nothing more \
+                 is submitted, run the command again to sign with the current committee",
+                self.last_committee_update_epoch
+            ));
+        }
+        Ok(())
+    }
+
+    // Reads the committee from `source` and checks it against the snapshot
+    pub async fn ensure_current(
+        &self,
+        source: &(impl CommitteeSource + Sync),
+        deadline: Deadline,
+    ) -> anyhow::Result<()> {
+        let summary = deadline
+            .run("starcoin bridge committee", source.latest_bridge_summary())
+            .await
+            .map_err(|e| anyhow!("Failed to re-check the bridge committee: {:?}", e))?;
+        self.check(&summary.committee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_bridge::starcoin_bridge_mock_client::StarcoinMockClient;
+    use starcoin_bridge_types::bridge::MoveTypeCommitteeMember;
+
+    fn committee(epoch: u64, members: &[(u8, u64, bool)]) -> BridgeCommitteeSummary {
+        BridgeCommitteeSummary {
+            members: members
+                .iter()
+                .map(|(key, voting_power, blocklisted)| {
+                    (
+                        vec![*key; 33],
+                        MoveTypeCommitteeMember {
+                            bridge_pubkey_bytes: vec![*key; 33],
+                            voting_power: *voting_power,
+                            blocklisted: *blocklisted,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            member_registration: vec![],
+            last_committee_update_epoch: epoch,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_ignores_member_order() {
+        let snapshot =
+            CommitteeSnapshot::new(&committee(3, &[(1, 6_000, false), (2, 4_000, false)]));
+        assert_eq!(snapshot.last_committee_update_epoch, 3);
+        assert_eq!(snapshot.members, 2);
+        assert_eq!(snapshot.committee_hash.len(), 2 + 64);
+        snapshot
+            .check(&committee(3, &[(2, 4_000, false), (1, 6_000, false)]))
+            .unwrap();
+        // Any change of stake is a different committee
+        assert!(snapshot
+            .check(&committee(3, &[(1, 5_000, false), (2, 5_000, false)]))
+            .is_err());
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["last-committee-update-epoch"], 3);
+        assert_eq!(json["members"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_rotation_between_signing_and_submission() {
+        let mock_client = StarcoinMockClient::default();
+        let members = [(1, 5_000, false), (2, 3_000, false), (3, 2_000, false)];
+        mock_client.set_bridge_committee(committee(7, &members));
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+
+        // Signing reads the committee once
+        let summary = starcoin_bridge_client
+            .latest_bridge_summary()
+            .await
+            .unwrap();
+        let snapshot = CommitteeSnapshot::new(&summary.committee);
+        assert_eq!(mock_client.bridge_summary_calls(), 1);
+        // Each submission reads it again, fresh, and passes while it holds
+        for _ in 0..2 {
+            snapshot
+                .ensure_current(&starcoin_bridge_client, Deadline::none())
+                .await
+                .unwrap();
+        }
+        assert_eq!(mock_client.bridge_summary_calls(), 3);
+
+        // A blocklist change keeps the epoch but not the signing set
+        mock_client.set_bridge_committee(committee(
+            7,
+            &[(1, 5_000, false), (2, 3_000, true), (3, 2_000, false)],
+        ));
+        let err = snapshot
+            .ensure_current(&starcoin_bridge_client, Deadline::none())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("committee of epoch 7 changed"), "{err}");
+
+        // The committee rotates before the next submission
+        mock_client.set_bridge_committee(committee(8, &[(4, 10_000, false)]));
+        let err = snapshot
+            .ensure_current(&starcoin_bridge_client, Deadline::none())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("rotated from epoch 7 to epoch 8"), "{err}");
+        assert!(err.contains("Nothing more is submitted"), "{err}");
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plans `sync-committee-state`. The committee contract of each EVM chain
+//! keeps its own blocklist, so a blocklist change executed on Starcoin has to
+//! be mirrored on Eth with a `BlocklistCommitteeAction`.
+//!
+//! Starcoin is the source of truth. Members are matched by the Eth address
+//! derived from their bridge public key, which is how the Eth committee
+//! keys them.
+
+use crate::{GovernanceClientCommands, GovernanceNonce, LoadedEvmChain};
+use anyhow::anyhow;
+use ethers::providers::Middleware;
+use ethers::types::Address as EthAddress;
+use fastcrypto::traits::ToFromBytes;
+use starcoin_bridge::abi::EthBridgeCommittee;
+use starcoin_bridge::crypto::BridgeAuthorityPublicKeyBytes;
+use starcoin_bridge::sanitize::{sanitize_untrusted, MAX_URL_BYTES};
+use starcoin_bridge::types::BlocklistType;
+use starcoin_bridge::utils::EthCallBatch;
+use starcoin_bridge_types::bridge::{BridgeCommitteeSummary, APPROVAL_THRESHOLD_TOKEN_TRANSFER};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StarcoinMember {
+    pub pubkey: BridgeAuthorityPublicKeyBytes,
+    pub eth_address: EthAddress,
+    pub voting_power: u64,
+    pub blocklisted: bool,
+    pub url: String,
+}
+
+// A member as the committee contract of an EVM chain sees it. Addresses
+// outside the committee have no stake.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EthMember {
+    pub stake: u64,
+    pub blocklisted: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitteeDiff {
+    pub to_blocklist: Vec<BridgeAuthorityPublicKeyBytes>,
+    pub to_unblocklist: Vec<BridgeAuthorityPublicKeyBytes>,
+    // Starcoin members without stake on Eth. Blocklist actions can't add
+    // members, so these are only reported.
+    pub missing_on_eth: Vec<EthAddress>,
+}
+
+impl CommitteeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.to_blocklist.is_empty() && self.to_unblocklist.is_empty()
+    }
+
+    // At most one action per blocklist type, with nonces left to
+    // `resolve_nonces`. Unblocklisting goes first, so that signing power is
+    // restored before any is removed.
+    pub fn commands(&self) -> Vec<GovernanceClientCommands> {
+        [
+            (BlocklistType::Unblocklist, &self.to_unblocklist),
+            (BlocklistType::Blocklist, &self.to_blocklist),
+        ]
+        .into_iter()
+        .filter(|(_, members)| !members.is_empty())
+        .map(
+            |(blocklist_type, members)| GovernanceClientCommands::UpdateCommitteeBlocklist {
+                nonce: GovernanceNonce::Auto,
+                blocklist_type,
+                pubkeys_hex: members.clone(),
+            },
+        )
+        .collect()
+    }
+}
+
+pub fn starcoin_members(committee: &BridgeCommitteeSummary) -> anyhow::Result<Vec<StarcoinMember>> {
+    committee
+        .members
+        .iter()
+        .map(|(_, member)| {
+            let pubkey = BridgeAuthorityPublicKeyBytes::from_bytes(&member.bridge_pubkey_bytes)
+                .map_err(|e| {
+                    anyhow!(
+                        "Invalid bridge pubkey of committee member {}: {:?}",
+                        member.starcoin_bridge_address,
+                        e
+                    )
+                })?;
+            Ok(StarcoinMember {
+                eth_address: pubkey.to_eth_address(),
+                pubkey,
+                voting_power: member.voting_power,
+                blocklisted: member.blocklisted,
+                url: sanitize_untrusted(&member.http_rest_url, MAX_URL_BYTES),
+            })
+        })
+        .collect()
+}
+
+// Reads the stake and blocklist status of `addresses` from the committee
+// contract of `evm_chain`, in a single multicall
+pub async fn eth_committee_members(
+    evm_chain: &LoadedEvmChain,
+    addresses: &[EthAddress],
+) -> anyhow::Result<HashMap<EthAddress, EthMember>> {
+    read_committee_members(
+        evm_chain.eth_bridge_committee_proxy_address,
+        evm_chain.provider(),
+        addresses,
+    )
+    .await
+}
+
+// As `eth_committee_members`, with the committee contract at
+// `committee_address`
+pub async fn read_committee_members<M: Middleware + 'static>(
+    committee_address: EthAddress,
+    client: Arc<M>,
+    addresses: &[EthAddress],
+) -> anyhow::Result<HashMap<EthAddress, EthMember>> {
+    let committee = EthBridgeCommittee::new(committee_address, client.clone());
+    let mut batch = EthCallBatch::new(client);
+    let handles = addresses
+        .iter()
+        .map(|address| {
+            (
+                *address,
+                batch.add(&committee.committee_stake(*address)),
+                batch.add(&committee.blocklist(*address)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let results = batch.execute().await;
+    handles
+        .into_iter()
+        .map(|(address, stake, blocklisted)| {
+            let read_error = |e| {
+                anyhow!(
+                    "Failed to read committee member {:?} on Eth: {:?}",
+                    address,
+                    e
+                )
+            };
+            let stake: u16 = stake.decode(&results).map_err(read_error)?;
+            let blocklisted = blocklisted.decode(&results).map_err(read_error)?;
+            Ok((
+                address,
+                EthMember {
+                    stake: stake as u64,
+                    blocklisted,
+                },
+            ))
+        })
+        .collect()
+}
+
+pub fn diff_committees(
+    starcoin: &[StarcoinMember],
+    eth: &HashMap<EthAddress, EthMember>,
+) -> CommitteeDiff {
+    let mut diff = CommitteeDiff::default();
+    for member in starcoin {
+        let eth_member = eth.get(&member.eth_address).copied().unwrap_or_default();
+        if eth_member.stake == 0 {
+            diff.missing_on_eth.push(member.eth_address);
+            continue;
+        }
+        match (member.blocklisted, eth_member.blocklisted) {
+            (true, false) => diff.to_blocklist.push(member.pubkey.clone()),
+            (false, true) => diff.to_unblocklist.push(member.pubkey.clone()),
+            _ => (),
+        }
+    }
+    diff
+}
+
+// Stake on Eth of the online members whose signatures count there, before
+// and after the blocklist is synced
+pub fn online_signing_stake(
+    starcoin: &[StarcoinMember],
+    eth: &HashMap<EthAddress, EthMember>,
+    online: &HashSet<EthAddress>,
+) -> (u64, u64) {
+    let mut before = 0;
+    let mut after = 0;
+    for member in starcoin {
+        let Some(eth_member) = eth.get(&member.eth_address) else {
+            continue;
+        };
+        if !online.contains(&member.eth_address) {
+            continue;
+        }
+        if !eth_member.blocklisted {
+            before += eth_member.stake;
+        }
+        // Once synced, the member is blocklisted on Eth as on Starcoin
+        if !member.blocklisted {
+            after += eth_member.stake;
+        }
+    }
+    (before, after)
+}
+
+// Refuses a sync that would leave the online, non-blocklisted members on Eth
+// without the stake to approve transfers. A sync that doesn't lower the
+// stake is always allowed, since it can only help.
+pub fn check_online_quorum(before: u64, after: u64) -> anyhow::Result<()> {
+    if after < before && after < APPROVAL_THRESHOLD_TOKEN_TRANSFER {
+        return Err(anyhow!(
+            "Syncing the blocklist would drop the online signing stake on Eth from {} to {}, \
+             below the quorum of {}",
+            before,
+            after,
+            APPROVAL_THRESHOLD_TOKEN_TRANSFER
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::KeyPair;
+
+    fn member(voting_power: u64, blocklisted: bool) -> StarcoinMember {
+        let (_, key): (_, Secp256k1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+        let pubkey = BridgeAuthorityPublicKeyBytes::from(key.public());
+        StarcoinMember {
+            eth_address: pubkey.to_eth_address(),
+            pubkey,
+            voting_power,
+            blocklisted,
+            url: "http://127.0.0.1:9191".to_string(),
+        }
+    }
+
+    fn eth_state(members: &[(&StarcoinMember, u64, bool)]) -> HashMap<EthAddress, EthMember> {
+        members
+            .iter()
+            .map(|(member, stake, blocklisted)| {
+                (
+                    member.eth_address,
+                    EthMember {
+                        stake: *stake,
+                        blocklisted: *blocklisted,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_committees() {
+        let synced = member(2500, false);
+        let newly_blocklisted = member(2500, true);
+        let unblocklisted = member(2500, false);
+        let not_on_eth = member(2500, true);
+        let starcoin = vec![
+            synced.clone(),
+            newly_blocklisted.clone(),
+            unblocklisted.clone(),
+            not_on_eth.clone(),
+        ];
+        let eth = eth_state(&[
+            (&synced, 2500, false),
+            (&newly_blocklisted, 2500, false),
+            (&unblocklisted, 2500, true),
+        ]);
+
+        let diff = diff_committees(&starcoin, &eth);
+        assert_eq!(
+            diff,
+            CommitteeDiff {
+                to_blocklist: vec![newly_blocklisted.pubkey.clone()],
+                to_unblocklist: vec![unblocklisted.pubkey.clone()],
+                missing_on_eth: vec![not_on_eth.eth_address],
+            }
+        );
+        let commands = diff.commands();
+        assert_eq!(commands.len(), 2);
+        let GovernanceClientCommands::UpdateCommitteeBlocklist {
+            nonce,
+            blocklist_type,
+            pubkeys_hex,
+        } = &commands[0]
+        else {
+            panic!("Unexpected command");
+        };
+        assert_eq!(*nonce, GovernanceNonce::Auto);
+        assert_eq!(*blocklist_type, BlocklistType::Unblocklist);
+        assert_eq!(pubkeys_hex, &vec![unblocklisted.pubkey.clone()]);
+        assert!(matches!(
+            &commands[1],
+            GovernanceClientCommands::UpdateCommitteeBlocklist {
+                blocklist_type: BlocklistType::Blocklist,
+                ..
+            }
+        ));
+
+        // Nothing to do once Eth matches Starcoin
+        let eth = eth_state(&[
+            (&synced, 2500, false),
+            (&newly_blocklisted, 2500, true),
+            (&unblocklisted, 2500, false),
+        ]);
+        let diff = diff_committees(&starcoin[..3], &eth);
+        assert!(diff.is_empty());
+        assert!(diff.commands().is_empty());
+    }
+
+    #[test]
+    fn test_online_quorum() {
+        let online_member = member(4000, false);
+        let blocklisted = member(4000, true);
+        let offline = member(2000, false);
+        let starcoin = vec![online_member.clone(), blocklisted.clone(), offline.clone()];
+        let eth = eth_state(&[
+            (&online_member, 4000, false),
+            (&blocklisted, 4000, false),
+            (&offline, 2000, false),
+        ]);
+        let online = HashSet::from([online_member.eth_address, blocklisted.eth_address]);
+
+        // Blocklisting leaves 4000 online, above the quorum
+        let (before, after) = online_signing_stake(&starcoin, &eth, &online);
+        assert_eq!((before, after), (8000, 4000));
+        check_online_quorum(before, after).unwrap();
+
+        // Without the online member, only the member being blocklisted can
+        // sign on Eth
+        let online = HashSet::from([blocklisted.eth_address]);
+        let (before, after) = online_signing_stake(&starcoin, &eth, &online);
+        assert_eq!((before, after), (4000, 0));
+        let err = check_online_quorum(before, after).unwrap_err();
+        assert!(err.to_string().contains("below the quorum"), "{err}");
+
+        // Unblocklisting never lowers the stake, even below quorum
+        let eth = eth_state(&[
+            (&online_member, 4000, true),
+            (&blocklisted, 4000, true),
+            (&offline, 2000, false),
+        ]);
+        let online = HashSet::from([online_member.eth_address]);
+        let (before, after) = online_signing_stake(&starcoin, &eth, &online);
+        assert_eq!((before, after), (0, 4000));
+        check_online_quorum(before, after).unwrap();
+        check_online_quorum(0, 0).unwrap();
+    }
+}
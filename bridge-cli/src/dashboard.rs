@@ -0,0 +1,925 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `bridge-cli dashboard`: a terminal UI for live bridge monitoring.
+//!
+//! Data is fetched through `DashboardSource` and folded into `DashboardData` by
+//! `refresh`, separately from rendering, so it can be tested without a
+//! terminal. Every fetch is bounded: a dead RPC turns its panel red and keeps
+//! the last known value instead of stalling the UI.
+
+use crate::hex_args::parse_object_id;
+use crate::{fetch_node_versions, ping_urls, version_distribution};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{Address as EthAddress, BlockNumber};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use starcoin_bridge::abi::EthBridgeEvent;
+use starcoin_bridge::eth_client::EthClient;
+use starcoin_bridge::events::StarcoinBridgeEvent;
+use starcoin_bridge::metered_eth_provider::MeteredEthHttpProvier;
+use starcoin_bridge::retry_with_max_elapsed_time;
+use starcoin_bridge::sanitize::{sanitize_untrusted, MAX_URL_BYTES};
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge::types::BridgeActionType;
+use starcoin_bridge_types::base_types::ObjectID;
+use starcoin_bridge_types::bridge::{BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_MODULE_NAME};
+use starcoin_bridge_types::event::EventID;
+use starcoin_bridge_types::Identifier;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, watch, Mutex};
+
+// Number of feed entries kept in memory
+const FEED_CAPACITY: usize = 200;
+// How far back the event feed starts when the dashboard opens
+const INITIAL_STARCOIN_LOOKBACK_BLOCKS: u64 = 32;
+const INITIAL_ETH_LOOKBACK_BLOCKS: u64 = 100;
+// Largest Eth block range queried for logs in one refresh
+const MAX_ETH_LOG_RANGE: u64 = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainHead {
+    pub height: u64,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitteeStake {
+    pub members: usize,
+    pub online_members: usize,
+    pub total_stake: u64,
+    pub online_stake: u64,
+    // Number of members per reported node version
+    pub versions: BTreeMap<String, usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedKind {
+    Transfer,
+    Governance,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedItem {
+    pub chain: &'static str,
+    pub tx: String,
+    pub label: String,
+    pub kind: FeedKind,
+    // Full rendering of the event, shown when the item is opened
+    pub detail: String,
+}
+
+impl FeedItem {
+    pub fn new(chain: &'static str, tx: String, event: &impl Debug) -> Self {
+        let label = event_label(&format!("{:?}", event));
+        let kind = if ["Transfer", "Deposit", "Claim", "TokenBridge"]
+            .iter()
+            .any(|word| label.contains(word))
+        {
+            FeedKind::Transfer
+        } else {
+            FeedKind::Governance
+        };
+        Self {
+            chain,
+            tx,
+            label,
+            kind,
+            detail: format!("{:#?}", event),
+        }
+    }
+}
+
+// Innermost variant name of a Debug rendered event, e.g. `TokensDepositedFilter`
+// for `EthStarcoinBridgeEvents(TokensDepositedFilter(TokensDepositedFilter { .. }))`.
+fn event_label(debug: &str) -> String {
+    let head = debug.split(['{', ' ']).next().unwrap_or_default();
+    head.split('(')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .next_back()
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+// Where the dashboard gets its data from. Implemented over RPC by `RpcSource`.
+#[async_trait]
+pub trait DashboardSource: Send + Sync {
+    async fn starcoin_head(&self) -> anyhow::Result<ChainHead>;
+    async fn eth_head(&self) -> anyhow::Result<ChainHead>;
+    // Next nonce per action type
+    async fn nonces(&self) -> anyhow::Result<BTreeMap<u8, u64>>;
+    async fn committee_stake(&self) -> anyhow::Result<CommitteeStake>;
+    // None when no bridge node is configured
+    async fn signing_queue_depth(&self) -> anyhow::Result<Option<i64>>;
+    // Bridge events since the previous call, oldest first
+    async fn new_events(&self) -> anyhow::Result<Vec<FeedItem>>;
+}
+
+// A value shown in a panel. A failed fetch keeps the last value and records
+// the error, so the panel can be drawn red with stale data.
+#[derive(Clone, Debug)]
+pub struct Panel<T> {
+    pub value: Option<T>,
+    pub error: Option<String>,
+    pub updated_at_ms: Option<u64>,
+}
+
+impl<T> Default for Panel<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            error: None,
+            updated_at_ms: None,
+        }
+    }
+}
+
+impl<T> Panel<T> {
+    fn update(&mut self, result: anyhow::Result<T>, now_ms: u64) {
+        match result {
+            Ok(value) => {
+                self.value = Some(value);
+                self.error = None;
+                self.updated_at_ms = Some(now_ms);
+            }
+            Err(e) => self.error = Some(format!("{:#}", e)),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DashboardData {
+    pub starcoin_head: Panel<ChainHead>,
+    pub eth_head: Panel<ChainHead>,
+    pub nonces: Panel<BTreeMap<u8, u64>>,
+    // Nonces when the dashboard started, the base of the deltas
+    pub initial_nonces: BTreeMap<u8, u64>,
+    pub committee: Panel<CommitteeStake>,
+    pub signing_queue: Panel<Option<i64>>,
+    // Number of events received by the last refresh
+    pub feed_status: Panel<usize>,
+    // Newest first
+    pub feed: VecDeque<FeedItem>,
+}
+
+impl DashboardData {
+    // Actions executed per action type since the dashboard started
+    pub fn nonce_deltas(&self) -> BTreeMap<u8, u64> {
+        let Some(nonces) = &self.nonces.value else {
+            return BTreeMap::new();
+        };
+        nonces
+            .iter()
+            .map(|(action_type, nonce)| {
+                let initial = self.initial_nonces.get(action_type).unwrap_or(nonce);
+                (*action_type, nonce.saturating_sub(*initial))
+            })
+            .collect()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+async fn bounded<T>(
+    fetch_timeout: Duration,
+    fut: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    tokio::time::timeout(fetch_timeout, fut)
+        .await
+        .map_err(|_| anyhow!("timed out after {:?}", fetch_timeout))?
+}
+
+// Fetches every panel concurrently, each bounded by `fetch_timeout`. The
+// committee is only refreshed when `include_committee` is set, since pinging
+// every member is slower than the other fetches.
+pub async fn refresh<S: DashboardSource + ?Sized>(
+    data: &mut DashboardData,
+    source: &S,
+    fetch_timeout: Duration,
+    include_committee: bool,
+) {
+    let (starcoin_head, eth_head, nonces, committee, signing_queue, events) = tokio::join!(
+        bounded(fetch_timeout, source.starcoin_head()),
+        bounded(fetch_timeout, source.eth_head()),
+        bounded(fetch_timeout, source.nonces()),
+        async {
+            if include_committee {
+                Some(bounded(fetch_timeout, source.committee_stake()).await)
+            } else {
+                None
+            }
+        },
+        bounded(fetch_timeout, source.signing_queue_depth()),
+        bounded(fetch_timeout, source.new_events()),
+    );
+    let now = now_ms();
+    data.starcoin_head.update(starcoin_head, now);
+    data.eth_head.update(eth_head, now);
+    if let Ok(nonces) = &nonces {
+        for (action_type, nonce) in nonces {
+            data.initial_nonces.entry(*action_type).or_insert(*nonce);
+        }
+    }
+    data.nonces.update(nonces, now);
+    if let Some(committee) = committee {
+        data.committee.update(committee, now);
+    }
+    data.signing_queue.update(signing_queue, now);
+    let events = events.map(|events| {
+        let count = events.len();
+        for item in events {
+            data.feed.push_front(item);
+        }
+        data.feed.truncate(FEED_CAPACITY);
+        count
+    });
+    data.feed_status.update(events, now);
+}
+
+// Retries `f` until it succeeds or `max_elapsed` passes.
+async fn retry_bounded<T, E, F, Fut>(max_elapsed: Duration, f: F) -> anyhow::Result<T>
+where
+    E: Debug,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    match retry_with_max_elapsed_time!(f(), max_elapsed) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) | Err(e) => Err(anyhow!("{:?}", e)),
+    }
+}
+
+pub struct RpcSource {
+    starcoin_bridge_client: StarcoinBridgeClient,
+    eth_client: EthClient<MeteredEthHttpProvier>,
+    eth_bridge_proxy_address: EthAddress,
+    bridge_package_id: ObjectID,
+    node_url: Option<String>,
+    // Retries stop after this, so the overall fetch fits in the panel timeout
+    retry_bound: Duration,
+    http_client: reqwest::Client,
+    // Positions the event feed continues from
+    starcoin_cursors: Mutex<HashMap<Identifier, EventID>>,
+    eth_cursor: Mutex<Option<u64>>,
+}
+
+impl RpcSource {
+    pub fn new(
+        starcoin_bridge_client: StarcoinBridgeClient,
+        eth_client: EthClient<MeteredEthHttpProvier>,
+        eth_bridge_proxy_address: EthAddress,
+        node_url: Option<String>,
+        retry_bound: Duration,
+    ) -> anyhow::Result<Self> {
+        let bridge_address = starcoin_bridge_client.bridge_address();
+        // Starcoin addresses are 16 bytes, left padded to an ObjectID
+        let bridge_package_id = parse_object_id(bridge_address)
+            .map_err(|e| anyhow!("Invalid bridge address {}: {}", bridge_address, e))?;
+        Ok(Self {
+            starcoin_bridge_client,
+            eth_client,
+            eth_bridge_proxy_address,
+            bridge_package_id,
+            node_url,
+            retry_bound,
+            http_client: reqwest::Client::new(),
+            starcoin_cursors: Default::default(),
+            eth_cursor: Default::default(),
+        })
+    }
+
+    async fn starcoin_events(&self) -> anyhow::Result<Vec<FeedItem>> {
+        let head = retry_bounded(self.retry_bound, || {
+            self.starcoin_bridge_client
+                .get_latest_checkpoint_sequence_number()
+        })
+        .await?;
+        let mut cursors = self.starcoin_cursors.lock().await;
+        let mut items = vec![];
+        for module in [BRIDGE_MODULE_NAME, BRIDGE_COMMITTEE_MODULE_NAME] {
+            let module = module.to_owned();
+            let cursor = *cursors
+                .entry(module.clone())
+                .or_insert((head.saturating_sub(INITIAL_STARCOIN_LOOKBACK_BLOCKS), 0));
+            let page = retry_bounded(self.retry_bound, || {
+                self.starcoin_bridge_client.query_events_by_module(
+                    self.bridge_package_id,
+                    module.clone(),
+                    Some(cursor),
+                )
+            })
+            .await?;
+            for event in &page.data {
+                if let Ok(Some(bridge_event)) =
+                    StarcoinBridgeEvent::try_from_starcoin_bridge_event(event)
+                {
+                    let tx = format!("0x{}", hex::encode(event.id.tx_digest));
+                    items.push(FeedItem::new("starcoin", tx, &bridge_event));
+                }
+            }
+            if let Some(next_cursor) = page.next_cursor {
+                cursors.insert(module, next_cursor.position());
+            }
+        }
+        Ok(items)
+    }
+
+    async fn eth_events(&self) -> anyhow::Result<Vec<FeedItem>> {
+        let head = self.eth_head().await?.height;
+        let mut cursor = self.eth_cursor.lock().await;
+        let from = match *cursor {
+            Some(last) if last >= head => return Ok(vec![]),
+            Some(last) => last + 1,
+            None => head.saturating_sub(INITIAL_ETH_LOOKBACK_BLOCKS),
+        };
+        let to = head.min(from + MAX_ETH_LOG_RANGE - 1);
+        let logs = retry_bounded(self.retry_bound, || {
+            self.eth_client
+                .get_events_in_range(self.eth_bridge_proxy_address, from, to)
+        })
+        .await?;
+        *cursor = Some(to);
+        Ok(logs
+            .iter()
+            .filter_map(|log| {
+                let event = EthBridgeEvent::try_from_eth_log(log)?;
+                Some(FeedItem::new("eth", format!("{:?}", log.tx_hash), &event))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl DashboardSource for RpcSource {
+    async fn starcoin_head(&self) -> anyhow::Result<ChainHead> {
+        let (height, timestamp_ms) = tokio::try_join!(
+            retry_bounded(self.retry_bound, || {
+                self.starcoin_bridge_client
+                    .get_latest_checkpoint_sequence_number()
+            }),
+            retry_bounded(self.retry_bound, || {
+                self.starcoin_bridge_client.get_block_timestamp()
+            }),
+        )?;
+        Ok(ChainHead {
+            height,
+            timestamp_ms,
+        })
+    }
+
+    async fn eth_head(&self) -> anyhow::Result<ChainHead> {
+        let provider = self.eth_client.provider();
+        let block = retry_bounded(self.retry_bound, || provider.get_block(BlockNumber::Latest))
+            .await?
+            .ok_or_else(|| anyhow!("Provider returned no latest block"))?;
+        Ok(ChainHead {
+            height: block
+                .number
+                .ok_or_else(|| anyhow!("Latest block has no number"))?
+                .as_u64(),
+            timestamp_ms: block.timestamp.as_u64() * 1000,
+        })
+    }
+
+    async fn nonces(&self) -> anyhow::Result<BTreeMap<u8, u64>> {
+        let summary = retry_bounded(self.retry_bound, || {
+            self.starcoin_bridge_client.get_bridge_summary()
+        })
+        .await?;
+        Ok(summary.sequence_nums.into_iter().collect())
+    }
+
+    async fn committee_stake(&self) -> anyhow::Result<CommitteeStake> {
+        let summary = retry_bounded(self.retry_bound, || {
+            self.starcoin_bridge_client.get_bridge_summary()
+        })
+        .await?;
+        let members = summary
+            .committee
+            .members
+            .into_iter()
+            .map(|(_, member)| member)
+            .filter(|member| !member.blocklisted)
+            .collect::<Vec<_>>();
+        let urls = members
+            .iter()
+            .map(|member| sanitize_untrusted(&member.http_rest_url, MAX_URL_BYTES))
+            .collect::<Vec<_>>();
+        let (online, versions) = tokio::join!(ping_urls(&urls), fetch_node_versions(&urls));
+        let mut stake = CommitteeStake {
+            members: members.len(),
+            versions: version_distribution(&versions),
+            ..Default::default()
+        };
+        for (member, online) in members.iter().zip(online) {
+            stake.total_stake += member.voting_power;
+            if online {
+                stake.online_members += 1;
+                stake.online_stake += member.voting_power;
+            }
+        }
+        Ok(stake)
+    }
+
+    async fn signing_queue_depth(&self) -> anyhow::Result<Option<i64>> {
+        let Some(node_url) = &self.node_url else {
+            return Ok(None);
+        };
+        let health: serde_json::Value = self
+            .http_client
+            .get(node_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        health
+            .get("signing_queue_depth")
+            .and_then(|depth| depth.as_i64())
+            .map(Some)
+            .ok_or_else(|| anyhow!("Node health report has no signing_queue_depth"))
+    }
+
+    async fn new_events(&self) -> anyhow::Result<Vec<FeedItem>> {
+        let (mut starcoin, eth) = tokio::try_join!(self.starcoin_events(), self.eth_events())?;
+        starcoin.extend(eth);
+        Ok(starcoin)
+    }
+}
+
+pub struct DashboardOptions {
+    pub refresh_interval: Duration,
+    pub committee_refresh_interval: Duration,
+    pub fetch_timeout: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct ViewState {
+    pub selected: usize,
+    pub detail_open: bool,
+}
+
+impl ViewState {
+    // Applies a key press. Returns true when the dashboard should exit.
+    pub fn handle_key(&mut self, code: KeyCode, feed_len: usize) -> bool {
+        match code {
+            KeyCode::Char('q') => return true,
+            KeyCode::Esc if !self.detail_open => return true,
+            KeyCode::Esc => self.detail_open = false,
+            KeyCode::Enter if feed_len > 0 => self.detail_open = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(feed_len.saturating_sub(1))
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+fn panel_block<T>(title: &str, panel: &Panel<T>) -> Block<'static> {
+    let color = if panel.is_failed() {
+        Color::Red
+    } else if panel.value.is_none() {
+        Color::DarkGray
+    } else {
+        Color::Green
+    };
+    Block::bordered()
+        .title(title.to_string())
+        .border_style(Style::default().fg(color))
+}
+
+fn panel_lines<T>(
+    panel: &Panel<T>,
+    lines: impl FnOnce(&T) -> Vec<Line<'static>>,
+) -> Vec<Line<'static>> {
+    let mut out = match &panel.value {
+        Some(value) => lines(value),
+        None if panel.error.is_none() => vec![Line::from("loading...")],
+        None => vec![],
+    };
+    if let Some(error) = &panel.error {
+        out.push(Line::styled(
+            format!("error: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    out
+}
+
+fn head_lines(head: &ChainHead, now_ms: u64) -> Vec<Line<'static>> {
+    vec![
+        Line::from(format!("height: {}", head.height)),
+        Line::from(format!(
+            "age: {}s",
+            now_ms.saturating_sub(head.timestamp_ms) / 1000
+        )),
+    ]
+}
+
+fn render_paragraph<T>(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    panel: &Panel<T>,
+    lines: impl FnOnce(&T) -> Vec<Line<'static>>,
+) {
+    frame.render_widget(
+        Paragraph::new(panel_lines(panel, lines)).block(panel_block(title, panel)),
+        area,
+    );
+}
+
+pub fn render(frame: &mut Frame, data: &DashboardData, view: &ViewState, now_ms: u64) {
+    let [top, middle, feed_area, help] = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Length(10),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [starcoin_area, eth_area, queue_area] =
+        Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(top);
+    let [nonces_area, committee_area] =
+        Layout::horizontal([Constraint::Ratio(1, 2); 2]).areas(middle);
+
+    render_paragraph(
+        frame,
+        starcoin_area,
+        "Starcoin head",
+        &data.starcoin_head,
+        |head| head_lines(head, now_ms),
+    );
+    render_paragraph(frame, eth_area, "Eth head", &data.eth_head, |head| {
+        head_lines(head, now_ms)
+    });
+    render_paragraph(
+        frame,
+        queue_area,
+        "Signing queue",
+        &data.signing_queue,
+        |depth| {
+            vec![Line::from(match depth {
+                Some(depth) => format!("pending: {}", depth),
+                None => "no --node-url given".to_string(),
+            })]
+        },
+    );
+    let deltas = data.nonce_deltas();
+    render_paragraph(
+        frame,
+        nonces_area,
+        "Nonces (+since start)",
+        &data.nonces,
+        |nonces| {
+            nonces
+                .iter()
+                .map(|(action_type, nonce)| {
+                    let name = BridgeActionType::try_from(*action_type)
+                        .map(|t| format!("{:?}", t))
+                        .unwrap_or_else(|_| format!("type {}", action_type));
+                    Line::from(format!(
+                        "{}: {} (+{})",
+                        name,
+                        nonce,
+                        deltas.get(action_type).unwrap_or(&0)
+                    ))
+                })
+                .collect()
+        },
+    );
+    render_paragraph(
+        frame,
+        committee_area,
+        "Committee",
+        &data.committee,
+        |stake| {
+            let mut lines = vec![
+                Line::from(format!(
+                    "online members: {}/{}",
+                    stake.online_members, stake.members
+                )),
+                Line::from(format!(
+                    "online stake: {}/{}",
+                    stake.online_stake, stake.total_stake
+                )),
+            ];
+            lines.extend(
+                stake
+                    .versions
+                    .iter()
+                    .map(|(version, count)| Line::from(format!("{}: {}", version, count))),
+            );
+            lines
+        },
+    );
+
+    let items = data
+        .feed
+        .iter()
+        .map(|item| {
+            let color = match item.kind {
+                FeedKind::Transfer => Color::Cyan,
+                FeedKind::Governance => Color::Yellow,
+            };
+            ListItem::new(format!("[{}] {} {}", item.chain, item.label, item.tx))
+                .style(Style::default().fg(color))
+        })
+        .collect::<Vec<_>>();
+    let feed = List::new(items)
+        .block(panel_block("Recent events", &data.feed_status))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut feed_state = ListState::default().with_selected(Some(view.selected));
+    frame.render_stateful_widget(feed, feed_area, &mut feed_state);
+    frame.render_widget(
+        Paragraph::new("q: quit  up/down: select  enter: open event  esc: close"),
+        help,
+    );
+
+    if view.detail_open {
+        if let Some(item) = data.feed.get(view.selected) {
+            let [_, popup, _] = Layout::vertical([
+                Constraint::Percentage(10),
+                Constraint::Percentage(80),
+                Constraint::Percentage(10),
+            ])
+            .areas(frame.area());
+            frame.render_widget(Clear, popup);
+            frame.render_widget(
+                Paragraph::new(format!("tx: {}\n\n{}", item.tx, item.detail))
+                    .wrap(Wrap { trim: false })
+                    .block(Block::bordered().title(format!("{} ({})", item.label, item.chain))),
+                popup,
+            );
+        }
+    }
+}
+
+// Runs the dashboard until the user quits. Data is refreshed by a background
+// task, so the UI stays responsive while fetches are in flight.
+pub async fn run(
+    source: Arc<dyn DashboardSource>,
+    options: DashboardOptions,
+) -> anyhow::Result<()> {
+    let (data_tx, mut data_rx) = watch::channel(DashboardData::default());
+    let refresher = tokio::spawn(async move {
+        let mut data = DashboardData::default();
+        let mut committee_refreshed_at: Option<Instant> = None;
+        loop {
+            let include_committee = match committee_refreshed_at {
+                Some(at) => at.elapsed() >= options.committee_refresh_interval,
+                None => true,
+            };
+            if include_committee {
+                committee_refreshed_at = Some(Instant::now());
+            }
+            refresh(
+                &mut data,
+                source.as_ref(),
+                options.fetch_timeout,
+                include_committee,
+            )
+            .await;
+            if data_tx.send(data.clone()).is_err() {
+                return;
+            }
+            tokio::time::sleep(options.refresh_interval).await;
+        }
+    });
+
+    // crossterm input is blocking, so it is read on its own thread
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                if key_tx.send(key.code).is_err() {
+                    return;
+                }
+            }
+            Ok(_) => {}
+            Err(_) => return,
+        }
+    });
+
+    let mut terminal = ratatui::init();
+    let mut view = ViewState::default();
+    let result = loop {
+        let data = data_rx.borrow().clone();
+        if let Err(e) = terminal.draw(|frame| render(frame, &data, &view, now_ms())) {
+            break Err(e.into());
+        }
+        tokio::select! {
+            Some(code) = key_rx.recv() => {
+                if view.handle_key(code, data.feed.len()) {
+                    break Ok(());
+                }
+            }
+            changed = data_rx.changed() => {
+                if changed.is_err() {
+                    break Ok(());
+                }
+            }
+            // Redraw so head ages keep counting between refreshes
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+        }
+    };
+    ratatui::restore();
+    refresher.abort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    #[derive(Default)]
+    struct FakeSource {
+        nonces: StdMutex<BTreeMap<u8, u64>>,
+        events: StdMutex<Vec<FeedItem>>,
+        eth_down: bool,
+        committee_hangs: bool,
+    }
+
+    #[async_trait]
+    impl DashboardSource for FakeSource {
+        async fn starcoin_head(&self) -> anyhow::Result<ChainHead> {
+            Ok(ChainHead {
+                height: 100,
+                timestamp_ms: now_ms(),
+            })
+        }
+
+        async fn eth_head(&self) -> anyhow::Result<ChainHead> {
+            if self.eth_down {
+                return Err(anyhow!("connection refused"));
+            }
+            Ok(ChainHead {
+                height: 200,
+                timestamp_ms: now_ms(),
+            })
+        }
+
+        async fn nonces(&self) -> anyhow::Result<BTreeMap<u8, u64>> {
+            Ok(self.nonces.lock().unwrap().clone())
+        }
+
+        async fn committee_stake(&self) -> anyhow::Result<CommitteeStake> {
+            if self.committee_hangs {
+                std::future::pending::<()>().await;
+            }
+            Ok(CommitteeStake {
+                members: 2,
+                online_members: 1,
+                total_stake: 10000,
+                online_stake: 6000,
+                versions: BTreeMap::from([("0.1.0".to_string(), 2)]),
+            })
+        }
+
+        async fn signing_queue_depth(&self) -> anyhow::Result<Option<i64>> {
+            Ok(Some(3))
+        }
+
+        async fn new_events(&self) -> anyhow::Result<Vec<FeedItem>> {
+            Ok(std::mem::take(&mut *self.events.lock().unwrap()))
+        }
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum TestEvent {
+        TokensDeposited { nonce: u64 },
+        EmergencyOpEvent { frozen: bool },
+    }
+
+    fn item(nonce: u64) -> FeedItem {
+        FeedItem::new(
+            "eth",
+            format!("0x{}", nonce),
+            &TestEvent::TokensDeposited { nonce },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refresh_folds_source_into_panels() {
+        let source = FakeSource::default();
+        *source.nonces.lock().unwrap() = BTreeMap::from([(0, 5), (3, 1)]);
+        *source.events.lock().unwrap() = vec![item(1), item(2)];
+        let mut data = DashboardData::default();
+        refresh(&mut data, &source, Duration::from_secs(1), true).await;
+
+        assert_eq!(data.starcoin_head.value.as_ref().unwrap().height, 100);
+        assert_eq!(data.eth_head.value.as_ref().unwrap().height, 200);
+        assert_eq!(data.signing_queue.value, Some(Some(3)));
+        assert_eq!(data.committee.value.as_ref().unwrap().online_stake, 6000);
+        assert_eq!(data.nonce_deltas(), BTreeMap::from([(0, 0), (3, 0)]));
+        // Newest first
+        assert_eq!(data.feed[0], item(2));
+        assert_eq!(data.feed_status.value, Some(2));
+
+        // Deltas count from the first refresh, the feed keeps older events
+        *source.nonces.lock().unwrap() = BTreeMap::from([(0, 7), (3, 1), (4, 2)]);
+        *source.events.lock().unwrap() = vec![item(3)];
+        refresh(&mut data, &source, Duration::from_secs(1), false).await;
+        assert_eq!(
+            data.nonce_deltas(),
+            BTreeMap::from([(0, 2), (3, 0), (4, 0)])
+        );
+        assert_eq!(data.feed.len(), 3);
+        assert_eq!(data.feed[0], item(3));
+        assert_eq!(data.feed_status.value, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_failing_and_hanging_fetches_mark_only_their_panel() {
+        let source = FakeSource {
+            eth_down: true,
+            committee_hangs: true,
+            ..Default::default()
+        };
+        let mut data = DashboardData::default();
+        let start = Instant::now();
+        refresh(&mut data, &source, Duration::from_millis(200), true).await;
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        assert!(data.eth_head.is_failed());
+        assert!(data
+            .eth_head
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("connection refused"));
+        assert!(data.committee.is_failed());
+        assert!(data.committee.error.as_ref().unwrap().contains("timed out"));
+        assert!(!data.starcoin_head.is_failed());
+        assert!(!data.nonces.is_failed());
+
+        // A failure keeps the last good value
+        let mut panel = Panel::default();
+        panel.update(Ok(1), 10);
+        panel.update(Err(anyhow!("down")), 20);
+        assert_eq!(panel.value, Some(1));
+        assert_eq!(panel.updated_at_ms, Some(10));
+        assert!(panel.is_failed());
+    }
+
+    #[test]
+    fn test_feed_item_labels() {
+        let transfer = item(1);
+        assert_eq!(transfer.label, "TokensDeposited");
+        assert_eq!(transfer.kind, FeedKind::Transfer);
+        let governance = FeedItem::new(
+            "starcoin",
+            "0x1".to_string(),
+            &TestEvent::EmergencyOpEvent { frozen: true },
+        );
+        assert_eq!(governance.label, "EmergencyOpEvent");
+        assert_eq!(governance.kind, FeedKind::Governance);
+        assert_eq!(
+            event_label("EthStarcoinBridgeEvents(TokensDepositedFilter(TokensDepositedFilter { nonce: 1 }))"),
+            "TokensDepositedFilter"
+        );
+    }
+
+    #[test]
+    fn test_view_state_keys() {
+        let mut view = ViewState::default();
+        assert!(!view.handle_key(KeyCode::Enter, 0));
+        assert!(!view.detail_open);
+        view.handle_key(KeyCode::Down, 3);
+        view.handle_key(KeyCode::Down, 3);
+        view.handle_key(KeyCode::Down, 3);
+        assert_eq!(view.selected, 2);
+        view.handle_key(KeyCode::Up, 3);
+        assert_eq!(view.selected, 1);
+        view.handle_key(KeyCode::Enter, 3);
+        assert!(view.detail_open);
+        // Esc closes the detail first, then quits
+        assert!(!view.handle_key(KeyCode::Esc, 3));
+        assert!(!view.detail_open);
+        assert!(view.handle_key(KeyCode::Esc, 3));
+        assert!(view.handle_key(KeyCode::Char('q'), 3));
+    }
+}
@@ -0,0 +1,577 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `view-eth-bridge`: the state of the bridge contracts on an EVM chain. Besides
+//! the addresses and nonces of the contracts, it reads the tokens registered in
+//! the config contract, the limits and usage of each route into the chain, the
+//! stake of the committee and whether the bridge is paused.
+//!
+//! Each section is read concurrently with its own multicall. A section that
+//! fails to read is reported in `errors` and left out, the rest of the view is
+//! still printed.
+
+use crate::committee_sync::{read_committee_members, starcoin_members, EthMember};
+use crate::transfer_budget::{from_eth_limiter_usd, ETH_LIMITER_USD_MULTIPLIER};
+use crate::{usd, ReadOnlyConfig, RouteLimit};
+use anyhow::anyhow;
+use ethers::providers::{JsonRpcClient, Middleware, Provider};
+use ethers::types::{Address as EthAddress, U256};
+use serde::Serialize;
+use starcoin_bridge::abi::EthERC20;
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge::types::BridgeActionType;
+use starcoin_bridge::utils::{get_eth_contracts, EthBridgeContracts, EthCallBatch, EthCallHandle};
+use starcoin_bridge_types::bridge::BridgeChainId;
+use std::future::Future;
+use std::sync::Arc;
+
+// Chains a route into the EVM chain can come from
+const BRIDGE_CHAINS: [BridgeChainId; 6] = [
+    BridgeChainId::StarcoinMainnet,
+    BridgeChainId::StarcoinTestnet,
+    BridgeChainId::StarcoinCustom,
+    BridgeChainId::EthMainnet,
+    BridgeChainId::EthSepolia,
+    BridgeChainId::EthCustom,
+];
+
+#[derive(Serialize, Default)]
+pub struct OutputEthBridge {
+    pub chain_id: u64,
+    pub bridge_proxy: EthAddress,
+    pub committee_proxy: EthAddress,
+    pub limiter_proxy: EthAddress,
+    pub config_proxy: EthAddress,
+    pub vault: EthAddress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonces: Option<Nonces>,
+    pub paused_tokens: Vec<u8>,
+    // Tokens registered in the config contract, by token id
+    pub tokens: Vec<EthToken>,
+    // Limit and usage over the current window of each route into the chain
+    pub limiter: Vec<RouteLimit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee: Option<EthCommitteeSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq, Eq)]
+pub struct Nonces {
+    pub token_transfer: u64,
+    pub blocklist_update: u64,
+    pub emergency_button: u64,
+    pub limit_update: u64,
+    pub asset_price_update: u64,
+    pub add_evm_tokens: u64,
+    pub token_pause: u64,
+    pub contract_upgrade_bridge: u64,
+    pub contract_upgrade_committee: u64,
+    pub contract_upgrade_limiter: u64,
+    pub contract_upgrade_config: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EthToken {
+    pub token_id: u8,
+    pub address: EthAddress,
+    // Decimals of the token's bridge units, as the config contract records them
+    pub starcoin_decimals: u8,
+    // None if the token doesn't answer `decimals()`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erc20_decimals: Option<u8>,
+    pub native: bool,
+    // USD price of one whole token, with the 8 decimals of the Eth contracts
+    pub price: u64,
+    pub price_usd: f64,
+    pub paused: bool,
+}
+
+// Stake of the committee contract. The contract can't list its members, so
+// they are the Starcoin committee members that have stake on the chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct EthCommitteeSummary {
+    pub members: usize,
+    pub total_stake: u64,
+    pub blocklisted_members: usize,
+    pub blocklisted_stake: u64,
+}
+
+impl EthCommitteeSummary {
+    pub fn new(members: impl IntoIterator<Item = EthMember>) -> Self {
+        members.into_iter().filter(|member| member.stake > 0).fold(
+            Self::default(),
+            |mut summary, member| {
+                summary.members += 1;
+                summary.total_stake += member.stake;
+                if member.blocklisted {
+                    summary.blocklisted_members += 1;
+                    summary.blocklisted_stake += member.stake;
+                }
+                summary
+            },
+        )
+    }
+}
+
+// Reads every section of the view of the contracts on chain `chain_id`. The
+// members of the committee contract are looked up with `committee_members`.
+pub async fn view_eth_bridge<P: JsonRpcClient + 'static>(
+    chain_id: u64,
+    contracts: &EthBridgeContracts<P>,
+    provider: &Arc<Provider<P>>,
+    committee_members: impl Future<Output = anyhow::Result<Vec<EthAddress>>>,
+) -> OutputEthBridge {
+    let (paused, nonces, tokens, limiter, committee) = futures::join!(
+        async { contracts.bridge.paused().call().await },
+        read_nonces(contracts, provider),
+        read_tokens(contracts, provider),
+        read_limiter(contracts, provider),
+        async {
+            let members = committee_members.await?;
+            read_committee(contracts, provider, &members).await
+        },
+    );
+
+    let mut output = OutputEthBridge {
+        chain_id,
+        bridge_proxy: contracts.bridge.address(),
+        committee_proxy: contracts.committee.address(),
+        limiter_proxy: contracts.limiter.address(),
+        config_proxy: contracts.config.address(),
+        vault: contracts.vault.address(),
+        ..Default::default()
+    };
+    let mut errors = vec![];
+    match paused {
+        Ok(paused) => output.paused = Some(paused),
+        Err(e) => errors.push(format!("Failed to read the paused state: {:?}", e)),
+    }
+    match nonces {
+        Ok(nonces) => output.nonces = Some(nonces),
+        Err(e) => errors.push(format!("Failed to read nonces: {}", e)),
+    }
+    match tokens {
+        Ok(tokens) => {
+            output.paused_tokens = tokens
+                .iter()
+                .filter(|token| token.paused)
+                .map(|token| token.token_id)
+                .collect();
+            output.tokens = tokens;
+        }
+        Err(e) => errors.push(format!("Failed to read tokens: {}", e)),
+    }
+    match limiter {
+        Ok(limiter) => output.limiter = limiter,
+        Err(e) => errors.push(format!("Failed to read the limiter: {}", e)),
+    }
+    match committee {
+        Ok(committee) => output.committee = Some(committee),
+        Err(e) => errors.push(format!("Failed to read the committee: {}", e)),
+    }
+    output.errors = (!errors.is_empty()).then_some(errors);
+    output
+}
+
+// The view of the default EVM chain of a read-only config, with the members
+// of the committee looked up on its Starcoin bridge
+pub async fn view_config_eth_bridge(
+    config: &ReadOnlyConfig,
+    starcoin_bridge_client: &StarcoinBridgeClient,
+) -> anyhow::Result<OutputEthBridge> {
+    let evm_chain = config.evm_chain(None)?;
+    let provider = evm_chain.provider();
+    let chain_id = provider.get_chainid().await?;
+    let contracts = get_eth_contracts(evm_chain.eth_bridge_proxy_address, &provider).await?;
+    Ok(view_eth_bridge(
+        chain_id.as_u64(),
+        &contracts,
+        &provider,
+        starcoin_committee_eth_addresses(starcoin_bridge_client),
+    )
+    .await)
+}
+
+// Eth addresses of the Starcoin committee members, derived from their bridge
+// public keys
+pub async fn starcoin_committee_eth_addresses(
+    starcoin_bridge_client: &StarcoinBridgeClient,
+) -> anyhow::Result<Vec<EthAddress>> {
+    let summary = starcoin_bridge_client
+        .get_bridge_summary()
+        .await
+        .map_err(|e| anyhow!("Failed to get the Starcoin committee: {:?}", e))?;
+    Ok(starcoin_members(&summary.committee)?
+        .into_iter()
+        .map(|member| member.eth_address)
+        .collect())
+}
+
+async fn read_nonces<P: JsonRpcClient + 'static>(
+    contracts: &EthBridgeContracts<P>,
+    provider: &Arc<Provider<P>>,
+) -> anyhow::Result<Nonces> {
+    let EthBridgeContracts {
+        bridge,
+        committee,
+        limiter,
+        config,
+        ..
+    } = contracts;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let message_type = BridgeActionType::EvmContractUpgrade as u8;
+    let bridge_upgrade_next_nonce = batch.add(&bridge.nonces(message_type));
+    let committee_upgrade_next_nonce = batch.add(&committee.nonces(message_type));
+    let limiter_upgrade_next_nonce = batch.add(&limiter.nonces(message_type));
+    let config_upgrade_next_nonce = batch.add(&config.nonces(message_type));
+    let token_transfer_next_nonce =
+        batch.add(&bridge.nonces(BridgeActionType::TokenTransfer as u8));
+    let blocklist_update_nonce =
+        batch.add(&committee.nonces(BridgeActionType::UpdateCommitteeBlocklist as u8));
+    let emergency_button_nonce = batch.add(&bridge.nonces(BridgeActionType::EmergencyButton as u8));
+    let limit_update_nonce = batch.add(&limiter.nonces(BridgeActionType::LimitUpdate as u8));
+    let asset_price_update_nonce =
+        batch.add(&config.nonces(BridgeActionType::AssetPriceUpdate as u8));
+    let add_tokens_nonce = batch.add(&config.nonces(BridgeActionType::AddTokensOnEvm as u8));
+    let token_pause_nonce = batch.add(&config.nonces(BridgeActionType::TokenPause as u8));
+    let results = batch.execute().await;
+    let nonce =
+        |handle: &EthCallHandle<u64>| handle.decode(&results).map_err(|e| anyhow!("{:?}", e));
+    Ok(Nonces {
+        token_transfer: nonce(&token_transfer_next_nonce)?,
+        blocklist_update: nonce(&blocklist_update_nonce)?,
+        emergency_button: nonce(&emergency_button_nonce)?,
+        limit_update: nonce(&limit_update_nonce)?,
+        asset_price_update: nonce(&asset_price_update_nonce)?,
+        add_evm_tokens: nonce(&add_tokens_nonce)?,
+        token_pause: nonce(&token_pause_nonce)?,
+        contract_upgrade_bridge: nonce(&bridge_upgrade_next_nonce)?,
+        contract_upgrade_committee: nonce(&committee_upgrade_next_nonce)?,
+        contract_upgrade_limiter: nonce(&limiter_upgrade_next_nonce)?,
+        contract_upgrade_config: nonce(&config_upgrade_next_nonce)?,
+    })
+}
+
+// The config contract can't list its tokens either, so every token id is
+// looked up, then the price, pause state and ERC20 decimals of the registered
+// ones are read in a second multicall
+async fn read_tokens<P: JsonRpcClient + 'static>(
+    contracts: &EthBridgeContracts<P>,
+    provider: &Arc<Provider<P>>,
+) -> anyhow::Result<Vec<EthToken>> {
+    let config = &contracts.config;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let supported_tokens = (0..=u8::MAX)
+        .map(|token_id| (token_id, batch.add(&config.supported_tokens(token_id))))
+        .collect::<Vec<_>>();
+    let results = batch.execute().await;
+    let mut registered = vec![];
+    for (token_id, handle) in supported_tokens {
+        let (address, starcoin_decimals, native) =
+            handle.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+        if !address.is_zero() {
+            registered.push((token_id, address, starcoin_decimals, native));
+        }
+    }
+
+    let mut batch = EthCallBatch::new(provider.clone());
+    let details = registered
+        .into_iter()
+        .map(|(token_id, address, starcoin_decimals, native)| {
+            let price = batch.add(&config.token_price_of(token_id));
+            let paused = batch.add(&config.is_token_paused(token_id));
+            let decimals = batch.add(&EthERC20::new(address, provider.clone()).decimals());
+            (
+                (token_id, address, starcoin_decimals, native),
+                (price, paused, decimals),
+            )
+        })
+        .collect::<Vec<_>>();
+    let results = batch.execute().await;
+    details
+        .into_iter()
+        .map(
+            |((token_id, address, starcoin_decimals, native), (price, paused, decimals))| {
+                let read_error = |e| anyhow!("Token {token_id}: {:?}", e);
+                let price = price.decode(&results).map_err(read_error)?;
+                Ok(EthToken {
+                    token_id,
+                    address,
+                    starcoin_decimals,
+                    erc20_decimals: decimals.decode(&results).ok(),
+                    native,
+                    price,
+                    price_usd: price as f64 / ETH_LIMITER_USD_MULTIPLIER as f64,
+                    paused: paused.decode(&results).map_err(read_error)?,
+                })
+            },
+        )
+        .collect()
+}
+
+// Routes into the chain from every chain the config contract supports. USD
+// amounts are converted to units of 1 / USD_MULTIPLIER, limits rounded down
+// and usage up, as for the deposit checks.
+async fn read_limiter<P: JsonRpcClient + 'static>(
+    contracts: &EthBridgeContracts<P>,
+    provider: &Arc<Provider<P>>,
+) -> anyhow::Result<Vec<RouteLimit>> {
+    let EthBridgeContracts {
+        limiter, config, ..
+    } = contracts;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let chain_id = batch.add(&config.chain_id());
+    let routes = BRIDGE_CHAINS
+        .into_iter()
+        .map(|chain| {
+            (
+                chain,
+                batch.add(&config.is_chain_supported(chain as u8)),
+                batch.add(&limiter.chain_limits(chain as u8)),
+                batch.add(&limiter.calculate_window_amount(chain as u8)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let results = batch.execute().await;
+    let chain_id = chain_id.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let receiving_chain = BridgeChainId::try_from(chain_id)
+        .map_err(|_| anyhow!("Unknown bridge chain id {chain_id} in the config contract"))?;
+    let mut route_limits = vec![];
+    for (sending_chain, supported, limit, window_amount) in routes {
+        let read_error = |e| anyhow!("Route from {:?}: {:?}", sending_chain, e);
+        if sending_chain == receiving_chain || !supported.decode(&results).map_err(read_error)? {
+            continue;
+        }
+        let limit: u64 = limit.decode(&results).map_err(read_error)?;
+        let window_amount: U256 = window_amount.decode(&results).map_err(read_error)?;
+        let limit = from_eth_limiter_usd(U256::from(limit), false);
+        let used = from_eth_limiter_usd(window_amount, true);
+        let remaining = limit.saturating_sub(used);
+        route_limits.push(RouteLimit {
+            sending_chain,
+            receiving_chain,
+            limit,
+            limit_usd: usd(limit),
+            used,
+            used_usd: usd(used),
+            remaining,
+            remaining_usd: usd(remaining),
+        });
+    }
+    Ok(route_limits)
+}
+
+async fn read_committee<P: JsonRpcClient + 'static>(
+    contracts: &EthBridgeContracts<P>,
+    provider: &Arc<Provider<P>>,
+    addresses: &[EthAddress],
+) -> anyhow::Result<EthCommitteeSummary> {
+    let members =
+        read_committee_members(contracts.committee.address(), provider.clone(), addresses).await?;
+    Ok(EthCommitteeSummary::new(members.into_values()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::Token;
+    use ethers::providers::MockProvider;
+    use ethers::types::Bytes;
+    use starcoin_bridge::abi::{
+        EthBridgeCommittee, EthBridgeConfig, EthBridgeLimiter, EthBridgeVault, EthStarcoinBridge,
+    };
+    use starcoin_bridge::types::USD_MULTIPLIER;
+
+    fn contracts() -> (
+        EthBridgeContracts<MockProvider>,
+        Arc<Provider<MockProvider>>,
+        MockProvider,
+    ) {
+        let (provider, mock) = Provider::mocked();
+        let provider = Arc::new(provider);
+        let contracts = EthBridgeContracts {
+            bridge: EthStarcoinBridge::new(EthAddress::repeat_byte(1), provider.clone()),
+            committee: EthBridgeCommittee::new(EthAddress::repeat_byte(2), provider.clone()),
+            limiter: EthBridgeLimiter::new(EthAddress::repeat_byte(3), provider.clone()),
+            vault: EthBridgeVault::new(EthAddress::repeat_byte(4), provider.clone()),
+            config: EthBridgeConfig::new(EthAddress::repeat_byte(5), provider.clone()),
+        };
+        (contracts, provider, mock)
+    }
+
+    // The output of a Multicall3 `aggregate3`, with the result of each call in
+    // order. None is a call that reverted.
+    fn aggregate3_output(results: Vec<Option<Vec<Token>>>) -> Bytes {
+        let results = results
+            .into_iter()
+            .map(|output| {
+                Token::Tuple(vec![
+                    Token::Bool(output.is_some()),
+                    Token::Bytes(
+                        output
+                            .map(|tokens| ethers::abi::encode(&tokens))
+                            .unwrap_or_default(),
+                    ),
+                ])
+            })
+            .collect();
+        ethers::abi::encode(&[Token::Array(results)]).into()
+    }
+
+    fn uint(value: u64) -> Option<Vec<Token>> {
+        Some(vec![Token::Uint(value.into())])
+    }
+
+    #[tokio::test]
+    async fn test_read_tokens() {
+        let (contracts, provider, mock) = contracts();
+        let usdc = EthAddress::repeat_byte(0xc);
+        let weth = EthAddress::repeat_byte(0xe);
+        let supported_tokens = (0..=u8::MAX)
+            .map(|token_id| {
+                let (address, decimals) = match token_id {
+                    2 => (weth, 8),
+                    3 => (usdc, 6),
+                    _ => (EthAddress::zero(), 0),
+                };
+                Some(vec![
+                    Token::Address(address),
+                    Token::Uint(decimals.into()),
+                    Token::Bool(false),
+                ])
+            })
+            .collect();
+        // Responses are served last in, first out
+        mock.push::<Bytes, _>(aggregate3_output(vec![
+            uint(2_500 * ETH_LIMITER_USD_MULTIPLIER),
+            Some(vec![Token::Bool(false)]),
+            uint(18),
+            uint(ETH_LIMITER_USD_MULTIPLIER),
+            Some(vec![Token::Bool(true)]),
+            // USDC doesn't answer `decimals()`
+            None,
+        ]))
+        .unwrap();
+        mock.push::<Bytes, _>(aggregate3_output(supported_tokens))
+            .unwrap();
+
+        let tokens = read_tokens(&contracts, &provider).await.unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                EthToken {
+                    token_id: 2,
+                    address: weth,
+                    starcoin_decimals: 8,
+                    erc20_decimals: Some(18),
+                    native: false,
+                    price: 2_500 * ETH_LIMITER_USD_MULTIPLIER,
+                    price_usd: 2_500.0,
+                    paused: false,
+                },
+                EthToken {
+                    token_id: 3,
+                    address: usdc,
+                    starcoin_decimals: 6,
+                    erc20_decimals: None,
+                    native: false,
+                    price: ETH_LIMITER_USD_MULTIPLIER,
+                    price_usd: 1.0,
+                    paused: true,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_limiter() {
+        let (contracts, provider, mock) = contracts();
+        // The chain itself, then (supported, limit, window amount) of each chain
+        let mut results = vec![uint(BridgeChainId::EthSepolia as u64)];
+        for chain in BRIDGE_CHAINS {
+            let supported = matches!(
+                chain,
+                BridgeChainId::StarcoinTestnet | BridgeChainId::EthSepolia
+            );
+            results.extend([
+                Some(vec![Token::Bool(supported)]),
+                uint(1_000 * ETH_LIMITER_USD_MULTIPLIER),
+                // $250 and a fraction of the smallest USD_MULTIPLIER unit
+                uint(250 * ETH_LIMITER_USD_MULTIPLIER + 1),
+            ]);
+        }
+        mock.push::<Bytes, _>(aggregate3_output(results)).unwrap();
+
+        let limits = read_limiter(&contracts, &provider).await.unwrap();
+        assert_eq!(
+            limits,
+            vec![RouteLimit {
+                sending_chain: BridgeChainId::StarcoinTestnet,
+                receiving_chain: BridgeChainId::EthSepolia,
+                limit: 1_000 * USD_MULTIPLIER,
+                limit_usd: 1_000.0,
+                used: 250 * USD_MULTIPLIER + 1,
+                used_usd: usd(250 * USD_MULTIPLIER + 1),
+                remaining: 750 * USD_MULTIPLIER - 1,
+                remaining_usd: usd(750 * USD_MULTIPLIER - 1),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_committee() {
+        let (contracts, provider, mock) = contracts();
+        let addresses = [1, 2, 3, 4].map(EthAddress::repeat_byte);
+        // (stake, blocklisted) of each address. The last one is not a member.
+        mock.push::<Bytes, _>(aggregate3_output(
+            [(5_000, false), (3_000, true), (2_000, false), (0, false)]
+                .into_iter()
+                .flat_map(|(stake, blocklisted)| {
+                    [uint(stake), Some(vec![Token::Bool(blocklisted)])]
+                })
+                .collect(),
+        ))
+        .unwrap();
+
+        let committee = read_committee(&contracts, &provider, &addresses)
+            .await
+            .unwrap();
+        assert_eq!(
+            committee,
+            EthCommitteeSummary {
+                members: 3,
+                total_stake: 10_000,
+                blocklisted_members: 1,
+                blocklisted_stake: 3_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_view_reports_failed_sections() {
+        // Nothing answers, so every section fails on its own
+        let (contracts, provider, _mock) = contracts();
+        let output = view_eth_bridge(31337, &contracts, &provider, async {
+            Err(anyhow!("No Starcoin RPC"))
+        })
+        .await;
+
+        assert_eq!(output.chain_id, 31337);
+        assert_eq!(output.config_proxy, EthAddress::repeat_byte(5));
+        assert!(output.paused.is_none());
+        assert!(output.nonces.is_none());
+        assert!(output.tokens.is_empty());
+        let errors = output.errors.clone().unwrap();
+        assert_eq!(errors.len(), 5, "{errors:?}");
+        assert!(errors
+            .iter()
+            .any(|e| e == "Failed to read the committee: No Starcoin RPC"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert!(json.get("nonces").is_none());
+        assert_eq!(json["errors"].as_array().unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks `client claim-on-eth` runs before it submits anything, so that a
+//! transfer that is already claimed, or whose signatures the Eth committee
+//! no longer accepts, doesn't cost a reverted transaction.
+//!
+//! The checks go through `EthClaimSource` so they can be tested without a
+//! chain.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::{Bytes, TxHash, H256};
+use starcoin_bridge::abi::{
+    eth_bridge_committee, eth_starcoin_bridge, EthBridgeCommittee, EthStarcoinBridge,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+// How far back from the latest block the claim of an already claimed
+// transfer is looked for. Providers reject log queries over unbounded ranges.
+const CLAIM_LOG_LOOKBACK_BLOCKS: u64 = 50_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EthClaimCheck {
+    // Nothing stops the claim
+    Claimable,
+    // With the claiming transaction, if it was found in the recent logs
+    AlreadyClaimed(Option<TxHash>),
+    // The signatures collected on Starcoin no longer hold enough stake in the
+    // Eth committee, with the reason the committee gave
+    InsufficientStake(String),
+}
+
+// The Eth side of a claim. Implemented over RPC by `RpcEthClaimSource`.
+#[async_trait]
+pub trait EthClaimSource: Send + Sync {
+    async fn is_transfer_processed(&self, seq_num: u64) -> anyhow::Result<bool>;
+    // The transaction that emitted `TokensClaimed` for the transfer
+    async fn claim_tx(&self, source_chain_id: u8, seq_num: u64) -> anyhow::Result<Option<TxHash>>;
+    // None if the committee accepts the signatures, the revert reason if not
+    async fn verify_signatures(
+        &self,
+        signatures: &[Bytes],
+        message: &eth_starcoin_bridge::Message,
+    ) -> anyhow::Result<Option<String>>;
+}
+
+pub async fn check_eth_claim(
+    source: &dyn EthClaimSource,
+    source_chain_id: u8,
+    seq_num: u64,
+    signatures: &[Bytes],
+    message: &eth_starcoin_bridge::Message,
+) -> anyhow::Result<EthClaimCheck> {
+    if source.is_transfer_processed(seq_num).await? {
+        // The claim is done either way, the hash is only informational
+        let claim_tx = source
+            .claim_tx(source_chain_id, seq_num)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to look up the claim of transfer {seq_num}: {:?}", e);
+                None
+            });
+        return Ok(EthClaimCheck::AlreadyClaimed(claim_tx));
+    }
+    Ok(match source.verify_signatures(signatures, message).await? {
+        None => EthClaimCheck::Claimable,
+        Some(reason) => EthClaimCheck::InsufficientStake(reason),
+    })
+}
+
+pub struct RpcEthClaimSource {
+    pub provider: Arc<Provider<Http>>,
+    pub bridge: EthStarcoinBridge<Provider<Http>>,
+    pub committee: EthBridgeCommittee<Provider<Http>>,
+}
+
+#[async_trait]
+impl EthClaimSource for RpcEthClaimSource {
+    async fn is_transfer_processed(&self, seq_num: u64) -> anyhow::Result<bool> {
+        Ok(self.bridge.is_transfer_processed(seq_num).call().await?)
+    }
+
+    async fn claim_tx(&self, source_chain_id: u8, seq_num: u64) -> anyhow::Result<Option<TxHash>> {
+        let latest = self.provider.get_block_number().await?.as_u64();
+        let claims = self
+            .bridge
+            .tokens_claimed_filter()
+            .topic1(H256::from_low_u64_be(source_chain_id as u64))
+            .topic2(H256::from_low_u64_be(seq_num))
+            .from_block(latest.saturating_sub(CLAIM_LOG_LOOKBACK_BLOCKS))
+            .query_with_meta()
+            .await?;
+        Ok(claims.first().map(|(_, meta)| meta.transaction_hash))
+    }
+
+    async fn verify_signatures(
+        &self,
+        signatures: &[Bytes],
+        message: &eth_starcoin_bridge::Message,
+    ) -> anyhow::Result<Option<String>> {
+        let message = eth_bridge_committee::Message {
+            message_type: message.message_type,
+            version: message.version,
+            nonce: message.nonce,
+            chain_id: message.chain_id,
+            payload: message.payload.clone(),
+        };
+        match self
+            .committee
+            .verify_signatures(signatures.to_vec(), message)
+            .call()
+            .await
+        {
+            Ok(()) => Ok(None),
+            // Only a revert says anything about the signatures
+            Err(e) if e.is_revert() => {
+                Ok(Some(e.decode_revert::<String>().unwrap_or_else(|| {
+                    "reverted without a reason".to_string()
+                })))
+            }
+            Err(e) => Err(anyhow!("Failed to verify signatures on Eth: {:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeSource {
+        processed: bool,
+        claim_tx: Option<TxHash>,
+        rejection: Option<String>,
+        verified: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl EthClaimSource for FakeSource {
+        async fn is_transfer_processed(&self, _seq_num: u64) -> anyhow::Result<bool> {
+            Ok(self.processed)
+        }
+
+        async fn claim_tx(
+            &self,
+            _source_chain_id: u8,
+            _seq_num: u64,
+        ) -> anyhow::Result<Option<TxHash>> {
+            self.claim_tx
+                .map(Some)
+                .ok_or_else(|| anyhow!("log query range too large"))
+        }
+
+        async fn verify_signatures(
+            &self,
+            _signatures: &[Bytes],
+            _message: &eth_starcoin_bridge::Message,
+        ) -> anyhow::Result<Option<String>> {
+            *self.verified.lock().unwrap() = true;
+            Ok(self.rejection.clone())
+        }
+    }
+
+    fn message() -> eth_starcoin_bridge::Message {
+        eth_starcoin_bridge::Message {
+            message_type: 0,
+            version: 1,
+            nonce: 7,
+            chain_id: 2,
+            payload: Bytes::from(vec![1, 2, 3]),
+        }
+    }
+
+    async fn check(source: &FakeSource) -> EthClaimCheck {
+        check_eth_claim(source, 2, 7, &[Bytes::from(vec![0; 65])], &message())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_already_claimed() {
+        let claim_tx = TxHash::repeat_byte(0xab);
+        let source = FakeSource {
+            processed: true,
+            claim_tx: Some(claim_tx),
+            ..Default::default()
+        };
+        assert_eq!(
+            check(&source).await,
+            EthClaimCheck::AlreadyClaimed(Some(claim_tx))
+        );
+        // Signatures don't matter once the transfer is claimed
+        assert!(!*source.verified.lock().unwrap());
+
+        // A failed log lookup doesn't fail the check
+        let source = FakeSource {
+            processed: true,
+            ..Default::default()
+        };
+        assert_eq!(check(&source).await, EthClaimCheck::AlreadyClaimed(None));
+    }
+
+    #[tokio::test]
+    async fn test_committee_rotated() {
+        let source = FakeSource {
+            rejection: Some("BridgeCommittee: Insufficient stake amount".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            check(&source).await,
+            EthClaimCheck::InsufficientStake(
+                "BridgeCommittee: Insufficient stake amount".to_string()
+            )
+        );
+
+        let source = FakeSource::default();
+        assert_eq!(check(&source).await, EthClaimCheck::Claimable);
+        assert!(*source.verified.lock().unwrap());
+    }
+}
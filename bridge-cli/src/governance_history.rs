@@ -0,0 +1,201 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Governance actions executed on chain, read from the `governance_actions`
+// table that the bridge indexer populates.
+
+use crate::output::{GovernanceHistoryOutput, GovernanceHistoryRow};
+use anyhow::{anyhow, Context};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use starcoin_bridge::tx_digest::{tagged, TxChain};
+use starcoin_bridge::types::BridgeActionType;
+use starcoin_bridge_pg_db::{Db, DbArgs};
+use starcoin_bridge_schema::models::{BridgeDataSource, GovernanceAction, GovernanceActionType};
+use starcoin_bridge_schema::schema::governance_actions;
+use starcoin_bridge_types::bridge::BridgeChainId;
+use url::Url;
+
+// The CLI runs its queries one at a time, so it doesn't need to wait long
+// for the database or keep more than one connection
+const DB_CONNECTION_TIMEOUT_MS: u64 = 10_000;
+
+#[derive(Clone, Debug, Default)]
+pub struct GovernanceHistoryFilter {
+    pub action_type: Option<BridgeActionType>,
+    // Inclusive nonce range. Rows without a nonce never match a range.
+    pub nonce_from: Option<u64>,
+    pub nonce_to: Option<u64>,
+    pub limit: u64,
+}
+
+// Accepts the action type name, in any case and with or without dashes, or
+// its numeric value, e.g. `emergency-button`, `EmergencyButton` or `2`.
+pub fn parse_governance_action_type(s: &str) -> Result<BridgeActionType, String> {
+    if let Ok(value) = s.parse::<u8>() {
+        return BridgeActionType::try_from(value).map_err(|e| e.to_string());
+    }
+    let normalized = s.replace(['-', '_'], "").to_lowercase();
+    (0..=u8::MAX)
+        .map_while(|value| BridgeActionType::try_from(value).ok())
+        .find(|action_type| action_type.to_string().to_lowercase() == normalized)
+        .ok_or_else(|| format!("Unknown action type: {s}"))
+}
+
+// The indexer's name for an action type. The indexer records actions from
+// the events they emit, so token transfers and pauses, which emit no
+// governance event, can't be queried.
+fn indexer_action_type(action_type: BridgeActionType) -> anyhow::Result<GovernanceActionType> {
+    Ok(match action_type {
+        BridgeActionType::UpdateCommitteeBlocklist => {
+            GovernanceActionType::UpdateCommitteeBlocklist
+        }
+        BridgeActionType::EmergencyButton => GovernanceActionType::EmergencyOperation,
+        BridgeActionType::LimitUpdate => GovernanceActionType::UpdateBridgeLimit,
+        BridgeActionType::AssetPriceUpdate => GovernanceActionType::UpdateTokenPrices,
+        BridgeActionType::EvmContractUpgrade => GovernanceActionType::UpgradeEVMContract,
+        BridgeActionType::AddTokensOnstarcoin => GovernanceActionType::AddStarcoinTokens,
+        BridgeActionType::AddTokensOnEvm => GovernanceActionType::AddEVMTokens,
+        BridgeActionType::TokenTransfer | BridgeActionType::TokenPause => {
+            return Err(anyhow!(
+                "{action_type} actions are not recorded by the bridge indexer"
+            ))
+        }
+    })
+}
+
+fn data_source(chain_id: BridgeChainId) -> BridgeDataSource {
+    if chain_id.is_starcoin_bridge_chain() {
+        BridgeDataSource::STARCOIN
+    } else {
+        BridgeDataSource::ETH
+    }
+}
+
+fn history_row(action: GovernanceAction) -> GovernanceHistoryRow {
+    let chain = match action.data_source {
+        BridgeDataSource::STARCOIN => TxChain::Starcoin,
+        BridgeDataSource::ETH => TxChain::Eth,
+    };
+    GovernanceHistoryRow {
+        nonce: action.nonce,
+        action_type: action.action.as_ref().to_string(),
+        tx_hash: tagged(chain, &format!("0x{}", hex::encode(&action.txn_digest))),
+        block_height: action.block_height,
+        timestamp_ms: action.timestamp_ms,
+        sender: format!("0x{}", hex::encode(&action.sender_address)),
+        payload: action.data,
+    }
+}
+
+// A read-only pool of one connection to the indexer database
+pub async fn connect_indexer_db(database_url: &str) -> anyhow::Result<Db> {
+    let url = Url::parse(database_url).context("Invalid indexer database url")?;
+    // Never echo the password of the url
+    let mut display_url = url.clone();
+    let _ = display_url.set_password(None);
+    Db::for_read(
+        url,
+        DbArgs {
+            db_connection_pool_size: 1,
+            db_connection_timeout_ms: DB_CONNECTION_TIMEOUT_MS,
+            ..Default::default()
+        },
+    )
+    .await
+    .with_context(|| format!("Indexer database at {display_url} is unreachable"))
+}
+
+// Governance actions of `chain_id` matching `filter`, newest first
+pub async fn query_governance_history(
+    database_url: &str,
+    chain_id: u8,
+    filter: &GovernanceHistoryFilter,
+) -> anyhow::Result<GovernanceHistoryOutput> {
+    let chain = BridgeChainId::try_from(chain_id)
+        .map_err(|_| anyhow!("Unknown bridge chain id: {chain_id}"))?;
+    let action = filter.action_type.map(indexer_action_type).transpose()?;
+    let db = connect_indexer_db(database_url).await?;
+    let mut conn = db
+        .connect()
+        .await
+        .context("Indexer database is unreachable")?;
+
+    let mut query = governance_actions::table
+        .filter(governance_actions::data_source.eq(data_source(chain)))
+        .into_boxed();
+    if let Some(action) = action {
+        query = query.filter(governance_actions::action.eq(action));
+    }
+    if let Some(nonce_from) = filter.nonce_from {
+        query = query.filter(governance_actions::nonce.ge(nonce_from as i64));
+    }
+    if let Some(nonce_to) = filter.nonce_to {
+        query = query.filter(governance_actions::nonce.le(nonce_to as i64));
+    }
+    let actions: Vec<GovernanceAction> = query
+        .order((
+            governance_actions::timestamp_ms.desc(),
+            governance_actions::id.desc(),
+        ))
+        .limit(filter.limit as i64)
+        .select(GovernanceAction::as_select())
+        .load(&mut conn)
+        .await
+        .context("Failed to query the governance_actions table")?;
+
+    Ok(GovernanceHistoryOutput {
+        chain_id,
+        actions: actions.into_iter().map(history_row).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_governance_action_type() {
+        for s in [
+            "emergency-button",
+            "EmergencyButton",
+            "emergency_button",
+            "2",
+        ] {
+            assert!(parse_governance_action_type(s).unwrap() == BridgeActionType::EmergencyButton);
+        }
+        assert!(
+            parse_governance_action_type("add-tokens-on-evm").unwrap()
+                == BridgeActionType::AddTokensOnEvm
+        );
+        assert!(parse_governance_action_type("unknown").is_err());
+        assert!(parse_governance_action_type("200").is_err());
+    }
+
+    #[test]
+    fn test_indexer_action_type() {
+        assert!(matches!(
+            indexer_action_type(BridgeActionType::LimitUpdate).unwrap(),
+            GovernanceActionType::UpdateBridgeLimit
+        ));
+        assert!(indexer_action_type(BridgeActionType::TokenTransfer).is_err());
+    }
+
+    #[test]
+    fn test_history_row() {
+        let row = history_row(GovernanceAction {
+            nonce: Some(4),
+            data_source: BridgeDataSource::STARCOIN,
+            txn_digest: vec![1u8; 32],
+            sender_address: vec![2u8; 16],
+            timestamp_ms: 1000,
+            action: GovernanceActionType::EmergencyOperation,
+            data: serde_json::json!({"frozen": true}),
+            block_height: Some(42),
+        });
+        assert_eq!(row.action_type, "EmergencyOperation");
+        assert_eq!(row.tx_hash, format!("stc:0x{}", hex::encode([1u8; 32])));
+        assert_eq!(row.sender, format!("0x{}", hex::encode([2u8; 16])));
+        assert_eq!(row.block_height, Some(42));
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of the hex byte strings, addresses and object ids the CLI takes.
+//! The functions return `Result<_, String>` so they can be used as clap
+//! value parsers directly. A `0x` prefix is always optional.
+
+use starcoin_bridge_types::base_types::{ObjectID, StarcoinAddress};
+
+const STARCOIN_ADDRESS_LENGTH: usize = 16;
+
+fn strip_0x(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let hex = strip_0x(s);
+    if hex.is_empty() {
+        return Err("expected hex, got an empty string".to_string());
+    }
+    hex::decode(hex).map_err(|e| format!("invalid hex {:?}: {}", s.trim(), e))
+}
+
+pub fn parse_hex_bytes(s: &str, len: usize) -> Result<Vec<u8>, String> {
+    let bytes = decode_hex(s)?;
+    if bytes.len() != len {
+        return Err(format!("expected {} bytes, got {}", len, bytes.len()));
+    }
+    Ok(bytes)
+}
+
+pub fn parse_hex_bytes32(s: &str) -> Result<[u8; 32], String> {
+    let bytes = parse_hex_bytes(s, 32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+// Short forms such as 0x1 are left padded with zeros, as Move does
+pub fn parse_starcoin_address(s: &str) -> Result<StarcoinAddress, String> {
+    let hex = strip_0x(s);
+    if hex.len() > STARCOIN_ADDRESS_LENGTH * 2 {
+        return Err(format!(
+            "Starcoin addresses are {} bytes, got {}",
+            STARCOIN_ADDRESS_LENGTH,
+            hex.len().div_ceil(2)
+        ));
+    }
+    if hex.is_empty() {
+        return Err("expected a Starcoin address, got an empty string".to_string());
+    }
+    let bytes = decode_hex(&format!("{:0>32}", hex))
+        .map_err(|_| format!("invalid Starcoin address {:?}", s.trim()))?;
+    let mut address = [0u8; STARCOIN_ADDRESS_LENGTH];
+    address.copy_from_slice(&bytes);
+    Ok(StarcoinAddress::new(address))
+}
+
+// Takes full 32 byte ids, and Starcoin addresses left padded to 32 bytes the
+// way the bridge package id is
+pub fn parse_object_id(s: &str) -> Result<ObjectID, String> {
+    if strip_0x(s).len() > STARCOIN_ADDRESS_LENGTH * 2 {
+        return parse_hex_bytes32(s);
+    }
+    let address = parse_starcoin_address(s)?;
+    let mut id = [0u8; 32];
+    id[32 - STARCOIN_ADDRESS_LENGTH..].copy_from_slice(address.as_ref());
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("0x0102").unwrap(), vec![1, 2]);
+        assert_eq!(decode_hex("0102").unwrap(), vec![1, 2]);
+        assert_eq!(decode_hex(" 0XAB ").unwrap(), vec![0xab]);
+        assert!(decode_hex("0x").unwrap_err().contains("empty"));
+        assert!(decode_hex("0x123").is_err());
+        assert!(decode_hex("0xzz").unwrap_err().contains("invalid hex"));
+    }
+
+    #[test]
+    fn test_parse_hex_bytes32() {
+        let hex = "ab".repeat(32);
+        assert_eq!(parse_hex_bytes32(&hex).unwrap(), [0xab; 32]);
+        assert_eq!(parse_hex_bytes32(&format!("0x{hex}")).unwrap(), [0xab; 32]);
+        assert_eq!(
+            parse_hex_bytes32(&"ab".repeat(20)).unwrap_err(),
+            "expected 32 bytes, got 20"
+        );
+        assert_eq!(
+            parse_hex_bytes32(&"ab".repeat(33)).unwrap_err(),
+            "expected 32 bytes, got 33"
+        );
+    }
+
+    #[test]
+    fn test_parse_starcoin_address() {
+        let full = "0x0000000000000000000000000000000a";
+        let address = parse_starcoin_address(full).unwrap();
+        assert_eq!(parse_starcoin_address("0xa").unwrap(), address);
+        assert_eq!(parse_starcoin_address("a").unwrap(), address);
+        assert_eq!(parse_starcoin_address("0x0a").unwrap(), address);
+        assert_eq!(parse_starcoin_address(&full[2..]).unwrap(), address);
+
+        assert_eq!(
+            parse_starcoin_address(&format!("0x{}", "ab".repeat(20))).unwrap_err(),
+            "Starcoin addresses are 16 bytes, got 20"
+        );
+        assert!(parse_starcoin_address("0x").is_err());
+        assert!(parse_starcoin_address("0xnot-hex").is_err());
+    }
+
+    #[test]
+    fn test_parse_object_id() {
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        assert_eq!(parse_object_id("0x1").unwrap(), expected);
+        assert_eq!(
+            parse_object_id(&format!("0x{}", "00".repeat(31) + "01")).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_object_id(&"ab".repeat(20)).unwrap_err(),
+            "expected 32 bytes, got 20"
+        );
+    }
+}
@@ -0,0 +1,702 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `bridge-cli audit-indexer`: reconciles the bridge indexer database with
+//! the chains. For each route it lists the deposit nonces the indexer never
+//! recorded, and compares the status the indexer recorded for the most recent
+//! transfers with their status on chain. The report carries the nonces and
+//! statuses needed to drive a backfill.
+//!
+//! The chains are read through `AuditSource`, so the audit can be tested
+//! against a seeded database and the mock client.
+
+use crate::governance_history::connect_indexer_db;
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use ethers::providers::{Http, Provider};
+use serde::Serialize;
+use starcoin_bridge::abi::EthStarcoinBridge;
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use starcoin_bridge::types::{BridgeActionStatus, BridgeActionType};
+use starcoin_bridge_schema::models::TokenTransferStatus;
+use starcoin_bridge_schema::schema::{token_transfer, token_transfer_data};
+use std::collections::{BTreeMap, HashMap};
+
+// The chain reads of the audit. Implemented over RPC by `RpcAuditSource`.
+#[async_trait]
+pub trait AuditSource: Send + Sync {
+    // Chain id of Starcoin, and the nonce of its next token transfer
+    async fn starcoin_next_transfer_nonce(&self) -> anyhow::Result<(u8, u64)>;
+    // Nonce of the next token transfer from the EVM chain
+    async fn eth_next_transfer_nonce(&self) -> anyhow::Result<u64>;
+    // Status recorded on Starcoin for transfer `nonce` from `source_chain_id`
+    async fn starcoin_transfer_status(
+        &self,
+        source_chain_id: u8,
+        nonce: u64,
+    ) -> anyhow::Result<BridgeActionStatus>;
+    // Whether the EVM chain claimed transfer `nonce` from Starcoin
+    async fn eth_transfer_processed(&self, nonce: u64) -> anyhow::Result<bool>;
+}
+
+pub struct RpcAuditSource<P: StarcoinClientInner> {
+    pub starcoin_bridge_client: StarcoinClient<P>,
+    pub bridge: EthStarcoinBridge<Provider<Http>>,
+    pub deadline: Deadline,
+}
+
+#[async_trait]
+impl<P: StarcoinClientInner> AuditSource for RpcAuditSource<P> {
+    async fn starcoin_next_transfer_nonce(&self) -> anyhow::Result<(u8, u64)> {
+        starcoin_next_transfer_nonce(&self.starcoin_bridge_client, self.deadline).await
+    }
+
+    async fn eth_next_transfer_nonce(&self) -> anyhow::Result<u64> {
+        self.bridge
+            .nonces(BridgeActionType::TokenTransfer as u8)
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to read the token transfer nonce on Eth: {:?}", e))
+    }
+
+    async fn starcoin_transfer_status(
+        &self,
+        source_chain_id: u8,
+        nonce: u64,
+    ) -> anyhow::Result<BridgeActionStatus> {
+        starcoin_transfer_status(
+            &self.starcoin_bridge_client,
+            source_chain_id,
+            nonce,
+            self.deadline,
+        )
+        .await
+    }
+
+    async fn eth_transfer_processed(&self, nonce: u64) -> anyhow::Result<bool> {
+        self.bridge
+            .is_transfer_processed(nonce)
+            .call()
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to read the status of transfer {nonce} on Eth: {:?}",
+                    e
+                )
+            })
+    }
+}
+
+async fn starcoin_next_transfer_nonce<P: StarcoinClientInner>(
+    client: &StarcoinClient<P>,
+    deadline: Deadline,
+) -> anyhow::Result<(u8, u64)> {
+    let summary = deadline
+        .run("starcoin bridge summary", client.get_bridge_summary())
+        .await
+        .map_err(|e| anyhow!("Failed to read the Starcoin bridge summary: {:?}", e))?;
+    // A message type that was never used has no sequence number yet
+    let next_nonce = summary
+        .sequence_nums
+        .iter()
+        .find(|(type_, _)| *type_ == BridgeActionType::TokenTransfer as u8)
+        .map_or(0, |(_, nonce)| *nonce);
+    Ok((summary.chain_id, next_nonce))
+}
+
+async fn starcoin_transfer_status<P: StarcoinClientInner>(
+    client: &StarcoinClient<P>,
+    source_chain_id: u8,
+    nonce: u64,
+    deadline: Deadline,
+) -> anyhow::Result<BridgeActionStatus> {
+    client
+        .get_token_transfer_action_onchain_status(source_chain_id, nonce, deadline)
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Failed to read the status of transfer {nonce} from chain {source_chain_id}: {:?}",
+                e
+            )
+        })
+}
+
+// How far a transfer got. Ordered, so the furthest of the indexer's records
+// of a transfer is its state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransferState {
+    Missing,
+    Deposited,
+    Approved,
+    Claimed,
+}
+
+impl From<TokenTransferStatus> for TransferState {
+    fn from(status: TokenTransferStatus) -> Self {
+        match status {
+            TokenTransferStatus::Deposited => TransferState::Deposited,
+            TokenTransferStatus::Approved => TransferState::Approved,
+            TokenTransferStatus::Claimed => TransferState::Claimed,
+        }
+    }
+}
+
+// Inclusive range of nonces
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NonceRange {
+    pub from: u64,
+    pub to: u64,
+}
+
+impl NonceRange {
+    pub fn count(&self) -> u64 {
+        self.to - self.from + 1
+    }
+}
+
+// Transfers that reached approval, and those that were claimed. A claimed
+// transfer counts as approved too.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateCounts {
+    pub approved: u64,
+    pub claimed: u64,
+}
+
+impl StateCounts {
+    fn add(&mut self, state: TransferState) {
+        if state >= TransferState::Approved {
+            self.approved += 1;
+        }
+        if state == TransferState::Claimed {
+            self.claimed += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateMismatch {
+    pub nonce: u64,
+    // The state on chain
+    pub expected_status: TransferState,
+    // The state the indexer recorded
+    pub found_status: TransferState,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RouteAudit {
+    pub source_chain_id: u8,
+    pub destination_chain_id: u8,
+    pub onchain_next_nonce: u64,
+    // Highest deposit nonce of the route the indexer recorded
+    pub indexed_max_nonce: Option<u64>,
+    // Deposits on chain the indexer never recorded
+    pub gaps: Vec<NonceRange>,
+    pub missing_nonces: u64,
+    // The most recent transfers, whose states were compared. None if the
+    // route has no transfers yet.
+    pub sampled: Option<NonceRange>,
+    pub indexed_counts: StateCounts,
+    pub onchain_counts: StateCounts,
+    // Sampled transfers whose state differs, and deposits the indexer
+    // recorded past the nonce on chain
+    pub mismatches: Vec<StateMismatch>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexerAuditReport {
+    pub routes: Vec<RouteAudit>,
+}
+
+impl IndexerAuditReport {
+    pub fn missing_nonces(&self) -> u64 {
+        self.routes.iter().map(|route| route.missing_nonces).sum()
+    }
+}
+
+// The ranges below `next_nonce` not covered by `indexed`, which is sorted
+fn nonce_gaps(indexed: &[u64], next_nonce: u64) -> Vec<NonceRange> {
+    let mut gaps = vec![];
+    let mut expected = 0;
+    for &nonce in indexed.iter().take_while(|nonce| **nonce < next_nonce) {
+        if nonce > expected {
+            gaps.push(NonceRange {
+                from: expected,
+                to: nonce - 1,
+            });
+        }
+        expected = nonce + 1;
+    }
+    if expected < next_nonce {
+        gaps.push(NonceRange {
+            from: expected,
+            to: next_nonce - 1,
+        });
+    }
+    gaps
+}
+
+struct Route {
+    source_chain_id: u8,
+    destination_chain_id: u8,
+    next_nonce: u64,
+    // Claims of the route happen on the EVM chain rather than on Starcoin
+    claimed_on_eth: bool,
+}
+
+async fn onchain_state(
+    source: &dyn AuditSource,
+    route: &Route,
+    nonce: u64,
+) -> anyhow::Result<TransferState> {
+    if route.claimed_on_eth && source.eth_transfer_processed(nonce).await? {
+        return Ok(TransferState::Claimed);
+    }
+    Ok(
+        match source
+            .starcoin_transfer_status(route.source_chain_id, nonce)
+            .await?
+        {
+            BridgeActionStatus::Claimed => TransferState::Claimed,
+            BridgeActionStatus::Approved => TransferState::Approved,
+            // Starcoin keeps no record of a transfer before its approval
+            BridgeActionStatus::Pending | BridgeActionStatus::NotFound => TransferState::Deposited,
+        },
+    )
+}
+
+async fn audit_route(
+    conn: &mut AsyncPgConnection,
+    source: &dyn AuditSource,
+    route: Route,
+    sample_size: u64,
+) -> anyhow::Result<RouteAudit> {
+    let chain_id = route.source_chain_id as i32;
+    let deposits: Vec<i64> = token_transfer::table
+        .filter(token_transfer::chain_id.eq(chain_id))
+        .filter(token_transfer::status.eq(TokenTransferStatus::Deposited))
+        .order(token_transfer::nonce.asc())
+        .select(token_transfer::nonce)
+        .load(conn)
+        .await
+        .context("Failed to query the token_transfer table")?;
+    let deposits = deposits
+        .into_iter()
+        .map(|nonce| nonce as u64)
+        .collect::<Vec<_>>();
+    let gaps = nonce_gaps(&deposits, route.next_nonce);
+
+    // A deposit the chain doesn't know of was indexed from another network
+    // or a reorged block
+    let mut mismatches = deposits
+        .iter()
+        .filter(|nonce| **nonce >= route.next_nonce)
+        .map(|nonce| StateMismatch {
+            nonce: *nonce,
+            expected_status: TransferState::Missing,
+            found_status: TransferState::Deposited,
+        })
+        .collect::<Vec<_>>();
+
+    let sample_from = route.next_nonce.saturating_sub(sample_size);
+    let mut indexed_states = BTreeMap::<u64, TransferState>::new();
+    let rows: Vec<(i64, TokenTransferStatus)> = token_transfer::table
+        .filter(token_transfer::chain_id.eq(chain_id))
+        .filter(token_transfer::nonce.ge(sample_from as i64))
+        .filter(token_transfer::nonce.lt(route.next_nonce as i64))
+        .select((token_transfer::nonce, token_transfer::status))
+        .load(conn)
+        .await
+        .context("Failed to query the token_transfer table")?;
+    for (nonce, status) in rows {
+        let state = indexed_states
+            .entry(nonce as u64)
+            .or_insert(TransferState::Missing);
+        *state = (*state).max(status.into());
+    }
+    // Starcoin numbers its transfers to every EVM chain in one sequence, so
+    // only those to the audited chain can be checked there
+    let destinations: HashMap<u64, u8> = if route.claimed_on_eth {
+        let rows: Vec<(i64, i32)> = token_transfer_data::table
+            .filter(token_transfer_data::chain_id.eq(chain_id))
+            .filter(token_transfer_data::nonce.ge(sample_from as i64))
+            .filter(token_transfer_data::nonce.lt(route.next_nonce as i64))
+            .select((
+                token_transfer_data::nonce,
+                token_transfer_data::destination_chain,
+            ))
+            .load(conn)
+            .await
+            .context("Failed to query the token_transfer_data table")?;
+        rows.into_iter()
+            .map(|(nonce, destination)| (nonce as u64, destination as u8))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut indexed_counts = StateCounts::default();
+    let mut onchain_counts = StateCounts::default();
+    for nonce in sample_from..route.next_nonce {
+        // Missing deposits are already reported as gaps
+        let Some(found) = indexed_states.get(&nonce).copied() else {
+            continue;
+        };
+        if destinations
+            .get(&nonce)
+            .is_some_and(|destination| *destination != route.destination_chain_id)
+        {
+            continue;
+        }
+        let expected = onchain_state(source, &route, nonce).await?;
+        indexed_counts.add(found);
+        onchain_counts.add(expected);
+        if found != expected {
+            mismatches.push(StateMismatch {
+                nonce,
+                expected_status: expected,
+                found_status: found,
+            });
+        }
+    }
+    mismatches.sort_by_key(|mismatch| mismatch.nonce);
+
+    Ok(RouteAudit {
+        source_chain_id: route.source_chain_id,
+        destination_chain_id: route.destination_chain_id,
+        onchain_next_nonce: route.next_nonce,
+        indexed_max_nonce: deposits.last().copied(),
+        missing_nonces: gaps.iter().map(NonceRange::count).sum(),
+        gaps,
+        sampled: (sample_from < route.next_nonce).then(|| NonceRange {
+            from: sample_from,
+            to: route.next_nonce - 1,
+        }),
+        indexed_counts,
+        onchain_counts,
+        mismatches,
+    })
+}
+
+// Audits both routes between Starcoin and `evm_chain_id`, comparing the
+// states of the last `sample_size` transfers of each
+pub async fn audit_indexer(
+    conn: &mut AsyncPgConnection,
+    source: &dyn AuditSource,
+    evm_chain_id: u8,
+    sample_size: u64,
+) -> anyhow::Result<IndexerAuditReport> {
+    let (starcoin_chain_id, starcoin_next_nonce) = source.starcoin_next_transfer_nonce().await?;
+    let eth_next_nonce = source.eth_next_transfer_nonce().await?;
+    let routes = [
+        Route {
+            source_chain_id: starcoin_chain_id,
+            destination_chain_id: evm_chain_id,
+            next_nonce: starcoin_next_nonce,
+            claimed_on_eth: true,
+        },
+        Route {
+            source_chain_id: evm_chain_id,
+            destination_chain_id: starcoin_chain_id,
+            next_nonce: eth_next_nonce,
+            claimed_on_eth: false,
+        },
+    ];
+    let mut audits = vec![];
+    for route in routes {
+        audits.push(audit_route(conn, source, route, sample_size).await?);
+    }
+    Ok(IndexerAuditReport { routes: audits })
+}
+
+pub async fn run_indexer_audit(
+    database_url: &str,
+    source: &dyn AuditSource,
+    evm_chain_id: u8,
+    sample_size: u64,
+) -> anyhow::Result<IndexerAuditReport> {
+    let db = connect_indexer_db(database_url).await?;
+    let mut conn = db
+        .connect()
+        .await
+        .context("Indexer database is unreachable")?;
+    audit_indexer(&mut conn, source, evm_chain_id, sample_size).await
+}
+
+// Block at which each transfer from `chain_id` in `from_nonce..=to_nonce`
+// was approved, as recorded by the indexer. Transfers it has no approval of
+// are left out.
+pub async fn indexed_approval_blocks(
+    database_url: &str,
+    chain_id: u8,
+    from_nonce: u64,
+    to_nonce: u64,
+) -> anyhow::Result<BTreeMap<u64, u64>> {
+    let db = connect_indexer_db(database_url).await?;
+    let mut conn = db
+        .connect()
+        .await
+        .context("Indexer database is unreachable")?;
+    let rows: Vec<(i64, i64)> = token_transfer::table
+        .filter(token_transfer::chain_id.eq(chain_id as i32))
+        .filter(token_transfer::status.eq(TokenTransferStatus::Approved))
+        .filter(token_transfer::nonce.ge(from_nonce as i64))
+        .filter(token_transfer::nonce.le(to_nonce as i64))
+        .select((token_transfer::nonce, token_transfer::block_height))
+        .load(&mut conn)
+        .await
+        .context("Failed to query the token_transfer table")?;
+    Ok(rows
+        .into_iter()
+        .map(|(nonce, block_height)| (nonce as u64, block_height as u64))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_async::AsyncConnection;
+    use starcoin_bridge::starcoin_bridge_mock_client::StarcoinMockClient;
+    use starcoin_bridge_pg_db::temp::TempDb;
+    use starcoin_bridge_pg_db::{Db, DbArgs};
+    use starcoin_bridge_schema::models::{BridgeDataSource, TokenTransfer, TokenTransferData};
+    use starcoin_bridge_schema::MIGRATIONS;
+    use std::collections::HashSet;
+
+    const STARCOIN: u8 = 0;
+    const ETH: u8 = 10;
+    const OTHER_EVM: u8 = 12;
+
+    // The mock client on the Starcoin side, and a fixed Eth side
+    struct TestSource {
+        starcoin_bridge_client: StarcoinClient<StarcoinMockClient>,
+        eth_next_nonce: u64,
+        eth_processed: HashSet<u64>,
+    }
+
+    #[async_trait]
+    impl AuditSource for TestSource {
+        async fn starcoin_next_transfer_nonce(&self) -> anyhow::Result<(u8, u64)> {
+            starcoin_next_transfer_nonce(&self.starcoin_bridge_client, Deadline::none()).await
+        }
+
+        async fn eth_next_transfer_nonce(&self) -> anyhow::Result<u64> {
+            Ok(self.eth_next_nonce)
+        }
+
+        async fn starcoin_transfer_status(
+            &self,
+            source_chain_id: u8,
+            nonce: u64,
+        ) -> anyhow::Result<BridgeActionStatus> {
+            starcoin_transfer_status(
+                &self.starcoin_bridge_client,
+                source_chain_id,
+                nonce,
+                Deadline::none(),
+            )
+            .await
+        }
+
+        async fn eth_transfer_processed(&self, nonce: u64) -> anyhow::Result<bool> {
+            Ok(self.eth_processed.contains(&nonce))
+        }
+    }
+
+    fn event(
+        chain_id: u8,
+        nonce: i64,
+        status: TokenTransferStatus,
+        data_source: BridgeDataSource,
+    ) -> TokenTransfer {
+        TokenTransfer {
+            chain_id: chain_id as i32,
+            nonce,
+            status,
+            block_height: 10,
+            timestamp_ms: 1000,
+            txn_hash: vec![nonce as u8; 32],
+            txn_sender: vec![1; 16],
+            gas_usage: 0,
+            data_source,
+            is_finalized: true,
+        }
+    }
+
+    fn deposit_data(chain_id: u8, nonce: i64, destination_chain: u8) -> TokenTransferData {
+        TokenTransferData {
+            chain_id: chain_id as i32,
+            nonce,
+            block_height: 10,
+            timestamp_ms: 1000,
+            txn_hash: vec![nonce as u8; 32],
+            sender_address: vec![1; 16],
+            destination_chain: destination_chain as i32,
+            recipient_address: vec![2; 20],
+            token_id: 3,
+            amount: 1000,
+            is_finalized: true,
+            sender_address_canonical: format!("0x{}", "01".repeat(16)),
+            recipient_address_canonical: format!("0x{}", "02".repeat(20)),
+        }
+    }
+
+    #[test]
+    fn test_nonce_gaps() {
+        let range = |from, to| NonceRange { from, to };
+        assert_eq!(nonce_gaps(&[], 0), vec![]);
+        assert_eq!(nonce_gaps(&[], 3), vec![range(0, 2)]);
+        assert_eq!(nonce_gaps(&[0, 1, 2], 3), vec![]);
+        assert_eq!(
+            nonce_gaps(&[1, 2, 5, 6], 9),
+            vec![range(0, 0), range(3, 4), range(7, 8)]
+        );
+        // Nonces past the chain's are not gaps
+        assert_eq!(nonce_gaps(&[0, 4], 2), vec![range(1, 1)]);
+        assert_eq!(range(3, 4).count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_audit_seeded_db() {
+        let db = TempDb::new().unwrap();
+        let url = db.database().url().clone();
+        Db::for_write(url.clone(), DbArgs::default())
+            .await
+            .unwrap()
+            .run_migrations(Some(&MIGRATIONS))
+            .await
+            .unwrap();
+        let mut conn = AsyncPgConnection::establish(url.as_str()).await.unwrap();
+
+        use TokenTransferStatus::*;
+        let (on_starcoin, on_eth) = (BridgeDataSource::STARCOIN, BridgeDataSource::ETH);
+        // Rows are keyed by the source chain of the transfer, whichever
+        // chain the event was indexed from.
+        // Starcoin to Eth: 0 claimed, 1 never indexed, 2 approved but
+        // claimed on Eth, 3 to another EVM chain, 4 deposited.
+        // Eth to Starcoin: 0 claimed, 1 approved but claimed on Starcoin,
+        // 2 and 3 never indexed, and 5 past the nonce on Eth.
+        diesel::insert_into(token_transfer::table)
+            .values(vec![
+                event(STARCOIN, 0, Deposited, on_starcoin),
+                event(STARCOIN, 0, Approved, on_starcoin),
+                event(STARCOIN, 0, Claimed, on_eth),
+                event(STARCOIN, 2, Deposited, on_starcoin),
+                event(STARCOIN, 2, Approved, on_starcoin),
+                event(STARCOIN, 3, Deposited, on_starcoin),
+                event(STARCOIN, 4, Deposited, on_starcoin),
+                event(ETH, 0, Deposited, on_eth),
+                event(ETH, 0, Approved, on_starcoin),
+                event(ETH, 0, Claimed, on_starcoin),
+                event(ETH, 1, Deposited, on_eth),
+                event(ETH, 1, Approved, on_starcoin),
+                event(ETH, 5, Deposited, on_eth),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        diesel::insert_into(token_transfer_data::table)
+            .values(vec![
+                deposit_data(STARCOIN, 0, ETH),
+                deposit_data(STARCOIN, 2, ETH),
+                deposit_data(STARCOIN, 3, OTHER_EVM),
+                deposit_data(STARCOIN, 4, ETH),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let mock_client = StarcoinMockClient::default();
+        mock_client.set_sequence_nums(vec![(BridgeActionType::TokenTransfer as u8, 5)]);
+        for nonce in [0, 1] {
+            mock_client.set_token_transfer_onchain_status(ETH, nonce, BridgeActionStatus::Claimed);
+        }
+        let source = TestSource {
+            starcoin_bridge_client: StarcoinClient::new_for_testing(mock_client),
+            eth_next_nonce: 4,
+            eth_processed: HashSet::from([0, 2]),
+        };
+
+        let report = audit_indexer(&mut conn, &source, ETH, 10).await.unwrap();
+        assert_eq!(report.missing_nonces(), 3);
+        let [starcoin, eth] = &report.routes[..] else {
+            panic!("Expected two routes, got {:?}", report.routes);
+        };
+
+        assert_eq!(
+            (starcoin.source_chain_id, starcoin.destination_chain_id),
+            (STARCOIN, ETH)
+        );
+        assert_eq!(starcoin.onchain_next_nonce, 5);
+        assert_eq!(starcoin.indexed_max_nonce, Some(4));
+        assert_eq!(starcoin.gaps, vec![NonceRange { from: 1, to: 1 }]);
+        assert_eq!(starcoin.sampled, Some(NonceRange { from: 0, to: 4 }));
+        // Transfer 3 is not compared, and 2 lags its claim on Eth
+        assert_eq!(
+            starcoin.indexed_counts,
+            StateCounts {
+                approved: 2,
+                claimed: 1
+            }
+        );
+        assert_eq!(
+            starcoin.onchain_counts,
+            StateCounts {
+                approved: 2,
+                claimed: 2
+            }
+        );
+        assert_eq!(
+            starcoin.mismatches,
+            vec![StateMismatch {
+                nonce: 2,
+                expected_status: TransferState::Claimed,
+                found_status: TransferState::Approved,
+            }]
+        );
+
+        assert_eq!(eth.onchain_next_nonce, 4);
+        assert_eq!(eth.indexed_max_nonce, Some(5));
+        assert_eq!(eth.gaps, vec![NonceRange { from: 2, to: 3 }]);
+        assert_eq!(eth.missing_nonces, 2);
+        assert_eq!(
+            eth.mismatches,
+            vec![
+                StateMismatch {
+                    nonce: 1,
+                    expected_status: TransferState::Claimed,
+                    found_status: TransferState::Approved,
+                },
+                StateMismatch {
+                    nonce: 5,
+                    expected_status: TransferState::Missing,
+                    found_status: TransferState::Deposited,
+                },
+            ]
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["routes"][1]["gaps"][0]["from"], 2);
+        assert_eq!(
+            json["routes"][1]["mismatches"][0]["expected-status"],
+            "claimed"
+        );
+
+        // A smaller sample only compares the most recent transfers
+        let report = audit_indexer(&mut conn, &source, ETH, 1).await.unwrap();
+        assert_eq!(
+            report.routes[1].sampled,
+            Some(NonceRange { from: 3, to: 3 })
+        );
+        assert_eq!(report.routes[1].onchain_counts, StateCounts::default());
+        assert_eq!(report.missing_nonces(), 3);
+    }
+}
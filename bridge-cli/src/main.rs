@@ -6,63 +6,119 @@ use ethers::providers::Middleware;
 use ethers::types::Address as EthAddress;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::ToFromBytes;
-use shared_crypto::intent::Intent;
-use shared_crypto::intent::IntentMessage;
-use starcoin_bridge::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use starcoin_bridge::abi::EthStarcoinBridge;
+use starcoin_bridge::committee_concentration::{
+    analyze_concentration, default_heuristics, resolve_profiles, AsnResolver, AsnTable,
+    ConcentrationReport, MemberProfile, DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT,
+};
 use starcoin_bridge::crypto::{BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes};
-use starcoin_bridge::eth_transaction_builder::build_eth_transaction;
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::eth_client::EthClient;
+use starcoin_bridge::metered_eth_provider::MeteredEthHttpProvier;
 use starcoin_bridge::metrics::BridgeMetrics;
+use starcoin_bridge::sanitize::{sanitize_untrusted, MAX_URL_BYTES};
 use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
-use starcoin_bridge::starcoin_bridge_transaction_builder::build_starcoin_bridge_transaction;
 use starcoin_bridge::types::BridgeActionType;
+use starcoin_bridge::utils::get_eth_contracts;
 use starcoin_bridge::utils::{
     examine_key, generate_bridge_authority_key_and_write_to_file,
     generate_bridge_client_key_and_write_to_file, generate_bridge_node_config_and_write_to_file,
 };
-use starcoin_bridge::utils::{get_eth_contracts, EthBridgeContracts};
+use starcoin_bridge_cli::certificate_cache::CertificateCache;
+use starcoin_bridge_cli::client::{BridgeOps, GovernanceOptions};
+use starcoin_bridge_cli::dashboard::{self, DashboardOptions, RpcSource};
+use starcoin_bridge_cli::eth_bridge_view::{
+    starcoin_committee_eth_addresses, view_config_eth_bridge, view_eth_bridge,
+};
+use starcoin_bridge_cli::governance_history::{query_governance_history, GovernanceHistoryFilter};
+use starcoin_bridge_cli::indexer_audit::{run_indexer_audit, RpcAuditSource};
+use starcoin_bridge_cli::networks::{resolve_eth_endpoint, NetworkEntry, NetworkRegistry};
+use starcoin_bridge_cli::output::{print_output, LogFormat};
+use starcoin_bridge_cli::signed_actions::SignedActionsFile;
+use starcoin_bridge_cli::wait_for_action::{
+    wait_for_action, ActionStatusSource, EthStatusSource, StarcoinStatusSource, WaitTarget,
+};
 use starcoin_bridge_cli::{
-    make_action, select_contract_address, Args, BridgeCliConfig, BridgeCommand,
-    LoadedBridgeCliConfig, Network, SEPOLIA_BRIDGE_PROXY_ADDR,
+    active_validators_by_address, encode_action, examine_config, fetch_node_versions,
+    governance_commands, ping_urls_with_timeout, process_env, register_committee, route_limits,
+    treasury_tokens, version_distribution, view_token_transfer, Args, BridgeCliConfig,
+    BridgeCommand, LoadedBridgeCliConfig, Network, PingResult, RouteLimit, TreasuryToken,
+    DEFAULT_PING_TIMEOUT,
 };
 use starcoin_bridge_config::Config;
-use starcoin_bridge_types::crypto::Signature;
-use starcoin_bridge_types::transaction::Transaction;
+use starcoin_bridge_keys::encrypted;
+use starcoin_bridge_keys::keygen::KeyScheme;
+use starcoin_bridge_sdk::StarcoinClientBuilder;
 use starcoin_bridge_vm_types::bridge::base_types::StarcoinAddress;
 use starcoin_bridge_vm_types::bridge::bridge::{
     BridgeChainId, MoveTypeCommitteeMember, MoveTypeCommitteeMemberRegistration,
+    APPROVAL_THRESHOLD_TOKEN_TRANSFER,
 };
 use starcoin_bridge_vm_types::bridge::committee::TOTAL_VOTING_POWER;
 use starcoin_bridge_vm_types::bridge::crypto::AuthorityPublicKeyBytes;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::str::from_utf8;
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
+use tracing::info;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Init logging
-    let (_guard, _filter_handle) = telemetry_subscribers::TelemetryConfig::new()
-        .with_env()
-        .init();
     let args = Args::parse();
+    // Init logging
+    let mut telemetry = telemetry_subscribers::TelemetryConfig::new().with_env();
+    if args.log_format == LogFormat::Json {
+        telemetry = telemetry.with_json();
+    }
+    let (_guard, _filter_handle) = telemetry.init();
+    let deadline = args.deadline();
+    let metrics = args.metrics();
 
     match args.command {
-        BridgeCommand::CreateBridgeValidatorKey { path } => {
-            generate_bridge_authority_key_and_write_to_file(&path)?;
+        BridgeCommand::CreateBridgeValidatorKey { path, encrypt } => {
+            generate_bridge_authority_key_and_write_to_file(&path, encrypt)?;
             println!("Bridge validator key generated at {}", path.display());
         }
-        BridgeCommand::CreateBridgeClientKey { path, use_ecdsa } => {
-            generate_bridge_client_key_and_write_to_file(&path, use_ecdsa)?;
+        BridgeCommand::CreateBridgeClientKey {
+            path,
+            key_scheme,
+            use_ecdsa,
+            encrypt,
+        } => {
+            let key_scheme = if use_ecdsa {
+                KeyScheme::Secp256k1
+            } else {
+                key_scheme
+            };
+            generate_bridge_client_key_and_write_to_file(&path, key_scheme, encrypt)?;
             println!("Bridge client key generated at {}", path.display());
         }
+        BridgeCommand::EncryptKey { path, out } => {
+            encrypted::encrypt_key_file(&path, &out)?;
+            println!("Encrypted key written to {}", out.display());
+        }
+        BridgeCommand::DecryptKey { path, out } => {
+            encrypted::decrypt_key_file(&path, &out)?;
+            println!("Decrypted key written to {}", out.display());
+        }
         BridgeCommand::ExamineKey {
             path,
             is_validator_key,
         } => {
             examine_key(&path, is_validator_key)?;
         }
+        BridgeCommand::ExamineConfig { config_path } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let report = examine_config(config, deadline).await;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if !report.passed {
+                return Err(anyhow::anyhow!("Config checks failed"));
+            }
+        }
         BridgeCommand::CreateBridgeNodeConfigTemplate { path, run_client } => {
             generate_bridge_node_config_and_write_to_file(&path, run_client)?;
             println!(
@@ -73,215 +129,171 @@ async fn main() -> anyhow::Result<()> {
 
         BridgeCommand::Governance {
             config_path,
+            network,
             chain_id,
             cmd,
+            batch_file,
             dry_run,
+            export_signatures,
+            force,
+            sig_timeout_secs,
+            min_quorum_report,
+            skip_onchain_validation,
+            allow_large_change,
+            max_change_factor,
+            fresh_signatures,
+            certificate_cache_dir,
+            certificate_ttl_secs,
         } => {
             let chain_id = BridgeChainId::try_from(chain_id).expect("Invalid chain id");
-            println!("Chain ID: {:?}", chain_id);
-            let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
-            let config = LoadedBridgeCliConfig::load(config).await?;
-            let metrics = Arc::new(BridgeMetrics::new_for_testing());
-            let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
-                &config.starcoin_bridge_rpc_url,
-                &config.starcoin_bridge_proxy_address,
-                metrics.clone(),
-            );
-
-            let (starcoin_bridge_key, starcoin_bridge_address, gas_object_ref) = config
-                .get_starcoin_bridge_account_info()
-                .await
-                .expect("Failed to get starcoin account info");
-            let bridge_summary = starcoin_bridge_client
-                .get_bridge_summary()
-                .await
-                .expect("Failed to get bridge summary");
-            let bridge_committee = Arc::new(
-                starcoin_bridge_client
-                    .get_bridge_committee()
-                    .await
-                    .expect("Failed to get bridge committee"),
-            );
-            let agg = BridgeAuthorityAggregator::new(
-                bridge_committee,
-                metrics,
-                Arc::new(BTreeMap::new()),
-            );
-
-            // Handle Starcoin Side
-            if chain_id.is_starcoin_bridge_chain() {
-                let starcoin_bridge_chain_id =
-                    BridgeChainId::try_from(bridge_summary.chain_id).unwrap();
-                assert_eq!(
-                    starcoin_bridge_chain_id, chain_id,
-                    "Chain ID mismatch, expected: {:?}, got from url: {:?}",
-                    chain_id, starcoin_bridge_chain_id
-                );
-                // Create BridgeAction
-                let starcoin_bridge_action = make_action(starcoin_bridge_chain_id, &cmd);
-                println!(
-                    "Action to execute on Starcoin: {:?}",
-                    starcoin_bridge_action
-                );
-                let certified_action = agg
-                    .request_committee_signatures(starcoin_bridge_action)
-                    .await
-                    .expect("Failed to request committee signatures");
-                if dry_run {
-                    println!("Dryrun succeeded.");
-                    return Ok(());
-                }
-                let bridge_arg = starcoin_bridge_client
-                    .get_mutable_bridge_object_arg_must_succeed()
-                    .await;
-                let rgp = starcoin_bridge_client
-                    .get_reference_gas_price_until_success()
-                    .await;
-                let id_token_map = starcoin_bridge_client.get_token_id_map().await.unwrap();
-                let tx = build_starcoin_bridge_transaction(
-                    starcoin_bridge_address,
-                    &gas_object_ref,
-                    certified_action,
-                    bridge_arg,
-                    &id_token_map,
-                    rgp,
-                )
-                .expect("Failed to build starcoin transaction");
-                let starcoin_bridge_sig = Signature::new_secure(
-                    &IntentMessage::new(Intent::starcoin_bridge_transaction(), tx.clone()),
-                    &starcoin_bridge_key,
-                );
-                let tx = Transaction::from_data(tx, vec![starcoin_bridge_sig]);
-                let resp = starcoin_bridge_client
-                    .execute_transaction_block_with_effects(tx)
-                    .await
-                    .expect("Failed to execute transaction block with effects");
-                if resp.status_ok().unwrap() {
-                    println!("Starcoin Transaction succeeded: {:?}", resp.digest);
-                } else {
-                    println!(
-                        "Starcoin Transaction failed: {:?}. Effects: {:?}",
-                        resp.digest, resp.effects
-                    );
-                }
-                return Ok(());
+            info!("Chain ID: {:?}", chain_id);
+            let network = network_entry(network)?;
+            if let Some((network, entry)) = &network {
+                entry.check_bridge_chain_id(network, chain_id)?;
             }
+            // Batches are checked before anything is requested from the committee
+            let cmds = governance_commands(cmd, batch_file.as_ref())?;
+            let ops = load_bridge_ops(config_path, network, deadline, metrics).await?;
+            let options = GovernanceOptions {
+                dry_run,
+                export_signatures,
+                force,
+                sig_timeout: Duration::from_secs(sig_timeout_secs),
+                min_quorum_report,
+                skip_onchain_validation,
+                allow_large_change,
+                max_change_factor,
+                certificate_cache: Some(CertificateCache::new(
+                    certificate_cache_dir,
+                    Duration::from_secs(certificate_ttl_secs),
+                )),
+                fresh_signatures,
+            };
+            let output = ops
+                .execute_governance_action(chain_id, cmds, &options)
+                .await?;
+            return print_output(args.output, &output);
+        }
 
-            // Handle eth side
-            // TODO assert chain id returned from rpc matches chain_id
-            let eth_signer_client = config.eth_signer();
-            // Create BridgeAction
-            let eth_action = make_action(chain_id, &cmd);
-            println!("Action to execute on Eth: {:?}", eth_action);
-            // Create Eth Signer Client
-            // TODO if a validator is blocklisted on eth, ignore their signatures?
-            let certified_action = agg
-                .request_committee_signatures(eth_action)
-                .await
-                .expect("Failed to request committee signatures");
-            if dry_run {
-                println!("Dryrun succeeded.");
-                return Ok(());
-            }
-            let contract_address = select_contract_address(&config, &cmd);
-            let tx = build_eth_transaction(
-                contract_address,
-                eth_signer_client.clone(),
-                certified_action,
-            )
-            .await
-            .expect("Failed to build eth transaction");
-            println!("sending Eth tx: {:?}", tx);
-            match tx.send().await {
-                Ok(tx_hash) => {
-                    println!("Transaction sent with hash: {:?}", tx_hash);
-                }
-                Err(err) => {
-                    let revert = err.as_revert();
-                    println!("Transaction reverted: {:?}", revert);
-                }
+        BridgeCommand::SyncCommitteeState {
+            config_path,
+            chain_id,
+            execute,
+            ping_timeout_secs,
+            sig_timeout_secs,
+        } => {
+            let chain_id = BridgeChainId::try_from(chain_id)
+                .map_err(|e| anyhow::anyhow!("Invalid chain id {}: {:?}", chain_id, e))?;
+            let ops = load_bridge_ops(config_path, None, deadline, metrics).await?;
+            let options = GovernanceOptions {
+                sig_timeout: Duration::from_secs(sig_timeout_secs),
+                ..Default::default()
             };
+            let output = ops
+                .sync_committee_state(
+                    chain_id,
+                    execute,
+                    Duration::from_secs(ping_timeout_secs),
+                    &options,
+                )
+                .await?;
+            return print_output(args.output, &output);
+        }
 
-            return Ok(());
+        BridgeCommand::ExecuteSignedAction {
+            config_path,
+            chain_id,
+            signatures_path,
+        } => {
+            let chain_id = BridgeChainId::try_from(chain_id)
+                .map_err(|e| anyhow::anyhow!("Invalid chain id {}: {:?}", chain_id, e))?;
+            let signed_actions = SignedActionsFile::read(&signatures_path)?;
+            let ops = load_bridge_ops(config_path, None, deadline, metrics).await?;
+            let output = ops
+                .execute_signed_actions(chain_id, &signed_actions)
+                .await?;
+            return print_output(args.output, &output);
+        }
+        BridgeCommand::EncodeAction { chain_id, cmd } => {
+            let chain_id = BridgeChainId::try_from(chain_id)
+                .map_err(|e| anyhow::anyhow!("Invalid chain id {}: {:?}", chain_id, e))?;
+            let output = encode_action(chain_id, &cmd)?;
+            return print_output(args.output, &output);
         }
 
         BridgeCommand::ViewEthBridge {
             network,
             bridge_proxy,
             eth_rpc_url,
+            config_path,
         } => {
-            let bridge_proxy = match network {
-                Some(Network::Testnet) => {
-                    Ok(EthAddress::from_str(SEPOLIA_BRIDGE_PROXY_ADDR).unwrap())
-                }
-                None => bridge_proxy.ok_or(anyhow::anyhow!(
-                    "Network or bridge proxy address must be provided"
-                )),
-            }?;
+            let network = network_entry(network)?;
+            let config = config_path
+                .map(|config_path| {
+                    BridgeCliConfig::load(config_path).map(|c| c.with_env_overrides(process_env))
+                })
+                .transpose()?;
+            // Without flags or a network everything comes from the config,
+            // which is loaded read-only as no key is needed
+            if let (Some(config), None, None, None) =
+                (&config, &eth_rpc_url, &bridge_proxy, &network)
+            {
+                let config = LoadedBridgeCliConfig::load_read_only(config.clone()).await?;
+                let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
+                    &config.starcoin_bridge_rpc_url,
+                    &config.starcoin_bridge_proxy_address,
+                    metrics,
+                );
+                let print = view_config_eth_bridge(&config, &starcoin_bridge_client).await?;
+                println!("{}", serde_json::to_string_pretty(&print).unwrap());
+                return Ok(());
+            }
+            let (eth_rpc_url, bridge_proxy) = resolve_eth_endpoint(
+                eth_rpc_url,
+                bridge_proxy,
+                network.as_ref().map(|(_, entry)| entry),
+                config.as_ref(),
+            )?;
             let provider = Arc::new(
                 ethers::prelude::Provider::<ethers::providers::Http>::try_from(eth_rpc_url)
                     .unwrap()
                     .interval(std::time::Duration::from_millis(2000)),
             );
             let chain_id = provider.get_chainid().await?;
-            let EthBridgeContracts {
-                bridge,
-                committee,
-                limiter,
-                vault,
-                config,
-            } = get_eth_contracts(bridge_proxy, &provider).await?;
-            let message_type = BridgeActionType::EvmContractUpgrade as u8;
-            let bridge_upgrade_next_nonce: u64 = bridge.nonces(message_type).call().await?;
-            let committee_upgrade_next_nonce: u64 = committee.nonces(message_type).call().await?;
-            let limiter_upgrade_next_nonce: u64 = limiter.nonces(message_type).call().await?;
-            let config_upgrade_next_nonce: u64 = config.nonces(message_type).call().await?;
-
-            let token_transfer_next_nonce: u64 = bridge
-                .nonces(BridgeActionType::TokenTransfer as u8)
-                .call()
-                .await?;
-            let blocklist_update_nonce: u64 = committee
-                .nonces(BridgeActionType::UpdateCommitteeBlocklist as u8)
-                .call()
-                .await?;
-            let emergency_button_nonce: u64 = bridge
-                .nonces(BridgeActionType::EmergencyButton as u8)
-                .call()
-                .await?;
-            let limit_update_nonce: u64 = limiter
-                .nonces(BridgeActionType::LimitUpdate as u8)
-                .call()
-                .await?;
-            let asset_price_update_nonce: u64 = config
-                .nonces(BridgeActionType::AssetPriceUpdate as u8)
-                .call()
-                .await?;
-            let add_tokens_nonce: u64 = config
-                .nonces(BridgeActionType::AddTokensOnEvm as u8)
-                .call()
-                .await?;
-
-            let print = OutputEthBridge {
-                chain_id: chain_id.as_u64(),
-                bridge_proxy: bridge.address(),
-                committee_proxy: committee.address(),
-                limiter_proxy: limiter.address(),
-                config_proxy: config.address(),
-                vault: vault.address(),
-                nonces: Nonces {
-                    token_transfer: token_transfer_next_nonce,
-                    blocklist_update: blocklist_update_nonce,
-                    emergency_button: emergency_button_nonce,
-                    limit_update: limit_update_nonce,
-                    asset_price_update: asset_price_update_nonce,
-                    add_evm_tokens: add_tokens_nonce,
-                    contract_upgrade_bridge: bridge_upgrade_next_nonce,
-                    contract_upgrade_committee: committee_upgrade_next_nonce,
-                    contract_upgrade_limiter: limiter_upgrade_next_nonce,
-                    contract_upgrade_config: config_upgrade_next_nonce,
-                },
+            if let Some((network, entry)) = &network {
+                entry.check_eth_chain(network, chain_id.as_u64(), None)?;
+            }
+            // The committee contract can't list its members, so the members of
+            // the Starcoin committee are checked
+            let starcoin_endpoint = network
+                .as_ref()
+                .and_then(|(_, entry)| {
+                    entry
+                        .starcoin_bridge_rpc_url
+                        .clone()
+                        .zip(entry.starcoin_bridge_proxy_address.clone())
+                })
+                .or_else(|| {
+                    config.as_ref().map(|config| {
+                        (
+                            config.starcoin_bridge_rpc_url.clone(),
+                            config.starcoin_bridge_proxy_address.clone(),
+                        )
+                    })
+                });
+            let committee_members = async {
+                let (rpc_url, proxy_address) = starcoin_endpoint.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Members are looked up on Starcoin, pass --network or --config-path"
+                    )
+                })?;
+                let starcoin_bridge_client =
+                    StarcoinBridgeClient::with_metrics(&rpc_url, &proxy_address, metrics);
+                starcoin_committee_eth_addresses(&starcoin_bridge_client).await
             };
+            let contracts = get_eth_contracts(bridge_proxy, &provider).await?;
+            let print =
+                view_eth_bridge(chain_id.as_u64(), &contracts, &provider, committee_members).await;
             println!("{}", serde_json::to_string_pretty(&print).unwrap());
             return Ok(());
         }
@@ -290,7 +302,6 @@ async fn main() -> anyhow::Result<()> {
             starcoin_bridge_rpc_url,
             starcoin_bridge_proxy_address,
         } => {
-            let metrics = Arc::new(BridgeMetrics::new_for_testing());
             let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
                 &starcoin_bridge_rpc_url,
                 &starcoin_bridge_proxy_address,
@@ -302,19 +313,14 @@ async fn main() -> anyhow::Result<()> {
                 .map_err(|e| anyhow::anyhow!("Failed to get bridge summary: {:?}", e))?;
             let move_type_bridge_committee = bridge_summary.committee;
 
-            // TODO: The stake and name lookups require Starcoin-specific APIs
-            // For now, create empty maps as placeholders
-            let stakes: HashMap<StarcoinAddress, u64> = HashMap::new();
-            let names: HashMap<StarcoinAddress, String> = move_type_bridge_committee
-                .member_registration
-                .iter()
-                .map(|(addr, entry)| {
-                    (
-                        *addr,
-                        String::from_utf8_lossy(&entry.http_rest_url).to_string(),
-                    )
-                })
-                .collect();
+            let system_state = StarcoinClientBuilder::default()
+                .url(&starcoin_bridge_rpc_url)
+                .bridge_address(&starcoin_bridge_proxy_address)
+                .build()?
+                .governance_api()
+                .get_latest_starcoin_bridge_system_state()
+                .await?;
+            let validators = active_validators_by_address(&system_state);
 
             let mut authorities = vec![];
             let mut output_wrapper = Output::<OutputStarcoinBridgeRegistration>::default();
@@ -332,21 +338,23 @@ async fn main() -> anyhow::Result<()> {
                     continue;
                 };
                 let eth_address = BridgeAuthorityPublicKeyBytes::from(&pubkey).to_eth_address();
-                let Ok(url) = from_utf8(&http_rest_url) else {
+                let url = sanitize_untrusted(&http_rest_url, MAX_URL_BYTES);
+                if url.as_bytes() != http_rest_url.as_slice() {
                     output_wrapper.add_error(format!(
-                        "Invalid bridge http url for validator: {}: {:?}",
-                        starcoin_bridge_address, http_rest_url
+                        "Sanitized invalid bridge http url for validator {}: {:?}",
+                        starcoin_bridge_address, url
                     ));
-                    continue;
-                };
-                let url = url.to_string();
+                }
 
-                // Get name from names map, or use URL as fallback
-                let name = names
-                    .get(&starcoin_bridge_address)
-                    .cloned()
-                    .unwrap_or_else(|| url.clone());
-                let stake = stakes.get(&starcoin_bridge_address).copied().unwrap_or(0);
+                // Members outside the active validator set carry no stake
+                let (name, stake, status) = match validators.get(&starcoin_bridge_address) {
+                    Some((name, stake)) => (
+                        sanitize_untrusted(name.as_bytes(), MAX_URL_BYTES),
+                        *stake,
+                        None,
+                    ),
+                    None => (url.clone(), 0, Some("not-active-validator".to_string())),
+                };
                 authorities.push((
                     name,
                     starcoin_bridge_address,
@@ -354,17 +362,20 @@ async fn main() -> anyhow::Result<()> {
                     eth_address,
                     url,
                     stake,
+                    status,
                 ));
             }
             let total_stake = authorities
                 .iter()
-                .map(|(_, _, _, _, _, stake)| *stake)
+                .map(|(_, _, _, _, _, stake, _)| *stake)
                 .sum::<u64>();
             let mut output = OutputStarcoinBridgeRegistration {
                 total_registered_stake: total_stake as f32 / TOTAL_VOTING_POWER as f32 * 100.0,
                 ..Default::default()
             };
-            for (name, starcoin_bridge_address, pubkey, eth_address, url, stake) in authorities {
+            for (name, starcoin_bridge_address, pubkey, eth_address, url, stake, status) in
+                authorities
+            {
                 output.committee.push(OutputMember {
                     name: name.clone(),
                     starcoin_bridge_address,
@@ -373,7 +384,9 @@ async fn main() -> anyhow::Result<()> {
                     url,
                     stake,
                     blocklisted: None,
-                    status: None,
+                    status,
+                    version: None,
+                    ping: None,
                 });
             }
             output_wrapper.inner = output;
@@ -385,8 +398,11 @@ async fn main() -> anyhow::Result<()> {
             starcoin_bridge_proxy_address,
             hex,
             ping,
+            analyze,
+            asn_table,
+            timeout_secs,
+            exit_nonzero_if_no_quorum,
         } => {
-            let metrics = Arc::new(BridgeMetrics::new_for_testing());
             let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
                 &starcoin_bridge_rpc_url,
                 &starcoin_bridge_proxy_address,
@@ -403,12 +419,6 @@ async fn main() -> anyhow::Result<()> {
             let names: HashMap<StarcoinAddress, (AuthorityPublicKeyBytes, String)> = HashMap::new();
 
             let mut authorities = vec![];
-            let mut ping_tasks = vec![];
-            let client = reqwest::Client::builder()
-                .connect_timeout(Duration::from_secs(10))
-                .timeout(Duration::from_secs(10))
-                .build()
-                .unwrap();
             let mut output_wrapper = Output::<OutputStarcoinBridge>::default();
             for (_, member) in move_type_bridge_committee.members {
                 let MoveTypeCommitteeMember {
@@ -426,14 +436,13 @@ async fn main() -> anyhow::Result<()> {
                     continue;
                 };
                 let eth_address = BridgeAuthorityPublicKeyBytes::from(&pubkey).to_eth_address();
-                let Ok(url) = from_utf8(&http_rest_url) else {
+                let url = sanitize_untrusted(&http_rest_url, MAX_URL_BYTES);
+                if url.as_bytes() != http_rest_url.as_slice() {
                     output_wrapper.add_error(format!(
-                        "Invalid bridge http url for validator: {}: {:?}",
-                        starcoin_bridge_address, http_rest_url
+                        "Sanitized invalid bridge http url for validator {}: {:?}",
+                        starcoin_bridge_address, url
                     ));
-                    continue;
-                };
-                let url = url.to_string();
+                }
 
                 // Use the address directly since names is HashMap<StarcoinAddress, ...>
                 let name = if let Some((_, n)) = names.get(&starcoin_bridge_address) {
@@ -442,10 +451,6 @@ async fn main() -> anyhow::Result<()> {
                     url.clone()
                 };
 
-                if ping {
-                    let client_clone = client.clone();
-                    ping_tasks.push(client_clone.get(url.clone()).send());
-                }
                 authorities.push((
                     name,
                     starcoin_bridge_address,
@@ -464,25 +469,60 @@ async fn main() -> anyhow::Result<()> {
                 total_stake: total_stake as f32 / TOTAL_VOTING_POWER as f32 * 100.0,
                 ..Default::default()
             };
-            let ping_tasks_resp = if !ping_tasks.is_empty() {
-                futures::future::join_all(ping_tasks)
-                    .await
-                    .into_iter()
-                    .map(|resp| {
-                        Some(match resp {
-                            Ok(resp) => resp.status().is_success(),
-                            Err(_e) => false,
-                        })
-                    })
-                    .collect::<Vec<_>>()
+            if analyze {
+                let asn_table = asn_table.as_deref().map(AsnTable::load).transpose()?;
+                let members = authorities
+                    .iter()
+                    .filter(|(_, _, _, _, _, _, blocklisted)| !blocklisted)
+                    .map(
+                        |(_, starcoin_bridge_address, _, eth_address, url, stake, _)| {
+                            MemberProfile::new(
+                                *starcoin_bridge_address,
+                                *eth_address,
+                                url.clone(),
+                                *stake,
+                            )
+                        },
+                    )
+                    .collect();
+                let members = resolve_profiles(
+                    members,
+                    asn_table.as_ref().map(|table| table as &dyn AsnResolver),
+                )
+                .await;
+                output.concentration = Some(analyze_concentration(
+                    &members,
+                    &default_heuristics(),
+                    DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT,
+                ));
+            }
+            let (ping_tasks_resp, versions) = if ping {
+                let urls = authorities
+                    .iter()
+                    .map(|(_, _, _, _, url, _, _)| url.clone())
+                    .collect::<Vec<_>>();
+                let timeout = timeout_secs.map_or(DEFAULT_PING_TIMEOUT, Duration::from_secs);
+                let (ping_resp, versions) = tokio::join!(
+                    ping_urls_with_timeout(&urls, timeout),
+                    fetch_node_versions(&urls)
+                );
+                output.version_distribution = Some(version_distribution(&versions));
+                (
+                    ping_resp.into_iter().map(Some).collect(),
+                    versions.into_iter().map(Some).collect(),
+                )
             } else {
-                vec![None; authorities.len()]
+                (vec![None; authorities.len()], vec![None; authorities.len()])
             };
             let mut total_online_stake = 0;
+            // Online stake of members whose signatures count
+            let mut signing_online_stake = 0;
             for (
                 (name, starcoin_bridge_address, pubkey, eth_address, url, stake, blocklisted),
-                ping_resp,
-            ) in authorities.into_iter().zip(ping_tasks_resp)
+                (ping_resp, version),
+            ) in authorities
+                .into_iter()
+                .zip(ping_tasks_resp.into_iter().zip(versions))
             {
                 let pubkey = if hex {
                     Hex::encode(pubkey.as_bytes())
@@ -491,8 +531,11 @@ async fn main() -> anyhow::Result<()> {
                 };
                 match ping_resp {
                     Some(resp) => {
-                        if resp {
+                        if resp.online {
                             total_online_stake += stake;
+                            if !blocklisted {
+                                signing_online_stake += stake;
+                            }
                         }
                         output.committee.push(OutputMember {
                             name: name.clone(),
@@ -502,11 +545,13 @@ async fn main() -> anyhow::Result<()> {
                             url,
                             stake,
                             blocklisted: Some(blocklisted),
-                            status: Some(if resp {
+                            status: Some(if resp.online {
                                 "online".to_string()
                             } else {
                                 "offline".to_string()
                             }),
+                            version,
+                            ping: Some(resp),
                         });
                     }
                     None => {
@@ -519,6 +564,8 @@ async fn main() -> anyhow::Result<()> {
                             stake,
                             blocklisted: Some(blocklisted),
                             status: None,
+                            version,
+                            ping: None,
                         });
                     }
                 }
@@ -526,6 +573,9 @@ async fn main() -> anyhow::Result<()> {
             if ping {
                 output.total_online_stake =
                     Some(total_online_stake as f32 / TOTAL_VOTING_POWER as f32 * 100.0);
+                output.online_stake_bps = Some(signing_online_stake * 10_000 / TOTAL_VOTING_POWER);
+                output.quorum_online =
+                    Some(signing_online_stake >= APPROVAL_THRESHOLD_TOKEN_TRANSFER);
             }
 
             // sequence nonces
@@ -535,51 +585,267 @@ async fn main() -> anyhow::Result<()> {
                     .insert(BridgeActionType::try_from(type_).unwrap(), nonce);
             }
 
+            // The values governance actions change, in on-chain and USD units
+            let now_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_millis() as u64;
+            output.treasury = treasury_tokens(&bridge_summary.treasury);
+            output.limiter = route_limits(&bridge_summary.limiter, now_ms);
+            output.paused_tokens = bridge_summary.treasury.paused_token_ids;
+
+            let quorum_online = output.quorum_online;
             output_wrapper.inner = output;
             println!("{}", serde_json::to_string_pretty(&output_wrapper).unwrap());
+            if exit_nonzero_if_no_quorum && quorum_online == Some(false) {
+                return Err(anyhow::anyhow!(
+                    "Online committee stake is below the quorum of {} bps",
+                    APPROVAL_THRESHOLD_TOKEN_TRANSFER * 10_000 / TOTAL_VOTING_POWER
+                ));
+            }
         }
-        BridgeCommand::Client { config_path, cmd } => {
-            let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
+        BridgeCommand::ViewTokenTransfer {
+            source_chain_id,
+            seq_num,
+            config_path,
+        } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let report = view_token_transfer(&config, source_chain_id, seq_num, deadline).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        BridgeCommand::Client {
+            config_path,
+            network,
+            ignore_paused,
+            cmd,
+        } => {
+            let network = network_entry(network)?;
+            let ops = load_bridge_ops(config_path, network, deadline, metrics).await?;
+            cmd.handle(&ops, args.output, ignore_paused).await?;
+            return Ok(());
+        }
+        BridgeCommand::Validator { config_path, cmd } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
             let config = LoadedBridgeCliConfig::load(config).await?;
-            let metrics = Arc::new(BridgeMetrics::new_for_testing());
             let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
                 &config.starcoin_bridge_rpc_url,
                 &config.starcoin_bridge_proxy_address,
                 metrics,
-            );
-            cmd.handle(&config, starcoin_bridge_client).await?;
+            )
+            .with_gas_config(config.gas_config.clone());
+            cmd.handle(&config, starcoin_bridge_client, deadline, args.output)
+                .await?;
             return Ok(());
         }
+        BridgeCommand::RegisterCommittee {
+            config_path,
+            bridge_authority_key_path,
+            node_url,
+        } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let config = LoadedBridgeCliConfig::load(config).await?;
+            let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
+                &config.starcoin_bridge_rpc_url,
+                &config.starcoin_bridge_proxy_address,
+                metrics,
+            )
+            .with_gas_config(config.gas_config.clone());
+            let registration = register_committee(
+                &bridge_authority_key_path,
+                &node_url,
+                &config,
+                &starcoin_bridge_client,
+                deadline,
+            )
+            .await?;
+            print_output(args.output, &registration)?;
+            return Ok(());
+        }
+        BridgeCommand::Audit { cmd } => {
+            cmd.handle(deadline).await?;
+        }
+        BridgeCommand::Config { cmd } => {
+            cmd.handle()?;
+        }
+        BridgeCommand::Tokens { cmd } => {
+            cmd.handle().await?;
+        }
+        BridgeCommand::State {
+            state_dir,
+            certificate_cache_dir,
+            cmd,
+        } => {
+            cmd.handle(state_dir, certificate_cache_dir, args.output)?;
+        }
+        BridgeCommand::Dashboard {
+            config_path,
+            node_url,
+            refresh_secs,
+            committee_refresh_secs,
+            fetch_timeout_secs,
+        } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let config = LoadedBridgeCliConfig::load_read_only(config).await?;
+            let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
+                &config.starcoin_bridge_rpc_url,
+                &config.starcoin_bridge_proxy_address,
+                metrics.clone(),
+            );
+            let eth_client = EthClient::<MeteredEthHttpProvier>::new(
+                &config.eth_rpc_url,
+                HashSet::from([config.eth_bridge_proxy_address]),
+                metrics,
+                true,
+            )
+            .await?;
+            let fetch_timeout = Duration::from_secs(fetch_timeout_secs);
+            let source = RpcSource::new(
+                starcoin_bridge_client,
+                eth_client,
+                config.eth_bridge_proxy_address,
+                node_url,
+                // Leave room for the last attempt within the fetch timeout
+                fetch_timeout / 2,
+            )?;
+            dashboard::run(
+                Arc::new(source),
+                DashboardOptions {
+                    refresh_interval: Duration::from_secs(refresh_secs),
+                    committee_refresh_interval: Duration::from_secs(committee_refresh_secs),
+                    fetch_timeout,
+                },
+            )
+            .await?;
+        }
+        BridgeCommand::GovernanceHistory {
+            database_url,
+            chain_id,
+            action_type,
+            nonce_from,
+            nonce_to,
+            limit,
+        } => {
+            let filter = GovernanceHistoryFilter {
+                action_type,
+                nonce_from,
+                nonce_to,
+                limit,
+            };
+            let history = query_governance_history(&database_url, chain_id, &filter).await?;
+            print_output(args.output, &history)?;
+        }
+        BridgeCommand::AuditIndexer {
+            database_url,
+            config_path,
+            chain_id,
+            sample_size,
+        } => {
+            let chain_id = chain_id
+                .map(|chain_id| {
+                    BridgeChainId::try_from(chain_id)
+                        .map_err(|e| anyhow::anyhow!("Invalid chain id {}: {:?}", chain_id, e))
+                })
+                .transpose()?;
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let config = LoadedBridgeCliConfig::load_read_only(config).await?;
+            let evm_chain = config.evm_chain(chain_id)?;
+            let source = RpcAuditSource {
+                starcoin_bridge_client: StarcoinBridgeClient::with_metrics(
+                    &config.starcoin_bridge_rpc_url,
+                    &config.starcoin_bridge_proxy_address,
+                    metrics,
+                ),
+                bridge: EthStarcoinBridge::new(
+                    evm_chain.eth_bridge_proxy_address,
+                    evm_chain.provider(),
+                ),
+                deadline,
+            };
+            let report = run_indexer_audit(
+                &database_url,
+                &source,
+                evm_chain.chain_id as u8,
+                sample_size,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            let missing_nonces = report.missing_nonces();
+            if missing_nonces > 0 {
+                return Err(anyhow::anyhow!(
+                    "The indexer is missing {missing_nonces} deposits"
+                ));
+            }
+        }
+        BridgeCommand::WaitForAction {
+            config_path,
+            source_chain_id,
+            seq_num,
+            target,
+            timeout_secs,
+            until,
+        } => {
+            let config = BridgeCliConfig::load(config_path)
+                .expect("Couldn't load BridgeCliConfig")
+                .with_env_overrides(process_env);
+            let config = LoadedBridgeCliConfig::load_read_only(config).await?;
+            let starcoin = StarcoinStatusSource {
+                starcoin_bridge_client: StarcoinBridgeClient::with_metrics(
+                    &config.starcoin_bridge_rpc_url,
+                    &config.starcoin_bridge_proxy_address,
+                    metrics,
+                ),
+                source_chain_id,
+                seq_num,
+            };
+            let source: Box<dyn ActionStatusSource> = match target {
+                WaitTarget::Starcoin => Box::new(starcoin),
+                WaitTarget::Eth => {
+                    let evm_chain = config.evm_chain(None)?;
+                    Box::new(EthStatusSource {
+                        starcoin,
+                        bridge: EthStarcoinBridge::new(
+                            evm_chain.eth_bridge_proxy_address,
+                            evm_chain.provider(),
+                        ),
+                    })
+                }
+            };
+            // Ctrl-C kills the process as usual if the handler can't be set up
+            let interrupt = async {
+                if tokio::signal::ctrl_c().await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            };
+            let report = wait_for_action(
+                source.as_ref(),
+                source_chain_id,
+                seq_num,
+                target,
+                until,
+                Duration::from_secs(timeout_secs),
+                interrupt,
+            )
+            .await;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            let exit_code = report.exit_code();
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
     }
 
     Ok(())
 }
 
-#[derive(serde::Serialize, Default)]
-struct OutputEthBridge {
-    chain_id: u64,
-    bridge_proxy: EthAddress,
-    committee_proxy: EthAddress,
-    limiter_proxy: EthAddress,
-    config_proxy: EthAddress,
-    vault: EthAddress,
-    nonces: Nonces,
-}
-
-#[derive(serde::Serialize, Default)]
-struct Nonces {
-    token_transfer: u64,
-    blocklist_update: u64,
-    emergency_button: u64,
-    limit_update: u64,
-    asset_price_update: u64,
-    add_evm_tokens: u64,
-    contract_upgrade_bridge: u64,
-    contract_upgrade_committee: u64,
-    contract_upgrade_limiter: u64,
-    contract_upgrade_config: u64,
-}
-
 #[derive(serde::Serialize, Default)]
 struct Output<P: Default> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -601,8 +867,24 @@ struct OutputStarcoinBridge {
     total_stake: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     total_online_stake: Option<f32>,
+    // With `--ping`, online stake of non-blocklisted members in basis points
+    // of the total voting power, and whether it reaches the token transfer
+    // approval threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    online_stake_bps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quorum_online: Option<bool>,
     committee: Vec<OutputMember>,
     nonces: HashMap<BridgeActionType, u64>,
+    paused_tokens: Vec<u8>,
+    treasury: Vec<TreasuryToken>,
+    // Transfer limit and usage over the last 24 hours of each route
+    limiter: Vec<RouteLimit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    concentration: Option<ConcentrationReport>,
+    // Number of members per reported node version, with `--ping`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_distribution: Option<BTreeMap<String, usize>>,
 }
 
 #[derive(serde::Serialize)]
@@ -617,6 +899,11 @@ struct OutputMember {
     blocklisted: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    // Latency, status code and error class of the `--ping` probe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ping: Option<PingResult>,
 }
 
 #[derive(serde::Serialize, Default)]
@@ -624,3 +911,43 @@ struct OutputStarcoinBridgeRegistration {
     total_registered_stake: f32,
     committee: Vec<OutputMember>,
 }
+
+// The registry entry of `--network`, if given
+fn network_entry(network: Option<Network>) -> anyhow::Result<Option<(Network, NetworkEntry)>> {
+    let Some(network) = network else {
+        return Ok(None);
+    };
+    let entry = NetworkRegistry::load(process_env)?.get(&network)?.clone();
+    Ok(Some((network, entry)))
+}
+
+// The config at `config_path` with the network and env overrides, and a
+// Starcoin client for it. The loaded chains must match the network.
+async fn load_bridge_ops(
+    config_path: std::path::PathBuf,
+    network: Option<(Network, NetworkEntry)>,
+    deadline: Deadline,
+    metrics: Arc<BridgeMetrics>,
+) -> anyhow::Result<BridgeOps> {
+    let mut config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
+    if let Some((_, entry)) = &network {
+        config = entry.apply_to_config(config);
+    }
+    let config = config.with_env_overrides(process_env);
+    let config = deadline
+        .run("config load", LoadedBridgeCliConfig::load(config))
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    if let Some((network, entry)) = &network {
+        entry.check_config(network, &config).await?;
+    }
+    let starcoin_bridge_client = StarcoinBridgeClient::with_metrics(
+        &config.starcoin_bridge_rpc_url,
+        &config.starcoin_bridge_proxy_address,
+        metrics.clone(),
+    )
+    .with_gas_config(config.gas_config.clone());
+    Ok(BridgeOps::new(config, starcoin_bridge_client)
+        .with_deadline(deadline)
+        .with_metrics(metrics))
+}
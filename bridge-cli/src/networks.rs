@@ -0,0 +1,514 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Known bridge deployments, so commands can take `--network` instead of
+//! raw contract addresses and RPC urls.
+//!
+//! The built-in entries only carry what is published for each network.
+//! Anything else, and custom networks such as a local environment, come from
+//! the TOML file named by `BRIDGE_NETWORKS_FILE`, keyed by network name:
+//!
+//!   [testnet]
+//!   eth-rpc-url = "https://ethereum-sepolia-rpc.publicnode.com"
+//!
+//!   [local]
+//!   eth-chain-id = 31337
+//!   starcoin-bridge-chain-id = 2
+//!   eth-bridge-chain-id = 12
+//!   eth-bridge-proxy-address = "0x..."
+//!   eth-rpc-url = "http://127.0.0.1:8545"
+//!
+//! A value given as a flag wins over the network, which wins over the CLI
+//! config file.
+
+use crate::{BridgeCliConfig, ReadOnlyConfig, SEPOLIA_BRIDGE_PROXY_ADDR};
+use anyhow::{anyhow, Context};
+use ethers::providers::Middleware;
+use ethers::types::Address as EthAddress;
+use serde::Deserialize;
+use starcoin_bridge_types::bridge::BridgeChainId;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::info;
+
+pub const BRIDGE_NETWORKS_FILE_ENV: &str = "BRIDGE_NETWORKS_FILE";
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    // A network defined only in the networks file
+    Custom(String),
+}
+
+impl Network {
+    pub fn name(&self) -> &str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Custom(name) => name,
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" => Err(anyhow!("Network name can't be empty")),
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            name => Ok(Network::Custom(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// What is known about a network. Unknown values are None and are then taken
+// from the CLI config.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetworkEntry {
+    // Chain id the Eth RPC reports, e.g. 1 for Ethereum mainnet
+    pub eth_chain_id: Option<u64>,
+    pub starcoin_bridge_chain_id: Option<BridgeChainId>,
+    pub eth_bridge_chain_id: Option<BridgeChainId>,
+    pub eth_bridge_proxy_address: Option<EthAddress>,
+    pub starcoin_bridge_proxy_address: Option<String>,
+    pub eth_rpc_url: Option<String>,
+    pub starcoin_bridge_rpc_url: Option<String>,
+}
+
+// An entry of the networks file. Bridge chain ids are given as numbers, like
+// `--chain-id`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct NetworkOverride {
+    eth_chain_id: Option<u64>,
+    starcoin_bridge_chain_id: Option<u8>,
+    eth_bridge_chain_id: Option<u8>,
+    eth_bridge_proxy_address: Option<EthAddress>,
+    starcoin_bridge_proxy_address: Option<String>,
+    eth_rpc_url: Option<String>,
+    starcoin_bridge_rpc_url: Option<String>,
+}
+
+fn bridge_chain_id(id: Option<u8>) -> anyhow::Result<Option<BridgeChainId>> {
+    id.map(|id| {
+        BridgeChainId::try_from(id).map_err(|e| anyhow!("Invalid chain id {}: {:?}", id, e))
+    })
+    .transpose()
+}
+
+impl NetworkEntry {
+    // Sets the values `other` has, keeping the rest
+    fn merge(&mut self, other: NetworkOverride) -> anyhow::Result<()> {
+        let starcoin_bridge_chain_id = bridge_chain_id(other.starcoin_bridge_chain_id)?;
+        let eth_bridge_chain_id = bridge_chain_id(other.eth_bridge_chain_id)?;
+        self.eth_chain_id = other.eth_chain_id.or(self.eth_chain_id);
+        self.starcoin_bridge_chain_id = starcoin_bridge_chain_id.or(self.starcoin_bridge_chain_id);
+        self.eth_bridge_chain_id = eth_bridge_chain_id.or(self.eth_bridge_chain_id);
+        self.eth_bridge_proxy_address = other
+            .eth_bridge_proxy_address
+            .or(self.eth_bridge_proxy_address);
+        self.starcoin_bridge_proxy_address = other
+            .starcoin_bridge_proxy_address
+            .or(self.starcoin_bridge_proxy_address.take());
+        self.eth_rpc_url = other.eth_rpc_url.or(self.eth_rpc_url.take());
+        self.starcoin_bridge_rpc_url = other
+            .starcoin_bridge_rpc_url
+            .or(self.starcoin_bridge_rpc_url.take());
+        Ok(())
+    }
+
+    // Replaces the values of `config` that the network knows. Environment
+    // overrides are applied afterwards, so they still take precedence.
+    pub fn apply_to_config(&self, mut config: BridgeCliConfig) -> BridgeCliConfig {
+        if let Some(url) = &self.starcoin_bridge_rpc_url {
+            config.starcoin_bridge_rpc_url = url.clone();
+        }
+        if let Some(url) = &self.eth_rpc_url {
+            config.eth_rpc_url = url.clone();
+        }
+        if let Some(address) = &self.starcoin_bridge_proxy_address {
+            config.starcoin_bridge_proxy_address = address.clone();
+        }
+        if let Some(address) = self.eth_bridge_proxy_address {
+            config.eth_bridge_proxy_address = address;
+        }
+        config
+    }
+
+    // Refuses to talk to an Eth RPC or bridge of another network
+    pub fn check_eth_chain(
+        &self,
+        network: &Network,
+        eth_chain_id: u64,
+        eth_bridge_chain_id: Option<BridgeChainId>,
+    ) -> anyhow::Result<()> {
+        if let Some(expected) = self.eth_chain_id {
+            if expected != eth_chain_id {
+                return Err(anyhow!(
+                    "Eth RPC is on chain {}, but {} is on chain {}",
+                    eth_chain_id,
+                    network,
+                    expected
+                ));
+            }
+        }
+        if let (Some(expected), Some(actual)) = (self.eth_bridge_chain_id, eth_bridge_chain_id) {
+            if expected != actual {
+                return Err(anyhow!(
+                    "Eth bridge has chain id {:?}, but {} expects {:?}",
+                    actual,
+                    network,
+                    expected
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Refuses a `--chain-id` that isn't one of the network's bridge chains
+    pub fn check_bridge_chain_id(
+        &self,
+        network: &Network,
+        chain_id: BridgeChainId,
+    ) -> anyhow::Result<()> {
+        let known = [self.starcoin_bridge_chain_id, self.eth_bridge_chain_id]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if !known.is_empty() && !known.contains(&chain_id) {
+            return Err(anyhow!(
+                "Chain id {:?} is not part of {}, which has {:?}",
+                chain_id,
+                network,
+                known
+            ));
+        }
+        Ok(())
+    }
+
+    // Checks the chains `config` was loaded from against the network
+    pub async fn check_config(
+        &self,
+        network: &Network,
+        config: &ReadOnlyConfig,
+    ) -> anyhow::Result<()> {
+        let eth_chain_id = config
+            .evm_chain(None)?
+            .provider()
+            .get_chainid()
+            .await
+            .map_err(|e| anyhow!("Failed to read the chain id of the Eth RPC: {:?}", e))?;
+        self.check_eth_chain(
+            network,
+            eth_chain_id.as_u64(),
+            Some(config.default_evm_chain_id),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkRegistry {
+    networks: BTreeMap<Network, NetworkEntry>,
+}
+
+impl NetworkRegistry {
+    // The built-in networks. Mainnet addresses aren't published yet, so only
+    // its chain ids are known.
+    pub fn well_known() -> Self {
+        let mainnet = NetworkEntry {
+            eth_chain_id: Some(1),
+            starcoin_bridge_chain_id: Some(BridgeChainId::StarcoinMainnet),
+            eth_bridge_chain_id: Some(BridgeChainId::EthMainnet),
+            ..Default::default()
+        };
+        let testnet = NetworkEntry {
+            eth_chain_id: Some(11155111),
+            starcoin_bridge_chain_id: Some(BridgeChainId::StarcoinTestnet),
+            eth_bridge_chain_id: Some(BridgeChainId::EthSepolia),
+            eth_bridge_proxy_address: Some(
+                EthAddress::from_str(SEPOLIA_BRIDGE_PROXY_ADDR).unwrap(),
+            ),
+            ..Default::default()
+        };
+        Self {
+            networks: BTreeMap::from([(Network::Mainnet, mainnet), (Network::Testnet, testnet)]),
+        }
+    }
+
+    // The built-in networks, with the networks file named by
+    // `BRIDGE_NETWORKS_FILE` in `env` applied on top
+    pub fn load(env: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let mut registry = Self::well_known();
+        if let Some(path) = env(BRIDGE_NETWORKS_FILE_ENV) {
+            info!("Using networks from {path}");
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read networks file {path}"))?;
+            registry
+                .apply_overrides(&contents)
+                .with_context(|| format!("Invalid networks file {path}"))?;
+        }
+        Ok(registry)
+    }
+
+    pub fn apply_overrides(&mut self, contents: &str) -> anyhow::Result<()> {
+        let overrides: BTreeMap<String, NetworkOverride> = toml::from_str(contents)?;
+        for (name, entry) in overrides {
+            let network = Network::from_str(&name)?;
+            self.networks
+                .entry(network)
+                .or_default()
+                .merge(entry)
+                .with_context(|| format!("Invalid entry for {name}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, network: &Network) -> anyhow::Result<&NetworkEntry> {
+        self.networks.get(network).ok_or_else(|| {
+            anyhow!(
+                "Unknown network {}, define it in the file named by {}",
+                network,
+                BRIDGE_NETWORKS_FILE_ENV
+            )
+        })
+    }
+}
+
+// The Eth RPC url and bridge proxy of commands that take them as flags, from
+// the flags, else the network, else the CLI config
+pub fn resolve_eth_endpoint(
+    eth_rpc_url: Option<String>,
+    bridge_proxy: Option<EthAddress>,
+    network: Option<&NetworkEntry>,
+    config: Option<&BridgeCliConfig>,
+) -> anyhow::Result<(String, EthAddress)> {
+    let eth_rpc_url = eth_rpc_url
+        .or_else(|| network.and_then(|entry| entry.eth_rpc_url.clone()))
+        .or_else(|| config.map(|config| config.eth_rpc_url.clone()))
+        .ok_or_else(|| anyhow!("Eth rpc url, network or config path must be provided"))?;
+    let bridge_proxy = bridge_proxy
+        .or_else(|| network.and_then(|entry| entry.eth_bridge_proxy_address))
+        .or_else(|| config.map(|config| config.eth_bridge_proxy_address))
+        .ok_or_else(|| anyhow!("Bridge proxy address, network or config path must be provided"))?;
+    Ok((eth_rpc_url, bridge_proxy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BridgeCliConfig {
+        serde_yaml::from_str(
+            "starcoin-bridge-rpc-url: http://127.0.0.1:9850\n\
+             eth-rpc-url: http://config:8545\n\
+             starcoin-bridge-proxy-address: '0x246b237c16c761e9478783dd83f7004a'\n\
+             eth-bridge-proxy-address: '0x0000000000000000000000000000000000000001'\n",
+        )
+        .unwrap()
+    }
+
+    const OVERRIDES: &str = r#"
+        [testnet]
+        eth-rpc-url = "http://sepolia:8545"
+
+        [local]
+        eth-chain-id = 31337
+        starcoin-bridge-chain-id = 2
+        eth-bridge-chain-id = 12
+        eth-bridge-proxy-address = "0x0000000000000000000000000000000000000002"
+    "#;
+
+    #[test]
+    fn test_network_names() {
+        assert_eq!(Network::from_str("mainnet").unwrap(), Network::Mainnet);
+        assert_eq!(Network::from_str("Testnet").unwrap(), Network::Testnet);
+        assert_eq!(
+            Network::from_str("local").unwrap(),
+            Network::Custom("local".to_string())
+        );
+        assert!(Network::from_str(" ").is_err());
+        assert_eq!(Network::Custom("local".to_string()).to_string(), "local");
+    }
+
+    #[test]
+    fn test_network_flag() {
+        use crate::{Args, BridgeCommand};
+        use clap::Parser;
+
+        let args = Args::try_parse_from([
+            "bridge-cli",
+            "client",
+            "--config-path",
+            "/tmp/bridge-cli.yaml",
+            "deposit-native-ether-on-eth",
+            "--ether-amount",
+            "0.5",
+            "--target-chain",
+            "2",
+            "--starcoin-bridge-recipient-address",
+            "0x246b237c16c761e9478783dd83f7004a",
+            "--network",
+            "local",
+        ]);
+        let Ok(Args {
+            command: BridgeCommand::Client { network, .. },
+            ..
+        }) = args
+        else {
+            panic!("expected client");
+        };
+        assert_eq!(network, Some(Network::Custom("local".to_string())));
+
+        let args = Args::try_parse_from([
+            "bridge-cli",
+            "view-eth-bridge",
+            "--network",
+            "testnet",
+            "--config-path",
+            "/tmp/bridge-cli.yaml",
+        ])
+        .unwrap();
+        assert!(matches!(
+            args.command,
+            BridgeCommand::ViewEthBridge {
+                network: Some(Network::Testnet),
+                config_path: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_registry_overrides() {
+        let mut registry = NetworkRegistry::well_known();
+        let local = Network::Custom("local".to_string());
+        assert!(registry.get(&local).is_err());
+        registry.apply_overrides(OVERRIDES).unwrap();
+
+        // Overrides keep the built-in values they don't set
+        let testnet = registry.get(&Network::Testnet).unwrap();
+        assert_eq!(testnet.eth_rpc_url.as_deref(), Some("http://sepolia:8545"));
+        assert_eq!(testnet.eth_chain_id, Some(11155111));
+        assert_eq!(
+            testnet.eth_bridge_proxy_address,
+            Some(EthAddress::from_str(SEPOLIA_BRIDGE_PROXY_ADDR).unwrap())
+        );
+
+        let local = registry.get(&local).unwrap();
+        assert_eq!(local.eth_chain_id, Some(31337));
+        assert_eq!(
+            local.starcoin_bridge_chain_id,
+            Some(BridgeChainId::StarcoinCustom)
+        );
+        assert_eq!(local.eth_bridge_chain_id, Some(BridgeChainId::EthCustom));
+
+        assert!(registry
+            .apply_overrides("[local]\neth-bridge-chain-id = 99\n")
+            .is_err());
+        assert!(registry
+            .apply_overrides("[local]\neth-rpc = \"http://typo\"\n")
+            .is_err());
+    }
+
+    #[test]
+    fn test_registry_load_from_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("networks.toml");
+        std::fs::write(&path, OVERRIDES).unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let registry =
+            NetworkRegistry::load(|var| (var == BRIDGE_NETWORKS_FILE_ENV).then(|| path.clone()))
+                .unwrap();
+        assert!(registry.get(&Network::Custom("local".to_string())).is_ok());
+
+        let registry = NetworkRegistry::load(|_| None).unwrap();
+        assert_eq!(registry, NetworkRegistry::well_known());
+        assert!(NetworkRegistry::load(|_| Some("/nonexistent/networks.toml".into())).is_err());
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let mut registry = NetworkRegistry::well_known();
+        registry.apply_overrides(OVERRIDES).unwrap();
+        let testnet = registry.get(&Network::Testnet).unwrap();
+        let config = config();
+        let flag_proxy = EthAddress::from_low_u64_be(3);
+
+        // Flags win over the network
+        let (url, proxy) = resolve_eth_endpoint(
+            Some("http://flag:8545".to_string()),
+            Some(flag_proxy),
+            Some(testnet),
+            Some(&config),
+        )
+        .unwrap();
+        assert_eq!(url, "http://flag:8545");
+        assert_eq!(proxy, flag_proxy);
+
+        // The network wins over the config
+        let (url, proxy) = resolve_eth_endpoint(None, None, Some(testnet), Some(&config)).unwrap();
+        assert_eq!(url, "http://sepolia:8545");
+        assert_eq!(
+            proxy,
+            EthAddress::from_str(SEPOLIA_BRIDGE_PROXY_ADDR).unwrap()
+        );
+
+        // The config fills in what the network doesn't know
+        let mainnet = registry.get(&Network::Mainnet).unwrap();
+        let (url, proxy) = resolve_eth_endpoint(None, None, Some(mainnet), Some(&config)).unwrap();
+        assert_eq!(url, "http://config:8545");
+        assert_eq!(proxy, config.eth_bridge_proxy_address);
+
+        assert!(resolve_eth_endpoint(None, None, Some(mainnet), None).is_err());
+
+        // Same order for commands that load the whole config, with the
+        // environment still on top
+        let config = testnet.apply_to_config(config);
+        assert_eq!(config.eth_rpc_url, "http://sepolia:8545");
+        assert_eq!(config.starcoin_bridge_rpc_url, "http://127.0.0.1:9850");
+        let config = config.with_env_overrides(|var| {
+            (var == crate::ETH_RPC_URL_ENV).then(|| "http://env:8545".to_string())
+        });
+        assert_eq!(config.eth_rpc_url, "http://env:8545");
+    }
+
+    #[test]
+    fn test_chain_mismatch_refused() {
+        let registry = NetworkRegistry::well_known();
+        let testnet = registry.get(&Network::Testnet).unwrap();
+        testnet
+            .check_eth_chain(&Network::Testnet, 11155111, Some(BridgeChainId::EthSepolia))
+            .unwrap();
+
+        let err = testnet
+            .check_eth_chain(&Network::Testnet, 1, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("is on chain 11155111"), "{err}");
+        let err = testnet
+            .check_eth_chain(&Network::Testnet, 11155111, Some(BridgeChainId::EthMainnet))
+            .unwrap_err();
+        assert!(err.to_string().contains("expects EthSepolia"), "{err}");
+
+        testnet
+            .check_bridge_chain_id(&Network::Testnet, BridgeChainId::StarcoinTestnet)
+            .unwrap();
+        assert!(testnet
+            .check_bridge_chain_id(&Network::Testnet, BridgeChainId::StarcoinMainnet)
+            .is_err());
+        // Nothing to check against on a network without chain ids
+        NetworkEntry::default()
+            .check_bridge_chain_id(&Network::Mainnet, BridgeChainId::StarcoinMainnet)
+            .unwrap();
+    }
+}
@@ -0,0 +1,870 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Results of the client and governance commands. With `--output json` the
+// result document is the only thing written to stdout, progress goes to the
+// logs on stderr, so the CLI can be scripted with `jq`.
+
+use crate::committee_snapshot::CommitteeSnapshot;
+use clap::ValueEnum;
+use ethers::types::{TransactionReceipt, TxHash};
+use serde::Serialize;
+use starcoin_bridge::starcoin_bridge_client::StarcoinTxReceipt;
+use starcoin_bridge::tx_digest::{tagged, TxChain, TxDigest};
+use starcoin_bridge::types::{BridgeActionType, USD_MULTIPLIER};
+use std::fmt;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    // Human readable lines
+    #[default]
+    Table,
+}
+
+// Format of the log records written to stderr
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    // One JSON object per record, for log pipelines
+    Json,
+}
+
+pub fn print_output<T: Serialize + fmt::Display>(
+    format: OutputFormat,
+    output: &T,
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(output)?),
+        OutputFormat::Table => print!("{output}"),
+    }
+    Ok(())
+}
+
+// A submitted transaction. The hash is tagged with its chain, e.g. `eth:0x..`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TxOutput {
+    pub tx_hash: String,
+    // `success` / `failed` on Eth, the execution status on Starcoin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+}
+
+impl TxOutput {
+    pub fn from_eth_receipt(receipt: &TransactionReceipt) -> Self {
+        Self {
+            tx_hash: TxDigest::eth(receipt.transaction_hash).to_string(),
+            status: receipt.status.map(|status| {
+                if status.as_u64() == 1 {
+                    "success".to_string()
+                } else {
+                    "failed".to_string()
+                }
+            }),
+            gas_used: receipt.gas_used.map(|gas| gas.as_u64()),
+        }
+    }
+
+    // An Eth transaction known only by its hash, e.g. from a log
+    pub fn from_eth_tx_hash(tx_hash: TxHash) -> Self {
+        Self {
+            tx_hash: TxDigest::eth(tx_hash).to_string(),
+            status: None,
+            gas_used: None,
+        }
+    }
+
+    pub fn from_starcoin_receipt(receipt: &StarcoinTxReceipt) -> Self {
+        Self {
+            tx_hash: tagged(TxChain::Starcoin, &receipt.txn_hash),
+            status: Some(receipt.status.clone()),
+            gas_used: receipt.gas_used,
+        }
+    }
+
+    // From the `chain.get_transaction_info` view of a Starcoin transaction
+    pub fn from_starcoin_txn_info(tx_hash: String, txn_info: &serde_json::Value) -> Self {
+        let status = txn_info.get("status").map(|status| match status.as_str() {
+            Some(status) => status.to_string(),
+            None => status.to_string(),
+        });
+        let gas_used = txn_info.get("gas_used").and_then(|gas| match gas {
+            serde_json::Value::String(gas) => gas.parse().ok(),
+            gas => gas.as_u64(),
+        });
+        Self {
+            tx_hash,
+            status,
+            gas_used,
+        }
+    }
+}
+
+impl fmt::Display for TxOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tx_hash)?;
+        match (&self.status, self.gas_used) {
+            (Some(status), Some(gas_used)) => write!(f, " ({status}, gas used {gas_used})"),
+            (Some(status), None) => write!(f, " ({status})"),
+            (None, Some(gas_used)) => write!(f, " (gas used {gas_used})"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DepositOutput {
+    // Approval of the bridge to spend an ERC20 token, when one was needed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approve_tx: Option<TxOutput>,
+    pub deposit_tx: TxOutput,
+    // Nonce of the transfer, None when the deposit event was not found
+    pub nonce: Option<u64>,
+    // Command that claims the transfer once approved, for deposits that
+    // are claimed manually
+    #[serde(skip)]
+    pub claim_command: Option<String>,
+}
+
+impl fmt::Display for DepositOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(approve_tx) = &self.approve_tx {
+            writeln!(f, "Approve transaction: {approve_tx}")?;
+        }
+        writeln!(f, "Deposit transaction: {}", self.deposit_tx)?;
+        match self.nonce {
+            Some(nonce) => writeln!(f, "Deposit nonce: {nonce}")?,
+            None => writeln!(f, "Deposit nonce: unknown, no deposit event found")?,
+        }
+        if let Some(claim_command) = &self.claim_command {
+            writeln!(f, "Once approved, claim it with `{claim_command}`")?;
+        }
+        Ok(())
+    }
+}
+
+// A token `deposit-on-starcoin` accepts, from `--list-tokens`
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegisteredToken {
+    pub token_id: u8,
+    // What to pass as `--coin-type`
+    pub coin_type: String,
+    // None when the treasury has no decimal multiplier for the token
+    pub decimals: Option<u8>,
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegisteredTokensOutput {
+    pub tokens: Vec<RegisteredToken>,
+}
+
+impl fmt::Display for RegisteredTokensOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tokens.is_empty() {
+            return writeln!(f, "No tokens registered on the bridge");
+        }
+        for token in &self.tokens {
+            let decimals = token
+                .decimals
+                .map_or_else(|| "unknown".to_string(), |d| d.to_string());
+            write!(
+                f,
+                "{:>3}  {}  decimals {decimals}",
+                token.token_id, token.coin_type
+            )?;
+            if token.paused {
+                write!(f, "  (paused)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// What a native ether deposit would submit, with `--dry-run`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EtherDepositDryRunOutput {
+    // Decimal string, wei amounts overflow JSON numbers
+    pub amount_wei: String,
+    pub target_chain: u8,
+    pub recipient: String,
+    // None when the estimate failed, e.g. the deposit would revert
+    pub estimated_gas: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl fmt::Display for EtherDepositDryRunOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Would deposit {} wei to {} on chain {}",
+            self.amount_wei, self.recipient, self.target_chain
+        )?;
+        if let Some(gas) = self.estimated_gas {
+            writeln!(f, "Estimated gas: {gas}")?;
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "Gas estimation failed: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+// A native ether deposit, or what it would submit with `--dry-run`
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum EtherDepositOutput {
+    DryRun(EtherDepositDryRunOutput),
+    Deposited(DepositOutput),
+}
+
+impl fmt::Display for EtherDepositOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EtherDepositOutput::DryRun(dry_run) => dry_run.fmt(f),
+            EtherDepositOutput::Deposited(deposit) => deposit.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClaimStatus {
+    Claimed,
+    AlreadyClaimed,
+    // No transfer with this seq num on the source chain
+    NotFound,
+    // The transfer is not approved by the committee yet
+    NotSigned,
+    DryRun,
+}
+
+impl ClaimStatus {
+    // Same as the serialized name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaimStatus::Claimed => "claimed",
+            ClaimStatus::AlreadyClaimed => "already-claimed",
+            ClaimStatus::NotFound => "not-found",
+            ClaimStatus::NotSigned => "not-signed",
+            ClaimStatus::DryRun => "dry-run",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClaimOutput {
+    pub source_chain_id: u8,
+    pub seq_num: u64,
+    pub status: ClaimStatus,
+    // Approval of an Eth -> Starcoin transfer submitted before the claim
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approve_tx: Option<TxOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim_tx: Option<TxOutput>,
+    // With `--dry-run`, what would be submitted: the transactions on
+    // Starcoin, the gas estimate on Eth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<serde_json::Value>,
+    // Of a claim on Eth, the hash of the message the committee signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_digest: Option<String>,
+}
+
+impl ClaimOutput {
+    pub fn new(source_chain_id: u8, seq_num: u64, status: ClaimStatus) -> Self {
+        Self {
+            source_chain_id,
+            seq_num,
+            status,
+            approve_tx: None,
+            claim_tx: None,
+            dry_run: None,
+            message_digest: None,
+        }
+    }
+}
+
+impl fmt::Display for ClaimOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let transfer = format!(
+            "Transfer {} from chain {}",
+            self.seq_num, self.source_chain_id
+        );
+        match self.status {
+            ClaimStatus::Claimed => writeln!(f, "{transfer} claimed")?,
+            ClaimStatus::AlreadyClaimed => writeln!(f, "{transfer} is already claimed")?,
+            ClaimStatus::NotFound => writeln!(f, "{transfer} not found")?,
+            ClaimStatus::NotSigned => writeln!(f, "{transfer} has no signatures yet")?,
+            ClaimStatus::DryRun => writeln!(f, "{transfer} claim dry run")?,
+        }
+        if let Some(approve_tx) = &self.approve_tx {
+            writeln!(f, "Approve transaction: {approve_tx}")?;
+        }
+        if let Some(claim_tx) = &self.claim_tx {
+            writeln!(f, "Claim transaction: {claim_tx}")?;
+        }
+        if let Some(message_digest) = &self.message_digest {
+            writeln!(f, "Message digest: {message_digest}")?;
+        }
+        if let Some(dry_run) = &self.dry_run {
+            writeln!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(dry_run).map_err(|_| fmt::Error)?
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// A committee member's entry after `validator update-node-url`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NodeUrlUpdateOutput {
+    pub starcoin_address: String,
+    pub bridge_pubkey: String,
+    pub voting_power: u64,
+    pub url: String,
+    pub tx: TxOutput,
+}
+
+impl fmt::Display for NodeUrlUpdateOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Committee member {}", self.starcoin_address)?;
+        writeln!(f, "  bridge pubkey: {}", self.bridge_pubkey)?;
+        writeln!(f, "  voting power: {}", self.voting_power)?;
+        writeln!(f, "  url: {}", self.url)?;
+        writeln!(f, "Update transaction: {}", self.tx)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitteeRegistrationStatus {
+    Registered,
+    // The pubkey was registered before, nothing was submitted
+    AlreadyRegistered,
+}
+
+// A validator's registration after `register-committee`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeRegistrationOutput {
+    pub status: CommitteeRegistrationStatus,
+    pub starcoin_address: String,
+    pub bridge_pubkey: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx: Option<TxOutput>,
+}
+
+impl fmt::Display for CommitteeRegistrationOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            CommitteeRegistrationStatus::Registered => writeln!(f, "Registered committee member")?,
+            CommitteeRegistrationStatus::AlreadyRegistered => {
+                writeln!(f, "Bridge pubkey is already registered")?
+            }
+        }
+        writeln!(f, "  starcoin address: {}", self.starcoin_address)?;
+        writeln!(f, "  bridge pubkey: {}", self.bridge_pubkey)?;
+        writeln!(f, "  url: {}", self.url)?;
+        if let Some(tx) = &self.tx {
+            writeln!(f, "Registration transaction: {}", tx)?;
+        }
+        Ok(())
+    }
+}
+
+// The transfer limit or token price an action replaces, with the value it
+// sets. Amounts are in on-chain units of 1 / USD_MULTIPLIER.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UsdChange {
+    pub current: u64,
+    pub current_usd: f64,
+    pub new: u64,
+    pub new_usd: f64,
+}
+
+impl UsdChange {
+    pub fn new(current: u64, new: u64) -> Self {
+        Self {
+            current,
+            current_usd: current as f64 / USD_MULTIPLIER as f64,
+            new,
+            new_usd: new as f64 / USD_MULTIPLIER as f64,
+        }
+    }
+
+    // How many times larger the larger of the two values is, infinite when
+    // only one of them is 0
+    pub fn factor(&self) -> f64 {
+        let (low, high) = (self.current.min(self.new), self.current.max(self.new));
+        match (low, high) {
+            (_, 0) => 1.0,
+            (0, _) => f64::INFINITY,
+            _ => high as f64 / low as f64,
+        }
+    }
+}
+
+impl fmt::Display for UsdChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${:.4} -> ${:.4}", self.current_usd, self.new_usd)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GovernanceActionOutput {
+    pub action_type: BridgeActionType,
+    pub nonce: u64,
+    // Debug form of the certified action
+    pub action: String,
+    // Eth addresses of the committee members whose signatures certify the
+    // action
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signers: Vec<String>,
+    // For limit and price updates, the value on chain before the action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd_change: Option<UsdChange>,
+    // Outcome of the `eth_call` of an Eth transaction, with the decoded
+    // revert reason if it would revert
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx: Option<TxOutput>,
+    // Why the transaction was not executed, e.g. an Eth revert
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GovernanceOutput {
+    pub chain_id: u8,
+    pub dry_run: bool,
+    // The committee every action was signed by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee: Option<CommitteeSnapshot>,
+    pub actions: Vec<GovernanceActionOutput>,
+}
+
+impl fmt::Display for GovernanceOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(committee) = &self.committee {
+            writeln!(
+                f,
+                "Signed by the committee of epoch {}: {} members, hash {}",
+                committee.last_committee_update_epoch, committee.members, committee.committee_hash
+            )?;
+        }
+        for action in &self.actions {
+            writeln!(
+                f,
+                "{} action, nonce {}: {}",
+                action.action_type, action.nonce, action.action
+            )?;
+            if !action.signers.is_empty() {
+                writeln!(f, "  signed by: {}", action.signers.join(", "))?;
+            }
+            if let Some(usd_change) = &action.usd_change {
+                writeln!(f, "  USD value: {usd_change}")?;
+            }
+            if let Some(simulation) = &action.simulation {
+                writeln!(f, "  simulation: {simulation}")?;
+            }
+            if let Some(tx) = &action.tx {
+                writeln!(f, "  transaction: {tx}")?;
+            }
+            if let Some(error) = &action.error {
+                writeln!(f, "  error: {error}")?;
+            }
+        }
+        if self.dry_run {
+            writeln!(f, "Dryrun succeeded.")?;
+        }
+        Ok(())
+    }
+}
+
+// Result of `sync-committee-state`. Members are listed by Eth address.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeSyncOutput {
+    pub chain_id: u8,
+    pub to_blocklist: Vec<String>,
+    pub to_unblocklist: Vec<String>,
+    // Starcoin members without stake on the EVM chain, which a blocklist
+    // action can't fix
+    pub missing_on_eth: Vec<String>,
+    // Stake on the EVM chain of the online members that can sign, before and
+    // after the blocklist is synced
+    pub online_stake_before: u64,
+    pub online_stake_after: u64,
+    pub quorum: u64,
+    // The executed actions, with `--execute`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub governance: Option<GovernanceOutput>,
+}
+
+impl fmt::Display for CommitteeSyncOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for address in &self.to_unblocklist {
+            writeln!(f, "Unblocklist {address}")?;
+        }
+        for address in &self.to_blocklist {
+            writeln!(f, "Blocklist {address}")?;
+        }
+        for address in &self.missing_on_eth {
+            writeln!(
+                f,
+                "Not in the committee on chain {}: {address}",
+                self.chain_id
+            )?;
+        }
+        if self.to_blocklist.is_empty() && self.to_unblocklist.is_empty() {
+            return writeln!(f, "Blocklist of chain {} is in sync", self.chain_id);
+        }
+        writeln!(
+            f,
+            "Online signing stake: {} -> {} (quorum {})",
+            self.online_stake_before, self.online_stake_after, self.quorum
+        )?;
+        match &self.governance {
+            Some(governance) => write!(f, "{governance}"),
+            None => writeln!(f, "Dry run, pass --execute to submit the actions"),
+        }
+    }
+}
+
+// A governance action recorded by the bridge indexer
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GovernanceHistoryRow {
+    // None for actions indexed from Starcoin events, which carry no nonce
+    pub nonce: Option<i64>,
+    // The indexer's name of the action type
+    pub action_type: String,
+    pub tx_hash: String,
+    // None for rows indexed before block heights were recorded
+    pub block_height: Option<i64>,
+    pub timestamp_ms: i64,
+    pub sender: String,
+    // The decoded event of the action
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GovernanceHistoryOutput {
+    pub chain_id: u8,
+    pub actions: Vec<GovernanceHistoryRow>,
+}
+
+impl fmt::Display for GovernanceHistoryOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.actions.is_empty() {
+            return writeln!(
+                f,
+                "No governance actions recorded for chain {}",
+                self.chain_id
+            );
+        }
+        for action in &self.actions {
+            let nonce = action
+                .nonce
+                .map_or_else(|| "-".to_string(), |n| n.to_string());
+            let block = action
+                .block_height
+                .map_or_else(|| "-".to_string(), |b| b.to_string());
+            writeln!(
+                f,
+                "nonce {nonce} {} tx {} block {block} at {} ms by {}",
+                action.action_type, action.tx_hash, action.timestamp_ms, action.sender
+            )?;
+            writeln!(f, "  {}", action.payload)?;
+        }
+        Ok(())
+    }
+}
+
+// What the committee signs for a governance action, from `encode-action`.
+// Byte fields are 0x prefixed hex.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EncodedActionOutput {
+    pub action_type: BridgeActionType,
+    pub chain_id: u8,
+    pub nonce: u64,
+    // The message as parsed by the Move and Solidity bridges
+    pub message: String,
+    // Keccak256 of the signing payload
+    pub digest: String,
+    // `STARCOIN_BRIDGE_MESSAGE` followed by the message
+    pub signing_payload: String,
+}
+
+impl fmt::Display for EncodedActionOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} action, chain {}, nonce {}",
+            self.action_type, self.chain_id, self.nonce
+        )?;
+        writeln!(f, "  message: {}", self.message)?;
+        writeln!(f, "  digest: {}", self.digest)?;
+        writeln!(f, "  signing payload: {}", self.signing_payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address as EthAddress, H256, U256, U64};
+    use serde_json::json;
+
+    fn tx() -> TxOutput {
+        TxOutput {
+            tx_hash: "eth:0x01".to_string(),
+            status: Some("success".to_string()),
+            gas_used: Some(21000),
+        }
+    }
+
+    #[test]
+    fn test_tx_output_from_eth_receipt() {
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            status: Some(U64::from(0)),
+            gas_used: Some(U256::from(50_000)),
+            ..Default::default()
+        };
+        let output = TxOutput::from_eth_receipt(&receipt);
+        assert_eq!(
+            output.tx_hash,
+            TxDigest::eth(H256::repeat_byte(1)).to_string()
+        );
+        assert_eq!(output.status.as_deref(), Some("failed"));
+        assert_eq!(output.gas_used, Some(50_000));
+    }
+
+    #[test]
+    fn test_tx_output_from_starcoin_txn_info() {
+        let output = TxOutput::from_starcoin_txn_info(
+            "stc:0x02".to_string(),
+            &json!({"status": "Executed", "gas_used": "12345"}),
+        );
+        assert_eq!(output.status.as_deref(), Some("Executed"));
+        assert_eq!(output.gas_used, Some(12345));
+
+        // Aborts are reported as an object
+        let output = TxOutput::from_starcoin_txn_info(
+            "stc:0x02".to_string(),
+            &json!({"status": {"MoveAbort": {"abort_code": "7"}}, "gas_used": 10}),
+        );
+        assert_eq!(
+            output.status.as_deref(),
+            Some(r#"{"MoveAbort":{"abort_code":"7"}}"#)
+        );
+        assert_eq!(output.gas_used, Some(10));
+    }
+
+    #[test]
+    fn test_deposit_output_json() {
+        let output = DepositOutput {
+            approve_tx: None,
+            deposit_tx: tx(),
+            nonce: Some(3),
+            claim_command: Some("client claim-on-eth --seq-num 3".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({
+                "deposit-tx": {"tx-hash": "eth:0x01", "status": "success", "gas-used": 21000},
+                "nonce": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_ether_deposit_dry_run_output() {
+        let output = EtherDepositDryRunOutput {
+            amount_wei: "500000000000000000".to_string(),
+            target_chain: 2,
+            recipient: "0x01".to_string(),
+            estimated_gas: Some(60000),
+            error: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({
+                "amount-wei": "500000000000000000",
+                "target-chain": 2,
+                "recipient": "0x01",
+                "estimated-gas": 60000,
+            })
+        );
+        assert_eq!(
+            output.to_string(),
+            "Would deposit 500000000000000000 wei to 0x01 on chain 2\nEstimated gas: 60000\n"
+        );
+    }
+
+    #[test]
+    fn test_claim_output_json() {
+        let mut output = ClaimOutput::new(12, 5, ClaimStatus::Claimed);
+        output.claim_tx = Some(tx());
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({
+                "source-chain-id": 12,
+                "seq-num": 5,
+                "status": "claimed",
+                "claim-tx": {"tx-hash": "eth:0x01", "status": "success", "gas-used": 21000},
+            })
+        );
+
+        let mut output = ClaimOutput::new(12, 6, ClaimStatus::AlreadyClaimed);
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({"source-chain-id": 12, "seq-num": 6, "status": "already-claimed"})
+        );
+        output.message_digest = Some("0x32b7".to_string());
+        assert_eq!(
+            serde_json::to_value(&output).unwrap()["message-digest"],
+            "0x32b7"
+        );
+        assert!(output.to_string().contains("Message digest: 0x32b7"));
+
+        // The claim of an already claimed transfer, as found in the logs
+        let mut output = ClaimOutput::new(12, 6, ClaimStatus::AlreadyClaimed);
+        output.claim_tx = Some(TxOutput::from_eth_tx_hash(H256::repeat_byte(1)));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            json["claim-tx"],
+            json!({"tx-hash": TxDigest::eth(H256::repeat_byte(1)).to_string()})
+        );
+    }
+
+    #[test]
+    fn test_committee_registration_output_json() {
+        let output = CommitteeRegistrationOutput {
+            status: CommitteeRegistrationStatus::AlreadyRegistered,
+            starcoin_address: "0x01".to_string(),
+            bridge_pubkey: "02aa".to_string(),
+            url: "http://127.0.0.1:9191".to_string(),
+            tx: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({
+                "status": "already-registered",
+                "starcoin-address": "0x01",
+                "bridge-pubkey": "02aa",
+                "url": "http://127.0.0.1:9191",
+            })
+        );
+        assert!(output
+            .to_string()
+            .starts_with("Bridge pubkey is already registered\n"));
+    }
+
+    #[test]
+    fn test_governance_dry_run_output_json() {
+        let output = GovernanceOutput {
+            chain_id: 12,
+            dry_run: true,
+            committee: None,
+            actions: vec![GovernanceActionOutput {
+                action_type: BridgeActionType::EmergencyButton,
+                nonce: 4,
+                action: "EmergencyAction".to_string(),
+                signers: vec![],
+                usd_change: None,
+                simulation: None,
+                tx: None,
+                error: None,
+            }],
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["chain-id"], json!(12));
+        assert_eq!(value["dry-run"], json!(true));
+        let action = &value["actions"][0];
+        let mut keys = action.as_object().unwrap().keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, vec!["action", "action-type", "nonce"]);
+        assert_eq!(action["nonce"], json!(4));
+        assert_eq!(
+            action["action-type"],
+            serde_json::to_value(BridgeActionType::EmergencyButton).unwrap()
+        );
+
+        let mut output = output;
+        output.actions[0].signers = vec![format!("{:?}", EthAddress::repeat_byte(1))];
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            value["actions"][0]["signers"],
+            json!(["0x0101010101010101010101010101010101010101"])
+        );
+        assert!(output
+            .to_string()
+            .contains("  signed by: 0x0101010101010101010101010101010101010101\n"));
+
+        output.committee = Some(CommitteeSnapshot {
+            last_committee_update_epoch: 5,
+            committee_hash: "0xab".to_string(),
+            members: 4,
+        });
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            value["committee"],
+            json!({"last-committee-update-epoch": 5, "committee-hash": "0xab", "members": 4})
+        );
+        assert!(output
+            .to_string()
+            .starts_with("Signed by the committee of epoch 5: 4 members, hash 0xab\n"));
+    }
+
+    #[test]
+    fn test_governance_history_output() {
+        let mut output = GovernanceHistoryOutput {
+            chain_id: 1,
+            actions: vec![],
+        };
+        assert_eq!(
+            output.to_string(),
+            "No governance actions recorded for chain 1\n"
+        );
+
+        output.actions.push(GovernanceHistoryRow {
+            nonce: None,
+            action_type: "EmergencyOperation".to_string(),
+            tx_hash: "stc:0x01".to_string(),
+            block_height: Some(42),
+            timestamp_ms: 1000,
+            sender: "0x02".to_string(),
+            payload: json!({"frozen": true}),
+        });
+        assert_eq!(
+            output.to_string(),
+            "nonce - EmergencyOperation tx stc:0x01 block 42 at 1000 ms by 0x02\n  {\"frozen\":true}\n"
+        );
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["actions"][0]["block-height"], json!(42));
+        assert_eq!(value["actions"][0]["nonce"], json!(null));
+    }
+}
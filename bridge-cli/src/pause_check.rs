@@ -0,0 +1,385 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pre-flight pause check of `bridge-cli client` deposits and claims. During
+//! an emergency pause the bridge contracts revert every transfer, so the
+//! check fails the command before it spends any gas.
+//!
+//! Chains are read through `PauseSource`, so the check can be tested without
+//! a chain.
+
+use crate::{BridgeClientCommands, LoadedBridgeCliConfig};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use starcoin_bridge::abi::EthStarcoinBridge;
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge_types::bridge::BridgeChainId;
+use std::fmt;
+
+// How far back the `Paused` event of a paused Eth bridge is looked for. Many
+// RPCs refuse log queries over wider ranges.
+const PAUSED_EVENT_LOOKBACK_BLOCKS: u64 = 10_000;
+
+// A side of the bridge that can be paused. The Starcoin bridge is the one of
+// the configured RPC, whatever its chain id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BridgeSide {
+    Starcoin,
+    Evm(BridgeChainId),
+}
+
+impl fmt::Display for BridgeSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeSide::Starcoin => write!(f, "Starcoin"),
+            BridgeSide::Evm(chain_id) => write!(f, "{:?}", chain_id),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PauseState {
+    Active,
+    // `since` says when the pause started, if it is known
+    Paused { since: Option<String> },
+}
+
+#[async_trait]
+pub trait PauseSource {
+    async fn pause_state(&self, side: BridgeSide) -> anyhow::Result<PauseState>;
+}
+
+impl BridgeClientCommands {
+    // The sides a command submits transactions to, plus the destination of
+    // a deposit, whose transfer could not be claimed until the pause ends
+    pub fn sides_to_check(
+        &self,
+        default_evm_chain: BridgeChainId,
+        evm_chains: &[BridgeChainId],
+    ) -> anyhow::Result<Vec<BridgeSide>> {
+        let sides = match self {
+            BridgeClientCommands::DepositNativeEtherOnEth { eth_chain, .. }
+            | BridgeClientCommands::DepositErc20OnEth { eth_chain, .. } => {
+                let eth_chain = match eth_chain {
+                    Some(chain_id) => BridgeChainId::try_from(*chain_id)
+                        .map_err(|e| anyhow!("Invalid eth chain id: {:?}", e))?,
+                    None => default_evm_chain,
+                };
+                vec![BridgeSide::Evm(eth_chain), BridgeSide::Starcoin]
+            }
+            // Listing the tokens submits nothing
+            BridgeClientCommands::DepositOnstarcoin {
+                list_tokens: true, ..
+            } => vec![],
+            BridgeClientCommands::DepositOnstarcoin { target_chain, .. } => {
+                let mut sides = vec![BridgeSide::Starcoin];
+                if let Some(target_chain) = target_chain {
+                    let target_chain = BridgeChainId::try_from(*target_chain)
+                        .map_err(|e| anyhow!("Invalid chain id: {:?}", e))?;
+                    sides.push(BridgeSide::Evm(target_chain));
+                }
+                sides
+            }
+            // The target chain is only known from the transfer message, so
+            // every configured EVM chain is checked
+            BridgeClientCommands::ClaimOnEth { .. } => {
+                evm_chains.iter().copied().map(BridgeSide::Evm).collect()
+            }
+            BridgeClientCommands::ClaimOnStarcoin { .. }
+            | BridgeClientCommands::AutoClaim { .. } => vec![BridgeSide::Starcoin],
+        };
+        Ok(sides)
+    }
+}
+
+// Fails on the first of `sides` that is paused
+pub async fn ensure_not_paused(
+    source: &impl PauseSource,
+    sides: &[BridgeSide],
+) -> anyhow::Result<()> {
+    for side in sides {
+        if let PauseState::Paused { since } = source.pause_state(*side).await? {
+            let since = since
+                .map(|since| format!(" since {since}"))
+                .unwrap_or_default();
+            return Err(anyhow!(
+                "Bridge is paused on {side}{since}, pass --ignore-paused to submit anyway"
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub struct RpcPauseSource<'a> {
+    pub config: &'a LoadedBridgeCliConfig,
+    pub starcoin_bridge_client: &'a StarcoinBridgeClient,
+}
+
+#[async_trait]
+impl PauseSource for RpcPauseSource<'_> {
+    async fn pause_state(&self, side: BridgeSide) -> anyhow::Result<PauseState> {
+        let chain_id = match side {
+            // The bridge summary doesn't record when it was frozen
+            BridgeSide::Starcoin => {
+                let paused = self
+                    .starcoin_bridge_client
+                    .is_bridge_paused()
+                    .await
+                    .map_err(|e| anyhow!("Failed to read the Starcoin bridge state: {:?}", e))?;
+                return Ok(if paused {
+                    PauseState::Paused { since: None }
+                } else {
+                    PauseState::Active
+                });
+            }
+            BridgeSide::Evm(chain_id) => chain_id,
+        };
+        let evm_chain = self.config.evm_chain(Some(chain_id))?;
+        let bridge =
+            EthStarcoinBridge::new(evm_chain.eth_bridge_proxy_address, evm_chain.provider());
+        let paused =
+            bridge.paused().call().await.map_err(|e| {
+                anyhow!("Failed to read the bridge state on {:?}: {:?}", chain_id, e)
+            })?;
+        if !paused {
+            return Ok(PauseState::Active);
+        }
+        // Best effort, the pause is reported either way
+        let latest = evm_chain.provider().get_block_number().await.ok();
+        let since = match latest {
+            Some(latest) => {
+                let from_block = latest.as_u64().saturating_sub(PAUSED_EVENT_LOOKBACK_BLOCKS);
+                bridge
+                    .paused_filter()
+                    .from_block(from_block)
+                    .to_block(latest)
+                    .query_with_meta()
+                    .await
+                    .ok()
+                    .map(|events| match events.last() {
+                        Some((_, meta)) => format!("block {}", meta.block_number),
+                        None => format!("before block {from_block}"),
+                    })
+            }
+            None => None,
+        };
+        Ok(PauseState::Paused { since })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address as EthAddress;
+    use starcoin_bridge_types::base_types::StarcoinAddress;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[derive(Default)]
+    struct FakeSource {
+        paused: HashMap<BridgeSide, Option<String>>,
+    }
+
+    #[async_trait]
+    impl PauseSource for FakeSource {
+        async fn pause_state(&self, side: BridgeSide) -> anyhow::Result<PauseState> {
+            Ok(match self.paused.get(&side) {
+                Some(since) => PauseState::Paused {
+                    since: since.clone(),
+                },
+                None => PauseState::Active,
+            })
+        }
+    }
+
+    fn paused_on(side: BridgeSide, since: Option<&str>) -> FakeSource {
+        FakeSource {
+            paused: HashMap::from([(side, since.map(str::to_string))]),
+        }
+    }
+
+    fn commands() -> Vec<(&'static str, BridgeClientCommands)> {
+        vec![
+            (
+                "deposit-native-ether-on-eth",
+                BridgeClientCommands::DepositNativeEtherOnEth {
+                    ether_amount: "1".to_string(),
+                    target_chain: BridgeChainId::StarcoinCustom as u8,
+                    starcoin_bridge_recipient_address: StarcoinAddress::new([7; 16]),
+                    allow_unknown_account: false,
+                    eth_chain: None,
+                    dry_run: false,
+                    force: false,
+                },
+            ),
+            (
+                "deposit-erc20-on-eth",
+                BridgeClientCommands::DepositErc20OnEth {
+                    token_address: EthAddress::repeat_byte(9),
+                    amount: "1".to_string(),
+                    token_decimals: None,
+                    target_chain: BridgeChainId::StarcoinCustom as u8,
+                    starcoin_bridge_recipient_address: StarcoinAddress::new([7; 16]),
+                    approve: false,
+                    allow_unknown_account: false,
+                    eth_chain: Some(BridgeChainId::EthCustom as u8),
+                    force: false,
+                },
+            ),
+            (
+                "deposit-on-starcoin",
+                BridgeClientCommands::DepositOnstarcoin {
+                    amount: Some(1),
+                    coin_type: Some("0x1::STC::STC".to_string()),
+                    target_chain: Some(BridgeChainId::EthCustom as u8),
+                    recipient_address: Some(EthAddress::repeat_byte(9)),
+                    allow_contract_recipient: false,
+                    force: false,
+                    list_tokens: false,
+                },
+            ),
+            (
+                "claim-on-eth",
+                BridgeClientCommands::ClaimOnEth {
+                    seq_num: 1,
+                    dry_run: false,
+                },
+            ),
+            (
+                "claim-on-starcoin",
+                BridgeClientCommands::ClaimOnStarcoin {
+                    source_chain_id: BridgeChainId::EthCustom as u8,
+                    seq_num: 1,
+                    eth_tx_hash: None,
+                    event_index: None,
+                    dry_run: false,
+                },
+            ),
+            (
+                "auto-claim",
+                BridgeClientCommands::AutoClaim {
+                    poll_interval_secs: 30,
+                    source_chain_id: BridgeChainId::EthCustom as u8,
+                    start_seq_num: 0,
+                    any_recipient: false,
+                    state_file: PathBuf::from("auto-claim-state.json"),
+                    dry_run: false,
+                    metrics_port: None,
+                },
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_paused_on_one_side() {
+        let eth = BridgeSide::Evm(BridgeChainId::EthCustom);
+        for (name, cmd) in commands() {
+            let sides = cmd
+                .sides_to_check(BridgeChainId::EthCustom, &[BridgeChainId::EthCustom])
+                .unwrap();
+            ensure_not_paused(&FakeSource::default(), &sides)
+                .await
+                .unwrap();
+
+            // Deposits need both sides, claims only the side they claim on
+            let (starcoin_blocks, eth_blocks) = match name {
+                "claim-on-eth" => (false, true),
+                "claim-on-starcoin" | "auto-claim" => (true, false),
+                _ => (true, true),
+            };
+            let result = ensure_not_paused(&paused_on(BridgeSide::Starcoin, None), &sides).await;
+            assert_eq!(result.is_err(), starcoin_blocks, "{name}");
+            if let Err(err) = result {
+                assert_eq!(
+                    err.to_string(),
+                    "Bridge is paused on Starcoin, pass --ignore-paused to submit anyway"
+                );
+            }
+            let result = ensure_not_paused(&paused_on(eth, Some("block 42")), &sides).await;
+            assert_eq!(result.is_err(), eth_blocks, "{name}");
+            if let Err(err) = result {
+                assert_eq!(
+                    err.to_string(),
+                    "Bridge is paused on EthCustom since block 42, pass --ignore-paused to submit anyway"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sides_to_check() {
+        let commands = commands()
+            .into_iter()
+            .map(|(_, cmd)| cmd)
+            .collect::<Vec<_>>();
+        let [deposit_ether, deposit_erc20, deposit_on_starcoin, claim_on_eth, ..] = &commands[..]
+        else {
+            panic!("Missing commands");
+        };
+        let evm_chains = [BridgeChainId::EthCustom, BridgeChainId::EthSepolia];
+
+        // Deposits from Eth default to the default EVM chain
+        assert_eq!(
+            deposit_ether
+                .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+                .unwrap(),
+            vec![
+                BridgeSide::Evm(BridgeChainId::EthSepolia),
+                BridgeSide::Starcoin
+            ]
+        );
+        assert_eq!(
+            deposit_erc20
+                .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+                .unwrap(),
+            vec![
+                BridgeSide::Evm(BridgeChainId::EthCustom),
+                BridgeSide::Starcoin
+            ]
+        );
+        assert_eq!(
+            deposit_on_starcoin
+                .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+                .unwrap(),
+            vec![
+                BridgeSide::Starcoin,
+                BridgeSide::Evm(BridgeChainId::EthCustom)
+            ]
+        );
+        assert_eq!(
+            claim_on_eth
+                .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+                .unwrap(),
+            evm_chains.map(BridgeSide::Evm).to_vec()
+        );
+
+        let bad_chain = BridgeClientCommands::DepositOnstarcoin {
+            amount: Some(1),
+            coin_type: Some("0x1::STC::STC".to_string()),
+            target_chain: Some(99),
+            recipient_address: Some(EthAddress::repeat_byte(9)),
+            allow_contract_recipient: false,
+            force: false,
+            list_tokens: false,
+        };
+        let err = bad_chain
+            .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid chain id"), "{err}");
+
+        let list_tokens = BridgeClientCommands::DepositOnstarcoin {
+            amount: None,
+            coin_type: None,
+            target_chain: None,
+            recipient_address: None,
+            allow_contract_recipient: false,
+            force: false,
+            list_tokens: true,
+        };
+        assert!(list_tokens
+            .sides_to_check(BridgeChainId::EthSepolia, &evm_chains)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Certified bridge actions saved to a file, so that committee signatures
+// collected by `governance --dry-run --export-signatures` can be submitted
+// later, e.g. from an air-gapped machine, with `execute-signed-action`.
+
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use starcoin_bridge::crypto::{
+    BridgeAuthorityPublicKeyBytes, BridgeAuthorityRecoverableSignature, BridgeAuthoritySignInfo,
+};
+use starcoin_bridge::types::{
+    BridgeAction, BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
+    VerifiedCertifiedBridgeAction,
+};
+use starcoin_bridge_types::bridge::BridgeChainId;
+use starcoin_bridge_vm_types::bridge::committee::TOTAL_VOTING_POWER;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SignedActionsFile {
+    // Chain the actions execute on
+    pub chain_id: u8,
+    // In the order they are executed
+    pub actions: Vec<SignedAction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SignedAction {
+    pub action: BridgeAction,
+    pub signatures: Vec<AuthoritySignature>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthoritySignature {
+    // Hex of the compressed bridge authority public key
+    pub public_key: String,
+    // Hex of the recoverable signature over the action's message bytes
+    pub signature: String,
+}
+
+impl SignedActionsFile {
+    pub fn new(
+        chain_id: BridgeChainId,
+        certified_actions: &[VerifiedCertifiedBridgeAction],
+    ) -> Self {
+        Self {
+            chain_id: chain_id as u8,
+            actions: certified_actions.iter().map(SignedAction::from).collect(),
+        }
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signed actions from {:?}", path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse signed actions in {:?}", path))
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write signed actions to {:?}", path))
+    }
+
+    // Checks every action against `committee`, as it is now rather than when
+    // the signatures were collected, and that it executes on `chain_id`
+    pub fn verify(
+        &self,
+        chain_id: BridgeChainId,
+        committee: &BridgeCommittee,
+    ) -> anyhow::Result<Vec<VerifiedCertifiedBridgeAction>> {
+        if self.chain_id != chain_id as u8 {
+            return Err(anyhow!(
+                "Signed actions are for chain {}, not {:?}",
+                self.chain_id,
+                chain_id
+            ));
+        }
+        self.actions
+            .iter()
+            .map(|signed_action| {
+                if signed_action.action.chain_id() != chain_id {
+                    return Err(anyhow!(
+                        "Action {:?} doesn't execute on {:?}",
+                        signed_action.action,
+                        chain_id
+                    ));
+                }
+                signed_action.verify(committee)
+            })
+            .collect()
+    }
+}
+
+impl From<&VerifiedCertifiedBridgeAction> for SignedAction {
+    fn from(certified_action: &VerifiedCertifiedBridgeAction) -> Self {
+        Self {
+            action: certified_action.data().clone(),
+            signatures: certified_action
+                .auth_sig()
+                .signatures
+                .iter()
+                .map(|(public_key, signature)| AuthoritySignature {
+                    public_key: Hex::encode(public_key.as_bytes()),
+                    signature: Hex::encode(signature.as_bytes()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SignedAction {
+    // Each signature must be from an active committee member and over this
+    // action, and together they must hold the voting power the action needs
+    pub fn verify(
+        &self,
+        committee: &BridgeCommittee,
+    ) -> anyhow::Result<VerifiedCertifiedBridgeAction> {
+        let mut signatures = BTreeMap::new();
+        let mut voting_power = 0;
+        for signature in &self.signatures {
+            let public_key: BridgeAuthorityPublicKeyBytes = signature
+                .public_key
+                .parse()
+                .map_err(|e| anyhow!("Invalid public key {}: {}", signature.public_key, e))?;
+            let member = committee
+                .member(&public_key)
+                .ok_or_else(|| anyhow!("{} is not a committee member", signature.public_key))?;
+            if member.is_blocklisted {
+                return Err(anyhow!(
+                    "Committee member {} is blocklisted",
+                    signature.public_key
+                ));
+            }
+            let recoverable_signature = Hex::decode(&signature.signature)
+                .map_err(|e| anyhow!("{}", e))
+                .and_then(|bytes| {
+                    BridgeAuthorityRecoverableSignature::from_bytes(&bytes)
+                        .map_err(|e| anyhow!("{}", e))
+                })
+                .with_context(|| format!("Invalid signature of {}", signature.public_key))?;
+            BridgeAuthoritySignInfo {
+                authority_pub_key: member.pubkey.clone(),
+                signature: recoverable_signature.clone(),
+            }
+            .verify(&self.action, committee)
+            .map_err(|e| {
+                anyhow!(
+                    "Signature of {} doesn't verify: {:?}",
+                    signature.public_key,
+                    e
+                )
+            })?;
+            if signatures
+                .insert(public_key, recoverable_signature)
+                .is_some()
+            {
+                return Err(anyhow!("{} signed more than once", signature.public_key));
+            }
+            voting_power += member.voting_power;
+        }
+        let threshold = self.action.approval_threshold();
+        if voting_power < threshold {
+            return Err(anyhow!(
+                "Signatures hold {} of {} voting power, {} is needed for {:?}",
+                voting_power,
+                TOTAL_VOTING_POWER,
+                threshold,
+                self.action
+            ));
+        }
+        Ok(VerifiedCertifiedBridgeAction::new_from_verified(
+            CertifiedBridgeAction::new_from_data_and_sig(
+                self.action.clone(),
+                BridgeCommitteeValiditySignInfo { signatures },
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::KeyPair;
+    use starcoin_bridge::crypto::BridgeAuthorityKeyPair;
+    use starcoin_bridge::types::{
+        BridgeAuthority, EmergencyAction, EmergencyActionType, LimitUpdateAction,
+    };
+    use starcoin_bridge_types::base_types::StarcoinAddress;
+    use starcoin_bridge_types::bridge::BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER;
+
+    fn test_committee() -> (BridgeCommittee, BridgeAuthorityKeyPair) {
+        let (_, key): (_, Secp256k1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+        let committee = BridgeCommittee::new(vec![BridgeAuthority {
+            starcoin_bridge_address: StarcoinAddress::ZERO,
+            pubkey: key.public().clone(),
+            voting_power: BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER,
+            base_url: "http://127.0.0.1:9191".to_string(),
+            is_blocklisted: false,
+        }])
+        .unwrap();
+        (committee, key)
+    }
+
+    fn certify(
+        action: BridgeAction,
+        key: &BridgeAuthorityKeyPair,
+    ) -> VerifiedCertifiedBridgeAction {
+        let sig = BridgeAuthoritySignInfo::new(&action, key);
+        VerifiedCertifiedBridgeAction::new_from_verified(
+            CertifiedBridgeAction::new_from_data_and_sig(
+                action,
+                BridgeCommitteeValiditySignInfo {
+                    signatures: BTreeMap::from([(sig.authority_pub_key_bytes(), sig.signature)]),
+                },
+            ),
+        )
+    }
+
+    fn actions() -> Vec<BridgeAction> {
+        vec![
+            BridgeAction::EmergencyAction(EmergencyAction {
+                nonce: 3,
+                chain_id: BridgeChainId::StarcoinCustom,
+                action_type: EmergencyActionType::Pause,
+            }),
+            BridgeAction::LimitUpdateAction(LimitUpdateAction {
+                nonce: 7,
+                chain_id: BridgeChainId::StarcoinCustom,
+                sending_chain_id: BridgeChainId::EthCustom,
+                new_usd_limit: 1_000_000,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_signed_actions_round_trip() {
+        let (committee, key) = test_committee();
+        let certified_actions: Vec<_> = actions()
+            .into_iter()
+            .map(|action| certify(action, &key))
+            .collect();
+        let file = SignedActionsFile::new(BridgeChainId::StarcoinCustom, &certified_actions);
+
+        let path = std::env::temp_dir().join(format!("signed-actions-{}.json", std::process::id()));
+        file.write(&path).unwrap();
+        let read = SignedActionsFile::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, file);
+
+        let verified = read
+            .verify(BridgeChainId::StarcoinCustom, &committee)
+            .unwrap();
+        assert_eq!(verified.len(), 2);
+        for (verified, certified) in verified.iter().zip(&certified_actions) {
+            assert_eq!(verified.data(), certified.data());
+            assert_eq!(
+                verified.auth_sig().signatures,
+                certified.auth_sig().signatures
+            );
+        }
+
+        // The actions execute on Starcoin only
+        assert!(read.verify(BridgeChainId::EthCustom, &committee).is_err());
+    }
+
+    #[test]
+    fn test_signed_action_rejects_tampering() {
+        let (committee, key) = test_committee();
+        let certified_action = certify(actions().remove(1), &key);
+        let signed_action = SignedAction::from(&certified_action);
+        signed_action.verify(&committee).unwrap();
+
+        // A raised limit is no longer covered by the signature
+        let mut tampered = signed_action.clone();
+        let BridgeAction::LimitUpdateAction(action) = &mut tampered.action else {
+            unreachable!()
+        };
+        action.new_usd_limit *= 10;
+        assert!(tampered.verify(&committee).is_err());
+
+        // Nor is a signature from outside the committee
+        let (other_committee, _) = test_committee();
+        assert!(signed_action.verify(&other_committee).is_err());
+
+        // Without signatures there is no voting power
+        let unsigned = SignedAction {
+            signatures: vec![],
+            ..signed_action
+        };
+        let err = unsigned.verify(&committee).unwrap_err();
+        assert!(err.to_string().contains("voting power"), "{}", err);
+    }
+}
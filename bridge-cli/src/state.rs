@@ -0,0 +1,868 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// State the CLI keeps on disk between commands: the certificate cache of
+// `governance` and the signed action files of `--export-signatures`. `state
+// export` bundles it into a zstd compressed tar archive with a manifest, so an
+// operator can move to another machine with `state import`.
+//
+// Paths are relative to the state directory, the working directory by
+// default, as the paths the commands write to are. Signed action files are
+// the JSON files at its top level that parse as one.
+
+use crate::certificate_cache::{now_ms, CacheEntry};
+use crate::signed_actions::SignedActionsFile;
+use crate::BridgeCliConfig;
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Keccak256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use tracing::warn;
+
+// Bumped whenever the layout of the archive or the manifest changes. Archives
+// of another version are refused rather than misread.
+pub const STATE_ARCHIVE_VERSION: u32 = 1;
+
+// First entry of every archive
+const MANIFEST_PATH: &str = "manifest.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StateFileKind {
+    // An entry of the certificate cache
+    Certificate,
+    // Certified actions saved by `governance --export-signatures`
+    SignedActions,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateFile {
+    // Relative to the state directory, `/` separated
+    pub path: String,
+    pub kind: StateFileKind,
+    pub size: u64,
+    pub modified_ms: u64,
+    // When the signatures of a certificate were collected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certified_at_ms: Option<u64>,
+    // Highest nonce of the actions in a signed action file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_nonce: Option<u64>,
+}
+
+impl StateFile {
+    fn new(path: String, kind: StateFileKind, content: &[u8], modified_ms: u64) -> Option<Self> {
+        let mut file = Self {
+            path,
+            kind,
+            size: content.len() as u64,
+            modified_ms,
+            certified_at_ms: None,
+            max_nonce: None,
+        };
+        match kind {
+            StateFileKind::Certificate => {
+                let entry = serde_json::from_slice::<CacheEntry>(content).ok()?;
+                file.certified_at_ms = Some(entry.certified_at_ms);
+            }
+            StateFileKind::SignedActions => {
+                let signed_actions = serde_json::from_slice::<SignedActionsFile>(content).ok()?;
+                file.max_nonce = signed_actions
+                    .actions
+                    .iter()
+                    .map(|signed_action| signed_action.action.seq_number())
+                    .max();
+            }
+        }
+        Some(file)
+    }
+
+    // Which of two copies of a file is newer: certificates by when they were
+    // collected, signed action files by their highest nonce, and the
+    // modification time when those are equal
+    fn version(&self) -> (u64, u64) {
+        let primary = self.certified_at_ms.or(self.max_nonce).unwrap_or_default();
+        (primary, self.modified_ms)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateManifest {
+    pub archive_version: u32,
+    // Version of the bridge-cli that wrote the archive
+    pub cli_version: String,
+    pub created_at_ms: u64,
+    // See `config_fingerprint`
+    pub config_fingerprint: String,
+    pub files: Vec<StateFile>,
+}
+
+// The files of a state directory, by path
+pub type StateFiles = BTreeMap<String, (StateFile, Vec<u8>)>;
+
+// Where the CLI state of a machine lives
+#[derive(Clone, Debug)]
+pub struct StateDir {
+    root: PathBuf,
+    // Relative to `root`
+    certificate_cache_dir: PathBuf,
+}
+
+impl StateDir {
+    pub fn new(root: PathBuf, certificate_cache_dir: PathBuf) -> anyhow::Result<Self> {
+        relative_path(&certificate_cache_dir.to_string_lossy()).with_context(|| {
+            format!(
+                "Certificate cache {:?} is not inside the state directory",
+                certificate_cache_dir
+            )
+        })?;
+        Ok(Self {
+            root,
+            certificate_cache_dir,
+        })
+    }
+
+    // Every state file, with its content. Files that don't parse as the kind
+    // their location implies are skipped.
+    pub fn scan(&self) -> anyhow::Result<StateFiles> {
+        let mut files = BTreeMap::new();
+        let cache_dir = self.root.join(&self.certificate_cache_dir);
+        for path in json_files(&cache_dir)? {
+            self.add(&mut files, &path, StateFileKind::Certificate)?;
+        }
+        for path in json_files(&self.root)? {
+            self.add(&mut files, &path, StateFileKind::SignedActions)?;
+        }
+        Ok(files)
+    }
+
+    fn add(&self, files: &mut StateFiles, path: &Path, kind: StateFileKind) -> anyhow::Result<()> {
+        let content =
+            std::fs::read(path).with_context(|| format!("Failed to read state file {:?}", path))?;
+        let modified_ms = std::fs::metadata(path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let relative = path
+            .strip_prefix(&self.root)?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        match StateFile::new(relative.clone(), kind, &content, modified_ms) {
+            Some(file) => {
+                files.insert(relative, (file, content));
+            }
+            // Other JSON files in the state directory are no concern of ours
+            None if kind == StateFileKind::SignedActions => {}
+            None => warn!("Skipping invalid state file {:?}", path),
+        }
+        Ok(())
+    }
+
+    // Content of the file at `path`, whether or not it is valid state
+    fn read(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let path = self.root.join(relative_path(path)?);
+        match std::fs::read(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {:?}", path)),
+        }
+    }
+
+    // Writes through a temporary file, like the certificate cache
+    fn write(&self, path: &str, content: &[u8]) -> anyhow::Result<()> {
+        let path = self.root.join(relative_path(path)?);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, content).with_context(|| format!("Failed to write {:?}", tmp))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+// The `.json` files directly in `dir`, none if it doesn't exist
+fn json_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", dir)),
+    };
+    let mut paths = vec![];
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+// Archive paths come from another machine, so they must not escape the state
+// directory
+fn relative_path(path: &str) -> anyhow::Result<PathBuf> {
+    let path = Path::new(path);
+    if path.as_os_str().is_empty()
+        || !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(anyhow!("Invalid state file path {:?}", path));
+    }
+    Ok(path.to_path_buf())
+}
+
+// Identifies the bridge a config points at: the proxy addresses and the EVM
+// chain ids, but not the RPC urls or key paths, which differ between
+// machines of the same operator
+pub fn config_fingerprint(config: &BridgeCliConfig) -> String {
+    let mut lines = vec![
+        format!(
+            "starcoin {}",
+            config.starcoin_bridge_proxy_address.to_lowercase()
+        ),
+        format!("eth {:?}", config.eth_bridge_proxy_address),
+    ];
+    let mut evm_chains = config
+        .evm_chains
+        .iter()
+        .map(|chain| {
+            format!(
+                "evm {} {:?}",
+                chain.chain_id, chain.eth_bridge_proxy_address
+            )
+        })
+        .collect::<Vec<_>>();
+    evm_chains.sort();
+    lines.extend(evm_chains);
+    Hex::encode(Keccak256::digest(lines.join("\n").as_bytes()).digest)
+}
+
+// Writes the state files to a zstd compressed tar archive at `output`, the
+// manifest first. Returns the manifest.
+pub fn export_state(
+    state_dir: &StateDir,
+    config_fingerprint: String,
+    output: &Path,
+) -> anyhow::Result<StateManifest> {
+    let files = state_dir.scan()?;
+    let manifest = StateManifest {
+        archive_version: STATE_ARCHIVE_VERSION,
+        cli_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at_ms: now_ms(),
+        config_fingerprint,
+        files: files.values().map(|(file, _)| file.clone()).collect(),
+    };
+    let writer = std::fs::File::create(output)
+        .with_context(|| format!("Failed to create state archive {:?}", output))?;
+    let mut builder = tar::Builder::new(zstd::Encoder::new(writer, 0)?);
+    let mut append = |path: &str, content: &[u8], modified_ms: u64| {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(content.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(modified_ms / 1000);
+        builder.append_data(&mut header, path, content)
+    };
+    append(
+        MANIFEST_PATH,
+        &serde_json::to_vec_pretty(&manifest)?,
+        manifest.created_at_ms,
+    )?;
+    for (path, (file, content)) in &files {
+        append(path, content, file.modified_ms)?;
+    }
+    builder
+        .into_inner()?
+        .finish()
+        .with_context(|| format!("Failed to write state archive {:?}", output))?;
+    Ok(manifest)
+}
+
+// Reads an archive written by `export_state`, checking every file against
+// the manifest
+pub fn read_state_archive(input: &Path) -> anyhow::Result<(StateManifest, StateFiles)> {
+    let reader = std::fs::File::open(input)
+        .with_context(|| format!("Failed to open state archive {:?}", input))?;
+    let mut archive = tar::Archive::new(zstd::Decoder::new(reader)?);
+    let mut manifest = None;
+    let mut contents = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        if manifest.is_none() {
+            if path != MANIFEST_PATH {
+                return Err(anyhow!("{:?} is not a state archive", input));
+            }
+            manifest = Some(parse_manifest(&content)?);
+        } else if contents.insert(path.clone(), content).is_some() {
+            return Err(anyhow!("{} is in the state archive twice", path));
+        }
+    }
+    let manifest = manifest.ok_or_else(|| anyhow!("{:?} is not a state archive", input))?;
+    let mut files = BTreeMap::new();
+    for expected in &manifest.files {
+        relative_path(&expected.path)?;
+        let content = contents
+            .remove(&expected.path)
+            .ok_or_else(|| anyhow!("{} is missing from the state archive", expected.path))?;
+        let file = StateFile::new(
+            expected.path.clone(),
+            expected.kind,
+            &content,
+            expected.modified_ms,
+        );
+        if file.as_ref() != Some(expected) {
+            return Err(anyhow!(
+                "{} doesn't match the manifest of the state archive",
+                expected.path
+            ));
+        }
+        files.insert(expected.path.clone(), (expected.clone(), content));
+    }
+    if let Some(path) = contents.keys().next() {
+        return Err(anyhow!(
+            "{} is not in the manifest of the state archive",
+            path
+        ));
+    }
+    Ok((manifest, files))
+}
+
+fn parse_manifest(content: &[u8]) -> anyhow::Result<StateManifest> {
+    // The version is read on its own, so that a manifest of another version
+    // is reported as such rather than as invalid
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct Versions {
+        archive_version: u32,
+        cli_version: String,
+    }
+    let versions: Versions =
+        serde_json::from_slice(content).context("Invalid state archive manifest")?;
+    if versions.archive_version != STATE_ARCHIVE_VERSION {
+        return Err(anyhow!(
+            "State archive version {} written by bridge-cli {} is not supported, this version reads {}",
+            versions.archive_version,
+            versions.cli_version,
+            STATE_ARCHIVE_VERSION
+        ));
+    }
+    serde_json::from_slice(content).context("Invalid state archive manifest")
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateImport {
+    pub imported: Vec<String>,
+    // Already present with the same content
+    pub unchanged: Vec<String>,
+}
+
+impl fmt::Display for StateImport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for path in &self.imported {
+            writeln!(f, "Imported {path}")?;
+        }
+        writeln!(
+            f,
+            "{} files imported, {} unchanged",
+            self.imported.len(),
+            self.unchanged.len()
+        )
+    }
+}
+
+// Writes the files of the archive at `input` to `state_dir`. Fails without
+// writing anything if the archive is of another bridge, unless `force`, or
+// if any local file is newer than its archived copy. Local files that are
+// not valid state can't be compared and are only replaced with `force`.
+pub fn import_state(
+    state_dir: &StateDir,
+    config_fingerprint: &str,
+    input: &Path,
+    force: bool,
+) -> anyhow::Result<StateImport> {
+    let (manifest, files) = read_state_archive(input)?;
+    if manifest.config_fingerprint != config_fingerprint {
+        if !force {
+            return Err(anyhow!(
+                "The state archive is of a config with fingerprint {}, not {}. Pass --force to import it anyway.",
+                manifest.config_fingerprint,
+                config_fingerprint
+            ));
+        }
+        warn!(
+            "Importing state of a config with fingerprint {} into {}",
+            manifest.config_fingerprint, config_fingerprint
+        );
+    }
+    let local = state_dir.scan()?;
+    let mut import = StateImport::default();
+    let mut conflicts = vec![];
+    let mut unrecognized = vec![];
+    for (path, (file, content)) in &files {
+        match local.get(path) {
+            Some((_, local_content)) if local_content == content => {
+                import.unchanged.push(path.clone());
+            }
+            Some((local_file, _)) if local_file.version() > file.version() => {
+                conflicts.push(path.clone());
+            }
+            Some(_) => import.imported.push(path.clone()),
+            None => match state_dir.read(path)? {
+                Some(local_content) if &local_content == content => {
+                    import.unchanged.push(path.clone());
+                }
+                Some(_) if !force => unrecognized.push(path.clone()),
+                _ => import.imported.push(path.clone()),
+            },
+        }
+    }
+    if !conflicts.is_empty() {
+        return Err(anyhow!(
+            "Local state is newer than the archive, nothing imported: {}",
+            conflicts.join(", ")
+        ));
+    }
+    if !unrecognized.is_empty() {
+        return Err(anyhow!(
+            "Local files are not valid state and can't be compared with the archive, nothing imported: {}. Pass --force to replace them.",
+            unrecognized.join(", ")
+        ));
+    }
+    for path in &import.imported {
+        state_dir.write(path, &files[path].1)?;
+    }
+    Ok(import)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateListing {
+    pub now_ms: u64,
+    pub files: Vec<StateFile>,
+}
+
+impl StateListing {
+    pub fn new(state_dir: &StateDir, now_ms: u64) -> anyhow::Result<Self> {
+        Ok(Self {
+            now_ms,
+            files: state_dir
+                .scan()?
+                .into_values()
+                .map(|(file, _)| file)
+                .collect(),
+        })
+    }
+
+    fn age(&self, ms: u64) -> Duration {
+        Duration::from_secs(self.now_ms.saturating_sub(ms) / 1000)
+    }
+}
+
+impl fmt::Display for StateListing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.files.is_empty() {
+            return writeln!(f, "No state stored");
+        }
+        for file in &self.files {
+            write!(
+                f,
+                "{} ({} bytes, modified {:?} ago)",
+                file.path,
+                file.size,
+                self.age(file.modified_ms)
+            )?;
+            match (file.certified_at_ms, file.max_nonce) {
+                (Some(certified_at_ms), _) => {
+                    writeln!(f, ": certified {:?} ago", self.age(certified_at_ms))?
+                }
+                (None, Some(max_nonce)) => writeln!(f, ": signed actions up to nonce {max_nonce}")?,
+                (None, None) => writeln!(f, ": no signed actions")?,
+            }
+        }
+        let total: u64 = self.files.iter().map(|file| file.size).sum();
+        writeln!(f, "{} files, {} bytes", self.files.len(), total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signed_actions::SignedAction;
+    use crate::EvmChainCliConfig;
+    use ethers::types::Address as EthAddress;
+    use starcoin_bridge::types::{BridgeAction, EmergencyAction, EmergencyActionType};
+    use starcoin_bridge_types::bridge::BridgeChainId;
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+    const FINGERPRINT: &str = "fingerprint";
+
+    fn signed_action(nonce: u64) -> SignedAction {
+        SignedAction {
+            action: BridgeAction::EmergencyAction(EmergencyAction {
+                nonce,
+                chain_id: BridgeChainId::EthCustom,
+                action_type: EmergencyActionType::Pause,
+            }),
+            signatures: vec![],
+        }
+    }
+
+    fn certificate(nonce: u64, certified_at_ms: u64) -> Vec<u8> {
+        serde_json::to_vec(&CacheEntry {
+            certified_at_ms,
+            certificate: signed_action(nonce),
+        })
+        .unwrap()
+    }
+
+    fn signed_actions(nonces: &[u64]) -> Vec<u8> {
+        serde_json::to_vec(&SignedActionsFile {
+            chain_id: BridgeChainId::EthCustom as u8,
+            actions: nonces.iter().map(|nonce| signed_action(*nonce)).collect(),
+        })
+        .unwrap()
+    }
+
+    fn state_dir(root: &Path) -> StateDir {
+        StateDir::new(root.to_path_buf(), "governance-certificates".into()).unwrap()
+    }
+
+    fn write(root: &Path, path: &str, content: &[u8]) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(root: &Path, path: &str) -> Vec<u8> {
+        std::fs::read(root.join(path)).unwrap()
+    }
+
+    // A machine with a cached certificate, a signed action file and a config
+    fn synthetic_state(root: &Path) {
+        write(
+            root,
+            "governance-certificates/aa.json",
+            &certificate(3, NOW_MS),
+        );
+        write(root, "signed.json", &signed_actions(&[4, 5]));
+        write(root, "config.json", b"{\"eth-rpc-url\": \"\"}");
+    }
+
+    fn export(root: &Path, archive: &Path) -> StateManifest {
+        export_state(&state_dir(root), FINGERPRINT.to_string(), archive).unwrap()
+    }
+
+    fn write_archive(archive: &Path, entries: &[(&str, &[u8])]) {
+        let writer = std::fs::File::create(archive).unwrap();
+        let mut builder = tar::Builder::new(zstd::Encoder::new(writer, 0).unwrap());
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            builder.append_data(&mut header, path, *content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let archive = source.path().join("state.tar.zst");
+        synthetic_state(source.path());
+
+        let manifest = export(source.path(), &archive);
+        assert_eq!(manifest.archive_version, STATE_ARCHIVE_VERSION);
+        assert_eq!(manifest.config_fingerprint, FINGERPRINT);
+        let files = manifest
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.kind))
+            .collect::<Vec<_>>();
+        // The config is not state
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "governance-certificates/aa.json",
+                    StateFileKind::Certificate
+                ),
+                ("signed.json", StateFileKind::SignedActions),
+            ]
+        );
+        assert_eq!(manifest.files[0].certified_at_ms, Some(NOW_MS));
+        assert_eq!(manifest.files[1].max_nonce, Some(5));
+
+        let (read_manifest, _) = read_state_archive(&archive).unwrap();
+        assert_eq!(read_manifest, manifest);
+
+        let import = import_state(&state_dir(target.path()), FINGERPRINT, &archive, false).unwrap();
+        assert_eq!(
+            import.imported,
+            vec!["governance-certificates/aa.json", "signed.json"]
+        );
+        for path in ["governance-certificates/aa.json", "signed.json"] {
+            assert_eq!(read(target.path(), path), read(source.path(), path));
+        }
+
+        // Importing again changes nothing
+        let import = import_state(&state_dir(target.path()), FINGERPRINT, &archive, false).unwrap();
+        assert!(import.imported.is_empty());
+        assert_eq!(import.unchanged.len(), 2);
+    }
+
+    #[test]
+    fn test_import_checks_config_fingerprint() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let archive = source.path().join("state.tar.zst");
+        synthetic_state(source.path());
+        export(source.path(), &archive);
+
+        let err = import_state(&state_dir(target.path()), "other", &archive, false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+        assert!(state_dir(target.path()).scan().unwrap().is_empty());
+
+        let import = import_state(&state_dir(target.path()), "other", &archive, true).unwrap();
+        assert_eq!(import.imported.len(), 2);
+    }
+
+    #[test]
+    fn test_import_refuses_newer_local_state() {
+        let source = tempfile::tempdir().unwrap();
+        let archive = source.path().join("state.tar.zst");
+        synthetic_state(source.path());
+        export(source.path(), &archive);
+
+        // A certificate collected after the archived one
+        let target = tempfile::tempdir().unwrap();
+        let newer = certificate(3, NOW_MS + 1);
+        write(target.path(), "governance-certificates/aa.json", &newer);
+        // Not even with --force
+        let err = import_state(&state_dir(target.path()), FINGERPRINT, &archive, true).unwrap_err();
+        assert!(
+            err.to_string().contains("governance-certificates/aa.json"),
+            "{err}"
+        );
+        // Nothing is written when any file conflicts
+        assert!(!target.path().join("signed.json").exists());
+        assert_eq!(
+            read(target.path(), "governance-certificates/aa.json"),
+            newer
+        );
+
+        // Signed actions up to a higher nonce
+        let target = tempfile::tempdir().unwrap();
+        write(target.path(), "signed.json", &signed_actions(&[6]));
+        let err =
+            import_state(&state_dir(target.path()), FINGERPRINT, &archive, false).unwrap_err();
+        assert!(err.to_string().contains("signed.json"), "{err}");
+
+        // Older local state is replaced
+        let target = tempfile::tempdir().unwrap();
+        write(
+            target.path(),
+            "governance-certificates/aa.json",
+            &certificate(3, NOW_MS - 1),
+        );
+        write(target.path(), "signed.json", &signed_actions(&[4]));
+        let import = import_state(&state_dir(target.path()), FINGERPRINT, &archive, false).unwrap();
+        assert_eq!(import.imported.len(), 2);
+        assert_eq!(
+            read(target.path(), "governance-certificates/aa.json"),
+            certificate(3, NOW_MS)
+        );
+    }
+
+    #[test]
+    fn test_import_refuses_to_replace_unrecognized_files() {
+        let source = tempfile::tempdir().unwrap();
+        let archive = source.path().join("state.tar.zst");
+        synthetic_state(source.path());
+        export(source.path(), &archive);
+
+        // A certificate that doesn't parse, and a JSON file at the path of a
+        // signed action file that is something else
+        let target = tempfile::tempdir().unwrap();
+        write(target.path(), "governance-certificates/aa.json", b"{");
+        write(target.path(), "signed.json", b"{\"notes\": []}");
+        assert!(state_dir(target.path()).scan().unwrap().is_empty());
+        let err =
+            import_state(&state_dir(target.path()), FINGERPRINT, &archive, false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+        assert!(
+            err.to_string().contains("governance-certificates/aa.json"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("signed.json"), "{err}");
+        assert_eq!(read(target.path(), "governance-certificates/aa.json"), b"{");
+        assert_eq!(read(target.path(), "signed.json"), b"{\"notes\": []}");
+
+        // --force replaces them
+        let target = tempfile::tempdir().unwrap();
+        write(target.path(), "governance-certificates/aa.json", b"{");
+        write(target.path(), "signed.json", b"{\"notes\": []}");
+        let import = import_state(&state_dir(target.path()), FINGERPRINT, &archive, true).unwrap();
+        assert_eq!(import.imported.len(), 2);
+        for path in ["governance-certificates/aa.json", "signed.json"] {
+            assert_eq!(read(target.path(), path), read(source.path(), path));
+        }
+    }
+
+    #[test]
+    fn test_read_state_archive_checks_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("state.tar.zst");
+        let manifest = |version: u32, files: Vec<StateFile>| {
+            serde_json::to_vec(&StateManifest {
+                archive_version: version,
+                cli_version: "9.9.9".to_string(),
+                created_at_ms: NOW_MS,
+                config_fingerprint: FINGERPRINT.to_string(),
+                files,
+            })
+            .unwrap()
+        };
+        let content = signed_actions(&[4]);
+        let file = |path: &str| {
+            StateFile::new(path.to_string(), StateFileKind::SignedActions, &content, 0).unwrap()
+        };
+        let expect_err = |entries: &[(&str, &[u8])], expected: &str| {
+            write_archive(&archive, entries);
+            let err = read_state_archive(&archive).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        };
+
+        // Archives of another version are refused
+        expect_err(
+            &[(MANIFEST_PATH, &manifest(2, vec![]))],
+            "State archive version 2 written by bridge-cli 9.9.9 is not supported",
+        );
+        // The manifest comes first
+        expect_err(
+            &[
+                ("signed.json", &content),
+                (MANIFEST_PATH, &manifest(1, vec![])),
+            ],
+            "is not a state archive",
+        );
+        expect_err(
+            &[(MANIFEST_PATH, &manifest(1, vec![file("signed.json")]))],
+            "signed.json is missing",
+        );
+        expect_err(
+            &[
+                (MANIFEST_PATH, &manifest(1, vec![])),
+                ("signed.json", &content),
+            ],
+            "signed.json is not in the manifest",
+        );
+        expect_err(
+            &[
+                (MANIFEST_PATH, &manifest(1, vec![file("signed.json")])),
+                ("signed.json", &signed_actions(&[5])),
+            ],
+            "signed.json doesn't match the manifest",
+        );
+        // Paths can't leave the state directory
+        expect_err(
+            &[(MANIFEST_PATH, &manifest(1, vec![file("../signed.json")]))],
+            "Invalid state file path",
+        );
+
+        write_archive(
+            &archive,
+            &[
+                (MANIFEST_PATH, &manifest(1, vec![file("signed.json")])),
+                ("signed.json", &content),
+            ],
+        );
+        let (_, files) = read_state_archive(&archive).unwrap();
+        assert_eq!(files["signed.json"].1, content);
+    }
+
+    #[test]
+    fn test_state_listing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            StateListing::new(&state_dir(dir.path()), NOW_MS)
+                .unwrap()
+                .to_string(),
+            "No state stored\n"
+        );
+
+        synthetic_state(dir.path());
+        let mut listing = StateListing::new(&state_dir(dir.path()), NOW_MS).unwrap();
+        for file in &mut listing.files {
+            file.modified_ms = NOW_MS - 60_000;
+        }
+        let listing = StateListing {
+            now_ms: NOW_MS + 3_600_000,
+            ..listing
+        };
+        let certificate_size = certificate(3, NOW_MS).len();
+        let signed_actions_size = signed_actions(&[4, 5]).len();
+        assert_eq!(
+            listing.to_string(),
+            format!(
+                "governance-certificates/aa.json ({certificate_size} bytes, modified 3660s ago): certified 3600s ago\n\
+                 signed.json ({signed_actions_size} bytes, modified 3660s ago): signed actions up to nonce 5\n\
+                 2 files, {} bytes\n",
+                certificate_size + signed_actions_size
+            )
+        );
+    }
+
+    #[test]
+    fn test_config_fingerprint() {
+        let config = |rpc_url: &str, evm_chains: Vec<(u8, u8)>| BridgeCliConfig {
+            starcoin_bridge_rpc_url: rpc_url.to_string(),
+            eth_rpc_url: rpc_url.to_string(),
+            starcoin_bridge_proxy_address: "0xABCD".to_string(),
+            eth_bridge_proxy_address: EthAddress::repeat_byte(1),
+            starcoin_bridge_key_path: None,
+            eth_key_path: None,
+            eth_signer: Default::default(),
+            evm_chains: evm_chains
+                .into_iter()
+                .map(|(chain_id, address)| EvmChainCliConfig {
+                    name: format!("chain-{chain_id}"),
+                    chain_id,
+                    eth_rpc_url: rpc_url.to_string(),
+                    eth_bridge_proxy_address: EthAddress::repeat_byte(address),
+                })
+                .collect(),
+            gas: None,
+        };
+        let fingerprint = config_fingerprint(&config("http://a", vec![(11, 2), (12, 3)]));
+        // Urls and the order of the chains don't matter
+        assert_eq!(
+            config_fingerprint(&config("http://b", vec![(12, 3), (11, 2)])),
+            fingerprint
+        );
+        // The bridge does
+        assert_ne!(
+            config_fingerprint(&config("http://a", vec![(11, 2), (12, 4)])),
+            fingerprint
+        );
+        assert_ne!(
+            config_fingerprint(&config("http://a", vec![(11, 2)])),
+            fingerprint
+        );
+    }
+}
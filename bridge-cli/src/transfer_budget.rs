@@ -0,0 +1,394 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pre-flight transfer limit check of `bridge-cli client` deposits. The
+//! limiter of the receiving chain only lets a route carry so much USD value
+//! over a rolling 24 hour window, and holds back any transfer over it, so a
+//! large deposit would stay pending without explanation until the window
+//! moves on.
+//!
+//! Transfers into Starcoin are limited by the Move limiter, transfers into an
+//! EVM chain by its `BridgeLimiter` contract. Deposits are valued with the
+//! notional values of the Starcoin treasury on both sides.
+
+use crate::{usd, LoadedEvmChain, ReadOnlyConfig};
+use anyhow::anyhow;
+use ethers::types::U256;
+use starcoin_bridge::abi::EthBridgeLimiter;
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge::types::USD_MULTIPLIER;
+use starcoin_bridge::utils::EthCallBatch;
+use starcoin_bridge_types::bridge::BridgeChainId;
+use tracing::warn;
+
+// The Eth `BridgeLimiter` keeps USD values with 8 decimals
+pub const ETH_LIMITER_USD_MULTIPLIER: u64 = 100_000_000;
+
+// What the limiter of a route lets through. USD values are in units of
+// 1 / USD_MULTIPLIER.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferBudget {
+    pub sending_chain: BridgeChainId,
+    pub receiving_chain: BridgeChainId,
+    pub limit: u64,
+    pub remaining: u64,
+    pub current_hour_since_epoch: u64,
+    // Value transferred in each hour of the window, oldest first
+    pub hourly_usage: Vec<(u64, u64)>,
+}
+
+impl TransferBudget {
+    // Hour since epoch from which a transfer of `usd_value` fits, as the
+    // hours of the window expire. None if it is over the whole limit.
+    pub fn fits_from_hour(&self, usd_value: u64) -> Option<u64> {
+        if usd_value > self.limit {
+            return None;
+        }
+        let mut remaining = self.remaining;
+        let mut hour = self.current_hour_since_epoch;
+        for (usage_hour, amount) in &self.hourly_usage {
+            if remaining >= usd_value {
+                break;
+            }
+            // An hour leaves the window 24 hours after it started
+            hour = usage_hour + 24;
+            remaining = remaining.saturating_add(*amount).min(self.limit);
+        }
+        Some(hour)
+    }
+}
+
+// USD value of `amount` base units of a token worth `notional_value` per
+// whole token of `decimal_multiplier` base units, rounded down like the
+// limiters do
+pub fn deposit_usd_value(
+    amount: u128,
+    notional_value: u64,
+    decimal_multiplier: u128,
+) -> anyhow::Result<u64> {
+    if decimal_multiplier == 0 {
+        return Err(anyhow!("Token has no decimal multiplier"));
+    }
+    let value = U256::from(amount) * U256::from(notional_value) / U256::from(decimal_multiplier);
+    u64::try_from(value).map_err(|_| anyhow!("USD value of {amount} base units overflows"))
+}
+
+// Fails when a transfer of `usd_value` is over `budget`, unless `force`, in
+// which case it only warns
+pub fn check_transfer_budget(
+    budget: &TransferBudget,
+    usd_value: u64,
+    force: bool,
+) -> anyhow::Result<()> {
+    if usd_value <= budget.remaining {
+        return Ok(());
+    }
+    let pending = match budget.fits_from_hour(usd_value) {
+        Some(hour) => format!(
+            "until the limit refreshes, within {} hour(s)",
+            hour - budget.current_hour_since_epoch
+        ),
+        None => format!(
+            "indefinitely, as it is over the whole limit of ${:.4}",
+            usd(budget.limit)
+        ),
+    };
+    let message = format!(
+        "Deposit of ${:.4} is over the remaining transfer limit of ${:.4} from {:?} to {:?}, \
+         so it would stay pending {}",
+        usd(usd_value),
+        usd(budget.remaining),
+        budget.sending_chain,
+        budget.receiving_chain,
+        pending
+    );
+    if force {
+        warn!("{message}, depositing anyway because of --force");
+        return Ok(());
+    }
+    Err(anyhow!("{message}. Pass --force to deposit anyway"))
+}
+
+// Budget of the Move limiter for transfers into Starcoin
+pub async fn starcoin_transfer_budget(
+    starcoin_bridge_client: &StarcoinBridgeClient,
+    sending_chain: BridgeChainId,
+    receiving_chain: BridgeChainId,
+) -> anyhow::Result<TransferBudget> {
+    let read_error = |e| anyhow!("Failed to read the Starcoin transfer limit: {:?}", e);
+    let remaining = starcoin_bridge_client
+        .get_remaining_transfer_budget(sending_chain, receiving_chain)
+        .await
+        .map_err(read_error)?;
+    let limiter = starcoin_bridge_client
+        .get_bridge_summary()
+        .await
+        .map_err(read_error)?
+        .limiter;
+    let current_hour_since_epoch = starcoin_bridge_client
+        .get_block_timestamp()
+        .await
+        .map_err(read_error)?
+        / 3_600_000;
+    Ok(TransferBudget {
+        sending_chain,
+        receiving_chain,
+        limit: limiter
+            .transfer_limit(sending_chain, receiving_chain)
+            .unwrap_or_default(),
+        remaining,
+        current_hour_since_epoch,
+        hourly_usage: limiter
+            .transfer_record(sending_chain, receiving_chain)
+            .map(|record| record.hourly_amounts(current_hour_since_epoch))
+            .unwrap_or_default(),
+    })
+}
+
+// A USD value of the Eth limiter in units of 1 / USD_MULTIPLIER
+pub fn from_eth_limiter_usd(amount: U256, round_up: bool) -> u64 {
+    let factor = U256::from(ETH_LIMITER_USD_MULTIPLIER / USD_MULTIPLIER);
+    let amount = if round_up {
+        amount.saturating_add(factor - 1) / factor
+    } else {
+        amount / factor
+    };
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+// Budget of the `BridgeLimiter` of `evm_chain` for transfers from
+// `sending_chain`. Limits are rounded down and usage up when converted to
+// USD_MULTIPLIER units, so the check errs on the safe side.
+pub async fn eth_transfer_budget(
+    evm_chain: &LoadedEvmChain,
+    sending_chain: BridgeChainId,
+) -> anyhow::Result<TransferBudget> {
+    let read_error = |e| anyhow!("Failed to read the limiter on {}: {:?}", evm_chain.name, e);
+    let limiter = EthBridgeLimiter::new(
+        evm_chain.eth_bridge_limiter_proxy_address,
+        evm_chain.provider(),
+    );
+    let mut batch = EthCallBatch::new(evm_chain.provider());
+    let limit = batch.add(&limiter.chain_limits(sending_chain as u8));
+    let current_hour = batch.add(&limiter.current_hour());
+    let results = batch.execute().await;
+    let limit = limit.decode(&results).map_err(read_error)?;
+    let current_hour = current_hour.decode(&results).map_err(read_error)?;
+
+    // The window is the current hour and the 23 before it, see
+    // `calculateWindowAmount`
+    let mut batch = EthCallBatch::new(evm_chain.provider());
+    let hours = (0..24)
+        .rev()
+        .filter_map(|i| current_hour.checked_sub(i))
+        .map(|hour| {
+            let key = (U256::from(sending_chain as u8) << 32) | U256::from(hour);
+            (hour, batch.add(&limiter.chain_hourly_transfer_amount(key)))
+        })
+        .collect::<Vec<_>>();
+    let results = batch.execute().await;
+    let mut hourly_usage = vec![];
+    for (hour, amount) in hours {
+        let amount: U256 = amount.decode(&results).map_err(read_error)?;
+        if !amount.is_zero() {
+            hourly_usage.push((hour as u64, from_eth_limiter_usd(amount, true)));
+        }
+    }
+    let limit = from_eth_limiter_usd(U256::from(limit), false);
+    let used = hourly_usage
+        .iter()
+        .fold(0u64, |used, (_, amount)| used.saturating_add(*amount));
+    Ok(TransferBudget {
+        sending_chain,
+        receiving_chain: evm_chain.chain_id,
+        limit,
+        remaining: limit.saturating_sub(used),
+        current_hour_since_epoch: current_hour as u64,
+        hourly_usage,
+    })
+}
+
+// Checks a deposit of `amount` base units of `token_id`, with
+// `decimal_multiplier` base units per token on the sending chain, against
+// the limiter of the receiving chain of `route`
+pub async fn check_deposit_budget(
+    config: &ReadOnlyConfig,
+    starcoin_bridge_client: &StarcoinBridgeClient,
+    route: (BridgeChainId, BridgeChainId),
+    token_id: u8,
+    amount: u128,
+    decimal_multiplier: u128,
+    force: bool,
+) -> anyhow::Result<()> {
+    let (sending_chain, receiving_chain) = route;
+    let notional_value = starcoin_bridge_client
+        .get_notional_values()
+        .await
+        .map_err(|e| anyhow!("Failed to read the token notional values: {:?}", e))?
+        .get(&token_id)
+        .copied()
+        .ok_or_else(|| anyhow!("Token {token_id} has no notional value on Starcoin"))?;
+    let usd_value = deposit_usd_value(amount, notional_value, decimal_multiplier)?;
+    let budget = if receiving_chain.is_starcoin_bridge_chain() {
+        starcoin_transfer_budget(starcoin_bridge_client, sending_chain, receiving_chain).await?
+    } else {
+        eth_transfer_budget(config.evm_chain(Some(receiving_chain))?, sending_chain).await?
+    };
+    check_transfer_budget(&budget, usd_value, force)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_bridge_types::bridge::{BridgeLimiterSummary, MoveTypeBridgeTransferRecord};
+
+    fn budget(limit: u64, hourly_usage: Vec<(u64, u64)>) -> TransferBudget {
+        let used: u64 = hourly_usage.iter().map(|(_, amount)| amount).sum();
+        TransferBudget {
+            sending_chain: BridgeChainId::EthCustom,
+            receiving_chain: BridgeChainId::StarcoinCustom,
+            limit,
+            remaining: limit.saturating_sub(used),
+            current_hour_since_epoch: 1_000,
+            hourly_usage,
+        }
+    }
+
+    #[test]
+    fn test_deposit_usd_value() {
+        // 1.5 ETH at $2,500.5, with 18 decimals
+        let value = deposit_usd_value(
+            1_500_000_000_000_000_000,
+            2_500 * USD_MULTIPLIER + 5_000,
+            10u128.pow(18),
+        )
+        .unwrap();
+        assert_eq!(value, 3_750 * USD_MULTIPLIER + 7_500);
+        assert_eq!(usd(value), 3_750.75);
+
+        // Rounded down to 1 / USD_MULTIPLIER: 0.000001 USDT is worth nothing
+        assert_eq!(deposit_usd_value(1, USD_MULTIPLIER, 1_000_000).unwrap(), 0);
+        assert_eq!(
+            deposit_usd_value(100, USD_MULTIPLIER, 1_000_000).unwrap(),
+            1
+        );
+
+        // Large amounts don't overflow the intermediate product
+        assert_eq!(
+            deposit_usd_value(u128::MAX / 2, 1, u128::MAX / 2).unwrap(),
+            1
+        );
+        assert!(deposit_usd_value(u128::MAX, u64::MAX, 1).is_err());
+        assert!(deposit_usd_value(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_fits_from_hour() {
+        let budget = budget(
+            1_000 * USD_MULTIPLIER,
+            vec![
+                (980, 300 * USD_MULTIPLIER),
+                (990, 500 * USD_MULTIPLIER),
+                (1_000, 100 * USD_MULTIPLIER),
+            ],
+        );
+        assert_eq!(budget.remaining, 100 * USD_MULTIPLIER);
+
+        assert_eq!(budget.fits_from_hour(100 * USD_MULTIPLIER), Some(1_000));
+        // Once hour 980 leaves the window at 1004
+        assert_eq!(budget.fits_from_hour(400 * USD_MULTIPLIER), Some(1_004));
+        assert_eq!(budget.fits_from_hour(900 * USD_MULTIPLIER), Some(1_014));
+        assert_eq!(budget.fits_from_hour(1_000 * USD_MULTIPLIER), Some(1_024));
+        assert_eq!(budget.fits_from_hour(1_000 * USD_MULTIPLIER + 1), None);
+    }
+
+    #[test]
+    fn test_check_transfer_budget() {
+        let budget = budget(1_000 * USD_MULTIPLIER, vec![(990, 900 * USD_MULTIPLIER)]);
+        check_transfer_budget(&budget, 100 * USD_MULTIPLIER, false).unwrap();
+
+        let err = check_transfer_budget(&budget, 150 * USD_MULTIPLIER, false).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("$150.0000"), "{err}");
+        assert!(
+            err.contains("remaining transfer limit of $100.0000"),
+            "{err}"
+        );
+        assert!(err.contains("within 14 hour(s)"), "{err}");
+        assert!(err.contains("--force"), "{err}");
+
+        let err = check_transfer_budget(&budget, 2_000 * USD_MULTIPLIER, false).unwrap_err();
+        assert!(
+            err.to_string().contains("whole limit of $1000.0000"),
+            "{err}"
+        );
+
+        // Forced deposits only warn
+        check_transfer_budget(&budget, 2_000 * USD_MULTIPLIER, true).unwrap();
+    }
+
+    #[test]
+    fn test_starcoin_limiter_usage() {
+        let limiter = BridgeLimiterSummary {
+            transfer_limit: vec![(
+                BridgeChainId::EthCustom,
+                BridgeChainId::StarcoinCustom,
+                1_000 * USD_MULTIPLIER,
+            )],
+            // Hours 975 to 1000: 975 and 976 are past the window
+            transfer_records: vec![(
+                BridgeChainId::EthCustom,
+                BridgeChainId::StarcoinCustom,
+                MoveTypeBridgeTransferRecord::new(
+                    1_000,
+                    975,
+                    [
+                        vec![100 * USD_MULTIPLIER; 2],
+                        vec![0; 2],
+                        vec![20 * USD_MULTIPLIER],
+                        vec![0; 20],
+                        vec![50 * USD_MULTIPLIER],
+                    ]
+                    .concat(),
+                    270 * USD_MULTIPLIER,
+                ),
+            )],
+        };
+        let route = (BridgeChainId::EthCustom, BridgeChainId::StarcoinCustom);
+        let record = limiter.transfer_record(route.0, route.1).unwrap();
+        assert_eq!(
+            record.hourly_amounts(1_000),
+            vec![(979, 20 * USD_MULTIPLIER), (1_000, 50 * USD_MULTIPLIER)]
+        );
+        assert_eq!(
+            limiter.remaining_transfer_budget(route.0, route.1, 1_000),
+            Some(930 * USD_MULTIPLIER)
+        );
+        assert_eq!(
+            limiter.remaining_transfer_budget(route.1, route.0, 1_000),
+            None
+        );
+
+        let budget = TransferBudget {
+            sending_chain: route.0,
+            receiving_chain: route.1,
+            limit: 1_000 * USD_MULTIPLIER,
+            remaining: 930 * USD_MULTIPLIER,
+            current_hour_since_epoch: 1_000,
+            hourly_usage: record.hourly_amounts(1_000),
+        };
+        assert_eq!(budget.fits_from_hour(950 * USD_MULTIPLIER), Some(1_003));
+    }
+
+    #[test]
+    fn test_eth_limiter_units() {
+        // $1 with the 8 decimals of the Eth limiter
+        let one_usd = U256::from(ETH_LIMITER_USD_MULTIPLIER);
+        assert_eq!(from_eth_limiter_usd(one_usd, false), USD_MULTIPLIER);
+        assert_eq!(from_eth_limiter_usd(one_usd, true), USD_MULTIPLIER);
+        // Usage below 1 / USD_MULTIPLIER still counts, limits don't round up
+        assert_eq!(from_eth_limiter_usd(U256::one(), true), 1);
+        assert_eq!(from_eth_limiter_usd(U256::one(), false), 0);
+        assert_eq!(from_eth_limiter_usd(U256::MAX, true), u64::MAX);
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `bridge-cli wait-for-action`: blocks until a token transfer is approved or
+//! claimed on its target chain, for scripts and CI pipelines.
+//!
+//! The status is polled with the backoff of `retry_with_max_elapsed_time!`,
+//! through `ActionStatusSource` so the wait can be tested with the mock
+//! client. A JSON report is printed when the wait ends, and the exit code
+//! tells how it ended without parsing the report.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::ValueEnum;
+use ethers::providers::{Http, Provider};
+use serde::Serialize;
+use starcoin_bridge::abi::EthStarcoinBridge;
+use starcoin_bridge::deadline::Deadline;
+use starcoin_bridge::retry_with_max_elapsed_time;
+use starcoin_bridge::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use starcoin_bridge::types::BridgeActionStatus;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+pub const EXIT_TIMEOUT: i32 = 2;
+pub const EXIT_NOT_FOUND: i32 = 3;
+// 128 + SIGINT, as shells report a command stopped with Ctrl-C
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+// Each status read gets this long. A read that fails is retried like one
+// that returned a status short of the target.
+const RPC_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WaitTarget {
+    Starcoin,
+    Eth,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WaitUntil {
+    // Signed by the committee, claimed or not
+    Approved,
+    Claimed,
+}
+
+impl WaitUntil {
+    pub fn is_reached(self, status: &BridgeActionStatus) -> bool {
+        match self {
+            WaitUntil::Approved => matches!(
+                status,
+                BridgeActionStatus::Approved | BridgeActionStatus::Claimed
+            ),
+            WaitUntil::Claimed => *status == BridgeActionStatus::Claimed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WaitOutcome {
+    Reached,
+    Timeout,
+    // Timed out without the transfer ever being recorded
+    NotFound,
+    Interrupted,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WaitReport {
+    pub source_chain_id: u8,
+    pub seq_num: u64,
+    pub target: WaitTarget,
+    pub until: WaitUntil,
+    pub outcome: WaitOutcome,
+    // The last status read, None if no read succeeded
+    pub status: Option<BridgeActionStatus>,
+    // Every status read, in order and without repeats
+    pub observed: Vec<BridgeActionStatus>,
+    pub elapsed_ms: u64,
+}
+
+impl WaitReport {
+    pub fn exit_code(&self) -> i32 {
+        match self.outcome {
+            WaitOutcome::Reached => 0,
+            WaitOutcome::Timeout => EXIT_TIMEOUT,
+            WaitOutcome::NotFound => EXIT_NOT_FOUND,
+            WaitOutcome::Interrupted => EXIT_INTERRUPTED,
+        }
+    }
+}
+
+// The status of one transfer on the target chain. Implemented over RPC by
+// `StarcoinStatusSource` and `EthStatusSource`.
+#[async_trait]
+pub trait ActionStatusSource: Send + Sync {
+    async fn status(&self) -> anyhow::Result<BridgeActionStatus>;
+}
+
+// Transfers to Starcoin, whose status is recorded by the Starcoin bridge
+pub struct StarcoinStatusSource<P: StarcoinClientInner> {
+    pub starcoin_bridge_client: StarcoinClient<P>,
+    pub source_chain_id: u8,
+    pub seq_num: u64,
+}
+
+#[async_trait]
+impl<P: StarcoinClientInner> ActionStatusSource for StarcoinStatusSource<P> {
+    async fn status(&self) -> anyhow::Result<BridgeActionStatus> {
+        self.starcoin_bridge_client
+            .get_token_transfer_action_onchain_status(
+                self.source_chain_id,
+                self.seq_num,
+                Deadline::after(RPC_ATTEMPT_TIMEOUT),
+            )
+            .await
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+}
+
+// Transfers from Starcoin to Eth. They are approved on Starcoin, where their
+// signatures are recorded, and claimed once the Eth bridge processed them.
+pub struct EthStatusSource<P: StarcoinClientInner> {
+    pub starcoin: StarcoinStatusSource<P>,
+    pub bridge: EthStarcoinBridge<Provider<Http>>,
+}
+
+#[async_trait]
+impl<P: StarcoinClientInner> ActionStatusSource for EthStatusSource<P> {
+    async fn status(&self) -> anyhow::Result<BridgeActionStatus> {
+        let seq_num = self.starcoin.seq_num;
+        let processed = self
+            .bridge
+            .is_transfer_processed(seq_num)
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to read transfer {seq_num} on Eth: {:?}", e))?;
+        if processed {
+            return Ok(BridgeActionStatus::Claimed);
+        }
+        // Only the Eth bridge knows whether the transfer was claimed there
+        Ok(match self.starcoin.status().await? {
+            BridgeActionStatus::Claimed => BridgeActionStatus::Approved,
+            status => status,
+        })
+    }
+}
+
+#[derive(Default)]
+struct Progress {
+    status: Option<BridgeActionStatus>,
+    observed: Vec<BridgeActionStatus>,
+}
+
+// Polls `source` for transfer `seq_num` from `source_chain_id` until its
+// status satisfies `until`, `timeout` passed or `interrupt` completed,
+// whichever comes first. NotFound is only final at the timeout, as the target
+// chain may not have recorded the transfer yet.
+pub async fn wait_for_action(
+    source: &dyn ActionStatusSource,
+    source_chain_id: u8,
+    seq_num: u64,
+    target: WaitTarget,
+    until: WaitUntil,
+    timeout: Duration,
+    interrupt: impl Future<Output = ()>,
+) -> WaitReport {
+    let start = Instant::now();
+    let progress = Mutex::new(Progress::default());
+    let poll_once = || async {
+        let status = source.status().await?;
+        let mut progress = progress.lock().unwrap();
+        if progress.observed.last() != Some(&status) {
+            info!("Transfer status is {:?}", status);
+            progress.observed.push(status.clone());
+        }
+        progress.status = Some(status.clone());
+        if until.is_reached(&status) {
+            Ok(())
+        } else {
+            Err(anyhow!("Transfer status is {:?}", status))
+        }
+    };
+    let outcome = tokio::select! {
+        result = async { retry_with_max_elapsed_time!(poll_once(), timeout) } => match result {
+            Ok(_) => WaitOutcome::Reached,
+            Err(_) => {
+                if progress.lock().unwrap().status == Some(BridgeActionStatus::NotFound) {
+                    WaitOutcome::NotFound
+                } else {
+                    WaitOutcome::Timeout
+                }
+            }
+        },
+        _ = interrupt => WaitOutcome::Interrupted,
+    };
+    let progress = progress.into_inner().unwrap();
+    WaitReport {
+        source_chain_id,
+        seq_num,
+        target,
+        until,
+        outcome,
+        status: progress.status,
+        observed: progress.observed,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_bridge::starcoin_bridge_mock_client::StarcoinMockClient;
+    use BridgeActionStatus::{Approved, Claimed, NotFound, Pending};
+
+    const SOURCE_CHAIN_ID: u8 = 12;
+    const SEQ_NUM: u64 = 7;
+
+    fn source(mock: &StarcoinMockClient) -> StarcoinStatusSource<StarcoinMockClient> {
+        StarcoinStatusSource {
+            starcoin_bridge_client: StarcoinClient::new_for_testing(mock.clone()),
+            source_chain_id: SOURCE_CHAIN_ID,
+            seq_num: SEQ_NUM,
+        }
+    }
+
+    // Sets the status of the transfer to each of `statuses` in turn, `delay`
+    // apart
+    fn transition(mock: &StarcoinMockClient, statuses: Vec<BridgeActionStatus>, delay: Duration) {
+        let mock = mock.clone();
+        tokio::spawn(async move {
+            for status in statuses {
+                tokio::time::sleep(delay).await;
+                mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, status);
+            }
+        });
+    }
+
+    async fn wait(
+        source: &dyn ActionStatusSource,
+        until: WaitUntil,
+        timeout: Duration,
+    ) -> WaitReport {
+        wait_for_action(
+            source,
+            SOURCE_CHAIN_ID,
+            SEQ_NUM,
+            WaitTarget::Starcoin,
+            until,
+            timeout,
+            std::future::pending(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_claimed() {
+        let mock = StarcoinMockClient::default();
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, Pending);
+        transition(&mock, vec![Approved, Claimed], Duration::from_millis(800));
+
+        let report = wait(&source(&mock), WaitUntil::Claimed, Duration::from_secs(30)).await;
+        assert_eq!(report.outcome, WaitOutcome::Reached);
+        assert_eq!(report.status, Some(Claimed));
+        assert_eq!(report.observed, vec![Pending, Approved, Claimed]);
+        assert_eq!(report.exit_code(), 0);
+        assert!(report.elapsed_ms >= 1600);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_approved() {
+        let mock = StarcoinMockClient::default();
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, Pending);
+        transition(&mock, vec![Approved], Duration::from_millis(500));
+
+        let report = wait(&source(&mock), WaitUntil::Approved, Duration::from_secs(30)).await;
+        assert_eq!(report.outcome, WaitOutcome::Reached);
+        assert_eq!(report.status, Some(Approved));
+        assert_eq!(report.observed, vec![Pending, Approved]);
+
+        // A claimed transfer is approved too
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, Claimed);
+        let report = wait(&source(&mock), WaitUntil::Approved, Duration::from_secs(30)).await;
+        assert_eq!(report.outcome, WaitOutcome::Reached);
+        assert_eq!(report.observed, vec![Claimed]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_timeout() {
+        let mock = StarcoinMockClient::default();
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, Pending);
+
+        let timeout = Duration::from_secs(1);
+        let report = wait(&source(&mock), WaitUntil::Claimed, timeout).await;
+        assert_eq!(report.outcome, WaitOutcome::Timeout);
+        assert_eq!(report.status, Some(Pending));
+        assert_eq!(report.observed, vec![Pending]);
+        assert_eq!(report.exit_code(), EXIT_TIMEOUT);
+        assert!(report.elapsed_ms >= timeout.as_millis() as u64);
+
+        // A transfer that is never recorded is reported as not found
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, NotFound);
+        let report = wait(&source(&mock), WaitUntil::Claimed, timeout).await;
+        assert_eq!(report.outcome, WaitOutcome::NotFound);
+        assert_eq!(report.exit_code(), EXIT_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_wait_interrupted() {
+        let mock = StarcoinMockClient::default();
+        mock.set_token_transfer_onchain_status(SOURCE_CHAIN_ID, SEQ_NUM, Approved);
+
+        let report = wait_for_action(
+            &source(&mock),
+            SOURCE_CHAIN_ID,
+            SEQ_NUM,
+            WaitTarget::Starcoin,
+            WaitUntil::Claimed,
+            Duration::from_secs(30),
+            tokio::time::sleep(Duration::from_millis(600)),
+        )
+        .await;
+        // The last status read is still reported
+        assert_eq!(report.outcome, WaitOutcome::Interrupted);
+        assert_eq!(report.status, Some(Approved));
+        assert_eq!(report.exit_code(), EXIT_INTERRUPTED);
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Emergency pause and unpause of the local bridge, driven through the CLI
+//! binary so argument parsing and config loading are covered too.
+//!
+//! Needs the environment of `./setup.sh -y` with the bridge server running,
+//! since the governance actions are signed by its committee member, and an
+//! Eth key funded by `make fund-eth-account`. Runs only when
+//! BRIDGE_TEST_LOCAL_ENV_DIR is the directory `setup.sh` ran in:
+//!
+//!   BRIDGE_TEST_LOCAL_ENV_DIR=$PWD cargo test -p starcoin-bridge-cli \
+//!     --test local_env_emergency -- --nocapture
+//!
+//! The committee is registered by `setup.sh` when the Move contracts are
+//! deployed, and the Starcoin bridge has a single member, so the validator
+//! key generated here is only examined, not registered.
+
+use starcoin_bridge::starcoin_bridge_client::StarcoinBridgeClient;
+use starcoin_bridge_cli::BridgeCliConfig;
+use starcoin_bridge_config::Config;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Chain id of the local Starcoin dev chain, as `setup.sh` deploys it
+const STARCOIN_CHAIN_ID: &str = "2";
+
+fn cli(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_starcoin-bridge-cli"))
+        .args(args)
+        .env("NO_PROXY", "localhost,127.0.0.1")
+        .output()
+        .expect("Failed to run starcoin-bridge-cli");
+    println!("$ starcoin-bridge-cli {}", args.join(" "));
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    output
+}
+
+fn cli_ok(args: &[&str]) -> String {
+    let output = cli(args);
+    assert!(
+        output.status.success(),
+        "starcoin-bridge-cli {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+// Last word of the line of `examine-key` output starting with `label`
+fn examined(output: &str, label: &str) -> String {
+    output
+        .lines()
+        .find(|line| line.starts_with(label))
+        .and_then(|line| line.split_whitespace().last())
+        .unwrap_or_else(|| panic!("No {label} in examine-key output"))
+        .trim_matches('"')
+        .to_string()
+}
+
+fn emergency_button(config_path: &Path, action_type: &str) {
+    cli_ok(&[
+        "governance",
+        "--config-path",
+        config_path.to_str().unwrap(),
+        "--chain-id",
+        STARCOIN_CHAIN_ID,
+        "emergency-button",
+        "--nonce",
+        "auto",
+        "--action-type",
+        action_type,
+    ]);
+}
+
+#[tokio::test]
+async fn test_local_env_emergency_pause_and_unpause() {
+    let Ok(env_dir) = std::env::var("BRIDGE_TEST_LOCAL_ENV_DIR") else {
+        println!("BRIDGE_TEST_LOCAL_ENV_DIR is not set, skipping test");
+        return;
+    };
+    let env_dir = PathBuf::from(env_dir);
+    // Removed with everything in it when dropped, also when the test fails
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // 1. Generate a validator key and check it reads back as one
+    let validator_key = temp_dir.path().join("bridge_authority.key");
+    cli_ok(&[
+        "create-bridge-validator-key",
+        validator_key.to_str().unwrap(),
+    ]);
+    let examined_key = cli_ok(&[
+        "examine-key",
+        validator_key.to_str().unwrap(),
+        "--is-validator-key",
+    ]);
+    assert!(examined_key.contains("Corresponding Ethereum address"));
+
+    // 2. Copy the CLI config of the local environment, so the test can't
+    // change the one `make` uses
+    let config = BridgeCliConfig::load(env_dir.join("bridge-config/cli-config.yaml"))
+        .expect("Run `make init-cli-config` in the local environment first");
+    let config_path = temp_dir.path().join("cli-config.yaml");
+    config.save(&config_path).unwrap();
+    let config_arg = config_path.to_str().unwrap();
+
+    let client_key = config
+        .starcoin_bridge_key_path
+        .clone()
+        .expect("The local CLI config has no Starcoin key");
+    let recipient = examined(
+        &cli_ok(&["examine-key", client_key.to_str().unwrap()]),
+        "Corresponding Starcoin address",
+    );
+    let deposit = [
+        "client",
+        "--config-path",
+        config_arg,
+        "deposit-native-ether-on-eth",
+        "--ether-amount",
+        "0.01",
+        "--target-chain",
+        STARCOIN_CHAIN_ID,
+        "--starcoin-bridge-recipient-address",
+        &recipient,
+        "--allow-unknown-account",
+    ];
+
+    let starcoin_client = StarcoinBridgeClient::new(
+        &config.starcoin_bridge_rpc_url,
+        &config.starcoin_bridge_proxy_address,
+    );
+    assert!(
+        !starcoin_client.is_bridge_paused().await.unwrap(),
+        "The local bridge is already paused"
+    );
+
+    // 3. Pause, then try a deposit to Starcoin
+    emergency_button(&config_path, "pause");
+    let paused = starcoin_client.is_bridge_paused().await;
+    let rejected_deposit = cli(&deposit);
+
+    // Unpause before checking anything, so a failed check doesn't leave the
+    // local bridge paused for other tests
+    emergency_button(&config_path, "unpause");
+    assert!(paused.unwrap(), "The bridge is not paused after pause");
+    assert!(!rejected_deposit.status.success());
+    let stderr = String::from_utf8_lossy(&rejected_deposit.stderr);
+    assert!(
+        stderr.contains("Bridge is paused on Starcoin"),
+        "Unexpected deposit error: {stderr}"
+    );
+
+    // 4. Deposits go through again once unpaused
+    assert!(!starcoin_client.is_bridge_paused().await.unwrap());
+    cli_ok(&deposit);
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Committee registration against the local bridge, driven through the CLI
+//! binary so argument parsing and config loading are covered too.
+//!
+//! Needs the environment of `./setup.sh -y`. Runs only when
+//! BRIDGE_TEST_LOCAL_ENV_DIR is the directory `setup.sh` ran in:
+//!
+//!   BRIDGE_TEST_LOCAL_ENV_DIR=$PWD cargo test -p starcoin-bridge-cli \
+//!     --test local_env_register_committee -- --nocapture
+//!
+//! `setup.sh` registers the authority key of the local bridge node and
+//! creates the committee when the Move contracts are deployed, so the
+//! registration of that key is found instead of submitted, and registering
+//! any other key is refused.
+
+use starcoin_bridge_cli::BridgeCliConfig;
+use starcoin_bridge_config::Config;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const NODE_URL: &str = "http://127.0.0.1:9191";
+
+fn cli(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_starcoin-bridge-cli"))
+        .args(args)
+        .env("NO_PROXY", "localhost,127.0.0.1")
+        .output()
+        .expect("Failed to run starcoin-bridge-cli");
+    println!("$ starcoin-bridge-cli {}", args.join(" "));
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    output
+}
+
+fn register_committee(config_path: &str, key_path: &str) -> Output {
+    cli(&[
+        "--output",
+        "json",
+        "register-committee",
+        "--config-path",
+        config_path,
+        "--bridge-authority-key-path",
+        key_path,
+        "--node-url",
+        NODE_URL,
+    ])
+}
+
+#[tokio::test]
+async fn test_local_env_register_committee() {
+    let Ok(env_dir) = std::env::var("BRIDGE_TEST_LOCAL_ENV_DIR") else {
+        println!("BRIDGE_TEST_LOCAL_ENV_DIR is not set, skipping test");
+        return;
+    };
+    let env_dir = PathBuf::from(env_dir);
+    // Removed with everything in it when dropped, also when the test fails
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // Copy the CLI config of the local environment, so the test can't change
+    // the one `make` uses
+    let config = BridgeCliConfig::load(env_dir.join("bridge-config/cli-config.yaml"))
+        .expect("Run `make init-cli-config` in the local environment first");
+    let config_path = temp_dir.path().join("cli-config.yaml");
+    config.save(&config_path).unwrap();
+    let config_arg = config_path.to_str().unwrap();
+
+    // 1. The key of the local bridge node is registered, its entry is
+    // returned and nothing is submitted
+    let node_key = env_dir.join("bridge-node/server-config/bridge_authority.key");
+    let output = register_committee(config_arg, node_key.to_str().unwrap());
+    assert!(
+        output.status.success(),
+        "register-committee failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let registration: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(registration["status"], "already-registered");
+    assert!(registration.get("tx").is_none());
+    assert_eq!(registration["bridge-pubkey"].as_str().unwrap().len(), 66);
+
+    // 2. A new key can't join a created committee
+    let new_key = temp_dir.path().join("bridge_authority.key");
+    let created = cli(&["create-bridge-validator-key", new_key.to_str().unwrap()]);
+    assert!(created.status.success());
+    let output = register_committee(config_arg, new_key.to_str().unwrap());
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("registration is closed"));
+
+    // 3. Urls are checked before anything is loaded
+    let output = cli(&[
+        "register-committee",
+        "--config-path",
+        config_arg,
+        "--bridge-authority-key-path",
+        node_key.to_str().unwrap(),
+        "--node-url",
+        "ftp://127.0.0.1:9191/path",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not http or https"));
+}
@@ -0,0 +1,920 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read-only HTTP API over the indexed tables, served when `--api-address`
+//! is set. Responses are the types in `api_types`.
+//!
+//! Transfers are listed from `token_transfer_status`. The detail of a single
+//! transfer also merges in its `token_transfer` events, which include those
+//! indexed from Eth.
+
+use crate::api_types::{
+    ApiError, GovernanceAction, GovernanceActionPage, Stats, TokenVolume, Transfer, TransferDetail,
+    TransferEvent, TransferPage, TransferStatus,
+};
+use crate::metrics::BridgeIndexerMetrics;
+use anyhow::Context;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use diesel::dsl::{count_star, sql};
+use diesel::sql_types::{BigInt, Text};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+};
+use diesel_async::pooled_connection::deadpool::Pool;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use starcoin_bridge::tx_digest::{tagged, TxChain};
+use starcoin_bridge_schema::models::{
+    BridgeDataSource, GovernanceAction as GovernanceActionRow, GovernanceActionType, TokenTransfer,
+    TokenTransferData, TokenTransferState, TokenTransferStatus,
+};
+use starcoin_bridge_schema::schema::{
+    governance_actions, token_transfer, token_transfer_data, token_transfer_status,
+};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+// When a transfer started, as far as the indexer knows. Transfers are listed
+// newest first by it.
+const STARTED_AT: &str = "COALESCE(deposited_at, approved_at, claimed_at)";
+
+#[derive(Clone)]
+struct ApiState {
+    pool: Pool<AsyncPgConnection>,
+    metrics: Arc<BridgeIndexerMetrics>,
+}
+
+pub fn router(pool: Pool<AsyncPgConnection>, metrics: Arc<BridgeIndexerMetrics>) -> Router {
+    Router::new()
+        .route("/transfers", get(get_transfers))
+        .route("/transfers/:source_chain/:nonce", get(get_transfer))
+        .route("/governance_actions", get(get_governance_actions))
+        .route("/stats", get(get_stats))
+        .with_state(ApiState { pool, metrics })
+}
+
+// Serves the API on `address` until `cancel` is cancelled
+pub async fn run_api_server(
+    address: SocketAddr,
+    pool: Pool<AsyncPgConnection>,
+    metrics: Arc<BridgeIndexerMetrics>,
+    cancel: CancellationToken,
+) -> anyhow::Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(address)
+        .await
+        .with_context(|| format!("Failed to bind indexer API at {address}"))?;
+    let app = router(pool, metrics);
+
+    Ok(tokio::spawn(async move {
+        info!("Starting indexer API on {}", address);
+        if let Err(e) = axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                cancel.cancelled().await;
+                info!("Shutdown received, shutting down indexer API");
+            })
+            .await
+        {
+            error!("Indexer API failed: {:?}", e);
+        }
+    }))
+}
+
+// An error response, sent with an `ApiError` body
+#[derive(Debug)]
+struct Failure {
+    status: StatusCode,
+    message: String,
+}
+
+impl Failure {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for Failure {
+    fn from(e: anyhow::Error) -> Self {
+        // Database errors stay in the logs
+        error!("Indexer API query failed: {:?}", e);
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: "Internal error".to_string(),
+        }
+    }
+}
+
+impl IntoResponse for Failure {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(ApiError {
+                error: self.message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+// Runs the query of `endpoint` and records its latency by response status
+async fn respond<T: Serialize>(
+    state: &ApiState,
+    endpoint: &str,
+    query: impl Future<Output = Result<T, Failure>>,
+) -> Response {
+    let start = Instant::now();
+    let response = match query.await {
+        Ok(body) => Json(body).into_response(),
+        Err(failure) => failure.into_response(),
+    };
+    state
+        .metrics
+        .api_request_latency
+        .with_label_values(&[endpoint, response.status().as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    response
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TransferQuery {
+    // Source chain id
+    chain: Option<u8>,
+    status: Option<String>,
+    // Sender or recipient, hex with or without 0x
+    address: Option<String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GovernanceActionQuery {
+    // e.g. `UpdateBridgeLimit`
+    action: Option<String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn get_transfers(
+    State(state): State<ApiState>,
+    Query(query): Query<TransferQuery>,
+) -> Response {
+    respond(&state, "transfers", query_transfers(&state.pool, query)).await
+}
+
+async fn get_transfer(
+    State(state): State<ApiState>,
+    Path((source_chain, nonce)): Path<(u8, u64)>,
+) -> Response {
+    respond(
+        &state,
+        "transfer",
+        query_transfer(&state.pool, source_chain, nonce),
+    )
+    .await
+}
+
+async fn get_governance_actions(
+    State(state): State<ApiState>,
+    Query(query): Query<GovernanceActionQuery>,
+) -> Response {
+    respond(
+        &state,
+        "governance_actions",
+        query_governance_actions(&state.pool, query),
+    )
+    .await
+}
+
+async fn get_stats(State(state): State<ApiState>) -> Response {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    respond(&state, "stats", query_stats(&state.pool, now_ms)).await
+}
+
+async fn query_transfers(
+    pool: &Pool<AsyncPgConnection>,
+    query: TransferQuery,
+) -> Result<TransferPage, Failure> {
+    use token_transfer_status::dsl;
+
+    let limit = page_size(query.limit)?;
+    let mut rows = dsl::token_transfer_status
+        .select(TokenTransferState::as_select())
+        .into_boxed();
+    if let Some(chain) = query.chain {
+        rows = rows.filter(dsl::source_chain_id.eq(chain as i32));
+    }
+    if let Some(status) = &query.status {
+        rows = rows.filter(dsl::status.eq(parse_status(status)?));
+    }
+    if let Some(address) = &query.address {
+        let address = parse_address(address)?;
+        rows = rows.filter(
+            dsl::sender
+                .eq(address.clone())
+                .or(dsl::recipient.eq(address)),
+        );
+    }
+    if let Some(cursor) = &query.cursor {
+        let [started_at, chain, nonce] = parse_cursor(cursor)?;
+        let started = || sql::<BigInt>(STARTED_AT);
+        rows = rows.filter(
+            started().lt(started_at).or(started().eq(started_at).and(
+                dsl::source_chain_id
+                    .lt(chain as i32)
+                    .or(dsl::source_chain_id
+                        .eq(chain as i32)
+                        .and(dsl::nonce.lt(nonce))),
+            )),
+        );
+    }
+
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get database connection")?;
+    let mut rows: Vec<TokenTransferState> = rows
+        .order((
+            sql::<BigInt>(STARTED_AT).desc(),
+            dsl::source_chain_id.desc(),
+            dsl::nonce.desc(),
+        ))
+        .limit(limit + 1)
+        .load(&mut conn)
+        .await
+        .context("Failed to query transfers")?;
+
+    let next_cursor = next_page(&mut rows, limit).map(|last| {
+        format!(
+            "{}:{}:{}",
+            started_at(last),
+            last.source_chain_id,
+            last.nonce
+        )
+    });
+    Ok(TransferPage {
+        transfers: rows.into_iter().map(transfer).collect(),
+        next_cursor,
+    })
+}
+
+async fn query_transfer(
+    pool: &Pool<AsyncPgConnection>,
+    source_chain: u8,
+    nonce: u64,
+) -> Result<TransferDetail, Failure> {
+    let key = (source_chain as i32, nonce as i64);
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get database connection")?;
+
+    let state = token_transfer_status::table
+        .find(key)
+        .select(TokenTransferState::as_select())
+        .first(&mut conn)
+        .await
+        .optional()
+        .context("Failed to query transfer status")?;
+    let data = token_transfer_data::table
+        .find(key)
+        .select(TokenTransferData::as_select())
+        .first(&mut conn)
+        .await
+        .optional()
+        .context("Failed to query transfer data")?;
+    let events = token_transfer::table
+        .filter(token_transfer::chain_id.eq(key.0))
+        .filter(token_transfer::nonce.eq(key.1))
+        .order((
+            token_transfer::timestamp_ms.asc(),
+            token_transfer::block_height.asc(),
+        ))
+        .select(TokenTransfer::as_select())
+        .load(&mut conn)
+        .await
+        .context("Failed to query transfer events")?;
+
+    transfer_detail(key, state, data, events).ok_or_else(|| {
+        Failure::not_found(format!(
+            "Transfer {nonce} from chain {source_chain} not found"
+        ))
+    })
+}
+
+async fn query_governance_actions(
+    pool: &Pool<AsyncPgConnection>,
+    query: GovernanceActionQuery,
+) -> Result<GovernanceActionPage, Failure> {
+    use governance_actions::dsl;
+
+    let limit = page_size(query.limit)?;
+    let mut rows = dsl::governance_actions
+        .select((dsl::id, GovernanceActionRow::as_select()))
+        .into_boxed();
+    if let Some(action) = &query.action {
+        let action = GovernanceActionType::from_str(action)
+            .map_err(|_| Failure::bad_request(format!("Unknown action `{action}`")))?;
+        rows = rows.filter(dsl::action.eq(action));
+    }
+    if let Some(cursor) = &query.cursor {
+        let [timestamp_ms, id] = parse_cursor(cursor)?;
+        rows = rows.filter(
+            dsl::timestamp_ms
+                .lt(timestamp_ms)
+                .or(dsl::timestamp_ms.eq(timestamp_ms).and(dsl::id.lt(id))),
+        );
+    }
+
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get database connection")?;
+    let mut rows: Vec<(i64, GovernanceActionRow)> = rows
+        .order((dsl::timestamp_ms.desc(), dsl::id.desc()))
+        .limit(limit + 1)
+        .load(&mut conn)
+        .await
+        .context("Failed to query governance actions")?;
+
+    let next_cursor =
+        next_page(&mut rows, limit).map(|(id, last)| format!("{}:{}", last.timestamp_ms, id));
+    Ok(GovernanceActionPage {
+        actions: rows
+            .into_iter()
+            .map(|(_, action)| governance_action(action))
+            .collect(),
+        next_cursor,
+    })
+}
+
+async fn query_stats(pool: &Pool<AsyncPgConnection>, now_ms: i64) -> Result<Stats, Failure> {
+    use token_transfer_data::dsl;
+
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get database connection")?;
+    // Counted from the events rather than `token_transfer_status`, which
+    // doesn't see transfers deposited or claimed on Eth
+    let total_transfers: i64 = token_transfer::table
+        .select(sql::<BigInt>("COUNT(DISTINCT (chain_id, nonce))"))
+        .get_result(&mut conn)
+        .await
+        .context("Failed to count transfers")?;
+    let claimed_transfers: i64 = token_transfer::table
+        .filter(token_transfer::status.eq(TokenTransferStatus::Claimed))
+        .count()
+        .get_result(&mut conn)
+        .await
+        .context("Failed to count claimed transfers")?;
+    let volumes: Vec<(i32, i64, String)> = dsl::token_transfer_data
+        .filter(dsl::timestamp_ms.gt(now_ms - DAY_MS))
+        .group_by(dsl::token_id)
+        .select((
+            dsl::token_id,
+            count_star(),
+            sql::<Text>("SUM(amount)::TEXT"),
+        ))
+        .order(dsl::token_id.asc())
+        .load(&mut conn)
+        .await
+        .context("Failed to query transfer volume")?;
+
+    Ok(Stats {
+        total_transfers: total_transfers as u64,
+        pending_transfers: total_transfers.saturating_sub(claimed_transfers) as u64,
+        volume_24h: volumes
+            .into_iter()
+            .map(|(token_id, transfers, amount)| TokenVolume {
+                token_id: token_id as u8,
+                transfers: transfers as u64,
+                amount,
+            })
+            .collect(),
+    })
+}
+
+fn page_size(limit: Option<i64>) -> Result<i64, Failure> {
+    match limit {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(limit) if (1..=MAX_PAGE_SIZE).contains(&limit) => Ok(limit),
+        Some(limit) => Err(Failure::bad_request(format!(
+            "Invalid limit {limit}, expected 1 to {MAX_PAGE_SIZE}"
+        ))),
+    }
+}
+
+// Drops the extra row fetched past `limit`. If there was one, the last row
+// kept is where the next page starts.
+fn next_page<T>(rows: &mut Vec<T>, limit: i64) -> Option<&T> {
+    if rows.len() as i64 <= limit {
+        return None;
+    }
+    rows.truncate(limit as usize);
+    rows.last()
+}
+
+fn parse_cursor<const N: usize>(cursor: &str) -> Result<[i64; N], Failure> {
+    cursor
+        .split(':')
+        .map(i64::from_str)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+        .and_then(|parts| parts.try_into().ok())
+        .ok_or_else(|| Failure::bad_request(format!("Invalid cursor `{cursor}`")))
+}
+
+fn parse_status(status: &str) -> Result<TokenTransferStatus, Failure> {
+    match status.to_ascii_lowercase().as_str() {
+        "deposited" => Ok(TokenTransferStatus::Deposited),
+        "approved" => Ok(TokenTransferStatus::Approved),
+        "claimed" => Ok(TokenTransferStatus::Claimed),
+        _ => Err(Failure::bad_request(format!(
+            "Invalid status `{status}`, expected deposited, approved or claimed"
+        ))),
+    }
+}
+
+fn parse_address(address: &str) -> Result<Vec<u8>, Failure> {
+    hex::decode(address.trim_start_matches("0x"))
+        .ok()
+        .filter(|bytes| !bytes.is_empty())
+        .ok_or_else(|| Failure::bad_request(format!("Invalid address `{address}`")))
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn tx_chain(data_source: BridgeDataSource) -> TxChain {
+    match data_source {
+        BridgeDataSource::STARCOIN => TxChain::Starcoin,
+        BridgeDataSource::ETH => TxChain::Eth,
+    }
+}
+
+fn started_at(state: &TokenTransferState) -> i64 {
+    state
+        .deposited_at
+        .or(state.approved_at)
+        .or(state.claimed_at)
+        .unwrap_or_default()
+}
+
+fn transfer_status(status: TokenTransferStatus) -> TransferStatus {
+    match status {
+        TokenTransferStatus::Deposited => TransferStatus::Deposited,
+        TokenTransferStatus::Approved => TransferStatus::Approved,
+        TokenTransferStatus::Claimed => TransferStatus::Claimed,
+    }
+}
+
+fn transfer(state: TokenTransferState) -> Transfer {
+    Transfer {
+        source_chain: state.source_chain_id as u8,
+        nonce: state.nonce as u64,
+        status: transfer_status(state.status),
+        deposited_at_ms: state.deposited_at,
+        approved_at_ms: state.approved_at,
+        claimed_at_ms: state.claimed_at,
+        token_id: state.token_id.map(|id| id as u8),
+        amount: state.amount.map(|amount| amount as u64),
+        sender: state.sender.as_deref().map(hex_string),
+        recipient: state.recipient.as_deref().map(hex_string),
+    }
+}
+
+// Merges what is known of a transfer. `state` misses the events indexed from
+// Eth, so the timestamps and status are completed from `events`, and the
+// deposit fields from `data`. None if nothing is known.
+fn transfer_detail(
+    (source_chain_id, nonce): (i32, i64),
+    state: Option<TokenTransferState>,
+    data: Option<TokenTransferData>,
+    events: Vec<TokenTransfer>,
+) -> Option<TransferDetail> {
+    if state.is_none() && data.is_none() && events.is_empty() {
+        return None;
+    }
+    let mut state = state.unwrap_or(TokenTransferState {
+        source_chain_id,
+        nonce,
+        deposited_at: None,
+        approved_at: None,
+        claimed_at: None,
+        amount: None,
+        sender: None,
+        recipient: None,
+        token_id: None,
+        status: TokenTransferStatus::Deposited,
+    });
+    if let Some(data) = &data {
+        state.deposited_at = state.deposited_at.or(Some(data.timestamp_ms));
+        state.amount = state.amount.or(Some(data.amount));
+        state.sender = state
+            .sender
+            .take()
+            .or_else(|| Some(data.sender_address.clone()));
+        state.recipient = state
+            .recipient
+            .take()
+            .or_else(|| Some(data.recipient_address.clone()));
+        state.token_id = state.token_id.or(Some(data.token_id));
+    }
+    for event in &events {
+        let at = match event.status {
+            TokenTransferStatus::Deposited => &mut state.deposited_at,
+            TokenTransferStatus::Approved => &mut state.approved_at,
+            TokenTransferStatus::Claimed => &mut state.claimed_at,
+        };
+        *at = at.or(Some(event.timestamp_ms));
+    }
+    state.status = if state.claimed_at.is_some() {
+        TokenTransferStatus::Claimed
+    } else if state.approved_at.is_some() {
+        TokenTransferStatus::Approved
+    } else {
+        TokenTransferStatus::Deposited
+    };
+
+    Some(TransferDetail {
+        transfer: transfer(state),
+        destination_chain: data.map(|data| data.destination_chain as u8),
+        events: events
+            .into_iter()
+            .map(|event| TransferEvent {
+                status: transfer_status(event.status),
+                tx_hash: tagged(tx_chain(event.data_source), &hex_string(&event.txn_hash)),
+                block_height: event.block_height,
+                timestamp_ms: event.timestamp_ms,
+                finalized: event.is_finalized,
+            })
+            .collect(),
+    })
+}
+
+fn governance_action(action: GovernanceActionRow) -> GovernanceAction {
+    GovernanceAction {
+        nonce: action.nonce.map(|nonce| nonce as u64),
+        action: action.action.as_ref().to_string(),
+        tx_hash: tagged(
+            tx_chain(action.data_source),
+            &hex_string(&action.txn_digest),
+        ),
+        sender: hex_string(&action.sender_address),
+        timestamp_ms: action.timestamp_ms,
+        block_height: action.block_height,
+        data: action.data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+    use serde::de::DeserializeOwned;
+    use starcoin_bridge_pg_db::temp::TempDb;
+    use starcoin_bridge_pg_db::{Db, DbArgs};
+    use starcoin_bridge_schema::MIGRATIONS;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn deposit_state(source_chain_id: i32, nonce: i64, deposited_at: i64) -> TokenTransferState {
+        TokenTransferState {
+            source_chain_id,
+            nonce,
+            deposited_at: Some(deposited_at),
+            approved_at: None,
+            claimed_at: None,
+            amount: Some(1000),
+            sender: Some(vec![1; 16]),
+            recipient: Some(vec![2; 20]),
+            token_id: Some(3),
+            status: TokenTransferStatus::Deposited,
+        }
+    }
+
+    fn deposit_data(
+        chain_id: i32,
+        nonce: i64,
+        timestamp_ms: i64,
+        amount: i64,
+    ) -> TokenTransferData {
+        TokenTransferData {
+            chain_id,
+            nonce,
+            block_height: 10,
+            timestamp_ms,
+            txn_hash: vec![nonce as u8; 32],
+            sender_address: vec![1; 16],
+            destination_chain: 12,
+            recipient_address: vec![2; 20],
+            token_id: 3,
+            amount,
+            is_finalized: true,
+            sender_address_canonical: format!("0x{}", "01".repeat(16)),
+            recipient_address_canonical: format!("0x{}", "02".repeat(20)),
+        }
+    }
+
+    fn event(
+        chain_id: i32,
+        nonce: i64,
+        status: TokenTransferStatus,
+        data_source: BridgeDataSource,
+        timestamp_ms: i64,
+    ) -> TokenTransfer {
+        TokenTransfer {
+            chain_id,
+            nonce,
+            status,
+            block_height: 10,
+            timestamp_ms,
+            txn_hash: vec![0xab; 32],
+            txn_sender: vec![1; 16],
+            gas_usage: 0,
+            data_source,
+            is_finalized: true,
+        }
+    }
+
+    fn governance_row(nonce: i64, timestamp_ms: i64) -> GovernanceActionRow {
+        GovernanceActionRow {
+            nonce: Some(nonce),
+            data_source: BridgeDataSource::ETH,
+            txn_digest: vec![nonce as u8; 32],
+            sender_address: vec![4; 20],
+            timestamp_ms,
+            action: GovernanceActionType::UpdateBridgeLimit,
+            data: serde_json::json!({ "new_limit": nonce }),
+            block_height: Some(nonce),
+        }
+    }
+
+    // A migrated test database with the seeded rows, and the API served
+    // over it. Returns the base URL of the API.
+    async fn serve_seeded_db() -> (TempDb, String) {
+        let db = TempDb::new().unwrap();
+        let url = db.database().url().clone();
+        Db::for_write(url.clone(), DbArgs::default())
+            .await
+            .unwrap()
+            .run_migrations(Some(&MIGRATIONS))
+            .await
+            .unwrap();
+        let pool = Pool::builder(AsyncDieselConnectionManager::<AsyncPgConnection>::new(
+            url.as_str(),
+        ))
+        .build()
+        .unwrap();
+
+        let mut conn = pool.get().await.unwrap();
+        // Three transfers: (2, 0) from Starcoin, claimed on Eth, so only its
+        // deposit is in `token_transfer_status`; (12, 0) from Eth, approved
+        // on Starcoin; (2, 1) from Starcoin, deposited over a day ago
+        let mut approved = deposit_state(12, 0, NOW_MS - 3_000);
+        approved.approved_at = Some(NOW_MS - 2_000);
+        approved.status = TokenTransferStatus::Approved;
+        diesel::insert_into(token_transfer_status::table)
+            .values(vec![
+                deposit_state(2, 0, NOW_MS - 1_000),
+                approved,
+                deposit_state(2, 1, NOW_MS - DAY_MS - 1),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        diesel::insert_into(token_transfer_data::table)
+            .values(vec![
+                deposit_data(2, 0, NOW_MS - 1_000, 1000),
+                deposit_data(12, 0, NOW_MS - 3_000, 500),
+                deposit_data(2, 1, NOW_MS - DAY_MS - 1, 7000),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        diesel::insert_into(token_transfer::table)
+            .values(vec![
+                event(
+                    2,
+                    0,
+                    TokenTransferStatus::Deposited,
+                    BridgeDataSource::STARCOIN,
+                    NOW_MS - 1_000,
+                ),
+                event(
+                    2,
+                    0,
+                    TokenTransferStatus::Claimed,
+                    BridgeDataSource::ETH,
+                    NOW_MS - 500,
+                ),
+                event(
+                    12,
+                    0,
+                    TokenTransferStatus::Deposited,
+                    BridgeDataSource::ETH,
+                    NOW_MS - 3_000,
+                ),
+                event(
+                    12,
+                    0,
+                    TokenTransferStatus::Approved,
+                    BridgeDataSource::STARCOIN,
+                    NOW_MS - 2_000,
+                ),
+                event(
+                    2,
+                    1,
+                    TokenTransferStatus::Deposited,
+                    BridgeDataSource::STARCOIN,
+                    NOW_MS - DAY_MS - 1,
+                ),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        diesel::insert_into(governance_actions::table)
+            .values(vec![
+                governance_row(1, NOW_MS - 2_000),
+                governance_row(2, NOW_MS - 1_000),
+                governance_row(3, NOW_MS - 1_000),
+            ])
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        drop(conn);
+
+        let registry = prometheus::Registry::new();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = router(pool, BridgeIndexerMetrics::new(&registry));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (db, format!("http://{address}"))
+    }
+
+    async fn get_json<T: DeserializeOwned>(url: String) -> (StatusCode, T) {
+        let response = reqwest::get(url).await.unwrap();
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+        let body = response.bytes().await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn keys(page: &TransferPage) -> Vec<(u8, u64)> {
+        page.transfers
+            .iter()
+            .map(|transfer| (transfer.source_chain, transfer.nonce))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_transfers() {
+        let (_db, api) = serve_seeded_db().await;
+
+        // Newest first, one page at a time
+        let (status, page) = get_json::<TransferPage>(format!("{api}/transfers?limit=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(keys(&page), vec![(2, 0), (12, 0)]);
+        assert_eq!(page.transfers[0].sender, Some(hex_string(&[1; 16])));
+        let cursor = page.next_cursor.unwrap();
+        let (_, page) =
+            get_json::<TransferPage>(format!("{api}/transfers?limit=2&cursor={cursor}")).await;
+        assert_eq!(keys(&page), vec![(2, 1)]);
+        assert_eq!(page.next_cursor, None);
+
+        let (_, page) = get_json::<TransferPage>(format!("{api}/transfers?chain=12")).await;
+        assert_eq!(keys(&page), vec![(12, 0)]);
+        let (_, page) = get_json::<TransferPage>(format!("{api}/transfers?status=Approved")).await;
+        assert_eq!(keys(&page), vec![(12, 0)]);
+        let address = hex_string(&[2; 20]);
+        let (_, page) =
+            get_json::<TransferPage>(format!("{api}/transfers?address={address}")).await;
+        assert_eq!(keys(&page), vec![(2, 0), (12, 0), (2, 1)]);
+        let (_, page) = get_json::<TransferPage>(format!("{api}/transfers?address=0x05")).await;
+        assert_eq!(keys(&page), vec![]);
+
+        for query in ["status=refunded", "cursor=abc", "address=0xzz", "limit=0"] {
+            let (status, error) = get_json::<ApiError>(format!("{api}/transfers?{query}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{query}: {}", error.error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_detail() {
+        let (_db, api) = serve_seeded_db().await;
+
+        // The claim on Eth is only known from the events
+        let (status, detail) = get_json::<TransferDetail>(format!("{api}/transfers/2/0")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(detail.transfer.status, TransferStatus::Claimed);
+        assert_eq!(detail.transfer.claimed_at_ms, Some(NOW_MS - 500));
+        assert_eq!(detail.transfer.amount, Some(1000));
+        assert_eq!(detail.destination_chain, Some(12));
+        let events: Vec<_> = detail
+            .events
+            .iter()
+            .map(|event| (event.status, event.tx_hash.clone()))
+            .collect();
+        let tx_hash = hex_string(&[0xab; 32]);
+        assert_eq!(
+            events,
+            vec![
+                (TransferStatus::Deposited, format!("stc:{tx_hash}")),
+                (TransferStatus::Claimed, format!("eth:{tx_hash}")),
+            ]
+        );
+
+        let (status, _) = get_json::<ApiError>(format!("{api}/transfers/2/9")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_governance_actions() {
+        let (_db, api) = serve_seeded_db().await;
+
+        let (status, page) =
+            get_json::<GovernanceActionPage>(format!("{api}/governance_actions?limit=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        // Ties on the timestamp are broken by insertion order, newest first
+        let nonces: Vec<_> = page.actions.iter().map(|action| action.nonce).collect();
+        assert_eq!(nonces, vec![Some(3), Some(2)]);
+        assert_eq!(page.actions[0].action, "UpdateBridgeLimit");
+        assert_eq!(page.actions[0].data, serde_json::json!({ "new_limit": 3 }));
+        let cursor = page.next_cursor.unwrap();
+        let (_, page) = get_json::<GovernanceActionPage>(format!(
+            "{api}/governance_actions?limit=2&cursor={cursor}"
+        ))
+        .await;
+        let nonces: Vec<_> = page.actions.iter().map(|action| action.nonce).collect();
+        assert_eq!(nonces, vec![Some(1)]);
+        assert_eq!(page.next_cursor, None);
+
+        let (_, page) = get_json::<GovernanceActionPage>(format!(
+            "{api}/governance_actions?action=EmergencyOperation"
+        ))
+        .await;
+        assert!(page.actions.is_empty());
+        let (status, _) =
+            get_json::<ApiError>(format!("{api}/governance_actions?action=Unknown")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let (db, _api) = serve_seeded_db().await;
+        let pool = Pool::builder(AsyncDieselConnectionManager::<AsyncPgConnection>::new(
+            db.database().url().as_str(),
+        ))
+        .build()
+        .unwrap();
+
+        let stats = query_stats(&pool, NOW_MS).await.unwrap();
+        assert_eq!(stats.total_transfers, 3);
+        // Only the transfer claimed on Eth is done
+        assert_eq!(stats.pending_transfers, 2);
+        // The deposit of a day ago is out of the window
+        assert_eq!(
+            stats.volume_24h,
+            vec![TokenVolume {
+                token_id: 3,
+                transfers: 2,
+                amount: "1500".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_cursor() {
+        assert_eq!(parse_cursor::<3>("100:2:7").unwrap(), [100, 2, 7]);
+        assert!(parse_cursor::<3>("100:2").is_err());
+        assert!(parse_cursor::<2>("100:x").is_err());
+    }
+}
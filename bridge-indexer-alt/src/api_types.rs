@@ -0,0 +1,111 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Responses of the indexer HTTP API served by `api`. They only depend on
+//! serde, so the CLI or a frontend can deserialize them without the database
+//! models.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Deposited,
+    Approved,
+    Claimed,
+}
+
+/// Latest known state of a transfer. Fields of the deposit are None until the
+/// deposit is indexed, which may happen after its approval or claim.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transfer {
+    pub source_chain: u8,
+    pub nonce: u64,
+    pub status: TransferStatus,
+    pub deposited_at_ms: Option<i64>,
+    pub approved_at_ms: Option<i64>,
+    pub claimed_at_ms: Option<i64>,
+    pub token_id: Option<u8>,
+    // In the bridge's 8 decimal units
+    pub amount: Option<u64>,
+    // 0x prefixed hex
+    pub sender: Option<String>,
+    pub recipient: Option<String>,
+}
+
+/// A page of `GET /transfers`, newest first. Pass `next_cursor` as `cursor`
+/// to get the next page; it is None on the last page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferPage {
+    pub transfers: Vec<Transfer>,
+    pub next_cursor: Option<String>,
+}
+
+/// A transaction that moved a transfer to `status`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferEvent {
+    pub status: TransferStatus,
+    // Chain tagged, e.g. `stc:0x..` or `eth:0x..`
+    pub tx_hash: String,
+    pub block_height: i64,
+    pub timestamp_ms: i64,
+    pub finalized: bool,
+}
+
+/// `GET /transfers/{source_chain}/{nonce}`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferDetail {
+    #[serde(flatten)]
+    pub transfer: Transfer,
+    // None until the deposit is indexed
+    pub destination_chain: Option<u8>,
+    // Oldest first
+    pub events: Vec<TransferEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceAction {
+    // None for actions indexed from Starcoin events, which carry no nonce
+    pub nonce: Option<u64>,
+    // The indexer's name of the action type, e.g. `UpdateBridgeLimit`
+    pub action: String,
+    // Chain tagged, e.g. `stc:0x..` or `eth:0x..`
+    pub tx_hash: String,
+    pub sender: String,
+    pub timestamp_ms: i64,
+    pub block_height: Option<i64>,
+    pub data: serde_json::Value,
+}
+
+/// A page of `GET /governance_actions`, newest first, paged like
+/// `TransferPage`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceActionPage {
+    pub actions: Vec<GovernanceAction>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenVolume {
+    pub token_id: u8,
+    pub transfers: u64,
+    // Sum of the deposited amounts, as a decimal string since it can exceed
+    // what JSON numbers hold exactly
+    pub amount: String,
+}
+
+/// `GET /stats`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub total_transfers: u64,
+    // Transfers not claimed yet
+    pub pending_transfers: u64,
+    // Deposits of the last 24 hours, by token id
+    pub volume_24h: Vec<TokenVolume>,
+}
+
+/// Body of every error response
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub error: String,
+}
@@ -9,27 +9,142 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use crate::handlers::canonical_address;
 use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use diesel::upsert::excluded;
+use diesel::{ExpressionMethods, NullableExpressionMethods, OptionalExtension, QueryDsl};
 use diesel_async::pooled_connection::deadpool::Pool;
 use diesel_async::AsyncPgConnection;
 use diesel_async::RunQueryDsl;
+use ethers::providers::JsonRpcClient;
 use ethers::types::Address as EthAddress;
-use starcoin_bridge::abi::{EthBridgeEvent, EthStarcoinBridgeEvents, EthToStarcoinTokenBridgeV1};
+use starcoin_bridge::abi::{
+    EthBridgeConfig, EthBridgeEvent, EthStarcoinBridgeEvents, EthToStarcoinTokenBridgeV1,
+};
 use starcoin_bridge::eth_client::EthClient;
 use starcoin_bridge::eth_syncer::EthSyncer;
 use starcoin_bridge::metrics::BridgeMetrics;
+use starcoin_bridge::tx_digest::TxDigest;
 use starcoin_bridge::types::EthLog;
 use starcoin_bridge_schema::models::{
-    BridgeDataSource, TokenTransfer, TokenTransferData, TokenTransferStatus,
+    BridgeDataSource, ProgressStore, TokenTransfer, TokenTransferData, TokenTransferStatus,
 };
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
+/// Blocks behind the last indexed block that are indexed again on restart,
+/// in case they were reorged while the indexer was down
+pub const DEFAULT_ETH_CONFIRMATIONS: u64 = 12;
+
+/// Persists the last block the ETH indexer fully processed, per task
+#[async_trait]
+pub trait EthProgressStore: Send + Sync {
+    async fn load(&self, task_name: &str) -> Result<Option<u64>>;
+    async fn save(&self, task_name: &str, block: u64) -> Result<()>;
+}
+
+/// `EthProgressStore` backed by the `progress_store` table
+pub struct PgEthProgressStore {
+    pool: Pool<AsyncPgConnection>,
+}
+
+impl PgEthProgressStore {
+    pub fn new(pool: Pool<AsyncPgConnection>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EthProgressStore for PgEthProgressStore {
+    async fn load(&self, task_name: &str) -> Result<Option<u64>> {
+        use starcoin_bridge_schema::schema::progress_store::dsl;
+
+        let mut conn = self.pool.get().await.context("Failed to get database connection")?;
+        let checkpoint: Option<i64> = dsl::progress_store
+            .filter(dsl::task_name.eq(task_name))
+            .select(dsl::checkpoint)
+            .first(&mut conn)
+            .await
+            .optional()
+            .context("Failed to load ETH indexer progress")?;
+        Ok(checkpoint.map(|block| block as u64))
+    }
+
+    async fn save(&self, task_name: &str, block: u64) -> Result<()> {
+        use starcoin_bridge_schema::schema::progress_store::dsl;
+
+        let mut conn = self.pool.get().await.context("Failed to get database connection")?;
+        diesel::insert_into(dsl::progress_store)
+            .values(&ProgressStore {
+                task_name: task_name.to_string(),
+                checkpoint: block as i64,
+                target_checkpoint: i64::MAX,
+                timestamp: None,
+            })
+            .on_conflict(dsl::task_name)
+            .do_update()
+            .set((
+                dsl::checkpoint.eq(excluded(dsl::checkpoint)),
+                dsl::timestamp.eq(diesel::dsl::now.nullable()),
+            ))
+            .execute(&mut conn)
+            .await
+            .context("Failed to save ETH indexer progress")?;
+        Ok(())
+    }
+}
+
+/// Where the ETH indexer of one bridge resumes from, and records its progress
+pub struct EthProgress {
+    store: Arc<dyn EthProgressStore>,
+    bridge_address: EthAddress,
+    confirmations: u64,
+}
+
+impl EthProgress {
+    pub fn new(store: Arc<dyn EthProgressStore>, bridge_address: EthAddress, confirmations: u64) -> Self {
+        Self {
+            store,
+            bridge_address,
+            confirmations,
+        }
+    }
+
+    // Progress is kept per contract, since each contract is synced on its own
+    fn task_name(&self, contract: EthAddress) -> String {
+        format!("eth_indexer_{:?}_{:?}", self.bridge_address, contract)
+    }
+
+    /// The block to start indexing `contract` from
+    pub async fn resume_block(&self, contract: EthAddress, start_block: u64) -> Result<u64> {
+        let last_indexed = self.store.load(&self.task_name(contract)).await?;
+        Ok(resume_block(last_indexed, start_block, self.confirmations))
+    }
+
+    pub async fn save(&self, contract: EthAddress, block: u64) -> Result<()> {
+        self.store.save(&self.task_name(contract), block).await
+    }
+}
+
+// Resumes `confirmations` blocks before the block after `last_indexed`, so
+// that blocks reorged while the indexer was down are indexed again, but never
+// before `start_block`
+fn resume_block(last_indexed: Option<u64>, start_block: u64, confirmations: u64) -> u64 {
+    match last_indexed {
+        Some(last_indexed) => (last_indexed + 1)
+            .saturating_sub(confirmations)
+            .max(start_block),
+        None => start_block,
+    }
+}
+
 /// Start the ETH indexer
 pub async fn start_eth_indexer(
     eth_rpc_url: String,
     eth_bridge_address: String,
     eth_start_block: u64,
+    eth_confirmations: u64,
     pool: Pool<AsyncPgConnection>,
     bridge_metrics: Arc<BridgeMetrics>,
 ) -> Result<Vec<JoinHandle<()>>> {
@@ -37,6 +152,7 @@ pub async fn start_eth_indexer(
     info!("  ETH RPC URL: {}", eth_rpc_url);
     info!("  Bridge Address: {}", eth_bridge_address);
     info!("  Start Block: {}", eth_start_block);
+    info!("  Confirmations: {}", eth_confirmations);
 
     // Parse bridge address
     let bridge_address: EthAddress = eth_bridge_address
@@ -57,37 +173,83 @@ pub async fn start_eth_indexer(
 
     // Try to get contract addresses from the proxy, but use only bridge address if it fails
     let provider = eth_client.provider();
-    let addresses_to_watch: HashMap<EthAddress, u64> = match starcoin_bridge::utils::get_eth_contract_addresses(bridge_address, &provider).await {
+    let mut eth_chain_id = None;
+    let contracts = match starcoin_bridge::utils::get_eth_contract_addresses(bridge_address, &provider).await {
         Ok(contract_addresses) => {
             info!("Found ETH contract addresses:");
             info!("  Committee: {:?}", contract_addresses.0);
             info!("  Limiter: {:?}", contract_addresses.1);
             info!("  Vault: {:?}", contract_addresses.2);
             info!("  Config: {:?}", contract_addresses.3);
-            
-            HashMap::from([
-                (bridge_address, eth_start_block),
-                (contract_addresses.0, eth_start_block), // committee
-                (contract_addresses.1, eth_start_block), // limiter
-                (contract_addresses.3, eth_start_block), // config
-            ])
+
+            // With several EVM chains indexed into the same tables, only accept
+            // deposits that carry this chain's id.
+            match EthBridgeConfig::new(contract_addresses.3, provider.clone()).chain_id().call().await {
+                Ok(chain_id) => {
+                    info!("  Chain ID: {}", chain_id);
+                    eth_chain_id = Some(chain_id);
+                }
+                Err(e) => warn!("Failed to get ETH bridge chain id: {:?}", e),
+            }
+
+            vec![
+                bridge_address,
+                contract_addresses.0, // committee
+                contract_addresses.1, // limiter
+                contract_addresses.3, // config
+            ]
         }
         Err(e) => {
             warn!("Failed to get ETH contract addresses (using bridge address only): {:?}", e);
-            HashMap::from([(bridge_address, eth_start_block)])
+            vec![bridge_address]
         }
     };
 
+    let progress = EthProgress::new(
+        Arc::new(PgEthProgressStore::new(pool.clone())),
+        bridge_address,
+        eth_confirmations,
+    );
+    run_eth_indexer(
+        eth_client,
+        contracts,
+        eth_start_block,
+        eth_chain_id,
+        pool,
+        progress,
+        bridge_metrics,
+    )
+    .await
+}
+
+/// Sync `contracts` from where their indexing last stopped, or from
+/// `eth_start_block` on the first run
+pub async fn run_eth_indexer<P: JsonRpcClient + 'static>(
+    eth_client: Arc<EthClient<P>>,
+    contracts: Vec<EthAddress>,
+    eth_start_block: u64,
+    eth_chain_id: Option<u8>,
+    pool: Pool<AsyncPgConnection>,
+    progress: EthProgress,
+    bridge_metrics: Arc<BridgeMetrics>,
+) -> Result<Vec<JoinHandle<()>>> {
+    let mut addresses_to_watch = HashMap::new();
+    for contract in contracts {
+        let start_block = progress.resume_block(contract, eth_start_block).await?;
+        info!("Indexing ETH contract {:?} from block {}", contract, start_block);
+        addresses_to_watch.insert(contract, start_block);
+    }
+
     // Start ETH syncer
-    let (mut handles, eth_events_rx, _finalized_rx) = EthSyncer::new(eth_client.clone(), addresses_to_watch)
-        .run(bridge_metrics.clone())
+    let (mut handles, eth_events_rx, _finalized_rx) = EthSyncer::new(eth_client, addresses_to_watch)
+        .run(bridge_metrics)
         .await
         .map_err(|e| anyhow!("Failed to start ETH syncer: {:?}", e))?;
 
     info!("ETH syncer started, waiting for events...");
 
     // Spawn event processing task
-    let process_handle = tokio::spawn(process_eth_events(eth_events_rx, pool));
+    let process_handle = tokio::spawn(process_eth_events(eth_events_rx, pool, eth_chain_id, progress));
 
     handles.push(process_handle);
     Ok(handles)
@@ -97,33 +259,52 @@ pub async fn start_eth_indexer(
 async fn process_eth_events(
     mut eth_events_rx: starcoin_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
     pool: Pool<AsyncPgConnection>,
+    eth_chain_id: Option<u8>,
+    progress: EthProgress,
 ) {
     while let Some((contract_addr, block_num, logs)) = eth_events_rx.recv().await {
-        if logs.is_empty() {
-            continue;
+        if !logs.is_empty() {
+            info!(
+                "Received {} logs from contract {:?} at block {}",
+                logs.len(),
+                contract_addr,
+                block_num
+            );
         }
 
-        info!(
-            "Received {} logs from contract {:?} at block {}",
-            logs.len(),
-            contract_addr,
-            block_num
-        );
-
+        let mut failed = false;
         for log in logs {
-            if let Err(e) = process_eth_log(&log, &pool).await {
+            if let Err(e) = process_eth_log(&log, &pool, eth_chain_id).await {
                 error!("Failed to process ETH log: {:?}", e);
+                failed = true;
             }
         }
+
+        // The syncer sends every queried range, even an empty one, so progress
+        // advances through blocks without bridge events too. A range with a
+        // failed log isn't recorded, so it's indexed again after a restart.
+        if failed {
+            continue;
+        }
+        if let Err(e) = progress.save(contract_addr, block_num).await {
+            warn!("Failed to save ETH indexer progress at block {}: {:?}", block_num, e);
+        }
     }
 }
 
-async fn process_eth_log(log: &EthLog, pool: &Pool<AsyncPgConnection>) -> Result<()> {
+async fn process_eth_log(
+    log: &EthLog,
+    pool: &Pool<AsyncPgConnection>,
+    eth_chain_id: Option<u8>,
+) -> Result<()> {
     // Try to parse the log as a bridge event
     let event = match EthBridgeEvent::try_from_eth_log(log) {
         Some(e) => e,
         None => {
-            warn!("Could not parse ETH log as bridge event: {:?}", log.tx_hash);
+            warn!(
+                "Could not parse ETH log as bridge event: {}",
+                TxDigest::eth(log.tx_hash)
+            );
             return Ok(());
         }
     };
@@ -138,7 +319,7 @@ async fn process_eth_log(log: &EthLog, pool: &Pool<AsyncPgConnection>) -> Result
 
     match event {
         EthBridgeEvent::EthStarcoinBridgeEvents(bridge_event) => {
-            process_bridge_event(bridge_event, log, timestamp_ms, &mut conn).await?;
+            process_bridge_event(bridge_event, log, timestamp_ms, eth_chain_id, &mut conn).await?;
         }
         _ => {
             // Committee, Limiter, Config events - for now just log them
@@ -153,6 +334,7 @@ async fn process_bridge_event(
     bridge_event: EthStarcoinBridgeEvents,
     log: &EthLog,
     timestamp_ms: i64,
+    eth_chain_id: Option<u8>,
     conn: &mut diesel_async::pooled_connection::deadpool::Object<AsyncPgConnection>,
 ) -> Result<()> {
     match bridge_event {
@@ -166,6 +348,16 @@ async fn process_bridge_event(
             let bridge_event = EthToStarcoinTokenBridgeV1::try_from(&deposit)
                 .map_err(|e| anyhow!("Failed to convert deposit event: {:?}", e))?;
 
+            if let Some(expected) = eth_chain_id {
+                if bridge_event.eth_chain_id as u8 != expected {
+                    warn!(
+                        "Skipping ETH deposit from chain {:?} on indexer for chain {}: nonce={}",
+                        bridge_event.eth_chain_id, expected, bridge_event.nonce
+                    );
+                    return Ok(());
+                }
+            }
+
             // Create token transfer record
             let transfer = TokenTransfer {
                 chain_id: bridge_event.eth_chain_id as i32,
@@ -193,21 +385,44 @@ async fn process_bridge_event(
                 token_id: bridge_event.token_id as i32,
                 amount: bridge_event.starcoin_bridge_adjusted_amount as i64,
                 is_finalized: true,
+                sender_address_canonical: canonical_address(
+                    bridge_event.eth_chain_id as i32,
+                    bridge_event.eth_address.as_bytes(),
+                ),
+                recipient_address_canonical: canonical_address(
+                    bridge_event.starcoin_bridge_chain_id as i32,
+                    &bridge_event.starcoin_bridge_address.to_vec(),
+                ),
             };
 
-            // Insert into database
+            // Insert into database. A log is indexed again when its block is
+            // within the confirmation window on restart: the rows it writes
+            // are keyed by the deposit it carries, so replaying a log is a
+            // no-op, and the log of a reorged deposit moves the row to its new
+            // block and transaction. The first indexing time is kept.
             use starcoin_bridge_schema::schema::{token_transfer, token_transfer_data};
 
             diesel::insert_into(token_transfer::table)
                 .values(&transfer)
-                .on_conflict_do_nothing()
+                .on_conflict((token_transfer::chain_id, token_transfer::nonce, token_transfer::status))
+                .do_update()
+                .set((
+                    token_transfer::block_height.eq(excluded(token_transfer::block_height)),
+                    token_transfer::txn_hash.eq(excluded(token_transfer::txn_hash)),
+                    token_transfer::txn_sender.eq(excluded(token_transfer::txn_sender)),
+                ))
                 .execute(conn)
                 .await
                 .context("Failed to insert token transfer")?;
 
             diesel::insert_into(token_transfer_data::table)
                 .values(&transfer_data)
-                .on_conflict_do_nothing()
+                .on_conflict((token_transfer_data::chain_id, token_transfer_data::nonce))
+                .do_update()
+                .set((
+                    token_transfer_data::block_height.eq(excluded(token_transfer_data::block_height)),
+                    token_transfer_data::txn_hash.eq(excluded(token_transfer_data::txn_hash)),
+                ))
                 .execute(conn)
                 .await
                 .context("Failed to insert token transfer data")?;
@@ -241,9 +456,16 @@ async fn process_bridge_event(
 
             use starcoin_bridge_schema::schema::token_transfer;
 
+            // Idempotent like the deposit rows above
             diesel::insert_into(token_transfer::table)
                 .values(&transfer)
-                .on_conflict_do_nothing()
+                .on_conflict((token_transfer::chain_id, token_transfer::nonce, token_transfer::status))
+                .do_update()
+                .set((
+                    token_transfer::block_height.eq(excluded(token_transfer::block_height)),
+                    token_transfer::txn_hash.eq(excluded(token_transfer::txn_hash)),
+                    token_transfer::txn_sender.eq(excluded(token_transfer::txn_sender)),
+                ))
                 .execute(conn)
                 .await
                 .context("Failed to insert token transfer claim")?;
@@ -261,3 +483,151 @@ async fn process_bridge_event(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+    use ethers::providers::MockError;
+    use ethers::types::{Block, TxHash};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::fmt::Debug;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    // A chain at a fixed head without any logs, recording the block ranges
+    // logs are queried for
+    #[derive(Clone, Debug)]
+    struct FakeEthRpc {
+        head: u64,
+        log_queries: Arc<Mutex<Vec<(u64, u64)>>>,
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for FakeEthRpc {
+        type Error = MockError;
+
+        async fn request<P: Serialize + Send + Sync + Debug, R: DeserializeOwned>(
+            &self,
+            method: &str,
+            params: P,
+        ) -> Result<R, MockError> {
+            let response = match method {
+                "eth_getBlockByNumber" => serde_json::to_value(Block::<TxHash> {
+                    number: Some(self.head.into()),
+                    ..Default::default()
+                })?,
+                "eth_getLogs" => {
+                    let filter = &serde_json::to_value(params)?[0];
+                    let block = |key: &str| {
+                        let hex = filter[key].as_str().unwrap().trim_start_matches("0x");
+                        u64::from_str_radix(hex, 16).unwrap()
+                    };
+                    self.log_queries
+                        .lock()
+                        .unwrap()
+                        .push((block("fromBlock"), block("toBlock")));
+                    serde_json::json!([])
+                }
+                _ => return Err(MockError::EmptyResponses),
+            };
+            Ok(serde_json::from_value(response)?)
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryProgressStore(Mutex<HashMap<String, u64>>);
+
+    #[async_trait]
+    impl EthProgressStore for MemoryProgressStore {
+        async fn load(&self, task_name: &str) -> Result<Option<u64>> {
+            Ok(self.0.lock().unwrap().get(task_name).copied())
+        }
+
+        async fn save(&self, task_name: &str, block: u64) -> Result<()> {
+            self.0.lock().unwrap().insert(task_name.to_string(), block);
+            Ok(())
+        }
+    }
+
+    // Runs the indexer of `contract`, starting at block 100, until it has
+    // indexed the chain up to `head`. Returns the ranges it queried.
+    async fn index_until_head(
+        store: Arc<MemoryProgressStore>,
+        contract: EthAddress,
+        head: u64,
+    ) -> Vec<(u64, u64)> {
+        let rpc = FakeEthRpc {
+            head,
+            log_queries: Default::default(),
+        };
+        let eth_client = Arc::new(EthClient::with_provider(
+            rpc.clone(),
+            HashSet::from([contract]),
+            true,
+        ));
+        // Never connected to: there are no logs to write
+        let pool = Pool::builder(AsyncDieselConnectionManager::<AsyncPgConnection>::new(
+            "postgres://localhost/unused",
+        ))
+        .build()
+        .unwrap();
+        let progress = EthProgress::new(store.clone(), contract, DEFAULT_ETH_CONFIRMATIONS);
+        let task_name = progress.task_name(contract);
+
+        let handles = run_eth_indexer(
+            eth_client,
+            vec![contract],
+            100,
+            None,
+            pool,
+            progress,
+            Arc::new(BridgeMetrics::new_for_testing()),
+        )
+        .await
+        .unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while store.load(&task_name).await.unwrap() != Some(head) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("indexer didn't reach the head");
+        handles.iter().for_each(JoinHandle::abort);
+
+        let log_queries = rpc.log_queries.lock().unwrap().clone();
+        log_queries
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_restart() {
+        starcoin_metrics::init_metrics(&prometheus::Registry::new());
+        let store = Arc::new(MemoryProgressStore::default());
+        let contract = EthAddress::repeat_byte(1);
+
+        // The first run starts at the start block
+        assert_eq!(
+            index_until_head(store.clone(), contract, 150).await,
+            vec![(100, 150)]
+        );
+        // After a restart, the confirmation window behind the last indexed
+        // block is indexed again, but nothing before it
+        assert_eq!(
+            index_until_head(store.clone(), contract, 160).await,
+            vec![(139, 160)]
+        );
+        // Progress is kept per bridge
+        let other_bridge = EthProgress::new(store, EthAddress::repeat_byte(2), 12);
+        assert_eq!(other_bridge.resume_block(contract, 100).await.unwrap(), 100);
+    }
+
+    #[test]
+    fn test_resume_block() {
+        assert_eq!(resume_block(None, 100, 12), 100);
+        assert_eq!(resume_block(Some(150), 100, 12), 139);
+        assert_eq!(resume_block(Some(150), 100, 0), 151);
+        // Never before the start block
+        assert_eq!(resume_block(Some(105), 100, 12), 100);
+        assert_eq!(resume_block(Some(5), 0, 12), 0);
+    }
+}
@@ -75,6 +75,7 @@ impl Processor for GovernanceActionHandler {
 
     fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
         let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms as i64;
+        let block_height = checkpoint.checkpoint_summary.sequence_number as i64;
 
         let mut results = vec![];
 
@@ -156,6 +157,7 @@ impl Processor for GovernanceActionHandler {
                     timestamp_ms,
                     action,
                     data,
+                    block_height: Some(block_height),
                 });
             }
         }
@@ -3,12 +3,14 @@
 
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
+use starcoin_bridge::encoding::STARCOIN_ADDRESS_LENGTH;
 use starcoin_bridge_indexer_alt_framework::types::full_checkpoint_content::CheckpointTransaction;
 
 pub mod error_handler;
 pub mod governance_action_handler;
 pub mod token_transfer_data_handler;
 pub mod token_transfer_handler;
+pub mod token_transfer_status_handler;
 
 const LIMITER: &IdentStr = ident_str!("limiter");
 const BRIDGE: &IdentStr = ident_str!("Bridge");
@@ -19,6 +21,39 @@ const TOKEN_DEPOSITED_EVENT: &IdentStr = ident_str!("TokenDepositedEvent");
 const TOKEN_TRANSFER_APPROVED: &IdentStr = ident_str!("TokenTransferApproved");
 const TOKEN_TRANSFER_CLAIMED: &IdentStr = ident_str!("TokenTransferClaimed");
 
+const ETH_ADDRESS_LENGTH: usize = 20;
+
+// Width in bytes of the addresses of a bridge chain, by the ids of
+// `BridgeChainId`. None for an unknown chain.
+fn address_width(chain_id: i32) -> Option<usize> {
+    match chain_id {
+        0..=2 => Some(STARCOIN_ADDRESS_LENGTH),
+        10..=12 => Some(ETH_ADDRESS_LENGTH),
+        _ => None,
+    }
+}
+
+// The hex digits of an address that was stored as hex text, with or without
+// 0x, rather than as bytes
+fn hex_text(address: &[u8]) -> Option<&str> {
+    let text = std::str::from_utf8(address).ok()?;
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    (!digits.is_empty() && digits.len() % 2 == 0 && digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then_some(digits)
+}
+
+/// An address of the chain `chain_id` as 0x-prefixed lowercase hex, left-padded
+/// to the address width of the chain. The backfill of the canonical columns
+/// in the migrations does the same in SQL.
+pub fn canonical_address(chain_id: i32, address: &[u8]) -> String {
+    let digits = match hex_text(address) {
+        Some(digits) => digits.to_lowercase(),
+        None => hex::encode(address),
+    };
+    let width = address_width(chain_id).map_or(0, |width| width * 2);
+    format!("0x{digits:0>width$}")
+}
+
 #[macro_export]
 macro_rules! struct_tag {
     ($address:ident, $module:ident, $name:ident) => {{
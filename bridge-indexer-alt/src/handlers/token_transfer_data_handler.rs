@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::handlers::{is_bridge_txn, BRIDGE, TOKEN_DEPOSITED_EVENT};
+use crate::handlers::{canonical_address, is_bridge_txn, BRIDGE, TOKEN_DEPOSITED_EVENT};
 use crate::struct_tag;
 use async_trait::async_trait;
 use diesel_async::RunQueryDsl;
@@ -49,25 +49,43 @@ impl Processor for TokenTransferDataHandler {
                 }
                 info!(?ev, "Observed Starcoin Deposit");
                 let event: MoveTokenDepositedEvent = bcs::from_bytes(&ev.contents)?;
-                results.push(TokenTransferData {
-                    chain_id: event.source_chain as i32,
-                    nonce: event.seq_num as i64,
+                results.push(transfer_data(
+                    &event,
                     block_height,
                     timestamp_ms,
-                    destination_chain: event.target_chain as i32,
-                    sender_address: event.sender_address.clone(),
-                    recipient_address: event.target_address.clone(),
-                    token_id: event.token_type as i32,
-                    amount: event.amount_starcoin_bridge_adjusted as i64,
-                    is_finalized: true,
-                    txn_hash: tx.transaction.digest().inner().to_vec(),
-                });
+                    tx.transaction.digest().inner().to_vec(),
+                ));
             }
         }
         Ok(results)
     }
 }
 
+fn transfer_data(
+    event: &MoveTokenDepositedEvent,
+    block_height: i64,
+    timestamp_ms: i64,
+    txn_hash: Vec<u8>,
+) -> TokenTransferData {
+    let chain_id = event.source_chain as i32;
+    let destination_chain = event.target_chain as i32;
+    TokenTransferData {
+        chain_id,
+        nonce: event.seq_num as i64,
+        block_height,
+        timestamp_ms,
+        destination_chain,
+        sender_address: event.sender_address.clone(),
+        recipient_address: event.target_address.clone(),
+        token_id: event.token_type as i32,
+        amount: event.amount_starcoin_bridge_adjusted as i64,
+        is_finalized: true,
+        txn_hash,
+        sender_address_canonical: canonical_address(chain_id, &event.sender_address),
+        recipient_address_canonical: canonical_address(destination_chain, &event.target_address),
+    }
+}
+
 #[async_trait]
 impl Handler for TokenTransferDataHandler {
     type Store = Db;
@@ -83,3 +101,94 @@ impl Handler for TokenTransferDataHandler {
             .await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(
+        source_chain: u8,
+        sender_address: Vec<u8>,
+        target_chain: u8,
+        target_address: Vec<u8>,
+    ) -> TokenTransferData {
+        transfer_data(
+            &MoveTokenDepositedEvent {
+                seq_num: 7,
+                source_chain,
+                sender_address,
+                target_chain,
+                target_address,
+                token_type: 3,
+                amount_starcoin_bridge_adjusted: 1000,
+            },
+            10,
+            100,
+            vec![0xab; 32],
+        )
+    }
+
+    #[test]
+    fn test_starcoin_to_eth_canonical_addresses() {
+        let data = deposit(2, vec![0xa1; 16], 12, vec![0xB2; 20]);
+        assert_eq!((data.chain_id, data.destination_chain), (2, 12));
+        assert_eq!(
+            data.sender_address_canonical,
+            format!("0x{}", "a1".repeat(16))
+        );
+        assert_eq!(
+            data.recipient_address_canonical,
+            format!("0x{}", "b2".repeat(20))
+        );
+        // The raw columns are kept as they are
+        assert_eq!(data.sender_address, vec![0xa1; 16]);
+        assert_eq!(data.recipient_address, vec![0xB2; 20]);
+    }
+
+    #[test]
+    fn test_eth_to_starcoin_canonical_addresses() {
+        let data = deposit(12, vec![0xc3; 20], 2, vec![0xd4; 16]);
+        assert_eq!(
+            data.sender_address_canonical,
+            format!("0x{}", "c3".repeat(20))
+        );
+        assert_eq!(
+            data.recipient_address_canonical,
+            format!("0x{}", "d4".repeat(16))
+        );
+    }
+
+    #[test]
+    fn test_canonical_addresses_are_padded_and_decoded() {
+        // Short addresses are left-padded to the width of their chain
+        let data = deposit(2, vec![0x01], 12, vec![0x02, 0x03]);
+        assert_eq!(
+            data.sender_address_canonical,
+            format!("0x{}01", "0".repeat(30))
+        );
+        assert_eq!(
+            data.recipient_address_canonical,
+            format!("0x{}0203", "0".repeat(36))
+        );
+
+        // Addresses written as hex text, with or without 0x and in any case
+        let eth = "ABcdef".repeat(6) + "0a0b";
+        let data = deposit(
+            12,
+            format!("0x{eth}").into_bytes(),
+            2,
+            "00ff".repeat(8).into_bytes(),
+        );
+        assert_eq!(
+            data.sender_address_canonical,
+            format!("0x{}", eth.to_lowercase())
+        );
+        assert_eq!(
+            data.recipient_address_canonical,
+            format!("0x{}", "00ff".repeat(8))
+        );
+
+        // Unknown chains are not padded
+        assert_eq!(canonical_address(99, &[0x0f]), "0x0f");
+    }
+}
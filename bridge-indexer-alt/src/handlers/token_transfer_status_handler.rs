@@ -0,0 +1,311 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::handlers::{
+    is_bridge_txn, BRIDGE, TOKEN_DEPOSITED_EVENT, TOKEN_TRANSFER_APPROVED, TOKEN_TRANSFER_CLAIMED,
+};
+use crate::struct_tag;
+use async_trait::async_trait;
+use diesel::dsl::sql;
+use diesel::sql_types::{BigInt, Binary, Integer, Nullable, Text};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use starcoin_bridge::events::{
+    MoveTokenDepositedEvent, MoveTokenTransferApproved, MoveTokenTransferClaimed,
+};
+use starcoin_bridge_indexer_alt_framework::pipeline::concurrent::Handler;
+use starcoin_bridge_indexer_alt_framework::pipeline::Processor;
+use starcoin_bridge_indexer_alt_framework::postgres::Db;
+use starcoin_bridge_indexer_alt_framework::store::Store;
+use starcoin_bridge_indexer_alt_framework::types::full_checkpoint_content::CheckpointData;
+use starcoin_bridge_schema::models::{TokenTransferState, TokenTransferStatus};
+use starcoin_bridge_schema::schema::token_transfer_status;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::info;
+
+// The status of a row, derived from the merged columns the same way as
+// `status_of`, so that rows converge whatever order they are written in
+const STATUS_SQL: &str = "CASE \
+    WHEN COALESCE(token_transfer_status.claimed_at, excluded.claimed_at) IS NOT NULL THEN 'Claimed' \
+    WHEN COALESCE(token_transfer_status.approved_at, excluded.approved_at) IS NOT NULL THEN 'Approved' \
+    ELSE 'Deposited' END";
+
+// Maintains one `token_transfer_status` row per transfer, keyed by source
+// chain and nonce. Deposits, approvals and claims are merged into the row as
+// they are indexed, in any order: a backfill may see the claim of a transfer
+// before its deposit.
+pub struct TokenTransferStatusHandler {
+    deposited_event_type: StructTag,
+    approved_event_type: StructTag,
+    claimed_event_type: StructTag,
+}
+
+impl TokenTransferStatusHandler {
+    pub fn new(bridge_address: AccountAddress) -> Self {
+        Self {
+            deposited_event_type: struct_tag!(bridge_address, BRIDGE, TOKEN_DEPOSITED_EVENT),
+            approved_event_type: struct_tag!(bridge_address, BRIDGE, TOKEN_TRANSFER_APPROVED),
+            claimed_event_type: struct_tag!(bridge_address, BRIDGE, TOKEN_TRANSFER_CLAIMED),
+        }
+    }
+}
+
+impl Processor for TokenTransferStatusHandler {
+    const NAME: &'static str = "token_transfer_status";
+    type Value = TokenTransferState;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>, anyhow::Error> {
+        let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms as i64;
+
+        let mut results = vec![];
+
+        for tx in &checkpoint.transactions {
+            if !is_bridge_txn(tx) {
+                continue;
+            }
+            for ev in tx.events.iter().flat_map(|e| &e.data) {
+                if self.deposited_event_type == ev.type_ {
+                    let event: MoveTokenDepositedEvent = bcs::from_bytes(&ev.contents)?;
+                    results.push(deposited(&event, timestamp_ms));
+                } else if self.approved_event_type == ev.type_ {
+                    let event: MoveTokenTransferApproved = bcs::from_bytes(&ev.contents)?;
+                    let key = event.message_key;
+                    results.push(approved(key.source_chain, key.bridge_seq_num, timestamp_ms));
+                } else if self.claimed_event_type == ev.type_ {
+                    let event: MoveTokenTransferClaimed = bcs::from_bytes(&ev.contents)?;
+                    let key = event.message_key;
+                    results.push(claimed(key.source_chain, key.bridge_seq_num, timestamp_ms));
+                }
+            }
+        }
+        if !results.is_empty() {
+            info!(
+                checkpoint = checkpoint.checkpoint_summary.sequence_number,
+                updates = results.len(),
+                "Observed token transfer status updates"
+            );
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl Handler for TokenTransferStatusHandler {
+    type Store = Db;
+
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut <Self::Store as Store>::Connection<'a>,
+    ) -> anyhow::Result<usize> {
+        use token_transfer_status::dsl;
+
+        // Postgres can't update the same row twice in one statement
+        let rows = merge_by_key(values);
+        Ok(diesel::insert_into(token_transfer_status::table)
+            .values(&rows)
+            .on_conflict((dsl::source_chain_id, dsl::nonce))
+            .do_update()
+            .set((
+                dsl::deposited_at.eq(sql::<Nullable<BigInt>>(
+                    "LEAST(token_transfer_status.deposited_at, excluded.deposited_at)",
+                )),
+                dsl::approved_at.eq(sql::<Nullable<BigInt>>(
+                    "LEAST(token_transfer_status.approved_at, excluded.approved_at)",
+                )),
+                dsl::claimed_at.eq(sql::<Nullable<BigInt>>(
+                    "LEAST(token_transfer_status.claimed_at, excluded.claimed_at)",
+                )),
+                dsl::amount.eq(sql::<Nullable<BigInt>>(
+                    "COALESCE(token_transfer_status.amount, excluded.amount)",
+                )),
+                dsl::sender.eq(sql::<Nullable<Binary>>(
+                    "COALESCE(token_transfer_status.sender, excluded.sender)",
+                )),
+                dsl::recipient.eq(sql::<Nullable<Binary>>(
+                    "COALESCE(token_transfer_status.recipient, excluded.recipient)",
+                )),
+                dsl::token_id.eq(sql::<Nullable<Integer>>(
+                    "COALESCE(token_transfer_status.token_id, excluded.token_id)",
+                )),
+                dsl::status.eq(sql::<Text>(STATUS_SQL)),
+            ))
+            .execute(conn)
+            .await?)
+    }
+}
+
+fn transfer_state(source_chain: u8, nonce: u64) -> TokenTransferState {
+    TokenTransferState {
+        source_chain_id: source_chain as i32,
+        nonce: nonce as i64,
+        deposited_at: None,
+        approved_at: None,
+        claimed_at: None,
+        amount: None,
+        sender: None,
+        recipient: None,
+        token_id: None,
+        status: TokenTransferStatus::Deposited,
+    }
+}
+
+fn deposited(event: &MoveTokenDepositedEvent, timestamp_ms: i64) -> TokenTransferState {
+    TokenTransferState {
+        deposited_at: Some(timestamp_ms),
+        amount: Some(event.amount_starcoin_bridge_adjusted as i64),
+        sender: Some(event.sender_address.clone()),
+        recipient: Some(event.target_address.clone()),
+        token_id: Some(event.token_type as i32),
+        ..transfer_state(event.source_chain, event.seq_num)
+    }
+}
+
+fn approved(source_chain: u8, nonce: u64, timestamp_ms: i64) -> TokenTransferState {
+    TokenTransferState {
+        approved_at: Some(timestamp_ms),
+        status: TokenTransferStatus::Approved,
+        ..transfer_state(source_chain, nonce)
+    }
+}
+
+fn claimed(source_chain: u8, nonce: u64, timestamp_ms: i64) -> TokenTransferState {
+    TokenTransferState {
+        claimed_at: Some(timestamp_ms),
+        status: TokenTransferStatus::Claimed,
+        ..transfer_state(source_chain, nonce)
+    }
+}
+
+fn status_of(state: &TokenTransferState) -> TokenTransferStatus {
+    if state.claimed_at.is_some() {
+        TokenTransferStatus::Claimed
+    } else if state.approved_at.is_some() {
+        TokenTransferStatus::Approved
+    } else {
+        TokenTransferStatus::Deposited
+    }
+}
+
+// The earliest of two optional timestamps, like `LEAST` in Postgres
+fn earliest(a: Option<i64>, b: Option<i64>) -> Option<i64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+// Merges `update` into `state` the same way `commit` merges a row into the
+// table. Merging is commutative and idempotent.
+fn merge(state: &mut TokenTransferState, update: &TokenTransferState) {
+    state.deposited_at = earliest(state.deposited_at, update.deposited_at);
+    state.approved_at = earliest(state.approved_at, update.approved_at);
+    state.claimed_at = earliest(state.claimed_at, update.claimed_at);
+    state.amount = state.amount.or(update.amount);
+    state.sender = state.sender.take().or_else(|| update.sender.clone());
+    state.recipient = state.recipient.take().or_else(|| update.recipient.clone());
+    state.token_id = state.token_id.or(update.token_id);
+    state.status = status_of(state);
+}
+
+// One row per transfer, ordered by key so that concurrent commits lock rows
+// in the same order
+fn merge_by_key(values: &[TokenTransferState]) -> Vec<TokenTransferState> {
+    let mut rows: BTreeMap<(i32, i64), TokenTransferState> = BTreeMap::new();
+    for value in values {
+        rows.entry((value.source_chain_id, value.nonce))
+            .and_modify(|state| merge(state, value))
+            .or_insert_with(|| value.clone());
+    }
+    rows.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(nonce: u64, timestamp_ms: i64) -> TokenTransferState {
+        deposited(
+            &MoveTokenDepositedEvent {
+                seq_num: nonce,
+                source_chain: 12,
+                sender_address: vec![1u8; 20],
+                target_chain: 2,
+                target_address: vec![2u8; 16],
+                token_type: 3,
+                amount_starcoin_bridge_adjusted: 1000,
+            },
+            timestamp_ms,
+        )
+    }
+
+    #[test]
+    fn test_out_of_order_events_converge() {
+        let events = [deposit(7, 100), approved(12, 7, 200), claimed(12, 7, 300)];
+        let expected = TokenTransferState {
+            source_chain_id: 12,
+            nonce: 7,
+            deposited_at: Some(100),
+            approved_at: Some(200),
+            claimed_at: Some(300),
+            amount: Some(1000),
+            sender: Some(vec![1u8; 20]),
+            recipient: Some(vec![2u8; 16]),
+            token_id: Some(3),
+            status: TokenTransferStatus::Claimed,
+        };
+        for order in [[0, 1, 2], [2, 1, 0], [2, 0, 1], [1, 2, 0]] {
+            // All events in one batch
+            let batch: Vec<_> = order.iter().map(|&i| events[i].clone()).collect();
+            assert_eq!(merge_by_key(&batch), vec![expected.clone()]);
+
+            // One event per batch, merged into the stored row
+            let mut state = events[order[0]].clone();
+            for &i in &order[1..] {
+                merge(&mut state, &events[i]);
+            }
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_merge_is_idempotent() {
+        let mut state = claimed(12, 7, 300);
+        merge(&mut state, &approved(12, 7, 200));
+        let merged = state.clone();
+        merge(&mut state, &approved(12, 7, 200));
+        merge(&mut state, &claimed(12, 7, 300));
+        assert_eq!(state, merged);
+        // The claim is known before the deposit is backfilled
+        assert_eq!(state.status, TokenTransferStatus::Claimed);
+        assert_eq!(state.amount, None);
+
+        merge(&mut state, &deposit(7, 100));
+        assert_eq!(state.deposited_at, Some(100));
+        assert_eq!(state.amount, Some(1000));
+        assert_eq!(state.status, TokenTransferStatus::Claimed);
+    }
+
+    #[test]
+    fn test_merge_by_key_keeps_transfers_apart() {
+        let rows = merge_by_key(&[
+            approved(12, 8, 200),
+            deposit(7, 100),
+            approved(2, 7, 150),
+            deposit(8, 90),
+        ]);
+        let keys: Vec<_> = rows
+            .iter()
+            .map(|row| (row.source_chain_id, row.nonce, row.status))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (2, 7, TokenTransferStatus::Approved),
+                (12, 7, TokenTransferStatus::Deposited),
+                (12, 8, TokenTransferStatus::Approved),
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+pub mod api;
+pub mod api_types;
 pub mod handlers;
 pub mod metrics;
 pub mod eth_indexer;
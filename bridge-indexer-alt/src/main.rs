@@ -5,11 +5,13 @@ use clap::Parser;
 use prometheus::Registry;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use starcoin_bridge_indexer_alt::eth_indexer::start_eth_indexer;
+use starcoin_bridge_indexer_alt::api::run_api_server;
+use starcoin_bridge_indexer_alt::eth_indexer::{start_eth_indexer, DEFAULT_ETH_CONFIRMATIONS};
 use starcoin_bridge_indexer_alt::handlers::error_handler::ErrorTransactionHandler;
 use starcoin_bridge_indexer_alt::handlers::governance_action_handler::GovernanceActionHandler;
 use starcoin_bridge_indexer_alt::handlers::token_transfer_data_handler::TokenTransferDataHandler;
 use starcoin_bridge_indexer_alt::handlers::token_transfer_handler::TokenTransferHandler;
+use starcoin_bridge_indexer_alt::handlers::token_transfer_status_handler::TokenTransferStatusHandler;
 use starcoin_bridge_indexer_alt::metrics::BridgeIndexerMetrics;
 use starcoin_bridge::metrics::BridgeMetrics;
 use starcoin_bridge_schema::MIGRATIONS;
@@ -17,6 +19,9 @@ use starcoin_bridge_indexer_alt_framework::ingestion::{ClientArgs, IngestionConf
 use starcoin_bridge_indexer_alt_framework::postgres::DbArgs;
 use starcoin_bridge_indexer_alt_framework::{Indexer, IndexerArgs};
 use starcoin_bridge_indexer_alt_metrics::{MetricsArgs, MetricsService};
+use diesel_async::pooled_connection::deadpool::Pool;
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use diesel_async::AsyncPgConnection;
 use tokio_util::sync::CancellationToken;
 use url::Url;
 
@@ -29,6 +34,10 @@ struct Args {
     indexer_args: IndexerArgs,
     #[clap(env, long, default_value = "0.0.0.0:9184")]
     metrics_address: SocketAddr,
+    /// Address to serve the read-only HTTP API over the indexed transfers
+    /// and governance actions on. The API is not served unless set.
+    #[clap(env, long)]
+    api_address: Option<SocketAddr>,
     #[clap(
         env,
         long,
@@ -55,9 +64,55 @@ struct Args {
     /// Ethereum bridge proxy contract address
     #[clap(env, long)]
     eth_bridge_address: Option<String>,
-    /// Starting block for ETH syncing
+    /// Starting block for ETH syncing on the first run. Once blocks have been
+    /// indexed, a restart resumes from the last indexed block instead, but
+    /// never before this block.
     #[clap(env, long, default_value = "0")]
     eth_start_block: u64,
+    /// Number of blocks behind the last indexed block that are indexed again
+    /// on restart, to pick up blocks reorged while the indexer was down.
+    /// Indexing them again is idempotent.
+    #[clap(env, long, default_value_t = DEFAULT_ETH_CONFIRMATIONS)]
+    eth_confirmations: u64,
+    /// Additional EVM chain to index, as `<rpc_url>,<bridge_address>[,<start_block>]`.
+    /// May be repeated. Rows are keyed by the chain id each bridge reports.
+    /// Each target resumes from its own progress, like the main ETH target.
+    #[clap(long = "extra-eth-target")]
+    extra_eth_targets: Vec<EthTarget>,
+}
+
+#[derive(Clone, Debug)]
+struct EthTarget {
+    rpc_url: String,
+    bridge_address: String,
+    start_block: u64,
+}
+
+impl std::str::FromStr for EthTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(',').map(str::trim).collect::<Vec<_>>();
+        let (rpc_url, bridge_address, start_block) = match parts.as_slice() {
+            [rpc_url, bridge_address] => (rpc_url, bridge_address, 0),
+            [rpc_url, bridge_address, start_block] => (
+                rpc_url,
+                bridge_address,
+                start_block
+                    .parse()
+                    .context("Invalid start block in --extra-eth-target")?,
+            ),
+            _ => anyhow::bail!(
+                "Expected `<rpc_url>,<bridge_address>[,<start_block>]`, got `{}`",
+                s
+            ),
+        };
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            bridge_address: bridge_address.to_string(),
+            start_block,
+        })
+    }
 }
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
@@ -69,6 +124,7 @@ async fn main() -> Result<(), anyhow::Error> {
         db_args,
         indexer_args,
         metrics_address,
+        api_address,
         database_url,
         remote_store_url,
         rpc_api_url,
@@ -77,6 +133,8 @@ async fn main() -> Result<(), anyhow::Error> {
         eth_rpc_url,
         eth_bridge_address,
         eth_start_block,
+        eth_confirmations,
+        extra_eth_targets,
     } = Args::parse();
 
     let cancel = CancellationToken::new();
@@ -143,6 +201,10 @@ async fn main() -> Result<(), anyhow::Error> {
         .concurrent_pipeline(TokenTransferDataHandler::new(bridge_addr), Default::default())
         .await?;
 
+    indexer
+        .concurrent_pipeline(TokenTransferStatusHandler::new(bridge_addr), Default::default())
+        .await?;
+
     indexer
         .concurrent_pipeline(
             GovernanceActionHandler::new(bridge_indexer_metrics.clone(), bridge_addr),
@@ -157,30 +219,66 @@ async fn main() -> Result<(), anyhow::Error> {
     let h_indexer = indexer.run().await?;
     let h_metrics = metrics.run().await?;
 
+    // Connection pool shared by the ETH indexer and the API
+    let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url.as_str());
+    let pool = Pool::builder(config).build()?;
+
+    let h_api = match api_address {
+        Some(api_address) => Some(
+            run_api_server(
+                api_address,
+                pool.clone(),
+                bridge_indexer_metrics.clone(),
+                cancel.child_token(),
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    let api_stopped = async move {
+        match h_api {
+            Some(h_api) => {
+                let _ = h_api.await;
+            }
+            None => std::future::pending().await,
+        }
+    };
+
     // Start ETH indexer if enabled
     let mut eth_handles = vec![];
     if enable_eth {
         let eth_rpc = eth_rpc_url.context("--eth-rpc-url required when --enable-eth is set")?;
         let eth_addr = eth_bridge_address.context("--eth-bridge-address required when --enable-eth is set")?;
-        
-        // Create a separate connection pool for ETH indexer
-        use diesel_async::pooled_connection::deadpool::Pool;
-        use diesel_async::pooled_connection::AsyncDieselConnectionManager;
-        use diesel_async::AsyncPgConnection;
-        
-        let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url.as_str());
-        let pool = Pool::builder(config).build()?;
-        
-        match start_eth_indexer(eth_rpc, eth_addr, eth_start_block, pool, bridge_metrics).await {
-            Ok(handles) => {
-                tracing::info!("ETH indexer started successfully");
-                eth_handles = handles;
-            }
-            Err(e) => {
-                tracing::error!("Failed to start ETH indexer: {:?}", e);
-                return Err(e);
+
+        let targets = std::iter::once(EthTarget {
+            rpc_url: eth_rpc,
+            bridge_address: eth_addr,
+            start_block: eth_start_block,
+        })
+        .chain(extra_eth_targets);
+        for target in targets {
+            match start_eth_indexer(
+                target.rpc_url,
+                target.bridge_address,
+                target.start_block,
+                eth_confirmations,
+                pool.clone(),
+                bridge_metrics.clone(),
+            )
+            .await
+            {
+                Ok(handles) => {
+                    tracing::info!("ETH indexer started successfully");
+                    eth_handles.extend(handles);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to start ETH indexer: {:?}", e);
+                    return Err(e);
+                }
             }
         }
+    } else if !extra_eth_targets.is_empty() {
+        anyhow::bail!("--extra-eth-target requires --enable-eth");
     }
 
     // Wait for all tasks
@@ -193,6 +291,9 @@ async fn main() -> Result<(), anyhow::Error> {
             _ = h_metrics => {
                 tracing::warn!("Metrics server stopped");
             }
+            _ = api_stopped => {
+                tracing::warn!("Indexer API stopped");
+            }
         }
     } else {
         // Both Starcoin and ETH indexers running
@@ -203,6 +304,9 @@ async fn main() -> Result<(), anyhow::Error> {
             _ = h_metrics => {
                 tracing::warn!("Metrics server stopped");
             }
+            _ = api_stopped => {
+                tracing::warn!("Indexer API stopped");
+            }
             _ = futures::future::join_all(eth_handles) => {
                 tracing::warn!("ETH indexer stopped");
             }
@@ -34,6 +34,9 @@ pub struct BridgeIndexerMetrics {
     // Token-specific metrics
     pub bridge_token_reserves: IntGaugeVec,
     pub bridge_supported_tokens: IntGaugeVec,
+
+    // Indexer HTTP API
+    pub api_request_latency: HistogramVec,
 }
 
 impl BridgeIndexerMetrics {
@@ -143,6 +146,15 @@ impl BridgeIndexerMetrics {
                 registry
             )
             .unwrap(),
+
+            api_request_latency: register_histogram_vec_with_registry!(
+                "bridge_indexer_api_request_latency_seconds",
+                "Latency of indexer API requests by endpoint and response status",
+                &["endpoint", "status"],
+                vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0],
+                registry
+            )
+            .unwrap(),
         })
     }
 }
@@ -0,0 +1 @@
+ALTER TABLE governance_actions DROP COLUMN block_height;
@@ -0,0 +1 @@
+ALTER TABLE governance_actions ADD COLUMN block_height BIGINT;
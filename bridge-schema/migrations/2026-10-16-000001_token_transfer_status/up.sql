@@ -0,0 +1,17 @@
+CREATE TABLE token_transfer_status
+(
+    source_chain_id             INT          NOT NULL,
+    nonce                       BIGINT       NOT NULL,
+    deposited_at                BIGINT,
+    approved_at                 BIGINT,
+    claimed_at                  BIGINT,
+    amount                      BIGINT,
+    sender                      bytea,
+    recipient                   bytea,
+    token_id                    INT,
+    status                      TEXT         NOT NULL,
+    PRIMARY KEY(source_chain_id, nonce)
+);
+CREATE INDEX token_transfer_status_status ON token_transfer_status (status);
+CREATE INDEX token_transfer_status_sender ON token_transfer_status (sender);
+CREATE INDEX token_transfer_status_recipient ON token_transfer_status (recipient);
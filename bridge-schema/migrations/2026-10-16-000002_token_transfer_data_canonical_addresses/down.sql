@@ -0,0 +1,2 @@
+ALTER TABLE token_transfer_data DROP COLUMN sender_address_canonical;
+ALTER TABLE token_transfer_data DROP COLUMN recipient_address_canonical;
@@ -0,0 +1,36 @@
+ALTER TABLE token_transfer_data ADD COLUMN sender_address_canonical TEXT;
+ALTER TABLE token_transfer_data ADD COLUMN recipient_address_canonical TEXT;
+
+-- Same as `canonical_address` of the indexer: addresses stored as hex text are
+-- decoded first, then written as 0x-prefixed lowercase hex, left-padded to 16
+-- bytes on Starcoin chains (0-2) and 20 bytes on Eth chains (10-12)
+CREATE FUNCTION pg_temp.canonical_address(chain_id INT, address BYTEA) RETURNS TEXT AS $$
+    SELECT '0x' || lpad(
+        digits,
+        GREATEST(
+            length(digits),
+            CASE
+                WHEN chain_id BETWEEN 0 AND 2 THEN 32
+                WHEN chain_id BETWEEN 10 AND 12 THEN 40
+                ELSE 0
+            END
+        ),
+        '0'
+    )
+    FROM (
+        SELECT CASE
+            WHEN encode(address, 'escape') ~ '^(0x)?([0-9a-fA-F]{2})+$'
+                THEN lower(regexp_replace(encode(address, 'escape'), '^0x', ''))
+            ELSE encode(address, 'hex')
+        END AS digits
+    ) AS hex
+$$ LANGUAGE SQL IMMUTABLE;
+
+UPDATE token_transfer_data SET
+    sender_address_canonical = pg_temp.canonical_address(chain_id, sender_address),
+    recipient_address_canonical = pg_temp.canonical_address(destination_chain, recipient_address);
+
+ALTER TABLE token_transfer_data ALTER COLUMN sender_address_canonical SET NOT NULL;
+ALTER TABLE token_transfer_data ALTER COLUMN recipient_address_canonical SET NOT NULL;
+CREATE INDEX token_transfer_data_sender_canonical ON token_transfer_data (sender_address_canonical);
+CREATE INDEX token_transfer_data_recipient_canonical ON token_transfer_data (recipient_address_canonical);
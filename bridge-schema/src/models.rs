@@ -14,7 +14,7 @@ use starcoin_bridge_indexer_builder::{Task, LIVE_TASK_TARGET_CHECKPOINT};
 
 use crate::schema::{
     governance_actions, progress_store, starcoin_bridge_error_transactions, starcoin_bridge_progress_store, token_transfer,
-    token_transfer_data,
+    token_transfer_data, token_transfer_status,
 };
 
 #[derive(Queryable, Selectable, Insertable, Identifiable, Debug)]
@@ -115,6 +115,27 @@ pub struct TokenTransferData {
     pub token_id: i32,
     pub amount: i64,
     pub is_finalized: bool,
+    // `sender_address` and `recipient_address` as 0x-prefixed lowercase hex,
+    // left-padded to the address width of the source and destination chain
+    pub sender_address_canonical: String,
+    pub recipient_address_canonical: String,
+}
+
+// Latest known state of a transfer, merged from its deposit, approval and
+// claim, which may be indexed in any order. Timestamps are in ms.
+#[derive(Queryable, Selectable, Insertable, Identifiable, Debug, FieldCount, Clone, PartialEq)]
+#[diesel(table_name = token_transfer_status, primary_key(source_chain_id, nonce))]
+pub struct TokenTransferState {
+    pub source_chain_id: i32,
+    pub nonce: i64,
+    pub deposited_at: Option<i64>,
+    pub approved_at: Option<i64>,
+    pub claimed_at: Option<i64>,
+    pub amount: Option<i64>,
+    pub sender: Option<Vec<u8>>,
+    pub recipient: Option<Vec<u8>>,
+    pub token_id: Option<i32>,
+    pub status: TokenTransferStatus,
 }
 
 #[derive(Queryable, Selectable, Insertable, Identifiable, Debug, FieldCount)]
@@ -137,6 +158,8 @@ pub struct GovernanceAction {
     pub timestamp_ms: i64,
     pub action: GovernanceActionType,
     pub data: serde_json::Value,
+    // None for rows indexed before the column was added
+    pub block_height: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, AsExpression, FromSqlRow, EnumString, AsRefStr)]
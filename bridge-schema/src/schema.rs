@@ -56,6 +56,23 @@ diesel::table! {
         token_id -> Int4,
         amount -> Int8,
         is_finalized -> Bool,
+        sender_address_canonical -> Text,
+        recipient_address_canonical -> Text,
+    }
+}
+
+diesel::table! {
+    token_transfer_status (source_chain_id, nonce) {
+        source_chain_id -> Int4,
+        nonce -> Int8,
+        deposited_at -> Nullable<Int8>,
+        approved_at -> Nullable<Int8>,
+        claimed_at -> Nullable<Int8>,
+        amount -> Nullable<Int8>,
+        sender -> Nullable<Bytea>,
+        recipient -> Nullable<Bytea>,
+        token_id -> Nullable<Int4>,
+        status -> Text,
     }
 }
 
@@ -69,6 +86,7 @@ diesel::table! {
         timestamp_ms -> Int8,
         action -> Text,
         data -> Jsonb,
+        block_height -> Nullable<Int8>,
     }
 }
 
@@ -79,4 +97,5 @@ diesel::allow_tables_to_appear_in_same_query!(
     starcoin_bridge_progress_store,
     token_transfer,
     token_transfer_data,
+    token_transfer_status,
 );
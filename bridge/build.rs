@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::process::Command;
+
+// Embeds the git revision the bridge is built from as `BRIDGE_GIT_REVISION`,
+// so nodes can report it. `GIT_REVISION` takes precedence, which is how
+// builds outside a git checkout provide it. Empty when neither is available.
+fn main() {
+    println!("cargo::rerun-if-env-changed=GIT_REVISION");
+    for path in ["../.git/HEAD", "../.git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo::rerun-if-changed={path}");
+        }
+    }
+    let revision = std::env::var("GIT_REVISION")
+        .ok()
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            String::from_utf8(output.stdout).ok()
+        })
+        .map(|revision| revision.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo::rustc-env=BRIDGE_GIT_REVISION={revision}");
+}
@@ -7,14 +7,13 @@ use crate::encoding::{
 };
 use crate::encoding::{
     COMMITTEE_BLOCKLIST_MESSAGE_VERSION, EMERGENCY_BUTTON_MESSAGE_VERSION,
-    TOKEN_TRANSFER_MESSAGE_VERSION,
+    TOKEN_PAUSE_MESSAGE_VERSION, TOKEN_TRANSFER_MESSAGE_VERSION,
 };
 use crate::error::{BridgeError, BridgeResult};
-use crate::types::ParsedTokenTransferMessage;
 use crate::types::{
     AddTokensOnEvmAction, AssetPriceUpdateAction, BlocklistCommitteeAction, BridgeAction,
     BridgeActionType, EmergencyAction, EthLog, EthToStarcoinBridgeAction, EvmContractUpgradeAction,
-    LimitUpdateAction, StarcoinToEthBridgeAction,
+    LimitUpdateAction, StarcoinToEthBridgeAction, TokenPauseAction,
 };
 use ethers::types::Log;
 use ethers::{
@@ -243,18 +242,6 @@ impl TryFrom<StarcoinToEthBridgeAction> for eth_starcoin_bridge::Message {
     }
 }
 
-impl From<ParsedTokenTransferMessage> for eth_starcoin_bridge::Message {
-    fn from(parsed_message: ParsedTokenTransferMessage) -> Self {
-        eth_starcoin_bridge::Message {
-            message_type: BridgeActionType::TokenTransfer as u8,
-            version: parsed_message.message_version,
-            nonce: parsed_message.seq_num,
-            chain_id: parsed_message.source_chain as u8,
-            payload: parsed_message.payload.into(),
-        }
-    }
-}
-
 impl TryFrom<EmergencyAction> for eth_starcoin_bridge::Message {
     type Error = BridgeError;
 
@@ -340,6 +327,23 @@ impl TryFrom<AddTokensOnEvmAction> for eth_bridge_config::Message {
     }
 }
 
+impl TryFrom<TokenPauseAction> for eth_bridge_config::Message {
+    type Error = BridgeError;
+
+    fn try_from(action: TokenPauseAction) -> BridgeResult<Self> {
+        Ok(eth_bridge_config::Message {
+            message_type: BridgeActionType::TokenPause as u8,
+            version: TOKEN_PAUSE_MESSAGE_VERSION,
+            nonce: action.nonce,
+            chain_id: action.chain_id as u8,
+            payload: action
+                .as_payload_bytes()
+                .map_err(|e| BridgeError::Generic(format!("Failed to encode payload: {}", e)))?
+                .into(),
+        })
+    }
+}
+
 impl TryFrom<EvmContractUpgradeAction> for eth_committee_upgradeable_contract::Message {
     type Error = BridgeError;
 
@@ -525,6 +529,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_eth_message_conversion_token_pause_action_regression() -> anyhow::Result<()> {
+        let action = TokenPauseAction {
+            nonce: 9,
+            chain_id: BridgeChainId::EthSepolia,
+            token_id: TOKEN_ID_ETH,
+            pause: true,
+        };
+        let message: eth_bridge_config::Message = action.try_into().unwrap();
+        assert_eq!(
+            message,
+            eth_bridge_config::Message {
+                message_type: BridgeActionType::TokenPause as u8,
+                version: TOKEN_PAUSE_MESSAGE_VERSION,
+                nonce: 9,
+                chain_id: BridgeChainId::EthSepolia as u8,
+                payload: vec![TOKEN_ID_ETH, 1].into(),
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_token_deposit_eth_log_to_starcoin_bridge_event_regression() -> anyhow::Result<()> {
         telemetry_subscribers::init_for_testing();
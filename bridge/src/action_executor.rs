@@ -7,6 +7,7 @@
 use crate::retry_with_max_elapsed_time;
 use crate::types::IsBridgePaused;
 use arc_swap::ArcSwap;
+use ethers::types::Address as EthAddress;
 use fastcrypto::traits::ToFromBytes;
 use starcoin_bridge_json_rpc_types::{StarcoinExecutionStatus, StarcoinTransactionBlockResponse};
 use starcoin_bridge_types::base_types::{ObjectID, ObjectRef, StarcoinAddress, TransactionDigest};
@@ -17,27 +18,37 @@ use starcoin_bridge_types::transaction::ObjectArg;
 use starcoin_bridge_types::TypeTag;
 use starcoin_metrics::spawn_logged_monitored_task;
 
+use crate::audit::verify_eth_deposit;
+use crate::config::ActionQueueConfig;
+use crate::eth_client::EthDepositSources;
 use crate::events::{
     TokenTransferAlreadyApproved, TokenTransferAlreadyClaimed, TokenTransferApproved,
     TokenTransferClaimed,
 };
+use crate::lifecycle::BridgeActionLifecycle;
 use crate::metrics::BridgeMetrics;
+use crate::recipient::check_action_recipient;
+use crate::tx_digest::{tagged, TxChain, TxDigest};
 use crate::{
     client::bridge_authority_aggregator::BridgeAuthorityAggregator,
     error::BridgeError,
-    starcoin_bridge_client::{StarcoinClient, StarcoinClientInner},
+    starcoin_bridge_client::{is_stale_sequence_error, StarcoinClient, StarcoinClientInner},
     starcoin_bridge_transaction_builder::StarcoinBridgeTransactionBuilder,
     storage::BridgeOrchestratorTables,
     types::{BridgeAction, BridgeActionStatus, VerifiedCertifiedBridgeAction},
 };
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::Duration;
-use tracing::{debug, error, info, instrument, warn, Instrument};
+use tracing::{error, info, instrument, warn, Instrument};
 
 pub const CHANNEL_SIZE: usize = 1000;
 pub const SIGNING_CONCURRENCY: usize = 10;
+// How often the signing queue gauges are refreshed while the loop waits for a signing slot
+const SIGNING_QUEUE_REPORT_INTERVAL: Duration = Duration::from_secs(5);
 
 // delay schedule: at most 16 times including the initial attempt
 // 0.1s, 0.2s, 0.4s, 0.8s, 1.6s, 3.2s, 6.4s, 12.8s, 25.6s, 51.2s, 102.4s, 204.8s, 409.6s, 819.2s, 1638.4s
@@ -49,8 +60,9 @@ async fn delay(attempt_times: u64) {
     tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
 }
 
+// (action, attempt times, time the action was put on the signing queue)
 #[derive(Debug)]
-pub struct BridgeActionExecutionWrapper(pub BridgeAction, pub u64);
+pub struct BridgeActionExecutionWrapper(pub BridgeAction, pub u64, pub Instant);
 
 #[derive(Debug)]
 pub struct CertifiedBridgeActionExecutionWrapper(pub VerifiedCertifiedBridgeAction, pub u64);
@@ -74,6 +86,10 @@ pub struct BridgeActionExecutor<C> {
     bridge_object_arg: ObjectArg,
     starcoin_bridge_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
     bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+    eth_deposit_sources: Arc<EthDepositSources>,
+    // Bridge, committee, limiter, config and vault contracts on every watched EVM chain
+    eth_bridge_contracts: Arc<Vec<EthAddress>>,
+    queue_config: ActionQueueConfig,
     metrics: Arc<BridgeMetrics>,
 }
 
@@ -105,6 +121,10 @@ where
         gas_object_id: ObjectID,
         starcoin_bridge_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        // When empty, Eth deposits are not re-checked before execution
+        eth_deposit_sources: EthDepositSources,
+        eth_bridge_contracts: Vec<EthAddress>,
+        queue_config: ActionQueueConfig,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let bridge_object_arg = starcoin_bridge_client
@@ -120,6 +140,9 @@ where
             bridge_object_arg,
             starcoin_bridge_token_type_tags,
             bridge_pause_rx,
+            eth_deposit_sources: Arc::new(eth_deposit_sources),
+            eth_bridge_contracts: Arc::new(eth_bridge_contracts),
+            queue_config,
             metrics,
         }
     }
@@ -134,7 +157,7 @@ where
         let key = self.key;
 
         let (sender, receiver) = starcoin_metrics::metered_channel::channel(
-            CHANNEL_SIZE,
+            self.queue_config.capacity,
             &starcoin_metrics::get_metrics()
                 .unwrap()
                 .channel_inflight
@@ -162,6 +185,8 @@ where
                 sender_clone,
                 receiver,
                 execution_tx_clone,
+                self.eth_bridge_contracts,
+                self.queue_config,
                 metrics,
             )
         ));
@@ -180,6 +205,7 @@ where
                 self.bridge_object_arg,
                 self.starcoin_bridge_token_type_tags,
                 self.bridge_pause_rx,
+                self.eth_deposit_sources,
                 metrics,
             )
         ));
@@ -199,18 +225,41 @@ where
         execution_queue_sender: starcoin_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
+        eth_bridge_contracts: Arc<Vec<EthAddress>>,
+        queue_config: ActionQueueConfig,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_signature_aggregation_loop");
         let semaphore = Arc::new(Semaphore::new(SIGNING_CONCURRENCY));
+        let mut high_water = HighWaterMonitor::new(&queue_config);
+        let mut report_interval = tokio::time::interval(SIGNING_QUEUE_REPORT_INTERVAL);
         while let Some(action) = signing_queue_receiver.recv().await {
+            // Only take the next action once a signing slot is free, so that a burst
+            // backs up into the bounded queue, and from there into the event watchers,
+            // instead of piling up as spawned tasks.
+            let permit = loop {
+                Self::report_signing_queue(
+                    &signing_queue_sender,
+                    Some(&action),
+                    &mut high_water,
+                    &metrics,
+                );
+                tokio::select! {
+                    permit = semaphore.clone().acquire_owned() => {
+                        break permit.expect("semaphore should not be closed");
+                    }
+                    _ = report_interval.tick() => {}
+                }
+            };
+            Self::report_signing_queue(&signing_queue_sender, None, &mut high_water, &metrics);
             Self::handle_signing_task(
-                &semaphore,
+                permit,
                 &auth_agg,
                 &signing_queue_sender,
                 &execution_queue_sender,
                 &starcoin_bridge_client,
                 &store,
+                &eth_bridge_contracts,
                 action,
                 &metrics,
             )
@@ -218,6 +267,35 @@ where
         }
     }
 
+    // Refreshes the signing queue gauges. `head` is the action the loop holds
+    // while waiting for a signing slot, which is also the oldest queued action.
+    fn report_signing_queue(
+        signing_queue_sender: &starcoin_metrics::metered_channel::Sender<
+            BridgeActionExecutionWrapper,
+        >,
+        head: Option<&BridgeActionExecutionWrapper>,
+        high_water: &mut HighWaterMonitor,
+        metrics: &Arc<BridgeMetrics>,
+    ) {
+        let depth = signing_queue_sender.gauge().get().max(0) as usize + head.is_some() as usize;
+        let oldest_age = head.map(|a| a.2.elapsed()).unwrap_or_default();
+        metrics
+            .action_executor_signing_queue_depth
+            .set(depth as i64);
+        metrics
+            .action_executor_signing_queue_oldest_item_age_secs
+            .set(oldest_age.as_secs() as i64);
+        if let Some(congested_for) = high_water.observe(depth, Instant::now()) {
+            warn!(
+                "Signing queue depth {} has been above the high-water mark {} for {}s, oldest action waiting {}s",
+                depth,
+                high_water.high_water_mark,
+                congested_for.as_secs(),
+                oldest_age.as_secs()
+            );
+        }
+    }
+
     async fn should_proceed_signing(starcoin_bridge_client: &Arc<StarcoinClient<C>>) -> bool {
         let Ok(Ok(is_paused)) = retry_with_max_elapsed_time!(
             starcoin_bridge_client.is_bridge_paused(),
@@ -231,7 +309,7 @@ where
 
     #[instrument(level = "error", skip_all, fields(action_key=?action.0.key(), attempt_times=?action.1))]
     async fn handle_signing_task(
-        semaphore: &Arc<Semaphore>,
+        permit: OwnedSemaphorePermit,
         auth_agg: &Arc<ArcSwap<BridgeAuthorityAggregator>>,
         signing_queue_sender: &starcoin_metrics::metered_channel::Sender<
             BridgeActionExecutionWrapper,
@@ -241,6 +319,7 @@ where
         >,
         starcoin_bridge_client: &Arc<StarcoinClient<C>>,
         store: &Arc<BridgeOrchestratorTables>,
+        eth_bridge_contracts: &Arc<Vec<EthAddress>>,
         action: BridgeActionExecutionWrapper,
         metrics: &Arc<BridgeMetrics>,
     ) {
@@ -264,15 +343,16 @@ where
         let execution_queue_sender_clone = execution_queue_sender.clone();
         let starcoin_bridge_client_clone = starcoin_bridge_client.clone();
         let store_clone = store.clone();
+        let eth_bridge_contracts_clone = eth_bridge_contracts.clone();
         let metrics_clone = metrics.clone();
-        let semaphore_clone = semaphore.clone();
         spawn_logged_monitored_task!(
             Self::request_signatures(
-                semaphore_clone,
+                permit,
                 starcoin_bridge_client_clone,
                 auth_agg_clone,
                 action,
                 store_clone,
+                eth_bridge_contracts_clone,
                 signing_queue_sender_clone,
                 execution_queue_sender_clone,
                 metrics_clone,
@@ -298,7 +378,7 @@ where
             )
             .await;
         match status {
-            BridgeActionStatus::Approved | BridgeActionStatus::Claimed => {
+            Ok(BridgeActionStatus::Approved | BridgeActionStatus::Claimed) => {
                 info!(
                     "Action already approved or claimed, removing action from pending logs: {:?}",
                     action
@@ -313,18 +393,28 @@ where
             }
             // Although theoretically a legit StarcoinToEthBridgeAction should not have
             // status `NotFound`
-            BridgeActionStatus::Pending | BridgeActionStatus::NotFound => false,
+            Ok(BridgeActionStatus::Pending | BridgeActionStatus::NotFound) => false,
+            // Only errors that retrying can't fix are returned, signing goes
+            // ahead and fails on its own if the action is invalid
+            Err(e) => {
+                error!(
+                    ?action,
+                    "Failed to get the on-chain status of the action: {:?}", e
+                );
+                false
+            }
         }
     }
 
     // Request signatures from validators
     // Staggering is handled by the semaphore and rate limiting in the orchestrator
     async fn request_signatures(
-        semaphore: Arc<Semaphore>,
+        permit: OwnedSemaphorePermit,
         starcoin_bridge_client: Arc<StarcoinClient<C>>,
         auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
         action: BridgeActionExecutionWrapper,
         store: Arc<BridgeOrchestratorTables>,
+        eth_bridge_contracts: Arc<Vec<EthAddress>>,
         signing_queue_sender: starcoin_metrics::metered_channel::Sender<
             BridgeActionExecutionWrapper,
         >,
@@ -333,12 +423,8 @@ where
         >,
         metrics: Arc<BridgeMetrics>,
     ) {
-        let _permit = semaphore
-            .acquire()
-            .await
-            .expect("semaphore should not be closed");
         info!("requesting signatures");
-        let BridgeActionExecutionWrapper(action, attempt_times) = action;
+        let BridgeActionExecutionWrapper(action, attempt_times, _) = action;
 
         // Only token transfer action should reach here
         match &action {
@@ -347,6 +433,18 @@ where
             _ => unreachable!("Non token transfer action should not reach here"),
         };
 
+        // The deposit already happened on the source chain, so an unclaimable
+        // recipient is only surfaced to operators rather than stalling the nonce.
+        if let Ok(bridge_address) =
+            StarcoinAddress::from_str(starcoin_bridge_client.bridge_address())
+        {
+            if let Err(e) = check_action_recipient(&action, &bridge_address, &eth_bridge_contracts)
+            {
+                warn!("Token transfer has an unclaimable recipient: {:?}", e);
+                metrics.action_executor_unclaimable_recipients.inc();
+            }
+        }
+
         // If the action is already processed, skip it.
         if Self::handle_already_processed_token_transfer_action_maybe(
             &starcoin_bridge_client,
@@ -358,13 +456,15 @@ where
         {
             return;
         }
+        let lifecycle = BridgeActionLifecycle::for_action(&action);
+        lifecycle.signatures_requested();
         match auth_agg
             .load()
             .request_committee_signatures(action.clone())
             .await
         {
             Ok(certificate) => {
-                info!("Sending certificate to execution");
+                lifecycle.signatures_collected(certificate.auth_sig().signatures.len(), None);
                 execution_queue_sender
                     .send(CertifiedBridgeActionExecutionWrapper(certificate, 0))
                     .await
@@ -376,6 +476,12 @@ where
                 warn!("Failed to collect sigs for bridge action: {:?}", e);
                 metrics.err_signature_aggregation.inc();
 
+                // Free the signing slot before re-enqueueing, otherwise a full signing
+                // queue and a loop waiting for a slot would block each other.
+                drop(permit);
+                // Errors that look permanent are asked again too: they are the
+                // answer of one authority, whose RPC may lag behind or be on
+                // another fork, and only the attempts bound the retries.
                 // TODO: spawn a task for this
                 if attempt_times >= MAX_SIGNING_ATTEMPTS {
                     metrics.err_signature_aggregation_too_many_failures.inc();
@@ -384,7 +490,11 @@ where
                 }
                 delay(attempt_times).await;
                 signing_queue_sender
-                    .send(BridgeActionExecutionWrapper(action, attempt_times + 1))
+                    .send(BridgeActionExecutionWrapper(
+                        action,
+                        attempt_times + 1,
+                        Instant::now(),
+                    ))
                     .await
                     .unwrap_or_else(|e| {
                         panic!("Sending to signing queue should not fail: {:?}", e);
@@ -410,6 +520,7 @@ where
         bridge_object_arg: ObjectArg,
         starcoin_bridge_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        eth_deposit_sources: Arc<EthDepositSources>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_onchain_execution_loop");
@@ -434,6 +545,7 @@ where
                 &execution_queue_sender,
                 &bridge_object_arg,
                 &starcoin_bridge_token_type_tags,
+                &eth_deposit_sources,
                 &metrics,
             )
             .await;
@@ -441,6 +553,40 @@ where
         panic!("Execution queue closed unexpectedly");
     }
 
+    fn reenqueue_execution_task(
+        certificate: VerifiedCertifiedBridgeAction,
+        attempt_times: u64,
+        execution_queue_sender: &starcoin_metrics::metered_channel::Sender<
+            CertifiedBridgeActionExecutionWrapper,
+        >,
+        metrics: &Arc<BridgeMetrics>,
+    ) {
+        let action_key = certificate.data().key();
+        let metrics_clone = metrics.clone();
+        let sender_clone = execution_queue_sender.clone();
+        spawn_logged_monitored_task!(async move {
+            if attempt_times >= MAX_EXECUTION_ATTEMPTS {
+                metrics_clone
+                    .err_starcoin_bridge_transaction_submission_too_many_failures
+                    .inc();
+                error!(
+                    "Manual intervention required. Failed after {MAX_EXECUTION_ATTEMPTS} attempts"
+                );
+                return;
+            }
+            delay(attempt_times).await;
+            sender_clone
+                .send(CertifiedBridgeActionExecutionWrapper(
+                    certificate,
+                    attempt_times + 1,
+                ))
+                .await
+                .unwrap_or_else(|e| panic!("Sending to execution queue should not fail: {:?}", e));
+            info!("Re-enqueued certificate for execution");
+        }
+        .instrument(tracing::debug_span!("reenqueue_execution_task", action_key=?action_key)));
+    }
+
     #[instrument(level = "error", skip_all, fields(action_key=?certificate_wrapper.0.data().key(), attempt_times=?certificate_wrapper.1))]
     async fn handle_execution_task(
         certificate_wrapper: CertifiedBridgeActionExecutionWrapper,
@@ -454,6 +600,7 @@ where
         >,
         bridge_object_arg: &ObjectArg,
         starcoin_bridge_token_type_tags: &ArcSwap<HashMap<u8, TypeTag>>,
+        eth_deposit_sources: &EthDepositSources,
         metrics: &Arc<BridgeMetrics>,
     ) {
         metrics
@@ -484,6 +631,47 @@ where
             return;
         }
 
+        // Defense in depth: re-derive the deposit from its Eth log and refuse to
+        // claim anything the deposit does not back.
+        if let BridgeAction::EthToStarcoinBridgeAction(a) = action {
+            if !eth_deposit_sources.is_empty() {
+                let eth_chain_id = a.eth_bridge_event.eth_chain_id;
+                let Some(source) = eth_deposit_sources.get(&eth_chain_id) else {
+                    metrics.err_eth_deposit_mismatch.inc();
+                    error!(
+                        "Manual intervention required. No Eth client for chain {}, refusing to submit",
+                        eth_chain_id
+                    );
+                    return;
+                };
+                match source
+                    .get_finalized_eth_deposit(a.eth_tx_hash, a.eth_event_index)
+                    .await
+                {
+                    Ok(deposit) => {
+                        if let Err(e) = verify_eth_deposit(&deposit, &a.eth_bridge_event) {
+                            metrics.err_eth_deposit_mismatch.inc();
+                            error!(
+                                "Manual intervention required. Action does not match its Eth deposit, refusing to submit: {:?}",
+                                e
+                            );
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to re-fetch Eth deposit for verification: {:?}", e);
+                        Self::reenqueue_execution_task(
+                            certificate.clone(),
+                            attempt_times,
+                            execution_queue_sender,
+                            metrics,
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+
         info!("Building Starcoin transaction");
 
         // Build Starcoin native transaction using the new builder
@@ -534,23 +722,6 @@ where
         // Get sender address from the key (this is who pays gas and signs)
         let sender_address = starcoin_bridge_key.starcoin_address();
 
-        // Get sequence number from Starcoin (from sender's account, not the contract)
-        let seq_number = match starcoin_bridge_client
-            .get_sequence_number(&sender_address.to_hex_literal())
-            .await
-        {
-            Ok(seq) => seq,
-            Err(e) => {
-                error!(
-                    "Failed to get sequence number for sender {}: {:?}",
-                    sender_address.to_hex_literal(),
-                    e
-                );
-                metrics.err_build_starcoin_bridge_transaction.inc();
-                return;
-            }
-        };
-
         // Get current block timestamp from chain for expiration calculation
         let block_timestamp_ms = match starcoin_bridge_client.get_block_timestamp().await {
             Ok(ts) => ts,
@@ -564,34 +735,8 @@ where
         // Get chain ID (use 254 for dev/local, should be configurable)
         let chain_id: u8 = 254;
 
-        // Build raw transaction
-        // module_address = starcoin_bridge_address (where the contract is deployed)
-        // sender = sender_address (from the key, who signs and pays gas)
-        let raw_txn = match StarcoinBridgeTransactionBuilder::build_claim_token(
-            *starcoin_bridge_address, // module_address - where bridge contract is deployed
-            sender_address,           // sender - who signs and pays gas
-            seq_number,
-            chain_id,
-            block_timestamp_ms, // current block timestamp for expiration
-            source_chain,
-            seq_num,
-            sender_addr,
-            target_chain,
-            target_addr,
-            token_type,
-            amount,
-            sig_bytes,
-        ) {
-            Ok(txn) => txn,
-            Err(err) => {
-                metrics.err_build_starcoin_bridge_transaction.inc();
-                error!(
-                    "Failed to build Starcoin transaction for action {:?}: {:?}",
-                    action, err
-                );
-                return;
-            }
-        };
+        let gas_unit_price = starcoin_bridge_client.get_gas_unit_price().await;
+        let max_gas_amount = starcoin_bridge_client.max_gas_amount(action.action_type());
 
         // Sign and submit approve transaction (don't wait for confirmation)
         info!(
@@ -599,12 +744,37 @@ where
             action_key
         );
         info!(
-            "[APPROVE] Transaction params: sender={:?}, seq={}, source_chain={}, bridge_seq_num={}, token_type={}",
-            sender_address, seq_number, source_chain, seq_num, token_type
+            "[APPROVE] Transaction params: sender={:?}, source_chain={}, bridge_seq_num={}, token_type={}",
+            sender_address, source_chain, seq_num, token_type
         );
 
+        // Build raw transaction
+        // module_address = starcoin_bridge_address (where the contract is deployed)
+        // sender = sender_address (from the key, who signs and pays gas)
+        // The client hands out the sender's sequence number (from the sender's
+        // account, not the contract), so concurrent executions don't race for it
+        let mut build_failed = false;
         let approve_result = starcoin_bridge_client
-            .sign_and_submit_transaction(starcoin_bridge_key, raw_txn)
+            .submit_with_managed_sequence(starcoin_bridge_key, |seq_number| {
+                StarcoinBridgeTransactionBuilder::build_claim_token(
+                    *starcoin_bridge_address, // module_address - where bridge contract is deployed
+                    sender_address,           // sender - who signs and pays gas
+                    seq_number,
+                    chain_id,
+                    block_timestamp_ms, // current block timestamp for expiration
+                    gas_unit_price,
+                    max_gas_amount,
+                    source_chain,
+                    seq_num,
+                    sender_addr.clone(),
+                    target_chain,
+                    target_addr.clone(),
+                    token_type,
+                    amount,
+                    sig_bytes.clone(),
+                )
+                .inspect_err(|_| build_failed = true)
+            })
             .await;
 
         info!(
@@ -613,40 +783,41 @@ where
         );
 
         match approve_result {
-            Ok(txn_hash) => {
+            Err(err) if build_failed => {
+                metrics.err_build_starcoin_bridge_transaction.inc();
+                error!(
+                    "Failed to build Starcoin transaction for action {:?}: {:?}",
+                    action, err
+                );
+                return;
+            }
+            Ok(submission) => {
+                let txn_hash = tagged(TxChain::Starcoin, &submission.txn_hash);
                 info!(
-                    "[APPROVE] ✓ Transaction submitted successfully! txn_hash={}, action_key={:?}",
-                    txn_hash, action_key
+                    "[APPROVE] ✓ Transaction submitted successfully! txn_hash={}, seq={}, action_key={:?}",
+                    txn_hash, submission.sequence_number, action_key
                 );
             }
             Err(err) => {
-                let err_str = format!("{:?}", err);
-                // SEQUENCE_NUMBER_TOO_OLD means a previous tx was already executed
-                if !err_str.contains("SEQUENCE_NUMBER_TOO_OLD") {
+                // SEQUENCE_NUMBER_TOO_OLD after the retries means a previous tx was already executed
+                if !is_stale_sequence_error(&err) {
                     error!(
                         "[APPROVE] ✗ Failed to submit approve transaction! action_key={:?}",
                         action_key
                     );
                     error!("[APPROVE] Error details: {:?}", err);
                     error!(
-                        "[APPROVE] Transaction params: sender={:?}, seq={}, source_chain={}, seq_num={}",
-                        sender_address, seq_number, source_chain, seq_num
+                        "[APPROVE] Transaction params: sender={:?}, source_chain={}, seq_num={}",
+                        sender_address, source_chain, seq_num
                     );
                     metrics.err_starcoin_bridge_transaction_submission.inc();
                     // Retry later
-                    let metrics_clone = metrics.clone();
-                    let sender_clone = execution_queue_sender.clone();
-                    spawn_logged_monitored_task!(async move {
-                        if attempt_times >= MAX_EXECUTION_ATTEMPTS {
-                            metrics_clone.err_starcoin_bridge_transaction_submission_too_many_failures.inc();
-                            error!("Manual intervention required. Failed after {MAX_EXECUTION_ATTEMPTS} attempts");
-                            return;
-                        }
-                        delay(attempt_times).await;
-                        sender_clone.send(CertifiedBridgeActionExecutionWrapper(certificate, attempt_times + 1)).await
-                            .unwrap_or_else(|e| panic!("Sending to execution queue should not fail: {:?}", e));
-                        info!("Re-enqueued certificate for execution");
-                    }.instrument(tracing::debug_span!("reenqueue_execution_task", action_key=?action_key)));
+                    Self::reenqueue_execution_task(
+                        certificate,
+                        attempt_times,
+                        execution_queue_sender,
+                        metrics,
+                    );
                     return;
                 }
                 warn!(
@@ -677,7 +848,7 @@ where
             }
 
             match status {
-                BridgeActionStatus::Approved => {
+                Ok(BridgeActionStatus::Approved) => {
                     info!(
                         "[APPROVE] ✓ Transfer APPROVED on chain after {}s! action_key={:?}, source_chain={}, seq_num={}",
                         i, action_key, source_chain, seq_num
@@ -686,7 +857,7 @@ where
                     approved = true;
                     break;
                 }
-                BridgeActionStatus::Claimed => {
+                Ok(BridgeActionStatus::Claimed) => {
                     info!(
                         "[APPROVE] ✓ Transfer already CLAIMED on chain! action_key={:?}, source_chain={}, seq_num={}",
                         action_key, source_chain, seq_num
@@ -700,6 +871,10 @@ where
                         });
                     return;
                 }
+                Err(e) => {
+                    error!(?action_key, "[APPROVE] Status lookup failed: {:?}", e);
+                    break;
+                }
                 _ => {
                     if i % 10 == 0 {
                         info!(
@@ -720,19 +895,12 @@ where
                 action_key, source_chain, seq_num
             );
             error!("[APPROVE] Will retry this transaction...");
-            let metrics_clone = metrics.clone();
-            let sender_clone = execution_queue_sender.clone();
-            spawn_logged_monitored_task!(async move {
-                if attempt_times >= MAX_EXECUTION_ATTEMPTS {
-                    metrics_clone.err_starcoin_bridge_transaction_submission_too_many_failures.inc();
-                    error!("Manual intervention required. Failed after {MAX_EXECUTION_ATTEMPTS} attempts");
-                    return;
-                }
-                delay(attempt_times).await;
-                sender_clone.send(CertifiedBridgeActionExecutionWrapper(certificate, attempt_times + 1)).await
-                    .unwrap_or_else(|e| panic!("Sending to execution queue should not fail: {:?}", e));
-                info!("Re-enqueued certificate for execution");
-            }.instrument(tracing::debug_span!("reenqueue_execution_task", action_key=?action_key)));
+            Self::reenqueue_execution_task(
+                certificate,
+                attempt_times,
+                execution_queue_sender,
+                metrics,
+            );
             return;
         }
 
@@ -759,53 +927,7 @@ where
         }
 
         // For EthToStarcoinBridgeAction, continue to submit claim transaction on Starcoin
-        // Get fresh sequence number for claim transaction
-        // Must be > approve sequence number (seq_number), poll until chain state is updated
-        let claim_seq_number = {
-            let mut retries = 0;
-            let max_retries = 10;
-            loop {
-                match starcoin_bridge_client
-                    .get_sequence_number(&sender_address.to_hex_literal())
-                    .await
-                {
-                    Ok(new_seq) => {
-                        if new_seq > seq_number {
-                            info!(
-                                "Got claim sequence number: {} (approve used: {})",
-                                new_seq, seq_number
-                            );
-                            break new_seq;
-                        }
-                        retries += 1;
-                        if retries >= max_retries {
-                            error!("Sequence number not updated after {} retries (got {}, expected > {})", 
-                                   max_retries, new_seq, seq_number);
-                            // Use approve seq + 1 as fallback
-                            break seq_number + 1;
-                        }
-                        debug!(
-                            "Sequence number {} not yet updated (expected > {}), retry {}/{}",
-                            new_seq, seq_number, retries, max_retries
-                        );
-                        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-                    }
-                    Err(e) => {
-                        error!("[CLAIM] ✗ Failed to get sequence number for claim transaction!");
-                        error!(
-                            "[CLAIM] Error: {:?}, sender={:?}, approve_seq={}",
-                            e, sender_address, seq_number
-                        );
-                        store
-                            .remove_pending_actions(&[action.digest()])
-                            .unwrap_or_else(|e| {
-                                panic!("Write to DB should not fail: {:?}", e);
-                            });
-                        return;
-                    }
-                }
-            }
-        };
+        // The client hands out the sequence number after the approve's
 
         // Get fresh block timestamp
         let claim_block_timestamp_ms = match starcoin_bridge_client.get_block_timestamp().await {
@@ -825,31 +947,46 @@ where
         // Build claim transaction
         info!("[CLAIM] Building claim transaction with parameters:");
         info!(
-            "[CLAIM]   bridge_address={:?}, sender={:?}, claim_seq={}",
-            starcoin_bridge_address, sender_address, claim_seq_number
+            "[CLAIM]   bridge_address={:?}, sender={:?}",
+            starcoin_bridge_address, sender_address
         );
         info!(
             "[CLAIM]   source_chain={}, bridge_seq_num={}, token_type={}, timestamp={}",
             source_chain, seq_num, token_type, claim_block_timestamp_ms
         );
-        let claim_txn = match StarcoinBridgeTransactionBuilder::build_claim_and_transfer(
-            *starcoin_bridge_address,
-            sender_address,
-            claim_seq_number,
-            chain_id,
-            claim_block_timestamp_ms,
-            claim_block_timestamp_ms,
-            source_chain,
-            seq_num,
-            token_type,
-        ) {
-            Ok(txn) => txn,
-            Err(err) => {
+        let gas_unit_price = starcoin_bridge_client.get_gas_unit_price().await;
+        let max_gas_amount = starcoin_bridge_client.max_gas_amount(action.action_type());
+        let token_type_tags = starcoin_bridge_token_type_tags.load_full();
+        let mut build_failed = false;
+
+        // Submit claim transaction
+        info!("[CLAIM] Submitting claim transaction to Starcoin...");
+        let claim_result = starcoin_bridge_client
+            .submit_with_managed_sequence(starcoin_bridge_key, |claim_seq_number| {
+                StarcoinBridgeTransactionBuilder::build_claim_and_transfer(
+                    *starcoin_bridge_address,
+                    sender_address,
+                    claim_seq_number,
+                    chain_id,
+                    claim_block_timestamp_ms,
+                    gas_unit_price,
+                    max_gas_amount,
+                    claim_block_timestamp_ms,
+                    source_chain,
+                    seq_num,
+                    token_type,
+                    &token_type_tags,
+                )
+                .inspect_err(|_| build_failed = true)
+            })
+            .await;
+        match claim_result {
+            Err(err) if build_failed => {
                 error!("[CLAIM] ✗ Failed to build claim transaction!");
                 error!("[CLAIM] Error: {:?}", err);
                 error!(
-                    "[CLAIM] Params: source_chain={}, seq_num={}, token_type={}, claim_seq={}",
-                    source_chain, seq_num, token_type, claim_seq_number
+                    "[CLAIM] Params: source_chain={}, seq_num={}, token_type={}",
+                    source_chain, seq_num, token_type
                 );
                 store
                     .remove_pending_actions(&[action.digest()])
@@ -858,18 +995,11 @@ where
                     });
                 return;
             }
-        };
-
-        // Submit claim transaction
-        info!("[CLAIM] Submitting claim transaction to Starcoin...");
-        match starcoin_bridge_client
-            .sign_and_submit_transaction(starcoin_bridge_key, claim_txn)
-            .await
-        {
-            Ok(claim_txn_hash) => {
+            Ok(submission) => {
+                let claim_txn_hash = tagged(TxChain::Starcoin, &submission.txn_hash);
                 info!(
-                    "[CLAIM] ✓ Transaction submitted successfully! claim_txn_hash={}, action_key={:?}",
-                    claim_txn_hash, action_key
+                    "[CLAIM] ✓ Transaction submitted successfully! claim_txn_hash={}, claim_seq={}, action_key={:?}",
+                    claim_txn_hash, submission.sequence_number, action_key
                 );
                 info!("[CLAIM] Polling for claim confirmation (max 30s)...");
 
@@ -890,7 +1020,7 @@ where
                         );
                     }
 
-                    if status == BridgeActionStatus::Claimed {
+                    if status == Ok(BridgeActionStatus::Claimed) {
                         info!(
                             "[CLAIM] ✓ CLAIMED on chain after {}s! claim_txn_hash={}, bridge transfer COMPLETE!",
                             i, claim_txn_hash
@@ -898,6 +1028,10 @@ where
                         metrics.eth_starcoin_bridge_token_transfer_claimed.inc();
                         break;
                     }
+                    if let Err(e) = status {
+                        error!("[CLAIM] Status lookup failed: {:?}", e);
+                        break;
+                    }
                 }
             }
             Err(err) => {
@@ -951,7 +1085,11 @@ where
                     or TokenTransferAlreadyApproved event but got: {:?}",
                     events
                 );
-                info!(?tx_digest, "Starcoin transaction executed successfully");
+                info!(
+                    tx_digest = %TxDigest::starcoin(tx_digest),
+                    "Starcoin transaction executed successfully"
+                );
+                BridgeActionLifecycle::for_action(action).tx_confirmed("success");
                 // track successful approval and claim events
                 relevant_events.iter().for_each(|e| {
                     if e.type_ == *TokenTransferClaimed.get().unwrap() {
@@ -990,7 +1128,8 @@ where
                 // After human examination, the node should be restarted and fetch them from WAL.
 
                 metrics.err_starcoin_bridge_transaction_execution.inc();
-                error!(?tx_digest, "Manual intervention is needed. Starcoin transaction executed and failed with error: {error:?}");
+                BridgeActionLifecycle::for_action(action).tx_confirmed("failure");
+                error!(tx_digest = %TxDigest::starcoin(tx_digest), "Manual intervention is needed. Starcoin transaction executed and failed with error: {error:?}");
             }
         }
     }
@@ -1000,10 +1139,10 @@ where
         starcoin_bridge_address: StarcoinAddress,
         gas_object_id: ObjectID,
         starcoin_bridge_client: &StarcoinClient<C>,
-    ) -> (GasCoin, ObjectRef) {
+    ) -> Result<(GasCoin, ObjectRef), BridgeError> {
         let (gas_coin, gas_obj_ref, owner) = starcoin_bridge_client
             .get_gas_data_panic_if_not_gas(gas_object_id)
-            .await;
+            .await?;
 
         // TODO: when we add multiple gas support in the future we could discard
         // transferred gas object instead.
@@ -1014,7 +1153,7 @@ where
             gas_object_id,
             starcoin_bridge_address
         );
-        (gas_coin, gas_obj_ref)
+        Ok((gas_coin, gas_obj_ref))
     }
 }
 
@@ -1022,14 +1161,66 @@ pub async fn submit_to_executor(
     tx: &starcoin_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
     action: BridgeAction,
 ) -> Result<(), BridgeError> {
-    tx.send(BridgeActionExecutionWrapper(action, 0))
+    let action = match tx.try_send(BridgeActionExecutionWrapper(action, 0, Instant::now())) {
+        Ok(()) => return Ok(()),
+        Err(tokio::sync::mpsc::error::TrySendError::Full(action)) => action,
+        Err(e) => return Err(BridgeError::Generic(e.to_string())),
+    };
+    // The caller only advances its event cursor after this returns, so waiting
+    // here pauses event processing without losing anything.
+    info!(
+        "Action executor queue is full, waiting to submit {:?}",
+        action.0.key()
+    );
+    tx.send(action)
         .await
         .map_err(|e| BridgeError::Generic(e.to_string()))
 }
 
+// Tracks how long the signing queue has stayed above its high-water mark.
+struct HighWaterMonitor {
+    high_water_mark: usize,
+    warn_after: Duration,
+    above_since: Option<Instant>,
+    last_warned_at: Option<Instant>,
+}
+
+impl HighWaterMonitor {
+    fn new(config: &ActionQueueConfig) -> Self {
+        Self {
+            high_water_mark: config.high_water_mark,
+            warn_after: Duration::from_secs(config.high_water_warn_after_secs),
+            above_since: None,
+            last_warned_at: None,
+        }
+    }
+
+    // Returns how long the queue has been congested when a warning is due.
+    // Warnings repeat at most once per `warn_after` while congestion lasts.
+    fn observe(&mut self, depth: usize, now: Instant) -> Option<Duration> {
+        if depth <= self.high_water_mark {
+            self.above_since = None;
+            self.last_warned_at = None;
+            return None;
+        }
+        let congested_for = now.duration_since(*self.above_since.get_or_insert(now));
+        if congested_for < self.warn_after {
+            return None;
+        }
+        if let Some(last_warned_at) = self.last_warned_at {
+            if now.duration_since(last_warned_at) < self.warn_after {
+                return None;
+            }
+        }
+        self.last_warned_at = Some(now);
+        Some(congested_for)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::events::init_all_struct_tags;
+    use crate::fault_injection::FaultInjector;
     use crate::starcoin_bridge_transaction_builder::build_starcoin_bridge_transaction;
     use crate::test_utils::TransactionDigestTestExt;
     use crate::test_utils::DUMMY_MUTALBE_BRIDGE_OBJECT_ARG;
@@ -1037,9 +1228,6 @@ mod tests {
     use fastcrypto::traits::KeyPair;
     use prometheus::Registry;
     use serial_test::serial;
-    use starcoin_bridge_json_rpc_types::StarcoinTransactionBlockEffects;
-    use starcoin_bridge_json_rpc_types::StarcoinTransactionBlockEvents;
-    use starcoin_bridge_json_rpc_types::{StarcoinEvent, StarcoinTransactionBlockResponse};
     use starcoin_bridge_types::base_types::random_object_ref;
     use starcoin_bridge_types::base_types::TransactionDigest;
     use starcoin_bridge_types::crypto::get_key_pair;
@@ -1065,6 +1253,29 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_high_water_monitor() {
+        let mut monitor = HighWaterMonitor::new(&ActionQueueConfig {
+            capacity: 10,
+            high_water_mark: 5,
+            high_water_warn_after_secs: 60,
+        });
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(monitor.observe(3, at(0)), None);
+        assert_eq!(monitor.observe(6, at(0)), None);
+        assert_eq!(monitor.observe(8, at(59)), None);
+        assert_eq!(monitor.observe(8, at(60)), Some(Duration::from_secs(60)));
+        // Not repeated until another `warn_after` has passed
+        assert_eq!(monitor.observe(8, at(90)), None);
+        assert_eq!(monitor.observe(8, at(120)), Some(Duration::from_secs(120)));
+        // Dropping to the mark ends the congestion
+        assert_eq!(monitor.observe(5, at(121)), None);
+        assert_eq!(monitor.observe(9, at(130)), None);
+        assert_eq!(monitor.observe(9, at(190)), Some(Duration::from_secs(60)));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_onchain_execution_loop() {
@@ -1072,7 +1283,6 @@ mod tests {
             signing_tx,
             _execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             store,
             secrets,
             _dummy_starcoin_bridge_key,
@@ -1085,6 +1295,7 @@ mod tests {
             _starcoin_bridge_address,
             _starcoin_bridge_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         /////////////////////////////////////////////////////////////////
@@ -1185,7 +1396,6 @@ mod tests {
             signing_tx,
             _execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             store,
             secrets,
             _dummy_starcoin_bridge_key,
@@ -1198,6 +1408,7 @@ mod tests {
             _starcoin_bridge_address,
             _starcoin_bridge_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         // Create action with nonce=3 to avoid conflicts with other tests
@@ -1286,6 +1497,166 @@ mod tests {
             .contains_key(&action.digest()));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_signature_aggregation_retries_rejected_action() {
+        let (
+            signing_tx,
+            _execution_tx,
+            starcoin_bridge_client_mock,
+            store,
+            secrets,
+            _dummy_starcoin_bridge_key,
+            mock0,
+            _mock1,
+            _mock2,
+            _mock3,
+            _handles,
+            _gas_object_ref,
+            _starcoin_bridge_address,
+            _starcoin_bridge_token_type_tags,
+            _bridge_pause_tx,
+            _metrics,
+        ) = setup().await;
+
+        let (action_certificate, starcoin_bridge_tx_digest, starcoin_bridge_tx_event_index) =
+            get_bridge_authority_approved_action_with_nonce(
+                vec![&mock0],
+                vec![&secrets[0]],
+                None,
+                true,
+                4,
+            );
+        let action = action_certificate.data().clone();
+        // The authority rejects the request as invalid, e.g. while its
+        // provider is on another fork
+        mock0.add_starcoin_bridge_event_response(
+            starcoin_bridge_tx_digest,
+            starcoin_bridge_tx_event_index,
+            Err(BridgeError::NoBridgeEventsInTxPosition),
+            None,
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+
+        let requested_times = || {
+            mock0.get_starcoin_bridge_token_events_requested(
+                starcoin_bridge_tx_digest,
+                starcoin_bridge_tx_event_index,
+            )
+        };
+        // The action is not dropped after the first answer
+        let now = std::time::Instant::now();
+        while requested_times() < 2 {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for the signature request to be retried");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+        assert!(store
+            .get_all_pending_actions()
+            .contains_key(&action.digest()));
+
+        // The authority catches up and signs
+        mock_bridge_authority_sigs(
+            vec![&mock0],
+            &action,
+            vec![&secrets[0]],
+            starcoin_bridge_tx_digest,
+            starcoin_bridge_tx_event_index,
+        );
+        starcoin_bridge_client_mock
+            .set_action_onchain_status(&action, BridgeActionStatus::Approved);
+
+        let now = std::time::Instant::now();
+        while store
+            .get_all_pending_actions()
+            .contains_key(&action.digest())
+        {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for action to be removed from WAL");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_signature_aggregation_flags_bridge_contract_recipient() {
+        let (
+            signing_tx,
+            _execution_tx,
+            starcoin_bridge_client_mock,
+            store,
+            _secrets,
+            _dummy_starcoin_bridge_key,
+            mock0,
+            _mock1,
+            _mock2,
+            _mock3,
+            _handles,
+            _gas_object_ref,
+            _starcoin_bridge_address,
+            _starcoin_bridge_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+
+        let starcoin_bridge_tx_digest = TransactionDigest::random();
+        let starcoin_bridge_tx_event_index = 0;
+        // The funds are sent to the bridge contract itself
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            Some(starcoin_bridge_tx_digest),
+            Some(starcoin_bridge_tx_event_index),
+            Some(4),
+            None,
+            None,
+            Some(test_eth_bridge_contract()),
+            None,
+        );
+        mock_bridge_authority_signing_errors(
+            vec![&mock0],
+            starcoin_bridge_tx_digest,
+            starcoin_bridge_tx_event_index,
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+
+        // The recipient is flagged, but signatures are still requested so the
+        // nonce does not stall
+        let now = std::time::Instant::now();
+        while mock0.get_starcoin_bridge_token_events_requested(
+            starcoin_bridge_tx_digest,
+            starcoin_bridge_tx_event_index,
+        ) == 0
+        {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for signatures to be requested");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+        assert!(metrics.action_executor_unclaimable_recipients.get() >= 1);
+
+        starcoin_bridge_client_mock
+            .set_action_onchain_status(&action, BridgeActionStatus::Approved);
+        let now = std::time::Instant::now();
+        while store
+            .get_all_pending_actions()
+            .contains_key(&action.digest())
+        {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for action to be removed from WAL");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_skip_request_signature_if_already_processed_on_chain() {
@@ -1293,7 +1664,6 @@ mod tests {
             signing_tx,
             _execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             store,
             _secrets,
             _dummy_starcoin_bridge_key,
@@ -1306,6 +1676,7 @@ mod tests {
             _starcoin_bridge_address,
             _starcoin_bridge_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         let starcoin_bridge_tx_digest = TransactionDigest::random();
@@ -1362,7 +1733,6 @@ mod tests {
             _signing_tx,
             execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             store,
             secrets,
             _dummy_starcoin_bridge_key,
@@ -1375,6 +1745,7 @@ mod tests {
             _starcoin_bridge_address,
             _starcoin_bridge_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         // Use nonce=5 to avoid conflicts
@@ -1432,7 +1803,6 @@ mod tests {
             _signing_tx,
             execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             store,
             secrets,
             _dummy_starcoin_bridge_key,
@@ -1445,6 +1815,7 @@ mod tests {
             _starcoin_bridge_address,
             _starcoin_bridge_token_type_tags,
             bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         // Use nonce=6 to avoid conflicts
@@ -1508,7 +1879,6 @@ mod tests {
             _signing_tx,
             execution_tx,
             starcoin_bridge_client_mock,
-            _tx_subscription,
             _store,
             secrets,
             _dummy_starcoin_bridge_key,
@@ -1521,6 +1891,7 @@ mod tests {
             _starcoin_bridge_address,
             starcoin_bridge_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
         let mut id_token_map: HashMap<u8, TypeTag> =
             (*starcoin_bridge_token_type_tags.load().clone()).clone();
@@ -1667,46 +2038,85 @@ mod tests {
         )
     }
 
-    /// Generate a random transaction digest for testing.
-    /// In Starcoin, we don't need Sui's Intent/Signature mechanism.
-    fn get_tx_digest_for_testing() -> TransactionDigest {
-        TransactionDigest::random()
-    }
+    #[tokio::test]
+    #[serial]
+    async fn test_no_duplicate_submission_under_faults() {
+        let (
+            _signing_tx,
+            execution_tx,
+            starcoin_bridge_client_mock,
+            store,
+            secrets,
+            _dummy_starcoin_bridge_key,
+            mock0,
+            _mock1,
+            _mock2,
+            _mock3,
+            _handles,
+            _gas_object_ref,
+            _starcoin_bridge_address,
+            _starcoin_bridge_token_type_tags,
+            _bridge_pause_tx,
+            _metrics,
+        ) = setup().await;
 
-    // Why is `wildcard` needed? This is because authority signatures
-    // are part of transaction data. Depending on whose signatures
-    // are included in what order, this may change the tx digest.
-    fn mock_transaction_response(
-        starcoin_bridge_client_mock: &StarcoinMockClient,
-        tx_digest: TransactionDigest,
-        status: StarcoinExecutionStatus,
-        events: Option<Vec<StarcoinEvent>>,
-        wildcard: bool,
-    ) {
-        let mut response = StarcoinTransactionBlockResponse::new(tx_digest);
-        let effects = StarcoinTransactionBlockEffects::new_for_testing(tx_digest, status);
-        if let Some(events) = events {
-            response.events = Some(StarcoinTransactionBlockEvents { data: events });
+        // Use nonce=8 to avoid conflicts
+        let (action_certificate, _, _) = get_bridge_authority_approved_action_with_nonce(
+            vec![&mock0],
+            vec![&secrets[0]],
+            None,
+            true,
+            8, // nonce = 8
+        );
+        let action = action_certificate.data().clone();
+        let action_digest = action.digest();
+
+        // The first two submissions fail, status reads are flaky and slow
+        starcoin_bridge_client_mock.set_fault_injector(
+            FaultInjector::builder(8)
+                .fail_first("sign_and_submit_transaction", 2)
+                .failure_rate("get_token_transfer_action_onchain_status", 0.3)
+                .latency(
+                    "get_token_transfer_action_onchain_status",
+                    Duration::from_millis(0),
+                    Duration::from_millis(50),
+                )
+                .build(),
+        );
+        starcoin_bridge_client_mock.set_action_onchain_status(&action, BridgeActionStatus::Pending);
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+
+        execution_tx
+            .send(CertifiedBridgeActionExecutionWrapper(action_certificate, 0))
+            .await
+            .unwrap();
+
+        // Play the chain: the approve lands, then the claim lands
+        let now = std::time::Instant::now();
+        for (submitted, status) in [
+            (1, BridgeActionStatus::Approved),
+            (2, BridgeActionStatus::Claimed),
+        ] {
+            while starcoin_bridge_client_mock.submitted_transactions().len() < submitted {
+                if now.elapsed().as_secs() > 30 {
+                    panic!("Timeout waiting for transaction submission");
+                }
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+            starcoin_bridge_client_mock.set_action_onchain_status(&action, status);
         }
-        response.effects = Some(effects);
-        if wildcard {
-            starcoin_bridge_client_mock.set_wildcard_transaction_response(Ok(response));
-        } else {
-            starcoin_bridge_client_mock.add_transaction_response(tx_digest, Ok(response));
+        while store.get_all_pending_actions().contains_key(&action_digest) {
+            if now.elapsed().as_secs() > 30 {
+                panic!("Timeout waiting for action to be removed from WAL");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         }
-    }
 
-    fn mock_transaction_error(
-        starcoin_bridge_client_mock: &StarcoinMockClient,
-        tx_digest: TransactionDigest,
-        error: BridgeError,
-        wildcard: bool,
-    ) {
-        if wildcard {
-            starcoin_bridge_client_mock.set_wildcard_transaction_response(Err(error));
-        } else {
-            starcoin_bridge_client_mock.add_transaction_response(tx_digest, Err(error));
-        }
+        // Retries never resubmitted an accepted transaction
+        let submitted = starcoin_bridge_client_mock.submitted_transactions();
+        assert_eq!(submitted.len(), 2);
+        assert_ne!(submitted[0].payload(), submitted[1].payload());
+        assert!(submitted[0].sequence_number() < submitted[1].sequence_number());
     }
 
     #[allow(clippy::type_complexity)]
@@ -1714,7 +2124,6 @@ mod tests {
         starcoin_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
         starcoin_metrics::metered_channel::Sender<CertifiedBridgeActionExecutionWrapper>,
         StarcoinMockClient,
-        tokio::sync::broadcast::Receiver<TransactionDigest>,
         Arc<BridgeOrchestratorTables>,
         Vec<BridgeAuthorityKeyPair>,
         StarcoinKeyPair,
@@ -1727,6 +2136,7 @@ mod tests {
         StarcoinAddress,
         Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         tokio::sync::watch::Sender<IsBridgePaused>,
+        Arc<BridgeMetrics>,
     ) {
         telemetry_subscribers::init_for_testing();
         let registry = Registry::new();
@@ -1740,7 +2150,6 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let store = BridgeOrchestratorTables::new(temp_dir.path());
         let starcoin_bridge_client_mock = StarcoinMockClient::default();
-        let tx_subscription = starcoin_bridge_client_mock.subscribe_to_requested_transactions();
         let starcoin_bridge_client = Arc::new(StarcoinClient::new_for_testing(
             starcoin_bridge_client_mock.clone(),
         ));
@@ -1783,7 +2192,10 @@ mod tests {
             gas_object_ref.0,
             starcoin_bridge_token_type_tags.clone(),
             bridge_pause_rx,
-            metrics,
+            HashMap::new(),
+            vec![test_eth_bridge_contract()],
+            ActionQueueConfig::default(),
+            metrics.clone(),
         )
         .await;
 
@@ -1794,7 +2206,6 @@ mod tests {
             signing_tx,
             execution_tx,
             starcoin_bridge_client_mock,
-            tx_subscription,
             store,
             secrets,
             dummy_starcoin_bridge_key,
@@ -1807,6 +2218,11 @@ mod tests {
             starcoin_bridge_address,
             starcoin_bridge_token_type_tags,
             bridge_pause_tx,
+            metrics,
         )
     }
+
+    fn test_eth_bridge_contract() -> EthAddress {
+        EthAddress::repeat_byte(0xbb)
+    }
 }
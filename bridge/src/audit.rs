@@ -0,0 +1,737 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retroactive audit of committee signatures attached to on-chain approvals.
+//!
+//! When a committee key is compromised and later blocklisted (or rotated out),
+//! we want to know which historical approvals carried its signature and whether
+//! those approvals would still have reached quorum without it. Membership is
+//! resolved against a `CommitteeTimeline`, i.e. the committee snapshots that
+//! were in effect as of the approval's block.
+//!
+//! It also holds the field-by-field comparison of a transfer action against
+//! the deposit it was derived from, shared by the claim paths and audits.
+
+use crate::abi::EthToStarcoinTokenBridgeV1;
+use crate::crypto::{
+    BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes, BridgeAuthorityRecoverableSignature,
+};
+use crate::error::{BridgeError, BridgeResult};
+use crate::types::{BridgeCommittee, ParsedTokenTransferMessage};
+use ethers::types::Address as EthAddress;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::Keccak256;
+use fastcrypto::traits::{RecoverableSignature, ToFromBytes};
+use serde::{Deserialize, Serialize};
+use starcoin_bridge_types::bridge::APPROVAL_THRESHOLD_TOKEN_TRANSFER;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeMemberSnapshot {
+    pub pubkey: BridgeAuthorityPublicKeyBytes,
+    pub voting_power: u64,
+    #[serde(default)]
+    pub blocklisted: bool,
+}
+
+// Committee membership that became effective at `from_block` and stays in
+// effect until the next snapshot in the timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeSnapshot {
+    pub from_block: u64,
+    pub members: Vec<CommitteeMemberSnapshot>,
+}
+
+impl CommitteeSnapshot {
+    pub fn from_committee(committee: &BridgeCommittee, from_block: u64) -> Self {
+        Self {
+            from_block,
+            members: committee
+                .members()
+                .iter()
+                .map(|(pubkey, authority)| CommitteeMemberSnapshot {
+                    pubkey: pubkey.clone(),
+                    voting_power: authority.voting_power,
+                    blocklisted: authority.is_blocklisted,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn member(
+        &self,
+        pubkey: &BridgeAuthorityPublicKeyBytes,
+    ) -> Option<&CommitteeMemberSnapshot> {
+        self.members.iter().find(|m| &m.pubkey == pubkey)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommitteeTimeline {
+    snapshots: BTreeMap<u64, CommitteeSnapshot>,
+}
+
+impl CommitteeTimeline {
+    pub fn new(snapshots: Vec<CommitteeSnapshot>) -> BridgeResult<Self> {
+        let mut timeline = Self::default();
+        for snapshot in snapshots {
+            timeline.insert(snapshot)?;
+        }
+        Ok(timeline)
+    }
+
+    pub fn insert(&mut self, snapshot: CommitteeSnapshot) -> BridgeResult<()> {
+        let mut seen = BTreeSet::new();
+        for member in &snapshot.members {
+            if !seen.insert(member.pubkey.clone()) {
+                return Err(BridgeError::InvalidBridgeCommittee(format!(
+                    "Duplicate member {:?} in committee snapshot at block {}",
+                    Hex::encode(member.pubkey.as_bytes()),
+                    snapshot.from_block
+                )));
+            }
+        }
+        if self.snapshots.contains_key(&snapshot.from_block) {
+            return Err(BridgeError::InvalidBridgeCommittee(format!(
+                "Duplicate committee snapshot at block {}",
+                snapshot.from_block
+            )));
+        }
+        self.snapshots.insert(snapshot.from_block, snapshot);
+        Ok(())
+    }
+
+    // Return the snapshot in effect at `block`, namely the last one with `from_block <= block`.
+    pub fn snapshot_at(&self, block: u64) -> Option<&CommitteeSnapshot> {
+        self.snapshots.range(..=block).next_back().map(|(_, s)| s)
+    }
+
+    pub fn latest(&self) -> Option<&CommitteeSnapshot> {
+        self.snapshots.values().next_back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+// An on-chain approval to audit: the signed message and the signatures stored with it.
+#[derive(Debug, Clone)]
+pub struct ApprovalRecord {
+    pub source_chain: u8,
+    pub nonce: u64,
+    // Block at which the approval landed. When unknown, membership is
+    // resolved against the latest snapshot.
+    pub approval_block: Option<u64>,
+    pub message_bytes: Vec<u8>,
+    pub approval_threshold: u64,
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl ApprovalRecord {
+    pub fn from_token_transfer(
+        message: &ParsedTokenTransferMessage,
+        approval_block: Option<u64>,
+        signatures: Vec<Vec<u8>>,
+    ) -> Self {
+        Self {
+            source_chain: message.source_chain as u8,
+            nonce: message.seq_num,
+            approval_block,
+            message_bytes: message.to_message_bytes(),
+            approval_threshold: APPROVAL_THRESHOLD_TOKEN_TRANSFER,
+            signatures,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignerFlag {
+    // Signature bytes could not be parsed or no key could be recovered
+    InvalidSignature,
+    // Signer was not a committee member when the approval landed
+    NotMemberAtApproval,
+    // Signer was already blocklisted when the approval landed
+    BlocklistedAtApproval,
+    // Signer is blocklisted in the latest committee
+    NowBlocklisted,
+    // Signer is no longer part of the latest committee
+    RotatedOut,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SignerAudit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_address: Option<EthAddress>,
+    pub voting_power_at_approval: u64,
+    pub flags: Vec<SignerFlag>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApprovalAudit {
+    pub source_chain: u8,
+    pub nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_block: Option<u64>,
+    // `from_block` of the snapshot used to resolve membership
+    pub snapshot_block: u64,
+    pub signers: Vec<SignerAudit>,
+    pub approval_threshold: u64,
+    pub stake_at_approval: u64,
+    pub stake_without_flagged: u64,
+    pub quorum_without_flagged: bool,
+}
+
+impl ApprovalAudit {
+    pub fn is_flagged(&self) -> bool {
+        self.signers.iter().any(|s| !s.flags.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SignatureAuditReport {
+    pub audited: u64,
+    pub flagged: Vec<ApprovalAudit>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl SignatureAuditReport {
+    pub fn record(&mut self, audit: ApprovalAudit) {
+        self.audited += 1;
+        if audit.is_flagged() {
+            self.flagged.push(audit);
+        }
+    }
+
+    pub fn flagged_without_quorum(&self) -> usize {
+        self.flagged
+            .iter()
+            .filter(|a| !a.quorum_without_flagged)
+            .count()
+    }
+}
+
+// Verify every signature of `record` against the committee as of its approval
+// block and recompute the approval's stake without the flagged signers.
+pub fn audit_approval(
+    record: &ApprovalRecord,
+    timeline: &CommitteeTimeline,
+) -> BridgeResult<ApprovalAudit> {
+    let latest = timeline
+        .latest()
+        .ok_or_else(|| BridgeError::InvalidBridgeCommittee("Committee timeline is empty".into()))?;
+    let snapshot = match record.approval_block {
+        Some(block) => timeline.snapshot_at(block).ok_or_else(|| {
+            BridgeError::InvalidBridgeCommittee(format!(
+                "No committee snapshot covers block {block}"
+            ))
+        })?,
+        None => latest,
+    };
+
+    let mut signers = vec![];
+    let mut seen = BTreeSet::new();
+    let mut stake_at_approval = 0;
+    let mut stake_without_flagged = 0;
+    for sig_bytes in &record.signatures {
+        let Some(pubkey) = recover_signer(&record.message_bytes, sig_bytes) else {
+            signers.push(SignerAudit {
+                pubkey: None,
+                eth_address: None,
+                voting_power_at_approval: 0,
+                flags: vec![SignerFlag::InvalidSignature],
+            });
+            continue;
+        };
+        // The same key signing twice must not be counted twice.
+        if !seen.insert(pubkey.clone()) {
+            continue;
+        }
+
+        let mut flags = vec![];
+        let voting_power_at_approval = match snapshot.member(&pubkey) {
+            None => {
+                flags.push(SignerFlag::NotMemberAtApproval);
+                0
+            }
+            Some(member) if member.blocklisted => {
+                flags.push(SignerFlag::BlocklistedAtApproval);
+                0
+            }
+            Some(member) => member.voting_power,
+        };
+        match latest.member(&pubkey) {
+            None => flags.push(SignerFlag::RotatedOut),
+            Some(member) if member.blocklisted => flags.push(SignerFlag::NowBlocklisted),
+            Some(_) => (),
+        }
+
+        stake_at_approval += voting_power_at_approval;
+        if flags.is_empty() {
+            stake_without_flagged += voting_power_at_approval;
+        }
+        signers.push(SignerAudit {
+            pubkey: Some(Hex::encode(pubkey.as_bytes())),
+            eth_address: Some(pubkey.to_eth_address()),
+            voting_power_at_approval,
+            flags,
+        });
+    }
+
+    Ok(ApprovalAudit {
+        source_chain: record.source_chain,
+        nonce: record.nonce,
+        approval_block: record.approval_block,
+        snapshot_block: snapshot.from_block,
+        signers,
+        approval_threshold: record.approval_threshold,
+        stake_at_approval,
+        stake_without_flagged,
+        quorum_without_flagged: stake_without_flagged >= record.approval_threshold,
+    })
+}
+
+// A transfer field whose value in the originating deposit differs from the
+// value carried by the action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransferFieldMismatch {
+    pub field: &'static str,
+    pub deposit: String,
+    pub action: String,
+}
+
+// Compare an Eth deposit re-derived from its on-chain log with the one carried
+// by the action being executed. Returns every mismatching field.
+pub fn diff_eth_deposit(
+    deposit: &EthToStarcoinTokenBridgeV1,
+    action: &EthToStarcoinTokenBridgeV1,
+) -> Vec<TransferFieldMismatch> {
+    let mut mismatches = vec![];
+    let mut check = |field: &'static str, deposit: String, action: String| {
+        if deposit != action {
+            mismatches.push(TransferFieldMismatch {
+                field,
+                deposit,
+                action,
+            });
+        }
+    };
+    check("nonce", deposit.nonce.to_string(), action.nonce.to_string());
+    check(
+        "eth_chain_id",
+        format!("{:?}", deposit.eth_chain_id),
+        format!("{:?}", action.eth_chain_id),
+    );
+    check(
+        "starcoin_bridge_chain_id",
+        format!("{:?}", deposit.starcoin_bridge_chain_id),
+        format!("{:?}", action.starcoin_bridge_chain_id),
+    );
+    check(
+        "sender",
+        format!("{:?}", deposit.eth_address),
+        format!("{:?}", action.eth_address),
+    );
+    check(
+        "recipient",
+        deposit.starcoin_bridge_address.to_hex_literal(),
+        action.starcoin_bridge_address.to_hex_literal(),
+    );
+    check(
+        "token_id",
+        deposit.token_id.to_string(),
+        action.token_id.to_string(),
+    );
+    check(
+        "amount",
+        deposit.starcoin_bridge_adjusted_amount.to_string(),
+        action.starcoin_bridge_adjusted_amount.to_string(),
+    );
+    mismatches
+}
+
+// Errors with a readable diff if the action does not match its deposit.
+pub fn verify_eth_deposit(
+    deposit: &EthToStarcoinTokenBridgeV1,
+    action: &EthToStarcoinTokenBridgeV1,
+) -> BridgeResult<()> {
+    let mismatches = diff_eth_deposit(deposit, action);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    let diff = mismatches
+        .iter()
+        .map(|m| format!("{}: deposit={} action={}", m.field, m.deposit, m.action))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(BridgeError::TransferMismatch(diff))
+}
+
+// Committee key that produced `sig_bytes` over `message`, or None if the
+// signature is malformed.
+pub fn recover_signer(message: &[u8], sig_bytes: &[u8]) -> Option<BridgeAuthorityPublicKeyBytes> {
+    let sig = BridgeAuthorityRecoverableSignature::from_bytes(sig_bytes).ok()?;
+    let pubkey: BridgeAuthorityPublicKey = sig.recover_with_hash::<Keccak256>(message).ok()?;
+    Some(BridgeAuthorityPublicKeyBytes::from(&pubkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::eth_starcoin_bridge::TokensDepositedFilter;
+    use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+    use crate::test_utils::get_test_starcoin_bridge_to_eth_bridge_action;
+    use crate::types::BridgeAction;
+    use fastcrypto::traits::KeyPair;
+    use starcoin_bridge_types::base_types::StarcoinAddress;
+    use starcoin_bridge_types::bridge::{BridgeChainId, MoveTypeTokenTransferPayload};
+    use starcoin_bridge_types::crypto::get_key_pair;
+    use std::str::FromStr;
+
+    fn keys(n: usize) -> Vec<BridgeAuthorityKeyPair> {
+        (0..n)
+            .map(|_| {
+                let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+                kp
+            })
+            .collect()
+    }
+
+    fn member(kp: &BridgeAuthorityKeyPair, voting_power: u64) -> CommitteeMemberSnapshot {
+        CommitteeMemberSnapshot {
+            pubkey: BridgeAuthorityPublicKeyBytes::from(kp.public()),
+            voting_power,
+            blocklisted: false,
+        }
+    }
+
+    fn record(
+        action: &BridgeAction,
+        signers: &[&BridgeAuthorityKeyPair],
+        approval_block: Option<u64>,
+    ) -> ApprovalRecord {
+        ApprovalRecord {
+            source_chain: action.chain_id() as u8,
+            nonce: action.seq_number(),
+            approval_block,
+            message_bytes: action.to_bytes().unwrap(),
+            approval_threshold: action.approval_threshold(),
+            signatures: signers
+                .iter()
+                .map(|kp| {
+                    BridgeAuthoritySignInfo::new(action, kp)
+                        .signature
+                        .as_bytes()
+                        .to_vec()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_timeline_resolves_snapshot_as_of_block() {
+        let ks = keys(3);
+        let timeline = CommitteeTimeline::new(vec![
+            CommitteeSnapshot {
+                from_block: 100,
+                members: vec![member(&ks[0], 5000), member(&ks[1], 5000)],
+            },
+            CommitteeSnapshot {
+                from_block: 200,
+                members: vec![member(&ks[1], 5000), member(&ks[2], 5000)],
+            },
+        ])
+        .unwrap();
+        assert!(timeline.snapshot_at(99).is_none());
+        assert_eq!(timeline.snapshot_at(100).unwrap().from_block, 100);
+        assert_eq!(timeline.snapshot_at(199).unwrap().from_block, 100);
+        assert_eq!(timeline.snapshot_at(200).unwrap().from_block, 200);
+        assert_eq!(timeline.snapshot_at(u64::MAX).unwrap().from_block, 200);
+        assert_eq!(timeline.latest().unwrap().from_block, 200);
+
+        // duplicated snapshots or members are rejected
+        let mut timeline = timeline;
+        timeline
+            .insert(CommitteeSnapshot {
+                from_block: 200,
+                members: vec![],
+            })
+            .unwrap_err();
+        CommitteeTimeline::new(vec![CommitteeSnapshot {
+            from_block: 0,
+            members: vec![member(&ks[0], 5000), member(&ks[0], 5000)],
+        }])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_audit_clean_approval() {
+        let ks = keys(2);
+        let timeline = CommitteeTimeline::new(vec![CommitteeSnapshot {
+            from_block: 0,
+            members: vec![member(&ks[0], 5000), member(&ks[1], 5000)],
+        }])
+        .unwrap();
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(1),
+            None,
+            None,
+            None,
+            None,
+        );
+        let audit = audit_approval(&record(&action, &[&ks[0]], Some(10)), &timeline).unwrap();
+        assert!(!audit.is_flagged());
+        assert_eq!(audit.stake_at_approval, 5000);
+        assert_eq!(audit.stake_without_flagged, 5000);
+        assert!(audit.quorum_without_flagged);
+    }
+
+    #[test]
+    fn test_audit_now_blocklisted_signer() {
+        let ks = keys(3);
+        let mut blocklisted = member(&ks[0], 3000);
+        blocklisted.blocklisted = true;
+        let timeline = CommitteeTimeline::new(vec![
+            CommitteeSnapshot {
+                from_block: 0,
+                members: vec![
+                    member(&ks[0], 3000),
+                    member(&ks[1], 3000),
+                    member(&ks[2], 4000),
+                ],
+            },
+            CommitteeSnapshot {
+                from_block: 500,
+                members: vec![blocklisted, member(&ks[1], 3000), member(&ks[2], 4000)],
+            },
+        ])
+        .unwrap();
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(7),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Quorum (3334) still holds with ks[2] alone.
+        let audit =
+            audit_approval(&record(&action, &[&ks[0], &ks[2]], Some(100)), &timeline).unwrap();
+        assert!(audit.is_flagged());
+        assert_eq!(audit.snapshot_block, 0);
+        assert_eq!(audit.signers[0].flags, vec![SignerFlag::NowBlocklisted]);
+        assert!(audit.signers[1].flags.is_empty());
+        assert_eq!(audit.stake_at_approval, 7000);
+        assert_eq!(audit.stake_without_flagged, 4000);
+        assert!(audit.quorum_without_flagged);
+
+        // Without ks[0], ks[1] alone (3000) does not reach quorum.
+        let audit =
+            audit_approval(&record(&action, &[&ks[0], &ks[1]], Some(100)), &timeline).unwrap();
+        assert_eq!(audit.stake_without_flagged, 3000);
+        assert!(!audit.quorum_without_flagged);
+
+        // An approval that landed after the blocklisting should not have counted ks[0] at all.
+        let audit =
+            audit_approval(&record(&action, &[&ks[0], &ks[2]], Some(600)), &timeline).unwrap();
+        assert_eq!(audit.snapshot_block, 500);
+        assert_eq!(
+            audit.signers[0].flags,
+            vec![
+                SignerFlag::BlocklistedAtApproval,
+                SignerFlag::NowBlocklisted
+            ]
+        );
+        assert_eq!(audit.signers[0].voting_power_at_approval, 0);
+        assert_eq!(audit.stake_at_approval, 4000);
+    }
+
+    #[test]
+    fn test_audit_rotated_out_and_unknown_signers() {
+        let ks = keys(4);
+        let timeline = CommitteeTimeline::new(vec![
+            CommitteeSnapshot {
+                from_block: 0,
+                members: vec![member(&ks[0], 5000), member(&ks[1], 5000)],
+            },
+            CommitteeSnapshot {
+                from_block: 1000,
+                members: vec![member(&ks[1], 5000), member(&ks[2], 5000)],
+            },
+        ])
+        .unwrap();
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // ks[0] was rotated out; ks[3] was never a member.
+        let audit =
+            audit_approval(&record(&action, &[&ks[0], &ks[3]], Some(10)), &timeline).unwrap();
+        assert_eq!(audit.signers[0].flags, vec![SignerFlag::RotatedOut]);
+        assert_eq!(
+            audit.signers[1].flags,
+            vec![SignerFlag::NotMemberAtApproval, SignerFlag::RotatedOut]
+        );
+        assert_eq!(audit.stake_at_approval, 5000);
+        assert_eq!(audit.stake_without_flagged, 0);
+        assert!(!audit.quorum_without_flagged);
+
+        // Without an approval block the latest snapshot is used.
+        let audit = audit_approval(&record(&action, &[&ks[2]], None), &timeline).unwrap();
+        assert_eq!(audit.snapshot_block, 1000);
+        assert!(!audit.is_flagged());
+
+        // Approval older than the first snapshot can't be resolved.
+        let timeline = CommitteeTimeline::new(vec![CommitteeSnapshot {
+            from_block: 50,
+            members: vec![member(&ks[0], 5000)],
+        }])
+        .unwrap();
+        audit_approval(&record(&action, &[&ks[0]], Some(10)), &timeline).unwrap_err();
+        audit_approval(
+            &record(&action, &[&ks[0]], None),
+            &CommitteeTimeline::default(),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_audit_invalid_and_duplicated_signatures() {
+        let ks = keys(2);
+        let timeline = CommitteeTimeline::new(vec![CommitteeSnapshot {
+            from_block: 0,
+            members: vec![member(&ks[0], 5000), member(&ks[1], 5000)],
+        }])
+        .unwrap();
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(4),
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut rec = record(&action, &[&ks[0], &ks[0]], Some(1));
+        rec.signatures.push(vec![1, 2, 3]);
+        let audit = audit_approval(&rec, &timeline).unwrap();
+        // duplicated signature is counted once
+        assert_eq!(audit.signers.len(), 2);
+        assert_eq!(audit.stake_at_approval, 5000);
+        assert_eq!(audit.signers[1].flags, vec![SignerFlag::InvalidSignature]);
+        assert!(audit.quorum_without_flagged);
+
+        let mut report = SignatureAuditReport::default();
+        report.record(audit);
+        assert_eq!(report.audited, 1);
+        assert_eq!(report.flagged.len(), 1);
+        assert_eq!(report.flagged_without_quorum(), 0);
+    }
+
+    #[test]
+    fn test_parsed_message_bytes_match_action_bytes() {
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(9),
+            None,
+            None,
+            None,
+            None,
+        );
+        let BridgeAction::StarcoinToEthBridgeAction(inner) = &action else {
+            unreachable!()
+        };
+        let message = ParsedTokenTransferMessage {
+            message_version: crate::encoding::TOKEN_TRANSFER_MESSAGE_VERSION,
+            seq_num: 9,
+            source_chain: action.chain_id(),
+            payload: crate::encoding::BridgeMessageEncoding::as_payload_bytes(inner).unwrap(),
+            parsed_payload: MoveTypeTokenTransferPayload {
+                sender_address: inner.starcoin_bridge_event.starcoin_bridge_address.to_vec(),
+                target_chain: inner.starcoin_bridge_event.eth_chain_id as u8,
+                target_address: inner.starcoin_bridge_event.eth_address.as_bytes().to_vec(),
+                token_type: inner.starcoin_bridge_event.token_id,
+                amount: inner.starcoin_bridge_event.amount_starcoin_bridge_adjusted,
+            },
+        };
+        assert_eq!(message.to_message_bytes(), action.to_bytes().unwrap());
+    }
+
+    fn fixture_deposit_log() -> TokensDepositedFilter {
+        TokensDepositedFilter {
+            source_chain_id: BridgeChainId::EthCustom as u8,
+            nonce: 7,
+            destination_chain_id: BridgeChainId::StarcoinCustom as u8,
+            token_id: 2,
+            starcoin_adjusted_amount: 4200000000,
+            sender_address: EthAddress::from_str("0x14dc79964da2c08b23698b3d3cc7ca32193d9955")
+                .unwrap(),
+            // Padded to 32 bytes as emitted by the Solidity contract
+            recipient_address: ethers::types::Bytes::from(
+                Hex::decode("0x000000000000000000000000000000003b1eb23133e94d08d0da9303cfd38e7d")
+                    .unwrap(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_verify_eth_deposit() {
+        let deposit = EthToStarcoinTokenBridgeV1::try_from(&fixture_deposit_log()).unwrap();
+
+        // Happy path
+        let action = deposit.clone();
+        assert!(diff_eth_deposit(&deposit, &action).is_empty());
+        verify_eth_deposit(&deposit, &action).unwrap();
+
+        let assert_mismatch = |action: EthToStarcoinTokenBridgeV1, field: &str| {
+            let mismatches = diff_eth_deposit(&deposit, &action);
+            assert_eq!(mismatches.len(), 1, "{:?}", mismatches);
+            assert_eq!(mismatches[0].field, field);
+            let err = verify_eth_deposit(&deposit, &action).unwrap_err();
+            assert!(matches!(err, BridgeError::TransferMismatch(diff) if diff.starts_with(field)));
+        };
+
+        let mut action = deposit.clone();
+        action.starcoin_bridge_adjusted_amount += 1;
+        assert_mismatch(action, "amount");
+
+        let mut action = deposit.clone();
+        action.starcoin_bridge_address = StarcoinAddress::random_for_testing_only();
+        assert_mismatch(action, "recipient");
+
+        let mut action = deposit.clone();
+        action.token_id = 3;
+        assert_mismatch(action, "token_id");
+
+        let mut action = deposit.clone();
+        action.nonce = 8;
+        assert_mismatch(action, "nonce");
+
+        // All differences are reported at once
+        let mut action = deposit.clone();
+        action.nonce = 8;
+        action.token_id = 3;
+        assert_eq!(diff_eth_deposit(&deposit, &action).len(), 2);
+    }
+}
@@ -14,16 +14,83 @@ use crate::metrics::BridgeMetrics;
 use crate::types::BridgeCommitteeValiditySignInfo;
 use crate::types::{
     BridgeAction, BridgeCommittee, CertifiedBridgeAction, VerifiedCertifiedBridgeAction,
+    BRIDGE_AUTHORITY_TOTAL_VOTING_POWER,
 };
+use serde::Serialize;
 use starcoin_bridge_types::base_types::ConciseableName;
 use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
-const TOTAL_TIMEOUT_MS: u64 = 5_000;
+pub const DEFAULT_SIGNATURE_TIMEOUT: Duration = Duration::from_millis(5_000);
 const RETRY_INTERVAL_MS: u64 = 500;
 
+/// How a signature request to one authority ended
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthoritySignatureOutcome {
+    Responded,
+    /// No signature within the timeout, with the last error if any
+    Timeout(Option<String>),
+    Error(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthoritySignatureResult {
+    pub authority: String,
+    pub voting_power: u64,
+    pub attempts: u32,
+    pub outcome: AuthoritySignatureOutcome,
+}
+
+/// Per-authority results of a signature request, so a request that fails to
+/// reach quorum shows who did not respond and why
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SignatureReport {
+    pub authorities: Vec<AuthoritySignatureResult>,
+}
+
+impl SignatureReport {
+    /// Voting power of the authorities that signed
+    pub fn gathered_voting_power(&self) -> u64 {
+        self.authorities
+            .iter()
+            .filter(|result| result.outcome == AuthoritySignatureOutcome::Responded)
+            .map(|result| result.voting_power)
+            .sum()
+    }
+}
+
+impl fmt::Display for SignatureReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.authorities {
+            write!(
+                f,
+                "  {} (voting power {}, {} attempts): ",
+                result.authority, result.voting_power, result.attempts
+            )?;
+            match &result.outcome {
+                AuthoritySignatureOutcome::Responded => writeln!(f, "responded")?,
+                AuthoritySignatureOutcome::Timeout(None) => writeln!(f, "timeout")?,
+                AuthoritySignatureOutcome::Timeout(Some(e)) => {
+                    writeln!(f, "timeout, last error: {e}")?
+                }
+                AuthoritySignatureOutcome::Error(e) => writeln!(f, "error: {e}")?,
+            }
+        }
+        write!(
+            f,
+            "  voting power gathered: {} / {}",
+            self.gathered_voting_power(),
+            BRIDGE_AUTHORITY_TOTAL_VOTING_POWER
+        )
+    }
+}
+
 pub struct BridgeAuthorityAggregator {
     pub committee: Arc<BridgeCommittee>,
     pub client: Arc<BridgeClient>,
@@ -31,6 +98,8 @@ pub struct BridgeAuthorityAggregator {
     pub metrics: Arc<BridgeMetrics>,
     /// Mapping from committee keys to names for metrics reporting
     pub committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    /// How long to keep asking for a signature
+    pub timeout: Duration,
 }
 
 impl BridgeAuthorityAggregator {
@@ -62,9 +131,15 @@ impl BridgeAuthorityAggregator {
             authority_key,
             metrics,
             committee_keys_to_names,
+            timeout: DEFAULT_SIGNATURE_TIMEOUT,
         }
     }
 
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     #[cfg(test)]
     pub fn new_for_testing(committee: Arc<BridgeCommittee>) -> Self {
         Self::new(
@@ -79,14 +154,52 @@ impl BridgeAuthorityAggregator {
         &self,
         action: BridgeAction,
     ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+        let (result, report) = self
+            .request_committee_signatures_with_report(action.clone())
+            .await;
+        match &result {
+            Ok(_) => debug!("Signatures for {:?}:\n{}", action, report),
+            Err(_) => warn!("Failed to get signatures for {:?}:\n{}", action, report),
+        }
+        result
+    }
+
+    /// Like `request_committee_signatures`, also returning what each authority
+    /// answered, whether or not the request succeeded
+    pub async fn request_committee_signatures_with_report(
+        &self,
+        action: BridgeAction,
+    ) -> (BridgeResult<VerifiedCertifiedBridgeAction>, SignatureReport) {
         let start = std::time::Instant::now();
-        let timeout = Duration::from_millis(TOTAL_TIMEOUT_MS);
         let retry_interval = Duration::from_millis(RETRY_INTERVAL_MS);
-
-        // Retry loop for the single authority
-        while start.elapsed() < timeout {
-            match self.client.request_sign_bridge_action(action.clone()).await {
-                Ok(verified_signed_action) => {
+        let mut result = AuthoritySignatureResult {
+            authority: self
+                .committee_keys_to_names
+                .get(&self.authority_key)
+                .cloned()
+                .unwrap_or_else(|| self.authority_key.concise().to_string()),
+            voting_power: self
+                .committee
+                .members()
+                .get(&self.authority_key)
+                .map(|authority| authority.voting_power)
+                .unwrap_or_default(),
+            attempts: 0,
+            outcome: AuthoritySignatureOutcome::Timeout(None),
+        };
+
+        // Retry loop for the single authority. A request still pending at the
+        // timeout counts as a timeout too.
+        while let Some(remaining) = self.timeout.checked_sub(start.elapsed()) {
+            result.attempts += 1;
+            let response = tokio::time::timeout(
+                remaining,
+                self.client.request_sign_bridge_action(action.clone()),
+            )
+            .await;
+            match response {
+                Err(_) => break,
+                Ok(Ok(verified_signed_action)) => {
                     info!(
                         "Got signature from single authority {}",
                         self.authority_key.concise()
@@ -111,22 +224,38 @@ impl BridgeAuthorityAggregator {
                         .with_label_values(&["single_authority"])
                         .inc();
 
-                    return Ok(verified_certified);
+                    result.outcome = AuthoritySignatureOutcome::Responded;
+                    return (
+                        Ok(verified_certified),
+                        SignatureReport {
+                            authorities: vec![result],
+                        },
+                    );
                 }
-                Err(BridgeError::TxNotFinalized) => {
+                Ok(Err(BridgeError::TxNotFinalized)) => {
                     warn!(
                         "Bridge authority {} observing transaction not yet finalized, retrying in {:?}",
                         self.authority_key.concise(),
                         retry_interval
                     );
+                    result.outcome = AuthoritySignatureOutcome::Timeout(Some(format!(
+                        "{:?}",
+                        BridgeError::TxNotFinalized
+                    )));
                     tokio::time::sleep(retry_interval).await;
                 }
-                Err(e) => {
+                Ok(Err(e)) => {
                     self.metrics
                         .auth_agg_bad_responses
                         .with_label_values(&["single_authority"])
                         .inc();
-                    return Err(e);
+                    result.outcome = AuthoritySignatureOutcome::Error(format!("{:?}", e));
+                    return (
+                        Err(e),
+                        SignatureReport {
+                            authorities: vec![result],
+                        },
+                    );
                 }
             }
         }
@@ -136,11 +265,16 @@ impl BridgeAuthorityAggregator {
             .with_label_values(&["single_authority"])
             .inc();
 
-        Err(BridgeError::TransientProviderError(format!(
-            "Bridge authority {} did not observe finalized transaction after {:?}",
-            self.authority_key.concise(),
-            timeout
-        )))
+        (
+            Err(BridgeError::transient_rpc(format!(
+                "Bridge authority {} did not observe finalized transaction after {:?}",
+                self.authority_key.concise(),
+                self.timeout
+            ))),
+            SignatureReport {
+                authorities: vec![result],
+            },
+        )
     }
 }
 
@@ -153,8 +287,10 @@ mod tests {
         get_test_starcoin_bridge_to_eth_bridge_action, sign_action_with_key,
         TransactionDigestTestExt,
     };
-    use crate::types::BridgeCommittee;
-    use starcoin_bridge_types::bridge::BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER;
+    use crate::types::{BridgeCommittee, TokenPauseAction};
+    use starcoin_bridge_types::bridge::{
+        BridgeChainId, BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, TOKEN_ID_USDC,
+    };
     use starcoin_bridge_types::digests::TransactionDigest;
 
     fn create_single_member_committee() -> (BridgeCommittee, crate::crypto::BridgeAuthorityKeyPair)
@@ -235,14 +371,56 @@ mod tests {
             Ok(sign_action_with_key(&action, &secrets[0])),
             None,
         );
+        let (certified, report) = agg
+            .request_committee_signatures_with_report(action.clone())
+            .await;
+        let certified = certified.unwrap();
+
+        // Verify the certified action
+        assert_eq!(certified.data(), &action);
+        assert_eq!(certified.auth_sig().signatures.len(), 1);
+        assert!(certified
+            .auth_sig()
+            .signatures
+            .contains_key(&authorities[0].pubkey_bytes()));
+        assert_eq!(report.authorities.len(), 1);
+        assert_eq!(report.authorities[0].attempts, 1);
+        assert_eq!(
+            report.authorities[0].outcome,
+            AuthoritySignatureOutcome::Responded
+        );
+        assert_eq!(
+            report.gathered_voting_power(),
+            BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_token_pause() {
+        telemetry_subscribers::init_for_testing();
+
+        let mock = BridgeRequestMockHandler::new();
+        let (_handles, authorities, mut secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER],
+            vec![mock.clone()],
+        );
+        mock.set_signer(secrets.swap_remove(0));
+
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
+        let agg = BridgeAuthorityAggregator::new_for_testing(Arc::new(committee));
+
+        // Governance actions are signed over the token pause route
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 3,
+            chain_id: BridgeChainId::StarcoinCustom,
+            token_id: TOKEN_ID_USDC,
+            pause: true,
+        });
         let certified = agg
             .request_committee_signatures(action.clone())
             .await
             .unwrap();
-
-        // Verify the certified action
         assert_eq!(certified.data(), &action);
-        assert_eq!(certified.auth_sig().signatures.len(), 1);
         assert!(certified
             .auth_sig()
             .signatures
@@ -285,10 +463,70 @@ mod tests {
             Err(BridgeError::RestAPIError("test error".into())),
             None,
         );
-        let err = agg
-            .request_committee_signatures(action.clone())
-            .await
-            .unwrap_err();
-        assert!(matches!(err, BridgeError::RestAPIError(_)));
+        let (result, report) = agg
+            .request_committee_signatures_with_report(action.clone())
+            .await;
+        assert!(matches!(result.unwrap_err(), BridgeError::RestAPIError(_)));
+        assert!(matches!(
+            report.authorities[0].outcome,
+            AuthoritySignatureOutcome::Error(_)
+        ));
+        assert_eq!(report.gathered_voting_power(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_timeout_report() {
+        telemetry_subscribers::init_for_testing();
+
+        let mock = BridgeRequestMockHandler::new();
+        let (_handles, authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER],
+            vec![mock.clone()],
+        );
+
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
+        let agg = BridgeAuthorityAggregator::new_for_testing(Arc::new(committee))
+            .with_timeout(Duration::from_millis(200));
+
+        let starcoin_bridge_tx_digest = TransactionDigest::random();
+        let action = get_test_starcoin_bridge_to_eth_bridge_action(
+            Some(starcoin_bridge_tx_digest),
+            Some(0),
+            Some(0),
+            Some(1000),
+            None,
+            None,
+            None,
+        );
+
+        // The authority answers after the timeout
+        mock.add_starcoin_bridge_event_response(
+            starcoin_bridge_tx_digest,
+            0,
+            Ok(sign_action_with_key(&action, &secrets[0])),
+            Some(Duration::from_secs(5)),
+        );
+        let (result, report) = agg
+            .request_committee_signatures_with_report(action.clone())
+            .await;
+        assert!(matches!(
+            result.unwrap_err(),
+            BridgeError::Rpc {
+                retryable: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            report.authorities[0].outcome,
+            AuthoritySignatureOutcome::Timeout(None)
+        );
+        assert_eq!(
+            report.authorities[0].voting_power,
+            BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER
+        );
+        assert!(report.to_string().ends_with(&format!(
+            "voting power gathered: 0 / {}",
+            BRIDGE_AUTHORITY_TOTAL_VOTING_POWER
+        )));
     }
 }
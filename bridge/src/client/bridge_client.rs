@@ -80,6 +80,13 @@ impl BridgeClient {
                 let type_ = (a.action_type as u8).to_string();
                 format!("sign/emergency_button/{chain_id}/{nonce}/{type_}")
             }
+            BridgeAction::TokenPauseAction(a) => {
+                let chain_id = (a.chain_id as u8).to_string();
+                let nonce = a.nonce.to_string();
+                let token_id = a.token_id.to_string();
+                let pause = if a.pause { "1" } else { "0" };
+                format!("sign/token_pause/{chain_id}/{nonce}/{token_id}/{pause}")
+            }
             BridgeAction::LimitUpdateAction(a) => {
                 let chain_id = (a.chain_id as u8).to_string();
                 let nonce = a.nonce.to_string();
@@ -205,21 +212,32 @@ impl BridgeClient {
             .get(url)
             .header(reqwest::header::ACCEPT, APPLICATION_JSON)
             .send()
-            .await?;
+            .await
+            .map_err(BridgeError::transient_rpc)?;
         if !resp.status().is_success() {
+            let status = resp.status();
             let error_status = format!("{:?}", resp.error_for_status_ref());
-            let resp_text = resp.text().await?;
+            let resp_text = resp.text().await.map_err(BridgeError::transient_rpc)?;
             return match resp_text {
                 text if text.contains(&format!("{:?}", BridgeError::TxNotFinalized)) => {
                     Err(BridgeError::TxNotFinalized)
                 }
+                // The authority found the request itself invalid
+                _ if status.is_client_error()
+                    && status != reqwest::StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    Err(BridgeError::Permanent(format!(
+                        "request_sign_bridge_action rejected with status {:?}: {:?}",
+                        error_status, resp_text
+                    )))
+                }
                 _ => Err(BridgeError::RestAPIError(format!(
                     "request_sign_bridge_action failed with status {:?}: {:?}",
                     error_status, resp_text
                 ))),
             };
         }
-        let signed_bridge_action = resp.json().await?;
+        let signed_bridge_action = resp.json().await.map_err(BridgeError::transient_rpc)?;
         verify_signed_bridge_action(
             &action,
             signed_bridge_action,
@@ -568,6 +586,17 @@ mod tests {
             "sign/emergency_button/2/5/0",
         );
 
+        let action = BridgeAction::TokenPauseAction(crate::types::TokenPauseAction {
+            chain_id: BridgeChainId::StarcoinCustom,
+            nonce: 6,
+            token_id: 3,
+            pause: false,
+        });
+        assert_eq!(
+            BridgeClient::bridge_action_to_path(&action),
+            "sign/token_pause/2/6/3/0",
+        );
+
         let action = BridgeAction::LimitUpdateAction(crate::types::LimitUpdateAction {
             chain_id: BridgeChainId::StarcoinCustom,
             nonce: 10,
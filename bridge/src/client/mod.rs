@@ -3,3 +3,4 @@
 
 pub mod bridge_authority_aggregator;
 pub mod bridge_client;
+pub mod ping;
@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Reachability checks of committee members, shared by the CLI's committee
+// view and the node's `/status` endpoint.
+
+use futures::future::join_all;
+use serde::Serialize;
+
+// Time a committee member has to answer a ping, unless overridden
+pub const DEFAULT_PING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+// Why a ping failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PingError {
+    // The url host did not resolve
+    Dns,
+    // The host resolved but refused or dropped the connection
+    Connect,
+    Timeout,
+    // The node answered with a non-success status
+    HttpStatus,
+    Other,
+}
+
+impl PingError {
+    fn classify(e: &reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return PingError::Timeout;
+        }
+        if !e.is_connect() {
+            return PingError::Other;
+        }
+        // reqwest reports resolver failures as connect errors, the resolver's
+        // own error further down the chain tells them apart
+        let mut source = std::error::Error::source(e);
+        while let Some(err) = source {
+            if err.to_string().starts_with("dns error") {
+                return PingError::Dns;
+            }
+            source = err.source();
+        }
+        PingError::Connect
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PingResult {
+    pub online: bool,
+    // Time until the response arrived, if one did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<PingError>,
+}
+
+// Pings every url concurrently. A url counts as online if it answers with a
+// success status within 10 seconds.
+pub async fn ping_urls(urls: &[String]) -> Vec<bool> {
+    ping_urls_with_timeout(urls, DEFAULT_PING_TIMEOUT)
+        .await
+        .into_iter()
+        .map(|result| result.online)
+        .collect()
+}
+
+// Pings every url concurrently, recording how each one answered within
+// `timeout`.
+pub async fn ping_urls_with_timeout(
+    urls: &[String],
+    timeout: std::time::Duration,
+) -> Vec<PingResult> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .build()
+        .unwrap();
+    join_all(urls.iter().map(|url| async {
+        let start = std::time::Instant::now();
+        match client.get(url).send().await {
+            Ok(resp) => {
+                let online = resp.status().is_success();
+                PingResult {
+                    online,
+                    latency_ms: Some(start.elapsed().as_millis() as u64),
+                    http_status: Some(resp.status().as_u16()),
+                    error: (!online).then_some(PingError::HttpStatus),
+                }
+            }
+            Err(e) => PingResult {
+                online: false,
+                latency_ms: None,
+                http_status: None,
+                error: Some(PingError::classify(&e)),
+            },
+        }
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ping_urls_with_timeout() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // Answers every connection with `status`, or never answers if None
+        async fn serve(status: Option<&'static str>) -> String {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    tokio::spawn(async move {
+                        let mut buf = [0u8; 1024];
+                        let _ = stream.read(&mut buf).await;
+                        match status {
+                            Some(status) => {
+                                let resp = format!(
+                                    "HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                                );
+                                let _ = stream.write_all(resp.as_bytes()).await;
+                            }
+                            None => tokio::time::sleep(std::time::Duration::from_secs(60)).await,
+                        }
+                    });
+                }
+            });
+            url
+        }
+
+        // A port nothing listens on
+        let closed = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let urls = vec![
+            serve(Some("200 OK")).await,
+            serve(Some("500 Internal Server Error")).await,
+            serve(None).await,
+            closed,
+        ];
+        let results = ping_urls_with_timeout(&urls, std::time::Duration::from_millis(500)).await;
+
+        assert!(results[0].online);
+        assert_eq!(results[0].http_status, Some(200));
+        assert!(results[0].latency_ms.is_some());
+        assert_eq!(results[0].error, None);
+
+        assert!(!results[1].online);
+        assert_eq!(results[1].http_status, Some(500));
+        assert_eq!(results[1].error, Some(PingError::HttpStatus));
+
+        assert!(!results[2].online);
+        assert_eq!(results[2].latency_ms, None);
+        assert_eq!(results[2].error, Some(PingError::Timeout));
+
+        assert_eq!(results[3].error, Some(PingError::Connect));
+
+        // Resolving may take a while, so it gets a longer timeout
+        let results = ping_urls_with_timeout(
+            &["http://bridge-node.invalid".to_string()],
+            std::time::Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(results[0].error, Some(PingError::Dns));
+
+        assert_eq!(
+            ping_urls(&urls[..2]).await,
+            vec![true, false],
+            "the boolean view keeps its meaning"
+        );
+    }
+}
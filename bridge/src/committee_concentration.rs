@@ -0,0 +1,522 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decentralization analysis of the bridge committee.
+//!
+//! Committee members are grouped by properties that suggest a shared operator
+//! or shared infrastructure: the derived Eth address, the host and resolved IPs
+//! of the member's url, and the ASN of those IPs when an ASN table is
+//! available. The grouping is pluggable through `GroupingHeuristic`. A group of
+//! two or more members whose combined voting power exceeds the configured share
+//! of the total voting power is reported as a concentration alert.
+
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::metrics::BridgeMetrics;
+use crate::types::{BridgeAuthority, BridgeCommittee, BRIDGE_AUTHORITY_TOTAL_VOTING_POWER};
+use arc_swap::ArcSwap;
+use ethers::types::Address as EthAddress;
+use serde::Serialize;
+use starcoin_bridge_types::base_types::StarcoinAddress;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT: u64 = 33;
+
+const HOST_RESOLUTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+// What the heuristics know about a committee member.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberProfile {
+    pub starcoin_bridge_address: StarcoinAddress,
+    pub eth_address: EthAddress,
+    pub url: String,
+    pub voting_power: u64,
+    // Resolved from the url host. Empty when resolution failed or was skipped.
+    pub ips: Vec<IpAddr>,
+    // ASNs of `ips`, when an ASN table is configured.
+    pub asns: Vec<u32>,
+}
+
+impl MemberProfile {
+    pub fn new(
+        starcoin_bridge_address: StarcoinAddress,
+        eth_address: EthAddress,
+        url: String,
+        voting_power: u64,
+    ) -> Self {
+        Self {
+            starcoin_bridge_address,
+            eth_address,
+            url,
+            voting_power,
+            ips: vec![],
+            asns: vec![],
+        }
+    }
+
+    pub fn from_authority(authority: &BridgeAuthority) -> Self {
+        Self::new(
+            authority.starcoin_bridge_address,
+            BridgeAuthorityPublicKeyBytes::from(&authority.pubkey).to_eth_address(),
+            authority.base_url.clone(),
+            authority.voting_power,
+        )
+    }
+
+    pub fn host(&self) -> Option<String> {
+        let url = url::Url::parse(&self.url).ok()?;
+        url.host_str().map(|host| host.to_ascii_lowercase())
+    }
+
+    fn port(&self) -> u16 {
+        url::Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.port_or_known_default())
+            .unwrap_or(443)
+    }
+}
+
+// Assigns committee members to groups along one dimension. A member may belong
+// to several groups of the same dimension, e.g. one per resolved IP.
+pub trait GroupingHeuristic: Send + Sync {
+    // Name of the dimension, used in reports and as the metric label.
+    fn dimension(&self) -> &'static str;
+    fn group_keys(&self, member: &MemberProfile) -> Vec<String>;
+}
+
+pub struct ByEthAddress;
+
+impl GroupingHeuristic for ByEthAddress {
+    fn dimension(&self) -> &'static str {
+        "eth_address"
+    }
+
+    fn group_keys(&self, member: &MemberProfile) -> Vec<String> {
+        vec![format!("{:?}", member.eth_address)]
+    }
+}
+
+pub struct ByHost;
+
+impl GroupingHeuristic for ByHost {
+    fn dimension(&self) -> &'static str {
+        "host"
+    }
+
+    fn group_keys(&self, member: &MemberProfile) -> Vec<String> {
+        member.host().into_iter().collect()
+    }
+}
+
+pub struct ByIp;
+
+impl GroupingHeuristic for ByIp {
+    fn dimension(&self) -> &'static str {
+        "ip"
+    }
+
+    fn group_keys(&self, member: &MemberProfile) -> Vec<String> {
+        member.ips.iter().map(|ip| ip.to_string()).collect()
+    }
+}
+
+pub struct ByAsn;
+
+impl GroupingHeuristic for ByAsn {
+    fn dimension(&self) -> &'static str {
+        "asn"
+    }
+
+    fn group_keys(&self, member: &MemberProfile) -> Vec<String> {
+        member.asns.iter().map(|asn| format!("AS{}", asn)).collect()
+    }
+}
+
+pub fn default_heuristics() -> Vec<Box<dyn GroupingHeuristic>> {
+    vec![
+        Box::new(ByEthAddress),
+        Box::new(ByHost),
+        Box::new(ByIp),
+        Box::new(ByAsn),
+    ]
+}
+
+// Maps IPs to the ASN announcing them.
+pub trait AsnResolver: Send + Sync {
+    fn asn(&self, ip: IpAddr) -> Option<u32>;
+}
+
+// ASN table loaded from a file with one `<prefix>/<len> <asn>` entry per line,
+// e.g. an export of a routing table. The longest matching prefix wins.
+#[derive(Debug, Default)]
+pub struct AsnTable {
+    entries: Vec<(IpAddr, u8, u32)>,
+}
+
+impl AsnTable {
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut entries = vec![];
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse = || -> Option<(IpAddr, u8, u32)> {
+                let (prefix, asn) = line.split_once(char::is_whitespace)?;
+                let (ip, len) = prefix.split_once('/')?;
+                let ip: IpAddr = ip.parse().ok()?;
+                let len: u8 = len.parse().ok()?;
+                let max_len = if ip.is_ipv4() { 32 } else { 128 };
+                if len > max_len {
+                    return None;
+                }
+                let asn = asn.trim().trim_start_matches("AS").parse().ok()?;
+                Some((ip, len, asn))
+            };
+            let entry = parse().ok_or_else(|| {
+                anyhow::anyhow!("Invalid ASN table entry on line {}: {}", line_no + 1, line)
+            })?;
+            entries.push(entry);
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read ASN table {:?}: {:?}", path, e))?;
+        Self::parse(&content)
+    }
+}
+
+fn prefix_matches(prefix: IpAddr, len: u8, ip: IpAddr) -> bool {
+    match (prefix, ip) {
+        (IpAddr::V4(prefix), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - len as u32).unwrap_or(0);
+            u32::from(prefix) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(prefix), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - len as u32).unwrap_or(0);
+            u128::from(prefix) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+impl AsnResolver for AsnTable {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        self.entries
+            .iter()
+            .filter(|(prefix, len, _)| prefix_matches(*prefix, *len, ip))
+            .max_by_key(|(_, len, _)| *len)
+            .map(|(_, _, asn)| *asn)
+    }
+}
+
+// Resolves the url host of every member, and the ASNs of the resolved IPs when
+// `asn_resolver` is given. Resolution failures leave the fields empty, so the
+// member is only grouped along the remaining dimensions.
+pub async fn resolve_profiles(
+    members: Vec<MemberProfile>,
+    asn_resolver: Option<&dyn AsnResolver>,
+) -> Vec<MemberProfile> {
+    let lookups = members.iter().map(|member| async move {
+        let host = member.host()?;
+        if let Ok(ip) = host.trim_matches(['[', ']']).parse::<IpAddr>() {
+            return Some(vec![ip]);
+        }
+        match tokio::time::timeout(
+            HOST_RESOLUTION_TIMEOUT,
+            tokio::net::lookup_host((host.as_str(), member.port())),
+        )
+        .await
+        {
+            Ok(Ok(addrs)) => Some(addrs.map(|addr| addr.ip()).collect()),
+            Ok(Err(e)) => {
+                warn!("Failed to resolve committee member host {}: {:?}", host, e);
+                None
+            }
+            Err(_) => {
+                warn!("Timed out resolving committee member host {}", host);
+                None
+            }
+        }
+    });
+    let resolved = futures::future::join_all(lookups).await;
+    members
+        .into_iter()
+        .zip(resolved)
+        .map(|(mut member, ips)| {
+            let ips: BTreeSet<IpAddr> = ips.unwrap_or_default().into_iter().collect();
+            member.ips = ips.into_iter().collect();
+            if let Some(resolver) = asn_resolver {
+                let asns: BTreeSet<u32> = member
+                    .ips
+                    .iter()
+                    .filter_map(|ip| resolver.asn(*ip))
+                    .collect();
+                member.asns = asns.into_iter().collect();
+            }
+            member
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConcentrationGroup {
+    pub dimension: String,
+    pub key: String,
+    pub members: Vec<StarcoinAddress>,
+    pub voting_power: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ConcentrationReport {
+    pub threshold_voting_power: u64,
+    // Largest voting power held by a group, per dimension
+    pub max_group_voting_power: BTreeMap<String, u64>,
+    // Groups of two or more members, largest first
+    pub groups: Vec<ConcentrationGroup>,
+    // Groups above `threshold_voting_power`
+    pub alerts: Vec<ConcentrationGroup>,
+}
+
+// Groups `members` with each heuristic. Only groups of two or more members
+// count, since the voting power of a single member is set by governance rather
+// than by shared infrastructure.
+pub fn analyze_concentration(
+    members: &[MemberProfile],
+    heuristics: &[Box<dyn GroupingHeuristic>],
+    max_group_voting_power_percent: u64,
+) -> ConcentrationReport {
+    let threshold_voting_power =
+        BRIDGE_AUTHORITY_TOTAL_VOTING_POWER * max_group_voting_power_percent / 100;
+    let mut report = ConcentrationReport {
+        threshold_voting_power,
+        ..Default::default()
+    };
+    for heuristic in heuristics {
+        let dimension = heuristic.dimension();
+        let mut groups: BTreeMap<String, BTreeMap<StarcoinAddress, u64>> = BTreeMap::new();
+        for member in members {
+            let keys: BTreeSet<String> = heuristic.group_keys(member).into_iter().collect();
+            for key in keys {
+                groups
+                    .entry(key)
+                    .or_default()
+                    .insert(member.starcoin_bridge_address, member.voting_power);
+            }
+        }
+        let mut max_voting_power = 0;
+        for (key, group) in groups {
+            if group.len() < 2 {
+                continue;
+            }
+            let group = ConcentrationGroup {
+                dimension: dimension.to_string(),
+                key,
+                voting_power: group.values().sum(),
+                members: group.into_keys().collect(),
+            };
+            max_voting_power = max_voting_power.max(group.voting_power);
+            if group.voting_power > threshold_voting_power {
+                report.alerts.push(group.clone());
+            }
+            report.groups.push(group);
+        }
+        report
+            .max_group_voting_power
+            .insert(dimension.to_string(), max_voting_power);
+    }
+    report
+        .groups
+        .sort_by(|a, b| b.voting_power.cmp(&a.voting_power));
+    report
+        .alerts
+        .sort_by(|a, b| b.voting_power.cmp(&a.voting_power));
+    report
+}
+
+// Analyzes the active members of `committee`, then logs, exports and publishes
+// the alerts for the health endpoint.
+pub async fn check_committee_concentration(
+    committee: Arc<BridgeCommittee>,
+    max_group_voting_power_percent: u64,
+    asn_resolver: Option<Arc<dyn AsnResolver>>,
+    alerts: Arc<ArcSwap<Vec<ConcentrationGroup>>>,
+    metrics: Arc<BridgeMetrics>,
+) {
+    let members = committee
+        .members()
+        .values()
+        .filter(|member| !member.is_blocklisted)
+        .map(MemberProfile::from_authority)
+        .collect();
+    let members = resolve_profiles(members, asn_resolver.as_deref()).await;
+    let report = analyze_concentration(
+        &members,
+        &default_heuristics(),
+        max_group_voting_power_percent,
+    );
+    for (dimension, voting_power) in &report.max_group_voting_power {
+        metrics
+            .committee_max_group_voting_power
+            .with_label_values(&[dimension.as_str()])
+            .set(*voting_power as i64);
+    }
+    metrics
+        .committee_concentration_alerts
+        .set(report.alerts.len() as i64);
+    for group in &report.alerts {
+        warn!(
+            dimension = group.dimension,
+            key = group.key,
+            voting_power = group.voting_power,
+            threshold = report.threshold_voting_power,
+            "Committee members sharing {} {} hold {} of the voting power: {:?}",
+            group.dimension,
+            group.key,
+            group.voting_power,
+            group.members
+        );
+    }
+    if report.alerts.is_empty() {
+        info!(
+            "Committee concentration check passed: {:?}",
+            report.max_group_voting_power
+        );
+    }
+    alerts.store(Arc::new(report.alerts));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn member(id: u8, eth: u8, url: &str, voting_power: u64) -> MemberProfile {
+        MemberProfile::new(
+            StarcoinAddress::new([id; 16]),
+            EthAddress::repeat_byte(eth),
+            url.to_string(),
+            voting_power,
+        )
+    }
+
+    fn analyze(
+        members: &[MemberProfile],
+        heuristic: impl GroupingHeuristic + 'static,
+    ) -> ConcentrationReport {
+        analyze_concentration(
+            members,
+            &[Box::new(heuristic)],
+            DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT,
+        )
+    }
+
+    #[test]
+    fn test_group_by_eth_address() {
+        let members = vec![
+            member(1, 1, "http://a.example:9191", 2000),
+            member(2, 1, "http://b.example:9191", 2000),
+            member(3, 3, "http://c.example:9191", 6000),
+        ];
+        let report = analyze(&members, ByEthAddress);
+        assert_eq!(report.threshold_voting_power, 3300);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].voting_power, 4000);
+        assert_eq!(
+            report.groups[0].members,
+            vec![StarcoinAddress::new([1; 16]), StarcoinAddress::new([2; 16])]
+        );
+        assert_eq!(report.alerts, report.groups);
+        assert_eq!(report.max_group_voting_power["eth_address"], 4000);
+
+        // The lone large member is not a concentration alert
+        let report = analyze(&members[1..], ByEthAddress);
+        assert!(report.groups.is_empty());
+        assert_eq!(report.max_group_voting_power["eth_address"], 0);
+    }
+
+    #[test]
+    fn test_group_by_host() {
+        let members = vec![
+            member(1, 1, "http://Node.Example:9191", 2000),
+            member(2, 2, "https://node.example:9192/path", 1000),
+            member(3, 3, "http://other.example:9191", 7000),
+        ];
+        let report = analyze(&members, ByHost);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].key, "node.example");
+        assert_eq!(report.groups[0].voting_power, 3000);
+        // Below the 33% threshold
+        assert!(report.alerts.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_ip_and_asn() {
+        let table = AsnTable::parse(
+            "# prefix asn\n10.0.0.0/8 64500\n10.1.0.0/16 AS64501\n192.168.0.0/16 64502\n",
+        )
+        .unwrap();
+        assert_eq!(
+            table.asn(IpAddr::V4(Ipv4Addr::new(10, 2, 0, 1))),
+            Some(64500)
+        );
+        // The longest prefix wins
+        assert_eq!(
+            table.asn(IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1))),
+            Some(64501)
+        );
+        assert_eq!(table.asn(IpAddr::V4(Ipv4Addr::new(172, 16, 0, 1))), None);
+        assert!(AsnTable::parse("10.0.0.0/33 1").is_err());
+
+        let mut a = member(1, 1, "http://a.example", 2500);
+        a.ips = vec![IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1))];
+        a.asns = vec![64501];
+        let mut b = member(2, 2, "http://b.example", 2500);
+        b.ips = vec![
+            IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(10, 1, 0, 2)),
+        ];
+        b.asns = vec![64501];
+        let mut c = member(3, 3, "http://c.example", 2500);
+        c.ips = vec![IpAddr::V4(Ipv4Addr::new(10, 1, 0, 3))];
+        c.asns = vec![64501];
+        let members = vec![a, b, c, member(4, 4, "http://d.example", 2500)];
+
+        let report = analyze(&members, ByIp);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].key, "10.1.0.1");
+        assert_eq!(report.groups[0].voting_power, 5000);
+        assert_eq!(report.alerts.len(), 1);
+
+        let report = analyze(&members, ByAsn);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].key, "AS64501");
+        assert_eq!(report.groups[0].voting_power, 7500);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_profiles_with_ip_hosts() {
+        let table = AsnTable::parse("127.0.0.0/8 64500").unwrap();
+        let members = vec![
+            member(1, 1, "http://127.0.0.1:9191", 4000),
+            member(2, 2, "http://127.0.0.1:9192", 4000),
+            member(3, 3, "not a url", 2000),
+        ];
+        let members = resolve_profiles(members, Some(&table)).await;
+        assert_eq!(members[0].ips, vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+        assert_eq!(members[0].asns, vec![64500]);
+        assert!(members[2].ips.is_empty());
+
+        let report = analyze_concentration(&members, &default_heuristics(), 50);
+        assert_eq!(report.threshold_voting_power, 5000);
+        let dimensions: Vec<_> = report.alerts.iter().map(|g| g.dimension.as_str()).collect();
+        assert_eq!(dimensions, vec!["host", "ip", "asn"]);
+        assert_eq!(report.max_group_voting_power["eth_address"], 0);
+    }
+}
@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::abi::EthBridgeConfig;
+use crate::committee_concentration::DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT;
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::error::BridgeError;
 use crate::eth_client::EthClient;
 use crate::metered_eth_provider::new_metered_eth_provider;
 use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::metrics::BridgeMetrics;
+use crate::secret::{redacted_json, Secret};
 use crate::starcoin_bridge_client::StarcoinBridgeClient;
+use crate::starcoin_bridge_transaction_builder::GasConfig;
+use crate::startup::{finish_steps, timed_step, StepReport};
 use crate::types::{is_route_valid, BridgeAction};
 use crate::utils::get_eth_contract_addresses;
 use anyhow::anyhow;
@@ -20,19 +24,22 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use starcoin_bridge_config::Config;
 use starcoin_bridge_json_rpc_types::Coin;
-use starcoin_bridge_keys::keypair_file::read_key;
+use starcoin_bridge_keys::encrypted;
+use starcoin_bridge_keys::keypair_file::{parse_key, read_key};
 use starcoin_bridge_sdk::apis::CoinReadApi;
 use starcoin_bridge_types::base_types::{ObjectID, ObjectRef, StarcoinAddress};
 use starcoin_bridge_types::bridge::BridgeChainId;
 use starcoin_bridge_types::crypto::{NetworkKeyPair, StarcoinKeyPair};
-use starcoin_bridge_types::digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier};
 use starcoin_bridge_types::event::EventID;
 use std::collections::BTreeMap;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
+
+pub const DEFAULT_METRICS_PORT: u16 = 9184;
+pub const DEFAULT_CLIENT_DB_PATH: &str = "bridge-client-db";
 
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -89,6 +96,72 @@ pub struct StarcoinConfig {
     // Otherwise, it will miss one event because of fullnode Event query semantics.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub starcoin_bridge_module_last_processed_event_id_override: Option<EventID>,
+    // Retries and rate limiting of calls to the fullnode. Defaults apply when
+    // absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_retry: Option<StarcoinRpcRetryConfig>,
+    // Transactions bid the node's reference gas price times this, e.g. 1.1
+    // to pay 10% more for priority. At least 1, defaults to 1. Overrides
+    // `gas.gas-unit-price-multiplier` when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price_multiplier: Option<f64>,
+    // Max gas of the transactions the client submits, by action type, and
+    // their gas price multiplier. Defaults apply when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<GasConfig>,
+}
+
+impl StarcoinConfig {
+    // The `gas` section, with `gas-price-multiplier` applied
+    pub fn gas_config(&self) -> GasConfig {
+        let mut gas_config = self.gas.clone().unwrap_or_default();
+        if let Some(multiplier) = self.gas_price_multiplier {
+            gas_config.gas_unit_price_multiplier = multiplier;
+        }
+        gas_config
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StarcoinRpcRetryConfig {
+    // Attempts per RPC call, including the first one. 1 disables retries.
+    // Only transient failures, e.g. timeouts or HTTP 429 and 5xx, are retried.
+    #[serde(default = "default_rpc_max_attempts")]
+    pub max_attempts: u32,
+    // Delay before the first retry. Each following delay doubles, with
+    // jitter, up to `max-backoff-ms`.
+    #[serde(default = "default_rpc_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_rpc_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    // Max requests per second sent to the fullnode, retries included.
+    // Unlimited when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_second: Option<u32>,
+}
+
+fn default_rpc_max_attempts() -> u32 {
+    4
+}
+
+fn default_rpc_initial_backoff_ms() -> u64 {
+    200
+}
+
+fn default_rpc_max_backoff_ms() -> u64 {
+    5_000
+}
+
+impl Default for StarcoinRpcRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_rpc_max_attempts(),
+            initial_backoff_ms: default_rpc_initial_backoff_ms(),
+            max_backoff_ms: default_rpc_max_backoff_ms(),
+            max_requests_per_second: None,
+        }
+    }
 }
 
 #[serde_as]
@@ -98,6 +171,7 @@ pub struct BridgeNodeConfig {
     // The port that the server listens on.
     pub server_listen_port: u16,
     // The port that for metrics server.
+    #[serde(default = "default_metrics_port")]
     pub metrics_port: u16,
     // Path of the file where bridge authority key (Secp256k1) is stored.
     pub bridge_authority_key_path: PathBuf,
@@ -113,14 +187,45 @@ pub struct BridgeNodeConfig {
     pub starcoin: StarcoinConfig,
     // Eth configuration
     pub eth: EthConfig,
+    // Further EVM chains the bridge is deployed on. They are watched and
+    // signed for like `eth`, which stays the chain the watchdog checks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evm_chains: Vec<EthConfig>,
     // Network key used for metrics pushing
-    #[serde(default = "default_ed25519_key_pair")]
-    pub metrics_key_pair: NetworkKeyPair,
+    #[serde(default = "default_metrics_key_pair")]
+    pub metrics_key_pair: Secret<NetworkKeyPair>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<MetricsConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watchdog_config: Option<WatchdogConfig>,
+
+    // Bounds of the queue between event processing and the action executor.
+    // Defaults apply when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_queue: Option<ActionQueueConfig>,
+
+    // Pre-signed governance actions executed by the node once their time
+    // window opens. Disabled when absent. Requires `run_client`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_actions: Option<ScheduledActionsConfig>,
+    // Alerting on committee members that share an eth address, host, ip or
+    // asn. Defaults apply when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee_concentration: Option<CommitteeConcentrationConfig>,
+    // Whether the server answers `/info` with the node version, features and
+    // config fingerprint. Enabled when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expose_node_info: Option<bool>,
+    // Url the other committee members reach the server at. The registration
+    // check compares it with the url registered on chain, and only checks
+    // that the authority key is a member when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    // Whether to refuse signing requests while the registration doesn't match
+    // the committee. Mismatches are only reported when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refuse_signing_on_registration_mismatch: Option<bool>,
 }
 
 pub fn default_ed25519_key_pair() -> NetworkKeyPair {
@@ -130,6 +235,14 @@ pub fn default_ed25519_key_pair() -> NetworkKeyPair {
     Ed25519KeyPair::from_bytes(&test_key_bytes).expect("Failed to create default Ed25519 keypair")
 }
 
+fn default_metrics_port() -> u16 {
+    DEFAULT_METRICS_PORT
+}
+
+fn default_metrics_key_pair() -> Secret<NetworkKeyPair> {
+    Secret::new(default_ed25519_key_pair())
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct MetricsConfig {
@@ -141,7 +254,7 @@ pub struct MetricsConfig {
     pub auth_username: Option<String>,
     /// Password for Basic Auth (required if auth_username is set)
     #[serde(default)]
-    pub auth_password: String,
+    pub auth_password: Secret<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -151,24 +264,310 @@ pub struct WatchdogConfig {
     pub total_supplies: BTreeMap<String, String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ActionQueueConfig {
+    // Max number of actions buffered for the executor. When full, the event
+    // watchers stop consuming events and their cursors stop advancing.
+    #[serde(default = "default_action_queue_capacity")]
+    pub capacity: usize,
+    // Queue depth considered congested.
+    #[serde(default = "default_action_queue_high_water_mark")]
+    pub high_water_mark: usize,
+    // Warn when the queue stays above `high_water_mark` for this long.
+    #[serde(default = "default_action_queue_high_water_warn_after_secs")]
+    pub high_water_warn_after_secs: u64,
+}
+
+fn default_action_queue_capacity() -> usize {
+    1000
+}
+
+fn default_action_queue_high_water_mark() -> usize {
+    800
+}
+
+fn default_action_queue_high_water_warn_after_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScheduledActionsConfig {
+    // Directory watched for bundle files. Results, quarantined bundles and
+    // the audit log are written here too.
+    pub dir: PathBuf,
+    #[serde(default = "default_scheduled_actions_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_scheduled_actions_poll_interval_secs() -> u64 {
+    10
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeConcentrationConfig {
+    // Alert when members sharing infrastructure together hold more than this
+    // percentage of the total voting power.
+    #[serde(default = "default_max_group_voting_power_percent")]
+    pub max_group_voting_power_percent: u64,
+    // File mapping IP prefixes to ASNs, one `<prefix>/<len> <asn>` per line.
+    // Members are not grouped by ASN when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asn_table_path: Option<PathBuf>,
+}
+
+fn default_max_group_voting_power_percent() -> u64 {
+    DEFAULT_MAX_GROUP_VOTING_POWER_PERCENT
+}
+
+impl Default for CommitteeConcentrationConfig {
+    fn default() -> Self {
+        Self {
+            max_group_voting_power_percent: default_max_group_voting_power_percent(),
+            asn_table_path: None,
+        }
+    }
+}
+
+impl Default for ActionQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_action_queue_capacity(),
+            high_water_mark: default_action_queue_high_water_mark(),
+            high_water_warn_after_secs: default_action_queue_high_water_warn_after_secs(),
+        }
+    }
+}
+
 impl Config for BridgeNodeConfig {}
 
+// A mistake in a node config that shows without connecting to the chains,
+// see `BridgeNodeConfig::validate`. Fields are named as in the config file.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigValidationError {
+    #[error("`{field}` is not a valid http(s) url: {value:?} ({reason})")]
+    InvalidUrl {
+        field: &'static str,
+        value: String,
+        reason: String,
+    },
+    #[error("`{field}` must be a {expected_len} byte hex address, got {value:?}")]
+    InvalidAddress {
+        field: &'static str,
+        value: String,
+        expected_len: usize,
+    },
+    #[error("`{field}` key file {path:?} does not exist")]
+    MissingKeyFile { field: &'static str, path: PathBuf },
+    #[error("`{field}` key file {path:?} is invalid: {reason}")]
+    InvalidKeyFile {
+        field: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+    #[error("`{field}` is required when `run-client` is true")]
+    MissingForClient { field: &'static str },
+    #[error("`starcoin.gas` is invalid: {0}")]
+    InvalidGasConfig(String),
+    #[error("`{field}` {chain_id} is configured more than once")]
+    DuplicateChainId { field: &'static str, chain_id: u8 },
+}
+
+fn check_url(field: &'static str, value: &str) -> Result<(), ConfigValidationError> {
+    let invalid = |reason: String| ConfigValidationError::InvalidUrl {
+        field,
+        value: value.to_string(),
+        reason,
+    };
+    let url = url::Url::parse(value).map_err(|e| invalid(e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!("unsupported scheme {}", url.scheme())));
+    }
+    Ok(())
+}
+
+fn check_address(
+    field: &'static str,
+    value: &str,
+    expected_len: usize,
+) -> Result<(), ConfigValidationError> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    match hex::decode(hex) {
+        Ok(bytes) if bytes.len() == expected_len => Ok(()),
+        _ => Err(ConfigValidationError::InvalidAddress {
+            field,
+            value: value.to_string(),
+            expected_len,
+        }),
+    }
+}
+
+fn check_key_file(
+    field: &'static str,
+    path: &Path,
+    require_secp256k1: bool,
+) -> Result<(), ConfigValidationError> {
+    if !path.is_file() {
+        return Err(ConfigValidationError::MissingKeyFile {
+            field,
+            path: path.to_path_buf(),
+        });
+    }
+    let invalid = |reason: String| ConfigValidationError::InvalidKeyFile {
+        field,
+        path: path.to_path_buf(),
+        reason,
+    };
+    let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    // The scheme of an encrypted key is only known once it is decrypted, which
+    // asks for the passphrase. That is left to startup.
+    if encrypted::is_encrypted(&contents) {
+        return Ok(());
+    }
+    parse_key(&contents, require_secp256k1)
+        .map(|_| ())
+        .map_err(|e| invalid(e.to_string()))
+}
+
 impl BridgeNodeConfig {
-    pub async fn validate(
+    // Checks that need nothing but the config and the key files, so typos fail
+    // on load rather than deep in a syncer. `prepare` does the rest once it is
+    // connected to the chains.
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        check_url(
+            "starcoin.starcoin-bridge-rpc-url",
+            &self.starcoin.starcoin_bridge_rpc_url,
+        )?;
+        check_url("eth.eth-rpc-url", &self.eth.eth_rpc_url)?;
+        if let Some(metrics) = &self.metrics {
+            check_url("metrics.push-url", &metrics.push_url)?;
+        }
+        if let Some(external_url) = &self.external_url {
+            check_url("external-url", external_url)?;
+        }
+        check_address(
+            "starcoin.starcoin-bridge-proxy-address",
+            &self.starcoin.starcoin_bridge_proxy_address,
+            16,
+        )?;
+        check_address(
+            "eth.eth-bridge-proxy-address",
+            &self.eth.eth_bridge_proxy_address,
+            20,
+        )?;
+        let mut eth_chain_ids = HashSet::from([self.eth.eth_bridge_chain_id]);
+        for eth in &self.evm_chains {
+            check_url("evm-chains.eth-rpc-url", &eth.eth_rpc_url)?;
+            check_address(
+                "evm-chains.eth-bridge-proxy-address",
+                &eth.eth_bridge_proxy_address,
+                20,
+            )?;
+            if !eth_chain_ids.insert(eth.eth_bridge_chain_id) {
+                return Err(ConfigValidationError::DuplicateChainId {
+                    field: "evm-chains.eth-bridge-chain-id",
+                    chain_id: eth.eth_bridge_chain_id,
+                });
+            }
+        }
+        // Signatures of the authority key are verified on Eth too
+        check_key_file(
+            "bridge-authority-key-path",
+            &self.bridge_authority_key_path,
+            true,
+        )?;
+        if let Some(path) = &self.starcoin.bridge_client_key_path {
+            check_key_file("starcoin.bridge-client-key-path", path, false)?;
+        }
+        self.starcoin
+            .gas_config()
+            .validate()
+            .map_err(|e| ConfigValidationError::InvalidGasConfig(e.to_string()))?;
+        if self.run_client {
+            // The client key falls back to the authority key, checked above
+            if self.db_path.is_none() {
+                return Err(ConfigValidationError::MissingForClient { field: "db-path" });
+            }
+            if self.eth.eth_contracts_start_block_fallback.is_none() {
+                return Err(ConfigValidationError::MissingForClient {
+                    field: "eth.eth-contracts-start-block-fallback",
+                });
+            }
+            if self
+                .evm_chains
+                .iter()
+                .any(|eth| eth.eth_contracts_start_block_fallback.is_none())
+            {
+                return Err(ConfigValidationError::MissingForClient {
+                    field: "evm-chains.eth-contracts-start-block-fallback",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Start blocks ahead of the given chain heads. These are only warned
+    // about, since the syncers wait for the chains to get there, but are
+    // usually a typo.
+    pub fn future_start_blocks(&self, eth_head: u64, starcoin_head: u64) -> Vec<String> {
+        let eth_start_blocks = [
+            (
+                "eth.eth-contracts-start-block-fallback",
+                self.eth.eth_contracts_start_block_fallback,
+            ),
+            (
+                "eth.eth-contracts-start-block-override",
+                self.eth.eth_contracts_start_block_override,
+            ),
+        ];
+        let mut warnings = eth_start_blocks
+            .into_iter()
+            .filter_map(|(field, block)| {
+                block
+                    .filter(|block| *block > eth_head)
+                    .map(|block| format!("`{field}` {block} is ahead of the Eth head {eth_head}"))
+            })
+            .collect::<Vec<_>>();
+        // EventID is (block_number, event_seq)
+        if let Some((block_number, _)) = self
+            .starcoin
+            .starcoin_bridge_module_last_processed_event_id_override
+        {
+            if block_number > starcoin_head {
+                warnings.push(format!(
+                    "`starcoin.starcoin-bridge-module-last-processed-event-id-override` block {} is ahead of the Starcoin head {}",
+                    block_number, starcoin_head
+                ));
+            }
+        }
+        warnings
+    }
+
+    // The resolved config with every secret replaced by its fingerprint.
+    // Key paths are rendered as-is, their contents are never read.
+    pub fn effective_config(&self) -> anyhow::Result<serde_json::Value> {
+        Ok(redacted_json(self)?)
+    }
+
+    pub async fn prepare(
         &self,
         metrics: Arc<BridgeMetrics>,
     ) -> anyhow::Result<(BridgeServerConfig, Option<BridgeClientConfig>)> {
         info!("Starting config validation");
-        if !is_route_valid(
-            BridgeChainId::try_from(self.starcoin.starcoin_bridge_chain_id)?,
-            BridgeChainId::try_from(self.eth.eth_bridge_chain_id)?,
-        ) {
-            return Err(anyhow!(
-                "Route between Starcoin chain id {} and Eth chain id {} is not valid",
-                self.starcoin.starcoin_bridge_chain_id,
-                self.eth.eth_bridge_chain_id,
-            ));
-        };
+        for eth in self.eth_chains() {
+            if !is_route_valid(
+                BridgeChainId::try_from(self.starcoin.starcoin_bridge_chain_id)?,
+                BridgeChainId::try_from(eth.eth_bridge_chain_id)?,
+            ) {
+                return Err(anyhow!(
+                    "Route between Starcoin chain id {} and Eth chain id {} is not valid",
+                    self.starcoin.starcoin_bridge_chain_id,
+                    eth.eth_bridge_chain_id,
+                ));
+            };
+        }
 
         // Load bridge authority key from file
         // The key must be a Secp256k1 key for bridge operations
@@ -201,13 +600,67 @@ impl BridgeNodeConfig {
         // Use JSON-RPC client to avoid nested tokio runtime issues
         tracing::info!("Creating JSON-RPC Starcoin client");
 
-        let starcoin_bridge_client = Arc::new(StarcoinBridgeClient::with_metrics(
-            &self.starcoin.starcoin_bridge_rpc_url,
-            &self.starcoin.starcoin_bridge_proxy_address,
-            metrics.clone(),
-        ));
+        let gas_config = self.starcoin.gas_config();
+        gas_config
+            .validate()
+            .map_err(|e| anyhow!("Invalid starcoin.gas config: {}", e))?;
+        let starcoin_bridge_client = Arc::new(
+            StarcoinBridgeClient::with_rpc_retry(
+                &self.starcoin.starcoin_bridge_rpc_url,
+                &self.starcoin.starcoin_bridge_proxy_address,
+                &self.starcoin.rpc_retry.clone().unwrap_or_default(),
+                metrics.clone(),
+            )
+            .with_gas_config(gas_config),
+        );
+
+        // Contract discovery on every EVM chain and the Starcoin client account
+        // lookup are independent, so run them concurrently.
+        let (eth_chains, starcoin_account) = tokio::join!(
+            futures::future::join_all(self.eth_chains().map(|eth| {
+                timed_step(
+                    format!(
+                        "eth contract discovery on chain {}",
+                        eth.eth_bridge_chain_id
+                    ),
+                    self.prepare_for_eth(eth, metrics.clone()),
+                )
+            })),
+            timed_step("starcoin client account", async {
+                if !self.run_client {
+                    return Ok(None);
+                }
+                self.prepare_for_starcoin(starcoin_bridge_client.clone(), metrics.clone())
+                    .await
+                    .map(Some)
+            }),
+        );
+        let mut steps: Vec<&dyn StepReport> = eth_chains
+            .iter()
+            .map(|eth| eth as &dyn StepReport)
+            .collect();
+        steps.push(&starcoin_account);
+        finish_steps("Config validation", &steps)?;
+        let eth_chains = eth_chains
+            .into_iter()
+            .map(|eth| eth.into_result())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let (eth_client, eth_contracts) = &eth_chains[0];
 
-        let (eth_client, eth_contracts) = self.prepare_for_eth(metrics.clone()).await?;
+        match tokio::try_join!(
+            eth_client.get_last_finalized_block_id(),
+            starcoin_bridge_client.get_latest_checkpoint_sequence_number(),
+        ) {
+            Ok((eth_head, starcoin_head)) => {
+                for warning in self.future_start_blocks(eth_head, starcoin_head) {
+                    warn!("{}", warning);
+                }
+            }
+            Err(e) => warn!(
+                "Couldn't check start blocks against the chain heads: {:?}",
+                e
+            ),
+        }
 
         // Validate approved actions that must be governace actions
         for action in &self.approved_governance_actions {
@@ -227,54 +680,89 @@ impl BridgeNodeConfig {
             server_listen_port: self.server_listen_port,
             starcoin_bridge_client: starcoin_bridge_client.clone(),
             eth_client: eth_client.clone(),
+            eth_clients: eth_chains
+                .iter()
+                .map(|(eth_client, _)| eth_client.clone())
+                .collect(),
             approved_governance_actions,
         };
         if !self.run_client {
+            // Scheduled actions are submitted with the client key
+            if self.scheduled_actions.is_some() {
+                return Err(anyhow!(
+                    "`scheduled-actions` requires `run-client` to be true"
+                ));
+            }
             return Ok((bridge_server_config, None));
         }
 
         // If client is enabled, prepare client config
-        let (bridge_client_key, client_starcoin_bridge_address, gas_object_ref) = self
-            .prepare_for_starcoin(starcoin_bridge_client.clone(), metrics)
-            .await?;
+        let (bridge_client_key, client_starcoin_bridge_address, gas_object_ref) = starcoin_account
+            .into_result()?
+            .expect("Starcoin client account is loaded when `run_client` is true");
 
         let db_path = self
             .db_path
             .clone()
             .ok_or(anyhow!("`db_path` is required when `run_client` is true"))?;
 
+        let action_queue = self.action_queue.clone().unwrap_or_default();
+        if action_queue.capacity == 0 || action_queue.high_water_mark > action_queue.capacity {
+            return Err(anyhow!(
+                "`action-queue` requires 0 < high-water-mark <= capacity, got {:?}",
+                action_queue
+            ));
+        }
+
+        let eth_chains = self
+            .eth_chains()
+            .zip(eth_chains)
+            .map(|(eth, (eth_client, eth_contracts))| -> anyhow::Result<_> {
+                Ok(EthChainClientConfig {
+                    chain_id: BridgeChainId::try_from(eth.eth_bridge_chain_id)?,
+                    eth_client,
+                    eth_contracts,
+                    // in `prepare_for_eth` we check if this is None when `run_client` is true. Safe to unwrap here.
+                    eth_contracts_start_block_fallback: eth
+                        .eth_contracts_start_block_fallback
+                        .unwrap(),
+                    eth_contracts_start_block_override: eth.eth_contracts_start_block_override,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
         let bridge_client_config = BridgeClientConfig {
             starcoin_bridge_address: client_starcoin_bridge_address,
             key: bridge_client_key,
             gas_object_ref,
             metrics_port: self.metrics_port,
             starcoin_bridge_client: starcoin_bridge_client.clone(),
-            eth_client: eth_client.clone(),
             db_path,
-            eth_contracts,
-            // in `prepare_for_eth` we check if this is None when `run_client` is true. Safe to unwrap here.
-            eth_contracts_start_block_fallback: self
-                .eth
-                .eth_contracts_start_block_fallback
-                .unwrap(),
-            eth_contracts_start_block_override: self.eth.eth_contracts_start_block_override,
+            eth_chains,
             starcoin_bridge_module_last_processed_event_id_override: self
                 .starcoin
                 .starcoin_bridge_module_last_processed_event_id_override,
+            action_queue,
+            scheduled_actions: self.scheduled_actions.clone(),
         };
 
         info!("Config validation complete");
         Ok((bridge_server_config, Some(bridge_client_config)))
     }
 
+    // `eth` followed by `evm-chains`
+    fn eth_chains(&self) -> impl Iterator<Item = &EthConfig> {
+        std::iter::once(&self.eth).chain(&self.evm_chains)
+    }
+
     async fn prepare_for_eth(
         &self,
+        eth: &EthConfig,
         metrics: Arc<BridgeMetrics>,
     ) -> anyhow::Result<(Arc<EthClient<MeteredEthHttpProvier>>, Vec<EthAddress>)> {
         info!("Creating Ethereum client provider");
-        let bridge_proxy_address = EthAddress::from_str(&self.eth.eth_bridge_proxy_address)?;
+        let bridge_proxy_address = EthAddress::from_str(&eth.eth_bridge_proxy_address)?;
         let provider = Arc::new(
-            new_metered_eth_provider(&self.eth.eth_rpc_url, metrics.clone())
+            new_metered_eth_provider(&eth.eth_rpc_url, metrics.clone())
                 .unwrap()
                 .interval(std::time::Duration::from_millis(2000)),
         );
@@ -291,7 +779,7 @@ impl BridgeNodeConfig {
         ) = get_eth_contract_addresses(bridge_proxy_address, &provider).await?;
         let config = EthBridgeConfig::new(config_address, provider.clone());
 
-        if self.run_client && self.eth.eth_contracts_start_block_fallback.is_none() {
+        if self.run_client && eth.eth_contracts_start_block_fallback.is_none() {
             return Err(anyhow!(
                 "eth_contracts_start_block_fallback is required when run_client is true"
             ));
@@ -300,10 +788,10 @@ impl BridgeNodeConfig {
         // If bridge chain id is Eth Mainent or Sepolia, we expect to see chain
         // identifier to match accordingly.
         let bridge_chain_id: u8 = config.chain_id().call().await?;
-        if self.eth.eth_bridge_chain_id != bridge_chain_id {
+        if eth.eth_bridge_chain_id != bridge_chain_id {
             return Err(anyhow!(
                 "Bridge chain id mismatch: expected {}, but connected to {}",
-                self.eth.eth_bridge_chain_id,
+                eth.eth_bridge_chain_id,
                 bridge_chain_id
             ));
         }
@@ -327,7 +815,7 @@ impl BridgeNodeConfig {
 
         let eth_client = Arc::new(
             EthClient::<MeteredEthHttpProvier>::new(
-                &self.eth.eth_rpc_url,
+                &eth.eth_rpc_url,
                 HashSet::from_iter(vec![
                     bridge_proxy_address,
                     committee_address,
@@ -336,7 +824,7 @@ impl BridgeNodeConfig {
                     vault_address,
                 ]),
                 metrics,
-                self.eth.eth_use_latest_block,
+                eth.eth_use_latest_block,
             )
             .await?,
         );
@@ -361,34 +849,15 @@ impl BridgeNodeConfig {
             Some(path) => read_key(path, false),
         }?;
 
-        // If bridge chain id is Starcoin Mainent or Testnet, we expect to see chain
-        // identifier to match accordingly.
-        let starcoin_bridge_identifier = starcoin_bridge_client
-            .get_chain_identifier()
-            .await
-            .map_err(|e| anyhow!("Error getting chain identifier from Starcoin: {:?}", e))?;
-        if self.starcoin.starcoin_bridge_chain_id == BridgeChainId::StarcoinMainnet as u8
-            && starcoin_bridge_identifier != get_mainnet_chain_identifier()
-        {
-            anyhow::bail!(
-                "Expected starcoin chain identifier {}, but connected to {}",
-                self.starcoin.starcoin_bridge_chain_id,
-                starcoin_bridge_identifier
-            );
-        }
-        if self.starcoin.starcoin_bridge_chain_id == BridgeChainId::StarcoinTestnet as u8
-            && starcoin_bridge_identifier != get_testnet_chain_identifier()
-        {
-            anyhow::bail!(
-                "Expected starcoin chain identifier {}, but connected to {}",
-                self.starcoin.starcoin_bridge_chain_id,
-                starcoin_bridge_identifier
-            );
-        }
-        info!(
-            "Connected to Starcoin chain: {}, Bridge chain id: {}",
-            starcoin_bridge_identifier, self.starcoin.starcoin_bridge_chain_id,
-        );
+        // The node must serve the chain the bridge is configured for
+        let bridge_chain_id = BridgeChainId::try_from(self.starcoin.starcoin_bridge_chain_id)
+            .map_err(|_| {
+                anyhow!(
+                    "Unknown starcoin bridge chain id: {}",
+                    self.starcoin.starcoin_bridge_chain_id
+                )
+            })?;
+        starcoin_bridge_client.describe(bridge_chain_id).await?;
 
         // Parse the bridge contract address from config (starcoin_bridge_proxy_address)
         // This is where the Move bridge module is deployed
@@ -438,7 +907,10 @@ pub struct BridgeServerConfig {
     pub eth_bridge_proxy_address: EthAddress,
     pub metrics_port: u16,
     pub starcoin_bridge_client: Arc<StarcoinBridgeClient>,
+    // Client of `eth`
     pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
+    // Clients of `eth` and every `evm-chains` entry, in config order
+    pub eth_clients: Vec<Arc<EthClient<MeteredEthHttpProvier>>>,
     // A list of approved governance actions. Action in this list will be signed when requested by client.
     pub approved_governance_actions: Vec<BridgeAction>,
 }
@@ -449,13 +921,22 @@ pub struct BridgeClientConfig {
     pub gas_object_ref: ObjectRef,
     pub metrics_port: u16,
     pub starcoin_bridge_client: Arc<StarcoinBridgeClient>,
-    pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
     pub db_path: PathBuf,
+    // `eth` and every `evm-chains` entry, in config order
+    pub eth_chains: Vec<EthChainClientConfig>,
+    pub starcoin_bridge_module_last_processed_event_id_override: Option<EventID>,
+    pub action_queue: ActionQueueConfig,
+    pub scheduled_actions: Option<ScheduledActionsConfig>,
+}
+
+// An EVM chain the client watches
+pub struct EthChainClientConfig {
+    pub chain_id: BridgeChainId,
+    pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
     pub eth_contracts: Vec<EthAddress>,
-    // See `BridgeNodeConfig` for the explanation of following two fields.
+    // See `EthConfig` for the explanation of following two fields.
     pub eth_contracts_start_block_fallback: u64,
     pub eth_contracts_start_block_override: Option<u64>,
-    pub starcoin_bridge_module_last_processed_event_id_override: Option<EventID>,
 }
 
 #[serde_as]
@@ -522,3 +1003,582 @@ pub struct EthContractAddresses {
     pub bridge_limiter: EthAddress,
     pub bridge_vault: EthAddress,
 }
+
+// A leaf field that differs between two rendered configs. `None` means the
+// field is absent on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigFieldDiff {
+    pub path: String,
+    pub left: Option<serde_json::Value>,
+    pub right: Option<serde_json::Value>,
+}
+
+// Field-level diff of two rendered configs (see `effective_config`), sorted by
+// dotted path. Arrays are compared element by element.
+pub fn diff_configs(left: &serde_json::Value, right: &serde_json::Value) -> Vec<ConfigFieldDiff> {
+    let mut diffs = vec![];
+    diff_config_values("", Some(left), Some(right), &mut diffs);
+    diffs
+}
+
+fn diff_config_values(
+    path: &str,
+    left: Option<&serde_json::Value>,
+    right: Option<&serde_json::Value>,
+    diffs: &mut Vec<ConfigFieldDiff>,
+) {
+    use serde_json::Value;
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match (left, right) {
+        (Some(Value::Object(l)), Some(Value::Object(r))) => {
+            let keys: std::collections::BTreeSet<&String> = l.keys().chain(r.keys()).collect();
+            for key in keys {
+                diff_config_values(&join(key), l.get(key), r.get(key), diffs);
+            }
+        }
+        (Some(Value::Array(l)), Some(Value::Array(r))) => {
+            for i in 0..l.len().max(r.len()) {
+                diff_config_values(&join(&i.to_string()), l.get(i), r.get(i), diffs);
+            }
+        }
+        (l, r) if l != r => diffs.push(ConfigFieldDiff {
+            path: path.to_string(),
+            left: l.cloned(),
+            right: r.cloned(),
+        }),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::fault_injection::FaultInjector;
+    use crate::starcoin_mock_node::StarcoinMockNode;
+    use crate::types::BridgeActionType;
+    use ethers::abi::Token;
+    use ethers::types::U256;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::EncodeDecodeBase64;
+    use serde_json::json;
+    use starcoin_bridge_types::crypto::get_key_pair;
+    use std::time::Duration;
+
+    fn test_config(auth_password: &str) -> BridgeNodeConfig {
+        BridgeNodeConfig {
+            server_listen_port: 9191,
+            metrics_port: 9184,
+            bridge_authority_key_path: PathBuf::from("/tmp/authority.key"),
+            run_client: false,
+            db_path: None,
+            approved_governance_actions: vec![],
+            starcoin: StarcoinConfig {
+                starcoin_bridge_rpc_url: "http://127.0.0.1:9850".to_string(),
+                starcoin_bridge_proxy_address: "0x246b237c16c761e9478783dd83f7004a".to_string(),
+                starcoin_bridge_chain_id: BridgeChainId::StarcoinCustom as u8,
+                bridge_client_key_path: None,
+                starcoin_bridge_module_last_processed_event_id_override: None,
+                rpc_retry: None,
+                gas_price_multiplier: None,
+                gas: None,
+            },
+            eth: EthConfig {
+                eth_rpc_url: "http://127.0.0.1:8545".to_string(),
+                eth_bridge_proxy_address: "0x0000000000000000000000000000000000000001".to_string(),
+                eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
+                eth_contracts_start_block_fallback: Some(0),
+                eth_contracts_start_block_override: None,
+                eth_use_latest_block: true,
+            },
+            evm_chains: vec![],
+            metrics_key_pair: default_metrics_key_pair(),
+            metrics: Some(MetricsConfig {
+                push_interval_seconds: None,
+                push_url: "http://metrics".to_string(),
+                auth_username: Some("bridge".to_string()),
+                auth_password: Secret::new(auth_password.to_string()),
+            }),
+            watchdog_config: None,
+            action_queue: None,
+            scheduled_actions: None,
+            committee_concentration: None,
+            expose_node_info: None,
+            external_url: None,
+            refuse_signing_on_registration_mismatch: None,
+        }
+    }
+
+    #[test]
+    fn test_effective_config_redacts_secrets() {
+        let config = test_config("hunter2");
+        let rendered = config.effective_config().unwrap();
+        let text = rendered.to_string();
+        assert!(!text.contains("hunter2"));
+
+        let private_key = serde_json::to_value(config.metrics_key_pair.expose_secret()).unwrap();
+        assert!(!text.contains(private_key.to_string().trim_matches('"')));
+
+        let password = rendered["metrics"]["auth-password"].as_str().unwrap();
+        assert!(password.starts_with("<redacted sha256:"));
+        assert_eq!(
+            rendered["eth"]["eth-rpc-url"],
+            json!("http://127.0.0.1:8545")
+        );
+        assert_eq!(
+            rendered["bridge-authority-key-path"],
+            json!("/tmp/authority.key")
+        );
+    }
+
+    #[test]
+    fn test_diff_configs() {
+        let a = test_config("hunter2");
+        let mut b = test_config("hunter3");
+        b.eth.eth_rpc_url = "http://10.0.0.1:8545".to_string();
+        b.db_path = Some(PathBuf::from("/tmp/db"));
+        b.metrics.as_mut().unwrap().push_interval_seconds = Some(30);
+
+        let diffs = diff_configs(
+            &a.effective_config().unwrap(),
+            &b.effective_config().unwrap(),
+        );
+        let paths = diffs.iter().map(|d| d.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "db-path",
+                "eth.eth-rpc-url",
+                "metrics.auth-password",
+                "metrics.push-interval-seconds",
+            ]
+        );
+        assert_eq!(diffs[0].left, None);
+        assert_eq!(diffs[0].right, Some(json!("/tmp/db")));
+        assert_eq!(diffs[1].left, Some(json!("http://127.0.0.1:8545")));
+        // Changed secrets show up as differing fingerprints only
+        let rendered = serde_json::to_string(&diffs).unwrap();
+        assert!(!rendered.contains("hunter2") && !rendered.contains("hunter3"));
+
+        // Identical configs have no diff
+        assert!(diff_configs(
+            &a.effective_config().unwrap(),
+            &test_config("hunter2").effective_config().unwrap()
+        )
+        .is_empty());
+    }
+
+    fn write_key(dir: &Path, name: &str, key: StarcoinKeyPair) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, key.encode_base64()).unwrap();
+        path
+    }
+
+    // A config that passes `validate`, with its keys in `dir`
+    fn valid_config(dir: &Path) -> BridgeNodeConfig {
+        let (_, authority_key): (_, Secp256k1KeyPair) = get_key_pair();
+        let mut config = test_config("hunter2");
+        config.bridge_authority_key_path = write_key(
+            dir,
+            "authority.key",
+            StarcoinKeyPair::Secp256k1(authority_key),
+        );
+        config
+    }
+
+    #[test]
+    fn test_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let validate = |mutate: fn(&mut BridgeNodeConfig)| {
+            let mut config = valid_config(dir.path());
+            mutate(&mut config);
+            config.validate()
+        };
+        validate(|_| ()).unwrap();
+
+        let err = validate(|c| c.eth.eth_rpc_url = "127.0.0.1:8545".to_string()).unwrap_err();
+        assert!(
+            matches!(err, ConfigValidationError::InvalidUrl { field, .. } if field == "eth.eth-rpc-url"),
+            "{err}"
+        );
+        let err =
+            validate(|c| c.starcoin.starcoin_bridge_rpc_url = "your_url".to_string()).unwrap_err();
+        assert!(matches!(err, ConfigValidationError::InvalidUrl { .. }));
+        let err = validate(|c| c.metrics.as_mut().unwrap().push_url = "ftp://metrics".to_string())
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported scheme ftp"), "{err}");
+
+        // An Eth address in place of the Starcoin one
+        let err = validate(|c| {
+            c.starcoin.starcoin_bridge_proxy_address =
+                "0x0000000000000000000000000000000000000001".to_string()
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigValidationError::InvalidAddress {
+                field: "starcoin.starcoin-bridge-proxy-address",
+                value: "0x0000000000000000000000000000000000000001".to_string(),
+                expected_len: 16,
+            }
+        );
+        // A digit short
+        let err = validate(|c| {
+            c.eth.eth_bridge_proxy_address = "0x000000000000000000000000000000000000001".to_string()
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigValidationError::InvalidAddress {
+                expected_len: 20,
+                ..
+            }
+        ));
+
+        let err = validate(|c| c.bridge_authority_key_path = PathBuf::from("/nonexistent.key"))
+            .unwrap_err();
+        assert!(matches!(err, ConfigValidationError::MissingKeyFile { .. }));
+        let err = validate(|c| {
+            c.starcoin.bridge_client_key_path = Some(PathBuf::from("/nonexistent.key"))
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigValidationError::MissingKeyFile {
+                field: "starcoin.bridge-client-key-path",
+                ..
+            }
+        ));
+
+        let err = validate(|c| c.run_client = true).unwrap_err();
+        assert_eq!(
+            err,
+            ConfigValidationError::MissingForClient { field: "db-path" }
+        );
+        let err = validate(|c| {
+            c.run_client = true;
+            c.db_path = Some(PathBuf::from("/tmp/db"));
+            c.eth.eth_contracts_start_block_fallback = None;
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigValidationError::MissingForClient {
+                field: "eth.eth-contracts-start-block-fallback"
+            }
+        );
+        validate(|c| {
+            c.run_client = true;
+            c.db_path = Some(PathBuf::from("/tmp/db"));
+        })
+        .unwrap();
+
+        let err = validate(|c| c.starcoin.gas_price_multiplier = Some(0.5)).unwrap_err();
+        assert!(matches!(err, ConfigValidationError::InvalidGasConfig(_)));
+        let err = validate(|c| {
+            c.starcoin.gas = Some(GasConfig {
+                max_gas_amount: 0,
+                ..Default::default()
+            })
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("max-gas-amount"), "{err}");
+
+        // Further EVM chains are checked like `eth`
+        validate(|c| c.evm_chains = vec![sepolia(&c.eth)]).unwrap();
+        let err = validate(|c| {
+            c.evm_chains = vec![EthConfig {
+                eth_rpc_url: "sepolia".to_string(),
+                ..sepolia(&c.eth)
+            }]
+        })
+        .unwrap_err();
+        assert!(
+            matches!(err, ConfigValidationError::InvalidUrl { field, .. } if field == "evm-chains.eth-rpc-url"),
+            "{err}"
+        );
+        let err = validate(|c| c.evm_chains = vec![sepolia(&c.eth), sepolia(&c.eth)]).unwrap_err();
+        assert_eq!(
+            err,
+            ConfigValidationError::DuplicateChainId {
+                field: "evm-chains.eth-bridge-chain-id",
+                chain_id: BridgeChainId::EthSepolia as u8,
+            }
+        );
+        // `eth` counts too
+        let err = validate(|c| c.evm_chains = vec![c.eth.clone()]).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigValidationError::DuplicateChainId { .. }
+        ));
+        let err = validate(|c| {
+            c.run_client = true;
+            c.db_path = Some(PathBuf::from("/tmp/db"));
+            c.evm_chains = vec![EthConfig {
+                eth_contracts_start_block_fallback: None,
+                ..sepolia(&c.eth)
+            }];
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigValidationError::MissingForClient {
+                field: "evm-chains.eth-contracts-start-block-fallback"
+            }
+        );
+    }
+
+    fn sepolia(eth: &EthConfig) -> EthConfig {
+        EthConfig {
+            eth_rpc_url: "http://127.0.0.1:8546".to_string(),
+            eth_bridge_chain_id: BridgeChainId::EthSepolia as u8,
+            ..eth.clone()
+        }
+    }
+
+    #[test]
+    fn test_starcoin_gas_config() {
+        let mut rendered = serde_json::to_value(test_config("hunter2")).unwrap();
+        rendered["starcoin"]["gas"] = json!({
+            "gas-unit-price-multiplier": 1.2,
+            "gas-budget-overrides": {"TokenTransfer": 2_000_000},
+        });
+        let config: BridgeNodeConfig = serde_json::from_value(rendered.clone()).unwrap();
+        let gas_config = config.starcoin.gas_config();
+        assert_eq!(gas_config.gas_unit_price_multiplier, 1.2);
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::TokenTransfer),
+            2_000_000
+        );
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::TokenPause),
+            GasConfig::default().max_gas_amount
+        );
+
+        // The older top level multiplier wins
+        rendered["starcoin"]["gas-price-multiplier"] = json!(1.5);
+        let config: BridgeNodeConfig = serde_json::from_value(rendered).unwrap();
+        assert_eq!(config.starcoin.gas_config().gas_unit_price_multiplier, 1.5);
+
+        // Defaults without either
+        assert_eq!(
+            test_config("hunter2").starcoin.gas_config(),
+            GasConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_validate_key_schemes() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, ed25519_key): (_, Ed25519KeyPair) = get_key_pair();
+        let ed25519_path = write_key(
+            dir.path(),
+            "ed25519.key",
+            StarcoinKeyPair::Ed25519(ed25519_key),
+        );
+
+        // Any scheme works for the client key, the authority key has to sign
+        // for Eth
+        let mut config = valid_config(dir.path());
+        config.starcoin.bridge_client_key_path = Some(ed25519_path.clone());
+        config.validate().unwrap();
+        config.bridge_authority_key_path = ed25519_path;
+        let err = config.validate().unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConfigValidationError::InvalidKeyFile {
+                    field: "bridge-authority-key-path",
+                    ..
+                }
+            ),
+            "{err}"
+        );
+
+        let garbage_path = dir.path().join("garbage.key");
+        std::fs::write(&garbage_path, "not a key").unwrap();
+        config.bridge_authority_key_path = garbage_path;
+        assert!(matches!(
+            config.validate().unwrap_err(),
+            ConfigValidationError::InvalidKeyFile { .. }
+        ));
+    }
+
+    #[test]
+    fn test_future_start_blocks() {
+        let mut config = test_config("hunter2");
+        config.eth.eth_contracts_start_block_fallback = Some(100);
+        assert!(config.future_start_blocks(100, 10).is_empty());
+
+        config.eth.eth_contracts_start_block_override = Some(200);
+        config
+            .starcoin
+            .starcoin_bridge_module_last_processed_event_id_override = Some((11, 0));
+        let warnings = config.future_start_blocks(150, 10);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("eth-contracts-start-block-override` 200"));
+        assert!(warnings[1].contains("block 11 is ahead of the Starcoin head 10"));
+    }
+
+    // A mock Eth node serving bridge contracts that report `bridge_chain_id`,
+    // and the contracts in the order `prepare` lists them
+    fn mock_eth_chain(
+        bridge_chain_id: BridgeChainId,
+        eth_chain_id: u64,
+        address_byte: u8,
+    ) -> (EthMockProvider, Vec<EthAddress>) {
+        let proxy = EthAddress::repeat_byte(address_byte);
+        let committee = EthAddress::repeat_byte(address_byte + 1);
+        let bridge_config = EthAddress::repeat_byte(address_byte + 2);
+        let limiter = EthAddress::repeat_byte(address_byte + 3);
+        let vault = EthAddress::repeat_byte(address_byte + 4);
+        let eth = EthMockProvider::new();
+        eth.add_method_response("eth_chainId", U256::from(eth_chain_id))
+            .unwrap();
+        eth.add_method_response("eth_blockNumber", U256::one())
+            .unwrap();
+        for (to, signature, output) in [
+            (proxy, "committee()", Token::Address(committee)),
+            (proxy, "limiter()", Token::Address(limiter)),
+            (proxy, "vault()", Token::Address(vault)),
+            (committee, "config()", Token::Address(bridge_config)),
+            (vault, "wETH()", Token::Address(EthAddress::zero())),
+            (
+                bridge_config,
+                "tokenAddressOf(uint8)",
+                Token::Address(EthAddress::zero()),
+            ),
+            (
+                bridge_config,
+                "chainID()",
+                Token::Uint((bridge_chain_id as u8).into()),
+            ),
+        ] {
+            eth.add_call_response(
+                to,
+                ethers::utils::id(signature),
+                ethers::abi::encode(&[output]).into(),
+            );
+        }
+        (eth, vec![proxy, committee, bridge_config, limiter, vault])
+    }
+
+    #[tokio::test]
+    async fn test_prepare_runs_steps_concurrently() {
+        let latency = Duration::from_millis(300);
+        let dir = tempfile::tempdir().unwrap();
+        let (eth, contracts) = mock_eth_chain(BridgeChainId::EthCustom, 31337, 1);
+        // The chain id is read twice, by the discovery and by the Eth client
+        eth.set_fault_injector(
+            FaultInjector::builder(0)
+                .latency("eth_chainId", latency, latency)
+                .build(),
+        );
+        // Chain info is read twice to check the chain, then once more for the
+        // start block check
+        let node = StarcoinMockNode::start().await;
+        node.set_fault_injector(
+            FaultInjector::builder(0)
+                .latency("chain.info", latency, latency)
+                .build(),
+        );
+
+        let mut config = valid_config(dir.path());
+        config.run_client = true;
+        config.db_path = Some(dir.path().join("db"));
+        config.eth.eth_rpc_url = eth.serve().await;
+        config.eth.eth_bridge_proxy_address = format!("{:?}", contracts[0]);
+        config.starcoin.starcoin_bridge_rpc_url = node.url().to_string();
+
+        // Eth discovery and the Starcoin account lookup overlap, so startup
+        // takes 3 latencies rather than 5
+        let start = std::time::Instant::now();
+        let (_, client_config) = config
+            .prepare(Arc::new(BridgeMetrics::new_for_testing()))
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= latency * 3);
+        assert!(elapsed < latency * 4, "prepare took {:?}", elapsed);
+        assert_eq!(
+            client_config.unwrap().eth_chains[0].eth_contracts,
+            contracts
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_multiple_evm_chains() {
+        let dir = tempfile::tempdir().unwrap();
+        let (custom_eth, custom_contracts) = mock_eth_chain(BridgeChainId::EthCustom, 31337, 1);
+        let (sepolia_eth, sepolia_contracts) =
+            mock_eth_chain(BridgeChainId::EthSepolia, 11155111, 0x10);
+        let node = StarcoinMockNode::start().await;
+
+        let mut config = valid_config(dir.path());
+        config.run_client = true;
+        config.db_path = Some(dir.path().join("db"));
+        config.eth.eth_rpc_url = custom_eth.serve().await;
+        config.eth.eth_bridge_proxy_address = format!("{:?}", custom_contracts[0]);
+        config.evm_chains = vec![EthConfig {
+            eth_rpc_url: sepolia_eth.serve().await,
+            eth_bridge_proxy_address: format!("{:?}", sepolia_contracts[0]),
+            eth_contracts_start_block_fallback: Some(7),
+            ..sepolia(&config.eth)
+        }];
+        config.starcoin.starcoin_bridge_rpc_url = node.url().to_string();
+
+        let (server_config, client_config) = config
+            .prepare(Arc::new(BridgeMetrics::new_for_testing()))
+            .await
+            .unwrap();
+        // `eth` stays the primary chain
+        assert_eq!(server_config.eth_bridge_proxy_address, custom_contracts[0]);
+        assert_eq!(server_config.eth_clients.len(), 2);
+        let eth_chains = client_config.unwrap().eth_chains;
+        assert_eq!(
+            eth_chains
+                .iter()
+                .map(|eth| (
+                    eth.chain_id,
+                    eth.eth_contracts.clone(),
+                    eth.eth_contracts_start_block_fallback
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (BridgeChainId::EthCustom, custom_contracts, 0),
+                (BridgeChainId::EthSepolia, sepolia_contracts, 7),
+            ]
+        );
+
+        // Each chain is checked against its own contracts: a proxy whose
+        // contracts report another bridge chain id is refused
+        let (other_eth, other_contracts) = mock_eth_chain(BridgeChainId::EthCustom, 11155111, 0x20);
+        config.evm_chains[0].eth_rpc_url = other_eth.serve().await;
+        config.evm_chains[0].eth_bridge_proxy_address = format!("{:?}", other_contracts[0]);
+        let err = config
+            .prepare(Arc::new(BridgeMetrics::new_for_testing()))
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            format!("{err:?}")
+                .contains("Bridge chain id mismatch: expected 11, but connected to 12"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_metrics_port_default() {
+        let mut rendered = serde_json::to_value(test_config("hunter2")).unwrap();
+        rendered.as_object_mut().unwrap().remove("metrics-port");
+        let config: BridgeNodeConfig = serde_json::from_value(rendered).unwrap();
+        assert_eq!(config.metrics_port, DEFAULT_METRICS_PORT);
+    }
+}
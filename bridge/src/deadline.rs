@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deadlines shared by the nested client calls of one operation.
+//!
+//! Each bounded retry has its own budget (usually 30 seconds), so an operation
+//! built from several of them could run for minutes. A `Deadline` is passed
+//! down instead, and every nested call retries only within what is left of
+//! it. Once it passes, the operation fails with `BridgeError::DeadlineExceeded`
+//! naming the step it was in. Daemon paths use `Deadline::none()`, which keeps
+//! the per call budgets as they are.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::retry_with_max_elapsed_time;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+    // No deadline, calls are only bounded by their own budgets
+    pub fn none() -> Self {
+        Self(None)
+    }
+
+    pub fn after(timeout: Duration) -> Self {
+        Self(Some(Instant::now() + timeout))
+    }
+
+    pub fn from_timeout(timeout: Option<Duration>) -> Self {
+        timeout.map_or_else(Self::none, Self::after)
+    }
+
+    // Time left, or None without a deadline
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    // Retry budget of a nested call: its own budget, capped by what is left
+    pub fn budget(&self, max: Duration) -> Duration {
+        self.remaining().map_or(max, |remaining| remaining.min(max))
+    }
+
+    pub fn check(&self, step: &str) -> BridgeResult<()> {
+        if self.is_expired() {
+            return Err(BridgeError::DeadlineExceeded(step.to_string()));
+        }
+        Ok(())
+    }
+
+    // Runs `fut`, failing with `DeadlineExceeded` for `step` if the deadline
+    // passes first.
+    pub async fn run<T, E>(
+        &self,
+        step: &str,
+        fut: impl Future<Output = Result<T, E>>,
+    ) -> BridgeResult<T>
+    where
+        E: Into<BridgeError>,
+    {
+        self.check(step)?;
+        match self.remaining() {
+            None => fut.await.map_err(Into::into),
+            Some(remaining) => tokio::time::timeout(remaining, fut)
+                .await
+                .map_err(|_| BridgeError::DeadlineExceeded(step.to_string()))?
+                .map_err(Into::into),
+        }
+    }
+}
+
+// Retries `f` for up to `max_elapsed`, or until the deadline passes. Errors
+// that are not retryable end the retries right away. Errors of the last
+// attempt are returned as is, unless the deadline has passed.
+pub async fn retry_within<T, F, Fut>(
+    deadline: Deadline,
+    step: &str,
+    max_elapsed: Duration,
+    f: F,
+) -> BridgeResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = BridgeResult<T>>,
+{
+    // The backoff retries every error, so the ones that are not retryable
+    // are handed to it as a result
+    let attempt = || async {
+        match f().await {
+            Err(e) if !e.is_retryable() => Ok(Err(e)),
+            result => result.map(Ok),
+        }
+    };
+    let result = deadline
+        .run(step, async {
+            match retry_with_max_elapsed_time!(attempt(), deadline.budget(max_elapsed)) {
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) | Err(e) => Err(e),
+            }
+        })
+        .await;
+    match result {
+        Err(e) if deadline.is_expired() && !matches!(e, BridgeError::DeadlineExceeded(_)) => {
+            warn!("Deadline exceeded in {}, last error: {:?}", step, e);
+            Err(BridgeError::DeadlineExceeded(step.to_string()))
+        }
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deadline_budget() {
+        let none = Deadline::none();
+        assert_eq!(none.remaining(), None);
+        assert_eq!(
+            none.budget(Duration::from_secs(30)),
+            Duration::from_secs(30)
+        );
+        none.check("step").unwrap();
+
+        let deadline = Deadline::after(Duration::from_secs(5));
+        assert!(deadline.budget(Duration::from_secs(30)) <= Duration::from_secs(5));
+        assert_eq!(
+            deadline.budget(Duration::from_secs(1)),
+            Duration::from_secs(1)
+        );
+
+        let expired = Deadline::after(Duration::ZERO);
+        assert!(expired.is_expired());
+        assert_eq!(expired.budget(Duration::from_secs(30)), Duration::ZERO);
+        assert_eq!(
+            expired.check("step").unwrap_err(),
+            BridgeError::DeadlineExceeded("step".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_within_stops_at_deadline() {
+        // A call that keeps failing is retried until the deadline, not for the
+        // whole per call budget
+        let deadline = Deadline::after(Duration::from_secs(2));
+        let instant = std::time::Instant::now();
+        let err = retry_within(
+            deadline,
+            "always fails",
+            Duration::from_secs(30),
+            || async { BridgeResult::<()>::Err(BridgeError::transient_rpc("down")) },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err,
+            BridgeError::DeadlineExceeded("always fails".to_string())
+        );
+        assert!(instant.elapsed() < Duration::from_millis(2500));
+
+        // A call that hangs is cut off at the deadline
+        let deadline = Deadline::after(Duration::from_millis(500));
+        let instant = std::time::Instant::now();
+        let err = retry_within(deadline, "hangs", Duration::from_secs(30), || async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            BridgeResult::Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err, BridgeError::DeadlineExceeded("hangs".to_string()));
+        assert!(instant.elapsed() < Duration::from_millis(1000));
+
+        // Without a deadline the error of the last attempt is kept
+        let err = retry_within(
+            Deadline::none(),
+            "always fails",
+            Duration::from_millis(500),
+            || async { BridgeResult::<()>::Err(BridgeError::transient_rpc("down")) },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, BridgeError::transient_rpc("down"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_within_stops_at_permanent_error() {
+        let calls = std::sync::atomic::AtomicU64::new(0);
+        let instant = std::time::Instant::now();
+        let err = retry_within(
+            Deadline::none(),
+            "rejected",
+            Duration::from_secs(30),
+            || async {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                BridgeResult::<()>::Err(BridgeError::Permanent("rejected".to_string()))
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, BridgeError::Permanent("rejected".to_string()));
+        assert_eq!(calls.into_inner(), 1);
+        assert!(instant.elapsed() < Duration::from_millis(400));
+
+        // Uncategorized errors are not retried either
+        let calls = std::sync::atomic::AtomicU64::new(0);
+        let err = retry_within(
+            Deadline::none(),
+            "unsupported",
+            Duration::from_secs(30),
+            || async {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                BridgeResult::<()>::Err(BridgeError::Generic("unsupported".to_string()))
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, BridgeError::Generic("unsupported".to_string()));
+        assert_eq!(calls.into_inner(), 1);
+    }
+}
@@ -4,6 +4,7 @@
 use crate::abi::{eth_starcoin_bridge, EthStarcoinBridge};
 use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
 use crate::crypto::BridgeAuthorityKeyPair;
+use crate::deadline::Deadline;
 use crate::e2e_tests::test_utils::TestClusterWrapperBuilder;
 use crate::e2e_tests::test_utils::{
     get_signatures, initiate_bridge_erc20_to_starcoin, initiate_bridge_eth_to_starcoin,
@@ -14,6 +15,7 @@ use crate::events::{
     StarcoinBridgeEvent, StarcoinToEthTokenBridgeV1, TokenTransferApproved, TokenTransferClaimed,
 };
 use crate::starcoin_bridge_transaction_builder::build_add_tokens_on_starcoin_bridge_transaction;
+use crate::starcoin_bridge_transaction_builder::MAX_GAS_AMOUNT;
 use crate::types::{AddTokensOnEvmAction, BridgeAction};
 use crate::utils::publish_and_register_coins_return_add_coins_on_starcoin_bridge_action;
 use ethers::prelude::*;
@@ -124,7 +126,7 @@ async fn test_bridge_from_eth_to_starcoin_bridge_to_eth() {
     // Test `get_parsed_token_transfer_message`
     let parsed_msg = bridge_test_cluster
         .bridge_client()
-        .get_parsed_token_transfer_message(starcoin_bridge_chain_id, nonce)
+        .get_parsed_token_transfer_message(starcoin_bridge_chain_id, nonce, Deadline::none())
         .await
         .unwrap()
         .unwrap();
@@ -143,7 +145,9 @@ async fn test_bridge_from_eth_to_starcoin_bridge_to_eth() {
     assert_eq!(parsed_msg.parsed_payload.amount, starcoin_bridge_amount);
 
     let message: eth_starcoin_bridge::Message = starcoin_bridge_to_eth_bridge_action.try_into().unwrap();
-    let signatures = get_signatures(bridge_test_cluster.bridge_client(), nonce, starcoin_bridge_chain_id).await;
+    let signatures = get_signatures(bridge_test_cluster.bridge_client(), nonce, starcoin_bridge_chain_id)
+        .await
+        .unwrap();
 
     let eth_starcoin_bridge = EthStarcoinBridge::new(
         bridge_test_cluster.contracts().starcoin_bridge,
@@ -243,6 +247,7 @@ async fn test_add_new_coins_on_starcoin_bridge_and_eth() {
         certified_starcoin_bridge_action,
         bridge_arg,
         1000,
+        MAX_GAS_AMOUNT,
     )
     .unwrap();
 
@@ -6,7 +6,7 @@ use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
 use crate::e2e_tests::test_utils::{
     initiate_bridge_eth_to_starcoin, initiate_bridge_starcoin_bridge_to_eth, BridgeTestClusterBuilder,
 };
-use crate::starcoin_bridge_transaction_builder::build_starcoin_bridge_transaction;
+use crate::starcoin_bridge_transaction_builder::{build_starcoin_bridge_transaction, GasConfig};
 use crate::types::{BridgeAction, EmergencyAction};
 use crate::types::{BridgeActionStatus, EmergencyActionType};
 use ethers::types::Address as EthAddress;
@@ -94,6 +94,7 @@ async fn test_starcoin_bridge_paused() {
             .await,
         &starcoin_bridge_token_type_tags,
         1000,
+        &GasConfig::default(),
     )
     .unwrap();
 
@@ -117,7 +118,8 @@ async fn test_starcoin_bridge_paused() {
             bridge_test_cluster.eth_chain_id() as u8,
             1,
         )
-        .await;
+        .await
+        .unwrap();
     assert_eq!(BridgeActionStatus::NotFound, res);
     // Transfer from Starcoin to eth should fail
     let starcoin_bridge_to_eth_bridge_action = initiate_bridge_starcoin_bridge_to_eth(
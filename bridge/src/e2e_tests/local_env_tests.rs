@@ -696,6 +696,8 @@ async fn test_complete_bridge_flow_eth_to_starcoin_to_eth() {
 
 /// Test: Bridge pause/unpause functionality  
 /// This covers the same scenario as complex.rs::test_starcoin_bridge_paused
+/// The pause and unpause themselves are executed through the CLI by
+/// bridge-cli/tests/local_env_emergency.rs
 #[tokio::test]
 async fn test_bridge_pause_and_transfer_blocking() {
     if !check_anvil().await {
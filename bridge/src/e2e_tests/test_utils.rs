@@ -4,6 +4,7 @@
 use crate::abi::EthBridgeConfig;
 use crate::abi::{EthBridgeCommittee, EthBridgeEvent, EthERC20, EthStarcoinBridge, EthStarcoinBridgeEvents};
 use crate::config::default_ed25519_key_pair;
+use crate::error::BridgeResult;
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::crypto::BridgeAuthoritySignInfo;
@@ -12,6 +13,7 @@ use crate::metrics::BridgeMetrics;
 use crate::server::BridgeNodePublicMetadata;
 use crate::starcoin_bridge_transaction_builder::build_add_tokens_on_starcoin_bridge_transaction;
 use crate::starcoin_bridge_transaction_builder::build_committee_register_transaction;
+use crate::starcoin_bridge_transaction_builder::MAX_GAS_AMOUNT;
 use crate::types::BridgeCommitteeValiditySignInfo;
 use crate::types::CertifiedBridgeAction;
 use crate::types::VerifiedCertifiedBridgeAction;
@@ -68,6 +70,7 @@ use tracing::info;
 
 use crate::config::{BridgeNodeConfig, EthConfig, StarcoinConfig};
 use crate::node::run_bridge_node;
+use crate::secret::Secret;
 use crate::starcoin_bridge_client::StarcoinBridgeClient;
 use anyhow::anyhow;
 use ethers::prelude::*;
@@ -800,10 +803,19 @@ pub(crate) async fn start_bridge_cluster(
                 starcoin_bridge_chain_id: BridgeChainId::StarcoinCustom as u8,
                 bridge_client_key_path: None,
                 starcoin_bridge_module_last_processed_event_id_override: None,
+                rpc_retry: None,
+                gas_price_multiplier: None,
+                gas: None,
             },
-            metrics_key_pair: default_ed25519_key_pair(),
+            metrics_key_pair: Secret::new(default_ed25519_key_pair()),
             metrics: None,
             watchdog_config: None,
+            action_queue: None,
+            scheduled_actions: None,
+            committee_concentration: None,
+            expose_node_info: None,
+            external_url: None,
+            refuse_signing_on_registration_mismatch: None,
         };
         // Spawn bridge node in memory
         handles.push(
@@ -823,15 +835,15 @@ pub async fn get_signatures(
     starcoin_bridge_client: &StarcoinBridgeClient,
     nonce: u64,
     starcoin_bridge_chain_id: u8,
-) -> Vec<Bytes> {
+) -> BridgeResult<Vec<Bytes>> {
     let sigs = starcoin_bridge_client
         .get_token_transfer_action_onchain_signatures_until_success(starcoin_bridge_chain_id, nonce)
-        .await
-        .unwrap();
+        .await?
+        .expect("Approved action should have signatures");
 
-    sigs.into_iter()
+    Ok(sigs.into_iter()
         .map(|sig: Vec<u8>| Bytes::from(sig))
-        .collect()
+        .collect())
 }
 
 pub(crate) async fn send_eth_tx_and_get_tx_receipt<B, M, D>(
@@ -1056,6 +1068,7 @@ impl TestClusterWrapperBuilder {
                 verifired_action_cert,
                 bridge_arg,
                 ref_gas_price,
+                MAX_GAS_AMOUNT,
             )
             .unwrap();
 
@@ -1378,7 +1391,8 @@ async fn wait_for_transfer_action_status(
         let timer = std::time::Instant::now();
         let res = starcoin_bridge_client
             .get_token_transfer_action_onchain_status_until_success(chain_id as u8, nonce)
-            .await;
+            .await
+            .unwrap();
         info!(
             "get_token_transfer_action_onchain_status_until_success took {:?}, status: {:?}",
             timer.elapsed(),
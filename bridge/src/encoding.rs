@@ -11,10 +11,14 @@ use crate::types::EmergencyAction;
 use crate::types::EthToStarcoinBridgeAction;
 use crate::types::EvmContractUpgradeAction;
 use crate::types::LimitUpdateAction;
+use crate::types::ParsedTokenTransferMessage;
 use crate::types::StarcoinToEthBridgeAction;
+use crate::types::TokenPauseAction;
 use anyhow::Result;
 use enum_dispatch::enum_dispatch;
 use ethers::types::Address as EthAddress;
+use fastcrypto::hash::HashFunction;
+use fastcrypto::hash::Keccak256;
 
 // Starcoin uses 16-byte addresses (128-bit), not 32-byte like Sui
 pub const STARCOIN_ADDRESS_LENGTH: usize = 16;
@@ -26,6 +30,7 @@ pub const ASSET_PRICE_UPDATE_MESSAGE_VERSION: u8 = 1;
 pub const EVM_CONTRACT_UPGRADE_MESSAGE_VERSION: u8 = 1;
 pub const ADD_TOKENS_ON_STARCOIN_MESSAGE_VERSION: u8 = 1;
 pub const ADD_TOKENS_ON_EVM_MESSAGE_VERSION: u8 = 1;
+pub const TOKEN_PAUSE_MESSAGE_VERSION: u8 = 1;
 
 pub const BRIDGE_MESSAGE_PREFIX: &[u8] = b"STARCOIN_BRIDGE_MESSAGE";
 
@@ -369,6 +374,29 @@ impl BridgeMessageEncoding for AddTokensOnEvmAction {
     }
 }
 
+impl BridgeMessageEncoding for TokenPauseAction {
+    fn as_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        // Add message type
+        bytes.push(BridgeActionType::TokenPause as u8);
+        // Add message version
+        bytes.push(TOKEN_PAUSE_MESSAGE_VERSION);
+        // Add nonce
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        // Add chain id
+        bytes.push(self.chain_id as u8);
+
+        // Add payload bytes
+        bytes.extend_from_slice(&self.as_payload_bytes()?);
+
+        Ok(bytes)
+    }
+
+    fn as_payload_bytes(&self) -> Result<Vec<u8>> {
+        Ok(vec![self.token_id, self.pause as u8])
+    }
+}
+
 impl BridgeAction {
     // Convert to message bytes to verify in Move and Solidity
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
@@ -379,6 +407,45 @@ impl BridgeAction {
         bytes.extend_from_slice(&self.as_bytes()?);
         Ok(bytes)
     }
+
+    // Everything the committee signs for this action. `encode-action` and the
+    // encoding golden tests both go through here.
+    pub fn encode(&self) -> Result<EncodedBridgeAction> {
+        let message = self.as_bytes()?;
+        let mut signing_payload = BRIDGE_MESSAGE_PREFIX.to_vec();
+        signing_payload.extend_from_slice(&message);
+        let digest = Keccak256::digest(&signing_payload).digest;
+        Ok(EncodedBridgeAction {
+            message,
+            signing_payload,
+            digest,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodedBridgeAction {
+    // Message bytes without the prefix, as parsed by Move and Solidity
+    pub message: Vec<u8>,
+    // `BRIDGE_MESSAGE_PREFIX` followed by the message, the bytes that are signed
+    pub signing_payload: Vec<u8>,
+    // Keccak256 of the signing payload, the same as `BridgeAction::digest`
+    pub digest: [u8; 32],
+}
+
+impl ParsedTokenTransferMessage {
+    // Rebuild the message bytes the committee signed for this on-chain record.
+    // This matches `BridgeAction::to_bytes` of the originating token transfer.
+    pub fn to_message_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(BRIDGE_MESSAGE_PREFIX);
+        bytes.push(BridgeActionType::TokenTransfer as u8);
+        bytes.push(self.message_version);
+        bytes.extend_from_slice(&self.seq_num.to_be_bytes());
+        bytes.push(self.source_chain as u8);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
 }
 
 #[cfg(test)]
@@ -390,6 +457,7 @@ mod tests {
     use crate::events::EmittedStarcoinToEthTokenBridgeV1;
     use crate::test_utils::{StarcoinAddressTestExt, TransactionDigestTestExt}; // Import test traits
     use crate::types::BlocklistType;
+    use crate::types::BridgeActionDigest;
     use crate::types::EmergencyActionType;
     use crate::types::USD_MULTIPLIER;
     use ethers::abi::ParamType;
@@ -636,6 +704,49 @@ mod tests {
         assert_eq!(bytes, expected_bytes("020100000000000000380b01"));
     }
 
+    #[test]
+    fn test_bridge_message_encoding_token_pause_action() {
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 7,
+            chain_id: BridgeChainId::StarcoinCustom,
+            token_id: TOKEN_ID_USDC,
+            pause: true,
+        });
+        let bytes = action.to_bytes().unwrap();
+        // prefix + msg_type(08) + version(01) + nonce(0000000000000007) + chain_id(02) +
+        // token_id(03) + pause(01)
+        assert_eq!(bytes, expected_bytes("08010000000000000007020301"));
+        assert_eq!(
+            action.digest(),
+            BridgeActionDigest::new(
+                Hex::decode("356e4f29fd1439f35d1283ddf070dbcd9096ea82a782459b6fa4e8fb7c05a369")
+                    .unwrap()
+                    .try_into()
+                    .unwrap()
+            )
+        );
+
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 8,
+            chain_id: BridgeChainId::EthSepolia,
+            token_id: TOKEN_ID_USDC,
+            pause: false,
+        });
+        let bytes = action.to_bytes().unwrap();
+        // prefix + msg_type(08) + version(01) + nonce(0000000000000008) + chain_id(0b) +
+        // token_id(03) + pause(00)
+        assert_eq!(bytes, expected_bytes("080100000000000000080b0300"));
+        assert_eq!(
+            action.digest(),
+            BridgeActionDigest::new(
+                Hex::decode("f953f93f006118a7323577b8e9da13f64233ef4b791d19cf4a4867a57ddc3223")
+                    .unwrap()
+                    .try_into()
+                    .unwrap()
+            )
+        );
+    }
+
     #[test]
     fn test_bridge_message_encoding_limit_update_action() {
         let action = BridgeAction::LimitUpdateAction(LimitUpdateAction {
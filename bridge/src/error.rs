@@ -29,12 +29,14 @@ pub enum BridgeError {
     AuthoritySignatureDuplication(String),
     // Too many errors when aggregating authority signatures
     AuthoritySignatureAggregationTooManyError(String),
-    // Transient Ethereum provider error
-    TransientProviderError(String),
-    // Ethereum provider error
-    ProviderError(String),
-    // TokenId is unknown
-    UnknownTokenId(u8),
+    // A call to a Starcoin or Ethereum node failed, and whether repeating it
+    // may succeed
+    Rpc {
+        source: String,
+        retryable: bool,
+    },
+    // TokenId is unknown, along with the known ones
+    UnknownTokenId(u8, Vec<u8>),
     // Invalid BridgeCommittee
     InvalidBridgeCommittee(String),
     // Invalid Bridge authority signature
@@ -51,6 +53,8 @@ pub enum BridgeError {
     MismatchedAuthoritySigner,
     // Signature is over a mismatched action
     MismatchedAction,
+    // Action fields do not match the originating deposit
+    TransferMismatch(String),
     // Action is not a governance action
     ActionIsNotGovernanceAction(BridgeAction),
     // Client requested a non-approved governace action
@@ -61,14 +65,117 @@ pub enum BridgeError {
     ActionIsNotTokenTransferAction,
     // Starcoin transaction failure due to generic error
     StarcoinTxFailureGeneric(String),
+    // Starcoin transaction was included on chain but did not execute, e.g. a
+    // Move abort
+    StarcoinTxFailure {
+        txn_hash: String,
+        status: String,
+        vm_error: Option<String>,
+    },
     // Zero value bridge transfer should not be allowed
     ZeroValueBridgeTransfer(String),
+    // Recipient is the zero address, a bridge contract or otherwise unable to claim
+    InvalidRecipientAddress(String),
+    // Bridging of this token is paused by governance
+    TokenPaused(u8),
+    // Event cursor was issued for a different filter than the query's
+    CursorFilterMismatch(String),
+    // The operation's deadline passed while in the given step
+    DeadlineExceeded(String),
     // Storage Error
     StorageError(String),
     // Rest API Error
     RestAPIError(String),
+    // The node refuses to sign until its committee registration is fixed
+    SigningRefused(String),
+    // Failure that repeating the operation won't fix
+    Permanent(String),
     // Uncategorized error
     Generic(String),
 }
 
+impl BridgeError {
+    pub fn transient_rpc(source: impl std::fmt::Display) -> Self {
+        Self::Rpc {
+            source: source.to_string(),
+            retryable: true,
+        }
+    }
+
+    pub fn permanent_rpc(source: impl std::fmt::Display) -> Self {
+        Self::Rpc {
+            source: source.to_string(),
+            retryable: false,
+        }
+    }
+
+    // Whether an operation that failed with this error may succeed when
+    // repeated, given the time. Retry loops give up right away on errors that
+    // are not. Uncategorized errors are assumed to be permanent, transient
+    // failures are raised with `transient_rpc`.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BridgeError::Rpc { retryable, .. } => *retryable,
+            BridgeError::TxNotFound
+            | BridgeError::TxNotFinalized
+            | BridgeError::AuthoritySignatureAggregationTooManyError(_)
+            | BridgeError::StorageError(_)
+            | BridgeError::RestAPIError(_)
+            | BridgeError::SigningRefused(_)
+            | BridgeError::DeadlineExceeded(_) => true,
+            BridgeError::InvalidTxHash
+            | BridgeError::OriginTxFailed
+            | BridgeError::NoBridgeEventsInTxPosition
+            | BridgeError::BridgeEventInUnrecognizedEthContract
+            | BridgeError::BridgeEventInUnrecognizedStarcoinPackage
+            | BridgeError::BridgeEventNotActionable
+            | BridgeError::BridgeSerializationError(_)
+            | BridgeError::AuthoritySignatureDuplication(_)
+            | BridgeError::UnknownTokenId(..)
+            | BridgeError::InvalidBridgeCommittee(_)
+            | BridgeError::InvalidBridgeAuthoritySignature(_)
+            | BridgeError::InvalidBridgeAuthority(_)
+            | BridgeError::InvalidAuthorityUrl(_)
+            | BridgeError::InvalidBridgeClientRequest(_)
+            | BridgeError::InvalidChainId
+            | BridgeError::MismatchedAuthoritySigner
+            | BridgeError::MismatchedAction
+            | BridgeError::TransferMismatch(_)
+            | BridgeError::ActionIsNotGovernanceAction(_)
+            | BridgeError::GovernanceActionIsNotApproved
+            | BridgeError::AuthoirtyUrlInvalid
+            | BridgeError::ActionIsNotTokenTransferAction
+            | BridgeError::StarcoinTxFailure { .. }
+            | BridgeError::ZeroValueBridgeTransfer(_)
+            | BridgeError::InvalidRecipientAddress(_)
+            | BridgeError::TokenPaused(_)
+            | BridgeError::CursorFilterMismatch(_)
+            | BridgeError::InternalError(_)
+            | BridgeError::StarcoinTxFailureGeneric(_)
+            | BridgeError::Permanent(_)
+            | BridgeError::Generic(_) => false,
+        }
+    }
+
+    // Whether the error is about the request itself rather than what the node
+    // saw while handling it. Only these are answered as client errors: an
+    // error from the node's own RPCs, e.g. a provider that lags behind or is
+    // on another fork, may go away and must not make requesters give up.
+    pub fn is_request_error(&self) -> bool {
+        matches!(
+            self,
+            BridgeError::InvalidTxHash
+                | BridgeError::NoBridgeEventsInTxPosition
+                | BridgeError::BridgeEventInUnrecognizedEthContract
+                | BridgeError::BridgeEventInUnrecognizedStarcoinPackage
+                | BridgeError::BridgeEventNotActionable
+                | BridgeError::InvalidBridgeClientRequest(_)
+                | BridgeError::InvalidChainId
+                | BridgeError::ActionIsNotGovernanceAction(_)
+                | BridgeError::GovernanceActionIsNotApproved
+                | BridgeError::ActionIsNotTokenTransferAction
+        )
+    }
+}
+
 pub type BridgeResult<T> = Result<T, BridgeError>;
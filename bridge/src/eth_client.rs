@@ -1,17 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::abi::EthBridgeEvent;
+use crate::abi::{EthBridgeEvent, EthToStarcoinTokenBridgeV1};
 use crate::error::{BridgeError, BridgeResult};
 use crate::metered_eth_provider::{new_metered_eth_provider, MeteredEthHttpProvier};
 use crate::metrics::BridgeMetrics;
 use crate::types::{BridgeAction, EthLog, RawEthLog};
+use async_trait::async_trait;
 use ethers::providers::{JsonRpcClient, Middleware, Provider};
 use ethers::types::TxHash;
 use ethers::types::{Block, Filter};
+use starcoin_bridge_types::bridge::BridgeChainId;
 use tap::TapFallible;
 
 #[cfg(test)]
@@ -61,10 +63,58 @@ impl EthClient<EthMockProvider> {
     }
 }
 
+// Source of finalized Eth deposits, used to re-check an action against the
+// deposit it claims to be derived from before executing it.
+#[async_trait]
+pub trait EthDepositSource: Send + Sync {
+    async fn get_finalized_eth_deposit(
+        &self,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<EthToStarcoinTokenBridgeV1>;
+}
+
+// Deposit sources keyed by the EVM chain they read from
+pub type EthDepositSources = HashMap<BridgeChainId, Arc<dyn EthDepositSource>>;
+
+#[async_trait]
+impl<P> EthDepositSource for EthClient<P>
+where
+    P: JsonRpcClient + 'static,
+{
+    async fn get_finalized_eth_deposit(
+        &self,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<EthToStarcoinTokenBridgeV1> {
+        match self
+            .get_finalized_bridge_action_maybe(tx_hash, event_idx)
+            .await?
+        {
+            BridgeAction::EthToStarcoinBridgeAction(action) => Ok(action.eth_bridge_event),
+            _ => Err(BridgeError::BridgeEventNotActionable),
+        }
+    }
+}
+
 impl<P> EthClient<P>
 where
     P: JsonRpcClient,
 {
+    // A client over any transport, e.g. a fake one in tests of crates that
+    // drive an `EthSyncer`
+    pub fn with_provider(
+        provider: P,
+        contract_addresses: HashSet<EthAddress>,
+        use_latest_block: bool,
+    ) -> Self {
+        Self {
+            provider: Provider::new(provider),
+            contract_addresses,
+            use_latest_block,
+        }
+    }
+
     pub async fn get_chain_id(&self) -> Result<u64, anyhow::Error> {
         let chain_id = self.provider.get_chainid().await?;
         Ok(chain_id.as_u64())
@@ -83,6 +133,11 @@ where
     // Returns BridgeAction from an Eth Transaction with transaction hash
     // and the event index. If event is declared in an unrecognized
     // contract, return error.
+    //
+    // Answers of the provider that don't add up, e.g. a receipt without a
+    // block number, are transient errors here and below: a provider that lags
+    // behind, a load balancer switching backends or a reorg all produce them,
+    // and asking again later gets a consistent answer.
     pub async fn get_finalized_bridge_action_maybe(
         &self,
         tx_hash: TxHash,
@@ -92,10 +147,10 @@ where
             .provider
             .get_transaction_receipt(tx_hash)
             .await
-            .map_err(BridgeError::from)?
+            .map_err(BridgeError::transient_rpc)?
             .ok_or(BridgeError::TxNotFound)?;
-        let receipt_block_num = receipt.block_number.ok_or(BridgeError::ProviderError(
-            "Provider returns log without block_number".into(),
+        let receipt_block_num = receipt.block_number.ok_or(BridgeError::transient_rpc(
+            "Provider returns log without block_number",
         ))?;
         // TODO: save the latest finalized block id so we don't have to query it every time
         let last_finalized_block_id = self.get_last_finalized_block_id().await?;
@@ -136,11 +191,14 @@ where
             self.provider
                 .request("eth_getBlockByNumber", (block_tag, false))
                 .await;
-        let block = block?.ok_or(BridgeError::TransientProviderError(
-            "Provider fails to return last finalized block".into(),
-        ))?;
-        let number = block.number.ok_or(BridgeError::TransientProviderError(
-            "Provider returns block without number".into(),
+        let block =
+            block
+                .map_err(BridgeError::transient_rpc)?
+                .ok_or(BridgeError::transient_rpc(
+                    "Provider fails to return last finalized block",
+                ))?;
+        let number = block.number.ok_or(BridgeError::transient_rpc(
+            "Provider returns block without number",
         ))?;
         Ok(number.as_u64())
     }
@@ -162,7 +220,7 @@ where
             // TODO use get_logs_paginated?
             .get_logs(&filter)
             .await
-            .map_err(BridgeError::from)
+            .map_err(BridgeError::transient_rpc)
             .tap_err(|e| {
                 tracing::error!(
                     "get_events_in_range failed. Filter: {:?}. Error {:?}",
@@ -173,7 +231,7 @@ where
 
         // Safeguard check that all events are emitted from requested contract address
         if logs.iter().any(|log| log.address != address) {
-            return Err(BridgeError::ProviderError(format!(
+            return Err(BridgeError::transient_rpc(format!(
                 "Provider returns logs from different contract address (expected: {:?}): {:?}",
                 address, logs
             )));
@@ -212,7 +270,7 @@ where
             .provider
             .get_logs(&filter)
             .await
-            .map_err(BridgeError::from)
+            .map_err(BridgeError::transient_rpc)
             .tap_err(|e| {
                 tracing::error!(
                     "get_events_in_range failed. Filter: {:?}. Error {:?}",
@@ -224,11 +282,11 @@ where
         logs.into_iter().map(
             |log| {
                 if !addresses.contains(&log.address) {
-                    return Err(BridgeError::ProviderError(format!("Provider returns logs from different contract address (expected: {:?}): {:?}", addresses, log)));
+                    return Err(BridgeError::transient_rpc(format!("Provider returns logs from different contract address (expected: {:?}): {:?}", addresses, log)));
                 }
                 Ok(RawEthLog {
-                block_number: log.block_number.ok_or(BridgeError::ProviderError("Provider returns log without block_number".into()))?.as_u64(),
-                tx_hash: log.transaction_hash.ok_or(BridgeError::ProviderError("Provider returns log without transaction_hash".into()))?,
+                block_number: log.block_number.ok_or(BridgeError::transient_rpc("Provider returns log without block_number"))?.as_u64(),
+                tx_hash: log.transaction_hash.ok_or(BridgeError::transient_rpc("Provider returns log without transaction_hash"))?,
                 log,
             })}
         ).collect::<Result<Vec<_>, _>>()
@@ -240,16 +298,16 @@ where
     async fn get_log_tx_details(&self, log: ethers::types::Log) -> BridgeResult<EthLog> {
         let block_number = log
             .block_number
-            .ok_or(BridgeError::ProviderError(
-                "Provider returns log without block_number".into(),
+            .ok_or(BridgeError::transient_rpc(
+                "Provider returns log without block_number",
             ))?
             .as_u64();
-        let tx_hash = log.transaction_hash.ok_or(BridgeError::ProviderError(
-            "Provider returns log without transaction_hash".into(),
+        let tx_hash = log.transaction_hash.ok_or(BridgeError::transient_rpc(
+            "Provider returns log without transaction_hash",
         ))?;
         // This is the log index in the block, rather than transaction.
-        let log_index = log.log_index.ok_or(BridgeError::ProviderError(
-            "Provider returns log without log_index".into(),
+        let log_index = log.log_index.ok_or(BridgeError::transient_rpc(
+            "Provider returns log without log_index",
         ))?;
 
         // Now get the log's index in the transaction. There is `transaction_log_index` field in
@@ -259,17 +317,17 @@ where
             .provider
             .get_transaction_receipt(tx_hash)
             .await
-            .map_err(BridgeError::from)?
-            .ok_or(BridgeError::ProviderError(format!(
+            .map_err(BridgeError::transient_rpc)?
+            .ok_or(BridgeError::transient_rpc(format!(
                 "Provide cannot find eth transaction for log: {:?})",
                 log
             )))?;
 
-        let receipt_block_num = receipt.block_number.ok_or(BridgeError::ProviderError(
-            "Provider returns log without block_number".into(),
+        let receipt_block_num = receipt.block_number.ok_or(BridgeError::transient_rpc(
+            "Provider returns log without block_number",
         ))?;
         if receipt_block_num.as_u64() != block_number {
-            return Err(BridgeError::ProviderError(format!("Provider returns receipt with different block number from log. Receipt: {:?}, Log: {:?}", receipt, log)));
+            return Err(BridgeError::transient_rpc(format!("Provider returns receipt with different block number from log. Receipt: {:?}, Log: {:?}", receipt, log)));
         }
 
         // Find the log index in the transaction
@@ -279,12 +337,12 @@ where
             if receipt_log.log_index == Some(log_index) {
                 // make sure the topics and data match
                 if receipt_log.topics != log.topics || receipt_log.data != log.data {
-                    return Err(BridgeError::ProviderError(format!("Provider returns receipt with different log from log. Receipt: {:?}, Log: {:?}", receipt, log)));
+                    return Err(BridgeError::transient_rpc(format!("Provider returns receipt with different log from log. Receipt: {:?}, Log: {:?}", receipt, log)));
                 }
                 log_index_in_tx = Some(idx);
             }
         }
-        let log_index_in_tx = log_index_in_tx.ok_or(BridgeError::ProviderError(format!(
+        let log_index_in_tx = log_index_in_tx.ok_or(BridgeError::transient_rpc(format!(
             "Couldn't find matching log: {:?} in transaction {}",
             log, tx_hash
         )))?;
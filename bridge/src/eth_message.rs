@@ -0,0 +1,259 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The message a Starcoin -> Eth transfer is claimed with on Eth.
+//!
+//! `BridgeUtils.computeHash` hashes `STARCOIN_BRIDGE_MESSAGE`, the message
+//! type, version, nonce (8 bytes big endian) and source chain, followed by the
+//! payload, and the contract checks the committee signatures against that
+//! hash. A field that doesn't match the signed bytes fails the claim with
+//! nothing but a signature error, so the message is built field by field here
+//! and pinned by `fixtures/eth_token_transfer_messages.json`.
+
+use crate::abi::eth_starcoin_bridge;
+use crate::encoding::{BRIDGE_MESSAGE_PREFIX, STARCOIN_ADDRESS_LENGTH};
+use crate::types::{BridgeActionType, ParsedTokenTransferMessage};
+use fastcrypto::hash::{HashFunction, Keccak256};
+use starcoin_bridge_types::bridge::MoveTypeTokenTransferPayload;
+
+// The committee signs the payload of the deposit event, whose sender is a
+// 16-byte Starcoin address. The same address left-padded to 32 bytes, as
+// Move may render it, is cut back to those 16 bytes: the contract rejects any
+// other sender length.
+fn starcoin_sender_bytes(sender_address: &[u8]) -> &[u8] {
+    if sender_address.len() == 2 * STARCOIN_ADDRESS_LENGTH {
+        let (padding, address) = sender_address.split_at(STARCOIN_ADDRESS_LENGTH);
+        if padding.iter().all(|b| *b == 0) {
+            return address;
+        }
+    }
+    sender_address
+}
+
+// Payload bytes as `BridgeUtils.decodeTokenTransferPayload` reads them
+pub fn token_transfer_payload_bytes(payload: &MoveTypeTokenTransferPayload) -> Vec<u8> {
+    let sender_address = starcoin_sender_bytes(&payload.sender_address);
+    let mut bytes = Vec::new();
+    bytes.push(sender_address.len() as u8);
+    bytes.extend_from_slice(sender_address);
+    bytes.push(payload.target_chain);
+    bytes.push(payload.target_address.len() as u8);
+    bytes.extend_from_slice(&payload.target_address);
+    bytes.push(payload.token_type);
+    bytes.extend_from_slice(&payload.amount.to_be_bytes());
+    bytes
+}
+
+// The payload is rebuilt from the parsed fields rather than copied from the
+// record, so a sender stored padded still hashes to what the committee signed
+impl From<ParsedTokenTransferMessage> for eth_starcoin_bridge::Message {
+    fn from(parsed_message: ParsedTokenTransferMessage) -> Self {
+        eth_starcoin_bridge::Message {
+            message_type: BridgeActionType::TokenTransfer as u8,
+            version: parsed_message.message_version,
+            nonce: parsed_message.seq_num,
+            chain_id: parsed_message.source_chain as u8,
+            payload: token_transfer_payload_bytes(&parsed_message.parsed_payload).into(),
+        }
+    }
+}
+
+impl eth_starcoin_bridge::Message {
+    // Bytes `BridgeUtils.encodeMessage` produces for this message, the prefix
+    // included
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut bytes = BRIDGE_MESSAGE_PREFIX.to_vec();
+        bytes.push(self.message_type);
+        bytes.push(self.version);
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        bytes.push(self.chain_id);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    // Keccak256 of the signing payload, the hash the committee signed and the
+    // contract recovers the signers from
+    pub fn digest(&self) -> [u8; 32] {
+        Keccak256::digest(self.signing_payload()).digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EmittedStarcoinToEthTokenBridgeV1;
+    use crate::types::{BridgeAction, StarcoinToEthBridgeAction};
+    use ethers::types::Address as EthAddress;
+    use fastcrypto::encoding::{Encoding, Hex};
+    use serde::Deserialize;
+    use starcoin_bridge_types::base_types::StarcoinAddress;
+    use starcoin_bridge_types::bridge::BridgeChainId;
+
+    const MESSAGE_FIXTURES: &str = include_str!("../fixtures/eth_token_transfer_messages.json");
+
+    // Digests match `BridgeUtils.computeHash`, see `testComputeHashStarcoin*`
+    // in contracts/evm/test/BridgeUtilsTest.t.sol
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct MessageFixture {
+        name: String,
+        message_version: u8,
+        seq_num: u64,
+        source_chain: u8,
+        sender_address: String,
+        target_chain: u8,
+        target_address: String,
+        token_id: u8,
+        amount: u64,
+        payload: String,
+        encoded_message: String,
+        digest: String,
+    }
+
+    fn fixtures() -> Vec<MessageFixture> {
+        serde_json::from_str(MESSAGE_FIXTURES).unwrap()
+    }
+
+    fn parsed_message(fixture: &MessageFixture) -> ParsedTokenTransferMessage {
+        let parsed_payload = MoveTypeTokenTransferPayload {
+            sender_address: Hex::decode(&fixture.sender_address).unwrap(),
+            target_chain: fixture.target_chain,
+            target_address: Hex::decode(&fixture.target_address).unwrap(),
+            token_type: fixture.token_id,
+            amount: fixture.amount,
+        };
+        // The record's payload as Move encoded it, sender padding included
+        let mut payload = vec![parsed_payload.sender_address.len() as u8];
+        payload.extend_from_slice(&parsed_payload.sender_address);
+        payload.push(parsed_payload.target_chain);
+        payload.push(parsed_payload.target_address.len() as u8);
+        payload.extend_from_slice(&parsed_payload.target_address);
+        payload.push(parsed_payload.token_type);
+        payload.extend_from_slice(&parsed_payload.amount.to_be_bytes());
+        ParsedTokenTransferMessage {
+            message_version: fixture.message_version,
+            seq_num: fixture.seq_num,
+            source_chain: BridgeChainId::try_from(fixture.source_chain).unwrap(),
+            payload,
+            parsed_payload,
+        }
+    }
+
+    #[test]
+    fn test_message_fields() {
+        for fixture in fixtures() {
+            let message = eth_starcoin_bridge::Message::from(parsed_message(&fixture));
+            let name = &fixture.name;
+            assert_eq!(
+                message.message_type,
+                BridgeActionType::TokenTransfer as u8,
+                "{name}"
+            );
+            assert_eq!(message.version, fixture.message_version, "{name}");
+            assert_eq!(message.nonce, fixture.seq_num, "{name}");
+            assert_eq!(message.chain_id, fixture.source_chain, "{name}");
+            assert_eq!(Hex::encode(&message.payload), fixture.payload, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_message_payload_fields() {
+        for fixture in fixtures() {
+            let message = eth_starcoin_bridge::Message::from(parsed_message(&fixture));
+            let payload = message.payload.to_vec();
+            let name = &fixture.name;
+
+            // The sender is always 16 bytes, padded or not on Starcoin
+            let sender = Hex::decode(&fixture.sender_address).unwrap();
+            assert_eq!(payload[0] as usize, STARCOIN_ADDRESS_LENGTH, "{name}");
+            assert_eq!(
+                payload[1..17],
+                sender[sender.len() - STARCOIN_ADDRESS_LENGTH..],
+                "{name}"
+            );
+            assert_eq!(payload[17], fixture.target_chain, "{name}");
+            assert_eq!(payload[18], 20, "{name}");
+            assert_eq!(
+                Hex::encode(&payload[19..39]),
+                fixture.target_address,
+                "{name}"
+            );
+            assert_eq!(payload[39], fixture.token_id, "{name}");
+            assert_eq!(
+                u64::from_be_bytes(payload[40..48].try_into().unwrap()),
+                fixture.amount,
+                "{name}"
+            );
+            assert_eq!(payload.len(), 48, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_message_digest_fixtures() {
+        for fixture in fixtures() {
+            let message = eth_starcoin_bridge::Message::from(parsed_message(&fixture));
+            assert_eq!(
+                Hex::encode(message.signing_payload()),
+                fixture.encoded_message,
+                "{}",
+                fixture.name
+            );
+            assert_eq!(
+                Hex::encode(message.digest()),
+                fixture.digest,
+                "Digest of {} changed, claims would fail signature verification",
+                fixture.name
+            );
+        }
+    }
+
+    // The claimed message hashes to the digest of the action the committee
+    // signed for the deposit
+    #[test]
+    fn test_message_digest_matches_signed_action() {
+        let fixture = fixtures()
+            .into_iter()
+            .find(|fixture| fixture.name == "testnet_to_sepolia")
+            .unwrap();
+        let message = eth_starcoin_bridge::Message::from(parsed_message(&fixture));
+        let action = StarcoinToEthBridgeAction {
+            starcoin_bridge_tx_digest: [0; 32],
+            starcoin_bridge_tx_event_index: 0,
+            starcoin_bridge_event: EmittedStarcoinToEthTokenBridgeV1 {
+                nonce: fixture.seq_num,
+                starcoin_bridge_chain_id: BridgeChainId::StarcoinTestnet,
+                eth_chain_id: BridgeChainId::EthSepolia,
+                starcoin_bridge_address: StarcoinAddress::from_bytes(
+                    Hex::decode(&fixture.sender_address).unwrap(),
+                )
+                .unwrap(),
+                eth_address: EthAddress::from_slice(&Hex::decode(&fixture.target_address).unwrap()),
+                token_id: fixture.token_id,
+                amount_starcoin_bridge_adjusted: fixture.amount,
+            },
+        };
+        assert_eq!(
+            message.digest(),
+            BridgeAction::StarcoinToEthBridgeAction(action.clone())
+                .encode()
+                .unwrap()
+                .digest
+        );
+        let from_action: eth_starcoin_bridge::Message = action.try_into().unwrap();
+        assert_eq!(message, from_action);
+    }
+
+    #[test]
+    fn test_starcoin_sender_bytes() {
+        let address = [7u8; 16];
+        assert_eq!(starcoin_sender_bytes(&address), address);
+        let mut padded = vec![0u8; 16];
+        padded.extend_from_slice(&address);
+        assert_eq!(starcoin_sender_bytes(&padded), address);
+        // Anything else is left for the contract to reject
+        let long = [1u8; 32];
+        assert_eq!(starcoin_sender_bytes(&long), long);
+        let short = [1u8; 8];
+        assert_eq!(starcoin_sender_bytes(&short), short);
+    }
+}
@@ -2,12 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! A mock implementation of Ethereum JSON-RPC client, based on `MockProvider` from `ethers-rs`.
+//!
+//! Responses are looked up by method and params first, then `eth_call`s by
+//! target and selector, then by method alone. Raw transactions sent to the
+//! mock are mined at once, so a `PendingTransaction` resolves to a successful
+//! receipt. `serve` exposes the same responses over HTTP, for code that only
+//! takes an RPC url.
 
+use crate::fault_injection::FaultInjector;
 use async_trait::async_trait;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
 use ethers::providers::JsonRpcClient;
 use ethers::providers::MockError;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address as EthAddress, Bytes, Log, Transaction, TransactionReceipt, TxHash};
+use ethers::utils::{keccak256, rlp};
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::{
@@ -15,6 +28,9 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+// Block the transactions sent to the mock are mined in
+const MOCK_MINED_BLOCK: u64 = 1;
+
 // Helper type that can be used to pass through the `params` value.
 // This is necessary because the wrapper provider is supposed to skip the `params` if it's of
 // size 0, see `crate::transports::common::Request`
@@ -24,10 +40,22 @@ enum MockParams {
     Zst,
 }
 
+#[derive(Debug, Default)]
+struct MockChain {
+    // Receipt logs of the transactions sent to each address
+    logs: HashMap<EthAddress, Vec<Log>>,
+    transactions: HashMap<TxHash, (Transaction, TransactionReceipt)>,
+}
+
 // Mock transport used in test environments.
 #[derive(Clone, Debug)]
 pub struct EthMockProvider {
     responses: Arc<Mutex<HashMap<(String, MockParams), Value>>>,
+    // (target, selector) -> `eth_call` output
+    call_responses: Arc<Mutex<HashMap<(EthAddress, [u8; 4]), Value>>>,
+    method_responses: Arc<Mutex<HashMap<String, Value>>>,
+    chain: Arc<Mutex<MockChain>>,
+    fault_injector: Arc<Mutex<Option<Arc<FaultInjector>>>>,
 }
 
 impl Default for EthMockProvider {
@@ -43,24 +71,28 @@ impl JsonRpcClient for EthMockProvider {
 
     // If `method` and `params` match previously set response by
     // `add_response`, return the response. Otherwise return
+    // MockError::EmptyResponses. Injected faults also surface as
     // MockError::EmptyResponses.
     async fn request<P: Serialize + Send + Sync + Debug, R: DeserializeOwned>(
         &self,
         method: &str,
         params: P,
     ) -> Result<R, MockError> {
+        let faults = self.fault_injector.lock().unwrap().clone();
+        if let Some(faults) = faults {
+            faults
+                .on_call(method)
+                .await
+                .map_err(|_| MockError::EmptyResponses)?;
+        }
         let params = if std::mem::size_of::<P>() == 0 {
-            MockParams::Zst
+            None
         } else {
-            MockParams::Value(serde_json::to_value(params)?.to_string())
+            Some(serde_json::to_value(params)?)
         };
         let element = self
-            .responses
-            .lock()
-            .unwrap()
-            .get(&(method.to_owned(), params))
-            .ok_or(MockError::EmptyResponses)?
-            .clone();
+            .respond(method, params.as_ref())
+            .ok_or(MockError::EmptyResponses)?;
         let res: R = serde_json::from_value(element)?;
 
         Ok(res)
@@ -71,9 +103,19 @@ impl EthMockProvider {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            call_responses: Default::default(),
+            method_responses: Default::default(),
+            chain: Default::default(),
+            fault_injector: Default::default(),
         }
     }
 
+    // Makes subsequent requests subject to the injector's faults, keyed by
+    // JSON-RPC method name.
+    pub fn set_fault_injector(&self, injector: Arc<FaultInjector>) {
+        self.fault_injector.lock().unwrap().replace(injector);
+    }
+
     pub fn add_response<P: Serialize + Send + Sync, T: Serialize + Send + Sync, K: Borrow<T>>(
         &self,
         method: &str,
@@ -92,6 +134,179 @@ impl EthMockProvider {
             .insert((method.to_owned(), params), value);
         Ok(())
     }
+
+    // Output of every `eth_call` of `selector` on `to`, whatever its
+    // arguments, unless a response matches the call's params exactly
+    pub fn add_call_response(&self, to: EthAddress, selector: [u8; 4], output: Bytes) {
+        self.call_responses
+            .lock()
+            .unwrap()
+            .insert((to, selector), json!(output));
+    }
+
+    // Response to `method` whatever its params, when nothing more specific
+    // matches
+    pub fn add_method_response<T: Serialize>(
+        &self,
+        method: &str,
+        data: T,
+    ) -> Result<(), MockError> {
+        self.method_responses
+            .lock()
+            .unwrap()
+            .insert(method.to_owned(), serde_json::to_value(data)?);
+        Ok(())
+    }
+
+    // Logs in the receipts of the transactions sent to `to` from now on
+    pub fn set_transaction_logs(&self, to: EthAddress, logs: Vec<Log>) {
+        self.chain.lock().unwrap().logs.insert(to, logs);
+    }
+
+    // Receipts of the transactions sent to the mock, in no particular order
+    pub fn mined_receipts(&self) -> Vec<TransactionReceipt> {
+        self.chain
+            .lock()
+            .unwrap()
+            .transactions
+            .values()
+            .map(|(_, receipt)| receipt.clone())
+            .collect()
+    }
+
+    // Serves the responses over HTTP on a free local port and returns its
+    // url. The server runs until the test's runtime shuts down.
+    pub async fn serve(&self) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let router = Router::new()
+            .route("/", post(handle_request))
+            .with_state(self.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        url
+    }
+
+    // `params` is None for methods called without any
+    fn respond(&self, method: &str, params: Option<&Value>) -> Option<Value> {
+        let key = match params {
+            Some(params) => MockParams::Value(params.to_string()),
+            None => MockParams::Zst,
+        };
+        if let Some(response) = self
+            .responses
+            .lock()
+            .unwrap()
+            .get(&(method.to_owned(), key))
+        {
+            return Some(response.clone());
+        }
+        let param = |i: usize| params.and_then(|params| params.get(i));
+        match method {
+            "eth_sendRawTransaction" => {
+                let raw: Bytes = serde_json::from_value(param(0)?.clone()).ok()?;
+                return Some(json!(self.mine(&raw)?));
+            }
+            "eth_getTransactionByHash" | "eth_getTransactionReceipt" => {
+                let hash: TxHash = serde_json::from_value(param(0)?.clone()).ok()?;
+                let chain = self.chain.lock().unwrap();
+                // Unknown transactions are pending
+                let Some((tx, receipt)) = chain.transactions.get(&hash) else {
+                    return Some(Value::Null);
+                };
+                return Some(if method == "eth_getTransactionByHash" {
+                    json!(tx)
+                } else {
+                    json!(receipt)
+                });
+            }
+            "eth_call" => {
+                let call = param(0)?;
+                let to: Option<EthAddress> = call
+                    .get("to")
+                    .and_then(|to| serde_json::from_value(to.clone()).ok());
+                let data: Option<Bytes> = call
+                    .get("data")
+                    .or_else(|| call.get("input"))
+                    .and_then(|data| serde_json::from_value(data.clone()).ok());
+                if let (Some(to), Some(data)) = (to, data) {
+                    if let Ok(selector) = <[u8; 4]>::try_from(data.get(..4).unwrap_or_default()) {
+                        if let Some(output) =
+                            self.call_responses.lock().unwrap().get(&(to, selector))
+                        {
+                            return Some(output.clone());
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+        self.method_responses.lock().unwrap().get(method).cloned()
+    }
+
+    // Mines the signed transaction `raw` in `MOCK_MINED_BLOCK`, with the
+    // logs set for its target. Returns its hash.
+    fn mine(&self, raw: &Bytes) -> Option<TxHash> {
+        let (request, signature) = TypedTransaction::decode_signed(&rlp::Rlp::new(raw)).ok()?;
+        let hash = TxHash::from(keccak256(raw));
+        let mut chain = self.chain.lock().unwrap();
+        let to = request.to_addr().copied();
+        let logs = to
+            .and_then(|to| chain.logs.get(&to).cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|log| Log {
+                block_number: Some(MOCK_MINED_BLOCK.into()),
+                transaction_hash: Some(hash),
+                ..log
+            })
+            .collect();
+        let tx = Transaction {
+            hash,
+            from: signature.recover(request.sighash()).ok()?,
+            to,
+            block_number: Some(MOCK_MINED_BLOCK.into()),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(MOCK_MINED_BLOCK.into()),
+            to,
+            gas_used: request.gas().copied(),
+            logs,
+            status: Some(1u64.into()),
+            ..Default::default()
+        };
+        chain.transactions.insert(hash, (tx, receipt));
+        Some(hash)
+    }
+}
+
+async fn handle_request(
+    State(mock): State<EthMockProvider>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let id = request.get("id").cloned().unwrap_or(json!(0));
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let faults = mock.fault_injector.lock().unwrap().clone();
+    let result = match faults {
+        Some(faults) => faults.on_call(method).await,
+        None => Ok(()),
+    }
+    .and_then(|()| {
+        mock.respond(method, request.get("params"))
+            .ok_or_else(|| format!("No response for {method}"))
+    });
+    Json(match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(message) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": -32000, "message": message},
+        }),
+    })
 }
 
 #[cfg(test)]
@@ -146,4 +361,23 @@ mod tests {
         let block = provider.get_block_number().await.unwrap();
         assert_eq!(block.as_u64(), 12);
     }
+
+    #[tokio::test]
+    async fn test_fault_injection() {
+        let mock = EthMockProvider::new();
+        let provider = ethers::providers::Provider::new(mock.clone());
+        mock.add_response("eth_blockNumber", (), U64::from(12))
+            .unwrap();
+        let faults = FaultInjector::builder(0)
+            .fail_first("eth_blockNumber", 2)
+            .build();
+        mock.set_fault_injector(faults.clone());
+
+        assert!(provider.get_block_number().await.is_err());
+        assert!(provider.get_block_number().await.is_err());
+        let block = provider.get_block_number().await.unwrap();
+        assert_eq!(block.as_u64(), 12);
+        assert_eq!(faults.calls("eth_blockNumber"), 3);
+        assert_eq!(faults.injected_failures("eth_blockNumber"), 2);
+    }
 }
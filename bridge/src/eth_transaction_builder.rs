@@ -9,7 +9,7 @@ use crate::abi::{eth_bridge_config, eth_bridge_limiter, EthBridgeConfig};
 use crate::error::{BridgeError, BridgeResult};
 use crate::types::{
     AddTokensOnEvmAction, AssetPriceUpdateAction, BlocklistCommitteeAction,
-    BridgeCommitteeValiditySignInfo, EvmContractUpgradeAction, LimitUpdateAction,
+    BridgeCommitteeValiditySignInfo, EvmContractUpgradeAction, LimitUpdateAction, TokenPauseAction,
     VerifiedCertifiedBridgeAction,
 };
 use crate::utils::EthSigner;
@@ -17,8 +17,11 @@ use crate::{
     abi::EthStarcoinBridge,
     types::{BridgeAction, EmergencyAction},
 };
+use ethers::abi::{short_signature, Abi, Detokenize, ParamType, Token};
 use ethers::prelude::*;
 use ethers::types::Address as EthAddress;
+use once_cell::sync::Lazy;
+use std::fmt;
 
 pub async fn build_eth_transaction(
     contract_address: EthAddress,
@@ -75,6 +78,9 @@ pub async fn build_eth_transaction(
             build_add_tokens_on_evm_transaction(contract_address, signer, action.clone(), sigs)
                 .await
         }
+        BridgeAction::TokenPauseAction(action) => {
+            build_token_pause_transaction(contract_address, signer, action.clone(), sigs).await
+        }
     }
 }
 
@@ -161,6 +167,22 @@ pub async fn build_add_tokens_on_evm_transaction(
     Ok(contract.add_tokens_with_signatures(signatures, message))
 }
 
+pub async fn build_token_pause_transaction(
+    contract_address: EthAddress,
+    signer: EthSigner,
+    action: TokenPauseAction,
+    sigs: &BridgeCommitteeValiditySignInfo,
+) -> BridgeResult<ContractCall<EthSigner, ()>> {
+    let contract = EthBridgeConfig::new(contract_address, signer.into());
+    let message: eth_bridge_config::Message = action.clone().try_into()?;
+    let signatures = sigs
+        .signatures
+        .values()
+        .map(|sig| Bytes::from(sig.as_ref().to_vec()))
+        .collect::<Vec<_>>();
+    Ok(contract.pause_token_with_signatures(signatures, message))
+}
+
 pub async fn build_evm_upgrade_transaction(
     signer: EthSigner,
     action: EvmContractUpgradeAction,
@@ -178,3 +200,306 @@ pub async fn build_evm_upgrade_transaction(
 }
 
 // TODO: add tests for eth transaction building
+
+// Outcome of an `eth_call` of a transaction at the latest block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimulationResult {
+    Success,
+    Reverted(RevertReason),
+}
+
+impl fmt::Display for SimulationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationResult::Success => write!(f, "succeeds"),
+            SimulationResult::Reverted(reason) => write!(f, "reverts with {reason}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevertReason {
+    // `require` or `revert` with a message
+    Error(String),
+    // A failed `assert`, an overflow and the like, by panic code
+    Panic(U256),
+    // A custom error of the bridge contracts, with its arguments by name
+    Custom {
+        name: String,
+        args: Vec<(String, String)>,
+    },
+    // Revert data that matches no known error, empty for a bare `revert()`
+    Unknown(Bytes),
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Error(message) => write!(f, "Error({message:?})"),
+            RevertReason::Panic(code) if *code <= U256::from(u8::MAX) => {
+                let code = code.as_u32();
+                write!(f, "Panic(0x{code:02x}): {}", panic_reason(code))
+            }
+            RevertReason::Panic(code) => write!(f, "Panic({code:#x}): unknown panic code"),
+            RevertReason::Custom { name, args } => {
+                let args = args
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect::<Vec<_>>();
+                write!(f, "{name}({})", args.join(", "))
+            }
+            RevertReason::Unknown(data) if data.is_empty() => write!(f, "no revert data"),
+            RevertReason::Unknown(data) => write!(f, "unknown revert data 0x{}", hex::encode(data)),
+        }
+    }
+}
+
+// What Solidity's panic codes stand for
+fn panic_reason(code: u32) -> &'static str {
+    match code {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop from an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to an uninitialized function",
+        _ => "unknown panic code",
+    }
+}
+
+// Custom errors of the bundled contract ABIs, by selector. Errors several
+// contracts declare share a selector and are listed once per contract.
+static CUSTOM_ERRORS: Lazy<Vec<([u8; 4], ethers::abi::AbiError)>> = Lazy::new(|| {
+    [
+        include_str!("../abi/starcoin_bridge.json"),
+        include_str!("../abi/bridge_committee.json"),
+        include_str!("../abi/bridge_committee_upgradeable.json"),
+        include_str!("../abi/bridge_config.json"),
+        include_str!("../abi/bridge_limiter.json"),
+        include_str!("../abi/bridge_vault.json"),
+        include_str!("../abi/erc20.json"),
+    ]
+    .into_iter()
+    .flat_map(|abi| {
+        let abi: Abi = serde_json::from_str(abi).expect("Bundled ABIs are valid");
+        abi.errors().cloned().collect::<Vec<_>>()
+    })
+    .map(|error| {
+        let kinds = error
+            .inputs
+            .iter()
+            .map(|input| input.kind.clone())
+            .collect::<Vec<_>>();
+        (short_signature(&error.name, &kinds), error)
+    })
+    .collect()
+});
+
+fn token_text(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("{address:?}"),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::String(value) => format!("{value:?}"),
+        token => token.to_string(),
+    }
+}
+
+// Decodes the revert data of a call: `Error(string)`, `Panic(uint256)` or a
+// custom error of the bridge contracts
+pub fn decode_revert_data(data: &[u8]) -> RevertReason {
+    let unknown = || RevertReason::Unknown(Bytes::from(data.to_vec()));
+    if data.len() < 4 {
+        return unknown();
+    }
+    let (selector, args) = data.split_at(4);
+    if selector == short_signature("Error", &[ParamType::String]) {
+        if let Ok(Some(Token::String(message))) =
+            ethers::abi::decode(&[ParamType::String], args).map(|tokens| tokens.into_iter().next())
+        {
+            return RevertReason::Error(message);
+        }
+        return unknown();
+    }
+    if selector == short_signature("Panic", &[ParamType::Uint(256)]) {
+        if let Ok(Some(Token::Uint(code))) = ethers::abi::decode(&[ParamType::Uint(256)], args)
+            .map(|tokens| tokens.into_iter().next())
+        {
+            return RevertReason::Panic(code);
+        }
+        return unknown();
+    }
+    for (error_selector, error) in CUSTOM_ERRORS.iter() {
+        if selector != error_selector {
+            continue;
+        }
+        let kinds = error
+            .inputs
+            .iter()
+            .map(|input| input.kind.clone())
+            .collect::<Vec<_>>();
+        if let Ok(tokens) = ethers::abi::decode(&kinds, args) {
+            return RevertReason::Custom {
+                name: error.name.clone(),
+                args: error
+                    .inputs
+                    .iter()
+                    .zip(&tokens)
+                    .map(|(input, token)| (input.name.clone(), token_text(token)))
+                    .collect(),
+            };
+        }
+    }
+    unknown()
+}
+
+// Runs `call` with `eth_call` at the latest block, without sending it, and
+// decodes why it would revert. Errors other than a revert, e.g. of the
+// provider, are returned as errors.
+pub async fn simulate_eth_transaction<M, D>(
+    call: &ContractCall<M, D>,
+) -> BridgeResult<SimulationResult>
+where
+    M: Middleware,
+    D: Detokenize,
+{
+    match call.clone().block(BlockNumber::Latest).call().await {
+        Ok(_) => Ok(SimulationResult::Success),
+        Err(e) => match e.as_revert() {
+            Some(data) => Ok(SimulationResult::Reverted(decode_revert_data(data))),
+            None => Err(BridgeError::transient_rpc(format!(
+                "Failed to simulate eth transaction: {:?}",
+                e
+            ))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::encode;
+
+    fn revert_data(name: &str, kinds: &[ParamType], tokens: &[Token]) -> Vec<u8> {
+        let mut data = short_signature(name, kinds).to_vec();
+        data.extend(encode(tokens));
+        data
+    }
+
+    #[test]
+    fn test_decode_error_string() {
+        let data = revert_data(
+            "Error",
+            &[ParamType::String],
+            &[Token::String(
+                "BridgeCommittee: Insufficient stake amount".to_string(),
+            )],
+        );
+        // The selector of `Error(string)`
+        assert_eq!(data[..4], [0x08, 0xc3, 0x79, 0xa0]);
+        let reason = decode_revert_data(&data);
+        assert_eq!(
+            reason,
+            RevertReason::Error("BridgeCommittee: Insufficient stake amount".to_string())
+        );
+        assert_eq!(
+            reason.to_string(),
+            "Error(\"BridgeCommittee: Insufficient stake amount\")"
+        );
+    }
+
+    #[test]
+    fn test_decode_panic_code() {
+        let data = revert_data(
+            "Panic",
+            &[ParamType::Uint(256)],
+            &[Token::Uint(U256::from(0x11))],
+        );
+        // The selector of `Panic(uint256)`
+        assert_eq!(data[..4], [0x4e, 0x48, 0x7b, 0x71]);
+        let reason = decode_revert_data(&data);
+        assert_eq!(reason, RevertReason::Panic(U256::from(0x11)));
+        assert_eq!(
+            reason.to_string(),
+            "Panic(0x11): arithmetic overflow or underflow"
+        );
+
+        let data = revert_data(
+            "Panic",
+            &[ParamType::Uint(256)],
+            &[Token::Uint(U256::from(0x99))],
+        );
+        assert_eq!(
+            decode_revert_data(&data).to_string(),
+            "Panic(0x99): unknown panic code"
+        );
+    }
+
+    #[test]
+    fn test_decode_custom_error() {
+        let account = EthAddress::repeat_byte(0xab);
+        let data = revert_data(
+            "OwnableUnauthorizedAccount",
+            &[ParamType::Address],
+            &[Token::Address(account)],
+        );
+        let reason = decode_revert_data(&data);
+        assert_eq!(
+            reason,
+            RevertReason::Custom {
+                name: "OwnableUnauthorizedAccount".to_string(),
+                args: vec![("account".to_string(), format!("{account:?}"))],
+            }
+        );
+        assert_eq!(
+            reason.to_string(),
+            format!("OwnableUnauthorizedAccount(account: {account:?})")
+        );
+
+        // Errors without arguments, and with numbers
+        let data = revert_data("EnforcedPause", &[], &[]);
+        assert_eq!(decode_revert_data(&data).to_string(), "EnforcedPause()");
+        let data = revert_data(
+            "ERC20InsufficientBalance",
+            &[
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+            ],
+            &[
+                Token::Address(account),
+                Token::Uint(U256::from(5)),
+                Token::Uint(U256::from(7)),
+            ],
+        );
+        assert_eq!(
+            decode_revert_data(&data).to_string(),
+            format!("ERC20InsufficientBalance(sender: {account:?}, balance: 5, needed: 7)")
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_revert_data() {
+        let data = revert_data("NotABridgeError", &[ParamType::Bool], &[Token::Bool(true)]);
+        assert_eq!(
+            decode_revert_data(&data),
+            RevertReason::Unknown(Bytes::from(data.clone()))
+        );
+        assert_eq!(decode_revert_data(&[]).to_string(), "no revert data");
+        // A truncated `Error(string)` is not taken for a message
+        let data = revert_data(
+            "Error",
+            &[ParamType::String],
+            &[Token::String("message".to_string())],
+        );
+        assert!(matches!(
+            decode_revert_data(&data[..20]),
+            RevertReason::Unknown(_)
+        ));
+    }
+}
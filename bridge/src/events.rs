@@ -11,6 +11,7 @@
 use crate::crypto::BridgeAuthorityPublicKey;
 use crate::error::BridgeError;
 use crate::error::BridgeResult;
+use crate::sanitize::{sanitize_untrusted, MAX_URL_BYTES};
 use crate::types::BridgeAction;
 use crate::types::StarcoinToEthBridgeAction;
 use ethers::types::Address as EthAddress;
@@ -21,6 +22,7 @@ use move_core_types::language_storage::StructTag;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use starcoin_bridge_json_rpc_types::StarcoinEvent;
+use starcoin_bridge_types::base_types::ObjectID;
 use starcoin_bridge_types::base_types::StarcoinAddress;
 use starcoin_bridge_types::base_types::TransactionDigest;
 use starcoin_bridge_types::bridge::BridgeChainId;
@@ -33,6 +35,53 @@ use starcoin_bridge_types::TypeTag;
 use starcoin_bridge_types::BRIDGE_PACKAGE_ID;
 use std::str::FromStr;
 
+// Move modules of the bridge package whose events are decoded, by their name
+// on chain and the identifier the node, its cursors and the event tags below
+// use for them. Module names are compared exactly after this mapping, so
+// neither `bridgeX` nor `BRIDGE` is taken for `bridge`.
+pub const BRIDGE_EVENT_MODULES: &[(&str, &str)] = &[
+    ("Bridge", "bridge"),
+    ("Committee", "committee"),
+    ("Treasury", "treasury"),
+    ("Limiter", "limiter"),
+];
+
+// On-chain name of the module with internal identifier `module`, `module`
+// itself when it is no internal identifier
+pub fn on_chain_module_name(module: &str) -> &str {
+    BRIDGE_EVENT_MODULES
+        .iter()
+        .find(|(_, internal)| *internal == module)
+        .map_or(module, |(on_chain, _)| on_chain)
+}
+
+// Whether `module` is the on-chain name of a module in `BRIDGE_EVENT_MODULES`
+pub fn is_mapped_module(module: &str) -> bool {
+    BRIDGE_EVENT_MODULES
+        .iter()
+        .any(|(on_chain, _)| *on_chain == module)
+}
+
+// Whether an event emitted by the on-chain module `event_module` belongs to
+// `module`, given by its internal identifier or its on-chain name
+pub fn event_module_matches(event_module: &str, module: &str) -> bool {
+    event_module == on_chain_module_name(module)
+}
+
+// The Starcoin address of a package, which is kept in the last 16 bytes of
+// its 32 byte id. Ids with anything in the first 16 bytes are rejected rather
+// than truncated.
+pub fn package_starcoin_address(package: &ObjectID) -> BridgeResult<[u8; 16]> {
+    let (padding, address) = package.split_at(16);
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(BridgeError::Generic(format!(
+            "Package id 0x{} is no Starcoin address, its first 16 bytes are not zero",
+            hex::encode(package)
+        )));
+    }
+    Ok(address.try_into().expect("16 bytes"))
+}
+
 // `TokendDepositedEvent` emitted in bridge.move
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct MoveTokenDepositedEvent {
@@ -290,6 +339,8 @@ impl TryFrom<MoveCommitteeMemberUrlUpdateEvent> for CommitteeMemberUrlUpdateEven
         let new_url = String::from_utf8(event.new_url).map_err(|e|
             BridgeError::Generic(format!("Failed to convert MoveBlocklistValidatorEvent to BlocklistValidatorEvent. Failed to convert new_url to String: {:?}", e))
         )?;
+        // Sanitized the same way as the committee urls it is compared with
+        let new_url = sanitize_untrusted(new_url.as_bytes(), MAX_URL_BYTES);
         Ok(Self { member, new_url })
     }
 }
@@ -383,10 +434,15 @@ macro_rules! declare_events {
 
         pub(crate) fn init_all_struct_tags() {
             $($variant.get_or_init(|| {
-                // Extract last 16 bytes for Starcoin address (first 16 bytes are padding)
-                let starcoin_addr = &BRIDGE_PACKAGE_ID[16..32];
-                let addr_hex = hex::encode(starcoin_addr);
-                StructTag::from_str(&format!("0x{}::{}", addr_hex, $event_tag)).unwrap()
+                let starcoin_addr = package_starcoin_address(&BRIDGE_PACKAGE_ID).unwrap();
+                let (module, name) = $event_tag.split_once("::").unwrap();
+                StructTag::from_str(&format!(
+                    "0x{}::{}::{}",
+                    hex::encode(starcoin_addr),
+                    on_chain_module_name(module),
+                    name
+                ))
+                .unwrap()
             });)*
         }
 
@@ -401,8 +457,9 @@ macro_rules! declare_events {
             let expected_module = parts[0];
             let expected_name = parts[1];
 
-            // Compare module and name (case-insensitive for module to handle Bridge vs bridge)
-            event.type_.module.as_str().eq_ignore_ascii_case(expected_module)
+            // The tag names the module by its internal identifier, see
+            // `BRIDGE_EVENT_MODULES`
+            event_module_matches(event.type_.module.as_str(), expected_module)
                 && event.type_.name.as_str() == expected_name
         }
 
@@ -578,6 +635,14 @@ pub mod tests {
         assert_eq!(event.member, kp.public);
         assert_eq!(event.new_url, new_url);
 
+        let event: CommitteeMemberUrlUpdateEvent = MoveCommitteeMemberUrlUpdateEvent {
+            member: kp.public.as_bytes().to_vec(),
+            new_url: b"https://example.com:443\x1b[2J\n".to_vec(),
+        }
+        .try_into()
+        .unwrap();
+        assert_eq!(event.new_url, "https://example.com:443[2J");
+
         CommitteeMemberUrlUpdateEvent::try_from(MoveCommitteeMemberUrlUpdateEvent {
             member: vec![1, 2, 3],
             new_url: new_url.as_bytes().to_vec(),
@@ -612,4 +677,64 @@ pub mod tests {
             other => panic!("Expected ZeroValueBridgeTransfer error, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_module_names_compared_exactly() {
+        for (on_chain, internal) in BRIDGE_EVENT_MODULES {
+            assert!(event_module_matches(on_chain, internal));
+            assert!(event_module_matches(on_chain, on_chain));
+            assert!(is_mapped_module(on_chain));
+            assert!(!is_mapped_module(internal));
+        }
+        // Case variants and names sharing a prefix are other modules
+        for module in ["bridge", "BRIDGE", "bRidge", "bridgeX", "BridgeX", "Bridg"] {
+            assert!(!event_module_matches(module, "bridge"), "{module}");
+            assert!(!event_module_matches(module, "Bridge"), "{module}");
+            assert!(!is_mapped_module(module), "{module}");
+        }
+        assert!(!event_module_matches("Bridge", "bridgeX"));
+        assert!(!event_module_matches("Bridge", "committee"));
+        // Unmapped modules only match their own name
+        assert!(event_module_matches("Foo", "Foo"));
+        assert!(!event_module_matches("Foo", "foo"));
+    }
+
+    #[test]
+    fn test_events_of_other_modules_not_decoded() {
+        let (mut event, action) =
+            get_test_starcoin_bridge_event_and_action(Identifier::new("bridge").unwrap());
+        assert_eq!(event.type_.module.as_str(), "Bridge");
+        let decoded = StarcoinBridgeEvent::try_from_starcoin_bridge_event(&event)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            decoded.try_into_bridge_action(event.id.tx_digest, 0),
+            Some(action)
+        );
+
+        for module in ["bridge", "BRIDGE", "bridgeX"] {
+            event.type_.module = Identifier::new(module).unwrap();
+            assert!(
+                StarcoinBridgeEvent::try_from_starcoin_bridge_event(&event)
+                    .unwrap()
+                    .is_none(),
+                "{module}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_package_starcoin_address() {
+        let mut package = [0u8; 32];
+        package[16..].copy_from_slice(&[7u8; 16]);
+        assert_eq!(package_starcoin_address(&package).unwrap(), [7u8; 16]);
+        assert_eq!(
+            package_starcoin_address(&BRIDGE_PACKAGE_ID).unwrap(),
+            BRIDGE_PACKAGE_ID[16..]
+        );
+
+        package[15] = 1;
+        package_starcoin_address(&package).unwrap_err();
+        package_starcoin_address(&[1u8; 32]).unwrap_err();
+    }
 }
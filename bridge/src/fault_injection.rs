@@ -0,0 +1,290 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `FaultInjector` makes the mock Starcoin client and the mock Eth provider
+//! misbehave on purpose, so retry, backoff and ordering logic can be tested
+//! against something other than a perfect RPC.
+//!
+//! Faults are keyed by method name: the `StarcoinClientInner` method name for
+//! `StarcoinMockClient` (e.g. `"sign_and_submit_transaction"`) and the JSON-RPC
+//! method for `StarcoinMockNode` (e.g. `"chain.get_events"`) and
+//! `EthMockProvider` (e.g. `"eth_blockNumber"`). All randomness
+//! comes from a single seeded RNG, so a test that drives calls in a fixed order
+//! sees the same faults on every run.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use starcoin_bridge_types::bridge::BridgeCommitteeSummary;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default)]
+struct MethodFaults {
+    failure_rate: f64,
+    fail_first: u64,
+    latency: Option<(Duration, Duration)>,
+    permanent: bool,
+}
+
+#[derive(Debug, Default)]
+struct CallCounts {
+    calls: HashMap<String, u64>,
+    injected_failures: HashMap<String, u64>,
+}
+
+#[derive(Debug)]
+pub struct FaultInjector {
+    methods: HashMap<String, MethodFaults>,
+    stale_head: Option<u64>,
+    stale_committee: Option<BridgeCommitteeSummary>,
+    out_of_order_events: bool,
+    rng: Mutex<StdRng>,
+    counts: Mutex<CallCounts>,
+}
+
+pub struct FaultInjectorBuilder {
+    seed: u64,
+    methods: HashMap<String, MethodFaults>,
+    stale_head: Option<u64>,
+    stale_committee: Option<BridgeCommitteeSummary>,
+    out_of_order_events: bool,
+}
+
+impl FaultInjectorBuilder {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            methods: HashMap::new(),
+            stale_head: None,
+            stale_committee: None,
+            out_of_order_events: false,
+        }
+    }
+
+    // Each call to `method` fails with probability `rate`.
+    pub fn failure_rate(mut self, method: &str, rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&rate),
+            "failure rate must be in [0, 1], got {rate}"
+        );
+        self.methods
+            .entry(method.to_string())
+            .or_default()
+            .failure_rate = rate;
+        self
+    }
+
+    // The first `n` calls to `method` fail, later calls go through.
+    pub fn fail_first(mut self, method: &str, n: u64) -> Self {
+        self.methods
+            .entry(method.to_string())
+            .or_default()
+            .fail_first = n;
+        self
+    }
+
+    // Failures of `method` are reported as permanent errors, which callers
+    // must not retry.
+    pub fn permanent(mut self, method: &str) -> Self {
+        self.methods
+            .entry(method.to_string())
+            .or_default()
+            .permanent = true;
+        self
+    }
+
+    // Each call to `method` is delayed by a uniformly drawn duration in `[min, max]`.
+    pub fn latency(mut self, method: &str, min: Duration, max: Duration) -> Self {
+        assert!(min <= max, "latency range is empty: {min:?} > {max:?}");
+        self.methods.entry(method.to_string()).or_default().latency = Some((min, max));
+        self
+    }
+
+    // The reported chain head stops advancing once it reaches `head`.
+    pub fn stale_head(mut self, head: u64) -> Self {
+        self.stale_head = Some(head);
+        self
+    }
+
+    // Bridge summaries report `committee` regardless of the current one.
+    pub fn stale_committee(mut self, committee: BridgeCommitteeSummary) -> Self {
+        self.stale_committee = Some(committee);
+        self
+    }
+
+    // Events within a page are delivered in a shuffled order.
+    pub fn out_of_order_events(mut self) -> Self {
+        self.out_of_order_events = true;
+        self
+    }
+
+    pub fn build(self) -> Arc<FaultInjector> {
+        Arc::new(FaultInjector {
+            methods: self.methods,
+            stale_head: self.stale_head,
+            stale_committee: self.stale_committee,
+            out_of_order_events: self.out_of_order_events,
+            rng: Mutex::new(StdRng::seed_from_u64(self.seed)),
+            counts: Mutex::new(CallCounts::default()),
+        })
+    }
+}
+
+impl FaultInjector {
+    pub fn builder(seed: u64) -> FaultInjectorBuilder {
+        FaultInjectorBuilder::new(seed)
+    }
+
+    // Called at the top of every mocked method. Applies the configured latency,
+    // then returns an error if this call should fail.
+    pub async fn on_call(&self, method: &str) -> Result<(), String> {
+        let (latency, fail) = self.decide(method);
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        if fail {
+            return Err(format!("Injected fault in {method}"));
+        }
+        Ok(())
+    }
+
+    fn decide(&self, method: &str) -> (Option<Duration>, bool) {
+        let mut counts = self.counts.lock().unwrap();
+        let calls = counts.calls.entry(method.to_string()).or_default();
+        *calls += 1;
+        let call = *calls;
+        let Some(faults) = self.methods.get(method) else {
+            return (None, false);
+        };
+        let mut rng = self.rng.lock().unwrap();
+        let latency = faults.latency.map(|(min, max)| rng.gen_range(min..=max));
+        // Always draw, so that `fail_first` does not shift the random stream
+        let unlucky = rng.gen_bool(faults.failure_rate);
+        let fail = call <= faults.fail_first || unlucky;
+        if fail {
+            *counts
+                .injected_failures
+                .entry(method.to_string())
+                .or_default() += 1;
+        }
+        (latency, fail)
+    }
+
+    // Number of calls to `method` seen so far, including failed ones.
+    pub fn calls(&self, method: &str) -> u64 {
+        self.counts
+            .lock()
+            .unwrap()
+            .calls
+            .get(method)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn injected_failures(&self, method: &str) -> u64 {
+        self.counts
+            .lock()
+            .unwrap()
+            .injected_failures
+            .get(method)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn is_permanent(&self, method: &str) -> bool {
+        self.methods
+            .get(method)
+            .is_some_and(|faults| faults.permanent)
+    }
+
+    pub fn head(&self, actual: u64) -> u64 {
+        self.stale_head.map_or(actual, |stale| actual.min(stale))
+    }
+
+    pub fn committee(&self, actual: BridgeCommitteeSummary) -> BridgeCommitteeSummary {
+        self.stale_committee.clone().unwrap_or(actual)
+    }
+
+    pub fn reorder<T>(&self, items: &mut [T]) {
+        if self.out_of_order_events {
+            items.shuffle(&mut *self.rng.lock().unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn failure_pattern(injector: &FaultInjector, method: &str, n: usize) -> Vec<bool> {
+        let mut pattern = vec![];
+        for _ in 0..n {
+            pattern.push(injector.on_call(method).await.is_err());
+        }
+        pattern
+    }
+
+    #[tokio::test]
+    async fn test_faults_are_deterministic_per_seed() {
+        let build = |seed| {
+            FaultInjector::builder(seed)
+                .failure_rate("query_events", 0.5)
+                .build()
+        };
+        let a = failure_pattern(&build(42), "query_events", 64).await;
+        let b = failure_pattern(&build(42), "query_events", 64).await;
+        let c = failure_pattern(&build(43), "query_events", 64).await;
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.contains(&true) && a.contains(&false));
+
+        let shuffled = |seed| {
+            let injector = FaultInjector::builder(seed).out_of_order_events().build();
+            let mut items: Vec<u32> = (0..16).collect();
+            injector.reorder(&mut items);
+            items
+        };
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_ne!(shuffled(7), (0..16).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_fail_first_then_succeed() {
+        let injector = FaultInjector::builder(0)
+            .fail_first("sign_and_submit_transaction", 3)
+            .build();
+        let pattern = failure_pattern(&injector, "sign_and_submit_transaction", 5).await;
+        assert_eq!(pattern, vec![true, true, true, false, false]);
+        assert_eq!(injector.calls("sign_and_submit_transaction"), 5);
+        assert_eq!(injector.injected_failures("sign_and_submit_transaction"), 3);
+
+        // Methods without faults are counted but never fail
+        assert!(failure_pattern(&injector, "get_sequence_number", 3)
+            .await
+            .iter()
+            .all(|failed| !failed));
+        assert_eq!(injector.calls("get_sequence_number"), 3);
+        assert_eq!(injector.injected_failures("get_sequence_number"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_latency_and_stale_data() {
+        let injector = FaultInjector::builder(1)
+            .latency(
+                "get_bridge_summary",
+                Duration::from_millis(50),
+                Duration::from_millis(100),
+            )
+            .stale_head(100)
+            .build();
+        let start = std::time::Instant::now();
+        injector.on_call("get_bridge_summary").await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        assert_eq!(injector.head(42), 42);
+        assert_eq!(injector.head(100), 100);
+        assert_eq!(injector.head(150), 100);
+    }
+}
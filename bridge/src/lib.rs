@@ -21,21 +21,35 @@
 
 pub mod abi;
 pub mod action_executor;
+pub mod audit;
 pub mod client;
+pub mod committee_concentration;
 pub mod config;
 pub mod crypto;
+pub mod deadline;
 pub mod encoding;
 pub mod error;
 pub mod eth_client;
+pub mod eth_message;
 pub mod eth_syncer;
 
 pub mod eth_transaction_builder;
 pub mod events;
+pub mod fault_injection;
+pub mod lifecycle;
 pub mod metered_eth_provider;
 pub mod metrics;
 pub mod monitor;
 pub mod node;
+pub mod node_info;
+pub mod node_status;
 pub mod orchestrator;
+pub mod rate_limited_logger;
+pub mod recipient;
+pub mod registration;
+pub mod sanitize;
+pub mod scheduled_actions;
+pub mod secret;
 pub mod server;
 pub mod simple_starcoin_rpc;
 pub mod starcoin_bridge_client;
@@ -47,18 +61,25 @@ pub mod starcoin_jsonrpc_client;
 mod starcoin_node_test;
 #[cfg(test)]
 pub mod starcoin_test_utils;
+pub mod startup;
 pub mod storage;
+pub mod token_math;
+pub mod token_modules;
+pub mod tx_digest;
 pub mod types;
 pub mod utils;
 
 #[cfg(any(feature = "test-utils", test))]
-pub(crate) mod eth_mock_provider;
+pub mod eth_mock_provider;
 
 pub mod starcoin_bridge_mock_client;
 
 #[cfg(any(feature = "test-utils", test))]
 pub mod test_utils;
 
+#[cfg(any(feature = "test-utils", test))]
+pub mod starcoin_mock_node;
+
 pub const BRIDGE_ENABLE_PROTOCOL_VERSION: u64 = 45;
 
 #[cfg(any(feature = "test-utils", test))]
@@ -100,6 +121,9 @@ macro_rules! retry_with_max_elapsed_time {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fault_injection::FaultInjector;
+    use crate::starcoin_bridge_client::StarcoinClient;
+    use crate::starcoin_bridge_mock_client::StarcoinMockClient;
     use std::time::Duration;
 
     async fn example_func_ok() -> anyhow::Result<()> {
@@ -127,4 +151,39 @@ mod tests {
         retry_with_max_elapsed_time!(example_func_err(), max_elapsed_time).unwrap_err();
         assert!(instant.elapsed() < max_elapsed_time);
     }
+
+    #[tokio::test]
+    async fn test_retry_with_max_elapsed_time_under_faults() {
+        telemetry_subscribers::init_for_testing();
+        let mock = StarcoinMockClient::default();
+        mock.set_latest_checkpoint_sequence_number(42);
+        let client = StarcoinClient::new_for_testing(mock.clone());
+        let method = "get_latest_checkpoint_sequence_number";
+
+        // Transient faults are retried until the first success, and no further
+        let faults = FaultInjector::builder(5).fail_first(method, 3).build();
+        mock.set_fault_injector(faults.clone());
+        let head = retry_with_max_elapsed_time!(
+            client.get_latest_checkpoint_sequence_number(),
+            Duration::from_secs(30)
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(head, 42);
+        assert_eq!(faults.calls(method), 4);
+
+        // A persistent fault gives up shortly after the budget runs out
+        let faults = FaultInjector::builder(5).failure_rate(method, 1.0).build();
+        mock.set_fault_injector(faults.clone());
+        let max_elapsed_time = Duration::from_secs(2);
+        let instant = std::time::Instant::now();
+        retry_with_max_elapsed_time!(
+            client.get_latest_checkpoint_sequence_number(),
+            max_elapsed_time
+        )
+        .unwrap_err();
+        assert!(instant.elapsed() < max_elapsed_time * 2);
+        assert!(faults.calls(method) > 1);
+        assert_eq!(faults.calls(method), faults.injected_failures(method));
+    }
 }
@@ -0,0 +1,206 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured records of the lifecycle of a bridge action.
+//!
+//! A `BridgeActionLifecycle` is created when an action enters the system, from
+//! the CLI or the node, and opens a `bridge_action_lifecycle` span. Each
+//! transition is then an event of that span, with the action type, chain id
+//! and nonce as fields of its own so a log pipeline can join the records of an
+//! action without reconstructing spans. With `--log-format json` they are
+//! written as JSON lines.
+
+use crate::types::{BridgeAction, BridgeActionType};
+use starcoin_bridge_types::bridge::BridgeChainId;
+use tracing::{field, info, info_span, Span};
+
+// Target of the lifecycle span and its events, to filter them with
+// `RUST_LOG=bridge_action_lifecycle=info`
+pub const LIFECYCLE_TARGET: &str = "bridge_action_lifecycle";
+
+#[derive(Clone, Debug)]
+pub struct BridgeActionLifecycle {
+    span: Span,
+    action_type: BridgeActionType,
+    chain_id: u8,
+    // Unknown for a deposit until its transaction emitted the deposit event
+    nonce: Option<u64>,
+}
+
+impl BridgeActionLifecycle {
+    pub fn new(action_type: BridgeActionType, chain_id: u8, nonce: u64) -> Self {
+        let mut lifecycle = Self::without_nonce(action_type, chain_id);
+        lifecycle.record_nonce(nonce);
+        lifecycle
+    }
+
+    // A deposit, whose nonce is only recorded once its transaction is executed
+    pub fn without_nonce(action_type: BridgeActionType, chain_id: u8) -> Self {
+        let span = info_span!(
+            target: LIFECYCLE_TARGET,
+            "bridge_action_lifecycle",
+            action_type = %action_type,
+            chain_id,
+            nonce = field::Empty
+        );
+        Self {
+            span,
+            action_type,
+            chain_id,
+            nonce: None,
+        }
+    }
+
+    pub fn for_action(action: &BridgeAction) -> Self {
+        Self::new(
+            action.action_type(),
+            action.chain_id() as u8,
+            action.seq_number(),
+        )
+    }
+
+    pub fn record_nonce(&mut self, nonce: u64) {
+        self.span.record("nonce", nonce);
+        self.nonce = Some(nonce);
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn signatures_requested(&self) {
+        let _enter = self.span.enter();
+        info!(
+            target: LIFECYCLE_TARGET,
+            action_type = %self.action_type,
+            chain_id = self.chain_id,
+            nonce = self.nonce,
+            "signatures_requested"
+        );
+    }
+
+    // `voting_power` is the stake of the signers, when it is known. Signatures
+    // read back from chain come without it.
+    pub fn signatures_collected(&self, count: usize, voting_power: Option<u64>) {
+        let _enter = self.span.enter();
+        info!(
+            target: LIFECYCLE_TARGET,
+            action_type = %self.action_type,
+            chain_id = self.chain_id,
+            nonce = self.nonce,
+            count,
+            voting_power,
+            "signatures_collected"
+        );
+    }
+
+    // `gas` is the gas limit of the transaction, when it is known
+    pub fn tx_built(&self, chain: BridgeChainId, gas: Option<u64>) {
+        let _enter = self.span.enter();
+        info!(
+            target: LIFECYCLE_TARGET,
+            action_type = %self.action_type,
+            chain_id = self.chain_id,
+            nonce = self.nonce,
+            chain = ?chain,
+            gas,
+            "tx_built"
+        );
+    }
+
+    pub fn tx_submitted(&self, hash: &str) {
+        let _enter = self.span.enter();
+        info!(
+            target: LIFECYCLE_TARGET,
+            action_type = %self.action_type,
+            chain_id = self.chain_id,
+            nonce = self.nonce,
+            hash,
+            "tx_submitted"
+        );
+    }
+
+    pub fn tx_confirmed(&self, status: &str) {
+        let _enter = self.span.enter();
+        info!(
+            target: LIFECYCLE_TARGET,
+            action_type = %self.action_type,
+            chain_id = self.chain_id,
+            nonce = self.nonce,
+            status,
+            "tx_confirmed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+
+    #[traced_test]
+    #[test]
+    fn test_lifecycle_events_carry_action_fields() {
+        let lifecycle = BridgeActionLifecycle::new(BridgeActionType::TokenTransfer, 2, 42);
+        lifecycle.signatures_requested();
+        lifecycle.signatures_collected(3, Some(6667));
+        lifecycle.tx_built(BridgeChainId::EthSepolia, Some(21_000));
+        lifecycle.tx_submitted("0xabc");
+        lifecycle.tx_confirmed("success");
+
+        for event in [
+            "signatures_requested",
+            "signatures_collected",
+            "tx_built",
+            "tx_submitted",
+            "tx_confirmed",
+        ] {
+            assert!(logs_contain(event), "missing {event}");
+        }
+        assert!(logs_contain("bridge_action_lifecycle"));
+        assert!(logs_contain("action_type=TokenTransfer"));
+        assert!(logs_contain("chain_id=2"));
+        assert!(logs_contain("nonce=42"));
+        assert!(logs_contain("count=3"));
+        assert!(logs_contain("voting_power=6667"));
+        assert!(logs_contain("gas=21000"));
+        assert!(logs_contain("hash=\"0xabc\""));
+        assert!(logs_contain("status=\"success\""));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_lifecycle_of_action() {
+        let action = crate::test_utils::get_test_starcoin_bridge_to_eth_bridge_action(
+            None,
+            None,
+            Some(7),
+            None,
+            None,
+            None,
+            None,
+        );
+        let lifecycle = BridgeActionLifecycle::for_action(&action);
+        lifecycle.signatures_requested();
+        assert!(logs_contain("signatures_requested"));
+        assert!(logs_contain("nonce=7"));
+        assert!(logs_contain(&format!(
+            "chain_id={}",
+            action.chain_id() as u8
+        )));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_lifecycle_nonce_recorded_later() {
+        let mut lifecycle =
+            BridgeActionLifecycle::without_nonce(BridgeActionType::TokenTransfer, 1);
+        lifecycle.tx_submitted("0xdef");
+        assert!(logs_contain("tx_submitted"));
+        assert!(!logs_contain("nonce="));
+
+        lifecycle.record_nonce(9);
+        lifecycle.tx_confirmed("success");
+        assert!(logs_contain("nonce=9"));
+    }
+}
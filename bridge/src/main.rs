@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use clap::Parser;
 use fastcrypto::traits::KeyPair;
 use starcoin_bridge::config::BridgeNodeConfig;
@@ -30,7 +31,11 @@ struct Args {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let config = BridgeNodeConfig::load(&args.config_path).unwrap();
+    let config = BridgeNodeConfig::load(&args.config_path)
+        .map_err(|e| anyhow!("Couldn't load config {:?}: {:?}", args.config_path, e))?;
+    config
+        .validate()
+        .map_err(|e| anyhow!("Invalid config {:?}: {}", args.config_path, e))?;
 
     // JSON-RPC client is fully async compatible - no runtime conflicts!
 
@@ -48,7 +53,12 @@ async fn main() -> anyhow::Result<()> {
         .with_prom_registry(&prometheus_registry)
         .init();
 
-    let metadata = BridgeNodePublicMetadata::new(VERSION, config.metrics_key_pair.public().clone());
+    info!("Effective config: {}", config.effective_config()?);
+
+    let metadata = BridgeNodePublicMetadata::new(
+        VERSION,
+        config.metrics_key_pair.expose_secret().public().clone(),
+    );
 
     // Start metrics push task if configured
     if let Some(metrics_config) = &config.metrics {
@@ -56,7 +66,7 @@ async fn main() -> anyhow::Result<()> {
             push_interval_seconds: metrics_config.push_interval_seconds.unwrap_or(60),
             push_url: metrics_config.push_url.clone(),
             auth_username: metrics_config.auth_username.clone(),
-            auth_password: metrics_config.auth_password.clone(),
+            auth_password: metrics_config.auth_password.expose_secret().clone(),
         };
         start_metrics_push_task(push_config, registry_service.clone());
     }
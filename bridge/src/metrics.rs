@@ -23,6 +23,7 @@ pub struct BridgeMetrics {
     pub(crate) err_starcoin_bridge_transaction_submission: IntCounter,
     pub(crate) err_starcoin_bridge_transaction_submission_too_many_failures: IntCounter,
     pub(crate) err_starcoin_bridge_transaction_execution: IntCounter,
+    pub(crate) err_eth_deposit_mismatch: IntCounter,
     pub(crate) requests_received: IntCounterVec,
     pub(crate) requests_ok: IntCounterVec,
     pub(crate) err_requests: IntCounterVec,
@@ -35,6 +36,7 @@ pub struct BridgeMetrics {
     pub(crate) starcoin_bridge_watcher_received_events: IntCounter,
     pub(crate) starcoin_bridge_watcher_received_actions: IntCounter,
     pub(crate) starcoin_bridge_watcher_unrecognized_events: IntCounter,
+    pub(crate) starcoin_bridge_unmapped_module_events: IntCounterVec,
     pub(crate) eth_watcher_received_events: IntCounter,
     pub(crate) eth_watcher_received_actions: IntCounter,
     pub(crate) eth_watcher_unrecognized_events: IntCounter,
@@ -43,6 +45,9 @@ pub struct BridgeMetrics {
     pub(crate) action_executor_signing_queue_skipped_actions: IntCounter,
     pub(crate) action_executor_execution_queue_received_actions: IntCounter,
     pub(crate) action_executor_execution_queue_skipped_actions_due_to_pausing: IntCounter,
+    pub(crate) action_executor_signing_queue_depth: IntGauge,
+    pub(crate) action_executor_signing_queue_oldest_item_age_secs: IntGauge,
+    pub(crate) action_executor_unclaimable_recipients: IntCounter,
 
     pub(crate) last_observed_actions_seq_num: IntGaugeVec,
 
@@ -57,6 +62,11 @@ pub struct BridgeMetrics {
     pub(crate) starcoin_bridge_rpc_errors: IntCounterVec,
     pub(crate) observed_governance_actions: IntCounterVec,
     pub(crate) current_bridge_voting_rights: IntGaugeVec,
+    pub(crate) token_module_changed: IntGaugeVec,
+    pub(crate) committee_max_group_voting_power: IntGaugeVec,
+    pub(crate) committee_concentration_alerts: IntGauge,
+    pub(crate) node_build_info: IntGaugeVec,
+    pub(crate) registration_mismatch: IntGauge,
 
     pub(crate) auth_agg_ok_responses: IntCounterVec,
     pub(crate) auth_agg_bad_responses: IntCounterVec,
@@ -65,6 +75,17 @@ pub struct BridgeMetrics {
     pub(crate) starcoin_bridge_eth_token_transfer_claimed: IntCounter,
     pub(crate) eth_starcoin_bridge_token_transfer_approved: IntCounter,
     pub(crate) eth_starcoin_bridge_token_transfer_claimed: IntCounter,
+
+    // Claims submitted by `bridge-cli client auto-claim`
+    pub auto_claim_attempted: IntCounter,
+    pub auto_claim_succeeded: IntCounter,
+    pub auto_claim_failed: IntCounter,
+
+    // Governance and client commands of `bridge-cli`
+    pub cli_signature_aggregation_latency: HistogramVec,
+    pub cli_authority_request_failures: IntCounterVec,
+    pub cli_transaction_submissions: IntCounterVec,
+    pub cli_claim_latency: HistogramVec,
 }
 
 impl BridgeMetrics {
@@ -106,6 +127,12 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            err_eth_deposit_mismatch: register_int_counter_with_registry!(
+                "bridge_err_eth_deposit_mismatch",
+                "Total number of certified actions refused because they do not match their Eth deposit",
+                registry,
+            )
+            .unwrap(),
             requests_received: register_int_counter_vec_with_registry!(
                 "bridge_requests_received",
                 "Total number of requests received in Server, by request type",
@@ -164,6 +191,13 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            starcoin_bridge_unmapped_module_events: register_int_counter_vec_with_registry!(
+                "bridge_starcoin_bridge_unmapped_module_events",
+                "Total number of events of the bridge package from modules the node has no name mapping for, by module",
+                &["module"],
+                registry,
+            )
+            .unwrap(),
             eth_watcher_unrecognized_events: register_int_counter_with_registry!(
                 "bridge_eth_watcher_unrecognized_events",
                 "Total number of unrecognized events in eth watcher",
@@ -200,6 +234,24 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            action_executor_signing_queue_depth: register_int_gauge_with_registry!(
+                "bridge_action_executor_signing_queue_depth",
+                "Number of actions waiting in the action executor signing queue",
+                registry,
+            )
+            .unwrap(),
+            action_executor_signing_queue_oldest_item_age_secs: register_int_gauge_with_registry!(
+                "bridge_action_executor_signing_queue_oldest_item_age_secs",
+                "Seconds the action at the head of the signing queue has been waiting",
+                registry,
+            )
+            .unwrap(),
+            action_executor_unclaimable_recipients: register_int_counter_with_registry!(
+                "bridge_action_executor_unclaimable_recipients",
+                "Total number of token transfers whose recipient can never claim them",
+                registry,
+            )
+            .unwrap(),
             gas_coin_balance: register_int_gauge_with_registry!(
                 "bridge_gas_coin_balance",
                 "Current balance of gas coin, in mist",
@@ -283,6 +335,39 @@ impl BridgeMetrics {
                 registry
             )
             .unwrap(),
+            token_module_changed: register_int_gauge_vec_with_registry!(
+                "bridge_token_module_changed",
+                "1 if the token's Starcoin Move module differs from the one recorded at registration",
+                &["token_id"],
+                registry,
+            )
+            .unwrap(),
+            committee_max_group_voting_power: register_int_gauge_vec_with_registry!(
+                "bridge_committee_max_group_voting_power",
+                "Largest voting power held by committee members sharing an eth address, host, ip or asn",
+                &["dimension"],
+                registry,
+            )
+            .unwrap(),
+            committee_concentration_alerts: register_int_gauge_with_registry!(
+                "bridge_committee_concentration_alerts",
+                "Number of committee member groups holding more voting power than allowed",
+                registry,
+            )
+            .unwrap(),
+            node_build_info: register_int_gauge_vec_with_registry!(
+                "bridge_node_build_info",
+                "Always 1, labelled with the node version, git revision and config fingerprint",
+                &["version", "git_revision", "config_fingerprint"],
+                registry,
+            )
+            .unwrap(),
+            registration_mismatch: register_int_gauge_with_registry!(
+                "bridge_node_registration_mismatch",
+                "1 while the node's authority key or url doesn't match its committee registration",
+                registry,
+            )
+            .unwrap(),
             auth_agg_ok_responses: register_int_counter_vec_with_registry!(
                 "bridge_auth_agg_ok_responses",
                 "Total number of ok response from auth agg",
@@ -325,6 +410,54 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            auto_claim_attempted: register_int_counter_with_registry!(
+                "bridge_auto_claim_attempted",
+                "Total number of claim transactions submitted by the auto-claimer",
+                registry,
+            )
+            .unwrap(),
+            auto_claim_succeeded: register_int_counter_with_registry!(
+                "bridge_auto_claim_succeeded",
+                "Total number of transfers claimed by the auto-claimer",
+                registry,
+            )
+            .unwrap(),
+            auto_claim_failed: register_int_counter_with_registry!(
+                "bridge_auto_claim_failed",
+                "Total number of failed claims of the auto-claimer",
+                registry,
+            )
+            .unwrap(),
+            cli_signature_aggregation_latency: register_histogram_vec_with_registry!(
+                "bridge_cli_signature_aggregation_latency",
+                "Latency of collecting the committee signatures of an action in the cli, by action type",
+                &["action_type"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            cli_authority_request_failures: register_int_counter_vec_with_registry!(
+                "bridge_cli_authority_request_failures",
+                "Total number of signature requests of the cli an authority failed, by authority and outcome",
+                &["authority", "outcome"],
+                registry,
+            )
+            .unwrap(),
+            cli_transaction_submissions: register_int_counter_vec_with_registry!(
+                "bridge_cli_transaction_submissions",
+                "Total number of transactions submitted by the cli, by chain and outcome",
+                &["chain", "outcome"],
+                registry,
+            )
+            .unwrap(),
+            cli_claim_latency: register_histogram_vec_with_registry!(
+                "bridge_cli_claim_latency",
+                "End to end latency of claims submitted by the cli, by target chain and status",
+                &["chain", "status"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
         }
     }
 
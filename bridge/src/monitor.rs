@@ -449,6 +449,10 @@ async fn get_latest_bridge_pause_status_with_emergency_event<C: StarcoinClientIn
 ) -> IsBridgePaused {
     let mut remaining_retry_times = REFRESH_BRIDGE_RETRY_TIMES;
     loop {
+        // The event means the cached summary is outdated
+        starcoin_bridge_client
+            .invalidate_bridge_summary_cache()
+            .await;
         let Ok(Ok(summary)) = retry_with_max_elapsed_time!(
             starcoin_bridge_client.get_bridge_summary(),
             Duration::from_secs(600)
@@ -3,8 +3,9 @@
 
 use crate::config::WatchdogConfig;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::eth_client::{EthClient, EthDepositSource, EthDepositSources};
 use crate::metered_eth_provider::MeteredEthHttpProvier;
-use crate::starcoin_bridge_client::StarcoinBridgeClient;
+use crate::starcoin_bridge_client::{StarcoinBridgeClient, StarcoinClient, StarcoinClientInner};
 use crate::starcoin_bridge_watchdog::eth_bridge_status::EthBridgeStatus;
 use crate::starcoin_bridge_watchdog::eth_vault_balance::{EthereumVaultBalance, VaultAsset};
 use crate::starcoin_bridge_watchdog::metrics::WatchdogMetrics;
@@ -17,24 +18,34 @@ use crate::utils::{
 use crate::{
     action_executor::BridgeActionExecutor,
     client::bridge_authority_aggregator::BridgeAuthorityAggregator,
+    committee_concentration::{check_committee_concentration, AsnResolver, AsnTable},
     config::{BridgeClientConfig, BridgeNodeConfig},
     eth_syncer::EthSyncer,
     events::init_all_struct_tags,
     metrics::BridgeMetrics,
     monitor::BridgeMonitor,
+    node_info::NodeInfo,
+    node_status::{refresh_node_status, NodeIdentity, NodeStatusCache},
     orchestrator::BridgeOrchestrator,
+    registration::{check_registration, watch_node_registration, RegistrationGuard},
+    scheduled_actions::{ActionScheduler, ScheduledActionStatus},
     server::{handler::BridgeRequestHandler, run_server, BridgeNodePublicMetadata},
     starcoin_bridge_syncer::StarcoinSyncer,
+    startup::{finish_steps, timed_step},
     storage::BridgeOrchestratorTables,
+    token_modules::{TokenModuleChange, TokenModuleWatcher, TOKEN_MODULE_CHECK_INTERVAL},
 };
 use arc_swap::ArcSwap;
 use ethers::providers::Provider;
 use ethers::types::Address as EthAddress;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
 use starcoin_bridge_types::{
     bridge::{
-        BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_LIMITER_MODULE_NAME, BRIDGE_MODULE_NAME,
-        BRIDGE_TREASURY_MODULE_NAME,
+        BridgeChainId, BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_LIMITER_MODULE_NAME,
+        BRIDGE_MODULE_NAME, BRIDGE_TREASURY_MODULE_NAME,
     },
+    crypto::StarcoinKeyPair,
     event::EventID,
     Identifier,
 };
@@ -51,23 +62,40 @@ use tracing::info;
 
 pub async fn run_bridge_node(
     config: BridgeNodeConfig,
-    metadata: BridgeNodePublicMetadata,
+    mut metadata: BridgeNodePublicMetadata,
     prometheus_registry: prometheus::Registry,
 ) -> anyhow::Result<JoinHandle<()>> {
     init_all_struct_tags();
     let metrics = Arc::new(BridgeMetrics::new(&prometheus_registry));
+    let node_info = NodeInfo::new(&config)?;
+    info!(
+        "Bridge node version {}, features {:?}, config fingerprint {}",
+        node_info.version, node_info.features, node_info.config_fingerprint
+    );
+    metrics
+        .node_build_info
+        .with_label_values(&[
+            node_info.version.to_string().as_str(),
+            node_info.version.git_revision.as_deref().unwrap_or(""),
+            node_info.config_fingerprint.as_str(),
+        ])
+        .set(1);
+    metadata.expose_node_info = config.expose_node_info.unwrap_or(true);
+    metadata.node_info = Some(node_info);
     let watchdog_config = config.watchdog_config.clone();
-    let (server_config, client_config) = config.validate(metrics.clone()).await?;
-    let starcoin_bridge_chain_identifier = server_config
-        .starcoin_bridge_client
-        .get_chain_identifier()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to get starcoin chain identifier: {:?}", e))?;
-    let eth_chain_identifier = server_config
-        .eth_client
-        .get_chain_id()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to get eth chain identifier: {:?}", e))?;
+    let concentration_config = config.committee_concentration.clone().unwrap_or_default();
+    let asn_table = concentration_config
+        .asn_table_path
+        .as_deref()
+        .map(AsnTable::load)
+        .transpose()?
+        .map(|table| Arc::new(table) as Arc<dyn AsnResolver>);
+    let (server_config, client_config) = config.prepare(metrics.clone()).await?;
+    let (starcoin_bridge_chain_identifier, eth_chain_identifier, committee) = fetch_chain_info(
+        &server_config.starcoin_bridge_client,
+        &server_config.eth_client,
+    )
+    .await?;
     prometheus_registry
         .register(starcoin_metrics::bridge_uptime_metric(
             "bridge",
@@ -78,25 +106,69 @@ pub async fn run_bridge_node(
         ))
         .unwrap();
 
-    let committee = Arc::new(
-        server_config
-            .starcoin_bridge_client
-            .get_bridge_committee()
-            .await
-            .expect("Failed to get committee"),
-    );
+    let committee = Arc::new(committee);
     let mut handles = vec![];
 
+    // Identify the node on `/status`
+    let pubkey = BridgeAuthorityPublicKeyBytes::from(server_config.key.public());
+    metadata.status.identity = Some(NodeIdentity {
+        public_key: Hex::encode(pubkey.as_bytes()),
+        registered_url: committee
+            .member(&pubkey)
+            .map(|member| member.base_url.clone()),
+    });
+
+    // Check the node is registered the way it is configured before serving
+    // any signing request, and again as the committee changes
+    let registration = RegistrationGuard::new(
+        config
+            .refuse_signing_on_registration_mismatch
+            .unwrap_or(false),
+    );
+    registration.record(
+        check_registration(&committee, &pubkey, config.external_url.as_deref()),
+        &metrics,
+    );
+    metadata.status.registration = registration.clone();
+    handles.push(spawn_logged_monitored_task!(watch_node_registration(
+        server_config.starcoin_bridge_client.clone(),
+        pubkey,
+        config.external_url.clone(),
+        registration.clone(),
+        metrics.clone(),
+    )));
+
+    // Keep the state `/status` reports fresh
+    handles.push(spawn_logged_monitored_task!(refresh_node_status(
+        metadata.status.clone(),
+        server_config.starcoin_bridge_client.clone(),
+        committee.clone(),
+    )));
+
     // Start watchdog
     let eth_provider = server_config.eth_client.provider();
     let eth_bridge_proxy_address = server_config.eth_bridge_proxy_address;
     let starcoin_bridge_client = server_config.starcoin_bridge_client.clone();
+    let paused_token_ids = metadata.paused_token_ids.clone();
+    let status = metadata.status.clone();
     handles.push(spawn_logged_monitored_task!(start_watchdog(
         watchdog_config,
         &prometheus_registry,
         eth_provider,
         eth_bridge_proxy_address,
-        starcoin_bridge_client
+        starcoin_bridge_client,
+        paused_token_ids,
+        status
+    )));
+
+    // Check how much voting power members sharing infrastructure hold. Host
+    // resolution can be slow, so this does not hold up startup.
+    handles.push(spawn_logged_monitored_task!(check_committee_concentration(
+        committee.clone(),
+        concentration_config.max_group_voting_power_percent,
+        asn_table,
+        metadata.committee_concentration_alerts.clone(),
+        metrics.clone(),
     )));
 
     // Update voting right metrics
@@ -124,6 +196,8 @@ pub async fn run_bridge_node(
             client_config,
             committee.clone(),
             committee_keys_to_names,
+            metadata.token_module_changes.clone(),
+            metadata.scheduled_actions.clone(),
             metrics.clone(),
         )
         .await?;
@@ -151,8 +225,9 @@ pub async fn run_bridge_node(
         BridgeRequestHandler::new(
             server_config.key,
             server_config.starcoin_bridge_client,
-            server_config.eth_client,
+            server_config.eth_clients,
             server_config.approved_governance_actions,
+            registration,
             metrics.clone(),
         ),
         metrics,
@@ -166,6 +241,8 @@ async fn start_watchdog(
     eth_provider: Arc<Provider<MeteredEthHttpProvier>>,
     eth_bridge_proxy_address: EthAddress,
     starcoin_bridge_client: Arc<StarcoinBridgeClient>,
+    paused_token_ids: Arc<ArcSwap<Vec<u8>>>,
+    status: NodeStatusCache,
 ) {
     let watchdog_metrics = WatchdogMetrics::new(registry);
     let (
@@ -231,11 +308,14 @@ async fn start_watchdog(
         eth_provider,
         eth_bridge_proxy_address,
         watchdog_metrics.eth_bridge_paused.clone(),
+        status.eth_bridge_paused,
     );
 
     let starcoin_bridge_status = StarcoinBridgeStatus::new(
         starcoin_bridge_client.clone(),
         watchdog_metrics.starcoin_bridge_paused.clone(),
+        paused_token_ids,
+        status.starcoin_bridge_paused,
     );
 
     let mut observables: Vec<Box<dyn Observable + Send + Sync>> = vec![
@@ -267,11 +347,49 @@ async fn start_watchdog(
     BridgeWatchDog::new(observables).run().await
 }
 
+// Fetches the chain identifiers and the committee. They are independent, so
+// they are fetched concurrently.
+async fn fetch_chain_info<C, P>(
+    starcoin_bridge_client: &StarcoinClient<C>,
+    eth_client: &EthClient<P>,
+) -> anyhow::Result<(String, u64, BridgeCommittee)>
+where
+    C: StarcoinClientInner,
+    P: ethers::providers::JsonRpcClient,
+{
+    let (starcoin_bridge_chain_identifier, eth_chain_identifier, committee) = tokio::join!(
+        timed_step(
+            "starcoin chain identifier",
+            starcoin_bridge_client.get_chain_identifier()
+        ),
+        timed_step("eth chain id", eth_client.get_chain_id()),
+        timed_step(
+            "bridge committee",
+            starcoin_bridge_client.get_bridge_committee()
+        ),
+    );
+    finish_steps(
+        "Node startup",
+        &[
+            &starcoin_bridge_chain_identifier,
+            &eth_chain_identifier,
+            &committee,
+        ],
+    )?;
+    Ok((
+        starcoin_bridge_chain_identifier.into_result()?,
+        eth_chain_identifier.into_result()?,
+        committee.into_result()?,
+    ))
+}
+
 // TODO: is there a way to clean up the overrides after it's stored in DB?
 async fn start_client_components(
     client_config: BridgeClientConfig,
     committee: Arc<BridgeCommittee>,
     committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    token_module_changes: Arc<ArcSwap<Vec<TokenModuleChange>>>,
+    scheduled_actions: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
     metrics: Arc<BridgeMetrics>,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
     let store: std::sync::Arc<BridgeOrchestratorTables> =
@@ -280,12 +398,17 @@ async fn start_client_components(
         &store,
         client_config.starcoin_bridge_module_last_processed_event_id_override,
     );
-    let eth_contracts_to_watch = get_eth_contracts_to_watch(
-        &store,
-        &client_config.eth_contracts,
-        client_config.eth_contracts_start_block_fallback,
-        client_config.eth_contracts_start_block_override,
-    );
+    // Cursors written before they were keyed by chain belong to `eth`, the
+    // only chain watched back then
+    let migrated = store
+        .migrate_eth_event_cursors(client_config.eth_chains[0].chain_id)
+        .expect("Failed to migrate eth event cursors in storage");
+    if migrated > 0 {
+        info!(
+            "Moved {} eth event cursors under chain {}",
+            migrated, client_config.eth_chains[0].chain_id
+        );
+    }
 
     let starcoin_bridge_client = client_config.starcoin_bridge_client.clone();
 
@@ -305,12 +428,23 @@ async fn start_client_components(
     );
 
     let mut all_handles = vec![];
-    let (task_handles, eth_events_rx, _) =
-        EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch)
-            .run(metrics.clone())
-            .await
-            .expect("Failed to start eth syncer");
-    all_handles.extend(task_handles);
+    let mut eth_events_rxs = vec![];
+    for eth_chain in &client_config.eth_chains {
+        let eth_contracts_to_watch = get_eth_contracts_to_watch(
+            &store,
+            eth_chain.chain_id,
+            &eth_chain.eth_contracts,
+            eth_chain.eth_contracts_start_block_fallback,
+            eth_chain.eth_contracts_start_block_override,
+        );
+        let (task_handles, eth_events_rx, _) =
+            EthSyncer::new(eth_chain.eth_client.clone(), eth_contracts_to_watch)
+                .run(metrics.clone())
+                .await
+                .expect("Failed to start eth syncer");
+        all_handles.extend(task_handles);
+        eth_events_rxs.push((eth_chain.chain_id, eth_events_rx));
+    }
 
     let (task_handles, starcoin_bridge_events_rx) = StarcoinSyncer::new(
         client_config.starcoin_bridge_client,
@@ -352,6 +486,22 @@ async fn start_client_components(
 
     let starcoin_bridge_token_type_tags =
         Arc::new(ArcSwap::from(Arc::new(starcoin_bridge_token_type_tags)));
+    if let Some(config) = &client_config.scheduled_actions {
+        // The executor takes ownership of the client key
+        let key = StarcoinKeyPair::from_bytes(&client_config.key.to_bytes())
+            .map_err(|e| anyhow::anyhow!("Failed to copy client key: {:?}", e))?;
+        let scheduler = ActionScheduler::new(
+            config.dir.clone(),
+            starcoin_bridge_client.clone(),
+            key,
+            client_config.starcoin_bridge_address,
+            scheduled_actions,
+        );
+        all_handles.push(spawn_logged_monitored_task!(
+            scheduler.run(Duration::from_secs(config.poll_interval_secs))
+        ));
+    }
+
     let bridge_action_executor = BridgeActionExecutor::new(
         starcoin_bridge_client.clone(),
         bridge_auth_agg.clone(),
@@ -361,10 +511,37 @@ async fn start_client_components(
         client_config.gas_object_ref.0,
         starcoin_bridge_token_type_tags.clone(),
         bridge_pause_rx,
+        client_config
+            .eth_chains
+            .iter()
+            .map(|eth_chain| {
+                (
+                    eth_chain.chain_id,
+                    eth_chain.eth_client.clone() as Arc<dyn EthDepositSource>,
+                )
+            })
+            .collect::<EthDepositSources>(),
+        client_config
+            .eth_chains
+            .iter()
+            .flat_map(|eth_chain| eth_chain.eth_contracts.clone())
+            .collect(),
+        client_config.action_queue.clone(),
         metrics.clone(),
     )
     .await;
 
+    let token_module_watcher = TokenModuleWatcher::new(
+        starcoin_bridge_client.clone(),
+        store.clone(),
+        starcoin_bridge_token_type_tags.clone(),
+        token_module_changes,
+        metrics.clone(),
+    );
+    all_handles.push(spawn_logged_monitored_task!(
+        token_module_watcher.run(TOKEN_MODULE_CHECK_INTERVAL)
+    ));
+
     let monitor = BridgeMonitor::new(
         starcoin_bridge_client.clone(),
         starcoin_bridge_monitor_rx,
@@ -379,7 +556,7 @@ async fn start_client_components(
     let orchestrator = BridgeOrchestrator::new(
         starcoin_bridge_client,
         starcoin_bridge_events_rx,
-        eth_events_rx,
+        eth_events_rxs,
         store.clone(),
         starcoin_bridge_monitor_tx,
         eth_monitor_tx,
@@ -433,12 +610,13 @@ fn get_starcoin_bridge_modules_to_watch(
 
 fn get_eth_contracts_to_watch(
     store: &std::sync::Arc<BridgeOrchestratorTables>,
+    chain_id: BridgeChainId,
     eth_contracts: &[EthAddress],
     eth_contracts_start_block_fallback: u64,
     eth_contracts_start_block_override: Option<u64>,
 ) -> HashMap<EthAddress, u64> {
     let stored_eth_cursors = store
-        .get_eth_event_cursors(eth_contracts)
+        .get_eth_event_cursors(chain_id, eth_contracts)
         .expect("Failed to get eth event cursors from storage");
     let mut eth_contracts_to_watch = HashMap::new();
     for (contract, stored_cursor) in eth_contracts.iter().zip(stored_eth_cursors) {
@@ -448,8 +626,8 @@ fn get_eth_contracts_to_watch(
             (Some(override_), _) => {
                 eth_contracts_to_watch.insert(*contract, override_);
                 info!(
-                    "Overriding cursor for eth bridge contract {} to {}. Stored cursor: {:?}",
-                    contract, override_, stored_cursor
+                    "Overriding cursor for eth bridge contract {} on chain {} to {}. Stored cursor: {:?}",
+                    contract, chain_id, override_, stored_cursor
                 );
             }
             (None, Some(stored_cursor)) => {
@@ -482,7 +660,8 @@ mod tests {
         let store = BridgeOrchestratorTables::new(temp_dir.path());
 
         // No override, no watermark found in DB, use fallback
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, None);
+        let contracts =
+            get_eth_contracts_to_watch(&store, BridgeChainId::EthCustom, &eth_contracts, 10, None);
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 10), (eth_contracts[1], 10)]
@@ -491,7 +670,13 @@ mod tests {
         );
 
         // no watermark found in DB, use override
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, Some(420));
+        let contracts = get_eth_contracts_to_watch(
+            &store,
+            BridgeChainId::EthCustom,
+            &eth_contracts,
+            10,
+            Some(420),
+        );
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 420), (eth_contracts[1], 420)]
@@ -500,14 +685,15 @@ mod tests {
         );
 
         store
-            .update_eth_event_cursor(eth_contracts[0], 100)
+            .update_eth_event_cursor(BridgeChainId::EthCustom, eth_contracts[0], 100)
             .unwrap();
         store
-            .update_eth_event_cursor(eth_contracts[1], 102)
+            .update_eth_event_cursor(BridgeChainId::EthCustom, eth_contracts[1], 102)
             .unwrap();
 
         // No override, found watermarks in DB, use +1
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, None);
+        let contracts =
+            get_eth_contracts_to_watch(&store, BridgeChainId::EthCustom, &eth_contracts, 10, None);
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 101), (eth_contracts[1], 103)]
@@ -515,8 +701,24 @@ mod tests {
                 .collect::<HashMap<_, _>>()
         );
 
+        // The same contracts on another chain have no watermark yet
+        let contracts =
+            get_eth_contracts_to_watch(&store, BridgeChainId::EthSepolia, &eth_contracts, 10, None);
+        assert_eq!(
+            contracts,
+            vec![(eth_contracts[0], 10), (eth_contracts[1], 10)]
+                .into_iter()
+                .collect::<HashMap<_, _>>()
+        );
+
         // use override
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, Some(200));
+        let contracts = get_eth_contracts_to_watch(
+            &store,
+            BridgeChainId::EthCustom,
+            &eth_contracts,
+            10,
+            Some(200),
+        );
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 200), (eth_contracts[1], 200)]
@@ -606,6 +808,77 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_fetch_chain_info_runs_steps_concurrently() {
+        use crate::eth_mock_provider::EthMockProvider;
+        use crate::fault_injection::FaultInjector;
+        use crate::starcoin_bridge_mock_client::StarcoinMockClient;
+        use crate::test_utils::{
+            bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+        };
+        use ethers::types::U256;
+        use starcoin_bridge_types::bridge::BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER;
+        use std::collections::HashSet;
+
+        let latency = Duration::from_millis(300);
+        let (authority, _, _) =
+            get_test_authority_and_key(BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, 9999);
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+        let starcoin_mock = StarcoinMockClient::default().with_chain_identifier("254");
+        starcoin_mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+            committee.clone(),
+        ));
+        let slow_starcoin = FaultInjector::builder(0)
+            .latency("get_chain_identifier", latency, latency)
+            .latency("get_bridge_summary", latency, latency)
+            .build();
+        starcoin_mock.set_fault_injector(slow_starcoin);
+        let eth_mock = EthMockProvider::new();
+        eth_mock
+            .add_response("eth_chainId", (), U256::from(12))
+            .unwrap();
+        eth_mock.set_fault_injector(
+            FaultInjector::builder(0)
+                .latency("eth_chainId", latency, latency)
+                .build(),
+        );
+        let starcoin_client = StarcoinClient::new_for_testing(starcoin_mock.clone());
+        let eth_client = EthClient::new_mocked(eth_mock, HashSet::new());
+
+        // Three slow steps take about as long as the slowest one, not the sum
+        let start = std::time::Instant::now();
+        let (starcoin_chain_identifier, eth_chain_id, fetched_committee) =
+            fetch_chain_info(&starcoin_client, &eth_client)
+                .await
+                .unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= latency);
+        assert!(elapsed < latency * 2, "startup took {:?}", elapsed);
+        assert_eq!(starcoin_chain_identifier, "254");
+        assert_eq!(eth_chain_id, 12);
+        assert_eq!(
+            fetched_committee.members().keys().collect::<Vec<_>>(),
+            committee.members().keys().collect::<Vec<_>>()
+        );
+
+        // A failed step fails startup and reports how the others went
+        starcoin_mock.set_fault_injector(
+            FaultInjector::builder(0)
+                .fail_first("get_bridge_summary", 1)
+                .build(),
+        );
+        let err = fetch_chain_info(&starcoin_client, &eth_client)
+            .await
+            .unwrap_err();
+        let rendered = format!("{:#}", err);
+        assert!(rendered.contains("bridge committee failed"), "{rendered}");
+        assert!(
+            rendered.contains("starcoin chain identifier: ok"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("eth chain id: ok"), "{rendered}");
+    }
+
     // NOTE: The following tests are disabled because they require e2e test infrastructure
     // (BridgeTestCluster, BridgeTestClusterBuilder) which depends on Sui test cluster.
     // They should be enabled once we have proper Starcoin test infrastructure.
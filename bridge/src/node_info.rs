@@ -0,0 +1,261 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Build and configuration details a node reports about itself, so operators
+//! can see which versions the committee runs. Served on the health endpoint
+//! and, unless disabled with `expose-node-info`, on `/info`.
+
+use crate::config::BridgeNodeConfig;
+use crate::encoding::{
+    ADD_TOKENS_ON_EVM_MESSAGE_VERSION, ADD_TOKENS_ON_STARCOIN_MESSAGE_VERSION,
+    ASSET_PRICE_UPDATE_MESSAGE_VERSION, COMMITTEE_BLOCKLIST_MESSAGE_VERSION,
+    EMERGENCY_BUTTON_MESSAGE_VERSION, EVM_CONTRACT_UPGRADE_MESSAGE_VERSION,
+    LIMIT_UPDATE_MESSAGE_VERSION, TOKEN_PAUSE_MESSAGE_VERSION, TOKEN_TRANSFER_MESSAGE_VERSION,
+};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+// Git revision the binary was built from, embedded by build.rs. Empty when
+// the build had no access to it.
+pub const GIT_REVISION: &str = env!("BRIDGE_GIT_REVISION");
+
+// Reported for nodes whose version could not be fetched or parsed
+pub const UNKNOWN_VERSION: &str = "unknown";
+
+// Length of the config fingerprint, in hex characters
+const CONFIG_FINGERPRINT_LEN: usize = 16;
+
+const MESSAGE_VERSIONS: [u8; 9] = [
+    TOKEN_TRANSFER_MESSAGE_VERSION,
+    COMMITTEE_BLOCKLIST_MESSAGE_VERSION,
+    EMERGENCY_BUTTON_MESSAGE_VERSION,
+    LIMIT_UPDATE_MESSAGE_VERSION,
+    ASSET_PRICE_UPDATE_MESSAGE_VERSION,
+    EVM_CONTRACT_UPGRADE_MESSAGE_VERSION,
+    ADD_TOKENS_ON_STARCOIN_MESSAGE_VERSION,
+    ADD_TOKENS_ON_EVM_MESSAGE_VERSION,
+    TOKEN_PAUSE_MESSAGE_VERSION,
+];
+
+// Semver version of a node, e.g. `0.1.0`, `0.2.0-rc.1` or `0.1.0+1a2b3c4d5e6f`
+// where the build metadata is the git revision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+    pub git_revision: Option<String>,
+}
+
+impl NodeVersion {
+    // Version of this binary
+    pub fn current() -> Self {
+        let mut version = env!("CARGO_PKG_VERSION")
+            .parse::<Self>()
+            .expect("CARGO_PKG_VERSION is a valid semver version");
+        version.git_revision = (!GIT_REVISION.is_empty()).then(|| GIT_REVISION.to_string());
+        version
+    }
+}
+
+impl FromStr for NodeVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_identifier = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-.".contains(c))
+        };
+        let (rest, git_revision) = match s.split_once('+') {
+            Some((rest, revision)) if is_identifier(revision) => (rest, Some(revision.to_string())),
+            Some(_) => anyhow::bail!("Invalid build metadata in version {:?}", s),
+            None => (s, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) if is_identifier(pre) => (core, Some(pre.to_string())),
+            Some(_) => anyhow::bail!("Invalid pre-release in version {:?}", s),
+            None => (rest, None),
+        };
+        let parts = core
+            .split('.')
+            .map(|part| {
+                if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                    anyhow::bail!("Invalid version {:?}", s);
+                }
+                Ok(part.parse::<u64>()?)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let [major, minor, patch] = parts[..] else {
+            anyhow::bail!("Invalid version {:?}, expected major.minor.patch", s);
+        };
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            git_revision,
+        })
+    }
+}
+
+impl fmt::Display for NodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(git_revision) = &self.git_revision {
+            write!(f, "+{}", git_revision)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for NodeVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NodeInfo {
+    pub version: NodeVersion,
+    // Range of bridge message versions this node can sign
+    pub min_message_version: u8,
+    pub max_message_version: u8,
+    // Optional components enabled in the config
+    pub features: Vec<String>,
+    // Hash of the redacted config, to tell whether nodes run the same config
+    // without revealing it
+    pub config_fingerprint: String,
+}
+
+impl NodeInfo {
+    pub fn new(config: &BridgeNodeConfig) -> anyhow::Result<Self> {
+        let mut features = vec![];
+        if config.run_client {
+            features.push("client");
+        }
+        if config.watchdog_config.is_some() {
+            features.push("watchdog");
+        }
+        if config.metrics.is_some() {
+            features.push("metrics-push");
+        }
+        if config.scheduled_actions.is_some() {
+            features.push("scheduled-actions");
+        }
+        if config.committee_concentration.is_some() {
+            features.push("committee-concentration");
+        }
+        let rendered = serde_json::to_vec(&config.effective_config()?)?;
+        let mut config_fingerprint = Hex::encode(Sha3_256::digest(rendered).digest);
+        config_fingerprint.truncate(CONFIG_FINGERPRINT_LEN);
+        Ok(Self {
+            version: NodeVersion::current(),
+            min_message_version: *MESSAGE_VERSIONS.iter().min().unwrap(),
+            max_message_version: *MESSAGE_VERSIONS.iter().max().unwrap(),
+            features: features.into_iter().map(String::from).collect(),
+            config_fingerprint,
+        })
+    }
+}
+
+// Version reported in an `/info` response body, or "unknown" if the body is
+// not valid node info.
+pub fn version_from_info_response(body: &[u8]) -> String {
+    serde_json::from_slice::<NodeInfo>(body)
+        .map(|info| info.version.to_string())
+        .unwrap_or_else(|_| UNKNOWN_VERSION.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_version_parse_and_display() {
+        for s in [
+            "0.1.0",
+            "1.22.333",
+            "0.2.0-rc.1",
+            "0.1.0+1a2b3c4d5e6f",
+            "1.0.0-beta+abc",
+        ] {
+            assert_eq!(s.parse::<NodeVersion>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            "0.2.0-rc.1+abc".parse::<NodeVersion>().unwrap(),
+            NodeVersion {
+                major: 0,
+                minor: 2,
+                patch: 0,
+                pre: Some("rc.1".to_string()),
+                git_revision: Some("abc".to_string()),
+            }
+        );
+        for s in [
+            "",
+            "1",
+            "1.2",
+            "1.2.3.4",
+            "a.b.c",
+            "1.2.3-",
+            "1.2.3+",
+            "1.-2.3",
+            "1.2.3+a b",
+        ] {
+            assert!(
+                s.parse::<NodeVersion>().is_err(),
+                "{:?} should not parse",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_current_version_is_embedded_at_build_time() {
+        let version = NodeVersion::current();
+        assert!(version.to_string().starts_with(env!("CARGO_PKG_VERSION")));
+        assert!(GIT_REVISION
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-.".contains(c)));
+        assert_eq!(version.git_revision.as_deref().unwrap_or(""), GIT_REVISION);
+        // Round trips through its string form
+        assert_eq!(version.to_string().parse::<NodeVersion>().unwrap(), version);
+    }
+
+    #[test]
+    fn test_version_from_info_response() {
+        let info = NodeInfo {
+            version: "0.1.0+abc".parse().unwrap(),
+            min_message_version: 1,
+            max_message_version: 1,
+            features: vec!["client".to_string()],
+            config_fingerprint: "0011223344556677".to_string(),
+        };
+        let body = serde_json::to_vec(&info).unwrap();
+        assert_eq!(version_from_info_response(&body), "0.1.0+abc");
+
+        assert_eq!(version_from_info_response(b""), UNKNOWN_VERSION);
+        assert_eq!(version_from_info_response(b"<html>"), UNKNOWN_VERSION);
+        assert_eq!(
+            version_from_info_response(br#"{"version":"not a version"}"#),
+            UNKNOWN_VERSION
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operational status of a node, served as JSON on `/status`. It is read from
+//! state the node's tasks keep up to date, the syncers' progress gauges and
+//! the caches below, so serving it costs no RPC calls.
+
+use crate::client::ping::{ping_urls_with_timeout, PingResult, DEFAULT_PING_TIMEOUT};
+use crate::metrics::BridgeMetrics;
+use crate::registration::RegistrationGuard;
+use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use crate::types::{BridgeAuthority, BridgeCommittee};
+use arc_swap::ArcSwap;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use prometheus::core::Collector;
+use prometheus::IntGaugeVec;
+use serde::Serialize;
+use starcoin_bridge_types::bridge::APPROVAL_THRESHOLD_TOKEN_TRANSFER;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+// How often the committee is pinged and the Starcoin head refreshed
+pub const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NodeStatus {
+    // Hex of the node's bridge authority public key
+    pub public_key: Option<String>,
+    // Url the node is registered with in the committee
+    pub registered_url: Option<String>,
+    // Why the registration doesn't match the committee, as last checked
+    pub registration_mismatch: Option<String>,
+    pub starcoin: ChainStatus,
+    pub eth: ChainStatus,
+    // Actions waiting for committee signatures
+    pub pending_actions: i64,
+    // None until the first check completes
+    pub committee: Option<CommitteeReachability>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ChainStatus {
+    // Latest block as last observed, the latest finalized one on Eth
+    pub head_block: Option<u64>,
+    // None until the watchdog first reads it
+    pub paused: Option<bool>,
+    // Empty unless the node runs the client
+    pub syncers: Vec<SyncerStatus>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SyncerStatus {
+    // Starcoin module or Eth contract the task syncs the events of
+    pub task: String,
+    pub last_processed_block: u64,
+    // Blocks behind `head-block`, if the head is known
+    pub lag: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeReachability {
+    pub members: Vec<MemberReachability>,
+    // Voting power of the online members whose signatures count
+    pub online_voting_power: u64,
+    pub quorum: u64,
+    pub quorum_reachable: bool,
+    pub checked_at_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MemberReachability {
+    pub public_key: String,
+    pub url: String,
+    pub voting_power: u64,
+    pub blocklisted: bool,
+    pub ping: PingResult,
+}
+
+#[derive(Clone, Debug)]
+pub struct NodeIdentity {
+    pub public_key: String,
+    pub registered_url: Option<String>,
+}
+
+// State behind `/status`. The identity is set at startup, the rest is
+// refreshed by the watchdog and `refresh_node_status`.
+#[derive(Clone, Default)]
+pub struct NodeStatusCache {
+    pub identity: Option<NodeIdentity>,
+    pub starcoin_head_block: Arc<ArcSwap<Option<u64>>>,
+    pub starcoin_bridge_paused: Arc<ArcSwap<Option<bool>>>,
+    pub eth_bridge_paused: Arc<ArcSwap<Option<bool>>>,
+    pub committee: Arc<ArcSwap<Option<CommitteeReachability>>>,
+    pub registration: RegistrationGuard,
+}
+
+impl NodeStatusCache {
+    pub fn snapshot(&self, metrics: &BridgeMetrics) -> NodeStatus {
+        // The gauge is zero until the first finalized block is read
+        let eth_head_block = u64::try_from(metrics.last_finalized_eth_block.get())
+            .ok()
+            .filter(|block| *block > 0);
+        let starcoin_head_block = **self.starcoin_head_block.load();
+        NodeStatus {
+            public_key: self.identity.as_ref().map(|id| id.public_key.clone()),
+            registered_url: self
+                .identity
+                .as_ref()
+                .and_then(|id| id.registered_url.clone()),
+            registration_mismatch: self
+                .registration
+                .mismatch()
+                .map(|mismatch| mismatch.to_string()),
+            starcoin: ChainStatus {
+                head_block: starcoin_head_block,
+                paused: **self.starcoin_bridge_paused.load(),
+                syncers: syncer_statuses(
+                    &metrics.last_synced_starcoin_bridge_checkpoints,
+                    starcoin_head_block,
+                ),
+            },
+            eth: ChainStatus {
+                head_block: eth_head_block,
+                paused: **self.eth_bridge_paused.load(),
+                syncers: syncer_statuses(&metrics.last_synced_eth_blocks, eth_head_block),
+            },
+            pending_actions: metrics.action_executor_signing_queue_depth.get(),
+            committee: (**self.committee.load()).clone(),
+        }
+    }
+}
+
+// One status per label of a syncer progress gauge, ordered by label
+fn syncer_statuses(gauge: &IntGaugeVec, head_block: Option<u64>) -> Vec<SyncerStatus> {
+    let mut statuses: Vec<_> = gauge
+        .collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .filter_map(|metric| {
+            let task = metric.get_label().first()?.get_value().to_string();
+            let last_processed_block = metric.get_gauge().get_value().max(0.0) as u64;
+            Some(SyncerStatus {
+                task,
+                last_processed_block,
+                lag: head_block.map(|head| head.saturating_sub(last_processed_block)),
+            })
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.task.cmp(&b.task));
+    statuses
+}
+
+// Pings every committee member, and checks whether the online members hold
+// enough voting power to approve a token transfer
+pub async fn check_committee_reachability(
+    committee: &BridgeCommittee,
+    timeout: Duration,
+) -> CommitteeReachability {
+    let members: Vec<_> = committee.members().values().collect();
+    let urls: Vec<_> = members
+        .iter()
+        .map(|member| member.base_url.clone())
+        .collect();
+    let pings = ping_urls_with_timeout(&urls, timeout).await;
+    let checked_at_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    committee_reachability(&members, pings, checked_at_ms)
+}
+
+fn committee_reachability(
+    members: &[&BridgeAuthority],
+    pings: Vec<PingResult>,
+    checked_at_ms: u64,
+) -> CommitteeReachability {
+    let members: Vec<_> = members
+        .iter()
+        .zip(pings)
+        .map(|(member, ping)| MemberReachability {
+            public_key: Hex::encode(member.pubkey_bytes().as_bytes()),
+            url: member.base_url.clone(),
+            voting_power: member.voting_power,
+            blocklisted: member.is_blocklisted,
+            ping,
+        })
+        .collect();
+    let online_voting_power = members
+        .iter()
+        .filter(|member| member.ping.online && !member.blocklisted)
+        .map(|member| member.voting_power)
+        .sum();
+    CommitteeReachability {
+        members,
+        online_voting_power,
+        quorum: APPROVAL_THRESHOLD_TOKEN_TRANSFER,
+        quorum_reachable: online_voting_power >= APPROVAL_THRESHOLD_TOKEN_TRANSFER,
+        checked_at_ms,
+    }
+}
+
+// Refreshes the Starcoin head and the committee reachability of `cache`
+pub async fn refresh_node_status<C>(
+    cache: NodeStatusCache,
+    starcoin_bridge_client: Arc<StarcoinClient<C>>,
+    committee: Arc<BridgeCommittee>,
+) where
+    C: StarcoinClientInner,
+{
+    let mut interval = tokio::time::interval(STATUS_REFRESH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let (head_block, reachability) = tokio::join!(
+            starcoin_bridge_client.get_latest_checkpoint_sequence_number(),
+            check_committee_reachability(&committee, DEFAULT_PING_TIMEOUT),
+        );
+        match head_block {
+            Ok(head_block) => cache.starcoin_head_block.store(Arc::new(Some(head_block))),
+            Err(e) => warn!("Failed to refresh the Starcoin head block: {:?}", e),
+        }
+        cache.committee.store(Arc::new(Some(reachability)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_authority_and_key;
+
+    #[test]
+    fn test_syncer_statuses() {
+        let metrics = BridgeMetrics::new_for_testing();
+        assert!(syncer_statuses(&metrics.last_synced_eth_blocks, Some(10)).is_empty());
+
+        for (task, block) in [("0xbb", 95), ("0xaa", 100)] {
+            metrics
+                .last_synced_eth_blocks
+                .with_label_values(&[task])
+                .set(block);
+        }
+        assert_eq!(
+            syncer_statuses(&metrics.last_synced_eth_blocks, Some(100)),
+            vec![
+                SyncerStatus {
+                    task: "0xaa".to_string(),
+                    last_processed_block: 100,
+                    lag: Some(0),
+                },
+                SyncerStatus {
+                    task: "0xbb".to_string(),
+                    last_processed_block: 95,
+                    lag: Some(5),
+                },
+            ]
+        );
+        assert_eq!(
+            syncer_statuses(&metrics.last_synced_eth_blocks, None)[1].lag,
+            None
+        );
+    }
+
+    #[test]
+    fn test_committee_reachability() {
+        let (online, _, _) = get_test_authority_and_key(6000, 9999);
+        let (offline, _, _) = get_test_authority_and_key(4000, 9998);
+        let ping = |online: bool| PingResult {
+            online,
+            latency_ms: None,
+            http_status: None,
+            error: None,
+        };
+
+        let reachability =
+            committee_reachability(&[&online, &offline], vec![ping(true), ping(false)], 1);
+        assert_eq!(reachability.online_voting_power, 6000);
+        assert!(reachability.quorum_reachable);
+        assert_eq!(reachability.members[1].url, offline.base_url);
+
+        // Blocklisted members don't count towards the quorum
+        let blocklisted = BridgeAuthority {
+            is_blocklisted: true,
+            ..online.clone()
+        };
+        let reachability = committee_reachability(&[&blocklisted], vec![ping(true)], 1);
+        assert_eq!(reachability.online_voting_power, 0);
+        assert!(!reachability.quorum_reachable);
+    }
+}
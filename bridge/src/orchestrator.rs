@@ -14,21 +14,24 @@ use crate::error::BridgeError;
 use crate::events::StarcoinBridgeEvent;
 use crate::metrics::BridgeMetrics;
 use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use crate::starcoin_bridge_syncer::StarcoinModuleEvents;
 use crate::storage::BridgeOrchestratorTables;
+use crate::tx_digest::TxDigest;
 use crate::types::EthLog;
 use ethers::types::Address as EthAddress;
-use starcoin_bridge_json_rpc_types::StarcoinEvent;
-use starcoin_bridge_types::Identifier;
+use starcoin_bridge_types::bridge::BridgeChainId;
 use starcoin_metrics::spawn_logged_monitored_task;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 
+pub type EthEventsReceiver =
+    starcoin_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>;
+
 pub struct BridgeOrchestrator<C> {
     _starcoin_bridge_client: Arc<StarcoinClient<C>>,
-    starcoin_bridge_events_rx:
-        starcoin_metrics::metered_channel::Receiver<(Identifier, Vec<StarcoinEvent>)>,
-    eth_events_rx: starcoin_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+    starcoin_bridge_events_rx: starcoin_metrics::metered_channel::Receiver<StarcoinModuleEvents>,
+    eth_events_rxs: Vec<(BridgeChainId, EthEventsReceiver)>,
     store: Arc<BridgeOrchestratorTables>,
     starcoin_bridge_monitor_tx: starcoin_metrics::metered_channel::Sender<StarcoinBridgeEvent>,
     eth_monitor_tx: starcoin_metrics::metered_channel::Sender<EthBridgeEvent>,
@@ -41,11 +44,11 @@ where
 {
     pub fn new(
         starcoin_bridge_client: Arc<StarcoinClient<C>>,
-        starcoin_bridge_events_rx: starcoin_metrics::metered_channel::Receiver<(
-            Identifier,
-            Vec<StarcoinEvent>,
-        )>,
-        eth_events_rx: starcoin_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        starcoin_bridge_events_rx: starcoin_metrics::metered_channel::Receiver<
+            StarcoinModuleEvents,
+        >,
+        // one receiver per watched EVM chain, fed by that chain's `EthSyncer`
+        eth_events_rxs: Vec<(BridgeChainId, EthEventsReceiver)>,
         store: Arc<BridgeOrchestratorTables>,
         starcoin_bridge_monitor_tx: starcoin_metrics::metered_channel::Sender<StarcoinBridgeEvent>,
         eth_monitor_tx: starcoin_metrics::metered_channel::Sender<EthBridgeEvent>,
@@ -54,7 +57,7 @@ where
         Self {
             _starcoin_bridge_client: starcoin_bridge_client,
             starcoin_bridge_events_rx,
-            eth_events_rx,
+            eth_events_rxs,
             store,
             starcoin_bridge_monitor_tx,
             eth_monitor_tx,
@@ -97,14 +100,16 @@ where
                 .expect("Submit to executor should not fail");
         }
 
-        let metrics_clone = self.metrics.clone();
-        task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
-            store_clone,
-            executor_sender,
-            self.eth_events_rx,
-            self.eth_monitor_tx,
-            metrics_clone,
-        )));
+        for (chain_id, eth_events_rx) in self.eth_events_rxs {
+            task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
+                chain_id,
+                store_clone.clone(),
+                executor_sender.clone(),
+                eth_events_rx,
+                self.eth_monitor_tx.clone(),
+                self.metrics.clone(),
+            )));
+        }
 
         task_handles
     }
@@ -112,16 +117,18 @@ where
     async fn run_starcoin_bridge_watcher(
         store: Arc<BridgeOrchestratorTables>,
         executor_tx: starcoin_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
-        mut starcoin_bridge_events_rx: starcoin_metrics::metered_channel::Receiver<(
-            Identifier,
-            Vec<StarcoinEvent>,
-        )>,
+        mut starcoin_bridge_events_rx: starcoin_metrics::metered_channel::Receiver<
+            StarcoinModuleEvents,
+        >,
         monitor_tx: starcoin_metrics::metered_channel::Sender<StarcoinBridgeEvent>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting starcoin watcher task");
-        while let Some((identifier, events)) = starcoin_bridge_events_rx.recv().await {
+        while let Some((identifier, cursor, events)) = starcoin_bridge_events_rx.recv().await {
             if events.is_empty() {
+                store
+                    .update_starcoin_bridge_event_cursor(identifier, cursor)
+                    .expect("Store operation should not fail");
                 continue;
             }
             info!("Received {} Starcoin events: {:?}", events.len(), events);
@@ -204,37 +211,31 @@ where
                 }
             }
 
-            // Unwrap safe: in the beginning of the loop we checked that events is not empty
-            let cursor = events.last().unwrap().id.clone();
-            let cursor_tuple: (u64, u64) = cursor.into();
             store
-                .update_starcoin_bridge_event_cursor(identifier, cursor_tuple)
+                .update_starcoin_bridge_event_cursor(identifier, cursor)
                 .expect("Store operation should not fail");
         }
         panic!("Starcoin event channel was closed unexpectedly");
     }
 
     async fn run_eth_watcher(
+        chain_id: BridgeChainId,
         store: Arc<BridgeOrchestratorTables>,
         executor_tx: starcoin_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
-        mut eth_events_rx: starcoin_metrics::metered_channel::Receiver<(
-            ethers::types::Address,
-            u64,
-            Vec<EthLog>,
-        )>,
+        mut eth_events_rx: EthEventsReceiver,
         eth_monitor_tx: starcoin_metrics::metered_channel::Sender<EthBridgeEvent>,
         metrics: Arc<BridgeMetrics>,
     ) {
-        info!("Starting eth watcher task");
+        info!("Starting eth watcher task for chain {}", chain_id);
         while let Some((contract, end_block, logs)) = eth_events_rx.recv().await {
             if logs.is_empty() {
                 store
-                    .update_eth_event_cursor(contract, end_block)
+                    .update_eth_event_cursor(chain_id, contract, end_block)
                     .expect("Store operation should not fail");
                 continue;
             }
 
-            info!("Received {} Eth events", logs.len());
+            info!("Received {} Eth events from chain {}", logs.len(), chain_id);
             metrics
                 .eth_watcher_received_events
                 .inc_by(logs.len() as u64);
@@ -263,6 +264,10 @@ where
                     .expect("Sending event to monitor channel should not fail");
 
                 match bridge_event.try_into_bridge_action(log.tx_hash, log.log_index_in_tx) {
+                    // A contract only speaks for the chain it is deployed on
+                    Ok(Some(action)) if action.chain_id() != chain_id => {
+                        error!(eth_tx_hash=%TxDigest::eth(log.tx_hash), eth_event_index=?log.log_index_in_tx, "Eth event on chain {} claims chain {}, ignoring", chain_id, action.chain_id());
+                    }
                     Ok(Some(action)) => {
                         metrics.last_observed_actions_seq_num.with_label_values(&[
                             action.chain_id().to_string().as_str(),
@@ -272,7 +277,7 @@ where
                     }
                     Ok(None) => {}
                     Err(e) => {
-                        error!(eth_tx_hash=?log.tx_hash, eth_event_index=?log.log_index_in_tx, "Error converting EthBridgeEvent to BridgeAction: {:?}", e);
+                        error!(eth_tx_hash=%TxDigest::eth(log.tx_hash), eth_event_index=?log.log_index_in_tx, "Error converting EthBridgeEvent to BridgeAction: {:?}", e);
                     }
                 }
             }
@@ -294,21 +299,28 @@ where
             }
 
             store
-                .update_eth_event_cursor(contract, end_block)
+                .update_eth_event_cursor(chain_id, contract, end_block)
                 .expect("Store operation should not fail");
         }
-        panic!("Eth event channel was closed");
+        panic!("Eth event channel for chain {} was closed", chain_id);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        test_utils::{get_test_eth_to_starcoin_bridge_action, get_test_log_and_action},
+        eth_client::EthClient,
+        eth_mock_provider::EthMockProvider,
+        eth_syncer::EthSyncer,
+        test_utils::{
+            get_test_eth_to_starcoin_bridge_action, get_test_log_and_action, mock_get_logs,
+            mock_last_finalized_block,
+        },
         types::BridgeActionDigest,
     };
     use ethers::types::{Address as EthAddress, TxHash};
     use prometheus::Registry;
+    use starcoin_bridge_types::Identifier;
     use std::str::FromStr;
 
     use super::*;
@@ -344,7 +356,7 @@ mod tests {
         let _handles = BridgeOrchestrator::new(
             Arc::new(starcoin_bridge_client),
             starcoin_bridge_events_rx,
-            eth_events_rx,
+            vec![(BridgeChainId::EthCustom, eth_events_rx)],
             store.clone(),
             starcoin_bridge_monitor_tx,
             eth_monitor_tx,
@@ -356,8 +368,13 @@ mod tests {
         let identifier = Identifier::from_str("test_starcoin_bridge_watcher_task").unwrap();
         let (starcoin_bridge_event, bridge_action) =
             get_test_starcoin_bridge_event_and_action(identifier.clone());
+        let cursor: (u64, u64) = starcoin_bridge_event.id.into();
         starcoin_bridge_events_tx
-            .send((identifier.clone(), vec![starcoin_bridge_event.clone()]))
+            .send((
+                identifier.clone(),
+                cursor,
+                vec![starcoin_bridge_event.clone()],
+            ))
             .await
             .unwrap();
 
@@ -379,17 +396,31 @@ mod tests {
             assert_eq!(actions.len(), 1);
             let action = actions.get(&bridge_action.digest()).unwrap();
             assert_eq!(action, &bridge_action);
-            // Convert EventID struct to tuple (block_number, event_seq) for comparison
-            let expected_cursor: (u64, u64) = starcoin_bridge_event.id.into();
             assert_eq!(
                 store
-                    .get_starcoin_bridge_event_cursors(&[identifier])
+                    .get_starcoin_bridge_event_cursors(&[identifier.clone()])
                     .unwrap()[0]
                     .unwrap(),
-                expected_cursor,
+                cursor,
             );
             break;
         }
+
+        // A batch without events still moves the cursor past the pages read
+        starcoin_bridge_events_tx
+            .send((identifier.clone(), (cursor.0 + 32, 0), vec![]))
+            .await
+            .unwrap();
+        while store
+            .get_starcoin_bridge_event_cursors(&[identifier.clone()])
+            .unwrap()[0]
+            != Some((cursor.0 + 32, 0))
+        {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the starcoin cursor to advance");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
     }
 
     #[tokio::test]
@@ -412,13 +443,15 @@ mod tests {
             store,
         ) = setup();
         let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let address = EthAddress::random();
+        let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 10);
         // start orchestrator
         let registry = Registry::new();
         let metrics = Arc::new(BridgeMetrics::new(&registry));
         let _handles = BridgeOrchestrator::new(
             Arc::new(starcoin_bridge_client),
             starcoin_bridge_events_rx,
-            eth_events_rx,
+            vec![(bridge_action.chain_id(), eth_events_rx)],
             store.clone(),
             starcoin_bridge_monitor_tx,
             eth_monitor_tx,
@@ -426,8 +459,6 @@ mod tests {
         )
         .run(executor)
         .await;
-        let address = EthAddress::random();
-        let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 10);
         let log_index_in_tx = 10;
         let log_block_num = log.block_number.unwrap().as_u64();
         let eth_log = EthLog {
@@ -462,7 +493,10 @@ mod tests {
             let action = actions.get(&bridge_action.digest()).unwrap();
             assert_eq!(action, &bridge_action);
             assert_eq!(
-                store.get_eth_event_cursors(&[address]).unwrap()[0].unwrap(),
+                store
+                    .get_eth_event_cursors(bridge_action.chain_id(), &[address])
+                    .unwrap()[0]
+                    .unwrap(),
                 end_block_num,
             );
             break;
@@ -508,7 +542,7 @@ mod tests {
         let _handles = BridgeOrchestrator::new(
             Arc::new(starcoin_bridge_client),
             starcoin_bridge_events_rx,
-            eth_events_rx,
+            vec![(BridgeChainId::EthCustom, eth_events_rx)],
             store.clone(),
             starcoin_bridge_monitor_tx,
             eth_monitor_tx,
@@ -526,10 +560,256 @@ mod tests {
         assert_eq!(digests.len(), 2);
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    // A burst larger than the executor queue backs up into the watcher instead of
+    // being dropped, and the cursor only advances once the whole batch is queued.
+    async fn test_eth_watcher_backpressure() {
+        let (
+            _starcoin_bridge_events_tx,
+            starcoin_bridge_events_rx,
+            eth_events_tx,
+            eth_events_rx,
+            starcoin_bridge_monitor_tx,
+            _starcoin_bridge_monitor_rx,
+            eth_monitor_tx,
+            _eth_monitor_rx,
+            starcoin_bridge_client,
+            store,
+        ) = setup();
+        let (executor, mut consumed_rx, permits) = SlowMockExecutor::new(2);
+        let address = EthAddress::random();
+        let mut eth_logs = vec![];
+        let mut expected = std::collections::HashSet::new();
+        let mut chain_id = None;
+        for _ in 0..5 {
+            let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 0);
+            expected.insert(bridge_action.digest());
+            chain_id = Some(bridge_action.chain_id());
+            eth_logs.push(EthLog {
+                block_number: log.block_number.unwrap().as_u64(),
+                tx_hash: log.transaction_hash.unwrap(),
+                log_index_in_tx: 0,
+                log,
+            });
+        }
+        let chain_id = chain_id.unwrap();
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(starcoin_bridge_client),
+            starcoin_bridge_events_rx,
+            vec![(chain_id, eth_events_rx)],
+            store.clone(),
+            starcoin_bridge_monitor_tx,
+            eth_monitor_tx,
+            metrics,
+        )
+        .run(executor)
+        .await;
+
+        let end_block_num = 100;
+        eth_events_tx
+            .send((address, end_block_num, eth_logs))
+            .await
+            .unwrap();
+
+        // The executor has not consumed anything and its queue only holds 2 actions,
+        // so the watcher is stuck on the batch: everything is in the WAL but the
+        // cursor has not moved.
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        assert_eq!(store.get_all_pending_actions().len(), 5);
+        assert_eq!(
+            store.get_eth_event_cursors(chain_id, &[address]).unwrap()[0],
+            None
+        );
+
+        // Let the executor drain the queue one action at a time
+        let mut consumed = std::collections::HashSet::new();
+        for _ in 0..5 {
+            permits.add_permits(1);
+            consumed.insert(consumed_rx.recv().await.unwrap());
+        }
+        assert_eq!(consumed, expected);
+
+        let start = std::time::Instant::now();
+        while store.get_eth_event_cursors(chain_id, &[address]).unwrap()[0] != Some(end_block_num) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the eth cursor to advance");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    // Every EVM chain gets its own watcher: the same contract address on two chains
+    // keeps two cursors, and a log claiming another chain is not acted on.
+    async fn test_eth_watchers_per_chain() {
+        let (
+            _starcoin_bridge_events_tx,
+            starcoin_bridge_events_rx,
+            eth_events_tx,
+            eth_events_rx,
+            starcoin_bridge_monitor_tx,
+            _starcoin_bridge_monitor_rx,
+            eth_monitor_tx,
+            _eth_monitor_rx,
+            starcoin_bridge_client,
+            store,
+        ) = setup();
+        let (other_eth_events_tx, other_eth_events_rx) = eth_events_channel();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let address = EthAddress::random();
+        let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 0);
+        let chain_id = bridge_action.chain_id();
+        let other_chain_id = BridgeChainId::EthCustom;
+        assert_ne!(chain_id, other_chain_id);
+
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(starcoin_bridge_client),
+            starcoin_bridge_events_rx,
+            vec![
+                (chain_id, eth_events_rx),
+                (other_chain_id, other_eth_events_rx),
+            ],
+            store.clone(),
+            starcoin_bridge_monitor_tx,
+            eth_monitor_tx,
+            metrics,
+        )
+        .run(executor)
+        .await;
+
+        let eth_log = EthLog {
+            block_number: log.block_number.unwrap().as_u64(),
+            tx_hash: log.transaction_hash.unwrap(),
+            log_index_in_tx: 0,
+            log,
+        };
+        other_eth_events_tx
+            .send((address, 50, vec![eth_log.clone()]))
+            .await
+            .unwrap();
+        eth_events_tx
+            .send((address, 20, vec![eth_log]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
+        let start = std::time::Instant::now();
+        while store.get_eth_event_cursors(chain_id, &[address]).unwrap()[0] != Some(20)
+            || store
+                .get_eth_event_cursors(other_chain_id, &[address])
+                .unwrap()[0]
+                != Some(50)
+        {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the eth cursors to advance");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        // cursors move after submission, so the other chain's copy would be here by now
+        assert!(executor_requested_action_rx.try_recv().is_err());
+        assert_eq!(
+            store
+                .get_all_pending_actions()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![bridge_action.digest()]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    // Two mock Eth nodes under different chain ids, each behind its own syncer, watching
+    // the same contract address: the deposit on one chain is submitted once and the
+    // cursors of the two chains advance independently.
+    async fn test_eth_syncers_on_two_mock_chains() {
+        let (
+            _starcoin_bridge_events_tx,
+            starcoin_bridge_events_rx,
+            _eth_events_tx,
+            _eth_events_rx,
+            starcoin_bridge_monitor_tx,
+            _starcoin_bridge_monitor_rx,
+            eth_monitor_tx,
+            _eth_monitor_rx,
+            starcoin_bridge_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let address = EthAddress::random();
+        let (mut log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 0);
+        log.block_number = Some(700.into());
+        let chain_id = bridge_action.chain_id();
+        let other_chain_id = BridgeChainId::EthCustom;
+        assert_ne!(chain_id, other_chain_id);
+
+        let provider = EthMockProvider::new();
+        mock_last_finalized_block(&provider, 777);
+        mock_get_logs(&provider, address, 100, 777, vec![log]);
+        let other_provider = EthMockProvider::new();
+        mock_last_finalized_block(&other_provider, 555);
+        mock_get_logs(&other_provider, address, 100, 555, vec![]);
+
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let mut eth_events_rxs = vec![];
+        for (id, provider) in [(chain_id, provider), (other_chain_id, other_provider)] {
+            let client = EthClient::new_mocked(
+                provider,
+                std::collections::HashSet::from_iter(vec![address]),
+            );
+            let (_handles, rx, _finalized_block_rx) = EthSyncer::new(
+                Arc::new(client),
+                std::collections::HashMap::from_iter(vec![(address, 100)]),
+            )
+            .run(metrics.clone())
+            .await
+            .unwrap();
+            eth_events_rxs.push((id, rx));
+        }
+
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(starcoin_bridge_client),
+            starcoin_bridge_events_rx,
+            eth_events_rxs,
+            store.clone(),
+            starcoin_bridge_monitor_tx,
+            eth_monitor_tx,
+            metrics,
+        )
+        .run(executor)
+        .await;
+
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
+        let start = std::time::Instant::now();
+        while store.get_eth_event_cursors(chain_id, &[address]).unwrap()[0] != Some(777)
+            || store
+                .get_eth_event_cursors(other_chain_id, &[address])
+                .unwrap()[0]
+                != Some(555)
+        {
+            if start.elapsed().as_secs() > 10 {
+                panic!("Timed out waiting for the eth cursors to advance");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        assert!(executor_requested_action_rx.try_recv().is_err());
+    }
+
     #[allow(clippy::type_complexity)]
     fn setup() -> (
-        starcoin_metrics::metered_channel::Sender<(Identifier, Vec<StarcoinEvent>)>,
-        starcoin_metrics::metered_channel::Receiver<(Identifier, Vec<StarcoinEvent>)>,
+        starcoin_metrics::metered_channel::Sender<StarcoinModuleEvents>,
+        starcoin_metrics::metered_channel::Receiver<StarcoinModuleEvents>,
         starcoin_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
         starcoin_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
         starcoin_metrics::metered_channel::Sender<StarcoinBridgeEvent>,
@@ -551,13 +831,7 @@ mod tests {
         let mock_client = StarcoinMockClient::default();
         let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
 
-        let (eth_events_tx, eth_events_rx) = starcoin_metrics::metered_channel::channel(
-            100,
-            &starcoin_metrics::get_metrics()
-                .unwrap()
-                .channel_inflight
-                .with_label_values(&["unit_test_eth_events_queue"]),
-        );
+        let (eth_events_tx, eth_events_rx) = eth_events_channel();
 
         let (starcoin_bridge_events_tx, starcoin_bridge_events_rx) =
             starcoin_metrics::metered_channel::channel(
@@ -596,6 +870,19 @@ mod tests {
         )
     }
 
+    fn eth_events_channel() -> (
+        starcoin_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
+        EthEventsReceiver,
+    ) {
+        starcoin_metrics::metered_channel::channel(
+            100,
+            &starcoin_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&["unit_test_eth_events_queue"]),
+        )
+    }
+
     // A `BridgeActionExecutorTrait` implementation that only tracks the submitted actions.
     struct MockExecutor {
         requested_transactions_tx: tokio::sync::broadcast::Sender<BridgeActionDigest>,
@@ -639,4 +926,63 @@ mod tests {
             (vec![handles], tx)
         }
     }
+
+    // A `BridgeActionExecutorTrait` implementation with a small queue that only
+    // consumes an action each time the test adds a permit.
+    struct SlowMockExecutor {
+        capacity: usize,
+        permits: Arc<tokio::sync::Semaphore>,
+        consumed_tx: tokio::sync::mpsc::UnboundedSender<BridgeActionDigest>,
+    }
+
+    impl SlowMockExecutor {
+        fn new(
+            capacity: usize,
+        ) -> (
+            Self,
+            tokio::sync::mpsc::UnboundedReceiver<BridgeActionDigest>,
+            Arc<tokio::sync::Semaphore>,
+        ) {
+            let (consumed_tx, consumed_rx) = tokio::sync::mpsc::unbounded_channel();
+            let permits = Arc::new(tokio::sync::Semaphore::new(0));
+            (
+                Self {
+                    capacity,
+                    permits: permits.clone(),
+                    consumed_tx,
+                },
+                consumed_rx,
+                permits,
+            )
+        }
+    }
+
+    impl BridgeActionExecutorTrait for SlowMockExecutor {
+        fn run(
+            self,
+        ) -> (
+            Vec<tokio::task::JoinHandle<()>>,
+            starcoin_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+        ) {
+            let (tx, mut rx) =
+                starcoin_metrics::metered_channel::channel::<BridgeActionExecutionWrapper>(
+                    self.capacity,
+                    &starcoin_metrics::get_metrics()
+                        .unwrap()
+                        .channel_inflight
+                        .with_label_values(&["unit_test_slow_mock_executor"]),
+                );
+
+            let handle = tokio::spawn(async move {
+                loop {
+                    self.permits.acquire().await.unwrap().forget();
+                    let Some(action) = rx.recv().await else {
+                        break;
+                    };
+                    self.consumed_tx.send(action.0.digest()).unwrap();
+                }
+            });
+            (vec![handle], tx)
+        }
+    }
 }
@@ -0,0 +1,169 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `RateLimitedLogger` throttles error logs in unbounded retry loops. The
+//! first failure is logged, then summaries are emitted at exponentially
+//! spaced attempt counts (2, 4, 8, ...), and a recovery line with the total
+//! outage duration is logged on the next success.
+//!
+//! Each loop owns its logger, so state is per-callsite and dropped with the
+//! loop. Metrics should still be incremented on every attempt by the caller.
+
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+pub struct RateLimitedLogger<C = SystemClock> {
+    name: &'static str,
+    clock: C,
+    failures: u64,
+    first_failure_at: Option<Instant>,
+    next_report_at: u64,
+}
+
+impl RateLimitedLogger<SystemClock> {
+    pub fn new(name: &'static str) -> Self {
+        Self::with_clock(name, SystemClock)
+    }
+}
+
+impl<C: Clock> RateLimitedLogger<C> {
+    pub fn with_clock(name: &'static str, clock: C) -> Self {
+        Self {
+            name,
+            clock,
+            failures: 0,
+            first_failure_at: None,
+            next_report_at: 1,
+        }
+    }
+
+    // Records a failed attempt. Returns true if a log line was emitted.
+    pub fn on_failure(&mut self, err: &impl Debug) -> bool {
+        let now = self.clock.now();
+        let first_failure_at = *self.first_failure_at.get_or_insert(now);
+        self.failures += 1;
+        if self.failures < self.next_report_at {
+            return false;
+        }
+        self.next_report_at = self.next_report_at.saturating_mul(2);
+        if self.failures == 1 {
+            error!("{} failed: {:?}", self.name, err);
+        } else {
+            error!(
+                "{} still failing after {} attempts / {}s, last error: {:?}",
+                self.name,
+                self.failures,
+                now.duration_since(first_failure_at).as_secs(),
+                err
+            );
+        }
+        true
+    }
+
+    // Records a successful attempt. If it ends an outage, logs a recovery
+    // line and returns the outage duration.
+    pub fn on_success(&mut self) -> Option<Duration> {
+        let first_failure_at = self.first_failure_at.take()?;
+        let outage = self.clock.now().duration_since(first_failure_at);
+        info!(
+            "{} recovered after {} failed attempts / {}s",
+            self.name,
+            self.failures,
+            outage.as_secs()
+        );
+        self.failures = 0;
+        self.next_report_at = 1;
+        Some(outage)
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_test::traced_test;
+
+    #[derive(Clone)]
+    struct MockClock(Arc<Mutex<Instant>>);
+
+    impl MockClock {
+        fn new() -> Self {
+            Self(Arc::new(Mutex::new(Instant::now())))
+        }
+
+        fn advance(&self, d: Duration) {
+            *self.0.lock().unwrap() += d;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn test_emission_schedule() {
+        let clock = MockClock::new();
+        let mut logger = RateLimitedLogger::with_clock("test_rpc", clock.clone());
+
+        let mut emitted = vec![];
+        for attempt in 1..=600u64 {
+            if logger.on_failure(&"boom") {
+                emitted.push(attempt);
+            }
+            clock.advance(Duration::from_secs(1));
+        }
+        assert_eq!(emitted, vec![1, 2, 4, 8, 16, 32, 64, 128, 256, 512]);
+        assert_eq!(logger.failures(), 600);
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_recovery_reports_outage_duration() {
+        let clock = MockClock::new();
+        let mut logger = RateLimitedLogger::with_clock("test_rpc", clock.clone());
+
+        // No outage, nothing to report
+        assert_eq!(logger.on_success(), None);
+        assert!(!logs_contain("recovered"));
+
+        for _ in 0..10 {
+            logger.on_failure(&"boom");
+            clock.advance(Duration::from_secs(30));
+        }
+        assert_eq!(logger.on_success(), Some(Duration::from_secs(300)));
+        assert!(logs_contain(
+            "test_rpc recovered after 10 failed attempts / 300s"
+        ));
+        assert_eq!(logger.failures(), 0);
+
+        // The schedule restarts after a recovery
+        assert!(logger.on_failure(&"boom"));
+        assert!(logger.on_failure(&"boom"));
+        assert!(!logger.on_failure(&"boom"));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(logger.on_success(), Some(Duration::from_secs(5)));
+        assert!(logs_contain(
+            "test_rpc recovered after 3 failed attempts / 5s"
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sanity checks on bridge transfer recipients. Funds sent to the zero
+//! address or to one of the bridge's own contracts can never be claimed,
+//! so both the CLI and the relayer run these before moving tokens.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::simple_starcoin_rpc::SimpleStarcoinRpcClient;
+use crate::types::BridgeAction;
+use ethers::providers::Middleware;
+use ethers::types::Address as EthAddress;
+use starcoin_bridge_types::base_types::StarcoinAddress;
+
+/// Rejects the zero address and any of the bridge's Eth contracts
+/// (bridge, committee, limiter, config, vault).
+pub fn check_eth_recipient(
+    recipient: &EthAddress,
+    bridge_contracts: &[EthAddress],
+) -> BridgeResult<()> {
+    if recipient.is_zero() {
+        return Err(BridgeError::InvalidRecipientAddress(
+            "Eth recipient is the zero address".to_string(),
+        ));
+    }
+    if bridge_contracts.contains(recipient) {
+        return Err(BridgeError::InvalidRecipientAddress(format!(
+            "Eth recipient {:?} is a bridge contract",
+            recipient
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects Eth recipients with deployed code, unless `allow_contract_recipient`
+/// is set. Contracts that cannot forward ETH/ERC20 would lock the funds.
+pub async fn check_eth_recipient_has_no_code<M: Middleware>(
+    provider: &M,
+    recipient: &EthAddress,
+    allow_contract_recipient: bool,
+) -> BridgeResult<()> {
+    if allow_contract_recipient {
+        return Ok(());
+    }
+    let code = provider
+        .get_code(*recipient, None)
+        .await
+        .map_err(|e| BridgeError::transient_rpc(format!("Failed to get code: {:?}", e)))?;
+    if !code.is_empty() {
+        return Err(BridgeError::InvalidRecipientAddress(format!(
+            "Eth recipient {:?} is a contract, pass --allow-contract-recipient to override",
+            recipient
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects the zero address and the Starcoin bridge module address.
+pub fn check_starcoin_recipient(
+    recipient: &StarcoinAddress,
+    bridge_address: &StarcoinAddress,
+) -> BridgeResult<()> {
+    if *recipient == StarcoinAddress::ZERO {
+        return Err(BridgeError::InvalidRecipientAddress(
+            "Starcoin recipient is the zero address".to_string(),
+        ));
+    }
+    if recipient == bridge_address {
+        return Err(BridgeError::InvalidRecipientAddress(format!(
+            "Starcoin recipient {} is the bridge address",
+            recipient.to_hex_literal()
+        )));
+    }
+    Ok(())
+}
+
+/// Returns whether the Starcoin recipient account exists on-chain. A missing
+/// account is not fatal (claiming creates it) but is usually a typo.
+pub async fn starcoin_recipient_exists(
+    rpc_client: &SimpleStarcoinRpcClient,
+    recipient: &StarcoinAddress,
+) -> BridgeResult<bool> {
+    let account = rpc_client
+        .get_account(&recipient.to_hex_literal())
+        .await
+        .map_err(|e| BridgeError::transient_rpc(format!("Failed to get account: {:?}", e)))?;
+    Ok(account.is_some())
+}
+
+/// Applies the static recipient checks to the destination of a token
+/// transfer action. Non token transfer actions always pass.
+pub fn check_action_recipient(
+    action: &BridgeAction,
+    starcoin_bridge_address: &StarcoinAddress,
+    eth_bridge_contracts: &[EthAddress],
+) -> BridgeResult<()> {
+    match action {
+        BridgeAction::EthToStarcoinBridgeAction(a) => check_starcoin_recipient(
+            &a.eth_bridge_event.starcoin_bridge_address,
+            starcoin_bridge_address,
+        ),
+        BridgeAction::StarcoinToEthBridgeAction(a) => {
+            check_eth_recipient(&a.starcoin_bridge_event.eth_address, eth_bridge_contracts)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use ethers::providers::Provider;
+    use ethers::types::Bytes;
+
+    #[test]
+    fn test_check_eth_recipient() {
+        let bridge = EthAddress::random();
+        let vault = EthAddress::random();
+        let contracts = [bridge, vault];
+
+        check_eth_recipient(&EthAddress::random(), &contracts).unwrap();
+        assert!(matches!(
+            check_eth_recipient(&EthAddress::zero(), &contracts),
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+        assert!(matches!(
+            check_eth_recipient(&bridge, &contracts),
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+        assert!(matches!(
+            check_eth_recipient(&vault, &contracts),
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_eth_recipient_has_no_code() {
+        let mock_provider = EthMockProvider::new();
+        let provider = Provider::new(mock_provider.clone());
+        let eoa = EthAddress::random();
+        let contract = EthAddress::random();
+        mock_provider
+            .add_response::<(EthAddress, &str), Bytes, Bytes>(
+                "eth_getCode",
+                (eoa, "latest"),
+                Bytes::default(),
+            )
+            .unwrap();
+        mock_provider
+            .add_response::<(EthAddress, &str), Bytes, Bytes>(
+                "eth_getCode",
+                (contract, "latest"),
+                Bytes::from(vec![0x60, 0x80, 0x60, 0x40]),
+            )
+            .unwrap();
+
+        check_eth_recipient_has_no_code(&provider, &eoa, false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            check_eth_recipient_has_no_code(&provider, &contract, false).await,
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+        // Override skips the lookup entirely
+        check_eth_recipient_has_no_code(&provider, &contract, true)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_check_starcoin_recipient() {
+        let bridge_address = StarcoinAddress::random_for_testing_only();
+
+        check_starcoin_recipient(&StarcoinAddress::random_for_testing_only(), &bridge_address)
+            .unwrap();
+        assert!(matches!(
+            check_starcoin_recipient(&StarcoinAddress::ZERO, &bridge_address),
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+        assert!(matches!(
+            check_starcoin_recipient(&bridge_address, &bridge_address),
+            Err(BridgeError::InvalidRecipientAddress(_))
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that the node is registered in the on-chain committee the way it is
+//! configured. A node whose authority key is not a member, or whose registered
+//! url is not the one it is reachable at, produces signatures nobody requests
+//! or counts. The check runs at startup and then periodically; mismatches are
+//! logged, exported as `bridge_node_registration_mismatch` and, if configured,
+//! make the server refuse signing requests until the registration is fixed.
+
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use crate::types::BridgeCommittee;
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+// How often the committee is read again to check the registration
+pub const REGISTRATION_CHECK_INTERVAL: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistrationMismatch {
+    // The authority key is not a committee member
+    MissingEntry,
+    // The member is registered with another url than the configured one
+    UrlMismatch {
+        registered_url: String,
+        configured_url: String,
+    },
+}
+
+impl std::fmt::Display for RegistrationMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrationMismatch::MissingEntry => {
+                write!(f, "the authority key is not a committee member")
+            }
+            RegistrationMismatch::UrlMismatch {
+                registered_url,
+                configured_url,
+            } => write!(
+                f,
+                "the registered url {} is not the configured url {}",
+                registered_url, configured_url
+            ),
+        }
+    }
+}
+
+// Finds the entry of `pubkey` in `committee` and compares its url with
+// `external_url`, if one is configured. Trailing slashes are ignored.
+pub fn check_registration(
+    committee: &BridgeCommittee,
+    pubkey: &BridgeAuthorityPublicKeyBytes,
+    external_url: Option<&str>,
+) -> Option<RegistrationMismatch> {
+    let Some(member) = committee.member(pubkey) else {
+        return Some(RegistrationMismatch::MissingEntry);
+    };
+    let external_url = external_url?;
+    if member.base_url.trim_end_matches('/') == external_url.trim_end_matches('/') {
+        return None;
+    }
+    Some(RegistrationMismatch::UrlMismatch {
+        registered_url: member.base_url.clone(),
+        configured_url: external_url.to_string(),
+    })
+}
+
+// Outcome of the last registration check, shared with the request handler
+#[derive(Clone, Default)]
+pub struct RegistrationGuard {
+    mismatch: Arc<ArcSwap<Option<RegistrationMismatch>>>,
+    // Whether signing requests are refused while there is a mismatch
+    refuse_signing: bool,
+}
+
+impl RegistrationGuard {
+    pub fn new(refuse_signing: bool) -> Self {
+        Self {
+            mismatch: Default::default(),
+            refuse_signing,
+        }
+    }
+
+    pub fn mismatch(&self) -> Option<RegistrationMismatch> {
+        (**self.mismatch.load()).clone()
+    }
+
+    pub fn record(&self, mismatch: Option<RegistrationMismatch>, metrics: &BridgeMetrics) {
+        metrics.registration_mismatch.set(mismatch.is_some() as i64);
+        match &mismatch {
+            Some(mismatch) => error!(
+                "REGISTRATION MISMATCH: {}. Signatures of this node don't count until the on-chain registration or the node config is updated{}",
+                mismatch,
+                if self.refuse_signing {
+                    ", signing requests are refused meanwhile"
+                } else {
+                    ""
+                }
+            ),
+            None if self.mismatch().is_some() => {
+                info!("The node registration matches the committee again")
+            }
+            None => info!("The node registration matches the committee"),
+        }
+        self.mismatch.store(Arc::new(mismatch));
+    }
+
+    pub fn check_signing_allowed(&self) -> BridgeResult<()> {
+        match self.mismatch() {
+            Some(mismatch) if self.refuse_signing => Err(BridgeError::SigningRefused(format!(
+                "Registration mismatch: {}",
+                mismatch
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Reads the committee every `REGISTRATION_CHECK_INTERVAL` and records the
+// outcome of the check in `guard`. Failed reads keep the last outcome.
+pub async fn watch_node_registration<C>(
+    starcoin_bridge_client: Arc<StarcoinClient<C>>,
+    pubkey: BridgeAuthorityPublicKeyBytes,
+    external_url: Option<String>,
+    guard: RegistrationGuard,
+    metrics: Arc<BridgeMetrics>,
+) where
+    C: StarcoinClientInner,
+{
+    let mut interval = tokio::time::interval(REGISTRATION_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    // The first tick completes right away, the startup check just ran
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match starcoin_bridge_client.get_bridge_committee().await {
+            Ok(committee) => guard.record(
+                check_registration(&committee, &pubkey, external_url.as_deref()),
+                &metrics,
+            ),
+            Err(e) => warn!(
+                "Failed to read the committee to check the registration: {:?}",
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::starcoin_bridge_mock_client::StarcoinMockClient;
+    use crate::test_utils::{
+        bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+    };
+    use fastcrypto::traits::KeyPair;
+    use starcoin_bridge_types::bridge::BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER;
+
+    // A mock client serving a committee whose only member is registered at
+    // port 9191, and the key of that member
+    fn setup() -> (
+        StarcoinClient<StarcoinMockClient>,
+        BridgeAuthorityPublicKeyBytes,
+    ) {
+        let (authority, _, key) =
+            get_test_authority_and_key(BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, 9191);
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+        let mock = StarcoinMockClient::default();
+        mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(committee));
+        (
+            StarcoinClient::new_for_testing(mock),
+            BridgeAuthorityPublicKeyBytes::from(key.public()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_registration_url_mismatch() {
+        let (client, pubkey) = setup();
+        let committee = client.get_bridge_committee().await.unwrap();
+
+        assert_eq!(
+            check_registration(&committee, &pubkey, Some("http://127.0.0.1:9191/")),
+            None
+        );
+        // Only the key is checked without a configured url
+        assert_eq!(check_registration(&committee, &pubkey, None), None);
+        assert_eq!(
+            check_registration(&committee, &pubkey, Some("http://10.0.0.1:9191")),
+            Some(RegistrationMismatch::UrlMismatch {
+                registered_url: "http://127.0.0.1:9191".to_string(),
+                configured_url: "http://10.0.0.1:9191".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registration_missing_entry() {
+        let (client, _) = setup();
+        let committee = client.get_bridge_committee().await.unwrap();
+        // The key was rotated without registering the new one
+        let (_, _, rotated) =
+            get_test_authority_and_key(BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, 9191);
+        let rotated = BridgeAuthorityPublicKeyBytes::from(rotated.public());
+
+        assert_eq!(
+            check_registration(&committee, &rotated, Some("http://127.0.0.1:9191")),
+            Some(RegistrationMismatch::MissingEntry)
+        );
+        assert_eq!(
+            check_registration(&committee, &rotated, None),
+            Some(RegistrationMismatch::MissingEntry)
+        );
+    }
+
+    #[test]
+    fn test_registration_guard() {
+        let metrics = BridgeMetrics::new_for_testing();
+
+        // Mismatches are only reported unless configured otherwise
+        let guard = RegistrationGuard::new(false);
+        guard.record(Some(RegistrationMismatch::MissingEntry), &metrics);
+        assert_eq!(metrics.registration_mismatch.get(), 1);
+        assert!(guard.check_signing_allowed().is_ok());
+
+        let guard = RegistrationGuard::new(true);
+        guard.record(None, &metrics);
+        assert_eq!(metrics.registration_mismatch.get(), 0);
+        assert!(guard.check_signing_allowed().is_ok());
+
+        guard.record(Some(RegistrationMismatch::MissingEntry), &metrics);
+        assert_eq!(metrics.registration_mismatch.get(), 1);
+        let err = guard.check_signing_allowed().unwrap_err();
+        assert!(matches!(err, BridgeError::SigningRefused(_)));
+        // Fixing the registration takes effect without a restart
+        assert!(err.is_retryable());
+
+        guard.record(None, &metrics);
+        assert_eq!(metrics.registration_mismatch.get(), 0);
+        assert!(guard.check_signing_allowed().is_ok());
+    }
+}
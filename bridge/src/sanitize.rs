@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sanitization of strings that come from on-chain bytes controlled by
+//! validators, such as committee member urls. Such values end up in logs,
+//! JSON outputs and the CLI dashboard, so they must not carry terminal escape
+//! sequences, line breaks or unbounded amounts of data.
+
+// Upper bound for a committee member url, in bytes
+pub const MAX_URL_BYTES: usize = 512;
+
+const TRUNCATION_MARKER: &str = "...";
+
+// Characters that are dropped: control characters (including ESC and line
+// breaks), and bidi and zero width formatting characters that can make the
+// displayed text differ from the actual one.
+fn is_unsafe_char(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}')
+}
+
+// Converts untrusted bytes to a string that is safe to log or display.
+// Invalid UTF-8 is replaced with U+FFFD, unsafe characters are dropped, and
+// the result is at most `max_bytes` long. A truncated value ends with "...".
+// Never panics.
+pub fn sanitize_untrusted(bytes: &[u8], max_bytes: usize) -> String {
+    // Only a bounded prefix is decoded, so a megabyte value costs no more
+    // than a long one. Every kept char takes at most 4 bytes of input.
+    let scanned = &bytes[..bytes.len().min(max_bytes.saturating_mul(4))];
+    let mut truncated = scanned.len() < bytes.len();
+    let mut out = String::new();
+    for c in String::from_utf8_lossy(scanned)
+        .chars()
+        .filter(|c| !is_unsafe_char(*c))
+    {
+        if out.len() + c.len_utf8() > max_bytes {
+            truncated = true;
+            break;
+        }
+        out.push(c);
+    }
+    if truncated {
+        let keep = max_bytes.saturating_sub(TRUNCATION_MARKER.len());
+        while out.len() > keep {
+            out.pop();
+        }
+        if max_bytes >= TRUNCATION_MARKER.len() {
+            out.push_str(TRUNCATION_MARKER);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sanitize_untrusted() {
+        assert_eq!(
+            sanitize_untrusted(b"https://example.com:9191", MAX_URL_BYTES),
+            "https://example.com:9191"
+        );
+        // Escape sequences and line breaks are dropped
+        assert_eq!(
+            sanitize_untrusted(b"http://a.com\x1b[31m\r\nFAKE LOG LINE", MAX_URL_BYTES),
+            "http://a.com[31mFAKE LOG LINE"
+        );
+        assert_eq!(
+            sanitize_untrusted("abc\u{202E}def".as_bytes(), MAX_URL_BYTES),
+            "abcdef"
+        );
+        // Invalid UTF-8 is replaced instead of rejected
+        assert_eq!(
+            sanitize_untrusted(&[b'a', 0xff, b'b'], MAX_URL_BYTES),
+            "a\u{FFFD}b"
+        );
+        assert_eq!(sanitize_untrusted(b"abcdefghij", 8), "abcde...");
+        assert_eq!(sanitize_untrusted(b"abcdefgh", 8), "abcdefgh");
+        assert_eq!(sanitize_untrusted(b"abcdefgh", 2), "ab");
+        // Multi byte chars are not split
+        assert_eq!(sanitize_untrusted("ééééé".as_bytes(), 8), "éé...");
+
+        let huge = vec![b'a'; 4 * 1024 * 1024];
+        let sanitized = sanitize_untrusted(&huge, MAX_URL_BYTES);
+        assert_eq!(sanitized.len(), MAX_URL_BYTES);
+        assert!(sanitized.ends_with(TRUNCATION_MARKER));
+    }
+
+    proptest! {
+        #[test]
+        fn test_sanitize_untrusted_bytes_is_safe_and_bounded(
+            bytes in proptest::collection::vec(any::<u8>(), 0..2048),
+            max_bytes in 0usize..600,
+        ) {
+            let sanitized = sanitize_untrusted(&bytes, max_bytes);
+            prop_assert!(sanitized.len() <= max_bytes);
+            prop_assert!(!sanitized.chars().any(is_unsafe_char));
+        }
+
+        #[test]
+        fn test_sanitize_untrusted_str_is_safe_and_bounded(
+            s in any::<String>(),
+            max_bytes in 0usize..600,
+        ) {
+            let sanitized = sanitize_untrusted(s.as_bytes(), max_bytes);
+            prop_assert!(sanitized.len() <= max_bytes);
+            prop_assert!(!sanitized.chars().any(is_unsafe_char));
+            // Clean values that fit are kept as they are
+            if s.len() <= max_bytes && !s.chars().any(is_unsafe_char) {
+                prop_assert_eq!(sanitized, s);
+            }
+        }
+    }
+}
@@ -0,0 +1,874 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Executes pre-signed governance actions once their time window opens.
+//!
+//! Operators drop bundle files (`<name>.json`) into the configured directory.
+//! Every poll, each bundle is re-validated against the current committee and
+//! the on-chain nonce, then submitted by the node when `not_before` has passed
+//! and the bundle says `enabled: true`. The outcome is written next to the
+//! bundle as `<name>.result.json`, which also stops it from running again.
+//! A bundle whose nonce is ahead of the on-chain one waits for the actions
+//! before it. Bundles that fail validation, including those whose nonce was
+//! already used, are moved to `quarantine/` along with a
+//! `<name>.reason` file. Every execution and quarantine is appended to
+//! `audit.log` as a JSON line.
+
+use crate::crypto::{BridgeAuthorityPublicKeyBytes, BridgeAuthoritySignInfo};
+use crate::error::{BridgeError, BridgeResult};
+use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use crate::starcoin_bridge_transaction_builder::starcoin_native::{
+    build_execute_emergency_op, build_execute_token_pause,
+};
+use crate::tx_digest::{tagged, TxChain};
+use crate::types::{BridgeAction, BridgeCommittee};
+use arc_swap::ArcSwap;
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use starcoin_bridge_types::base_types::StarcoinAddress;
+use starcoin_bridge_types::bridge::BridgeSummary;
+use starcoin_bridge_types::crypto::StarcoinKeyPair;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::{self, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+const BUNDLE_EXTENSION: &str = ".json";
+const RESULT_EXTENSION: &str = ".result.json";
+const REASON_EXTENSION: &str = ".reason";
+const QUARANTINE_DIR: &str = "quarantine";
+const AUDIT_LOG: &str = "audit.log";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledActionBundle {
+    pub action: BridgeAction,
+    pub signatures: Vec<BridgeAuthoritySignInfo>,
+    // Unix timestamp in seconds. The action is not executed before it.
+    pub not_before: u64,
+    // Must be explicitly set for the node to execute the bundle
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledActionState {
+    Waiting,
+    Disabled,
+    Executed,
+    Failed,
+    Quarantined,
+}
+
+// One bundle as reported by the health endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledActionStatus {
+    pub file: String,
+    pub state: ScheduledActionState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<BridgeAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<u64>,
+    // Tx digest, execution error or quarantine reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledActionResult {
+    pub executed_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledActionAuditEntry {
+    pub timestamp: u64,
+    pub file: String,
+    pub state: ScheduledActionState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<BridgeAction>,
+    pub detail: String,
+}
+
+// Wall clock in unix seconds, replaced by a fake one in tests.
+pub trait WallClock: Send + Sync {
+    fn now_secs(&self) -> u64;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemWallClock;
+
+impl WallClock for SystemWallClock {
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before unix epoch")
+            .as_secs()
+    }
+}
+
+// Checks that `bundle` can still be executed on Starcoin: the action is a
+// supported governance action for this bridge, its signatures reach quorum in
+// the current committee and its nonce has not been used yet. A nonce ahead of
+// the expected one is valid, the bundle runs once the actions before it did.
+pub fn validate_bundle(
+    bundle: &ScheduledActionBundle,
+    committee: &BridgeCommittee,
+    summary: &BridgeSummary,
+) -> Result<(), String> {
+    let action = &bundle.action;
+    if !matches!(
+        action,
+        BridgeAction::EmergencyAction(_) | BridgeAction::TokenPauseAction(_)
+    ) {
+        return Err(format!(
+            "Action type {} cannot be scheduled",
+            action.action_type() as u8
+        ));
+    }
+    if action.chain_id() as u8 != summary.chain_id {
+        return Err(format!(
+            "Action is for chain {:?}, but the bridge is on chain {}",
+            action.chain_id(),
+            summary.chain_id
+        ));
+    }
+
+    let mut signers = BTreeMap::<BridgeAuthorityPublicKeyBytes, u64>::new();
+    for sig in &bundle.signatures {
+        sig.verify(action, committee)
+            .map_err(|e| format!("Invalid signature: {:?}", e))?;
+        let signer = sig.authority_pub_key_bytes();
+        let stake = committee.active_stake(&signer);
+        if signers.insert(signer, stake).is_some() {
+            return Err("Duplicate signer".to_string());
+        }
+    }
+    let stake: u64 = signers.values().sum();
+    if stake < action.approval_threshold() {
+        return Err(format!(
+            "Signatures carry {} voting power, below threshold {}",
+            stake,
+            action.approval_threshold()
+        ));
+    }
+
+    let expected_nonce = expected_nonce(action, summary);
+    if action.seq_number() < expected_nonce {
+        return Err(format!(
+            "Nonce {} was already used, the expected nonce is {}",
+            action.seq_number(),
+            expected_nonce
+        ));
+    }
+    Ok(())
+}
+
+// Next nonce the bridge accepts for actions of the type of `action`
+fn expected_nonce(action: &BridgeAction, summary: &BridgeSummary) -> u64 {
+    summary
+        .sequence_nums
+        .iter()
+        .find(|(action_type, _)| *action_type == action.action_type() as u8)
+        .map_or(0, |(_, nonce)| *nonce)
+}
+
+pub struct ActionScheduler<C, K = SystemWallClock> {
+    dir: PathBuf,
+    starcoin_bridge_client: Arc<StarcoinClient<C>>,
+    key: StarcoinKeyPair,
+    // Where the bridge Move module is deployed
+    bridge_module_address: StarcoinAddress,
+    statuses: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
+    clock: K,
+}
+
+impl<C> ActionScheduler<C, SystemWallClock>
+where
+    C: StarcoinClientInner + 'static,
+{
+    pub fn new(
+        dir: PathBuf,
+        starcoin_bridge_client: Arc<StarcoinClient<C>>,
+        key: StarcoinKeyPair,
+        bridge_module_address: StarcoinAddress,
+        statuses: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
+    ) -> Self {
+        Self::with_clock(
+            dir,
+            starcoin_bridge_client,
+            key,
+            bridge_module_address,
+            statuses,
+            SystemWallClock,
+        )
+    }
+}
+
+impl<C, K> ActionScheduler<C, K>
+where
+    C: StarcoinClientInner + 'static,
+    K: WallClock,
+{
+    pub fn with_clock(
+        dir: PathBuf,
+        starcoin_bridge_client: Arc<StarcoinClient<C>>,
+        key: StarcoinKeyPair,
+        bridge_module_address: StarcoinAddress,
+        statuses: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
+        clock: K,
+    ) -> Self {
+        Self {
+            dir,
+            starcoin_bridge_client,
+            key,
+            bridge_module_address,
+            statuses,
+            clock,
+        }
+    }
+
+    pub async fn run(self, poll_interval: Duration) {
+        info!(dir = ?self.dir, "Starting scheduled action executor");
+        let mut interval = time::interval(poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("Failed to process scheduled actions: {:?}", e);
+            }
+        }
+    }
+
+    // Processes every bundle in the directory once, in file name order, and
+    // publishes their status. The nonces are read again after each execution,
+    // so consecutive actions can run in the same poll.
+    pub async fn poll(&self) -> BridgeResult<()> {
+        let mut statuses = vec![];
+        let bundle_files = self.bundle_files()?;
+        // Only hit the RPC when there is something to validate
+        let mut chain_state = if bundle_files.iter().any(|f| !result_path(f).exists()) {
+            let summary = self.starcoin_bridge_client.get_bridge_summary().await?;
+            let committee = self.starcoin_bridge_client.get_bridge_committee().await?;
+            Some((summary, committee))
+        } else {
+            None
+        };
+        for path in bundle_files {
+            let status = match &mut chain_state {
+                Some((summary, committee)) => {
+                    self.process_bundle(&path, summary, committee).await?
+                }
+                None => executed_status(&path)?,
+            };
+            statuses.push(status);
+        }
+        statuses.extend(self.quarantined()?);
+        self.statuses.store(Arc::new(statuses));
+        Ok(())
+    }
+
+    // Validates and, if its time has come, executes the bundle at `path`.
+    // `summary` is read again after an execution.
+    async fn process_bundle(
+        &self,
+        path: &Path,
+        summary: &mut BridgeSummary,
+        committee: &BridgeCommittee,
+    ) -> BridgeResult<ScheduledActionStatus> {
+        if result_path(path).exists() {
+            return executed_status(path);
+        }
+        let file = file_name(path);
+        let bundle = match read_bundle(path) {
+            Ok(bundle) => bundle,
+            Err(reason) => return self.quarantine(path, None, reason),
+        };
+        if let Err(reason) = validate_bundle(&bundle, committee, summary) {
+            return self.quarantine(path, Some(bundle.action), reason);
+        }
+        let mut status = ScheduledActionStatus {
+            file,
+            state: ScheduledActionState::Waiting,
+            action: Some(bundle.action.clone()),
+            not_before: Some(bundle.not_before),
+            detail: None,
+        };
+        if !bundle.enabled {
+            status.state = ScheduledActionState::Disabled;
+            return Ok(status);
+        }
+        let expected_nonce = expected_nonce(&bundle.action, summary);
+        if bundle.action.seq_number() > expected_nonce {
+            status.detail = Some(format!(
+                "Waiting for the actions before nonce {}, the expected nonce is {}",
+                bundle.action.seq_number(),
+                expected_nonce
+            ));
+            return Ok(status);
+        }
+        if self.clock.now_secs() < bundle.not_before {
+            return Ok(status);
+        }
+
+        info!(file = status.file, action = ?bundle.action, "Executing scheduled action");
+        let result = match self.execute(&bundle).await {
+            Ok(txn_hash) => ScheduledActionResult {
+                executed_at: self.clock.now_secs(),
+                tx_digest: Some(tagged(TxChain::Starcoin, &txn_hash)),
+                error: None,
+            },
+            Err(e) => {
+                error!(file = status.file, "Scheduled action failed: {:?}", e);
+                ScheduledActionResult {
+                    executed_at: self.clock.now_secs(),
+                    tx_digest: None,
+                    error: Some(format!("{:?}", e)),
+                }
+            }
+        };
+        write_json(&result_path(path), &result)?;
+        let status = status_from_result(status, &result);
+        self.audit(
+            &status.file,
+            status.state,
+            Some(bundle.action),
+            detail(&result),
+        )?;
+        *summary = self.starcoin_bridge_client.get_bridge_summary().await?;
+        Ok(status)
+    }
+
+    async fn execute(&self, bundle: &ScheduledActionBundle) -> BridgeResult<String> {
+        let client = &self.starcoin_bridge_client;
+        let sender = self.key.starcoin_address();
+        let block_timestamp_ms = client.get_block_timestamp().await?;
+        let chain_id = client
+            .get_chain_identifier()
+            .await?
+            .parse::<u8>()
+            .map_err(|e| BridgeError::Generic(format!("Invalid Starcoin chain id: {e}")))?;
+        // The Starcoin committee has a single member, so quorum means exactly
+        // one valid signature.
+        let signature = bundle.signatures[0].signature.as_bytes().to_vec();
+        let gas_unit_price = client.get_gas_unit_price().await;
+        let max_gas_amount = client.max_gas_amount(bundle.action.action_type());
+        let build_txn = |sequence_number| match &bundle.action {
+            BridgeAction::EmergencyAction(a) => build_execute_emergency_op(
+                self.bridge_module_address,
+                sender,
+                sequence_number,
+                chain_id,
+                block_timestamp_ms,
+                gas_unit_price,
+                max_gas_amount,
+                a.chain_id as u8,
+                a.nonce,
+                a.action_type as u8,
+                signature.clone(),
+            ),
+            BridgeAction::TokenPauseAction(a) => build_execute_token_pause(
+                self.bridge_module_address,
+                sender,
+                sequence_number,
+                chain_id,
+                block_timestamp_ms,
+                gas_unit_price,
+                max_gas_amount,
+                a.chain_id as u8,
+                a.nonce,
+                a.token_id,
+                a.pause,
+                signature.clone(),
+            ),
+            action => Err(BridgeError::Generic(format!(
+                "Unsupported scheduled action: {:?}",
+                action
+            ))),
+        };
+        client
+            .submit_with_managed_sequence_and_wait(&self.key, build_txn)
+            .await
+            .map(|receipt| receipt.txn_hash)
+    }
+
+    // Moves the bundle into `quarantine/` next to a file explaining why.
+    fn quarantine(
+        &self,
+        path: &Path,
+        action: Option<BridgeAction>,
+        reason: String,
+    ) -> BridgeResult<ScheduledActionStatus> {
+        let file = file_name(path);
+        warn!(file, reason, "Quarantining scheduled action");
+        let quarantine_dir = self.dir.join(QUARANTINE_DIR);
+        fs::create_dir_all(&quarantine_dir).map_err(io_error)?;
+        fs::rename(path, quarantine_dir.join(&file)).map_err(io_error)?;
+        fs::write(reason_path(&quarantine_dir, &file), &reason).map_err(io_error)?;
+        self.audit(
+            &file,
+            ScheduledActionState::Quarantined,
+            action.clone(),
+            reason.clone(),
+        )?;
+        Ok(ScheduledActionStatus {
+            file,
+            state: ScheduledActionState::Quarantined,
+            action,
+            not_before: None,
+            detail: Some(reason),
+        })
+    }
+
+    fn quarantined(&self) -> BridgeResult<Vec<ScheduledActionStatus>> {
+        let quarantine_dir = self.dir.join(QUARANTINE_DIR);
+        if !quarantine_dir.exists() {
+            return Ok(vec![]);
+        }
+        Ok(list_bundles(&quarantine_dir)?
+            .into_iter()
+            .map(|path| {
+                let file = file_name(&path);
+                let bundle = read_bundle(&path).ok();
+                ScheduledActionStatus {
+                    detail: fs::read_to_string(reason_path(&quarantine_dir, &file)).ok(),
+                    file,
+                    state: ScheduledActionState::Quarantined,
+                    action: bundle.as_ref().map(|b| b.action.clone()),
+                    not_before: bundle.map(|b| b.not_before),
+                }
+            })
+            .collect())
+    }
+
+    fn bundle_files(&self) -> BridgeResult<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+        list_bundles(&self.dir)
+    }
+
+    fn audit(
+        &self,
+        file: &str,
+        state: ScheduledActionState,
+        action: Option<BridgeAction>,
+        detail: String,
+    ) -> BridgeResult<()> {
+        let entry = ScheduledActionAuditEntry {
+            timestamp: self.clock.now_secs(),
+            file: file.to_string(),
+            state,
+            action,
+            detail,
+        };
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?;
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(AUDIT_LOG))
+            .map_err(io_error)?;
+        writeln!(log, "{line}").map_err(io_error)
+    }
+}
+
+// Bundle files in `dir`, sorted by name so execution order is predictable.
+fn list_bundles(dir: &Path) -> BridgeResult<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let name = file_name(&path);
+        if path.is_file() && name.ends_with(BUNDLE_EXTENSION) && !name.ends_with(RESULT_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_bundle(path: &Path) -> Result<ScheduledActionBundle, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read bundle: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Malformed bundle: {e}"))
+}
+
+fn executed_status(path: &Path) -> BridgeResult<ScheduledActionStatus> {
+    let content = fs::read_to_string(result_path(path)).map_err(io_error)?;
+    let result: ScheduledActionResult = serde_json::from_str(&content)
+        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?;
+    let bundle = read_bundle(path).ok();
+    let status = ScheduledActionStatus {
+        file: file_name(path),
+        state: ScheduledActionState::Executed,
+        action: bundle.as_ref().map(|b| b.action.clone()),
+        not_before: bundle.map(|b| b.not_before),
+        detail: None,
+    };
+    Ok(status_from_result(status, &result))
+}
+
+fn status_from_result(
+    status: ScheduledActionStatus,
+    result: &ScheduledActionResult,
+) -> ScheduledActionStatus {
+    let state = if result.error.is_some() {
+        ScheduledActionState::Failed
+    } else {
+        ScheduledActionState::Executed
+    };
+    ScheduledActionStatus {
+        state,
+        detail: Some(detail(result)),
+        ..status
+    }
+}
+
+fn detail(result: &ScheduledActionResult) -> String {
+    result
+        .error
+        .clone()
+        .or_else(|| result.tx_digest.clone())
+        .unwrap_or_default()
+}
+
+fn result_path(bundle: &Path) -> PathBuf {
+    let name = file_name(bundle);
+    let stem = name.strip_suffix(BUNDLE_EXTENSION).unwrap_or(&name);
+    bundle.with_file_name(format!("{stem}{RESULT_EXTENSION}"))
+}
+
+fn reason_path(dir: &Path, file: &str) -> PathBuf {
+    let stem = file.strip_suffix(BUNDLE_EXTENSION).unwrap_or(file);
+    dir.join(format!("{stem}{REASON_EXTENSION}"))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> BridgeResult<()> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?;
+    fs::write(path, content).map_err(io_error)
+}
+
+fn io_error(e: std::io::Error) -> BridgeError {
+    BridgeError::Generic(format!("Scheduled actions IO error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::starcoin_bridge_mock_client::StarcoinMockClient;
+    use crate::test_utils::{
+        bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+    };
+    use crate::types::{BridgeActionType, EmergencyAction, EmergencyActionType, TokenPauseAction};
+    use starcoin_bridge_types::bridge::{BridgeChainId, BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER};
+    use starcoin_bridge_types::crypto::get_key_pair;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Clone, Default)]
+    struct FakeClock(Arc<AtomicU64>);
+
+    impl FakeClock {
+        fn set(&self, secs: u64) {
+            self.0.store(secs, Ordering::Relaxed);
+        }
+    }
+
+    impl WallClock for FakeClock {
+        fn now_secs(&self) -> u64 {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    struct Setup {
+        dir: tempfile::TempDir,
+        mock: StarcoinMockClient,
+        clock: FakeClock,
+        statuses: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
+        scheduler: ActionScheduler<StarcoinMockClient, FakeClock>,
+        secret: crate::crypto::BridgeAuthorityKeyPair,
+    }
+
+    fn setup() -> Setup {
+        let (authority, _, secret) =
+            get_test_authority_and_key(BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, 9999);
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+        let mock = StarcoinMockClient::default().with_chain_identifier("254");
+        mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(committee));
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::default();
+        let statuses: Arc<ArcSwap<Vec<ScheduledActionStatus>>> = Default::default();
+        let (_, key) = get_key_pair::<fastcrypto::ed25519::Ed25519KeyPair>();
+        let scheduler = ActionScheduler::with_clock(
+            dir.path().to_path_buf(),
+            Arc::new(StarcoinClient::new_for_testing(mock.clone())),
+            StarcoinKeyPair::Ed25519(key),
+            StarcoinAddress::new([1u8; 16]),
+            statuses.clone(),
+            clock.clone(),
+        );
+        Setup {
+            dir,
+            mock,
+            clock,
+            statuses,
+            scheduler,
+            secret,
+        }
+    }
+
+    fn write_bundle(setup: &Setup, name: &str, action: BridgeAction, enabled: bool) {
+        let bundle = ScheduledActionBundle {
+            signatures: vec![BridgeAuthoritySignInfo::new(&action, &setup.secret)],
+            action,
+            not_before: 1_000,
+            enabled,
+        };
+        write_json(&setup.dir.path().join(name), &bundle).unwrap();
+    }
+
+    fn pause_action(nonce: u64) -> BridgeAction {
+        BridgeAction::EmergencyAction(EmergencyAction {
+            nonce,
+            chain_id: BridgeChainId::StarcoinMainnet,
+            action_type: EmergencyActionType::Pause,
+        })
+    }
+
+    fn audit_log(setup: &Setup) -> Vec<ScheduledActionAuditEntry> {
+        fs::read_to_string(setup.dir.path().join(AUDIT_LOG))
+            .unwrap_or_default()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn states(setup: &Setup) -> Vec<(String, ScheduledActionState)> {
+        setup
+            .statuses
+            .load()
+            .iter()
+            .map(|s| (s.file.clone(), s.state))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_load_validate_execute() {
+        let setup = setup();
+        setup
+            .mock
+            .set_sequence_nums(vec![(BridgeActionType::EmergencyButton as u8, 4)]);
+        write_bundle(&setup, "pause.json", pause_action(4), true);
+
+        // Valid but the window is not open yet
+        setup.clock.set(999);
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(
+            states(&setup),
+            vec![("pause.json".to_string(), ScheduledActionState::Waiting)]
+        );
+        assert!(setup.mock.submitted_transactions().is_empty());
+
+        setup.clock.set(1_000);
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(
+            states(&setup),
+            vec![("pause.json".to_string(), ScheduledActionState::Executed)]
+        );
+        assert_eq!(setup.mock.submitted_transactions().len(), 1);
+        let result: ScheduledActionResult = serde_json::from_str(
+            &fs::read_to_string(setup.dir.path().join("pause.result.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result.executed_at, 1_000);
+        assert!(result.tx_digest.unwrap().starts_with("stc:0x"));
+        let audit = audit_log(&setup);
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].state, ScheduledActionState::Executed);
+        assert_eq!(audit[0].action, Some(pause_action(4)));
+
+        // The result file keeps it from running again, even though the nonce
+        // has moved on
+        setup
+            .mock
+            .set_sequence_nums(vec![(BridgeActionType::EmergencyButton as u8, 5)]);
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(setup.mock.submitted_transactions().len(), 1);
+        assert_eq!(
+            states(&setup),
+            vec![("pause.json".to_string(), ScheduledActionState::Executed)]
+        );
+        assert_eq!(audit_log(&setup).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_bundle_is_not_executed() {
+        let setup = setup();
+        write_bundle(&setup, "pause.json", pause_action(0), false);
+        setup.clock.set(2_000);
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(
+            states(&setup),
+            vec![("pause.json".to_string(), ScheduledActionState::Disabled)]
+        );
+        assert!(setup.mock.submitted_transactions().is_empty());
+        assert!(!setup.dir.path().join("pause.result.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_quarantine() {
+        let setup = setup();
+        setup.clock.set(2_000);
+        setup.mock.set_sequence_nums(vec![
+            (BridgeActionType::EmergencyButton as u8, 4),
+            (BridgeActionType::TokenPause as u8, 2),
+        ]);
+
+        // Stale nonce
+        write_bundle(&setup, "a_stale.json", pause_action(3), true);
+        // Nonce ahead of the chain, waits for the actions before it
+        write_bundle(&setup, "f_future.json", pause_action(5), true);
+        // Signed by someone outside the committee
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 2,
+            chain_id: BridgeChainId::StarcoinMainnet,
+            token_id: 3,
+            pause: true,
+        });
+        let (_, _, outsider) = get_test_authority_and_key(5000, 9998);
+        let bundle = ScheduledActionBundle {
+            signatures: vec![BridgeAuthoritySignInfo::new(&action, &outsider)],
+            action: action.clone(),
+            not_before: 0,
+            enabled: true,
+        };
+        write_json(&setup.dir.path().join("b_outsider.json"), &bundle).unwrap();
+        // Not enough signatures
+        let bundle = ScheduledActionBundle {
+            signatures: vec![],
+            ..bundle
+        };
+        write_json(&setup.dir.path().join("c_unsigned.json"), &bundle).unwrap();
+        // Not a bundle at all
+        fs::write(setup.dir.path().join("d_garbage.json"), "{").unwrap();
+        // A valid bundle alongside is still executed
+        write_bundle(&setup, "e_valid.json", action, true);
+
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(setup.mock.submitted_transactions().len(), 1);
+        let quarantine_dir = setup.dir.path().join(QUARANTINE_DIR);
+        for (name, reason) in [
+            (
+                "a_stale",
+                "Nonce 3 was already used, the expected nonce is 4",
+            ),
+            ("b_outsider", "Invalid signature"),
+            ("c_unsigned", "below threshold"),
+            ("d_garbage", "Malformed bundle"),
+        ] {
+            assert!(!setup.dir.path().join(format!("{name}.json")).exists());
+            assert!(quarantine_dir.join(format!("{name}.json")).exists());
+            let written =
+                fs::read_to_string(quarantine_dir.join(format!("{name}.reason"))).unwrap();
+            assert!(written.contains(reason), "{name}: {written}");
+        }
+        assert!(setup.dir.path().join("f_future.json").exists());
+        assert!(!quarantine_dir.join("f_future.json").exists());
+        assert_eq!(
+            states(&setup),
+            vec![
+                ("e_valid.json".to_string(), ScheduledActionState::Executed),
+                ("f_future.json".to_string(), ScheduledActionState::Waiting),
+                (
+                    "a_stale.json".to_string(),
+                    ScheduledActionState::Quarantined
+                ),
+                (
+                    "b_outsider.json".to_string(),
+                    ScheduledActionState::Quarantined
+                ),
+                (
+                    "c_unsigned.json".to_string(),
+                    ScheduledActionState::Quarantined
+                ),
+                (
+                    "d_garbage.json".to_string(),
+                    ScheduledActionState::Quarantined
+                ),
+            ]
+        );
+        let audit = audit_log(&setup);
+        assert_eq!(
+            audit.iter().map(|e| e.state).collect::<Vec<_>>(),
+            vec![
+                ScheduledActionState::Quarantined,
+                ScheduledActionState::Quarantined,
+                ScheduledActionState::Quarantined,
+                ScheduledActionState::Quarantined,
+                ScheduledActionState::Executed,
+            ]
+        );
+
+        // Quarantined bundles stay listed with their reason
+        setup.scheduler.poll().await.unwrap();
+        let statuses = setup.statuses.load();
+        assert_eq!(statuses.len(), 6);
+        assert!(statuses[1]
+            .detail
+            .as_ref()
+            .unwrap()
+            .contains("Waiting for the actions before nonce 5"));
+        assert!(statuses[2].detail.as_ref().unwrap().contains("Nonce 3"));
+        assert_eq!(audit_log(&setup).len(), 5);
+
+        // The future bundle runs once the chain catches up
+        setup
+            .mock
+            .set_sequence_nums(vec![(BridgeActionType::EmergencyButton as u8, 5)]);
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(setup.mock.submitted_transactions().len(), 2);
+        assert_eq!(
+            states(&setup)[1],
+            ("f_future.json".to_string(), ScheduledActionState::Executed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_bundles_run_in_one_poll() {
+        let setup = setup();
+        setup.clock.set(1_000);
+        setup
+            .mock
+            .set_sequence_nums(vec![(BridgeActionType::EmergencyButton as u8, 4)]);
+        setup
+            .mock
+            .advance_sequence_num_on_submit(BridgeActionType::EmergencyButton as u8);
+        write_bundle(&setup, "pause_4.json", pause_action(4), true);
+        write_bundle(&setup, "pause_5.json", pause_action(5), true);
+        write_bundle(&setup, "pause_7.json", pause_action(7), true);
+
+        setup.scheduler.poll().await.unwrap();
+        assert_eq!(setup.mock.submitted_transactions().len(), 2);
+        assert_eq!(
+            states(&setup),
+            vec![
+                ("pause_4.json".to_string(), ScheduledActionState::Executed),
+                ("pause_5.json".to_string(), ScheduledActionState::Executed),
+                ("pause_7.json".to_string(), ScheduledActionState::Waiting),
+            ]
+        );
+        assert!(!setup.dir.path().join(QUARANTINE_DIR).exists());
+    }
+}
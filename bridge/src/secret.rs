@@ -0,0 +1,126 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `Secret<T>` marks configuration values that must never be printed.
+//!
+//! `Display` and `Debug` always redact. `Serialize` is transparent so config
+//! files still round-trip, except inside `redacted_json`, which renders every
+//! `Secret` as a short sha256 fingerprint. Two configs with the same secret
+//! therefore render identically, while the secret itself never leaves memory.
+
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static REDACT: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Serialize> Secret<T> {
+    // Short sha256 prefix of the serialized value, stable across runs.
+    pub fn fingerprint(&self) -> String {
+        let bytes = serde_json::to_vec(&self.0).unwrap_or_default();
+        let digest = Sha256::digest(&bytes);
+        format!("sha256:{}", &Hex::encode(digest.digest)[..16])
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+impl<T: Serialize> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if REDACT.with(|r| r.get()) {
+            serializer.serialize_str(&format!("<redacted {}>", self.fingerprint()))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+// Serialize `value` to JSON with every `Secret` replaced by its fingerprint.
+pub fn redacted_json<T: Serialize>(value: &T) -> serde_json::Result<serde_json::Value> {
+    struct Guard(bool);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            REDACT.with(|r| r.set(self.0));
+        }
+    }
+    let _guard = Guard(REDACT.with(|r| r.replace(true)));
+    serde_json::to_value(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct TestConfig {
+        url: String,
+        password: Secret<String>,
+    }
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let config: TestConfig =
+            serde_json::from_str(r#"{"url":"http://a","password":"hunter2"}"#).unwrap();
+        assert_eq!(config.password.expose_secret(), "hunter2");
+        assert_eq!(format!("{}", config.password), "<redacted>");
+        assert_eq!(format!("{:?}", config.password), "<redacted>");
+
+        let rendered = redacted_json(&config).unwrap().to_string();
+        assert!(!rendered.contains("hunter2"));
+        assert!(rendered.contains(&config.password.fingerprint()));
+        assert!(rendered.contains("http://a"));
+
+        // Plain serialization still round-trips the config file
+        let plain = serde_json::to_string(&config).unwrap();
+        assert!(plain.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let a = Secret::new("hunter2".to_string());
+        let b = Secret::new("hunter2".to_string());
+        let c = Secret::new("hunter3".to_string());
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert!(a.fingerprint().starts_with("sha256:"));
+    }
+}
@@ -7,6 +7,7 @@ use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
 use crate::error::{BridgeError, BridgeResult};
 use crate::eth_client::EthClient;
 use crate::metrics::BridgeMetrics;
+use crate::registration::RegistrationGuard;
 use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
 use crate::types::{BridgeAction, SignedBridgeAction};
 use async_trait::async_trait;
@@ -61,8 +62,10 @@ struct StarcoinActionVerifier<C> {
     starcoin_bridge_client: Arc<StarcoinClient<C>>,
 }
 
+// Looks the transaction up on every watched EVM chain, as a tx hash alone
+// doesn't say which chain it is on
 struct EthActionVerifier<P> {
-    eth_client: Arc<EthClient<P>>,
+    eth_clients: Vec<Arc<EthClient<P>>>,
 }
 
 #[async_trait::async_trait]
@@ -94,10 +97,22 @@ where
 
     async fn verify(&self, key: (TxHash, u16)) -> BridgeResult<BridgeAction> {
         let (tx_hash, event_idx) = key;
-        self.eth_client
-            .get_finalized_bridge_action_maybe(tx_hash, event_idx)
-            .await
-            .tap_ok(|action| info!("Eth action found: {:?}", action))
+        let mut err = BridgeError::TxNotFound;
+        for eth_client in &self.eth_clients {
+            match eth_client
+                .get_finalized_bridge_action_maybe(tx_hash, event_idx)
+                .await
+            {
+                Ok(action) => {
+                    info!("Eth action found: {:?}", action);
+                    return Ok(action);
+                }
+                // Not on this chain, try the next one
+                Err(BridgeError::TxNotFound) => {}
+                Err(e) => err = e,
+            }
+        }
+        Err(err)
     }
 }
 
@@ -225,6 +240,9 @@ pub struct BridgeRequestHandler {
         BridgeAction,
         oneshot::Sender<BridgeResult<SignedBridgeAction>>,
     )>,
+    // Refuses signing requests while the registration doesn't match, if so
+    // configured
+    registration: RegistrationGuard,
 }
 
 impl BridgeRequestHandler {
@@ -234,8 +252,9 @@ impl BridgeRequestHandler {
     >(
         signer: BridgeAuthorityKeyPair,
         starcoin_bridge_client: Arc<StarcoinClient<SC>>,
-        eth_client: Arc<EthClient<EP>>,
+        eth_clients: Vec<Arc<EthClient<EP>>>,
         approved_governance_actions: Vec<BridgeAction>,
+        registration: RegistrationGuard,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let (starcoin_bridge_signer_tx, starcoin_bridge_rx) =
@@ -272,7 +291,7 @@ impl BridgeRequestHandler {
         .spawn(starcoin_bridge_rx);
         SignerWithCache::new(
             signer.clone(),
-            EthActionVerifier { eth_client },
+            EthActionVerifier { eth_clients },
             metrics.clone(),
         )
         .spawn(eth_rx);
@@ -287,6 +306,7 @@ impl BridgeRequestHandler {
             starcoin_bridge_signer_tx,
             eth_signer_tx,
             governance_signer_tx,
+            registration,
         }
     }
 }
@@ -298,6 +318,7 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         tx_hash_hex: String,
         event_idx: u16,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        self.registration.check_signing_allowed()?;
         let tx_hash = TxHash::from_str(&tx_hash_hex).map_err(|_| BridgeError::InvalidTxHash)?;
 
         let (tx, rx) = oneshot::channel();
@@ -316,6 +337,7 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         tx_digest_hex: String,
         event_idx: u16,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        self.registration.check_signing_allowed()?;
         // Client sends hex-encoded tx_digest, decode it
         let tx_digest: TransactionDigest = hex::decode(tx_digest_hex.trim_start_matches("0x"))
             .ok()
@@ -336,6 +358,7 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         &self,
         action: BridgeAction,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        self.registration.check_signing_allowed()?;
         if !action.is_governace_action() {
             return Err(BridgeError::ActionIsNotGovernanceAction(action));
         }
@@ -525,7 +548,7 @@ mod tests {
             HashSet::from_iter(vec![contract_address]),
         );
         let eth_verifier = EthActionVerifier {
-            eth_client: Arc::new(eth_client),
+            eth_clients: vec![Arc::new(eth_client)],
         };
         let metrics = Arc::new(BridgeMetrics::new_for_testing());
         let mut eth_signer_with_cache =
@@ -586,6 +609,68 @@ mod tests {
         entry_.unwrap().lock().await.clone().unwrap().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_eth_verifier_looks_up_every_chain() {
+        let first_provider = EthMockProvider::default();
+        let second_provider = EthMockProvider::default();
+        let second_contract = EthAddress::random();
+        let eth_verifier = EthActionVerifier {
+            eth_clients: vec![
+                Arc::new(EthClient::new_mocked(
+                    first_provider.clone(),
+                    HashSet::from_iter(vec![EthAddress::random()]),
+                )),
+                Arc::new(EthClient::new_mocked(
+                    second_provider.clone(),
+                    HashSet::from_iter(vec![second_contract]),
+                )),
+            ],
+        };
+        let no_receipt = |provider: &EthMockProvider, tx_hash: TxHash| {
+            provider
+                .add_response::<[TxHash; 1], Option<TransactionReceipt>, Option<TransactionReceipt>>(
+                    "eth_getTransactionReceipt",
+                    [tx_hash],
+                    None,
+                )
+                .unwrap();
+        };
+
+        // The deposit is on the second chain only
+        let eth_tx_hash = TxHash::random();
+        let (log, action) = get_test_log_and_action(second_contract, eth_tx_hash, 0);
+        no_receipt(&first_provider, eth_tx_hash);
+        second_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        mock_last_finalized_block(&second_provider, log.block_number.unwrap().as_u64());
+        assert_eq!(eth_verifier.verify((eth_tx_hash, 0)).await.unwrap(), action);
+
+        // Neither chain knows the transaction
+        let unknown_tx_hash = TxHash::random();
+        no_receipt(&first_provider, unknown_tx_hash);
+        no_receipt(&second_provider, unknown_tx_hash);
+        assert_eq!(
+            eth_verifier.verify((unknown_tx_hash, 0)).await.unwrap_err(),
+            BridgeError::TxNotFound
+        );
+
+        // A chain that has the transaction explains why it can't be signed yet
+        mock_last_finalized_block(&second_provider, 0);
+        assert_eq!(
+            eth_verifier.verify((eth_tx_hash, 0)).await.unwrap_err(),
+            BridgeError::TxNotFinalized
+        );
+    }
+
     #[tokio::test]
     async fn test_signer_with_governace_verifier() {
         let action_1 = BridgeAction::EmergencyAction(EmergencyAction {
@@ -4,16 +4,23 @@
 #![allow(clippy::inconsistent_digit_grouping)]
 use crate::with_metrics;
 use crate::{
+    committee_concentration::ConcentrationGroup,
     crypto::BridgeAuthorityPublicKeyBytes,
     error::BridgeError,
     metrics::BridgeMetrics,
+    node_info::NodeInfo,
+    node_status::{NodeStatus, NodeStatusCache},
+    scheduled_actions::ScheduledActionStatus,
     server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait},
+    token_modules::TokenModuleChange,
     types::{
         AddTokensOnEvmAction, AddTokensOnStarcoinAction, AssetPriceUpdateAction,
         BlocklistCommitteeAction, BlocklistType, BridgeAction, EmergencyAction,
         EmergencyActionType, EvmContractUpgradeAction, LimitUpdateAction, SignedBridgeAction,
+        TokenPauseAction,
     },
 };
+use arc_swap::ArcSwap;
 use axum::{
     extract::{Path, State},
     Json,
@@ -35,7 +42,7 @@ pub mod governance_verifier;
 pub mod handler;
 
 #[cfg(any(feature = "test-utils", test))]
-pub(crate) mod mock_handler;
+pub mod mock_handler;
 
 pub const APPLICATION_JSON: &str = "application/json";
 
@@ -45,6 +52,8 @@ pub const MAX_LIST_SIZE: usize = 255;
 
 pub const PING_PATH: &str = "/ping";
 pub const METRICS_KEY_PATH: &str = "/metrics_pub_key";
+pub const INFO_PATH: &str = "/info";
+pub const STATUS_PATH: &str = "/status";
 
 // Important: for BridgeActions, the paths need to match the ones in bridge_client.rs
 // Note: Using :param syntax for axum 0.7.x (not {param} which is for axum 0.8.x)
@@ -53,6 +62,7 @@ pub const STARCOIN_TO_ETH_TX_PATH: &str = "/sign/bridge_tx/starcoin/eth/:tx_dige
 pub const COMMITTEE_BLOCKLIST_UPDATE_PATH: &str =
     "/sign/update_committee_blocklist/:chain_id/:nonce/:type/:keys";
 pub const EMERGENCY_BUTTON_PATH: &str = "/sign/emergency_button/:chain_id/:nonce/:type";
+pub const TOKEN_PAUSE_PATH: &str = "/sign/token_pause/:chain_id/:nonce/:token_id/:pause";
 pub const LIMIT_UPDATE_PATH: &str =
     "/sign/update_limit/:chain_id/:nonce/:sending_chain_id/:new_usd_limit";
 pub const ASSET_PRICE_UPDATE_PATH: &str =
@@ -72,6 +82,31 @@ pub const ADD_TOKENS_ON_EVM_PATH: &str =
 pub struct BridgeNodePublicMetadata {
     pub version: &'static str,
     pub metrics_pubkey: Option<Arc<Ed25519PublicKey>>,
+    // Tokens paused by governance, refreshed by the watchdog and reported by
+    // the health endpoint.
+    #[serde(skip)]
+    pub paused_token_ids: Arc<ArcSwap<Vec<u8>>>,
+    // Token modules upgraded since registration, refreshed by the token module
+    // watcher and reported by the health endpoint.
+    #[serde(skip)]
+    pub token_module_changes: Arc<ArcSwap<Vec<TokenModuleChange>>>,
+    // Pre-signed governance bundles picked up by the action scheduler.
+    #[serde(skip)]
+    pub scheduled_actions: Arc<ArcSwap<Vec<ScheduledActionStatus>>>,
+    // Committee member groups holding too much voting power, found by the
+    // concentration check at startup.
+    #[serde(skip)]
+    pub committee_concentration_alerts: Arc<ArcSwap<Vec<ConcentrationGroup>>>,
+    // Version, features and config fingerprint, set by the node at startup.
+    // Reported by the health endpoint, and by `/info` if `expose_node_info`.
+    #[serde(skip)]
+    pub node_info: Option<NodeInfo>,
+    #[serde(skip)]
+    pub expose_node_info: bool,
+    // Syncer progress, pause state and committee reachability, reported by
+    // `/status`.
+    #[serde(skip)]
+    pub status: NodeStatusCache,
 }
 
 impl BridgeNodePublicMetadata {
@@ -79,6 +114,13 @@ impl BridgeNodePublicMetadata {
         Self {
             version,
             metrics_pubkey: Some(metrics_pubkey.into()),
+            paused_token_ids: Default::default(),
+            token_module_changes: Default::default(),
+            scheduled_actions: Default::default(),
+            committee_concentration_alerts: Default::default(),
+            node_info: None,
+            expose_node_info: false,
+            status: Default::default(),
         }
     }
 
@@ -86,6 +128,13 @@ impl BridgeNodePublicMetadata {
         Self {
             version: "testing",
             metrics_pubkey: None,
+            paused_token_ids: Default::default(),
+            token_module_changes: Default::default(),
+            scheduled_actions: Default::default(),
+            committee_concentration_alerts: Default::default(),
+            node_info: None,
+            expose_node_info: false,
+            status: Default::default(),
         }
     }
 }
@@ -117,6 +166,8 @@ pub(crate) fn make_router(
         .route("/", get(health_check))
         .route(PING_PATH, get(ping))
         .route(METRICS_KEY_PATH, get(metrics_key_fetch))
+        .route(INFO_PATH, get(node_info))
+        .route(STATUS_PATH, get(node_status))
         .route(ETH_TO_STARCOIN_TX_PATH, get(handle_eth_tx_hash))
         .route(
             STARCOIN_TO_ETH_TX_PATH,
@@ -127,6 +178,7 @@ pub(crate) fn make_router(
             get(handle_update_committee_blocklist_action),
         )
         .route(EMERGENCY_BUTTON_PATH, get(handle_emergency_action))
+        .route(TOKEN_PAUSE_PATH, get(handle_token_pause_action))
         .route(LIMIT_UPDATE_PATH, get(handle_limit_update_action))
         .route(
             ASSET_PRICE_UPDATE_PATH,
@@ -146,13 +198,17 @@ pub(crate) fn make_router(
 }
 
 impl axum::response::IntoResponse for BridgeError {
-    // TODO: distinguish client error.
+    // Invalid requests are rejected as client errors, so that the requester
+    // stops retrying them. Anything else is a server error, and is asked
+    // again later: a lagging or inconsistent RPC on this node can fail a valid
+    // request for a while.
     fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {:?}", self),
-        )
-            .into_response()
+        let status = if self.is_request_error() {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        (status, format!("Something went wrong: {:?}", self)).into_response()
     }
 }
 
@@ -165,8 +221,32 @@ where
     }
 }
 
-async fn health_check() -> StatusCode {
-    StatusCode::OK
+#[derive(serde::Serialize)]
+struct HealthStatus {
+    paused_token_ids: Vec<u8>,
+    token_module_changes: Vec<TokenModuleChange>,
+    scheduled_actions: Vec<ScheduledActionStatus>,
+    committee_concentration_alerts: Vec<ConcentrationGroup>,
+    // Actions waiting for committee signatures
+    signing_queue_depth: i64,
+    node_info: Option<NodeInfo>,
+}
+
+async fn health_check(
+    State((_handler, metrics, metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Json<HealthStatus> {
+    Json(HealthStatus {
+        paused_token_ids: metadata.paused_token_ids.load().to_vec(),
+        token_module_changes: metadata.token_module_changes.load().to_vec(),
+        scheduled_actions: metadata.scheduled_actions.load().to_vec(),
+        committee_concentration_alerts: metadata.committee_concentration_alerts.load().to_vec(),
+        signing_queue_depth: metrics.action_executor_signing_queue_depth.get(),
+        node_info: metadata.node_info.clone(),
+    })
 }
 
 // Validates that a comma-separated list doesn't exceed the maximum allowed size
@@ -202,6 +282,30 @@ async fn metrics_key_fetch(
     Ok(Json(metadata.metrics_pubkey.clone()))
 }
 
+async fn node_info(
+    State((_handler, _metrics, metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<NodeInfo>, StatusCode> {
+    match &metadata.node_info {
+        Some(node_info) if metadata.expose_node_info => Ok(Json(node_info.clone())),
+        _ => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+// Served from cached state, so it is cheap enough to poll
+async fn node_status(
+    State((_handler, metrics, metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Json<NodeStatus> {
+    Json(metadata.status.snapshot(&metrics))
+}
+
 #[instrument(level = "error", skip_all, fields(tx_hash_hex=tx_hash_hex, event_idx=event_idx))]
 async fn handle_eth_tx_hash(
     Path((tx_hash_hex, event_idx)): Path<(String, u16)>,
@@ -314,6 +418,41 @@ async fn handle_emergency_action(
     with_metrics!(metrics.clone(), "handle_emergency_action", future).await
 }
 
+#[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, token_id=token_id, pause=pause))]
+async fn handle_token_pause_action(
+    Path((chain_id, nonce, token_id, pause)): Path<(u8, u64, u8, u8)>,
+    State((handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<SignedBridgeAction>, BridgeError> {
+    let future = async {
+        let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
+            BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
+        })?;
+        let pause = match pause {
+            0 => false,
+            1 => true,
+            _ => {
+                return Err(BridgeError::InvalidBridgeClientRequest(format!(
+                    "Invalid pause flag: {}",
+                    pause
+                )))
+            }
+        };
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            chain_id,
+            nonce,
+            token_id,
+            pause,
+        });
+        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
+        Ok(sig)
+    };
+    with_metrics!(metrics.clone(), "handle_token_pause_action", future).await
+}
+
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, sending_chain_id=sending_chain_id, new_usd_limit=new_usd_limit))]
 async fn handle_limit_update_action(
     Path((chain_id, nonce, sending_chain_id, new_usd_limit)): Path<(u8, u64, u8, u64)>,
@@ -688,6 +827,34 @@ mod tests {
     use crate::test_utils::get_test_authorities_and_run_mock_bridge_server;
     use crate::types::BridgeCommittee;
 
+    #[test]
+    fn test_error_status_codes() {
+        use axum::response::IntoResponse;
+        let status = |e: BridgeError| e.into_response().status();
+        assert_eq!(status(BridgeError::InvalidTxHash), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            status(BridgeError::BridgeEventNotActionable),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(BridgeError::GovernanceActionIsNotApproved),
+            StatusCode::BAD_REQUEST
+        );
+        // Errors of the node's own RPCs are asked again, even permanent ones
+        assert_eq!(
+            status(BridgeError::permanent_rpc("receipt without block number")),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status(BridgeError::transient_rpc("connection refused")),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status(BridgeError::OriginTxFailed),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
     #[tokio::test]
     async fn test_bridge_server_handle_blocklist_update_action_path() {
         let client = setup();
@@ -718,6 +885,19 @@ mod tests {
         client.request_sign_bridge_action(action).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_bridge_server_handle_token_pause_action_path() {
+        let client = setup();
+
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 56,
+            chain_id: BridgeChainId::StarcoinCustom,
+            token_id: TOKEN_ID_BTC,
+            pause: true,
+        });
+        client.request_sign_bridge_action(action).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_bridge_server_handle_limit_update_action_path() {
         let client = setup();
@@ -807,6 +987,142 @@ mod tests {
         client.request_sign_bridge_action(action).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_node_info_endpoint() {
+        let info: NodeInfo = serde_json::from_value(serde_json::json!({
+            "version": "0.1.0+abc",
+            "min-message-version": 1,
+            "max-message-version": 1,
+            "features": ["client"],
+            "config-fingerprint": "0011223344556677",
+        }))
+        .unwrap();
+        let state = |node_info: Option<NodeInfo>, expose_node_info: bool| {
+            let mut metadata = BridgeNodePublicMetadata::empty_for_testing();
+            metadata.node_info = node_info;
+            metadata.expose_node_info = expose_node_info;
+            State((
+                Arc::new(BridgeRequestMockHandler::new()),
+                Arc::new(BridgeMetrics::new_for_testing()),
+                Arc::new(metadata),
+            ))
+        };
+
+        let Json(served) = node_info(state(Some(info.clone()), true)).await.unwrap();
+        assert_eq!(served, info);
+        assert_eq!(
+            node_info(state(Some(info.clone()), false))
+                .await
+                .unwrap_err(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            node_info(state(None, true)).await.unwrap_err(),
+            StatusCode::NOT_FOUND
+        );
+        // The health endpoint reports it either way
+        let Json(health) = health_check(state(Some(info.clone()), false)).await;
+        assert_eq!(health.node_info, Some(info));
+    }
+
+    #[tokio::test]
+    async fn test_status_endpoint() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        // A committee whose only member is this server
+        let (mut authority, _, _) = crate::test_utils::get_test_authority_and_key(10000, 0);
+        authority.base_url = url.clone();
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        metrics
+            .last_synced_starcoin_bridge_checkpoints
+            .with_label_values(&["Bridge"])
+            .set(90);
+        metrics
+            .last_synced_eth_blocks
+            .with_label_values(&["0xaa"])
+            .set(480);
+        metrics.last_finalized_eth_block.set(500);
+        metrics.action_executor_signing_queue_depth.set(3);
+
+        let mut metadata = BridgeNodePublicMetadata::empty_for_testing();
+        metadata.status.identity = Some(crate::node_status::NodeIdentity {
+            public_key: "02aa".to_string(),
+            registered_url: Some(url.clone()),
+        });
+        metadata
+            .status
+            .starcoin_head_block
+            .store(Arc::new(Some(100)));
+        metadata
+            .status
+            .eth_bridge_paused
+            .store(Arc::new(Some(false)));
+        let status = metadata.status.clone();
+        let router = make_router(
+            Arc::new(BridgeRequestMockHandler::new()),
+            metrics,
+            Arc::new(metadata),
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let get_status = || async {
+            let body = reqwest::get(format!("{url}{STATUS_PATH}"))
+                .await
+                .unwrap()
+                .bytes()
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let body = get_status().await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "public-key": "02aa",
+                "registered-url": url,
+                "registration-mismatch": null,
+                "starcoin": {
+                    "head-block": 100,
+                    "paused": null,
+                    "syncers": [{"task": "Bridge", "last-processed-block": 90, "lag": 10}],
+                },
+                "eth": {
+                    "head-block": 500,
+                    "paused": false,
+                    "syncers": [{"task": "0xaa", "last-processed-block": 480, "lag": 20}],
+                },
+                "pending-actions": 3,
+                "committee": null,
+            })
+        );
+
+        // Reachability is reported once the committee has been pinged
+        let reachability = crate::node_status::check_committee_reachability(
+            &committee,
+            std::time::Duration::from_secs(5),
+        )
+        .await;
+        status.committee.store(Arc::new(Some(reachability)));
+        let committee = get_status().await["committee"].clone();
+        assert_eq!(committee["online-voting-power"], 10000);
+        assert_eq!(committee["quorum-reachable"], true);
+        assert_eq!(committee["members"][0]["url"], url);
+        assert_eq!(committee["members"][0]["ping"]["online"], true);
+
+        // The status is still served while the registration doesn't match
+        status.registration.record(
+            Some(crate::registration::RegistrationMismatch::MissingEntry),
+            &BridgeMetrics::new_for_testing(),
+        );
+        assert_eq!(
+            get_status().await["registration-mismatch"],
+            "the authority key is not a committee member"
+        );
+    }
+
     fn setup() -> BridgeClient {
         let mock = BridgeRequestMockHandler::new();
         let (_handles, authorities, mut secrets) =
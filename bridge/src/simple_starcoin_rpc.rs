@@ -2,17 +2,31 @@
 // Replaces the heavy starcoin-rpc-client to avoid tokio runtime conflicts
 // Uses HTTP JSON-RPC (default port 9850)
 
+use crate::config::StarcoinRpcRetryConfig;
+use crate::metrics::BridgeMetrics;
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use backoff::backoff::Backoff;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// The only call with a side effect: it's retried only when the node surely
+// didn't handle it
+const SUBMIT_TRANSACTION_METHOD: &str = "txpool.submit_hex_transaction";
 
 #[derive(Clone, Debug)]
 pub struct SimpleStarcoinRpcClient {
-    http_client: reqwest::Client,
-    rpc_url: String,
+    transport: Arc<dyn RpcTransport>,
     request_id: std::sync::Arc<AtomicU64>,
     bridge_address: String,
+    rpc_retry: StarcoinRpcRetryConfig,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    metrics: Option<Arc<BridgeMetrics>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -39,16 +53,163 @@ struct JsonRpcError {
     message: String,
 }
 
+// Failure to get a response to a request
+#[derive(Debug)]
+enum TransportError {
+    // The node didn't handle the request: it was unreachable or rate limited
+    // the client
+    Unhandled(anyhow::Error),
+    // The node may have handled the request, e.g. it timed out or failed
+    MaybeHandled(anyhow::Error),
+    // Retrying won't help
+    Permanent(anyhow::Error),
+}
+
+impl TransportError {
+    fn is_retryable(&self, method: &str) -> bool {
+        match self {
+            TransportError::Unhandled(_) => true,
+            TransportError::MaybeHandled(_) => method != SUBMIT_TRANSACTION_METHOD,
+            TransportError::Permanent(_) => false,
+        }
+    }
+
+    fn into_inner(self) -> anyhow::Error {
+        match self {
+            TransportError::Unhandled(e)
+            | TransportError::MaybeHandled(e)
+            | TransportError::Permanent(e) => e,
+        }
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_connect() {
+            TransportError::Unhandled(e.into())
+        } else if e.is_builder() {
+            TransportError::Permanent(e.into())
+        } else {
+            TransportError::MaybeHandled(e.into())
+        }
+    }
+}
+
+// Error of a call that repeating won't fix: the node answered it with an
+// error, or it can't be sent. Other errors of a call are transient.
+#[derive(Debug, thiserror::Error)]
+#[error("{0:#}")]
+pub struct PermanentRpcError(anyhow::Error);
+
+// Whether `error` of a `SimpleStarcoinRpcClient` call may go away when the
+// call is repeated
+pub fn is_retryable_rpc_error(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<PermanentRpcError>().is_none()
+}
+
+// Sends a request to the node and returns the raw response
+#[async_trait]
+trait RpcTransport: Send + Sync + std::fmt::Debug {
+    async fn send(&self, request: &JsonRpcRequest) -> Result<String, TransportError>;
+}
+
+#[derive(Debug)]
+struct HttpTransport {
+    http_client: reqwest::Client,
+    rpc_url: String,
+}
+
+#[async_trait]
+impl RpcTransport for HttpTransport {
+    async fn send(&self, request: &JsonRpcRequest) -> Result<String, TransportError> {
+        let response = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            tracing::error!("[RPC] <<< HTTP error {} \n{}", status, error_text);
+            let error = anyhow!("HTTP error: {} - {}", status, error_text);
+            return Err(match status {
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => {
+                    TransportError::Unhandled(error)
+                }
+                status if status.is_server_error() => TransportError::MaybeHandled(error),
+                _ => TransportError::Permanent(error),
+            });
+        }
+        Ok(response.text().await?)
+    }
+}
+
+// Spaces requests evenly, across all clones of a client
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
 impl SimpleStarcoinRpcClient {
     pub fn new(rpc_url: impl Into<String>, bridge_address: impl Into<String>) -> Self {
+        Self::with_transport(
+            Arc::new(HttpTransport {
+                http_client: reqwest::Client::new(),
+                rpc_url: rpc_url.into(),
+            }),
+            bridge_address,
+        )
+    }
+
+    fn with_transport(transport: Arc<dyn RpcTransport>, bridge_address: impl Into<String>) -> Self {
         Self {
-            http_client: reqwest::Client::new(),
-            rpc_url: rpc_url.into(),
+            transport,
             request_id: std::sync::Arc::new(AtomicU64::new(1)),
             bridge_address: bridge_address.into(),
+            rpc_retry: StarcoinRpcRetryConfig::default(),
+            rate_limiter: None,
+            metrics: None,
         }
     }
 
+    /// Retry and rate limit calls per `rpc_retry`. Each retried error is
+    /// counted in `starcoin_bridge_rpc_errors` under the RPC method, and each
+    /// call that runs out of attempts under `<method>:retries_exhausted`.
+    pub fn with_rpc_retry(
+        mut self,
+        rpc_retry: &StarcoinRpcRetryConfig,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        self.rate_limiter = rpc_retry
+            .max_requests_per_second
+            .map(|rps| Arc::new(RateLimiter::new(rps)));
+        self.rpc_retry = rpc_retry.clone();
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Get the bridge contract address
     pub fn bridge_address(&self) -> &str {
         &self.bridge_address
@@ -81,21 +242,7 @@ impl SimpleStarcoinRpcClient {
             tracing::info!("[RPC] >>> {}\n{}", method, request_json);
         }
 
-        let response = self
-            .http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await?;
-            tracing::error!("[RPC] <<< HTTP error {} \n{}", status, error_text);
-            return Err(anyhow!("HTTP error: {} - {}", status, error_text));
-        }
-
-        let response_text = response.text().await?;
+        let response_text = self.send_with_retry(&request).await?;
 
         if verbose {
             tracing::info!("[RPC] <<< {}\n{}", method, response_text);
@@ -111,13 +258,67 @@ impl SimpleStarcoinRpcClient {
                 request_json,
                 &response_text
             );
-            return Err(anyhow!("RPC error {}: {}", error.code, error.message));
+            return Err(
+                PermanentRpcError(anyhow!("RPC error {}: {}", error.code, error.message)).into(),
+            );
         }
 
         // Return the result, which may be null (valid for queries that return Option)
         Ok(rpc_response.result.unwrap_or(Value::Null))
     }
 
+    // Sends `request`, retrying transient failures with exponential backoff.
+    // Errors returned by the node in the JSON-RPC response aren't retried.
+    async fn send_with_retry(&self, request: &JsonRpcRequest) -> Result<String> {
+        let method = request.method.as_str();
+        let mut backoff = backoff::ExponentialBackoff {
+            initial_interval: Duration::from_millis(self.rpc_retry.initial_backoff_ms),
+            randomization_factor: 0.2,
+            multiplier: 2.0,
+            max_interval: Duration::from_millis(self.rpc_retry.max_backoff_ms),
+            max_elapsed_time: None,
+            ..Default::default()
+        };
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let error = match self.transport.send(request).await {
+                Ok(response) => return Ok(response),
+                Err(e @ TransportError::Permanent(_)) => {
+                    return Err(PermanentRpcError(e.into_inner()).into())
+                }
+                Err(e) if !e.is_retryable(method) => return Err(e.into_inner()),
+                Err(e) => e.into_inner(),
+            };
+            if attempt >= self.rpc_retry.max_attempts {
+                self.count_error(&format!("{method}:retries_exhausted"));
+                return Err(error.context(format!("{method} failed after {attempt} attempts")));
+            }
+            self.count_error(method);
+            let delay = backoff.next_backoff().unwrap_or(backoff.max_interval);
+            tracing::warn!(
+                method,
+                attempt,
+                "[RPC] Retrying in {:?} after error: {:#}",
+                delay,
+                error
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn count_error(&self, label: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .starcoin_bridge_rpc_errors
+                .with_label_values(&[label])
+                .inc();
+        }
+    }
+
     // Chain info
     pub async fn chain_info(&self) -> Result<Value> {
         self.call("chain.info", vec![]).await
@@ -192,6 +393,18 @@ impl SimpleStarcoinRpcClient {
         }
     }
 
+    // Get the compiled bytecode of a published Move module, None if it does
+    // not exist
+    pub async fn get_module(&self, address: &str, module_name: &str) -> Result<Option<Vec<u8>>> {
+        let result = self
+            .call(
+                "state.get_code",
+                vec![json!(format!("{}::{}", address, module_name))],
+            )
+            .await?;
+        parse_code_view(&result)
+    }
+
     // Get account state
     pub async fn get_account(&self, address: &str) -> Result<Option<Value>> {
         let result = self.call("state.get_account", vec![json!(address)]).await?;
@@ -240,6 +453,23 @@ impl SimpleStarcoinRpcClient {
         }
     }
 
+    // Balance of `token_type` held by an account, 0 if it has none
+    pub async fn get_token_balance(&self, address: &str, token_type: &str) -> Result<u128> {
+        let resource = self
+            .get_resource(
+                address,
+                &format!(
+                    "0x00000000000000000000000000000001::Account::Balance<{}>",
+                    token_type
+                ),
+            )
+            .await?;
+        match resource {
+            Some(resource) => parse_balance_resource(&resource),
+            None => Ok(0),
+        }
+    }
+
     // Query events by transaction hash
     pub async fn get_events_by_txn_hash(&self, txn_hash: &str) -> Result<Vec<Value>> {
         let result = self
@@ -267,12 +497,8 @@ impl SimpleStarcoinRpcClient {
     // Submit transaction with verbose logging
     pub async fn submit_transaction(&self, signed_txn: &str) -> Result<Value> {
         // Use verbose logging for transaction submission (shows full JSON request/response)
-        self.call_with_log(
-            "txpool.submit_hex_transaction",
-            vec![json!(signed_txn)],
-            true,
-        )
-        .await
+        self.call_with_log(SUBMIT_TRANSACTION_METHOD, vec![json!(signed_txn)], true)
+            .await
     }
 
     /// Sign a RawUserTransaction and submit it to the network
@@ -282,17 +508,6 @@ impl SimpleStarcoinRpcClient {
         key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
         raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
     ) -> Result<String> {
-        use starcoin_bridge_types::crypto::StarcoinKeyPair;
-        use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
-        use starcoin_vm_types::account_address::AccountAddress;
-        use starcoin_vm_types::genesis_config::ChainId as NativeChainId;
-        use starcoin_vm_types::identifier::Identifier;
-        use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
-        use starcoin_vm_types::transaction::{
-            RawUserTransaction as NativeRawUserTransaction, ScriptFunction,
-            TransactionPayload as NativeTransactionPayload,
-        };
-
         tracing::info!(
             "[RPC] >>> sign_and_submit_transaction(sender={:?}, seq={}, chain_id={}, gas={}/{})",
             raw_txn.sender,
@@ -302,78 +517,8 @@ impl SimpleStarcoinRpcClient {
             raw_txn.gas_unit_price
         );
 
-        // Convert our RawUserTransaction to Starcoin native RawUserTransaction
-        // StarcoinAddress is [u8; 16], AccountAddress::new expects [u8; 16]
-        let sender = AccountAddress::new(*raw_txn.sender);
-
-        // Convert payload - need to rebuild with starcoin_vm_types types
-        let native_payload = match &raw_txn.payload {
-            starcoin_bridge_types::transaction::TransactionPayload::ScriptFunction(sf) => {
-                tracing::info!(
-                    "[RPC]     payload: {:?}::{}::{}, type_args={:?}, args_count={}",
-                    sf.module.address(),
-                    sf.module.name(),
-                    sf.function,
-                    sf.ty_args,
-                    sf.args.len()
-                );
-
-                // Rebuild ModuleId with starcoin_vm_types types
-                let module_addr = AccountAddress::new(**sf.module.address());
-                let module_name = Identifier::new(sf.module.name().as_str())
-                    .map_err(|e| anyhow!("Invalid module name: {:?}", e))?;
-                let native_module = ModuleId::new(module_addr, module_name);
-
-                let function_name = Identifier::new(sf.function.as_str())
-                    .map_err(|e| anyhow!("Invalid function name: {:?}", e))?;
-
-                // Convert type args - they should be compatible via BCS
-                let native_ty_args: Vec<TypeTag> = sf
-                    .ty_args
-                    .iter()
-                    .map(|t| {
-                        // Serialize and deserialize to convert between move_core_types versions
-                        let bytes = bcs::to_bytes(t).unwrap();
-                        bcs_ext::from_bytes(&bytes).unwrap()
-                    })
-                    .collect();
-
-                NativeTransactionPayload::ScriptFunction(ScriptFunction::new(
-                    native_module,
-                    function_name,
-                    native_ty_args,
-                    sf.args.clone(),
-                ))
-            }
-            _ => return Err(anyhow!("Only ScriptFunction payload is supported")),
-        };
-
-        let native_raw_txn = NativeRawUserTransaction::new_with_default_gas_token(
-            sender,
-            raw_txn.sequence_number,
-            native_payload,
-            raw_txn.max_gas_amount,
-            raw_txn.gas_unit_price,
-            raw_txn.expiration_timestamp_secs,
-            NativeChainId::new(raw_txn.chain_id.0),
-        );
-
-        // Get Ed25519 private key bytes and create Starcoin Ed25519PrivateKey
-        let (public_key_bytes, private_key_bytes) = match key {
-            StarcoinKeyPair::Ed25519(kp) => {
-                use fastcrypto::traits::{KeyPair as FastcryptoKeyPair, ToFromBytes};
-                let priv_bytes = kp.as_bytes()[..32].to_vec(); // Ed25519 private key is first 32 bytes
-                let pub_bytes = kp.public().as_bytes().to_vec();
-                (pub_bytes, priv_bytes)
-            }
-            _ => return Err(anyhow!("Only Ed25519 keys are supported for Starcoin")),
-        };
-
-        // Create Starcoin native Ed25519 keys
-        let private_key = Ed25519PrivateKey::try_from(private_key_bytes.as_slice())
-            .map_err(|e| anyhow!("Invalid Ed25519 private key: {:?}", e))?;
-        let public_key = Ed25519PublicKey::try_from(public_key_bytes.as_slice())
-            .map_err(|e| anyhow!("Invalid Ed25519 public key: {:?}", e))?;
+        let native_raw_txn = native_raw_transaction(&raw_txn)?;
+        let (private_key, public_key) = native_ed25519_keys(key)?;
 
         // Sign using Starcoin's native signing
         let signed_txn = native_raw_txn
@@ -501,10 +646,45 @@ impl SimpleStarcoinRpcClient {
         self.call("contract.dry_run", vec![json!(signed_txn)]).await
     }
 
+    /// Execute `raw_txn` as signed by `key` without submitting it. Returns
+    /// the `txn_output` of `contract.dry_run_raw`, which has the `status` and
+    /// `gas_used` of the transaction.
+    pub async fn dry_run_raw_transaction(
+        &self,
+        key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+    ) -> Result<Value> {
+        use starcoin_crypto::ValidCryptoMaterialStringExt;
+
+        let native_raw_txn = native_raw_transaction(&raw_txn)?;
+        let (_, public_key) = native_ed25519_keys(key)?;
+        let raw_txn_bytes = bcs_ext::to_bytes(&native_raw_txn)
+            .map_err(|e| anyhow!("Failed to serialize raw transaction: {}", e))?;
+        let public_key_hex = public_key
+            .to_encoded_string()
+            .map_err(|e| anyhow!("Failed to encode public key: {}", e))?;
+        let result = self
+            .call(
+                "contract.dry_run_raw",
+                vec![
+                    json!(format!("0x{}", hex::encode(raw_txn_bytes))),
+                    json!(format!("0x{}", public_key_hex)),
+                ],
+            )
+            .await?;
+        Ok(result.get("txn_output").cloned().unwrap_or(result))
+    }
+
     // Get gas price (estimate from recent blocks)
+    /// Get the node's gas price estimate, from the gas prices of the
+    /// transactions in its pool
     pub async fn get_gas_price(&self) -> Result<u64> {
-        // Starcoin doesn't have dynamic gas price, return default
-        Ok(1)
+        let response = self.call("txpool.gas_price", vec![]).await?;
+        response
+            .as_u64()
+            .or_else(|| response.as_str()?.parse().ok())
+            .filter(|price| *price > 0)
+            .ok_or_else(|| anyhow!("Invalid gas price: {}", response))
     }
 
     /// Get the Bridge resource from chain state
@@ -582,10 +762,350 @@ impl SimpleStarcoinRpcClient {
     }
 }
 
+// Rebuilds a bridge transaction with the native Starcoin types, for correct
+// BCS serialization
+fn native_raw_transaction(
+    raw_txn: &starcoin_bridge_types::transaction::RawUserTransaction,
+) -> Result<starcoin_vm_types::transaction::RawUserTransaction> {
+    use starcoin_vm_types::account_address::AccountAddress;
+    use starcoin_vm_types::genesis_config::ChainId as NativeChainId;
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
+    use starcoin_vm_types::transaction::{
+        RawUserTransaction as NativeRawUserTransaction, ScriptFunction,
+        TransactionPayload as NativeTransactionPayload,
+    };
+
+    // Convert our RawUserTransaction to Starcoin native RawUserTransaction
+    // StarcoinAddress is [u8; 16], AccountAddress::new expects [u8; 16]
+    let sender = AccountAddress::new(*raw_txn.sender);
+
+    // Convert payload - need to rebuild with starcoin_vm_types types
+    let native_payload = match &raw_txn.payload {
+        starcoin_bridge_types::transaction::TransactionPayload::ScriptFunction(sf) => {
+            tracing::info!(
+                "[RPC]     payload: {:?}::{}::{}, type_args={:?}, args_count={}",
+                sf.module.address(),
+                sf.module.name(),
+                sf.function,
+                sf.ty_args,
+                sf.args.len()
+            );
+
+            // Rebuild ModuleId with starcoin_vm_types types
+            let module_addr = AccountAddress::new(**sf.module.address());
+            let module_name = Identifier::new(sf.module.name().as_str())
+                .map_err(|e| anyhow!("Invalid module name: {:?}", e))?;
+            let native_module = ModuleId::new(module_addr, module_name);
+
+            let function_name = Identifier::new(sf.function.as_str())
+                .map_err(|e| anyhow!("Invalid function name: {:?}", e))?;
+
+            // Convert type args - they should be compatible via BCS
+            let native_ty_args: Vec<TypeTag> = sf
+                .ty_args
+                .iter()
+                .map(|t| {
+                    // Serialize and deserialize to convert between move_core_types versions
+                    let bytes = bcs::to_bytes(t).unwrap();
+                    bcs_ext::from_bytes(&bytes).unwrap()
+                })
+                .collect();
+
+            NativeTransactionPayload::ScriptFunction(ScriptFunction::new(
+                native_module,
+                function_name,
+                native_ty_args,
+                sf.args.clone(),
+            ))
+        }
+        _ => return Err(anyhow!("Only ScriptFunction payload is supported")),
+    };
+
+    Ok(NativeRawUserTransaction::new_with_default_gas_token(
+        sender,
+        raw_txn.sequence_number,
+        native_payload,
+        raw_txn.max_gas_amount,
+        raw_txn.gas_unit_price,
+        raw_txn.expiration_timestamp_secs,
+        NativeChainId::new(raw_txn.chain_id.0),
+    ))
+}
+
+// Native Starcoin keys of `key`, which must be Ed25519
+fn native_ed25519_keys(
+    key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+) -> Result<(
+    starcoin_crypto::ed25519::Ed25519PrivateKey,
+    starcoin_crypto::ed25519::Ed25519PublicKey,
+)> {
+    use starcoin_bridge_types::crypto::StarcoinKeyPair;
+    use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+
+    // Get Ed25519 private key bytes and create Starcoin Ed25519PrivateKey
+    let (public_key_bytes, private_key_bytes) = match key {
+        StarcoinKeyPair::Ed25519(kp) => {
+            use fastcrypto::traits::{KeyPair as FastcryptoKeyPair, ToFromBytes};
+            let priv_bytes = kp.as_bytes()[..32].to_vec(); // Ed25519 private key is first 32 bytes
+            let pub_bytes = kp.public().as_bytes().to_vec();
+            (pub_bytes, priv_bytes)
+        }
+        _ => return Err(anyhow!("Only Ed25519 keys are supported for Starcoin")),
+    };
+
+    // Create Starcoin native Ed25519 keys
+    let private_key = Ed25519PrivateKey::try_from(private_key_bytes.as_slice())
+        .map_err(|e| anyhow!("Invalid Ed25519 private key: {:?}", e))?;
+    let public_key = Ed25519PublicKey::try_from(public_key_bytes.as_slice())
+        .map_err(|e| anyhow!("Invalid Ed25519 public key: {:?}", e))?;
+    Ok((private_key, public_key))
+}
+
+// `state.get_code` returns `{"code": "0x<bytecode>", "abi": ...}`, or null
+// when the module is not published.
+fn parse_code_view(result: &Value) -> Result<Option<Vec<u8>>> {
+    if result.is_null() {
+        return Ok(None);
+    }
+    let code = result
+        .get("code")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("Missing code in state.get_code response: {}", result))?;
+    let bytes = hex::decode(code.trim_start_matches("0x"))
+        .map_err(|e| anyhow!("Invalid module bytecode hex: {}", e))?;
+    Ok(Some(bytes))
+}
+
+// A decoded `Account::Balance` resource is
+// `{"json": {"token": {"value": <u128>}}, "raw": ...}`. Large values are
+// returned as strings.
+fn parse_balance_resource(resource: &Value) -> Result<u128> {
+    let value = resource
+        .get("json")
+        .and_then(|j| j.get("token"))
+        .and_then(|t| t.get("value"))
+        .ok_or_else(|| anyhow!("Missing token value in balance resource: {}", resource))?;
+    value
+        .as_u64()
+        .map(u128::from)
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| anyhow!("Invalid token value in balance resource: {}", value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // A `state.get_code` response for a small token module
+    const TOKEN_MODULE_CODE_VIEW: &str = r#"{
+        "code": "0xa11ceb0b060000000a01000402040403081105191207214d086e200a8e01050c930113000001010002000003000100000402010002070800030708000301060800010301060b00010900",
+        "abi": null
+    }"#;
+
+    #[test]
+    fn test_parse_code_view() {
+        let view: Value = serde_json::from_str(TOKEN_MODULE_CODE_VIEW).unwrap();
+        let code = parse_code_view(&view).unwrap().unwrap();
+        // Move bytecode magic
+        assert_eq!(&code[..4], &[0xa1, 0x1c, 0xeb, 0x0b]);
+        assert_eq!(code.len(), 74);
+
+        assert_eq!(parse_code_view(&Value::Null).unwrap(), None);
+        assert!(parse_code_view(&json!({"abi": null})).is_err());
+        assert!(parse_code_view(&json!({"code": "0xzz"})).is_err());
+    }
+
+    #[test]
+    fn test_parse_balance_resource() {
+        let resource = json!({"json": {"token": {"value": 1500}}, "raw": "0x"});
+        assert_eq!(parse_balance_resource(&resource).unwrap(), 1500);
+        let resource =
+            json!({"json": {"token": {"value": "340282366920938463463374607431768211455"}}});
+        assert_eq!(parse_balance_resource(&resource).unwrap(), u128::MAX);
+        assert!(parse_balance_resource(&json!({"json": {}})).is_err());
+        assert!(parse_balance_resource(&json!({"json": {"token": {"value": "x"}}})).is_err());
+    }
+
+    // Fails the first `failures` requests with `error`, then answers
+    #[derive(Debug)]
+    struct FlakyTransport {
+        failures: u64,
+        error: fn() -> TransportError,
+        requests: AtomicU64,
+    }
+
+    impl FlakyTransport {
+        fn new(failures: u64, error: fn() -> TransportError) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                error,
+                requests: AtomicU64::new(0),
+            })
+        }
+
+        fn requests(&self) -> u64 {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl RpcTransport for FlakyTransport {
+        async fn send(&self, request: &JsonRpcRequest) -> Result<String, TransportError> {
+            if self.requests.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err((self.error)());
+            }
+            Ok(json!({"jsonrpc": "2.0", "result": {"ok": true}, "id": request.id}).to_string())
+        }
+    }
+
+    // Answers every request with the same result
+    #[derive(Debug)]
+    struct StaticTransport(Value);
+
+    #[async_trait]
+    impl RpcTransport for StaticTransport {
+        async fn send(&self, request: &JsonRpcRequest) -> Result<String, TransportError> {
+            Ok(json!({"jsonrpc": "2.0", "result": self.0, "id": request.id}).to_string())
+        }
+    }
+
+    fn rate_limited() -> TransportError {
+        TransportError::Unhandled(anyhow!("HTTP error: 429 Too Many Requests"))
+    }
+
+    fn timed_out() -> TransportError {
+        TransportError::MaybeHandled(anyhow!("operation timed out"))
+    }
+
+    fn flaky_client(
+        transport: Arc<FlakyTransport>,
+        max_attempts: u32,
+        metrics: Arc<BridgeMetrics>,
+    ) -> SimpleStarcoinRpcClient {
+        let rpc_retry = StarcoinRpcRetryConfig {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            max_requests_per_second: None,
+        };
+        SimpleStarcoinRpcClient::with_transport(transport, "0x1")
+            .with_rpc_retry(&rpc_retry, metrics)
+    }
+
+    fn rpc_errors(metrics: &BridgeMetrics, label: &str) -> u64 {
+        metrics
+            .starcoin_bridge_rpc_errors
+            .with_label_values(&[label])
+            .get()
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried() {
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let transport = FlakyTransport::new(2, rate_limited);
+        let client = flaky_client(transport.clone(), 4, metrics.clone());
+
+        assert_eq!(client.chain_info().await.unwrap(), json!({"ok": true}));
+        assert_eq!(transport.requests(), 3);
+        assert_eq!(rpc_errors(&metrics, "chain.info"), 2);
+        assert_eq!(rpc_errors(&metrics, "chain.info:retries_exhausted"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_are_bounded() {
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let transport = FlakyTransport::new(10, timed_out);
+        let client = flaky_client(transport.clone(), 3, metrics.clone());
+
+        let err = client.chain_info().await.unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"), "{err:#}");
+        assert!(is_retryable_rpc_error(&err));
+        assert_eq!(transport.requests(), 3);
+        assert_eq!(rpc_errors(&metrics, "chain.info"), 2);
+        assert_eq!(rpc_errors(&metrics, "chain.info:retries_exhausted"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let transport = FlakyTransport::new(1, || {
+            TransportError::Permanent(anyhow!("HTTP error: 400 Bad Request"))
+        });
+        let client = flaky_client(transport.clone(), 4, metrics.clone());
+
+        let err = client.chain_info().await.unwrap_err();
+        assert!(!is_retryable_rpc_error(&err), "{err:#}");
+        assert_eq!(transport.requests(), 1);
+        assert_eq!(rpc_errors(&metrics, "chain.info"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_submission_is_retried_only_if_unhandled() {
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+
+        // The transaction may have been submitted already
+        let transport = FlakyTransport::new(1, timed_out);
+        let client = flaky_client(transport.clone(), 4, metrics.clone());
+        assert!(client.submit_transaction("0x00").await.is_err());
+        assert_eq!(transport.requests(), 1);
+
+        let transport = FlakyTransport::new(1, rate_limited);
+        let client = flaky_client(transport.clone(), 4, metrics.clone());
+        assert!(client.submit_transaction("0x00").await.is_ok());
+        assert_eq!(transport.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_gas_price() {
+        let client = |result: Value| {
+            SimpleStarcoinRpcClient::with_transport(Arc::new(StaticTransport(result)), "0x1")
+        };
+        assert_eq!(client(json!("1000")).get_gas_price().await.unwrap(), 1000);
+        assert_eq!(client(json!(3)).get_gas_price().await.unwrap(), 3);
+        assert!(client(json!("0")).get_gas_price().await.is_err());
+        assert!(client(Value::Null).get_gas_price().await.is_err());
+    }
+
+    // The address the bridge derives for an Ed25519 key must be the one
+    // Starcoin itself derives, or transactions go out from the wrong sender
+    #[test]
+    fn test_starcoin_address_matches_authentication_key() {
+        use fastcrypto::encoding::{Encoding, Hex};
+        use fastcrypto::traits::ToFromBytes;
+        use starcoin_bridge_types::crypto::{get_key_pair, StarcoinKeyPair};
+        use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+
+        // RFC 8032's first test vector, and a few random keys
+        let mut key_pairs = vec![fastcrypto::ed25519::Ed25519KeyPair::from_bytes(
+            &Hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .unwrap(),
+        )
+        .unwrap()];
+        key_pairs.extend((0..3).map(|_| get_key_pair().1));
+        for kp in key_pairs {
+            let kp = StarcoinKeyPair::Ed25519(kp);
+            let (_, public_key) = native_ed25519_keys(&kp).unwrap();
+            assert_eq!(
+                kp.starcoin_address().to_vec(),
+                AuthenticationKey::ed25519(&public_key)
+                    .derived_address()
+                    .to_vec()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests() {
+        let rate_limiter = RateLimiter::new(20);
+        let start = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.acquire().await;
+        }
+        // The first request goes out immediately, the others 50ms apart
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
     #[tokio::test]
     async fn test_chain_info() {
         let client = SimpleStarcoinRpcClient::new(
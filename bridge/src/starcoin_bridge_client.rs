@@ -12,8 +12,8 @@ use serde::de::DeserializeOwned;
 use starcoin_bridge_json_rpc_api::BridgeReadApiClient;
 #[cfg(test)]
 use starcoin_bridge_json_rpc_types::DevInspectResults;
-use starcoin_bridge_json_rpc_types::{EventFilter, Page, StarcoinEvent};
-use starcoin_bridge_json_rpc_types::{EventPage, StarcoinTransactionBlockResponse};
+use starcoin_bridge_json_rpc_types::{EventCursor, EventFilter, StarcoinEvent};
+use starcoin_bridge_json_rpc_types::{EventPage, Page, StarcoinTransactionBlockResponse};
 #[cfg(test)]
 use starcoin_bridge_json_rpc_types::{
     StarcoinObjectDataOptions, StarcoinTransactionBlockResponseOptions,
@@ -25,8 +25,8 @@ use starcoin_bridge_types::base_types::ObjectRef;
 use starcoin_bridge_types::base_types::StarcoinAddress;
 use starcoin_bridge_types::base_types::{ObjectID, TransactionDigest};
 use starcoin_bridge_types::bridge::{
-    BridgeSummary, BridgeTreasurySummary, MoveTypeCommitteeMember,
-    MoveTypeParsedTokenTransferMessage,
+    BridgeChainId, BridgeCommitteeSummary, BridgeSummary, BridgeTreasurySummary,
+    MoveTypeCommitteeMember, MoveTypeParsedTokenTransferMessage,
 };
 use starcoin_bridge_types::event::EventID;
 use starcoin_bridge_types::gas_coin::GasCoin;
@@ -44,25 +44,188 @@ use starcoin_bridge_types::BRIDGE_PACKAGE_ID;
 #[cfg(test)]
 use starcoin_bridge_types::STARCOIN_BRIDGE_OBJECT_ID;
 use std::collections::HashMap;
-use std::str::from_utf8;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::OnceCell;
+use tokio::sync::{Mutex, OnceCell, RwLock};
+use tokio::time::Instant;
 use tracing::{error, warn};
 
+use crate::config::StarcoinRpcRetryConfig;
 use crate::crypto::BridgeAuthorityPublicKey;
+use crate::deadline::{retry_within, Deadline};
 use crate::error::{BridgeError, BridgeResult};
-use crate::events::StarcoinBridgeEvent;
+use crate::events::{
+    event_module_matches, is_mapped_module, package_starcoin_address, StarcoinBridgeEvent,
+};
 use crate::metrics::BridgeMetrics;
-use crate::retry_with_max_elapsed_time;
+use crate::rate_limited_logger::RateLimitedLogger;
+use crate::sanitize::{sanitize_untrusted, MAX_URL_BYTES};
+use crate::starcoin_bridge_transaction_builder::{
+    gas_with_margin, GasConfig, DEFAULT_GAS_UNIT_PRICE,
+};
+use crate::starcoin_jsonrpc_client::events_from_rpc;
 use crate::starcoin_jsonrpc_client::StarcoinJsonRpcClient;
-use crate::types::BridgeActionStatus;
 use crate::types::ParsedTokenTransferMessage;
 use crate::types::{BridgeAction, BridgeAuthority, BridgeCommittee};
+use crate::types::{BridgeActionStatus, BridgeActionType};
+
+// Bridge summaries fetched within this long are reused
+pub const DEFAULT_BRIDGE_SUMMARY_TTL: Duration = Duration::from_secs(10);
+// The token id map only changes when tokens are added, so it is kept longer
+pub const DEFAULT_TOKEN_ID_MAP_TTL: Duration = Duration::from_secs(300);
+// Delay before the first retry of `get_reference_gas_price_until_success`,
+// doubling up to the max
+const GAS_PRICE_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(100);
+const GAS_PRICE_RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+// A submitted transaction is polled for this many times, 30 seconds in total
+const TX_CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const TX_CONFIRMATION_POLLS: u32 = 60;
+// Polls to wait for the transaction info once the sequence number advanced,
+// before concluding another transaction of the sender took the sequence number
+const TX_INFO_MISSING_POLLS: u32 = 6;
+// Resubmissions with the sequence number on chain, after the node rejected a
+// transaction for a stale one
+const MAX_STALE_SEQUENCE_RETRIES: u32 = 3;
+// Reads of a gas object that found nothing before giving up on it, 20
+// seconds apart in total
+pub const MAX_GAS_OBJECT_MISSES: u32 = 5;
+const GAS_OBJECT_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A Starcoin transaction that executed successfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarcoinTxReceipt {
+    pub txn_hash: String,
+    pub status: String,
+    pub block_number: Option<u64>,
+    pub gas_used: Option<u64>,
+}
+
+// Reads the outcome of a transaction from its `chain.get_transaction_info`
+// view. The status is `"Executed"` on success, and otherwise a string such as
+// `"OutOfGas"` or an object such as `{"MoveAbort": {..}}`.
+fn starcoin_tx_receipt(
+    txn_hash: &str,
+    txn_info: &serde_json::Value,
+) -> BridgeResult<StarcoinTxReceipt> {
+    let (status, vm_error) = match txn_info.get("status") {
+        Some(serde_json::Value::String(status)) => (status.clone(), None),
+        Some(serde_json::Value::Object(status)) if status.len() == 1 => {
+            let (name, detail) = status.iter().next().unwrap();
+            (name.clone(), Some(detail.to_string()))
+        }
+        Some(status) => (status.to_string(), None),
+        None => ("Unknown".to_string(), None),
+    };
+    if !status.eq_ignore_ascii_case("Executed") {
+        return Err(BridgeError::StarcoinTxFailure {
+            txn_hash: txn_hash.to_string(),
+            status,
+            vm_error,
+        });
+    }
+    // Numbers are rendered as strings or numbers depending on the node version
+    let number = |field: &str| {
+        txn_info.get(field).and_then(|value| match value {
+            serde_json::Value::String(value) => value.parse().ok(),
+            value => value.as_u64(),
+        })
+    };
+    Ok(StarcoinTxReceipt {
+        txn_hash: txn_hash.to_string(),
+        status,
+        block_number: number("block_number"),
+        gas_used: number("gas_used"),
+    })
+}
+
+// A value fetched at most once per `ttl`. A zero `ttl` disables caching.
+struct TtlCache<T> {
+    ttl: Duration,
+    entry: RwLock<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TtlCache<T> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
+        }
+    }
+
+    fn fresh(&self, entry: &Option<(Instant, T)>) -> Option<T> {
+        entry
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    async fn get_or_try_fetch<F, Fut>(&self, fetch: F) -> BridgeResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = BridgeResult<T>>,
+    {
+        if self.ttl.is_zero() {
+            return fetch().await;
+        }
+        if let Some(value) = self.fresh(&*self.entry.read().await) {
+            return Ok(value);
+        }
+        // Concurrent misses wait for a single fetch. Errors are not cached.
+        let mut entry = self.entry.write().await;
+        if let Some(value) = self.fresh(&entry) {
+            return Ok(value);
+        }
+        let value = fetch().await?;
+        *entry = Some((Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    async fn invalidate(&self) {
+        *self.entry.write().await = None;
+    }
+}
+
+// A transaction accepted by the node, with the sequence number it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedSubmission {
+    pub txn_hash: String,
+    pub sequence_number: u64,
+}
+
+// Whether the node rejected a transaction for a sequence number the account
+// already used
+pub fn is_stale_sequence_error(error: &BridgeError) -> bool {
+    format!("{:?}", error).contains("SEQUENCE_NUMBER_TOO_OLD")
+}
+
+// The next sequence number of each sender, by address. An account's lock is
+// held from reserving a sequence number until the node accepted or rejected
+// the transaction, so concurrent submissions of one account take consecutive
+// sequence numbers. None until seeded from the chain.
+#[derive(Default)]
+struct SequenceManager {
+    accounts: std::sync::Mutex<HashMap<String, Arc<Mutex<Option<u64>>>>>,
+}
+
+impl SequenceManager {
+    fn account(&self, sender: &str) -> Arc<Mutex<Option<u64>>> {
+        self.accounts
+            .lock()
+            .unwrap()
+            .entry(sender.to_string())
+            .or_default()
+            .clone()
+    }
+}
 
 pub struct StarcoinClient<P> {
     inner: P,
     bridge_metrics: Arc<BridgeMetrics>,
+    bridge_summary_cache: TtlCache<BridgeSummary>,
+    token_id_map_cache: TtlCache<HashMap<u8, TypeTag>>,
+    sequences: SequenceManager,
+    gas_config: GasConfig,
 }
 
 // JSON-RPC based client (default, no runtime conflicts)
@@ -73,20 +236,44 @@ pub type StarcoinBridgeSdkClient = StarcoinBridgeClient;
 
 impl StarcoinBridgeClient {
     pub fn new(rpc_url: &str, bridge_address: &str) -> Self {
-        Self {
-            inner: StarcoinJsonRpcClient::new(rpc_url, bridge_address),
-            bridge_metrics: Arc::new(BridgeMetrics::new_for_testing()),
-        }
+        Self::with_metrics(
+            rpc_url,
+            bridge_address,
+            Arc::new(BridgeMetrics::new_for_testing()),
+        )
     }
 
     pub fn with_metrics(
         rpc_url: &str,
         bridge_address: &str,
         bridge_metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self::with_rpc_retry(
+            rpc_url,
+            bridge_address,
+            &StarcoinRpcRetryConfig::default(),
+            bridge_metrics,
+        )
+    }
+
+    pub fn with_rpc_retry(
+        rpc_url: &str,
+        bridge_address: &str,
+        rpc_retry: &StarcoinRpcRetryConfig,
+        bridge_metrics: Arc<BridgeMetrics>,
     ) -> Self {
         Self {
-            inner: StarcoinJsonRpcClient::new(rpc_url, bridge_address),
+            inner: StarcoinJsonRpcClient::with_rpc_retry(
+                rpc_url,
+                bridge_address,
+                rpc_retry,
+                bridge_metrics.clone(),
+            ),
             bridge_metrics,
+            bridge_summary_cache: TtlCache::new(DEFAULT_BRIDGE_SUMMARY_TTL),
+            token_id_map_cache: TtlCache::new(DEFAULT_TOKEN_ID_MAP_TTL),
+            sequences: SequenceManager::default(),
+            gas_config: GasConfig::default(),
         }
     }
 
@@ -113,8 +300,13 @@ impl StarcoinClient<StarcoinSdkClient> {
         let self_ = Self {
             inner,
             bridge_metrics,
+            bridge_summary_cache: TtlCache::new(DEFAULT_BRIDGE_SUMMARY_TTL),
+            token_id_map_cache: TtlCache::new(DEFAULT_TOKEN_ID_MAP_TTL),
+            sequences: SequenceManager::default(),
+            gas_config: GasConfig::default(),
         };
-        self_.describe().await?;
+        // Test clusters run dev nodes
+        self_.describe(BridgeChainId::StarcoinCustom).await?;
         Ok(self_)
     }
 
@@ -127,60 +319,252 @@ impl<P> StarcoinClient<P>
 where
     P: StarcoinClientInner,
 {
+    // Nothing is cached, so changes to a mock client show up right away. Use
+    // the `with_*_ttl` setters to test caching.
     pub fn new_for_testing(inner: P) -> Self {
         Self {
             inner,
             bridge_metrics: Arc::new(BridgeMetrics::new_for_testing()),
+            bridge_summary_cache: TtlCache::new(Duration::ZERO),
+            token_id_map_cache: TtlCache::new(Duration::ZERO),
+            sequences: SequenceManager::default(),
+            gas_config: GasConfig::default(),
         }
     }
 
+    // How long a bridge summary is reused by `get_bridge_summary` and the
+    // lookups built on it. Zero disables caching.
+    pub fn with_bridge_summary_ttl(mut self, ttl: Duration) -> Self {
+        self.bridge_summary_cache = TtlCache::new(ttl);
+        self
+    }
+
+    // How long the token id map is reused. Zero disables caching.
+    pub fn with_token_id_map_ttl(mut self, ttl: Duration) -> Self {
+        self.token_id_map_cache = TtlCache::new(ttl);
+        self
+    }
+
+    // Scales the reference gas price in `get_gas_unit_price`, e.g. 1.1 bids
+    // 10% above it for priority
+    pub fn with_gas_price_multiplier(mut self, multiplier: f64) -> Self {
+        self.gas_config.gas_unit_price_multiplier = multiplier;
+        self
+    }
+
+    // Gas price multiplier and max gas of the transactions built with this
+    // client, see `get_gas_unit_price` and `max_gas_amount`
+    pub fn with_gas_config(mut self, gas_config: GasConfig) -> Self {
+        self.gas_config = gas_config;
+        self
+    }
+
+    pub fn gas_config(&self) -> &GasConfig {
+        &self.gas_config
+    }
+
+    // Max gas of the transactions executing actions of `action_type`
+    pub fn max_gas_amount(&self, action_type: BridgeActionType) -> u64 {
+        self.gas_config.max_gas_amount_for(action_type)
+    }
+
+    // Drops the cached bridge summary and token id map, for callers that
+    // just changed bridge state, e.g. by executing a governance action.
+    pub async fn invalidate_bridge_summary_cache(&self) {
+        self.bridge_summary_cache.invalidate().await;
+        self.token_id_map_cache.invalidate().await;
+    }
+
     /// Get the configured bridge contract address
     pub fn bridge_address(&self) -> &str {
         self.inner.bridge_address()
     }
 
-    async fn describe(&self) -> anyhow::Result<()> {
+    // Log the chain and head block of the node, and fail if the node serves
+    // another chain than `bridge_chain_id`
+    pub async fn describe(&self, bridge_chain_id: BridgeChainId) -> anyhow::Result<()> {
         let chain_id = self.inner.get_chain_identifier().await?;
         let block_number = self.inner.get_latest_checkpoint_sequence_number().await?;
         tracing::info!(
             "StarcoinClient is connected to chain {chain_id}, current block number: {block_number}"
         );
-        // Chain identifier is informational - actual chain ID validation happens in config.rs
+        let node_chain_id = chain_id
+            .parse::<u8>()
+            .map_err(|_| anyhow!("Starcoin node returned an invalid chain id: {chain_id}"))?;
+        let node_bridge_chain_id = BridgeChainId::from_starcoin_node_chain_id(node_chain_id);
+        if node_bridge_chain_id != bridge_chain_id {
+            anyhow::bail!(
+                "Starcoin node is on chain {chain_id} ({node_bridge_chain_id:?}), but the bridge is configured for {bridge_chain_id:?}"
+            );
+        }
         Ok(())
     }
 
+    // Get the mutable bridge object arg on chain, retrying within `deadline`.
+    // After the first success, the result is cached since the value should never change.
+    pub async fn get_mutable_bridge_object_arg(
+        &self,
+        deadline: Deadline,
+    ) -> BridgeResult<ObjectArg> {
+        static ARG: OnceCell<ObjectArg> = OnceCell::const_new();
+        ARG.get_or_try_init(|| {
+            retry_within(
+                deadline,
+                "get_mutable_bridge_object_arg",
+                Duration::from_secs(30),
+                || async {
+                    self.inner
+                        .get_mutable_bridge_object_arg()
+                        .await
+                        .map_err(P::rpc_error)
+                },
+            )
+        })
+        .await
+        .cloned()
+    }
+
     // Get the mutable bridge object arg on chain.
     // We retry a few times in case of errors. If it fails eventually, we panic.
     // In general it's safe to call in the beginning of the program.
-    // After the first call, the result is cached since the value should never change.
     pub async fn get_mutable_bridge_object_arg_must_succeed(&self) -> ObjectArg {
-        static ARG: OnceCell<ObjectArg> = OnceCell::const_new();
-        ARG.get_or_init(|| async move {
-            let Ok(Ok(bridge_object_arg)) = retry_with_max_elapsed_time!(
-                self.inner.get_mutable_bridge_object_arg(),
-                Duration::from_secs(30)
-            ) else {
-                panic!("Failed to get bridge object arg after retries");
-            };
-            bridge_object_arg
-        })
-        .await
-        .clone()
+        self.get_mutable_bridge_object_arg(Deadline::none())
+            .await
+            .expect("Failed to get bridge object arg after retries")
+    }
+
+    // Query emitted Events matching `filter`. The cursor must have been issued
+    // for the same filter. Cursors persisted before they carried a filter
+    // fingerprint are accepted with a warning; the returned page's cursor is
+    // always in the current format.
+    pub async fn query_events(
+        &self,
+        filter: EventFilter,
+        // cursor is exclusive
+        cursor: Option<EventCursor>,
+    ) -> BridgeResult<EventPage> {
+        if let Some(cursor) = &cursor {
+            cursor
+                .check(&filter)
+                .map_err(|e| BridgeError::CursorFilterMismatch(e.to_string()))?;
+            if cursor.is_legacy() {
+                warn!(
+                    "Accepting event cursor {} without a filter fingerprint, it will be re-issued in the current format",
+                    cursor
+                );
+            }
+        }
+        self.inner
+            .query_events(filter, cursor)
+            .await
+            .map_err(P::rpc_error)
     }
 
     // Query emitted Events that are defined in the given Move Module.
+    // `module` is an internal identifier or an on-chain module name, and is
+    // matched exactly after mapping it, see `BRIDGE_EVENT_MODULES`.
     pub async fn query_events_by_module(
         &self,
         package: ObjectID,
         module: Identifier,
         // cursor is exclusive
         cursor: Option<EventID>,
-    ) -> BridgeResult<Page<StarcoinEvent>> {
+    ) -> BridgeResult<EventPage> {
+        let starcoin_addr = package_starcoin_address(&package)?;
         // Use the trait method which mock client can override
-        self.inner
-            .query_events_by_module(package, module, cursor)
+        let events = self
+            .inner
+            .query_events_by_module(package, module.clone(), cursor)
             .await
-            .map_err(|e| BridgeError::InternalError(format!("Query events failed: {:?}", e)))
+            .map_err(P::rpc_error)?;
+
+        // The node can't filter by module
+        let received = events.data.len();
+        let mut data = Vec::with_capacity(received);
+        for event in events.data {
+            let event_module = event.type_.module.as_str();
+            // New Move modules must be added to the mapping to be watched
+            if event.type_.address.as_ref() == starcoin_addr.as_slice()
+                && !is_mapped_module(event_module)
+            {
+                warn!(
+                    module = event_module,
+                    "Event {:?} of the bridge package is from an unmapped module", event.id
+                );
+                self.bridge_metrics
+                    .starcoin_bridge_unmapped_module_events
+                    .with_label_values(&[event_module])
+                    .inc();
+            }
+            if event_module_matches(event_module, module.as_str()) {
+                data.push(event);
+            }
+        }
+        if data.len() == received {
+            tracing::debug!(
+                module = module.as_str(),
+                events = received,
+                "Server-side event filter matched the module, client-side filter was a no-op"
+            );
+        } else {
+            tracing::debug!(
+                module = module.as_str(),
+                received,
+                kept = data.len(),
+                "Client-side event filter dropped events of other modules"
+            );
+        }
+        Ok(EventPage {
+            data,
+            next_cursor: events.next_cursor,
+            has_next_page: events.has_next_page,
+        })
+    }
+
+    // Collects events of `module` after `cursor`, following pages until at
+    // least `limit` events are collected or the pages run out. Pages emptied
+    // by the module filter are followed too, so a page of other modules'
+    // events doesn't hide the ones after it. Pages are never split, which
+    // would skip the rest of the page, so more than `limit` events may come
+    // back. `next_cursor` is past every page read, whether it held events of
+    // the module or not, and is where to resume after a restart.
+    pub async fn query_events_by_module_paginated(
+        &self,
+        package: ObjectID,
+        module: Identifier,
+        // cursor is exclusive
+        cursor: Option<EventID>,
+        limit: usize,
+    ) -> BridgeResult<ModuleEventPage> {
+        let mut data = vec![];
+        let mut next_cursor = cursor;
+        loop {
+            let mut page = self
+                .query_events_by_module(package, module.clone(), next_cursor)
+                .await?;
+            // The RPC does not guarantee ordering within a page
+            page.data
+                .sort_by_key(|event| (event.id.block_number, event.id.event_seq));
+            let page_end = page
+                .next_cursor
+                .map(|cursor| cursor.position())
+                .into_iter()
+                .chain(page.data.last().map(|event| event.id.into()))
+                .max();
+            data.extend(page.data);
+            // A page that doesn't move the cursor would be served again
+            let advanced = page_end.is_some() && page_end > next_cursor;
+            if advanced {
+                next_cursor = page_end;
+            }
+            if !page.has_next_page || !advanced || data.len() >= limit {
+                return Ok(ModuleEventPage {
+                    data,
+                    next_cursor,
+                    has_next_page: page.has_next_page,
+                });
+            }
+        }
     }
 
     // Returns BridgeAction from a Starcoin Transaction with transaction hash
@@ -196,7 +580,11 @@ where
         tx_digest: &TransactionDigest,
         event_idx: u16,
     ) -> BridgeResult<BridgeAction> {
-        let events = self.inner.get_events_by_tx_digest(*tx_digest).await?;
+        let events = self
+            .inner
+            .get_events_by_tx_digest(*tx_digest)
+            .await
+            .map_err(P::rpc_error)?;
 
         // Get expected bridge address from config (16 bytes for Starcoin)
         let expected_addr = hex::decode(self.bridge_address().trim_start_matches("0x"))
@@ -241,11 +629,13 @@ where
             .ok_or(BridgeError::BridgeEventNotActionable)
     }
 
+    // Cached for the bridge summary ttl, see `with_bridge_summary_ttl`
     pub async fn get_bridge_summary(&self) -> BridgeResult<BridgeSummary> {
-        self.inner
-            .get_bridge_summary()
+        self.bridge_summary_cache
+            .get_or_try_fetch(|| async {
+                self.inner.get_bridge_summary().await.map_err(P::rpc_error)
+            })
             .await
-            .map_err(|e| BridgeError::InternalError(format!("Can't get bridge committee: {e}")))
     }
 
     pub async fn is_bridge_paused(&self) -> BridgeResult<bool> {
@@ -254,26 +644,79 @@ where
             .map(|summary| summary.is_frozen)
     }
 
+    // USD value, with `USD_MULTIPLIER` precision, that transfers from
+    // `sending_chain` to `receiving_chain` can still carry before the
+    // limiter holds them back, as of the latest block. Only routes into
+    // Starcoin are limited here, the reverse is up to the Eth `BridgeLimiter`.
+    pub async fn get_remaining_transfer_budget(
+        &self,
+        sending_chain: BridgeChainId,
+        receiving_chain: BridgeChainId,
+    ) -> BridgeResult<u64> {
+        let limiter = self.get_bridge_summary().await?.limiter;
+        let current_hour_since_epoch = self.get_block_timestamp().await? / 3_600_000;
+        limiter
+            .remaining_transfer_budget(sending_chain, receiving_chain, current_hour_since_epoch)
+            .ok_or_else(|| {
+                BridgeError::InternalError(format!(
+                    "No transfer limit for route {:?} -> {:?}",
+                    sending_chain, receiving_chain
+                ))
+            })
+    }
+
     pub async fn get_treasury_summary(&self) -> BridgeResult<BridgeTreasurySummary> {
         Ok(self.get_bridge_summary().await?.treasury)
     }
 
+    pub async fn is_token_paused(&self, token_id: u8) -> BridgeResult<bool> {
+        Ok(self.get_treasury_summary().await?.is_token_paused(token_id))
+    }
+
+    // Pre-flight check for deposits and claims, so a paused token fails fast
+    // instead of reverting on-chain.
+    pub async fn ensure_token_not_paused(&self, token_id: u8) -> BridgeResult<()> {
+        if self.is_token_paused(token_id).await? {
+            return Err(BridgeError::TokenPaused(token_id));
+        }
+        Ok(())
+    }
+
+    // Cached for the token id map ttl, see `with_token_id_map_ttl`
     pub async fn get_token_id_map(&self) -> BridgeResult<HashMap<u8, TypeTag>> {
-        self.get_bridge_summary()
-            .await?
-            .treasury
-            .id_token_type_map
-            .into_iter()
-            .map(|(id, name)| {
-                parse_starcoin_bridge_type_tag(&format!("0x{name}"))
-                    .map(|name| (id, name))
-                    .map_err(|e| {
-                        BridgeError::InternalError(format!(
-                            "Failed to retrieve token id mapping: {e}, type name: {name}"
-                        ))
+        self.token_id_map_cache
+            .get_or_try_fetch(|| async {
+                self.get_bridge_summary()
+                    .await?
+                    .treasury
+                    .id_token_type_map
+                    .into_iter()
+                    .map(|(id, name)| {
+                        parse_starcoin_bridge_type_tag(&format!("0x{name}"))
+                            .map(|name| (id, name))
+                            .map_err(|e| {
+                                BridgeError::InternalError(format!(
+                                    "Failed to retrieve token id mapping: {e}, type name: {name}"
+                                ))
+                            })
                     })
+                    .collect()
             })
-            .collect()
+            .await
+    }
+
+    // Bytecode of the Move module defining `token_type`, None if the module is
+    // not published (or the type is not a struct).
+    pub async fn get_token_module_bytes(
+        &self,
+        token_type: &TypeTag,
+    ) -> BridgeResult<Option<Vec<u8>>> {
+        let TypeTag::Struct(tag) = token_type else {
+            return Ok(None);
+        };
+        self.inner
+            .get_module_bytes(&format!("0x{}", tag.address.to_hex()), tag.module.as_str())
+            .await
     }
 
     pub async fn get_notional_values(&self) -> BridgeResult<HashMap<u8, u64>> {
@@ -302,65 +745,131 @@ where
     }
 
     pub async fn get_bridge_committee(&self) -> BridgeResult<BridgeCommittee> {
-        let bridge_summary =
-            self.inner.get_bridge_summary().await.map_err(|e| {
-                BridgeError::InternalError(format!("Can't get bridge committee: {e}"))
-            })?;
-        let move_type_bridge_committee = bridge_summary.committee;
+        let bridge_summary = self
+            .inner
+            .get_bridge_summary()
+            .await
+            .map_err(P::rpc_error)?;
+        bridge_committee_from_summary(bridge_summary.committee)
+    }
 
-        let mut authorities = vec![];
-        // Convert MoveTypeBridgeCommittee members to BridgeAuthority
-        // This logic is here because BridgeCommittee needs to be constructed from authorities
-        for (_, member) in move_type_bridge_committee.members {
-            let MoveTypeCommitteeMember {
-                starcoin_bridge_address,
-                bridge_pubkey_bytes,
-                voting_power,
-                http_rest_url,
-                blocklisted,
-            } = member;
-            let pubkey = BridgeAuthorityPublicKey::from_bytes(&bridge_pubkey_bytes)?;
-            let base_url = from_utf8(&http_rest_url).unwrap_or_else(|_e| {
-                warn!(
-                    "Bridge authority address: {}, pubkey: {:?} has invalid http url: {:?}",
-                    starcoin_bridge_address, bridge_pubkey_bytes, http_rest_url
-                );
-                ""
-            });
-            authorities.push(BridgeAuthority {
-                starcoin_bridge_address,
-                pubkey,
-                voting_power,
-                base_url: base_url.into(),
-                is_blocklisted: blocklisted,
-            });
+    pub async fn get_chain_identifier(&self) -> BridgeResult<String> {
+        self.inner
+            .get_chain_identifier()
+            .await
+            .map_err(P::rpc_error)
+    }
+
+    // Retries `f` in rounds of 30 seconds until it succeeds, `deadline`
+    // passes or it fails with an error that is not retryable. Without a
+    // deadline only the latter are returned.
+    async fn retry_until<T, F, Fut>(
+        &self,
+        method: &'static str,
+        context: &str,
+        deadline: Deadline,
+        f: F,
+    ) -> BridgeResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = BridgeResult<T>>,
+    {
+        let mut logger = RateLimitedLogger::new(method);
+        loop {
+            match retry_within(deadline, method, Duration::from_secs(30), &f).await {
+                Ok(value) => {
+                    logger.on_success();
+                    return Ok(value);
+                }
+                Err(e @ BridgeError::DeadlineExceeded(_)) => return Err(e),
+                // Retrying won't help
+                Err(e) if !e.is_retryable() => return Err(e),
+                Err(e) => {
+                    self.bridge_metrics
+                        .starcoin_bridge_rpc_errors
+                        .with_label_values(&[method])
+                        .inc();
+                    if logger.on_failure(&e) && !context.is_empty() {
+                        error!(method, context, "[QUERY] Lookup failing");
+                    }
+                }
+            }
         }
-        BridgeCommittee::new(authorities)
     }
 
-    pub async fn get_chain_identifier(&self) -> BridgeResult<String> {
-        Ok(self.inner.get_chain_identifier().await?)
+    pub async fn get_reference_gas_price(&self, deadline: Deadline) -> BridgeResult<u64> {
+        self.retry_until("get_reference_gas_price", "", deadline, || async {
+            self.inner
+                .get_reference_gas_price()
+                .await
+                .map_err(P::rpc_error)
+        })
+        .await
     }
 
-    pub async fn get_reference_gas_price_until_success(&self) -> u64 {
+    // Retries until the price is read, with a growing delay between reads.
+    // With `max_attempts`, gives up with the last error after that many reads.
+    // Errors that are not retryable are returned right away.
+    pub async fn get_reference_gas_price_until_success(
+        &self,
+        max_attempts: Option<u32>,
+    ) -> BridgeResult<u64> {
+        let method = "get_reference_gas_price";
+        let mut logger = RateLimitedLogger::new(method);
+        let mut delay = GAS_PRICE_RETRY_INITIAL_DELAY;
+        let mut attempts = 0;
         loop {
-            let Ok(Ok(rgp)) = retry_with_max_elapsed_time!(
-                self.inner.get_reference_gas_price(),
-                Duration::from_secs(30)
-            ) else {
+            attempts += 1;
+            match self.inner.get_reference_gas_price().await {
+                Ok(price) => {
+                    logger.on_success();
+                    return Ok(price);
+                }
+                Err(e) => {
+                    let e = P::rpc_error(e);
+                    self.bridge_metrics
+                        .starcoin_bridge_rpc_errors
+                        .with_label_values(&[method])
+                        .inc();
+                    if !e.is_retryable() || max_attempts.is_some_and(|max| attempts >= max) {
+                        return Err(e);
+                    }
+                    logger.on_failure(&e);
+                }
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(GAS_PRICE_RETRY_MAX_DELAY);
+        }
+    }
+
+    // Gas unit price for the transactions the bridge submits: the reference
+    // price scaled by the gas price multiplier. Falls back to
+    // `DEFAULT_GAS_UNIT_PRICE` if the price can't be read, as an underpriced
+    // transaction still beats one that is never sent.
+    pub async fn get_gas_unit_price(&self) -> u64 {
+        let reference_gas_price = match self.inner.get_reference_gas_price().await {
+            Ok(price) => price,
+            Err(e) => {
                 self.bridge_metrics
                     .starcoin_bridge_rpc_errors
                     .with_label_values(&["get_reference_gas_price"])
                     .inc();
-                error!("Failed to get reference gas price");
-                continue;
-            };
-            return rgp;
-        }
+                warn!(
+                    "Failed to get reference gas price, using {}: {:?}",
+                    DEFAULT_GAS_UNIT_PRICE,
+                    P::rpc_error(e)
+                );
+                DEFAULT_GAS_UNIT_PRICE
+            }
+        };
+        self.gas_config.gas_unit_price(reference_gas_price)
     }
 
     pub async fn get_latest_checkpoint_sequence_number(&self) -> BridgeResult<u64> {
-        Ok(self.inner.get_latest_checkpoint_sequence_number().await?)
+        self.inner
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .map_err(P::rpc_error)
     }
 
     pub async fn execute_transaction_block_with_effects(
@@ -370,76 +879,100 @@ where
         self.inner.execute_transaction_block_with_effects(tx).await
     }
 
-    // This function polls until action status is success
+    // Polls the action status until the lookup succeeds or `deadline` passes.
+    pub async fn get_token_transfer_action_onchain_status(
+        &self,
+        source_chain_id: u8,
+        seq_number: u64,
+        deadline: Deadline,
+    ) -> BridgeResult<BridgeActionStatus> {
+        let context = format!("source_chain_id={source_chain_id} seq_number={seq_number}");
+        self.retry_until(
+            "get_token_transfer_action_onchain_status",
+            &context,
+            deadline,
+            || async {
+                let bridge_object_arg = self.get_mutable_bridge_object_arg(deadline).await?;
+                self.inner
+                    .get_token_transfer_action_onchain_status(
+                        bridge_object_arg,
+                        source_chain_id,
+                        seq_number,
+                    )
+                    .await
+            },
+        )
+        .await
+    }
+
+    // This function polls until action status is success, or the lookup
+    // fails with an error that is not retryable.
     // Performance in tests can be improved by using a mock client
     pub async fn get_token_transfer_action_onchain_status_until_success(
         &self,
         source_chain_id: u8,
         seq_number: u64,
-    ) -> BridgeActionStatus {
-        loop {
-            let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
-            let Ok(Ok(status)) = retry_with_max_elapsed_time!(
-                self.inner.get_token_transfer_action_onchain_status(
-                    bridge_object_arg.clone(),
-                    source_chain_id,
-                    seq_number
-                ),
-                Duration::from_secs(30)
-            ) else {
-                self.bridge_metrics
-                    .starcoin_bridge_rpc_errors
-                    .with_label_values(&["get_token_transfer_action_onchain_status"])
-                    .inc();
-                error!(
-                    "[QUERY] Failed to get token transfer action onchain status: source_chain={}, seq_num={}",
-                    source_chain_id, seq_number
-                );
-                continue;
-            };
+    ) -> BridgeResult<BridgeActionStatus> {
+        self.get_token_transfer_action_onchain_status(source_chain_id, seq_number, Deadline::none())
+            .await
+    }
 
-            return status;
-        }
+    pub async fn get_token_transfer_action_onchain_signatures(
+        &self,
+        source_chain_id: u8,
+        seq_number: u64,
+        deadline: Deadline,
+    ) -> BridgeResult<Option<Vec<Vec<u8>>>> {
+        let context = format!("source_chain_id={source_chain_id} seq_number={seq_number}");
+        self.retry_until(
+            "get_token_transfer_action_onchain_signatures",
+            &context,
+            deadline,
+            || async {
+                let bridge_object_arg = self.get_mutable_bridge_object_arg(deadline).await?;
+                self.inner
+                    .get_token_transfer_action_onchain_signatures(
+                        bridge_object_arg,
+                        source_chain_id,
+                        seq_number,
+                    )
+                    .await
+            },
+        )
+        .await
     }
 
+    // Polls the signatures until the lookup succeeds, or fails with an error
+    // that is not retryable.
     pub async fn get_token_transfer_action_onchain_signatures_until_success(
         &self,
         source_chain_id: u8,
         seq_number: u64,
-    ) -> Option<Vec<Vec<u8>>> {
-        loop {
-            let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
-            let Ok(Ok(sigs)) = retry_with_max_elapsed_time!(
-                self.inner.get_token_transfer_action_onchain_signatures(
-                    bridge_object_arg.clone(),
-                    source_chain_id,
-                    seq_number
-                ),
-                Duration::from_secs(30)
-            ) else {
-                self.bridge_metrics
-                    .starcoin_bridge_rpc_errors
-                    .with_label_values(&["get_token_transfer_action_onchain_signatures"])
-                    .inc();
-                error!(
-                    source_chain_id,
-                    seq_number, "Failed to get token transfer action onchain signatures"
-                );
-                continue;
-            };
-            return sigs;
-        }
+    ) -> BridgeResult<Option<Vec<Vec<u8>>>> {
+        self.get_token_transfer_action_onchain_signatures(
+            source_chain_id,
+            seq_number,
+            Deadline::none(),
+        )
+        .await
     }
 
     pub async fn get_parsed_token_transfer_message(
         &self,
         source_chain_id: u8,
         seq_number: u64,
+        deadline: Deadline,
     ) -> BridgeResult<Option<ParsedTokenTransferMessage>> {
-        let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
-        let message = self
-            .inner
-            .get_parsed_token_transfer_message(bridge_object_arg, source_chain_id, seq_number)
+        let bridge_object_arg = self.get_mutable_bridge_object_arg(deadline).await?;
+        let message = deadline
+            .run(
+                "get_parsed_token_transfer_message",
+                self.inner.get_parsed_token_transfer_message(
+                    bridge_object_arg,
+                    source_chain_id,
+                    seq_number,
+                ),
+            )
             .await?;
         Ok(match message {
             Some(payload) => Some(ParsedTokenTransferMessage::try_from(payload)?),
@@ -450,7 +983,7 @@ where
     pub async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner) {
+    ) -> BridgeResult<(GasCoin, ObjectRef, Owner)> {
         self.inner
             .get_gas_data_panic_if_not_gas(gas_object_id)
             .await
@@ -458,17 +991,13 @@ where
 
     /// Get account sequence number for transaction building
     pub async fn get_sequence_number(&self, address: &str) -> BridgeResult<u64> {
-        self.inner.get_sequence_number(address).await.map_err(|e| {
-            BridgeError::InternalError(format!("Failed to get sequence number: {:?}", e))
-        })
+        self.inner.get_sequence_number(address).await
     }
 
     /// Get the current block timestamp from the Starcoin chain
     /// Returns the timestamp in milliseconds from genesis
     pub async fn get_block_timestamp(&self) -> BridgeResult<u64> {
-        self.inner.get_block_timestamp().await.map_err(|e| {
-            BridgeError::InternalError(format!("Failed to get block timestamp: {:?}", e))
-        })
+        self.inner.get_block_timestamp().await
     }
 
     /// Sign and submit a transaction to the Starcoin network
@@ -477,25 +1006,139 @@ where
         key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
         raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
     ) -> BridgeResult<String> {
-        self.inner
-            .sign_and_submit_transaction(key, raw_txn)
-            .await
-            .map_err(|e| {
-                BridgeError::InternalError(format!("Transaction submission failed: {:?}", e))
-            })
+        self.inner.sign_and_submit_transaction(key, raw_txn).await
     }
 
-    /// Sign, submit and wait for transaction confirmation
-    /// Polls for up to 30 seconds until the transaction is confirmed on chain
-    /// by checking that the account sequence number has incremented
-    pub async fn sign_and_submit_and_wait_transaction(
+    /// Builds a transaction of `key`'s account with `build_fn`, given the
+    /// sequence number to use, and submits it. Submissions of one account
+    /// through this client are serialized and take consecutive sequence
+    /// numbers, starting from the one on chain. A failed submission releases
+    /// its sequence number, and a rejection for a stale sequence number is
+    /// retried with the one on chain, up to `MAX_STALE_SEQUENCE_RETRIES` times.
+    pub async fn submit_with_managed_sequence<F>(
         &self,
         key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
-        raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
-    ) -> BridgeResult<String> {
-        // Get the expected sequence number after transaction confirms
-        let expected_seq = raw_txn.sequence_number() + 1;
-        let sender_address = key.starcoin_address().to_hex_literal();
+        mut build_fn: F,
+    ) -> BridgeResult<ManagedSubmission>
+    where
+        F: FnMut(u64) -> BridgeResult<starcoin_bridge_types::transaction::RawUserTransaction>,
+    {
+        let sender = key.starcoin_address().to_hex_literal();
+        let account = self.sequences.account(&sender);
+        let mut next = account.lock().await;
+        let mut stale_retries = 0;
+        loop {
+            let sequence_number = match *next {
+                Some(sequence_number) => sequence_number,
+                None => self.get_sequence_number(&sender).await?,
+            };
+            // Nothing was submitted, the next submission reuses the number
+            let raw_txn = build_fn(sequence_number).inspect_err(|_| {
+                *next = Some(sequence_number);
+            })?;
+            match self.sign_and_submit_transaction(key, raw_txn).await {
+                Ok(txn_hash) => {
+                    *next = Some(sequence_number + 1);
+                    return Ok(ManagedSubmission {
+                        txn_hash,
+                        sequence_number,
+                    });
+                }
+                Err(e)
+                    if is_stale_sequence_error(&e)
+                        && stale_retries < MAX_STALE_SEQUENCE_RETRIES =>
+                {
+                    stale_retries += 1;
+                    // Another client of the account used the number
+                    let on_chain = self.get_sequence_number(&sender).await?;
+                    warn!(
+                        %sender,
+                        sequence_number,
+                        on_chain,
+                        "Stale sequence number, resubmitting"
+                    );
+                    *next = Some(on_chain.max(sequence_number + 1));
+                }
+                Err(e) => {
+                    // The node may still have taken the transaction, so the
+                    // next submission reads the sequence number on chain
+                    *next = None;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// `submit_with_managed_sequence`, then waits for the transaction like
+    /// `sign_and_submit_and_wait_transaction`
+    pub async fn submit_with_managed_sequence_and_wait<F>(
+        &self,
+        key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        build_fn: F,
+    ) -> BridgeResult<StarcoinTxReceipt>
+    where
+        F: FnMut(u64) -> BridgeResult<starcoin_bridge_types::transaction::RawUserTransaction>,
+    {
+        let submission = self.submit_with_managed_sequence(key, build_fn).await?;
+        tracing::info!(
+            txn_hash = ?submission.txn_hash,
+            sequence_number = submission.sequence_number,
+            "Transaction submitted, waiting for confirmation"
+        );
+        self.wait_for_transaction(
+            &submission.txn_hash,
+            &key.starcoin_address().to_hex_literal(),
+            submission.sequence_number + 1,
+            TX_CONFIRMATION_POLL_INTERVAL,
+            TX_CONFIRMATION_POLLS,
+        )
+        .await
+    }
+
+    /// Gas to give a transaction of `action_type`: what a dry run of it used,
+    /// plus `GAS_ESTIMATE_MARGIN`. Fails if the dry run fails, or if the
+    /// estimate is over the max gas configured for `action_type`, as the
+    /// transaction would run out of gas.
+    pub async fn estimate_gas(
+        &self,
+        key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+        action_type: BridgeActionType,
+    ) -> BridgeResult<u64> {
+        let output = self
+            .inner
+            .dry_run_transaction(key, raw_txn)
+            .await
+            .map_err(|e| BridgeError::InternalError(format!("Dry run failed: {:?}", e)))?;
+        let gas_used = starcoin_tx_receipt("dry-run", &output)?
+            .gas_used
+            .ok_or_else(|| {
+                BridgeError::InternalError("Dry run output has no gas_used".to_string())
+            })?;
+        let estimate = gas_with_margin(gas_used);
+        let max_gas_amount = self.max_gas_amount(action_type);
+        if estimate > max_gas_amount {
+            return Err(BridgeError::Generic(format!(
+                "Estimated gas {} of a {} transaction is over its max gas amount {}",
+                estimate, action_type, max_gas_amount
+            )));
+        }
+        Ok(estimate)
+    }
+
+    /// Sign, submit and wait for transaction confirmation
+    /// Polls for up to 30 seconds until the account sequence number has
+    /// incremented, then checks how the transaction executed. An aborted
+    /// transaction also increments the sequence number, and fails with
+    /// `BridgeError::StarcoinTxFailure`.
+    pub async fn sign_and_submit_and_wait_transaction(
+        &self,
+        key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+    ) -> BridgeResult<StarcoinTxReceipt> {
+        // Get the expected sequence number after transaction confirms
+        let expected_seq = raw_txn.sequence_number() + 1;
+        let sender_address = key.starcoin_address().to_hex_literal();
 
         let txn_hash = self.sign_and_submit_transaction(key, raw_txn).await?;
 
@@ -504,45 +1147,141 @@ where
             expected_seq,
             "Transaction submitted, waiting for confirmation"
         );
+        self.wait_for_transaction(
+            &txn_hash,
+            &sender_address,
+            expected_seq,
+            TX_CONFIRMATION_POLL_INTERVAL,
+            TX_CONFIRMATION_POLLS,
+        )
+        .await
+    }
 
-        // Poll for transaction confirmation (max 30 seconds, check every 500ms)
-        for i in 0..60 {
-            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-
-            // Check if transaction is confirmed by verifying sequence number has incremented
-            match self.get_sequence_number(&sender_address).await {
-                Ok(current_seq) => {
-                    if current_seq >= expected_seq {
-                        tracing::info!(
-                            ?txn_hash,
-                            current_seq,
-                            expected_seq,
-                            "Transaction confirmed on chain"
-                        );
-                        return Ok(txn_hash);
-                    }
-                    if i % 10 == 0 {
-                        tracing::debug!(
-                            ?txn_hash,
-                            current_seq,
-                            expected_seq,
-                            "Still waiting for confirmation..."
-                        );
+    async fn wait_for_transaction(
+        &self,
+        txn_hash: &str,
+        sender_address: &str,
+        expected_seq: u64,
+        poll_interval: Duration,
+        polls: u32,
+    ) -> BridgeResult<StarcoinTxReceipt> {
+        let mut missing_polls = 0;
+        for i in 0..polls {
+            tokio::time::sleep(poll_interval).await;
+
+            let current_seq = match self.get_sequence_number(sender_address).await {
+                Ok(current_seq) => current_seq,
+                Err(e) => {
+                    tracing::warn!(?txn_hash, ?e, "Failed to get sequence number, retrying...");
+                    continue;
+                }
+            };
+            if current_seq < expected_seq {
+                if i % 10 == 0 {
+                    tracing::debug!(
+                        ?txn_hash,
+                        current_seq,
+                        expected_seq,
+                        "Still waiting for confirmation..."
+                    );
+                }
+                continue;
+            }
+
+            // The sequence number also moves when another transaction of the
+            // sender is included, so the outcome is looked up by hash
+            match self.inner.get_transaction_info(txn_hash).await {
+                Ok(Some(txn_info)) => {
+                    let receipt = starcoin_tx_receipt(txn_hash, &txn_info).inspect_err(|e| {
+                        tracing::warn!(?txn_hash, ?e, "Transaction failed on chain");
+                    })?;
+                    tracing::info!(
+                        ?txn_hash,
+                        block_number = ?receipt.block_number,
+                        gas_used = ?receipt.gas_used,
+                        "Transaction confirmed on chain"
+                    );
+                    return Ok(receipt);
+                }
+                Ok(None) => {
+                    missing_polls += 1;
+                    if missing_polls >= TX_INFO_MISSING_POLLS {
+                        return Err(BridgeError::InternalError(format!(
+                            "Sequence number {} of {} was used by another transaction, {} was not included",
+                            expected_seq - 1,
+                            sender_address,
+                            txn_hash
+                        )));
                     }
                 }
                 Err(e) => {
-                    tracing::warn!(?txn_hash, ?e, "Failed to get sequence number, retrying...");
+                    tracing::warn!(?txn_hash, ?e, "Failed to get transaction info, retrying...");
                 }
             }
         }
 
         Err(BridgeError::InternalError(format!(
-            "Transaction {} not confirmed after 30 seconds timeout",
-            txn_hash
+            "Transaction {} not confirmed after {:?} timeout",
+            txn_hash,
+            poll_interval * polls
         )))
     }
 }
 
+// Events of one module over several pages. The cursor is the (block number,
+// event seq) position the syncers persist.
+pub type ModuleEventPage = Page<StarcoinEvent, EventID>;
+
+// The committee of a bridge summary, for callers that need the summary as
+// well and so can't use `get_bridge_committee` without a second read
+pub fn bridge_committee_from_summary(
+    move_type_bridge_committee: BridgeCommitteeSummary,
+) -> BridgeResult<BridgeCommittee> {
+    let mut authorities = vec![];
+    // Convert MoveTypeBridgeCommittee members to BridgeAuthority
+    // This logic is here because BridgeCommittee needs to be constructed from authorities
+    for (_, member) in move_type_bridge_committee.members {
+        let MoveTypeCommitteeMember {
+            starcoin_bridge_address,
+            bridge_pubkey_bytes,
+            voting_power,
+            http_rest_url,
+            blocklisted,
+        } = member;
+        let pubkey = BridgeAuthorityPublicKey::from_bytes(&bridge_pubkey_bytes)?;
+        // The url is validator controlled, sanitize it before it reaches
+        // logs and outputs
+        let base_url = sanitize_untrusted(&http_rest_url, MAX_URL_BYTES);
+        if base_url.as_bytes() != http_rest_url.as_slice() {
+            warn!(
+                "Bridge authority address: {}, pubkey: {:?} has invalid http url of {} bytes, using sanitized url: {:?}",
+                starcoin_bridge_address,
+                bridge_pubkey_bytes,
+                http_rest_url.len(),
+                base_url
+            );
+        }
+        authorities.push(BridgeAuthority {
+            starcoin_bridge_address,
+            pubkey,
+            voting_power,
+            base_url,
+            is_blocklisted: blocklisted,
+        });
+    }
+    BridgeCommittee::new(authorities)
+}
+
+// SDK errors of a request the client or node can't handle, e.g. a malformed
+// one, are permanent. Transport and node failures are not.
+pub fn sdk_rpc_error(error: starcoin_bridge_sdk::error::Error) -> BridgeError {
+    match error {
+        starcoin_bridge_sdk::error::Error::ClientError(_)
+        | starcoin_bridge_sdk::error::Error::Serialization(_) => BridgeError::permanent_rpc(error),
+        _ => BridgeError::transient_rpc(error),
+    }
+}
+
 // Use a trait to abstract over the StarcoinSDKClient and StarcoinMockClient for testing.
 #[async_trait]
 pub trait StarcoinClientInner: Send + Sync {
@@ -551,40 +1290,39 @@ pub trait StarcoinClientInner: Send + Sync {
     /// Get the configured bridge contract address
     fn bridge_address(&self) -> &str;
 
+    // Classifies a failure of the methods returning `Self::Error`. Unless the
+    // implementation knows better, they are assumed to be transient.
+    fn rpc_error(error: Self::Error) -> BridgeError {
+        BridgeError::transient_rpc(error)
+    }
+
+    // The cursor is validated against `query` by `StarcoinClient::query_events`.
     async fn query_events(
         &self,
         query: EventFilter,
-        cursor: Option<EventID>,
+        cursor: Option<EventCursor>,
     ) -> Result<EventPage, Self::Error>;
 
     /// Query events by module - for mock client support
-    /// Default implementation uses query_events with empty filter
+    /// Default implementation filters by the package address. Events are
+    /// filtered by `module` in `StarcoinClient::query_events_by_module`,
+    /// which also checked that `package` is a Starcoin address.
     async fn query_events_by_module(
         &self,
         package: ObjectID,
-        module: Identifier,
+        _module: Identifier,
         cursor: Option<EventID>,
     ) -> Result<EventPage, Self::Error> {
-        // Default implementation - filter events by module
-        let filter = EventFilter::default();
-        let events = self.query_events(filter, cursor).await?;
-
-        // Filter to matching module (Starcoin uses 16-byte addresses in last 16 bytes of ObjectID)
-        let starcoin_addr = &package[16..32];
-        let filtered_data: Vec<_> = events
+        let starcoin_addr = package_starcoin_address(&package)
+            .expect("Package id checked by StarcoinClient::query_events_by_module");
+        let filter = EventFilter::move_event_module(&format!("0x{}", hex::encode(starcoin_addr)));
+        let cursor = cursor.map(|position| EventCursor::new(position, &filter));
+        let mut events = self.query_events(filter, cursor).await?;
+        // Other packages' events are not expected but are dropped here too
+        events
             .data
-            .into_iter()
-            .filter(|event| {
-                event.type_.address.as_ref() == starcoin_addr
-                    && event.type_.module.as_str().to_lowercase() == module.as_str().to_lowercase()
-            })
-            .collect();
-
-        Ok(Page {
-            data: filtered_data,
-            next_cursor: events.next_cursor,
-            has_next_page: events.has_next_page,
-        })
+            .retain(|event| event.type_.address.as_ref() == starcoin_addr.as_slice());
+        Ok(events)
     }
 
     async fn get_events_by_tx_digest(
@@ -628,10 +1366,13 @@ pub trait StarcoinClientInner: Send + Sync {
         seq_number: u64,
     ) -> Result<Option<MoveTypeParsedTokenTransferMessage>, BridgeError>;
 
+    // Errors once the gas object can't be read `MAX_GAS_OBJECT_MISSES` times
+    // in a row, e.g. because the account doesn't exist. Panics if the object
+    // is not gas.
     async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner);
+    ) -> Result<(GasCoin, ObjectRef, Owner), BridgeError>;
 
     /// Get account sequence number for transaction building
     async fn get_sequence_number(&self, address: &str) -> Result<u64, BridgeError>;
@@ -646,6 +1387,41 @@ pub trait StarcoinClientInner: Send + Sync {
         key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
         raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
     ) -> Result<String, BridgeError>;
+
+    /// Execute a transaction signed by `key` without submitting it, returning
+    /// the node's dry run output, which has `status` and `gas_used` fields
+    /// like the `chain.get_transaction_info` view
+    async fn dry_run_transaction(
+        &self,
+        _key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        _raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+    ) -> Result<serde_json::Value, BridgeError> {
+        Err(BridgeError::Generic(
+            "dry_run_transaction is not supported by this client".to_string(),
+        ))
+    }
+
+    /// Get the `chain.get_transaction_info` view of a transaction, None if it
+    /// is not on chain
+    async fn get_transaction_info(
+        &self,
+        _txn_hash: &str,
+    ) -> Result<Option<serde_json::Value>, BridgeError> {
+        Err(BridgeError::Generic(
+            "get_transaction_info is not supported by this client".to_string(),
+        ))
+    }
+
+    /// Get the bytecode of a published Move module, None if it does not exist
+    async fn get_module_bytes(
+        &self,
+        _address: &str,
+        _module: &str,
+    ) -> Result<Option<Vec<u8>>, BridgeError> {
+        Err(BridgeError::Generic(
+            "get_module_bytes is not supported by this client".to_string(),
+        ))
+    }
 }
 
 // SDK-based implementation (only for tests)
@@ -654,6 +1430,10 @@ pub trait StarcoinClientInner: Send + Sync {
 impl StarcoinClientInner for StarcoinSdkClient {
     type Error = starcoin_bridge_sdk::error::Error;
 
+    fn rpc_error(error: Self::Error) -> BridgeError {
+        sdk_rpc_error(error)
+    }
+
     fn bridge_address(&self) -> &str {
         // Return a dummy address for testing
         "0x0000000000000000000000000000000b"
@@ -662,7 +1442,7 @@ impl StarcoinClientInner for StarcoinSdkClient {
     async fn query_events(
         &self,
         query: EventFilter,
-        cursor: Option<EventID>,
+        cursor: Option<EventCursor>,
     ) -> Result<EventPage, Self::Error> {
         self.event_api()
             .query_events(query, cursor, None, false)
@@ -679,19 +1459,13 @@ impl StarcoinClientInner for StarcoinSdkClient {
         &self,
         tx_digest: TransactionDigest,
     ) -> Result<Vec<StarcoinEvent>, Self::Error> {
-        // Query events from Starcoin using the SDK
-        // Note: Currently get_events returns Vec<Event> where Event is Vec<u8> (stub)
-        // We need to convert these to proper StarcoinEvent objects
-        let _events = self.event_api().get_events(&tx_digest).await.map_err(|e| {
+        let raw_events = self.event_api().get_events(&tx_digest).await.map_err(|e| {
             starcoin_bridge_sdk::error::Error::from(std::io::Error::new(
                 std::io::ErrorKind::Other,
                 format!("Failed to get events: {}", e),
             ))
         })?;
-
-        // TODO: Parse the raw event bytes into StarcoinEvent objects
-        // This requires understanding the event structure from Starcoin transactions
-        Ok(vec![])
+        Ok(events_from_rpc(&raw_events, tx_digest))
     }
 
     async fn get_chain_identifier(&self) -> Result<String, Self::Error> {
@@ -826,8 +1600,8 @@ impl StarcoinClientInner for StarcoinSdkClient {
     async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner) {
-        loop {
+    ) -> Result<(GasCoin, ObjectRef, Owner), BridgeError> {
+        for attempt in 1..=MAX_GAS_OBJECT_MISSES {
             match self
                 .read_api()
                 .get_object_with_options(
@@ -841,10 +1615,10 @@ impl StarcoinClientInner for StarcoinSdkClient {
             {
                 Ok(Some(gas_obj)) => {
                     let owner = gas_obj.owner.clone().expect("Owner is requested");
-                    // TODO: Parse gas coin value from object data
-                    // For now, use a default value
                     let gas_coin = GasCoin {
-                        value: 1_000_000_000,
+                        value: gas_obj
+                            .gas_balance
+                            .unwrap_or_else(|| panic!("{:?} is not a gas object", gas_object_id)),
                     };
                     // Convert Owner manually to avoid cyclic dependency
                     let owner_converted = match owner {
@@ -863,14 +1637,23 @@ impl StarcoinClientInner for StarcoinSdkClient {
                         },
                         starcoin_bridge_json_rpc_types::Owner::Immutable => starcoin_bridge_types::object::Owner::Immutable,
                     };
-                    return (gas_coin, gas_obj.object_ref(), owner_converted);
+                    return Ok((gas_coin, gas_obj.object_ref(), owner_converted));
                 }
                 other => {
-                    warn!("Can't get gas object: {:?}: {:?}", gas_object_id, other);
-                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    warn!(
+                        "Can't get gas object (attempt {attempt}/{MAX_GAS_OBJECT_MISSES}): {:?}: {:?}",
+                        gas_object_id, other
+                    );
+                    if attempt < MAX_GAS_OBJECT_MISSES {
+                        tokio::time::sleep(GAS_OBJECT_RETRY_INTERVAL).await;
+                    }
                 }
             }
         }
+        Err(BridgeError::InternalError(format!(
+            "Gas object {} not found after {MAX_GAS_OBJECT_MISSES} attempts",
+            hex::encode(gas_object_id)
+        )))
     }
 
     async fn get_sequence_number(&self, _address: &str) -> Result<u64, BridgeError> {
@@ -977,20 +1760,132 @@ mod tests {
     use ethers::types::Address as EthAddress;
     use move_core_types::account_address::AccountAddress;
     use serde::{Deserialize, Serialize};
-    use starcoin_bridge_types::bridge::{BridgeChainId, TOKEN_ID_STARCOIN, TOKEN_ID_USDC};
+    use starcoin_bridge_types::bridge::{TOKEN_ID_STARCOIN, TOKEN_ID_USDC};
     use std::str::FromStr;
 
     use super::*;
     use crate::events::{init_all_struct_tags, StarcoinToEthTokenBridgeV1};
+    use crate::fault_injection::FaultInjector;
+    use crate::starcoin_bridge_transaction_builder::{
+        gas_unit_price_with_multiplier, StarcoinBridgeTransactionBuilder, MAX_GAS_AMOUNT,
+    };
+    use crate::starcoin_mock_node::StarcoinMockNode;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use starcoin_bridge_types::bridge::{BridgeLimiterSummary, MoveTypeBridgeTransferRecord};
+    use starcoin_bridge_types::crypto::{get_key_pair, StarcoinKeyPair};
+
+    // What the tests below need of a mock chain, so they run both against
+    // `StarcoinMockClient` and, through the JSON-RPC client, `StarcoinMockNode`
+    trait MockChain {
+        type Inner: StarcoinClientInner;
+
+        fn client(&self) -> StarcoinClient<Self::Inner>;
+        fn add_events_by_tx_digest(&self, tx_digest: TransactionDigest, events: Vec<StarcoinEvent>);
+        fn set_paused_token_ids(&self, token_ids: Vec<u8>);
+        fn set_limiter(&self, limiter: BridgeLimiterSummary);
+    }
+
+    impl MockChain for StarcoinMockClient {
+        type Inner = StarcoinMockClient;
+
+        fn client(&self) -> StarcoinClient<StarcoinMockClient> {
+            StarcoinClient::new_for_testing(self.clone())
+        }
+
+        fn add_events_by_tx_digest(
+            &self,
+            tx_digest: TransactionDigest,
+            events: Vec<StarcoinEvent>,
+        ) {
+            StarcoinMockClient::add_events_by_tx_digest(self, tx_digest, events)
+        }
+
+        fn set_paused_token_ids(&self, token_ids: Vec<u8>) {
+            StarcoinMockClient::set_paused_token_ids(self, token_ids)
+        }
+
+        fn set_limiter(&self, limiter: BridgeLimiterSummary) {
+            StarcoinMockClient::set_limiter(self, limiter)
+        }
+    }
+
+    impl MockChain for StarcoinMockNode {
+        type Inner = StarcoinJsonRpcClient;
+
+        fn client(&self) -> StarcoinBridgeClient {
+            StarcoinClient::new_for_testing(StarcoinJsonRpcClient::new(
+                self.url(),
+                self.bridge_address(),
+            ))
+        }
+
+        fn add_events_by_tx_digest(
+            &self,
+            tx_digest: TransactionDigest,
+            events: Vec<StarcoinEvent>,
+        ) {
+            StarcoinMockNode::add_events_by_tx_digest(self, tx_digest, events)
+        }
+
+        fn set_paused_token_ids(&self, token_ids: Vec<u8>) {
+            StarcoinMockNode::set_paused_token_ids(self, token_ids)
+        }
+
+        fn set_limiter(&self, limiter: BridgeLimiterSummary) {
+            StarcoinMockNode::set_limiter(self, limiter)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_describe_checks_chain_id() {
+        let client = StarcoinClient::new_for_testing(
+            StarcoinMockClient::default().with_chain_identifier("254"),
+        );
+        client
+            .describe(BridgeChainId::StarcoinCustom)
+            .await
+            .unwrap();
+        let err = client
+            .describe(BridgeChainId::StarcoinMainnet)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("StarcoinMainnet"), "{err}");
+
+        let client = StarcoinClient::new_for_testing(
+            StarcoinMockClient::default().with_chain_identifier("251"),
+        );
+        client
+            .describe(BridgeChainId::StarcoinTestnet)
+            .await
+            .unwrap();
+        assert!(client
+            .describe(BridgeChainId::StarcoinCustom)
+            .await
+            .is_err());
+
+        let client = StarcoinClient::new_for_testing(
+            StarcoinMockClient::default().with_chain_identifier("starcoin-dev"),
+        );
+        assert!(client
+            .describe(BridgeChainId::StarcoinCustom)
+            .await
+            .is_err());
+    }
 
     #[tokio::test]
     async fn get_bridge_action_by_tx_digest_and_event_idx_maybe() {
+        telemetry_subscribers::init_for_testing();
+        check_get_bridge_action_by_tx_digest_and_event_idx_maybe(StarcoinMockClient::default())
+            .await;
+        check_get_bridge_action_by_tx_digest_and_event_idx_maybe(StarcoinMockNode::start().await)
+            .await;
+    }
+
+    async fn check_get_bridge_action_by_tx_digest_and_event_idx_maybe(mock_client: impl MockChain) {
         // Note: for random events generated in this test, we only care about
         // tx_digest and event_seq, so it's ok that package and module does
         // not match the query parameters.
-        telemetry_subscribers::init_for_testing();
-        let mock_client = StarcoinMockClient::default();
-        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let starcoin_bridge_client = mock_client.client();
         let tx_digest = TransactionDigest::random();
 
         // Ensure all struct tags are inited
@@ -1102,6 +1997,886 @@ mod tests {
             .await
             .unwrap_err();
     }
+
+    #[tokio::test]
+    async fn test_ensure_token_not_paused() {
+        check_ensure_token_not_paused(StarcoinMockClient::default()).await;
+        check_ensure_token_not_paused(StarcoinMockNode::start().await).await;
+    }
+
+    async fn check_ensure_token_not_paused(mock_client: impl MockChain) {
+        let starcoin_bridge_client = mock_client.client();
+
+        starcoin_bridge_client
+            .ensure_token_not_paused(TOKEN_ID_USDC)
+            .await
+            .unwrap();
+
+        mock_client.set_paused_token_ids(vec![TOKEN_ID_USDC]);
+        assert!(starcoin_bridge_client
+            .is_token_paused(TOKEN_ID_USDC)
+            .await
+            .unwrap());
+        assert_eq!(
+            starcoin_bridge_client
+                .ensure_token_not_paused(TOKEN_ID_USDC)
+                .await
+                .unwrap_err(),
+            BridgeError::TokenPaused(TOKEN_ID_USDC)
+        );
+        // Other tokens keep bridging
+        starcoin_bridge_client
+            .ensure_token_not_paused(TOKEN_ID_STARCOIN)
+            .await
+            .unwrap();
+        // The global flag is independent of per-token pauses
+        assert!(!starcoin_bridge_client.is_bridge_paused().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_remaining_transfer_budget() {
+        check_get_remaining_transfer_budget(StarcoinMockClient::default()).await;
+        check_get_remaining_transfer_budget(StarcoinMockNode::start().await).await;
+    }
+
+    async fn check_get_remaining_transfer_budget(mock_client: impl MockChain) {
+        let starcoin_bridge_client = mock_client.client();
+        let route = (BridgeChainId::EthCustom, BridgeChainId::StarcoinCustom);
+        // Both mocks are at the current time
+        let hour = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            / 3600;
+        mock_client.set_limiter(BridgeLimiterSummary {
+            transfer_limit: vec![(route.0, route.1, 1_000)],
+            // 300 a day ago, past the window, then 200 an hour ago
+            transfer_records: vec![(
+                route.0,
+                route.1,
+                MoveTypeBridgeTransferRecord::new(
+                    hour - 1,
+                    hour - 24,
+                    [vec![300], vec![0; 22], vec![200]].concat(),
+                    500,
+                ),
+            )],
+        });
+
+        assert_eq!(
+            starcoin_bridge_client
+                .get_remaining_transfer_budget(route.0, route.1)
+                .await
+                .unwrap(),
+            800
+        );
+        // No limit, so the limiter would refuse the transfer
+        starcoin_bridge_client
+            .get_remaining_transfer_budget(route.1, route.0)
+            .await
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_events_from_rpc() {
+        use move_core_types::language_storage::StructTag;
+
+        telemetry_subscribers::init_for_testing();
+        let tx_digest = TransactionDigest::random();
+        // Response of `chain.get_events_by_txn_hash` for a deposit, which also
+        // emits framework events
+        let raw_events: Vec<serde_json::Value> = serde_json::from_value(serde_json::json!([
+            {
+                "block_number": "42",
+                "event_seq_number": "0",
+                "type_tag": "0x00000000000000000000000000000001::Account::WithdrawEvent",
+                "data": "0x0a00000000000000",
+            },
+            {
+                "block_number": "42",
+                "event_seq_number": "1",
+                "type_tag": "0x0b8e0206e990e41e913a7f03d1c60675::Bridge::TokenDepositedEvent",
+                "data": "0x0102",
+            },
+            {
+                "block_number": "42",
+                "event_seq_number": "2",
+                "type_tag": "not a type tag",
+                "data": "0x",
+            },
+            {
+                "block_number": 42,
+                "event_seq_number": "3",
+                "type_tag": "0x0b8e0206e990e41e913a7f03d1c60675::Committee::BlocklistValidatorEvent",
+                "data": "0x03",
+            },
+        ]))
+        .unwrap();
+
+        let events = events_from_rpc(&raw_events, tx_digest);
+        let struct_tag = |s: &str| StructTag::from_str(s).unwrap();
+        // Non-bridge events are kept for the caller to filter, the event
+        // with an invalid type tag is skipped
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.type_.clone(), e.id.event_seq, e.bcs.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    struct_tag("0x00000000000000000000000000000001::Account::WithdrawEvent"),
+                    0,
+                    vec![10, 0, 0, 0, 0, 0, 0, 0]
+                ),
+                (
+                    struct_tag("0x0b8e0206e990e41e913a7f03d1c60675::Bridge::TokenDepositedEvent"),
+                    1,
+                    vec![1, 2]
+                ),
+                (
+                    struct_tag(
+                        "0x0b8e0206e990e41e913a7f03d1c60675::Committee::BlocklistValidatorEvent"
+                    ),
+                    3,
+                    vec![3]
+                ),
+            ]
+        );
+        assert!(events
+            .iter()
+            .all(|e| e.id.tx_digest == tx_digest && e.id.block_number == 42));
+        assert!(events_from_rpc(&[], tx_digest).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_summary_cache() {
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone())
+            .with_bridge_summary_ttl(Duration::from_secs(60));
+
+        // Every lookup built on the summary shares one fetch within the ttl
+        for _ in 0..3 {
+            starcoin_bridge_client.get_bridge_summary().await.unwrap();
+            starcoin_bridge_client.get_token_id_map().await.unwrap();
+            starcoin_bridge_client.get_notional_values().await.unwrap();
+            starcoin_bridge_client.is_bridge_paused().await.unwrap();
+            starcoin_bridge_client.get_treasury_summary().await.unwrap();
+        }
+        assert_eq!(mock_client.bridge_summary_calls(), 1);
+
+        // Until invalidated, changes on chain are not seen
+        mock_client.set_paused_token_ids(vec![TOKEN_ID_USDC]);
+        assert!(!starcoin_bridge_client
+            .is_token_paused(TOKEN_ID_USDC)
+            .await
+            .unwrap());
+        starcoin_bridge_client
+            .invalidate_bridge_summary_cache()
+            .await;
+        assert!(starcoin_bridge_client
+            .is_token_paused(TOKEN_ID_USDC)
+            .await
+            .unwrap());
+        assert_eq!(mock_client.bridge_summary_calls(), 2);
+
+        // Concurrent misses share a single fetch
+        starcoin_bridge_client
+            .invalidate_bridge_summary_cache()
+            .await;
+        futures::future::join_all((0..10).map(|_| starcoin_bridge_client.get_bridge_summary()))
+            .await;
+        assert_eq!(mock_client.bridge_summary_calls(), 3);
+
+        // Errors are not cached
+        let failing_client = StarcoinMockClient::default();
+        failing_client.set_fault_injector(
+            FaultInjector::builder(1)
+                .failure_rate("get_bridge_summary", 1.0)
+                .build(),
+        );
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(failing_client.clone())
+            .with_bridge_summary_ttl(Duration::from_secs(60));
+        starcoin_bridge_client
+            .get_bridge_summary()
+            .await
+            .unwrap_err();
+        starcoin_bridge_client
+            .get_bridge_summary()
+            .await
+            .unwrap_err();
+        assert_eq!(failing_client.bridge_summary_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_summary_cache_expires() {
+        let mock_client = StarcoinMockClient::default();
+        // The token id map outlives the summary it was built from
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone())
+            .with_bridge_summary_ttl(Duration::from_millis(200))
+            .with_token_id_map_ttl(Duration::from_secs(60));
+
+        starcoin_bridge_client.get_bridge_summary().await.unwrap();
+        starcoin_bridge_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_calls(), 1);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        starcoin_bridge_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_calls(), 1);
+        starcoin_bridge_client.get_bridge_summary().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_calls(), 2);
+
+        // Without a ttl every lookup fetches
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        starcoin_bridge_client.get_bridge_summary().await.unwrap();
+        starcoin_bridge_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gas_unit_price() {
+        // The mock node's reference gas price is 1000
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        assert_eq!(starcoin_bridge_client.get_gas_unit_price().await, 1000);
+        let starcoin_bridge_client = starcoin_bridge_client.with_gas_price_multiplier(1.1);
+        assert_eq!(starcoin_bridge_client.get_gas_unit_price().await, 1100);
+
+        // Falls back to the default price if the node can't be asked
+        mock_client.set_fault_injector(
+            FaultInjector::builder(1)
+                .failure_rate("get_reference_gas_price", 1.0)
+                .build(),
+        );
+        assert_eq!(
+            starcoin_bridge_client.get_gas_unit_price().await,
+            gas_unit_price_with_multiplier(DEFAULT_GAS_UNIT_PRICE, 1.1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_reference_gas_price_until_success() {
+        let mock_client = StarcoinMockClient::default();
+        let faults = FaultInjector::builder(1)
+            .fail_first("get_reference_gas_price", 2)
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        assert_eq!(
+            starcoin_bridge_client
+                .get_reference_gas_price_until_success(None)
+                .await
+                .unwrap(),
+            1000
+        );
+        assert_eq!(faults.calls("get_reference_gas_price"), 3);
+
+        // Gives up after `max_attempts` reads
+        let faults = FaultInjector::builder(1)
+            .failure_rate("get_reference_gas_price", 1.0)
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+        starcoin_bridge_client
+            .get_reference_gas_price_until_success(Some(3))
+            .await
+            .unwrap_err();
+        assert_eq!(faults.calls("get_reference_gas_price"), 3);
+
+        // Gives up on the first error that retrying won't fix
+        let faults = FaultInjector::builder(1)
+            .failure_rate("get_reference_gas_price", 1.0)
+            .permanent("get_reference_gas_price")
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+        let err = starcoin_bridge_client
+            .get_reference_gas_price_until_success(None)
+            .await
+            .unwrap_err();
+        assert!(!err.is_retryable(), "{:?}", err);
+        assert_eq!(faults.calls("get_reference_gas_price"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_status_until_success_stops_at_permanent_error() {
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        starcoin_bridge_client
+            .get_mutable_bridge_object_arg(Deadline::none())
+            .await
+            .unwrap();
+        let faults = FaultInjector::builder(3)
+            .failure_rate("get_token_transfer_action_onchain_status", 1.0)
+            .permanent("get_token_transfer_action_onchain_status")
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+
+        let err = starcoin_bridge_client
+            .get_token_transfer_action_onchain_status_until_success(0, 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::Permanent(_)), "{:?}", err);
+        assert_eq!(faults.calls("get_token_transfer_action_onchain_status"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_signatures_until_success_stops_at_permanent_error() {
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        starcoin_bridge_client
+            .get_mutable_bridge_object_arg(Deadline::none())
+            .await
+            .unwrap();
+        let faults = FaultInjector::builder(3)
+            .failure_rate("get_token_transfer_action_onchain_signatures", 1.0)
+            .permanent("get_token_transfer_action_onchain_signatures")
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+
+        let err = starcoin_bridge_client
+            .get_token_transfer_action_onchain_signatures_until_success(0, 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::Permanent(_)), "{:?}", err);
+        assert_eq!(
+            faults.calls("get_token_transfer_action_onchain_signatures"),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_events_by_module_paginated() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let mut package = [0u8; 32];
+        package[16..].fill(1);
+        let bridge = Identifier::from_str("Bridge").unwrap();
+        let event = |module: &str, block_number: u64| {
+            let mut event = StarcoinEvent::random_for_testing();
+            event.type_.module = Identifier::from_str(module).unwrap();
+            event.id.block_number = block_number;
+            event.id.event_seq = 0;
+            event
+        };
+        // Bridge events interleaved with other modules' events, one page
+        // holding none of them
+        let pages = [
+            (
+                (10, 0),
+                vec![event("Committee", 11), event("Bridge", 12)],
+                (13, 0),
+                true,
+            ),
+            (
+                (13, 0),
+                vec![event("Committee", 14), event("Limiter", 15)],
+                (15, 0),
+                true,
+            ),
+            (
+                (15, 0),
+                vec![event("Bridge", 17), event("Committee", 16)],
+                (17, 0),
+                true,
+            ),
+            ((17, 0), vec![event("Bridge", 18)], (18, 0), false),
+        ];
+        for (cursor, data, next_cursor, has_next_page) in pages {
+            mock_client.add_event_response(
+                package,
+                bridge.clone(),
+                cursor,
+                EventPage {
+                    data,
+                    next_cursor: Some(EventCursor::new(next_cursor, &EventFilter::default())),
+                    has_next_page,
+                },
+            );
+        }
+        let blocks = |page: &ModuleEventPage| {
+            page.data
+                .iter()
+                .map(|event| event.id.block_number)
+                .collect::<Vec<_>>()
+        };
+
+        let page = starcoin_bridge_client
+            .query_events_by_module_paginated(package, bridge.clone(), Some((10, 0)), 10)
+            .await
+            .unwrap();
+        assert_eq!(blocks(&page), vec![12, 17, 18]);
+        assert_eq!(page.next_cursor, Some((18, 0)));
+        assert!(!page.has_next_page);
+
+        // Stops at the limit, and resuming from the cursor skips nothing
+        let page = starcoin_bridge_client
+            .query_events_by_module_paginated(package, bridge.clone(), Some((10, 0)), 2)
+            .await
+            .unwrap();
+        assert_eq!(blocks(&page), vec![12, 17]);
+        assert_eq!(page.next_cursor, Some((17, 0)));
+        assert!(page.has_next_page);
+        let page = starcoin_bridge_client
+            .query_events_by_module_paginated(package, bridge.clone(), page.next_cursor, 2)
+            .await
+            .unwrap();
+        assert_eq!(blocks(&page), vec![18]);
+
+        // A page emptied by the filter still moves the cursor
+        let page = starcoin_bridge_client
+            .query_events_by_module_paginated(package, bridge.clone(), Some((13, 0)), 1)
+            .await
+            .unwrap();
+        assert_eq!(blocks(&page), vec![17]);
+        assert_eq!(page.next_cursor, Some((17, 0)));
+
+        // Nothing new keeps the cursor
+        let page = starcoin_bridge_client
+            .query_events_by_module_paginated(package, bridge, Some((18, 0)), 10)
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+        assert_eq!(page.next_cursor, Some((18, 0)));
+
+        // The cursor persists as plain JSON
+        let json = serde_json::to_string(&page.next_cursor).unwrap();
+        assert_eq!(json, "[18,0]");
+        assert_eq!(
+            serde_json::from_str::<Option<EventID>>(&json).unwrap(),
+            page.next_cursor
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_events_by_module_exact_module_names() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let mut package = [0u8; 32];
+        package[16..].fill(2);
+        let bridge_address = StarcoinAddress::new([2u8; 16]);
+        let bridge = Identifier::from_str("bridge").unwrap();
+        let event = |address: StarcoinAddress, module: &str, block_number: u64| {
+            let mut event = StarcoinEvent::random_for_testing();
+            event.type_.address = address;
+            event.type_.module = Identifier::from_str(module).unwrap();
+            event.id.block_number = block_number;
+            event
+        };
+        mock_client.add_event_response(
+            package,
+            bridge.clone(),
+            (10, 0),
+            EventPage {
+                data: vec![
+                    event(bridge_address, "Bridge", 11),
+                    event(bridge_address, "bridgeX", 12),
+                    event(bridge_address, "BRIDGE", 13),
+                    event(bridge_address, "bridge", 14),
+                    event(bridge_address, "Committee", 15),
+                    event(StarcoinAddress::new([3u8; 16]), "Other", 16),
+                ],
+                next_cursor: Some(EventCursor::new((16, 0), &EventFilter::default())),
+                has_next_page: false,
+            },
+        );
+
+        let page = starcoin_bridge_client
+            .query_events_by_module(package, bridge.clone(), Some((10, 0)))
+            .await
+            .unwrap();
+        let blocks = page
+            .data
+            .iter()
+            .map(|event| event.id.block_number)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![11]);
+
+        // Events of the bridge package from unknown modules are counted,
+        // those of other packages are not
+        let unmapped = |module: &str| {
+            starcoin_bridge_client
+                .bridge_metrics
+                .starcoin_bridge_unmapped_module_events
+                .with_label_values(&[module])
+                .get()
+        };
+        for module in ["bridgeX", "BRIDGE", "bridge"] {
+            assert_eq!(unmapped(module), 1, "{module}");
+        }
+        assert_eq!(unmapped("Bridge"), 0);
+        assert_eq!(unmapped("Committee"), 0);
+        assert_eq!(unmapped("Other"), 0);
+
+        // Package ids must be Starcoin addresses
+        starcoin_bridge_client
+            .query_events_by_module([1u8; 32], bridge, Some((10, 0)))
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_query_events_checks_cursor_filter() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let mut package = [0u8; 32];
+        package[31] = 1;
+        let bridge_filter = EventFilter::move_event_type("0x01::bridge::Event");
+        let committee_filter = EventFilter::move_event_type("0x01::committee::Event");
+        let next_cursor = EventCursor::new((20, 0), &bridge_filter);
+        mock_client.add_event_response(
+            package,
+            Identifier::from_str("bridge").unwrap(),
+            (10, 0),
+            EventPage {
+                data: vec![],
+                next_cursor: Some(next_cursor),
+                has_next_page: false,
+            },
+        );
+
+        let cursor = EventCursor::new((10, 0), &bridge_filter);
+        let page = starcoin_bridge_client
+            .query_events(bridge_filter.clone(), Some(cursor))
+            .await
+            .unwrap();
+        assert_eq!(page.next_cursor, Some(next_cursor));
+
+        // A cursor from one filter is rejected for another
+        let err = starcoin_bridge_client
+            .query_events(committee_filter, Some(cursor))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::CursorFilterMismatch(_)));
+
+        // A persisted cursor without a fingerprint is accepted and the page
+        // comes back with a cursor in the current format
+        let legacy: EventCursor = "10:0".parse().unwrap();
+        let page = starcoin_bridge_client
+            .query_events(bridge_filter, Some(legacy))
+            .await
+            .unwrap();
+        let reissued = page.next_cursor.unwrap();
+        assert!(!reissued.is_legacy());
+        assert!(reissued.to_string().starts_with("2:"));
+    }
+
+    fn test_claim_txn(
+        key: &StarcoinKeyPair,
+        sequence_number: u64,
+    ) -> starcoin_bridge_types::transaction::RawUserTransaction {
+        let sender = key.starcoin_address();
+        StarcoinBridgeTransactionBuilder::build_claim_and_transfer(
+            sender,
+            sender,
+            sequence_number,
+            254,
+            0,
+            DEFAULT_GAS_UNIT_PRICE,
+            MAX_GAS_AMOUNT,
+            0,
+            BridgeChainId::EthCustom as u8,
+            1,
+            TOKEN_ID_USDC,
+            &HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_submit_and_wait_under_faults() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let faults = FaultInjector::builder(3)
+            .latency(
+                "sign_and_submit_transaction",
+                Duration::from_millis(10),
+                Duration::from_millis(200),
+            )
+            .fail_first("get_sequence_number", 3)
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Secp256k1(kp);
+
+        // Confirmation polling rides out failed sequence number reads
+        starcoin_bridge_client
+            .sign_and_submit_and_wait_transaction(&key, test_claim_txn(&key, 0))
+            .await
+            .unwrap();
+        assert_eq!(faults.calls("get_sequence_number"), 4);
+
+        // A rejected submission fails fast, without polling for confirmation
+        mock_client.add_sign_and_submit_response(Err(BridgeError::Generic("rejected".to_string())));
+        starcoin_bridge_client
+            .sign_and_submit_and_wait_transaction(&key, test_claim_txn(&key, 1))
+            .await
+            .unwrap_err();
+        assert_eq!(faults.calls("get_sequence_number"), 4);
+
+        // Nothing was submitted twice
+        let submitted = mock_client.submitted_transactions();
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(submitted[0].sequence_number(), 0);
+    }
+
+    const TEST_TXN_HASH: &str =
+        "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+    #[tokio::test]
+    async fn test_sign_and_submit_and_wait_checks_execution_status() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Secp256k1(kp);
+
+        mock_client.set_transaction_info(
+            TEST_TXN_HASH,
+            Some(serde_json::json!({
+                "status": "Executed",
+                "block_number": "42",
+                "gas_used": 1234,
+            })),
+        );
+        let receipt = starcoin_bridge_client
+            .sign_and_submit_and_wait_transaction(&key, test_claim_txn(&key, 0))
+            .await
+            .unwrap();
+        assert_eq!(
+            receipt,
+            StarcoinTxReceipt {
+                txn_hash: TEST_TXN_HASH.to_string(),
+                status: "Executed".to_string(),
+                block_number: Some(42),
+                gas_used: Some(1234),
+            }
+        );
+
+        // An aborted transaction bumps the sequence number too
+        mock_client.set_transaction_info(
+            TEST_TXN_HASH,
+            Some(serde_json::json!({
+                "status": {"MoveAbort": {"location": "Bridge", "abort_code": "7"}},
+                "gas_used": "100",
+            })),
+        );
+        let err = starcoin_bridge_client
+            .sign_and_submit_and_wait_transaction(&key, test_claim_txn(&key, 1))
+            .await
+            .unwrap_err();
+        let BridgeError::StarcoinTxFailure {
+            txn_hash,
+            status,
+            vm_error,
+        } = err
+        else {
+            panic!("Expected a StarcoinTxFailure");
+        };
+        assert_eq!(txn_hash, TEST_TXN_HASH);
+        assert_eq!(status, "MoveAbort");
+        let vm_error: serde_json::Value = serde_json::from_str(&vm_error.unwrap()).unwrap();
+        assert_eq!(vm_error["abort_code"], "7");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transaction_timeout() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let sender = "0x1";
+        let poll_interval = Duration::from_millis(1);
+
+        // The sequence number never advances
+        let err = starcoin_bridge_client
+            .wait_for_transaction(TEST_TXN_HASH, sender, 1, poll_interval, 5)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, BridgeError::InternalError(ref msg) if msg.contains("not confirmed"))
+        );
+
+        // The sequence number advances, but through another transaction
+        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Secp256k1(kp);
+        starcoin_bridge_client
+            .sign_and_submit_transaction(&key, test_claim_txn(&key, 0))
+            .await
+            .unwrap();
+        let other_hash = "0x01";
+        mock_client.set_transaction_info(other_hash, None);
+        let err = starcoin_bridge_client
+            .wait_for_transaction(other_hash, sender, 1, poll_interval, 60)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, BridgeError::InternalError(ref msg) if msg.contains("used by another transaction"))
+        );
+    }
+
+    async fn submit_test_claim(
+        starcoin_bridge_client: &StarcoinClient<StarcoinMockClient>,
+        key: &StarcoinKeyPair,
+    ) -> BridgeResult<ManagedSubmission> {
+        starcoin_bridge_client
+            .submit_with_managed_sequence(key, |sequence_number| {
+                Ok(test_claim_txn(key, sequence_number))
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submissions_take_consecutive_sequence_numbers() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        // Slow reads and submissions, so that unmanaged submissions would all
+        // read the same sequence number
+        mock_client.set_fault_injector(
+            FaultInjector::builder(5)
+                .latency(
+                    "get_sequence_number",
+                    Duration::from_millis(1),
+                    Duration::from_millis(20),
+                )
+                .latency(
+                    "sign_and_submit_transaction",
+                    Duration::from_millis(1),
+                    Duration::from_millis(20),
+                )
+                .build(),
+        );
+        mock_client.set_sequence_number(3);
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Secp256k1(kp);
+
+        let (client, key) = (&starcoin_bridge_client, &key);
+        let submissions =
+            futures::future::join_all((0..5).map(move |_| submit_test_claim(client, key))).await;
+        let mut sequence_numbers = submissions
+            .into_iter()
+            .map(|submission| submission.unwrap().sequence_number)
+            .collect::<Vec<_>>();
+        sequence_numbers.sort();
+        assert_eq!(sequence_numbers, vec![3, 4, 5, 6, 7]);
+        // The node got them in order
+        let submitted = mock_client
+            .submitted_transactions()
+            .iter()
+            .map(|txn| txn.sequence_number())
+            .collect::<Vec<_>>();
+        assert_eq!(submitted, vec![3, 4, 5, 6, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_managed_sequence_after_failures() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let faults = FaultInjector::builder(6).build();
+        mock_client.set_fault_injector(faults.clone());
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Secp256k1(kp);
+        let submit = || submit_test_claim(&starcoin_bridge_client, &key);
+
+        assert_eq!(submit().await.unwrap().sequence_number, 0);
+        // Another client of the account took 1 and 2, the stale submission is
+        // retried with the sequence number on chain
+        mock_client.set_sequence_number(3);
+        assert_eq!(submit().await.unwrap().sequence_number, 3);
+
+        // A rejected submission gives its sequence number back
+        mock_client.add_sign_and_submit_response(Err(BridgeError::Generic("rejected".to_string())));
+        submit().await.unwrap_err();
+        assert_eq!(submit().await.unwrap().sequence_number, 4);
+        // So does a transaction that fails to build
+        starcoin_bridge_client
+            .submit_with_managed_sequence(&key, |_| {
+                Err(BridgeError::Generic("bad payload".to_string()))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(submit().await.unwrap().sequence_number, 5);
+
+        // Stale rejections are retried a bounded number of times
+        mock_client.set_wildcard_sign_and_submit_response(Err(BridgeError::Generic(
+            "SEQUENCE_NUMBER_TOO_OLD".to_string(),
+        )));
+        let calls = faults.calls("sign_and_submit_transaction");
+        let err = submit().await.unwrap_err();
+        assert!(is_stale_sequence_error(&err), "{err:?}");
+        assert_eq!(
+            faults.calls("sign_and_submit_transaction") - calls,
+            1 + MAX_STALE_SEQUENCE_RETRIES as u64
+        );
+
+        let submitted = mock_client
+            .submitted_transactions()
+            .iter()
+            .map(|txn| txn.sequence_number())
+            .collect::<Vec<_>>();
+        assert_eq!(submitted, vec![0, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_nested_calls_respect_deadline() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = StarcoinMockClient::default();
+        let starcoin_bridge_client = StarcoinClient::new_for_testing(mock_client.clone());
+        // Warm up the cached bridge object arg
+        starcoin_bridge_client
+            .get_mutable_bridge_object_arg(Deadline::none())
+            .await
+            .unwrap();
+        let tolerance = Duration::from_millis(500);
+
+        // Without a deadline the status lookup would retry forever
+        let faults = FaultInjector::builder(7)
+            .failure_rate("get_token_transfer_action_onchain_status", 1.0)
+            .build();
+        mock_client.set_fault_injector(faults.clone());
+        let timeout = Duration::from_secs(2);
+        let instant = std::time::Instant::now();
+        let err = starcoin_bridge_client
+            .get_token_transfer_action_onchain_status(0, 1, Deadline::after(timeout))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BridgeError::DeadlineExceeded("get_token_transfer_action_onchain_status".to_string())
+        );
+        assert!(instant.elapsed() < timeout + tolerance);
+        assert!(faults.calls("get_token_transfer_action_onchain_status") > 1);
+
+        // A slow call is cut off, and later steps sharing the deadline fail fast
+        let faults = FaultInjector::builder(7)
+            .latency(
+                "get_reference_gas_price",
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+            )
+            .build();
+        mock_client.set_fault_injector(faults);
+        let timeout = Duration::from_secs(1);
+        let deadline = Deadline::after(timeout);
+        let instant = std::time::Instant::now();
+        let err = starcoin_bridge_client
+            .get_reference_gas_price(deadline)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BridgeError::DeadlineExceeded("get_reference_gas_price".to_string())
+        );
+        let err = starcoin_bridge_client
+            .get_parsed_token_transfer_message(0, 1, deadline)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::DeadlineExceeded(_)));
+        assert!(instant.elapsed() < timeout + tolerance);
+    }
 }
 
 // E2E tests that require real Starcoin environment - use external deployed node
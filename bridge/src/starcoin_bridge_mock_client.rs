@@ -4,23 +4,25 @@
 //! A mock implementation of Starcoin JSON-RPC client.
 
 use crate::error::{BridgeError, BridgeResult};
+use crate::fault_injection::FaultInjector;
 use async_trait::async_trait;
 use starcoin_bridge_json_rpc_types::StarcoinTransactionBlockResponse;
-use starcoin_bridge_json_rpc_types::{EventFilter, EventPage, StarcoinEvent};
+use starcoin_bridge_json_rpc_types::{EventCursor, EventFilter, EventPage, StarcoinEvent};
 use starcoin_bridge_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
 use starcoin_bridge_types::bridge::{
-    BridgeCommitteeSummary, BridgeSummary, MoveTypeParsedTokenTransferMessage,
+    BridgeCommitteeSummary, BridgeLimiterSummary, BridgeSummary, BridgeTreasurySummary,
+    MoveTypeParsedTokenTransferMessage,
 };
 use starcoin_bridge_types::event::EventID;
 use starcoin_bridge_types::gas_coin::GasCoin;
 use starcoin_bridge_types::object::Owner;
-use starcoin_bridge_types::transaction::{ObjectArg, Transaction};
+use starcoin_bridge_types::transaction::{ObjectArg, RawUserTransaction, Transaction};
 use starcoin_bridge_types::Identifier;
 use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
-use crate::starcoin_bridge_client::StarcoinClientInner;
+use crate::starcoin_bridge_client::{sdk_rpc_error, StarcoinClientInner};
 use crate::types::{BridgeAction, BridgeActionStatus, IsBridgePaused};
 
 // Dummy bridge object arg function
@@ -47,18 +49,33 @@ pub struct StarcoinMockClient {
             >,
         >,
     >,
-    transaction_responses:
-        Arc<Mutex<HashMap<TransactionDigest, BridgeResult<StarcoinTransactionBlockResponse>>>>,
+    // Legacy `Transaction`s carry no digest to key responses by, so every
+    // execution gets this one
     wildcard_transaction_response:
         Arc<Mutex<Option<BridgeResult<StarcoinTransactionBlockResponse>>>>,
     get_object_info: Arc<Mutex<HashMap<ObjectID, (GasCoin, ObjectRef, Owner)>>>,
     onchain_status: Arc<Mutex<HashMap<(u8, u64), BridgeActionStatus>>>,
+    // Records of token transfers, and the signatures of the approved ones
+    token_transfer_messages: Arc<Mutex<HashMap<(u8, u64), MoveTypeParsedTokenTransferMessage>>>,
+    onchain_signatures: Arc<Mutex<HashMap<(u8, u64), Vec<Vec<u8>>>>>,
     bridge_committee_summary: Arc<Mutex<Option<BridgeCommitteeSummary>>>,
     is_paused: Arc<Mutex<Option<IsBridgePaused>>>,
-    requested_transactions_tx: tokio::sync::broadcast::Sender<TransactionDigest>,
+    paused_token_ids: Arc<Mutex<Vec<u8>>>,
+    limiter: Arc<Mutex<BridgeLimiterSummary>>,
+    sequence_nums: Arc<Mutex<Vec<(u8, u64)>>>,
+    // Action type whose nonce every successful submission uses up
+    advancing_action_type: Arc<Mutex<Option<u8>>>,
+    bridge_summary_calls: Arc<AtomicU64>,
     // Mock for sign_and_submit_transaction
     sign_and_submit_responses: Arc<Mutex<VecDeque<BridgeResult<String>>>>,
     wildcard_sign_and_submit_response: Arc<Mutex<Option<BridgeResult<String>>>>,
+    // Account sequence number, advanced by every successful submission
+    sequence_number: Arc<AtomicU64>,
+    submitted_transactions: Arc<Mutex<Vec<RawUserTransaction>>>,
+    // Transaction info views by hash. Transactions not in the map executed.
+    transaction_infos: Arc<Mutex<HashMap<String, Option<serde_json::Value>>>>,
+    module_bytes: Arc<Mutex<HashMap<(String, String), Vec<u8>>>>,
+    fault_injector: Arc<Mutex<Option<Arc<FaultInjector>>>>,
 }
 
 impl StarcoinMockClient {
@@ -70,18 +87,96 @@ impl StarcoinMockClient {
             past_event_query_params: Default::default(),
             events_by_module: Default::default(),
             events_by_tx_digest: Default::default(),
-            transaction_responses: Default::default(),
             wildcard_transaction_response: Default::default(),
             get_object_info: Default::default(),
             onchain_status: Default::default(),
+            token_transfer_messages: Default::default(),
+            onchain_signatures: Default::default(),
             bridge_committee_summary: Default::default(),
             is_paused: Default::default(),
-            requested_transactions_tx: tokio::sync::broadcast::channel(10000).0,
+            paused_token_ids: Default::default(),
+            limiter: Default::default(),
+            sequence_nums: Default::default(),
+            advancing_action_type: Default::default(),
+            bridge_summary_calls: Arc::new(AtomicU64::new(0)),
             sign_and_submit_responses: Default::default(),
             wildcard_sign_and_submit_response: Default::default(),
+            sequence_number: Arc::new(AtomicU64::new(0)),
+            submitted_transactions: Default::default(),
+            transaction_infos: Default::default(),
+            module_bytes: Default::default(),
+            fault_injector: Default::default(),
+        }
+    }
+
+    pub fn with_chain_identifier(mut self, chain_identifier: &str) -> Self {
+        self.chain_identifier = chain_identifier.to_string();
+        self
+    }
+
+    pub fn set_module_bytes(&self, address: &str, module: &str, bytes: Vec<u8>) {
+        self.module_bytes
+            .lock()
+            .unwrap()
+            .insert((address.to_string(), module.to_string()), bytes);
+    }
+
+    // Makes subsequent calls subject to the injector's faults.
+    pub fn set_fault_injector(&self, injector: Arc<FaultInjector>) {
+        self.fault_injector.lock().unwrap().replace(injector);
+    }
+
+    // Transactions accepted by sign_and_submit_transaction, in submission order.
+    pub fn submitted_transactions(&self) -> Vec<RawUserTransaction> {
+        self.submitted_transactions.lock().unwrap().clone()
+    }
+
+    fn faults(&self) -> Option<Arc<FaultInjector>> {
+        self.fault_injector.lock().unwrap().clone()
+    }
+
+    async fn inject_fault(&self, method: &str) -> Result<(), String> {
+        match self.faults() {
+            Some(faults) => faults.on_call(method).await,
+            None => Ok(()),
         }
     }
 
+    async fn inject_sdk_fault(
+        &self,
+        method: &str,
+    ) -> Result<(), starcoin_bridge_sdk::error::Error> {
+        self.inject_fault(method).await.map_err(|e| {
+            if self.is_permanent_fault(method) {
+                starcoin_bridge_sdk::error::Error::ClientError(e)
+            } else {
+                starcoin_bridge_sdk::error::Error::StarcoinError(e)
+            }
+        })
+    }
+
+    async fn inject_bridge_fault(&self, method: &str) -> BridgeResult<()> {
+        self.inject_fault(method).await.map_err(|e| {
+            if self.is_permanent_fault(method) {
+                BridgeError::Permanent(e)
+            } else {
+                BridgeError::transient_rpc(e)
+            }
+        })
+    }
+
+    fn is_permanent_fault(&self, method: &str) -> bool {
+        self.faults()
+            .is_some_and(|faults| faults.is_permanent(method))
+    }
+
+    fn reorder_events(&self, mut page: EventPage) -> EventPage {
+        if let Some(faults) = self.faults() {
+            faults.reorder(&mut page.data);
+        }
+        page
+    }
+
     pub fn add_event_response(
         &self,
         package: ObjectID,
@@ -120,22 +215,43 @@ impl StarcoinMockClient {
         );
     }
 
-    pub fn add_transaction_response(
+    pub fn set_action_onchain_status(&self, action: &BridgeAction, status: BridgeActionStatus) {
+        self.set_token_transfer_onchain_status(
+            action.chain_id() as u8,
+            action.seq_number(),
+            status,
+        );
+    }
+
+    pub fn set_token_transfer_onchain_status(
         &self,
-        tx_digest: TransactionDigest,
-        response: BridgeResult<StarcoinTransactionBlockResponse>,
+        source_chain_id: u8,
+        seq_number: u64,
+        status: BridgeActionStatus,
     ) {
-        self.transaction_responses
+        self.onchain_status
             .lock()
             .unwrap()
-            .insert(tx_digest, response);
+            .insert((source_chain_id, seq_number), status);
     }
 
-    pub fn set_action_onchain_status(&self, action: &BridgeAction, status: BridgeActionStatus) {
-        self.onchain_status
+    pub fn set_token_transfer_message(&self, message: MoveTypeParsedTokenTransferMessage) {
+        self.token_transfer_messages
             .lock()
             .unwrap()
-            .insert((action.chain_id() as u8, action.seq_number()), status);
+            .insert((message.source_chain, message.seq_num), message);
+    }
+
+    pub fn set_token_transfer_onchain_signatures(
+        &self,
+        source_chain_id: u8,
+        seq_number: u64,
+        signatures: Vec<Vec<u8>>,
+    ) {
+        self.onchain_signatures
+            .lock()
+            .unwrap()
+            .insert((source_chain_id, seq_number), signatures);
     }
 
     pub fn set_bridge_committee(&self, committee: BridgeCommitteeSummary) {
@@ -149,6 +265,39 @@ impl StarcoinMockClient {
         self.is_paused.lock().unwrap().replace(value);
     }
 
+    pub fn set_paused_token_ids(&self, token_ids: Vec<u8>) {
+        *self.paused_token_ids.lock().unwrap() = token_ids;
+    }
+
+    pub fn set_limiter(&self, limiter: BridgeLimiterSummary) {
+        *self.limiter.lock().unwrap() = limiter;
+    }
+
+    // Next expected nonce per action type, as reported by the bridge summary.
+    pub fn set_sequence_nums(&self, sequence_nums: Vec<(u8, u64)>) {
+        *self.sequence_nums.lock().unwrap() = sequence_nums;
+    }
+
+    // Makes every successful submission use up the next nonce of
+    // `action_type`, as executing an action of that type on chain does
+    pub fn advance_sequence_num_on_submit(&self, action_type: u8) {
+        self.advancing_action_type
+            .lock()
+            .unwrap()
+            .replace(action_type);
+    }
+
+    // Moves the account sequence number, as a transaction submitted by
+    // another client of the account would
+    pub fn set_sequence_number(&self, value: u64) {
+        self.sequence_number.store(value, Ordering::Relaxed);
+    }
+
+    // Number of `get_bridge_summary` calls served so far
+    pub fn bridge_summary_calls(&self) -> u64 {
+        self.bridge_summary_calls.load(Ordering::Relaxed)
+    }
+
     pub fn set_wildcard_transaction_response(
         &self,
         response: BridgeResult<StarcoinTransactionBlockResponse>,
@@ -158,7 +307,7 @@ impl StarcoinMockClient {
 
     pub fn set_latest_checkpoint_sequence_number(&self, value: u64) {
         self.latest_checkpoint_sequence_number
-            .store(value, std::sync::atomic::Ordering::Relaxed);
+            .store(value, Ordering::Relaxed);
     }
 
     pub fn add_gas_object_info(&self, gas_coin: GasCoin, object_ref: ObjectRef, owner: Owner) {
@@ -168,12 +317,6 @@ impl StarcoinMockClient {
             .insert(object_ref.0, (gas_coin, object_ref, owner));
     }
 
-    pub fn subscribe_to_requested_transactions(
-        &self,
-    ) -> tokio::sync::broadcast::Receiver<TransactionDigest> {
-        self.requested_transactions_tx.subscribe()
-    }
-
     /// Add a response for sign_and_submit_transaction (will be consumed in order)
     pub fn add_sign_and_submit_response(&self, response: BridgeResult<String>) {
         self.sign_and_submit_responses
@@ -182,6 +325,15 @@ impl StarcoinMockClient {
             .push_back(response);
     }
 
+    /// Set the transaction info view returned for `txn_hash`, None when the
+    /// transaction is not on chain
+    pub fn set_transaction_info(&self, txn_hash: &str, txn_info: Option<serde_json::Value>) {
+        self.transaction_infos
+            .lock()
+            .unwrap()
+            .insert(txn_hash.to_string(), txn_info);
+    }
+
     /// Set a wildcard response for sign_and_submit_transaction (used when queue is empty)
     pub fn set_wildcard_sign_and_submit_response(&self, response: BridgeResult<String>) {
         *self.wildcard_sign_and_submit_response.lock().unwrap() = Some(response);
@@ -192,6 +344,10 @@ impl StarcoinMockClient {
 impl StarcoinClientInner for StarcoinMockClient {
     type Error = starcoin_bridge_sdk::error::Error;
 
+    fn rpc_error(error: Self::Error) -> BridgeError {
+        sdk_rpc_error(error)
+    }
+
     fn bridge_address(&self) -> &str {
         // Return the actual bridge address used in init_all_struct_tags()
         // This matches BRIDGE_PACKAGE_ID[16..32] = 0x0b8e0206e990e41e913a7f03d1c60675
@@ -203,8 +359,10 @@ impl StarcoinClientInner for StarcoinMockClient {
     async fn query_events(
         &self,
         query: EventFilter,
-        cursor: Option<EventID>,
+        cursor: Option<EventCursor>,
     ) -> Result<EventPage, Self::Error> {
+        self.inject_sdk_fault("query_events").await?;
+        let cursor = cursor.map(|c| c.position());
         let events = self.events.lock().unwrap();
 
         // EventFilter is now a struct with type_tags field
@@ -228,12 +386,13 @@ impl StarcoinClientInner for StarcoinMockClient {
                         .lock()
                         .unwrap()
                         .push_back(key.clone());
-                    return Ok(events.get(&key).cloned().unwrap_or_else(|| {
+                    let page = events.get(&key).cloned().unwrap_or_else(|| {
                         panic!(
                             "No preset events found for type_tag: {:?}, cursor: {:?}",
                             first_tag, cursor
                         )
-                    }));
+                    });
+                    return Ok(self.reorder_events(page));
                 }
             }
         }
@@ -252,26 +411,36 @@ impl StarcoinClientInner for StarcoinMockClient {
         module: Identifier,
         cursor: Option<EventID>,
     ) -> Result<EventPage, Self::Error> {
-        let events = self.events_by_module.lock().unwrap();
-        let key = (package, module.clone(), cursor);
+        self.inject_sdk_fault("query_events_by_module").await?;
+        let key = (package, module, cursor);
 
         self.past_event_query_params
             .lock()
             .unwrap()
             .push_back(key.clone());
 
-        // Return preset events if available, otherwise empty page
-        Ok(events.get(&key).cloned().unwrap_or_else(|| EventPage {
-            data: vec![],
-            next_cursor: None,
-            has_next_page: false,
-        }))
+        // Return preset events if available, otherwise empty page. Like the
+        // default implementation, events of other modules are left to
+        // `StarcoinClient` to drop.
+        let page = self
+            .events_by_module
+            .lock()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| EventPage {
+                data: vec![],
+                next_cursor: None,
+                has_next_page: false,
+            });
+        Ok(self.reorder_events(page))
     }
 
     async fn get_events_by_tx_digest(
         &self,
         tx_digest: TransactionDigest,
     ) -> Result<Vec<StarcoinEvent>, Self::Error> {
+        self.inject_sdk_fault("get_events_by_tx_digest").await?;
         let events = self.events_by_tx_digest.lock().unwrap();
 
         match events
@@ -287,13 +456,17 @@ impl StarcoinClientInner for StarcoinMockClient {
     }
 
     async fn get_chain_identifier(&self) -> Result<String, Self::Error> {
+        self.inject_sdk_fault("get_chain_identifier").await?;
         Ok(self.chain_identifier.clone())
     }
 
     async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, Self::Error> {
-        Ok(self
+        self.inject_sdk_fault("get_latest_checkpoint_sequence_number")
+            .await?;
+        let head = self
             .latest_checkpoint_sequence_number
-            .load(std::sync::atomic::Ordering::Relaxed))
+            .load(Ordering::Relaxed);
+        Ok(self.faults().map_or(head, |faults| faults.head(head)))
     }
 
     async fn get_mutable_bridge_object_arg(&self) -> Result<ObjectArg, Self::Error> {
@@ -301,25 +474,36 @@ impl StarcoinClientInner for StarcoinMockClient {
     }
 
     async fn get_reference_gas_price(&self) -> Result<u64, Self::Error> {
+        self.inject_sdk_fault("get_reference_gas_price").await?;
         Ok(1000)
     }
 
     async fn get_bridge_summary(&self) -> Result<BridgeSummary, Self::Error> {
+        self.bridge_summary_calls.fetch_add(1, Ordering::Relaxed);
+        self.inject_sdk_fault("get_bridge_summary").await?;
+        let committee = self
+            .bridge_committee_summary
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_default();
+        let committee = match self.faults() {
+            Some(faults) => faults.committee(committee),
+            None => committee,
+        };
         Ok(BridgeSummary {
             bridge_version: 0,
             message_version: 0,
             chain_id: 0,
-            sequence_nums: vec![],
+            sequence_nums: self.sequence_nums.lock().unwrap().clone(),
             bridge_records_id: [0u8; 32],
             is_frozen: self.is_paused.lock().unwrap().unwrap_or_default(),
-            limiter: Default::default(),
-            committee: self
-                .bridge_committee_summary
-                .lock()
-                .unwrap()
-                .clone()
-                .unwrap_or_default(),
-            treasury: Default::default(),
+            limiter: self.limiter.lock().unwrap().clone(),
+            committee,
+            treasury: BridgeTreasurySummary {
+                paused_token_ids: self.paused_token_ids.lock().unwrap().clone(),
+                ..Default::default()
+            },
         })
     }
 
@@ -329,6 +513,8 @@ impl StarcoinClientInner for StarcoinMockClient {
         source_chain_id: u8,
         seq_number: u64,
     ) -> Result<BridgeActionStatus, BridgeError> {
+        self.inject_bridge_fault("get_token_transfer_action_onchain_status")
+            .await?;
         Ok(self
             .onchain_status
             .lock()
@@ -341,42 +527,52 @@ impl StarcoinClientInner for StarcoinMockClient {
     async fn get_token_transfer_action_onchain_signatures(
         &self,
         _bridge_object_arg: ObjectArg,
-        _source_chain_id: u8,
-        _seq_number: u64,
+        source_chain_id: u8,
+        seq_number: u64,
     ) -> Result<Option<Vec<Vec<u8>>>, BridgeError> {
-        unimplemented!()
+        self.inject_bridge_fault("get_token_transfer_action_onchain_signatures")
+            .await?;
+        Ok(self
+            .onchain_signatures
+            .lock()
+            .unwrap()
+            .get(&(source_chain_id, seq_number))
+            .cloned())
     }
 
     async fn get_parsed_token_transfer_message(
         &self,
         _bridge_object_arg: ObjectArg,
-        _source_chain_id: u8,
-        _seq_number: u64,
+        source_chain_id: u8,
+        seq_number: u64,
     ) -> Result<Option<MoveTypeParsedTokenTransferMessage>, BridgeError> {
-        unimplemented!()
+        Ok(self
+            .token_transfer_messages
+            .lock()
+            .unwrap()
+            .get(&(source_chain_id, seq_number))
+            .cloned())
     }
 
     async fn execute_transaction_block_with_effects(
         &self,
         tx: Transaction,
     ) -> Result<StarcoinTransactionBlockResponse, BridgeError> {
-        self.requested_transactions_tx.send(*tx.digest()).unwrap();
-        match self.transaction_responses.lock().unwrap().get(tx.digest()) {
-            Some(response) => response.clone(),
-            None => self
-                .wildcard_transaction_response
-                .lock()
-                .unwrap()
-                .clone()
-                .unwrap_or_else(|| panic!("No preset transaction response found for tx: {:?}", tx)),
-        }
+        self.inject_bridge_fault("execute_transaction_block_with_effects")
+            .await?;
+        self.wildcard_transaction_response
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| panic!("No preset transaction response found for tx: {:?}", tx))
     }
 
     async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner) {
-        self.get_object_info
+    ) -> Result<(GasCoin, ObjectRef, Owner), BridgeError> {
+        Ok(self
+            .get_object_info
             .lock()
             .unwrap()
             .get(&gas_object_id)
@@ -386,15 +582,16 @@ impl StarcoinClientInner for StarcoinMockClient {
                     "No preset gas object info found for gas_object_id: {:?}",
                     gas_object_id
                 )
-            })
+            }))
     }
 
     async fn get_sequence_number(&self, _address: &str) -> Result<u64, BridgeError> {
-        // Mock implementation for testing
-        Ok(0)
+        self.inject_bridge_fault("get_sequence_number").await?;
+        Ok(self.sequence_number.load(Ordering::Relaxed))
     }
 
     async fn get_block_timestamp(&self) -> Result<u64, BridgeError> {
+        self.inject_bridge_fault("get_block_timestamp").await?;
         // Mock implementation: return current system time in milliseconds
         Ok(std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -405,22 +602,76 @@ impl StarcoinClientInner for StarcoinMockClient {
     async fn sign_and_submit_transaction(
         &self,
         _key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
-        _raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+        raw_txn: RawUserTransaction,
     ) -> Result<String, BridgeError> {
-        // Try to get a response from the queue first
-        if let Some(response) = self.sign_and_submit_responses.lock().unwrap().pop_front() {
-            return response;
+        self.inject_bridge_fault("sign_and_submit_transaction")
+            .await?;
+        // Like the node, reject sequence numbers the account already used
+        let sequence_number = self.sequence_number.load(Ordering::Relaxed);
+        if raw_txn.sequence_number() < sequence_number {
+            return Err(BridgeError::Generic(format!(
+                "SEQUENCE_NUMBER_TOO_OLD: {} < {}",
+                raw_txn.sequence_number(),
+                sequence_number
+            )));
+        }
+        // Try to get a response from the queue first, then fall back to
+        // the wildcard response if set
+        let queued = self.sign_and_submit_responses.lock().unwrap().pop_front();
+        let response = queued
+            .or_else(|| {
+                self.wildcard_sign_and_submit_response
+                    .lock()
+                    .unwrap()
+                    .clone()
+            })
+            // Default: return success with a dummy tx hash
+            .unwrap_or_else(|| {
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                )
+            });
+        if response.is_ok() {
+            self.sequence_number
+                .fetch_max(raw_txn.sequence_number() + 1, Ordering::Relaxed);
+            self.submitted_transactions.lock().unwrap().push(raw_txn);
+            if let Some(action_type) = *self.advancing_action_type.lock().unwrap() {
+                let mut sequence_nums = self.sequence_nums.lock().unwrap();
+                match sequence_nums.iter_mut().find(|(t, _)| *t == action_type) {
+                    Some((_, nonce)) => *nonce += 1,
+                    None => sequence_nums.push((action_type, 1)),
+                }
+            }
         }
-        // Fall back to wildcard response if set
-        if let Some(response) = self
-            .wildcard_sign_and_submit_response
+        response
+    }
+
+    async fn get_transaction_info(
+        &self,
+        txn_hash: &str,
+    ) -> Result<Option<serde_json::Value>, BridgeError> {
+        self.inject_bridge_fault("get_transaction_info").await?;
+        Ok(self
+            .transaction_infos
             .lock()
             .unwrap()
-            .clone()
-        {
-            return response;
-        }
-        // Default: return success with a dummy tx hash
-        Ok("0x0000000000000000000000000000000000000000000000000000000000000000".to_string())
+            .get(txn_hash)
+            .cloned()
+            .unwrap_or_else(|| Some(serde_json::json!({"status": "Executed"}))))
+    }
+
+    async fn get_module_bytes(
+        &self,
+        address: &str,
+        module: &str,
+    ) -> Result<Option<Vec<u8>>, BridgeError> {
+        self.inject_bridge_fault("get_module_bytes").await?;
+        Ok(self
+            .module_bytes
+            .lock()
+            .unwrap()
+            .get(&(address.to_string(), module.to_string()))
+            .cloned())
     }
 }
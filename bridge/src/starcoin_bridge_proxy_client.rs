@@ -7,12 +7,11 @@ use crate::starcoin_rpc_proxy_client::StarcoinRpcProxyClient;
 use async_trait::async_trait;
 use once_cell::sync::{Lazy, OnceCell};
 use starcoin_bridge_json_rpc_types::StarcoinTransactionBlockResponse;
-use starcoin_bridge_json_rpc_types::{EventFilter, EventPage, StarcoinEvent};
+use starcoin_bridge_json_rpc_types::{EventCursor, EventFilter, EventPage, StarcoinEvent};
 use starcoin_bridge_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
 use starcoin_bridge_types::bridge::{
     BridgeSummary, MoveTypeParsedTokenTransferMessage,
 };
-use starcoin_bridge_types::event::EventID;
 use starcoin_bridge_types::gas_coin::GasCoin;
 use starcoin_bridge_types::object::Owner;
 use starcoin_bridge_types::transaction::{ObjectArg, Transaction};
@@ -95,7 +94,7 @@ impl StarcoinClientInner for StarcoinProxyClient {
     async fn query_events(
         &self,
         _query: EventFilter,
-        _cursor: Option<EventID>,
+        _cursor: Option<EventCursor>,
     ) -> Result<EventPage, Self::Error> {
         // TODO: Add query_events to proxy protocol
         Ok(EventPage {
@@ -181,8 +180,8 @@ impl StarcoinClientInner for StarcoinProxyClient {
     async fn get_gas_data_panic_if_not_gas(
         &self,
         _gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner) {
+    ) -> Result<(GasCoin, ObjectRef, Owner), BridgeError> {
         // TODO: Add get_gas_data_panic_if_not_gas to proxy protocol
-        panic!("Not implemented in proxy client")
+        Err(BridgeError::Generic("Not implemented in proxy client".into()))
     }
 }
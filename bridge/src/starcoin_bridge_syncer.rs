@@ -23,6 +23,12 @@ use tokio::{
 
 const STARCOIN_EVENTS_CHANNEL_SIZE: usize = 1000;
 
+// Events collected per query before they are handed over
+const STARCOIN_EVENTS_QUERY_LIMIT: usize = 1000;
+
+// Events of a module, along with the cursor to resume after them
+pub type StarcoinModuleEvents = (Identifier, EventID, Vec<StarcoinEvent>);
+
 // Map from contract address to their start cursor (exclusive)
 pub type StarcoinTargetModules = HashMap<Identifier, Option<EventID>>;
 
@@ -59,7 +65,7 @@ where
         query_interval: Duration,
     ) -> BridgeResult<(
         Vec<JoinHandle<()>>,
-        starcoin_metrics::metered_channel::Receiver<(Identifier, Vec<StarcoinEvent>)>,
+        starcoin_metrics::metered_channel::Receiver<StarcoinModuleEvents>,
     )> {
         let (events_tx, events_rx) = starcoin_metrics::metered_channel::channel(
             STARCOIN_EVENTS_CHANNEL_SIZE,
@@ -73,10 +79,8 @@ where
         let mut task_handles = vec![];
         for (module, cursor) in self.cursors {
             let metrics = self.metrics.clone();
-            let events_rx_clone: starcoin_metrics::metered_channel::Sender<(
-                Identifier,
-                Vec<StarcoinEvent>,
-            )> = events_tx.clone();
+            let events_rx_clone: starcoin_metrics::metered_channel::Sender<StarcoinModuleEvents> =
+                events_tx.clone();
             let starcoin_bridge_client_clone = self.starcoin_bridge_client.clone();
             task_handles.push(spawn_logged_monitored_task!(
                 Self::run_event_listening_task(
@@ -99,7 +103,7 @@ where
         // The module where interested events are defined.
         module: Identifier,
         initial_cursor: Option<EventID>,
-        events_sender: starcoin_metrics::metered_channel::Sender<(Identifier, Vec<StarcoinEvent>)>,
+        events_sender: starcoin_metrics::metered_channel::Sender<StarcoinModuleEvents>,
         starcoin_bridge_client: Arc<StarcoinClient<C>>,
         query_interval: Duration,
         metrics: Arc<BridgeMetrics>,
@@ -135,10 +139,11 @@ where
         loop {
             interval.tick().await;
             let Ok(Ok(events)) = retry_with_max_elapsed_time!(
-                starcoin_bridge_client.query_events_by_module(
+                starcoin_bridge_client.query_events_by_module_paginated(
                     bridge_package_id,
                     module.clone(),
-                    cursor
+                    cursor,
+                    STARCOIN_EVENTS_QUERY_LIMIT
                 ),
                 Duration::from_secs(120)
             ) else {
@@ -146,21 +151,26 @@ where
                 continue;
             };
 
+            // Events come in cursor order. The cursor moves past the pages
+            // read even when they held no events of the module, and is sent
+            // along so it's persisted with the events.
+            let next_cursor = match events.next_cursor {
+                Some(next_cursor) if events.next_cursor != cursor => next_cursor,
+                // Nothing new since the last query
+                _ => continue,
+            };
+            if !events.has_next_page {
+                // If this is the last page, it means we have processed all events up to the latest checkpoint
+                // We can then update the latest checkpoint metric.
+                notify.notify_one();
+            }
             let len = events.data.len();
+            events_sender
+                .send((module.clone(), next_cursor, events.data))
+                .await
+                .expect("All Starcoin event channel receivers are closed");
+            cursor = Some(next_cursor);
             if len != 0 {
-                if !events.has_next_page {
-                    // If this is the last page, it means we have processed all events up to the latest checkpoint
-                    // We can then update the latest checkpoint metric.
-                    notify.notify_one();
-                }
-                events_sender
-                    .send((module.clone(), events.data.clone()))
-                    .await
-                    .expect("All Starcoin event channel receivers are closed");
-                // Update cursor from last event
-                if let Some(last_event) = events.data.last() {
-                    cursor = Some(last_event.id.clone().into());
-                }
                 tracing::info!(?module, ?cursor, "Observed {len} new Starcoin events");
             }
         }
@@ -172,15 +182,23 @@ where
 mod tests {
     use super::*;
 
+    use crate::fault_injection::FaultInjector;
     use crate::test_utils::TransactionDigestTestExt;
     use crate::{
         starcoin_bridge_client::StarcoinClient, starcoin_bridge_mock_client::StarcoinMockClient,
     };
     use prometheus::Registry;
-    use starcoin_bridge_json_rpc_types::EventPage;
+    use starcoin_bridge_json_rpc_types::{EventCursor, EventFilter, EventPage};
     use starcoin_bridge_types::Identifier;
     use tokio::time::timeout;
 
+    // Package ids hold a Starcoin address in their last 16 bytes
+    fn random_package_id() -> ObjectID {
+        let mut package = ObjectID::random();
+        package[..16].fill(0);
+        package
+    }
+
     #[tokio::test]
     async fn test_starcoin_bridge_syncer_basic() -> anyhow::Result<()> {
         telemetry_subscribers::init_for_testing();
@@ -188,7 +206,7 @@ mod tests {
         starcoin_metrics::init_metrics(&registry);
         let metrics = Arc::new(BridgeMetrics::new(&registry));
         let mock = StarcoinMockClient::default();
-        let bridge_package_id = ObjectID::random();
+        let bridge_package_id = random_package_id();
         let client = Arc::new(StarcoinClient::new_for_testing(mock.clone()));
         let module_foo = Identifier::new("Foo").unwrap();
         let module_bar = Identifier::new("Bar").unwrap();
@@ -230,12 +248,11 @@ mod tests {
         event_1.type_.module = module_foo.clone();
         // Create cursor from event's id
         let event_1_cursor: EventID = event_1.id.into();
-        let module_foo_events_1: starcoin_bridge_json_rpc_types::Page<StarcoinEvent, (u64, u64)> =
-            EventPage {
-                data: vec![event_1.clone(), event_1.clone()],
-                next_cursor: Some(event_1_cursor),
-                has_next_page: false,
-            };
+        let module_foo_events_1 = EventPage {
+            data: vec![event_1.clone(), event_1.clone()],
+            next_cursor: Some(EventCursor::new(event_1_cursor, &EventFilter::default())),
+            has_next_page: false,
+        };
         add_event_response(
             &mock,
             bridge_package_id,
@@ -251,8 +268,9 @@ mod tests {
             module_foo_events_1.clone(),
         );
 
-        let (identifier, received_events) = events_rx.recv().await.unwrap();
+        let (identifier, cursor, received_events) = events_rx.recv().await.unwrap();
         assert_eq!(identifier, module_foo);
+        assert_eq!(cursor, event_1_cursor);
         assert_eq!(received_events.len(), 2);
         assert_eq!(received_events[0].id, event_1.id);
         assert_eq!(received_events[1].id, event_1.id);
@@ -272,15 +290,19 @@ mod tests {
         let event_2_cursor: EventID = event_2.id.into();
         let module_bar_events_1 = EventPage {
             data: vec![event_2.clone()],
-            next_cursor: Some(event_2_cursor),
+            next_cursor: Some(EventCursor::new(event_2_cursor, &EventFilter::default())),
             has_next_page: true, // Set to true so that the syncer will not update the last synced checkpoint
         };
+        // The head is past the pages served so far
         add_event_response(
             &mock,
             bridge_package_id,
             module_bar.clone(),
             event_2_cursor,
-            empty_events.clone(),
+            EventPage {
+                has_next_page: true,
+                ..EventPage::empty()
+            },
         );
 
         add_event_response(
@@ -291,7 +313,7 @@ mod tests {
             module_bar_events_1,
         );
 
-        let (identifier, received_events) = events_rx.recv().await.unwrap();
+        let (identifier, _, received_events) = events_rx.recv().await.unwrap();
         assert_eq!(identifier, module_bar);
         assert_eq!(received_events.len(), 1);
         assert_eq!(received_events[0].id, event_2.id);
@@ -308,12 +330,111 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_starcoin_bridge_syncer_under_faults() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        starcoin_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let mock = StarcoinMockClient::default();
+        // Flaky queries, shuffled pages and a head that stops at 500
+        mock.set_fault_injector(
+            FaultInjector::builder(11)
+                .failure_rate("query_events_by_module", 0.3)
+                .out_of_order_events()
+                .stale_head(500)
+                .build(),
+        );
+        mock.set_latest_checkpoint_sequence_number(999);
+        let bridge_package_id = random_package_id();
+        let client = Arc::new(StarcoinClient::new_for_testing(mock.clone()));
+        let module_foo = Identifier::new("Foo").unwrap();
+
+        let events: Vec<StarcoinEvent> = (10..14)
+            .map(|block_number| {
+                let mut event = StarcoinEvent::random_for_testing();
+                event.type_.module = module_foo.clone();
+                event.id.block_number = block_number;
+                event.id.event_seq = 0;
+                event
+            })
+            .collect();
+        let cursor: EventID = (1, 0);
+        add_event_response(
+            &mock,
+            bridge_package_id,
+            module_foo.clone(),
+            cursor,
+            EventPage {
+                data: events[..3].to_vec(),
+                next_cursor: Some(EventCursor::new(
+                    events[2].id.into(),
+                    &EventFilter::default(),
+                )),
+                has_next_page: false,
+            },
+        );
+        // The next page is only reachable from the furthest event of the first
+        add_event_response(
+            &mock,
+            bridge_package_id,
+            module_foo.clone(),
+            events[2].id.into(),
+            EventPage {
+                data: events[3..].to_vec(),
+                next_cursor: Some(EventCursor::new(
+                    events[3].id.into(),
+                    &EventFilter::default(),
+                )),
+                has_next_page: false,
+            },
+        );
+        add_event_response(
+            &mock,
+            bridge_package_id,
+            module_foo.clone(),
+            events[3].id.into(),
+            EventPage::empty(),
+        );
+
+        let target_modules = HashMap::from_iter(vec![(module_foo.clone(), Some(cursor))]);
+        let interval = Duration::from_millis(200);
+        let (_handles, mut events_rx) =
+            StarcoinSyncer::new(client, bridge_package_id, target_modules, metrics.clone())
+                .run(interval)
+                .await
+                .unwrap();
+
+        // Cursor never skips events: every event arrives exactly once, in order
+        let mut received = vec![];
+        while received.len() < events.len() {
+            let (_, _, batch) = timeout(Duration::from_secs(30), events_rx.recv())
+                .await?
+                .unwrap();
+            received.extend(batch);
+        }
+        assert_eq!(
+            received.iter().map(|e| e.id).collect::<Vec<_>>(),
+            events.iter().map(|e| e.id).collect::<Vec<_>>()
+        );
+        assert_no_more_events(interval, &mut events_rx).await;
+
+        // The synced checkpoint follows the stale head rather than the real one
+        let metric = metrics
+            .last_synced_starcoin_bridge_checkpoints
+            .with_label_values(&["Foo"]);
+        timeout(Duration::from_secs(10), async {
+            while metric.get() != 500 {
+                time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await?;
+        Ok(())
+    }
+
     async fn assert_no_more_events(
         interval: Duration,
-        events_rx: &mut starcoin_metrics::metered_channel::Receiver<(
-            Identifier,
-            Vec<StarcoinEvent>,
-        )>,
+        events_rx: &mut starcoin_metrics::metered_channel::Receiver<StarcoinModuleEvents>,
     ) {
         match timeout(interval * 2, events_rx.recv()).await {
             Err(_e) => (),
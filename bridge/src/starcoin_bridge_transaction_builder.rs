@@ -16,6 +16,7 @@
 use fastcrypto::traits::ToFromBytes;
 use move_core_types::ident_str;
 use move_core_types::language_storage::ModuleId;
+use serde::{Deserialize, Serialize};
 use starcoin_bridge_types::bridge::{
     BRIDGE_CREATE_ADD_TOKEN_ON_STARCOIN_MESSAGE_FUNCTION_NAME,
     BRIDGE_EXECUTE_SYSTEM_MESSAGE_FUNCTION_NAME, BRIDGE_MESSAGE_MODULE_NAME, BRIDGE_MODULE_NAME,
@@ -32,13 +33,119 @@ use std::{collections::HashMap, str::FromStr};
 
 use crate::{
     error::{BridgeError, BridgeResult},
-    types::{BridgeAction, VerifiedCertifiedBridgeAction},
+    types::{BridgeAction, BridgeActionType, VerifiedCertifiedBridgeAction},
 };
 
 // =============================================================================
 // Starcoin Native Transaction Builders
 // =============================================================================
 
+/// Max gas of every transaction the bridge submits
+pub const MAX_GAS_AMOUNT: u64 = 10_000_000;
+
+/// Gas unit price used when the node's reference price can't be read, the
+/// lowest price a Starcoin node accepts
+pub const DEFAULT_GAS_UNIT_PRICE: u64 = 1;
+
+/// Scale a reference gas price by `multiplier` to bid for priority, rounding
+/// up. Never bids below the reference price.
+pub fn gas_unit_price_with_multiplier(reference_gas_price: u64, multiplier: f64) -> u64 {
+    let price = (reference_gas_price as f64 * multiplier).ceil();
+    if price.is_finite() && price < u64::MAX as f64 {
+        (price as u64).max(reference_gas_price)
+    } else {
+        reference_gas_price
+    }
+}
+
+/// Transactions bid the reference gas price as is unless configured otherwise
+pub const DEFAULT_GAS_PRICE_MULTIPLIER: f64 = 1.0;
+
+/// Headroom added to the gas a dry run used, as state can change between the
+/// dry run and execution
+pub const GAS_ESTIMATE_MARGIN: f64 = 1.2;
+
+/// Gas a transaction that used `gas_used` in a dry run is given, with
+/// `GAS_ESTIMATE_MARGIN` on top, rounding up
+pub fn gas_with_margin(gas_used: u64) -> u64 {
+    let gas = (gas_used as f64 * GAS_ESTIMATE_MARGIN).ceil();
+    if gas.is_finite() && gas < u64::MAX as f64 {
+        (gas as u64).max(gas_used)
+    } else {
+        u64::MAX
+    }
+}
+
+/// Gas parameters of the transactions the bridge submits, from the
+/// `gas` section of the node and CLI configs
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GasConfig {
+    /// Max gas of a transaction, unless its action type has an override
+    #[serde(default = "default_max_gas_amount")]
+    pub max_gas_amount: u64,
+    /// Transactions bid the node's reference gas price times this, e.g. 1.1
+    /// to pay 10% more for priority. At least 1.
+    #[serde(default = "default_gas_unit_price_multiplier")]
+    pub gas_unit_price_multiplier: f64,
+    /// Max gas by action type, e.g. `TokenTransfer: 2000000`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub gas_budget_overrides: HashMap<BridgeActionType, u64>,
+}
+
+fn default_max_gas_amount() -> u64 {
+    MAX_GAS_AMOUNT
+}
+
+fn default_gas_unit_price_multiplier() -> f64 {
+    DEFAULT_GAS_PRICE_MULTIPLIER
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            max_gas_amount: default_max_gas_amount(),
+            gas_unit_price_multiplier: default_gas_unit_price_multiplier(),
+            gas_budget_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl GasConfig {
+    /// Max gas of the transactions executing actions of `action_type`
+    pub fn max_gas_amount_for(&self, action_type: BridgeActionType) -> u64 {
+        self.gas_budget_overrides
+            .get(&action_type)
+            .copied()
+            .unwrap_or(self.max_gas_amount)
+    }
+
+    /// Gas unit price bid for a node reference price of `reference_gas_price`
+    pub fn gas_unit_price(&self, reference_gas_price: u64) -> u64 {
+        gas_unit_price_with_multiplier(reference_gas_price, self.gas_unit_price_multiplier)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_gas_amount == 0 {
+            anyhow::bail!("max-gas-amount must be positive");
+        }
+        if !(self.gas_unit_price_multiplier >= 1.0 && self.gas_unit_price_multiplier.is_finite()) {
+            anyhow::bail!(
+                "gas-unit-price-multiplier must be a number of at least 1, got {}",
+                self.gas_unit_price_multiplier
+            );
+        }
+        if let Some((action_type, _)) = self
+            .gas_budget_overrides
+            .iter()
+            .find(|(_, max_gas)| **max_gas == 0)
+        {
+            anyhow::bail!("gas-budget-overrides of {action_type} must be positive");
+        }
+        Ok(())
+    }
+}
+
 /// Bridge module address as StarcoinAddress (16 bytes)
 /// This matches the Bridge address in stc-bridge-move/Move.toml: 0xf8eda27b31a0dcd9b6c06074d74a2c6c
 pub fn bridge_module_address() -> StarcoinAddress {
@@ -98,6 +205,8 @@ impl StarcoinBridgeTransactionBuilder {
     /// * `sequence_number` - The transaction sequence number
     /// * `chain_id` - The Starcoin chain ID
     /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
     /// * `source_chain` - Source chain ID (e.g., ETH chain ID)
     /// * `seq_num` - Bridge sequence number
     /// * `sender_address` - Original sender address on source chain
@@ -112,6 +221,8 @@ impl StarcoinBridgeTransactionBuilder {
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         source_chain: u8,
         seq_num: u64,
         sender_address: Vec<u8>,
@@ -127,6 +238,8 @@ impl StarcoinBridgeTransactionBuilder {
             sequence_number,
             chain_id,
             block_timestamp_ms,
+            gas_unit_price,
+            max_gas_amount,
             source_chain,
             seq_num,
             sender_address,
@@ -146,10 +259,13 @@ impl StarcoinBridgeTransactionBuilder {
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         clock_timestamp_ms: u64,
         source_chain: u8,
         seq_num: u64,
         token_id: u8,
+        token_types: &HashMap<u8, TypeTag>,
     ) -> BridgeResult<starcoin_bridge_types::transaction::RawUserTransaction> {
         starcoin_native::build_claim_and_transfer(
             module_address,
@@ -157,10 +273,13 @@ impl StarcoinBridgeTransactionBuilder {
             sequence_number,
             chain_id,
             block_timestamp_ms,
+            gas_unit_price,
+            max_gas_amount,
             clock_timestamp_ms,
             source_chain,
             seq_num,
             token_id,
+            token_types,
         )
     }
 }
@@ -184,8 +303,8 @@ pub mod starcoin_native {
     }
 
     /// Build a RawUserTransaction for approving token transfer
-    /// Uses the script function `approve_bridge_token_transfer_single` for single signature
-    /// or `approve_bridge_token_transfer_two`/`approve_bridge_token_transfer_three` for multiple signatures
+    /// Uses the script function `approve_bridge_token_transfer`, which takes all
+    /// signatures as a single `vector<vector<u8>>` argument
     ///
     /// # Arguments
     /// * `module_address` - The address where the bridge module is deployed
@@ -193,6 +312,8 @@ pub mod starcoin_native {
     /// * `sequence_number` - The transaction sequence number
     /// * `chain_id` - The Starcoin chain ID
     /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
     /// * `source_chain` - Source chain ID (e.g., ETH chain ID)
     /// * `seq_num` - Bridge sequence number
     /// * `sender_address` - Original sender address on source chain
@@ -200,13 +321,15 @@ pub mod starcoin_native {
     /// * `target_address` - Target address on Starcoin
     /// * `token_type` - Token type ID
     /// * `amount` - Amount to transfer
-    /// * `signatures` - The aggregated signatures (1-3 signatures supported)
+    /// * `signatures` - The aggregated signatures, at least one
     pub fn build_approve_token_transfer(
         module_address: StarcoinAddress,
         sender: StarcoinAddress,
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         // Message parameters
         source_chain: u8,
         seq_num: u64,
@@ -219,91 +342,37 @@ pub mod starcoin_native {
     ) -> BridgeResult<RawUserTransaction> {
         let module_id = ModuleId::new(
             module_address,
-            Identifier::new("Bridge").map_err(|e| BridgeError::Generic(e.to_string()))?,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
         );
 
-        // Choose function based on number of signatures
-        let (function_name, args) = match signatures.len() {
-            1 => (
-                "approve_bridge_token_transfer_single",
-                vec![
-                    bcs::to_bytes(&source_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&seq_num)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&sender_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&token_type)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&amount)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[0])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                ],
-            ),
-            2 => (
-                "approve_bridge_token_transfer_two",
-                vec![
-                    bcs::to_bytes(&source_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&seq_num)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&sender_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&token_type)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&amount)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[0])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[1])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                ],
-            ),
-            3 => (
-                "approve_bridge_token_transfer_three",
-                vec![
-                    bcs::to_bytes(&source_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&seq_num)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&sender_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_chain)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&target_address)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&token_type)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&amount)
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[0])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[1])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                    bcs::to_bytes(&signatures[2])
-                        .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
-                ],
-            ),
-            n => {
-                return Err(BridgeError::Generic(format!(
-                    "Unsupported number of signatures: {}. Only 1-3 signatures are supported.",
-                    n
-                )))
-            }
-        };
+        if signatures.is_empty() {
+            return Err(BridgeError::Permanent(
+                "At least one signature is required to approve a token transfer".to_string(),
+            ));
+        }
+        let args = vec![
+            bcs::to_bytes(&source_chain)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&seq_num)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&sender_address)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&target_chain)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&target_address)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&token_type)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&amount)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            bcs::to_bytes(&signatures)
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+        ];
 
         let script_function = ScriptFunction::new(
             module_id,
-            Identifier::new(function_name).map_err(|e| BridgeError::Generic(e.to_string()))?,
+            Identifier::new("approve_bridge_token_transfer")
+                .map_err(|e| BridgeError::Permanent(e.to_string()))?,
             vec![],
             args,
         );
@@ -312,8 +381,8 @@ pub mod starcoin_native {
             sender,
             sequence_number,
             script_function,
-            10_000_000, // max_gas_amount
-            1,          // gas_unit_price
+            max_gas_amount,
+            gas_unit_price,
             calculate_expiration_from_block(block_timestamp_ms),
             ChainId::new(chain_id),
         ))
@@ -327,39 +396,39 @@ pub mod starcoin_native {
     /// * `sequence_number` - The transaction sequence number
     /// * `chain_id` - The Starcoin chain ID
     /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
     /// * `clock_timestamp_ms` - Clock timestamp for the claim operation
     /// * `source_chain` - Source chain ID
     /// * `seq_num` - Bridge sequence number
-    /// * `token_id` - Token ID (1=BTC, 2=ETH, 3=USDC, 4=USDT)
+    /// * `token_id` - Token ID
+    /// * `token_types` - Token id to type map of the bridge treasury, see
+    ///   `StarcoinBridgeClient::get_token_id_map`
     pub fn build_claim_and_transfer(
         module_address: StarcoinAddress,
         sender: StarcoinAddress,
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         clock_timestamp_ms: u64,
         source_chain: u8,
         seq_num: u64,
         token_id: u8,
+        token_types: &HashMap<u8, TypeTag>,
     ) -> BridgeResult<RawUserTransaction> {
         let module_id = ModuleId::new(
             module_address,
-            Identifier::new("Bridge").map_err(|e| BridgeError::Generic(e.to_string()))?,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
         );
 
-        // Choose function based on token ID
-        let function_name = match token_id {
-            1 => "claim_bridge_btc",
-            2 => "claim_bridge_eth",
-            3 => "claim_bridge_usdc",
-            4 => "claim_bridge_usdt",
-            _ => return Err(BridgeError::UnknownTokenId(token_id)),
-        };
+        let (function_name, type_args) = claim_function(token_id, token_types)?;
 
         let script_function = ScriptFunction::new(
             module_id,
-            Identifier::new(function_name).map_err(|e| BridgeError::Generic(e.to_string()))?,
-            vec![],
+            Identifier::new(function_name).map_err(|e| BridgeError::Permanent(e.to_string()))?,
+            type_args,
             vec![
                 bcs::to_bytes(&clock_timestamp_ms)
                     .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
@@ -374,8 +443,8 @@ pub mod starcoin_native {
             sender,
             sequence_number,
             script_function,
-            10_000_000,
-            1,
+            max_gas_amount,
+            gas_unit_price,
             calculate_expiration_from_block(block_timestamp_ms),
             ChainId::new(chain_id),
         ))
@@ -389,6 +458,8 @@ pub mod starcoin_native {
     /// * `sequence_number` - The transaction sequence number
     /// * `chain_id` - The Starcoin chain ID
     /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
     /// * `source_chain` - Source chain ID
     /// * `seq_num` - Bridge sequence number
     /// * `op_type` - Emergency operation type
@@ -399,6 +470,8 @@ pub mod starcoin_native {
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         source_chain: u8,
         seq_num: u64,
         op_type: u8,
@@ -406,13 +479,13 @@ pub mod starcoin_native {
     ) -> BridgeResult<RawUserTransaction> {
         let module_id = ModuleId::new(
             module_address,
-            Identifier::new("Bridge").map_err(|e| BridgeError::Generic(e.to_string()))?,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
         );
 
         let script_function = ScriptFunction::new(
             module_id,
             Identifier::new("execute_emergency_op_single")
-                .map_err(|e| BridgeError::Generic(e.to_string()))?,
+                .map_err(|e| BridgeError::Permanent(e.to_string()))?,
             vec![],
             vec![
                 bcs::to_bytes(&source_chain)
@@ -430,13 +503,252 @@ pub mod starcoin_native {
             sender,
             sequence_number,
             script_function,
-            10_000_000,
-            1,
+            max_gas_amount,
+            gas_unit_price,
+            calculate_expiration_from_block(block_timestamp_ms),
+            ChainId::new(chain_id),
+        ))
+    }
+
+    /// Build a RawUserTransaction for pausing or unpausing a single token
+    ///
+    /// # Arguments
+    /// * `module_address` - The address where the bridge module is deployed
+    /// * `sender` - The sender address
+    /// * `sequence_number` - The transaction sequence number
+    /// * `chain_id` - The Starcoin chain ID
+    /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
+    /// * `source_chain` - Source chain ID
+    /// * `seq_num` - Bridge sequence number
+    /// * `token_id` - The token to pause or unpause
+    /// * `pause` - `true` to pause, `false` to unpause
+    /// * `signature` - The signature for the operation
+    pub fn build_execute_token_pause(
+        module_address: StarcoinAddress,
+        sender: StarcoinAddress,
+        sequence_number: u64,
+        chain_id: u8,
+        block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
+        source_chain: u8,
+        seq_num: u64,
+        token_id: u8,
+        pause: bool,
+        signature: Vec<u8>,
+    ) -> BridgeResult<RawUserTransaction> {
+        let module_id = ModuleId::new(
+            module_address,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
+        );
+
+        let script_function = ScriptFunction::new(
+            module_id,
+            Identifier::new("execute_token_pause_single")
+                .map_err(|e| BridgeError::Permanent(e.to_string()))?,
+            vec![],
+            vec![
+                bcs::to_bytes(&source_chain)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+                bcs::to_bytes(&seq_num)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+                bcs::to_bytes(&token_id)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+                bcs::to_bytes(&pause)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+                bcs::to_bytes(&signature)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            ],
+        );
+
+        Ok(RawUserTransaction::new_script_function(
+            sender,
+            sequence_number,
+            script_function,
+            max_gas_amount,
+            gas_unit_price,
             calculate_expiration_from_block(block_timestamp_ms),
             ChainId::new(chain_id),
         ))
     }
 
+    /// Build a RawUserTransaction registering the sender as a committee member
+    /// with its bridge authority public key and the url of its bridge node.
+    /// Registration closes once the committee is created, and the Move entry
+    /// only accepts the bridge account as sender.
+    ///
+    /// # Arguments
+    /// * `module_address` - The address where the bridge module is deployed
+    /// * `sender` - The registering validator's address
+    /// * `sequence_number` - The transaction sequence number
+    /// * `chain_id` - The Starcoin chain ID
+    /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
+    /// * `bridge_pubkey_bytes` - Compressed Secp256k1 public key of the bridge authority
+    /// * `url` - The url of the validator's bridge node
+    pub fn build_register_committee_member(
+        module_address: StarcoinAddress,
+        sender: StarcoinAddress,
+        sequence_number: u64,
+        chain_id: u8,
+        block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
+        bridge_pubkey_bytes: &[u8],
+        url: &str,
+    ) -> BridgeResult<RawUserTransaction> {
+        let module_id = ModuleId::new(
+            module_address,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
+        );
+
+        let script_function = ScriptFunction::new(
+            module_id,
+            Identifier::new("register_committee_member")
+                .map_err(|e| BridgeError::Permanent(e.to_string()))?,
+            vec![],
+            vec![
+                bcs::to_bytes(bridge_pubkey_bytes)
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+                bcs::to_bytes(url.as_bytes())
+                    .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
+            ],
+        );
+
+        Ok(RawUserTransaction::new_script_function(
+            sender,
+            sequence_number,
+            script_function,
+            max_gas_amount,
+            gas_unit_price,
+            calculate_expiration_from_block(block_timestamp_ms),
+            ChainId::new(chain_id),
+        ))
+    }
+
+    /// Build a RawUserTransaction updating the http rest url of the sender's
+    /// committee member. The sender must be a committee member.
+    ///
+    /// # Arguments
+    /// * `module_address` - The address where the bridge module is deployed
+    /// * `sender` - The committee member's address
+    /// * `sequence_number` - The transaction sequence number
+    /// * `chain_id` - The Starcoin chain ID
+    /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
+    /// * `new_url` - The new url of the member's bridge node
+    pub fn build_update_node_url(
+        module_address: StarcoinAddress,
+        sender: StarcoinAddress,
+        sequence_number: u64,
+        chain_id: u8,
+        block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
+        new_url: &str,
+    ) -> BridgeResult<RawUserTransaction> {
+        let module_id = ModuleId::new(
+            module_address,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
+        );
+
+        let script_function = ScriptFunction::new(
+            module_id,
+            Identifier::new("update_committee_member_url")
+                .map_err(|e| BridgeError::Permanent(e.to_string()))?,
+            vec![],
+            vec![bcs::to_bytes(new_url.as_bytes())
+                .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?],
+        );
+
+        Ok(RawUserTransaction::new_script_function(
+            sender,
+            sequence_number,
+            script_function,
+            max_gas_amount,
+            gas_unit_price,
+            calculate_expiration_from_block(block_timestamp_ms),
+            ChainId::new(chain_id),
+        ))
+    }
+
+    /// Generic entry function claiming a token, the token type is its type argument
+    pub const CLAIM_TOKEN_FUNCTION: &str = "claim_bridge_token";
+    /// Generic entry function depositing a token, the token type is its type argument
+    pub const SEND_TOKEN_FUNCTION: &str = "send_bridge_token";
+
+    /// Dedicated claim entries of the tokens the bridge launched with, by
+    /// token id, for bridges deployed before the generic entry existed
+    const LEGACY_CLAIM_FUNCTIONS: [(u8, &str); 4] = [
+        (1, "claim_bridge_btc"),
+        (2, "claim_bridge_eth"),
+        (3, "claim_bridge_usdc"),
+        (4, "claim_bridge_usdt"),
+    ];
+
+    /// Entry function and type arguments claiming `token_id`. Tokens in
+    /// `token_types` go through the generic entry, so tokens added by
+    /// governance can be claimed. The dedicated entries are only used for
+    /// launch tokens missing from the map.
+    pub fn claim_function(
+        token_id: u8,
+        token_types: &HashMap<u8, TypeTag>,
+    ) -> BridgeResult<(&'static str, Vec<TypeTag>)> {
+        if let Some(token_type) = token_types.get(&token_id) {
+            return Ok((CLAIM_TOKEN_FUNCTION, vec![token_type.clone()]));
+        }
+        if let Some((_, function_name)) = LEGACY_CLAIM_FUNCTIONS
+            .iter()
+            .find(|(id, _)| *id == token_id)
+        {
+            return Ok((function_name, vec![]));
+        }
+        let mut known: Vec<u8> = token_types
+            .keys()
+            .copied()
+            .chain(LEGACY_CLAIM_FUNCTIONS.iter().map(|(id, _)| *id))
+            .collect();
+        known.sort();
+        known.dedup();
+        Err(BridgeError::UnknownTokenId(token_id, known))
+    }
+
+    /// Dedicated deposit entry of a launch token, for bridges deployed before
+    /// the generic entry existed. Token types are like `0xADDR::ETH::ETH`, the
+    /// module name selects it.
+    pub fn send_token_function(token_type: &TypeTag) -> Option<&'static str> {
+        let TypeTag::Struct(s) = token_type else {
+            return None;
+        };
+        match s.module.as_str() {
+            "ETH" => Some("send_bridge_eth"),
+            "BTC" => Some("send_bridge_btc"),
+            "USDC" => Some("send_bridge_usdc"),
+            "USDT" => Some("send_bridge_usdt"),
+            _ => None,
+        }
+    }
+
+    /// Entry function and type arguments depositing `token_type`, picked the
+    /// same way as `claim_function`
+    pub fn deposit_function(
+        token_type: &TypeTag,
+        token_types: &HashMap<u8, TypeTag>,
+    ) -> BridgeResult<(&'static str, Vec<TypeTag>)> {
+        if token_types.values().any(|t| t == token_type) {
+            return Ok((SEND_TOKEN_FUNCTION, vec![token_type.clone()]));
+        }
+        send_token_function(token_type)
+            .map(|function_name| (function_name, vec![]))
+            .ok_or_else(|| {
+                BridgeError::Permanent(format!("Unsupported token type: {}", token_type))
+            })
+    }
+
     /// Build a RawUserTransaction for sending tokens to another chain (Starcoin -> ETH)
     ///
     /// # Arguments
@@ -445,51 +757,38 @@ pub mod starcoin_native {
     /// * `sequence_number` - The transaction sequence number
     /// * `chain_id` - The Starcoin chain ID
     /// * `block_timestamp_ms` - Current block timestamp in milliseconds (from chain.info)
+    /// * `gas_unit_price` - Gas price bid in nanoSTC, see `StarcoinClient::get_gas_unit_price`
+    /// * `max_gas_amount` - Max gas the transaction may use, see `GasConfig`
     /// * `target_chain` - Target chain ID
     /// * `target_address` - Target address on the target chain
     /// * `amount` - Amount to transfer
     /// * `token_type` - The token type tag
+    /// * `token_types` - Token id to type map of the bridge treasury
     pub fn build_send_token(
         module_address: StarcoinAddress,
         sender: StarcoinAddress,
         sequence_number: u64,
         chain_id: u8,
         block_timestamp_ms: u64,
+        gas_unit_price: u64,
+        max_gas_amount: u64,
         target_chain: u8,
         target_address: Vec<u8>,
         amount: u128,
         token_type: TypeTag,
+        token_types: &HashMap<u8, TypeTag>,
     ) -> BridgeResult<RawUserTransaction> {
         let module_id = ModuleId::new(
             module_address,
-            Identifier::new("Bridge").map_err(|e| BridgeError::Generic(e.to_string()))?,
+            Identifier::new("Bridge").map_err(|e| BridgeError::Permanent(e.to_string()))?,
         );
 
-        // Determine function name based on token type
-        // Token type is like: 0xADDR::ETH::ETH, we extract the module name
-        let function_name = match &token_type {
-            TypeTag::Struct(s) => {
-                let module_name = s.module.as_str();
-                match module_name {
-                    "ETH" => "send_bridge_eth",
-                    "BTC" => "send_bridge_btc",
-                    "USDC" => "send_bridge_usdc",
-                    "USDT" => "send_bridge_usdt",
-                    _ => {
-                        return Err(BridgeError::Generic(format!(
-                            "Unsupported token type: {}",
-                            module_name
-                        )))
-                    }
-                }
-            }
-            _ => return Err(BridgeError::Generic("Expected struct type tag".to_string())),
-        };
+        let (function_name, type_args) = deposit_function(&token_type, token_types)?;
 
         let script_function = ScriptFunction::new(
             module_id,
-            Identifier::new(function_name).map_err(|e| BridgeError::Generic(e.to_string()))?,
-            vec![], // No type args needed, function is specific to token
+            Identifier::new(function_name).map_err(|e| BridgeError::Permanent(e.to_string()))?,
+            type_args,
             vec![
                 bcs::to_bytes(&target_chain)
                     .map_err(|e| BridgeError::BridgeSerializationError(e.to_string()))?,
@@ -504,8 +803,8 @@ pub mod starcoin_native {
             sender,
             sequence_number,
             script_function,
-            10_000_000,
-            1,
+            max_gas_amount,
+            gas_unit_price,
             calculate_expiration_from_block(block_timestamp_ms),
             ChainId::new(chain_id),
         ))
@@ -523,8 +822,10 @@ pub fn build_starcoin_bridge_transaction(
     bridge_object_arg: ObjectArg,
     starcoin_bridge_token_type_tags: &HashMap<u8, TypeTag>,
     rgp: u64,
+    gas_config: &GasConfig,
 ) -> BridgeResult<TransactionData> {
     // TODO: Check chain id?
+    let gas_budget = gas_config.max_gas_amount_for(action.data().action_type());
     match action.data() {
         BridgeAction::EthToStarcoinBridgeAction(_) => build_token_bridge_approve_transaction(
             client_address,
@@ -534,6 +835,7 @@ pub fn build_starcoin_bridge_transaction(
             bridge_object_arg,
             starcoin_bridge_token_type_tags,
             rgp,
+            gas_budget,
         ),
         BridgeAction::StarcoinToEthBridgeAction(_) => build_token_bridge_approve_transaction(
             client_address,
@@ -543,6 +845,7 @@ pub fn build_starcoin_bridge_transaction(
             bridge_object_arg,
             starcoin_bridge_token_type_tags,
             rgp,
+            gas_budget,
         ),
         BridgeAction::BlocklistCommitteeAction(_) => build_committee_blocklist_approve_transaction(
             client_address,
@@ -550,6 +853,7 @@ pub fn build_starcoin_bridge_transaction(
             action,
             bridge_object_arg,
             rgp,
+            gas_budget,
         ),
         BridgeAction::EmergencyAction(_) => build_emergency_op_approve_transaction(
             client_address,
@@ -557,6 +861,7 @@ pub fn build_starcoin_bridge_transaction(
             action,
             bridge_object_arg,
             rgp,
+            gas_budget,
         ),
         BridgeAction::LimitUpdateAction(_) => build_limit_update_approve_transaction(
             client_address,
@@ -564,6 +869,7 @@ pub fn build_starcoin_bridge_transaction(
             action,
             bridge_object_arg,
             rgp,
+            gas_budget,
         ),
         BridgeAction::AssetPriceUpdateAction(_) => build_asset_price_update_approve_transaction(
             client_address,
@@ -571,6 +877,7 @@ pub fn build_starcoin_bridge_transaction(
             action,
             bridge_object_arg,
             rgp,
+            gas_budget,
         ),
         BridgeAction::EvmContractUpgradeAction(_) => {
             // It does not need a Starcoin tranaction to execute EVM contract upgrade
@@ -583,12 +890,22 @@ pub fn build_starcoin_bridge_transaction(
                 action,
                 bridge_object_arg,
                 rgp,
+                gas_budget,
+                gas_budget,
             )
         }
         BridgeAction::AddTokensOnEvmAction(_) => {
             // It does not need a Starcoin tranaction to add tokens on EVM
             unreachable!()
         }
+        BridgeAction::TokenPauseAction(_) => build_token_pause_approve_transaction(
+            client_address,
+            gas_object_ref,
+            action,
+            bridge_object_arg,
+            rgp,
+            gas_budget,
+        ),
     }
 }
 
@@ -600,20 +917,24 @@ fn build_token_bridge_approve_transaction(
     bridge_object_arg: ObjectArg,
     starcoin_bridge_token_type_tags: &HashMap<u8, TypeTag>,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
     let mut builder = ProgrammableTransactionBuilder::new();
 
-    let (source_chain, seq_num, sender, target_chain, target, token_type, amount) =
+    // Bridge messages carry addresses as `vector<u8>`, 16 bytes for Starcoin
+    // and 20 for Eth
+    let from_starcoin = matches!(bridge_action, BridgeAction::StarcoinToEthBridgeAction(_));
+    let (source_chain, seq_num, starcoin_address, eth_address, target_chain, token_type, amount) =
         match bridge_action {
             BridgeAction::StarcoinToEthBridgeAction(a) => {
                 let bridge_event = a.starcoin_bridge_event;
                 (
                     bridge_event.starcoin_bridge_chain_id,
                     bridge_event.nonce,
-                    bridge_event.starcoin_bridge_address.to_vec(),
+                    bridge_event.starcoin_bridge_address,
+                    bridge_event.eth_address.to_fixed_bytes(),
                     bridge_event.eth_chain_id,
-                    bridge_event.eth_address.to_fixed_bytes().to_vec(),
                     bridge_event.token_id,
                     bridge_event.amount_starcoin_bridge_adjusted,
                 )
@@ -623,9 +944,9 @@ fn build_token_bridge_approve_transaction(
                 (
                     bridge_event.eth_chain_id,
                     bridge_event.nonce,
-                    bridge_event.eth_address.to_fixed_bytes().to_vec(),
+                    bridge_event.starcoin_bridge_address,
+                    bridge_event.eth_address.to_fixed_bytes(),
                     bridge_event.starcoin_bridge_chain_id,
-                    bridge_event.starcoin_bridge_address.to_vec(),
                     bridge_event.token_id,
                     bridge_event.starcoin_bridge_adjusted_amount,
                 )
@@ -633,23 +954,21 @@ fn build_token_bridge_approve_transaction(
             _ => unreachable!(),
         };
 
-    let source_chain = builder.pure(source_chain as u8).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let sender = builder.pure(sender.clone()).map_err(|e| {
-        BridgeError::BridgeSerializationError(format!(
-            "Failed to serialize sender: {:?}. Err: {:?}",
-            sender, e
-        ))
-    })?;
-    let target_chain = builder.pure(target_chain as u8).unwrap();
-    let target = builder.pure(target.clone()).map_err(|e| {
-        BridgeError::BridgeSerializationError(format!(
-            "Failed to serialize target: {:?}. Err: {:?}",
-            target, e
-        ))
-    })?;
-    let arg_token_type = builder.pure(token_type).unwrap();
-    let amount = builder.pure(amount).unwrap();
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let sender = if from_starcoin {
+        builder.pure_vec_bytes(starcoin_address.as_ref())
+    } else {
+        builder.pure_eth_address(eth_address)
+    };
+    let target_chain = builder.pure_u8(target_chain as u8);
+    let target = if from_starcoin {
+        builder.pure_eth_address(eth_address)
+    } else {
+        builder.pure_vec_bytes(starcoin_address.as_ref())
+    };
+    let arg_token_type = builder.pure_u8(token_type);
+    let amount = builder.pure_u64(amount);
 
     let arg_msg = builder.programmable_move_call(
         BRIDGE_PACKAGE_ID,
@@ -697,7 +1016,12 @@ fn build_token_bridge_approve_transaction(
             ident_str!("claim_and_transfer_token").to_owned(),
             vec![starcoin_bridge_token_type_tags
                 .get(&token_type)
-                .ok_or(BridgeError::UnknownTokenId(token_type))?
+                .ok_or_else(|| {
+                    let mut known: Vec<u8> =
+                        starcoin_bridge_token_type_tags.keys().copied().collect();
+                    known.sort();
+                    BridgeError::UnknownTokenId(token_type, known)
+                })?
                 .clone()],
             vec![arg_bridge, arg_clock, source_chain, seq_num],
         );
@@ -709,7 +1033,7 @@ fn build_token_bridge_approve_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
         rgp,
     ))
 }
@@ -720,6 +1044,7 @@ fn build_emergency_op_approve_transaction(
     action: VerifiedCertifiedBridgeAction,
     bridge_object_arg: ObjectArg,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
 
@@ -731,9 +1056,9 @@ fn build_emergency_op_approve_transaction(
     };
 
     // Unwrap: these should not fail
-    let source_chain = builder.pure(source_chain as u8).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let action_type = builder.pure(action_type as u8).unwrap();
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let action_type = builder.pure_u8(action_type as u8);
     let arg_bridge = builder.obj(bridge_object_arg).unwrap();
 
     let arg_msg = builder.programmable_move_call(
@@ -769,7 +1094,69 @@ fn build_emergency_op_approve_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
+        rgp,
+    ))
+}
+
+fn build_token_pause_approve_transaction(
+    client_address: StarcoinAddress,
+    gas_object_ref: &ObjectRef,
+    action: VerifiedCertifiedBridgeAction,
+    bridge_object_arg: ObjectArg,
+    rgp: u64,
+    gas_budget: u64,
+) -> BridgeResult<TransactionData> {
+    let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let (source_chain, seq_num, token_id, pause) = match bridge_action {
+        BridgeAction::TokenPauseAction(a) => (a.chain_id, a.nonce, a.token_id, a.pause),
+        _ => unreachable!(),
+    };
+
+    // Unwrap: these should not fail
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let token_id = builder.pure_u8(token_id);
+    let pause = builder.pure_bool(pause);
+    let arg_bridge = builder.obj(bridge_object_arg).unwrap();
+
+    let arg_msg = builder.programmable_move_call(
+        BRIDGE_PACKAGE_ID,
+        ident_str!("message").to_owned(),
+        ident_str!("create_token_pause_message").to_owned(),
+        vec![],
+        vec![source_chain, seq_num, token_id, pause],
+    );
+
+    let mut sig_bytes = vec![];
+    for (_, sig) in sigs.signatures {
+        sig_bytes.push(sig.as_bytes().to_vec());
+    }
+    let arg_signatures = builder.pure(sig_bytes.clone()).map_err(|e| {
+        BridgeError::BridgeSerializationError(format!(
+            "Failed to serialize signatures: {:?}. Err: {:?}",
+            sig_bytes, e
+        ))
+    })?;
+
+    builder.programmable_move_call(
+        BRIDGE_PACKAGE_ID,
+        ident_str!("Bridge").to_owned(),
+        ident_str!("execute_system_message").to_owned(),
+        vec![],
+        vec![arg_bridge, arg_msg, arg_signatures],
+    );
+
+    let pt = builder.finish();
+
+    Ok(TransactionData::new_programmable(
+        client_address,
+        vec![*gas_object_ref],
+        pt,
+        gas_budget,
         rgp,
     ))
 }
@@ -780,6 +1167,7 @@ fn build_committee_blocklist_approve_transaction(
     action: VerifiedCertifiedBridgeAction,
     bridge_object_arg: ObjectArg,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
 
@@ -793,9 +1181,9 @@ fn build_committee_blocklist_approve_transaction(
     };
 
     // Unwrap: these should not fail
-    let source_chain = builder.pure(source_chain as u8).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let blocklist_type = builder.pure(blocklist_type as u8).unwrap();
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let blocklist_type = builder.pure_u8(blocklist_type as u8);
     let members_to_update = members_to_update
         .into_iter()
         .map(|m| m.to_eth_address().as_bytes().to_vec())
@@ -836,7 +1224,7 @@ fn build_committee_blocklist_approve_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
         rgp,
     ))
 }
@@ -847,6 +1235,7 @@ fn build_limit_update_approve_transaction(
     action: VerifiedCertifiedBridgeAction,
     bridge_object_arg: ObjectArg,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
 
@@ -860,10 +1249,10 @@ fn build_limit_update_approve_transaction(
     };
 
     // Unwrap: these should not fail
-    let receiving_chain_id = builder.pure(receiving_chain_id as u8).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let sending_chain_id = builder.pure(sending_chain_id as u8).unwrap();
-    let new_usd_limit = builder.pure(new_usd_limit).unwrap();
+    let receiving_chain_id = builder.pure_u8(receiving_chain_id as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let sending_chain_id = builder.pure_u8(sending_chain_id as u8);
+    let new_usd_limit = builder.pure_u64(new_usd_limit);
     let arg_bridge = builder.obj(bridge_object_arg).unwrap();
 
     let arg_msg = builder.programmable_move_call(
@@ -899,7 +1288,7 @@ fn build_limit_update_approve_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
         rgp,
     ))
 }
@@ -910,6 +1299,7 @@ fn build_asset_price_update_approve_transaction(
     action: VerifiedCertifiedBridgeAction,
     bridge_object_arg: ObjectArg,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
 
@@ -923,10 +1313,10 @@ fn build_asset_price_update_approve_transaction(
     };
 
     // Unwrap: these should not fail
-    let source_chain = builder.pure(source_chain as u8).unwrap();
-    let token_id = builder.pure(token_id).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let new_price = builder.pure(new_usd_price).unwrap();
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let token_id = builder.pure_u8(token_id);
+    let seq_num = builder.pure_u64(seq_num);
+    let new_price = builder.pure_u64(new_usd_price);
     let arg_bridge = builder.obj(bridge_object_arg).unwrap();
 
     let arg_msg = builder.programmable_move_call(
@@ -962,7 +1352,7 @@ fn build_asset_price_update_approve_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
         rgp,
     ))
 }
@@ -973,6 +1363,7 @@ pub fn build_add_tokens_on_starcoin_bridge_transaction(
     action: VerifiedCertifiedBridgeAction,
     bridge_object_arg: ObjectArg,
     rgp: u64,
+    gas_budget: u64,
 ) -> BridgeResult<TransactionData> {
     let (bridge_action, sigs) = action.into_inner().into_data_and_sig();
 
@@ -994,10 +1385,10 @@ pub fn build_add_tokens_on_starcoin_bridge_transaction(
         .iter()
         .map(|type_name| type_name.to_canonical_string())
         .collect::<Vec<_>>();
-    let source_chain = builder.pure(source_chain as u8).unwrap();
-    let seq_num = builder.pure(seq_num).unwrap();
-    let native_token = builder.pure(native).unwrap();
-    let token_ids = builder.pure(token_ids).unwrap();
+    let source_chain = builder.pure_u8(source_chain as u8);
+    let seq_num = builder.pure_u64(seq_num);
+    let native_token = builder.pure_bool(native);
+    let token_ids = builder.pure_vec_bytes(&token_ids);
     let token_type_names = builder.pure(token_type_names).unwrap();
     let token_prices = builder.pure(token_prices).unwrap();
 
@@ -1038,7 +1429,7 @@ pub fn build_add_tokens_on_starcoin_bridge_transaction(
         client_address,
         vec![*gas_object_ref],
         pt,
-        100_000_000,
+        gas_budget,
         rgp,
     ))
 }
@@ -1055,14 +1446,8 @@ pub fn build_committee_register_transaction(
     let mut builder = ProgrammableTransactionBuilder::new();
     let system_state = builder.obj(ObjectArg::STARCOIN_SYSTEM_MUT).unwrap();
     let bridge = builder.obj(bridge_object_arg).unwrap();
-    let bridge_pubkey = builder
-        .input(CallArg::Pure(
-            bcs::to_bytes(&bridge_authority_pub_key_bytes).unwrap(),
-        ))
-        .unwrap();
-    let url = builder
-        .input(CallArg::Pure(bcs::to_bytes(bridge_url.as_bytes()).unwrap()))
-        .unwrap();
+    let bridge_pubkey = builder.pure_vec_bytes(&bridge_authority_pub_key_bytes);
+    let url = builder.pure_vec_bytes(bridge_url.as_bytes());
     builder.programmable_move_call(
         BRIDGE_PACKAGE_ID,
         BRIDGE_MODULE_NAME.into(),
@@ -1090,9 +1475,7 @@ pub fn build_committee_update_url_transaction(
 ) -> BridgeResult<TransactionData> {
     let mut builder = ProgrammableTransactionBuilder::new();
     let bridge = builder.obj(bridge_object_arg).unwrap();
-    let url = builder
-        .input(CallArg::Pure(bcs::to_bytes(bridge_url.as_bytes()).unwrap()))
-        .unwrap();
+    let url = builder.pure_vec_bytes(bridge_url.as_bytes());
     builder.programmable_move_call(
         BRIDGE_PACKAGE_ID,
         BRIDGE_MODULE_NAME.into(),
@@ -1110,6 +1493,425 @@ pub fn build_committee_update_url_transaction(
     Ok(data)
 }
 
+#[cfg(test)]
+mod native_tests {
+    use super::starcoin_native::{
+        build_approve_token_transfer, build_claim_and_transfer, build_execute_token_pause,
+        build_register_committee_member, build_send_token, build_update_node_url,
+        send_token_function, CLAIM_TOKEN_FUNCTION, SEND_TOKEN_FUNCTION,
+    };
+    use super::*;
+    use starcoin_bridge_types::transaction::TransactionPayload;
+
+    #[test]
+    fn test_send_token_function() {
+        let token = |s: &str| TypeTag::from_str(s).unwrap();
+        assert_eq!(
+            send_token_function(&token("0x1::USDT::USDT")),
+            Some("send_bridge_usdt")
+        );
+        assert_eq!(
+            send_token_function(&token("0x1::ETH::ETH")),
+            Some("send_bridge_eth")
+        );
+        assert_eq!(send_token_function(&token("0x1::STC::STC")), None);
+        assert_eq!(send_token_function(&token("u64")), None);
+    }
+
+    #[test]
+    fn test_gas_unit_price_with_multiplier() {
+        assert_eq!(gas_unit_price_with_multiplier(1_000, 1.0), 1_000);
+        assert_eq!(gas_unit_price_with_multiplier(1_000, 1.1), 1_100);
+        // Rounded up, so a priority bid is never lost to rounding
+        assert_eq!(gas_unit_price_with_multiplier(1, 1.1), 2);
+        // Never below the reference price
+        assert_eq!(gas_unit_price_with_multiplier(1_000, 0.5), 1_000);
+        assert_eq!(gas_unit_price_with_multiplier(u64::MAX, 2.0), u64::MAX);
+        assert_eq!(gas_unit_price_with_multiplier(10, f64::NAN), 10);
+    }
+
+    #[test]
+    fn test_gas_config_overrides() {
+        let gas_config = GasConfig {
+            max_gas_amount: 5_000_000,
+            gas_unit_price_multiplier: 1.5,
+            gas_budget_overrides: HashMap::from([
+                (BridgeActionType::TokenTransfer, 2_000_000),
+                (BridgeActionType::AddTokensOnstarcoin, 40_000_000),
+            ]),
+        };
+        gas_config.validate().unwrap();
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::TokenTransfer),
+            2_000_000
+        );
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::AddTokensOnstarcoin),
+            40_000_000
+        );
+        // Action types without an override get the default
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::EmergencyButton),
+            5_000_000
+        );
+        assert_eq!(gas_config.gas_unit_price(10), 15);
+
+        // Overrides reach the transactions built for their action type
+        let build_pause = |max_gas_amount| {
+            starcoin_native::build_execute_token_pause(
+                bridge_module_address(),
+                StarcoinAddress::new([7u8; 16]),
+                0,
+                254,
+                1_700_000_000_000,
+                gas_config.gas_unit_price(DEFAULT_GAS_UNIT_PRICE),
+                max_gas_amount,
+                2,
+                5,
+                3,
+                true,
+                vec![1, 2, 3],
+            )
+            .unwrap()
+        };
+        let tx = build_pause(gas_config.max_gas_amount_for(BridgeActionType::TokenPause));
+        assert_eq!(tx.max_gas_amount(), 5_000_000);
+        assert_eq!(tx.gas_unit_price(), 2);
+        let gas_config = GasConfig {
+            gas_budget_overrides: HashMap::from([(BridgeActionType::TokenPause, 300_000)]),
+            ..gas_config.clone()
+        };
+        let tx = build_pause(gas_config.max_gas_amount_for(BridgeActionType::TokenPause));
+        assert_eq!(tx.max_gas_amount(), 300_000);
+    }
+
+    #[test]
+    fn test_gas_config_serde_and_validate() {
+        let gas_config: GasConfig = serde_yaml::from_str(
+            "max-gas-amount: 3000000\n\
+             gas-budget-overrides:\n  TokenTransfer: 1000000\n  LimitUpdate: 200000\n",
+        )
+        .unwrap();
+        assert_eq!(gas_config.max_gas_amount, 3_000_000);
+        assert_eq!(
+            gas_config.gas_unit_price_multiplier,
+            DEFAULT_GAS_PRICE_MULTIPLIER
+        );
+        assert_eq!(
+            gas_config.max_gas_amount_for(BridgeActionType::LimitUpdate),
+            200_000
+        );
+        let yaml = serde_yaml::to_string(&gas_config).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<GasConfig>(&yaml).unwrap(),
+            gas_config
+        );
+
+        let default: GasConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(default, GasConfig::default());
+        assert_eq!(
+            default.max_gas_amount_for(BridgeActionType::TokenTransfer),
+            MAX_GAS_AMOUNT
+        );
+        assert!(
+            serde_yaml::from_str::<GasConfig>("gas-budget-overrides:\n  NotAnAction: 1\n").is_err()
+        );
+
+        let invalid = [
+            GasConfig {
+                max_gas_amount: 0,
+                ..Default::default()
+            },
+            GasConfig {
+                gas_unit_price_multiplier: 0.9,
+                ..Default::default()
+            },
+            GasConfig {
+                gas_unit_price_multiplier: f64::INFINITY,
+                ..Default::default()
+            },
+            GasConfig {
+                gas_budget_overrides: HashMap::from([(BridgeActionType::TokenPause, 0)]),
+                ..Default::default()
+            },
+        ];
+        for gas_config in invalid {
+            assert!(gas_config.validate().is_err(), "{:?}", gas_config);
+        }
+    }
+
+    #[test]
+    fn test_gas_with_margin() {
+        assert_eq!(gas_with_margin(0), 0);
+        assert_eq!(gas_with_margin(100_000), 120_000);
+        // Rounded up
+        assert_eq!(gas_with_margin(1), 2);
+        assert_eq!(gas_with_margin(u64::MAX), u64::MAX);
+    }
+
+    fn script_function(tx: &RawUserTransaction) -> &ScriptFunction {
+        let TransactionPayload::ScriptFunction(f) = tx.payload() else {
+            panic!("expected a script function payload");
+        };
+        f
+    }
+
+    #[test]
+    fn test_build_claim_and_transfer_from_token_map() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let build = |token_id: u8, token_types: &HashMap<u8, TypeTag>| {
+            build_claim_and_transfer(
+                bridge_module_address(),
+                sender,
+                5,
+                254,
+                1_700_000_000_000,
+                DEFAULT_GAS_UNIT_PRICE,
+                MAX_GAS_AMOUNT,
+                1_700_000_000_000,
+                12,
+                9,
+                token_id,
+                token_types,
+            )
+        };
+        // A token added by governance, with no dedicated claim entry
+        let new_token =
+            TypeTag::from_str("0xf8eda27b31a0dcd9b6c06074d74a2c6c::WSTC::WSTC").unwrap();
+        let token_types = HashMap::from([(7, new_token.clone())]);
+        let tx = build(7, &token_types).unwrap();
+        let f = script_function(&tx);
+        assert_eq!(f.module().name().as_str(), "Bridge");
+        assert_eq!(f.function().as_str(), CLAIM_TOKEN_FUNCTION);
+        assert_eq!(f.ty_args(), &[new_token]);
+        assert_eq!(
+            f.args(),
+            &[
+                bcs::to_bytes(&1_700_000_000_000u64).unwrap(),
+                bcs::to_bytes(&12u8).unwrap(),
+                bcs::to_bytes(&9u64).unwrap(),
+            ]
+        );
+
+        // Launch tokens missing from the map fall back to their own entry
+        let tx = build(3, &token_types).unwrap();
+        assert_eq!(
+            script_function(&tx).function().as_str(),
+            "claim_bridge_usdc"
+        );
+        assert!(script_function(&tx).ty_args().is_empty());
+
+        assert_eq!(
+            build(8, &token_types).unwrap_err(),
+            BridgeError::UnknownTokenId(8, vec![1, 2, 3, 4, 7])
+        );
+    }
+
+    #[test]
+    fn test_build_send_token_from_token_map() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let build = |token_type: &TypeTag, token_types: &HashMap<u8, TypeTag>| {
+            build_send_token(
+                bridge_module_address(),
+                sender,
+                5,
+                254,
+                1_700_000_000_000,
+                DEFAULT_GAS_UNIT_PRICE,
+                MAX_GAS_AMOUNT,
+                12,
+                vec![1u8; 20],
+                100,
+                token_type.clone(),
+                token_types,
+            )
+        };
+        let new_token =
+            TypeTag::from_str("0xf8eda27b31a0dcd9b6c06074d74a2c6c::WSTC::WSTC").unwrap();
+        let token_types = HashMap::from([(7, new_token.clone())]);
+        let tx = build(&new_token, &token_types).unwrap();
+        assert_eq!(
+            script_function(&tx).function().as_str(),
+            SEND_TOKEN_FUNCTION
+        );
+        assert_eq!(script_function(&tx).ty_args(), &[new_token.clone()]);
+
+        let eth = TypeTag::from_str("0xf8eda27b31a0dcd9b6c06074d74a2c6c::ETH::ETH").unwrap();
+        let tx = build(&eth, &token_types).unwrap();
+        assert_eq!(script_function(&tx).function().as_str(), "send_bridge_eth");
+
+        assert!(build(&new_token, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_build_approve_token_transfer() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let build = |signatures: Vec<Vec<u8>>| {
+            build_approve_token_transfer(
+                bridge_module_address(),
+                sender,
+                11,
+                254,
+                1_700_000_000_000,
+                DEFAULT_GAS_UNIT_PRICE,
+                MAX_GAS_AMOUNT,
+                12,
+                5,
+                vec![0xab; 20],
+                2,
+                vec![0xcd; 16],
+                3,
+                1_000,
+                signatures,
+            )
+        };
+
+        for n in [1u8, 3, 4, 7] {
+            let signatures: Vec<Vec<u8>> = (0..n).map(|i| vec![i; 65]).collect();
+            let tx = build(signatures.clone()).unwrap();
+            assert_eq!(tx.sender(), sender);
+            assert_eq!(tx.sequence_number(), 11);
+
+            let TransactionPayload::ScriptFunction(f) = tx.payload() else {
+                panic!("expected a script function payload");
+            };
+            assert_eq!(f.module().name().as_str(), "Bridge");
+            assert_eq!(f.function().as_str(), "approve_bridge_token_transfer");
+            let args = f.args();
+            assert_eq!(args.len(), 8);
+            assert_eq!(bcs::from_bytes::<u8>(&args[0]).unwrap(), 12);
+            assert_eq!(bcs::from_bytes::<u64>(&args[1]).unwrap(), 5);
+            assert_eq!(
+                bcs::from_bytes::<Vec<u8>>(&args[2]).unwrap(),
+                vec![0xab; 20]
+            );
+            assert_eq!(bcs::from_bytes::<u8>(&args[3]).unwrap(), 2);
+            assert_eq!(
+                bcs::from_bytes::<Vec<u8>>(&args[4]).unwrap(),
+                vec![0xcd; 16]
+            );
+            assert_eq!(bcs::from_bytes::<u8>(&args[5]).unwrap(), 3);
+            assert_eq!(bcs::from_bytes::<u64>(&args[6]).unwrap(), 1_000);
+            assert_eq!(
+                bcs::from_bytes::<Vec<Vec<u8>>>(&args[7]).unwrap(),
+                signatures
+            );
+        }
+
+        let err = build(vec![]).unwrap_err();
+        assert!(
+            matches!(&err, BridgeError::Permanent(msg) if msg.contains("At least one signature")),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_build_execute_token_pause() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let tx = build_execute_token_pause(
+            bridge_module_address(),
+            sender,
+            11,
+            254,
+            1_700_000_000_000,
+            1_000,
+            2_000_000,
+            2,
+            5,
+            3,
+            true,
+            vec![1, 2, 3],
+        )
+        .unwrap();
+        assert_eq!(tx.sender(), sender);
+        assert_eq!(tx.sequence_number(), 11);
+        assert_eq!(tx.chain_id().id(), 254);
+        assert_eq!(tx.gas_unit_price(), 1_000);
+        assert_eq!(tx.max_gas_amount(), 2_000_000);
+        assert_eq!(tx.expiration_timestamp_secs(), 1_700_000_000 + 3_600);
+
+        let TransactionPayload::ScriptFunction(f) = tx.payload() else {
+            panic!("expected a script function payload");
+        };
+        assert_eq!(f.module().address(), &bridge_module_address());
+        assert_eq!(f.module().name().as_str(), "Bridge");
+        assert_eq!(f.function().as_str(), "execute_token_pause_single");
+        assert!(f.ty_args().is_empty());
+        assert_eq!(
+            f.args(),
+            &[
+                vec![2],
+                5u64.to_le_bytes().to_vec(),
+                vec![3],
+                vec![1],
+                vec![3, 1, 2, 3],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_register_committee_member() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let pubkey = [2u8; 33];
+        let tx = build_register_committee_member(
+            bridge_module_address(),
+            sender,
+            9,
+            254,
+            1_700_000_000_000,
+            DEFAULT_GAS_UNIT_PRICE,
+            MAX_GAS_AMOUNT,
+            &pubkey,
+            "https://node.example.com:9191",
+        )
+        .unwrap();
+        assert_eq!(tx.sender(), sender);
+        assert_eq!(tx.sequence_number(), 9);
+
+        let TransactionPayload::ScriptFunction(f) = tx.payload() else {
+            panic!("expected a script function payload");
+        };
+        assert_eq!(f.module().name().as_str(), "Bridge");
+        assert_eq!(f.function().as_str(), "register_committee_member");
+        assert_eq!(
+            f.args(),
+            &[
+                bcs::to_bytes(pubkey.as_slice()).unwrap(),
+                bcs::to_bytes(b"https://node.example.com:9191".as_slice()).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_update_node_url() {
+        let sender = StarcoinAddress::new([7u8; 16]);
+        let tx = build_update_node_url(
+            bridge_module_address(),
+            sender,
+            4,
+            254,
+            1_700_000_000_000,
+            DEFAULT_GAS_UNIT_PRICE,
+            MAX_GAS_AMOUNT,
+            "https://node.example.com:9191",
+        )
+        .unwrap();
+        assert_eq!(tx.sender(), sender);
+        assert_eq!(tx.sequence_number(), 4);
+
+        let TransactionPayload::ScriptFunction(f) = tx.payload() else {
+            panic!("expected a script function payload");
+        };
+        assert_eq!(f.module().name().as_str(), "Bridge");
+        assert_eq!(f.function().as_str(), "update_committee_member_url");
+        assert_eq!(
+            f.args(),
+            &[bcs::to_bytes(b"https://node.example.com:9191".as_slice()).unwrap()]
+        );
+    }
+}
+
 // TODO: These tests are temporarily disabled because they require TestClusterWrapperBuilder
 // from e2e_tests module, which depends on full Starcoin testnet infrastructure.
 // Re-enable when e2e test infrastructure is available.
@@ -6,6 +6,7 @@
 use crate::abi::EthStarcoinBridge;
 use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::starcoin_bridge_watchdog::Observable;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use ethers::providers::Provider;
 use ethers::types::Address as EthAddress;
@@ -17,6 +18,7 @@ use tracing::{error, info};
 pub struct EthBridgeStatus {
     bridge_contract: EthStarcoinBridge<Provider<MeteredEthHttpProvier>>,
     metric: IntGauge,
+    paused: Arc<ArcSwap<Option<bool>>>,
 }
 
 impl EthBridgeStatus {
@@ -24,11 +26,13 @@ impl EthBridgeStatus {
         provider: Arc<Provider<MeteredEthHttpProvier>>,
         bridge_address: EthAddress,
         metric: IntGauge,
+        paused: Arc<ArcSwap<Option<bool>>>,
     ) -> Self {
         let bridge_contract = EthStarcoinBridge::new(bridge_address, provider.clone());
         Self {
             bridge_contract,
             metric,
+            paused,
         }
     }
 }
@@ -44,6 +48,7 @@ impl Observable for EthBridgeStatus {
         match status {
             Ok(status) => {
                 self.metric.set(status as i64);
+                self.paused.store(Arc::new(Some(status)));
                 info!("Eth Bridge Status: {:?}", status);
             }
             Err(e) => {
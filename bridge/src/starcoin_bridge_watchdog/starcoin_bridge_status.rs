@@ -1,10 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-//! The StarcoinBridgeStatus observable monitors whether the Starcoin Bridge is paused.
+//! The StarcoinBridgeStatus observable monitors whether the Starcoin Bridge is paused,
+//! globally or for individual tokens.
 
 use crate::starcoin_bridge_client::StarcoinBridgeClient;
 use crate::starcoin_bridge_watchdog::Observable;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use prometheus::IntGauge;
 use std::sync::Arc;
@@ -15,13 +17,22 @@ use tracing::{error, info};
 pub struct StarcoinBridgeStatus {
     starcoin_bridge_client: Arc<StarcoinBridgeClient>,
     metric: IntGauge,
+    paused_token_ids: Arc<ArcSwap<Vec<u8>>>,
+    paused: Arc<ArcSwap<Option<bool>>>,
 }
 
 impl StarcoinBridgeStatus {
-    pub fn new(starcoin_bridge_client: Arc<StarcoinBridgeClient>, metric: IntGauge) -> Self {
+    pub fn new(
+        starcoin_bridge_client: Arc<StarcoinBridgeClient>,
+        metric: IntGauge,
+        paused_token_ids: Arc<ArcSwap<Vec<u8>>>,
+        paused: Arc<ArcSwap<Option<bool>>>,
+    ) -> Self {
         Self {
             starcoin_bridge_client,
             metric,
+            paused_token_ids,
+            paused,
         }
     }
 }
@@ -33,11 +44,18 @@ impl Observable for StarcoinBridgeStatus {
     }
 
     async fn observe_and_report(&self) {
-        let status = self.starcoin_bridge_client.is_bridge_paused().await;
-        match status {
-            Ok(status) => {
+        let summary = self.starcoin_bridge_client.get_bridge_summary().await;
+        match summary {
+            Ok(summary) => {
+                let status = summary.is_frozen;
                 self.metric.set(status as i64);
-                info!("Starcoin Bridge Status: {:?}", status);
+                self.paused.store(Arc::new(Some(status)));
+                info!(
+                    "Starcoin Bridge Status: {:?}, paused tokens: {:?}",
+                    status, summary.treasury.paused_token_ids
+                );
+                self.paused_token_ids
+                    .store(Arc::new(summary.treasury.paused_token_ids));
             }
             Err(e) => {
                 error!("Error getting starcoin bridge status: {:?}", e);
@@ -185,7 +185,7 @@ where
         self.inner
             .get_bridge_summary()
             .await
-            .map_err(|e| BridgeError::InternalError(format!("Can't get bridge committee: {e}")))
+            .map_err(|e| BridgeError::transient_rpc(format!("Can't get bridge committee: {e}")))
     }
 
     pub async fn is_bridge_paused(&self) -> BridgeResult<bool> {
@@ -244,7 +244,7 @@ where
     pub async fn get_bridge_committee(&self) -> BridgeResult<BridgeCommittee> {
         let bridge_summary =
             self.inner.get_bridge_summary().await.map_err(|e| {
-                BridgeError::InternalError(format!("Can't get bridge committee: {e}"))
+                BridgeError::transient_rpc(format!("Can't get bridge committee: {e}"))
             })?;
         let move_type_bridge_committee = bridge_summary.committee;
 
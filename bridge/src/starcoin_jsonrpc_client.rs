@@ -1,23 +1,24 @@
 // StarcoinClientInner implementation using simple JSON-RPC
 // This completely replaces the starcoin-rpc-client SDK
 
+use crate::config::StarcoinRpcRetryConfig;
 use crate::error::BridgeError;
-use crate::simple_starcoin_rpc::SimpleStarcoinRpcClient;
+use crate::metrics::BridgeMetrics;
+use crate::simple_starcoin_rpc::{is_retryable_rpc_error, SimpleStarcoinRpcClient};
 use crate::starcoin_bridge_client::StarcoinClientInner;
 use async_trait::async_trait;
 use starcoin_bridge_json_rpc_types::{
-    EventFilter, EventPage, StarcoinEvent, StarcoinExecutionStatus,
+    EventCursor, EventFilter, EventPage, StarcoinEvent, StarcoinExecutionStatus,
     StarcoinTransactionBlockEffects, StarcoinTransactionBlockResponse,
 };
-// Use the tuple EventID type from starcoin_bridge_types
 use starcoin_bridge_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
 use starcoin_bridge_types::bridge::{
     BridgeSummary, MoveTypeParsedTokenTransferMessage, MoveTypeTokenTransferPayload,
 };
-use starcoin_bridge_types::event::EventID;
 use starcoin_bridge_types::gas_coin::GasCoin;
 use starcoin_bridge_types::object::Owner;
 use starcoin_bridge_types::transaction::{ObjectArg, Transaction};
+use std::sync::Arc;
 
 use crate::types::BridgeActionStatus;
 
@@ -43,6 +44,28 @@ impl JsonValueExt for serde_json::Value {
     }
 }
 
+/// Converts the raw event views emitted by a transaction into `StarcoinEvent`s.
+/// Events of any module are kept, so callers can filter them. Events that
+/// can't be parsed, e.g. because of an unknown type tag, are skipped with a
+/// warning rather than failing the whole transaction.
+pub(crate) fn events_from_rpc(
+    raw_events: &[serde_json::Value],
+    tx_digest: TransactionDigest,
+) -> Vec<StarcoinEvent> {
+    raw_events
+        .iter()
+        .filter_map(
+            |event_value| match StarcoinEvent::try_from_rpc_event(event_value, tx_digest) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    tracing::warn!("Failed to parse event: {:?}, error: {}", event_value, e);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct StarcoinJsonRpcClient {
     rpc: SimpleStarcoinRpcClient,
@@ -55,6 +78,20 @@ impl StarcoinJsonRpcClient {
         }
     }
 
+    /// A client whose calls are retried and rate limited per `rpc_retry`,
+    /// with retried calls counted in `starcoin_bridge_rpc_errors`
+    pub fn with_rpc_retry(
+        rpc_url: &str,
+        bridge_address: &str,
+        rpc_retry: &StarcoinRpcRetryConfig,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            rpc: SimpleStarcoinRpcClient::new(rpc_url, bridge_address)
+                .with_rpc_retry(rpc_retry, metrics),
+        }
+    }
+
     /// Get the underlying RPC client
     pub fn rpc(&self) -> &SimpleStarcoinRpcClient {
         &self.rpc
@@ -138,8 +175,8 @@ impl StarcoinJsonRpcClient {
     ) -> Result<BridgeSummary, JsonRpcError> {
         use starcoin_bridge_types::base_types::StarcoinAddress;
         use starcoin_bridge_types::bridge::{
-            BridgeCommitteeSummary, BridgeLimiterSummary, BridgeTokenMetadata,
-            BridgeTreasurySummary, MoveTypeCommitteeMember,
+            BridgeChainId, BridgeCommitteeSummary, BridgeLimiterSummary, BridgeTokenMetadata,
+            BridgeTreasurySummary, MoveTypeBridgeTransferRecord, MoveTypeCommitteeMember,
         };
 
         // The RPC response has structure: { "json": { "inner": { ... } }, "raw": "..." }
@@ -247,7 +284,21 @@ impl StarcoinJsonRpcClient {
         {
             for entry in tokens_data {
                 let token_type = entry.get("key").and_then(|k| k.as_str()).unwrap_or("");
-                supported_tokens.push((token_type.to_string(), BridgeTokenMetadata::default()));
+                // Structure: { "id": 3, "decimal_multiplier": 1000000, "notional_value": 10000, "native_token": false }
+                let metadata = entry.get("value").unwrap_or(&serde_json::Value::Null);
+                let field = |name: &str| metadata.get(name).and_then(|v| v.as_u64_flex());
+                supported_tokens.push((
+                    token_type.to_string(),
+                    BridgeTokenMetadata {
+                        id: field("id").unwrap_or(0) as u8,
+                        decimal_multiplier: field("decimal_multiplier").unwrap_or(0),
+                        notional_value: field("notional_value").unwrap_or(0),
+                        native_token: metadata
+                            .get("native_token")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                    },
+                ));
             }
         }
 
@@ -267,10 +318,82 @@ impl StarcoinJsonRpcClient {
             }
         }
 
+        // Per-token pause flags, either a plain list of ids or a VecMap of id -> paused
+        // Structure: { "treasury": { "paused_tokens": [3] } } or { "paused_tokens": { "data": [...] } }
+        let mut paused_token_ids = vec![];
+        match treasury.get("paused_tokens") {
+            Some(serde_json::Value::Array(ids)) => {
+                paused_token_ids.extend(ids.iter().filter_map(|v| v.as_u64()).map(|id| id as u8));
+            }
+            Some(paused) => {
+                if let Some(paused_data) = paused.get("data").and_then(|d| d.as_array()) {
+                    for entry in paused_data {
+                        let is_paused = entry
+                            .get("value")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        if let Some(id) = entry.get("key").and_then(|k| k.as_u64()) {
+                            if is_paused {
+                                paused_token_ids.push(id as u8);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+
         let treasury_summary = BridgeTreasurySummary {
             supported_tokens,
             id_token_type_map,
+            paused_token_ids,
+        };
+
+        // Parse limiter
+        // Structure: { "limiter": { "transfer_limits": { "data": [ { "key": { "source": 12, "destination": 2 }, "value": 5000000 } ] },
+        //                           "transfer_records": { "data": [ { "key": { ... }, "value": { "hour_head": ..., ... } } ] } } }
+        let limiter = inner.get("limiter").unwrap_or(&serde_json::Value::Null);
+        let limiter_entries = |name: &str| {
+            limiter
+                .get(name)
+                .and_then(|m| m.get("data"))
+                .and_then(|d| d.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| {
+                    let route = entry.get("key")?;
+                    let chain = |name: &str| {
+                        let id = route.get(name)?.as_u64_flex()?;
+                        BridgeChainId::try_from(u8::try_from(id).ok()?).ok()
+                    };
+                    Some((chain("source")?, chain("destination")?, entry.get("value")?))
+                })
         };
+        let transfer_limit = limiter_entries("transfer_limits")
+            .filter_map(|(source, destination, limit)| {
+                Some((source, destination, limit.as_u64_flex()?))
+            })
+            .collect();
+        let transfer_records = limiter_entries("transfer_records")
+            .map(|(source, destination, record)| {
+                let field = |name: &str| record.get(name).and_then(|v| v.as_u64_flex());
+                let per_hour_amounts = record
+                    .get("per_hour_amounts")
+                    .and_then(|v| v.as_array())
+                    .map(|amounts| amounts.iter().filter_map(|a| a.as_u64_flex()).collect())
+                    .unwrap_or_default();
+                (
+                    source,
+                    destination,
+                    MoveTypeBridgeTransferRecord::new(
+                        field("hour_head").unwrap_or(0),
+                        field("hour_tail").unwrap_or(0),
+                        per_hour_amounts,
+                        field("total_amount").unwrap_or(0),
+                    ),
+                )
+            })
+            .collect();
 
         // Parse sequence_nums
         let mut sequence_nums = vec![];
@@ -294,25 +417,52 @@ impl StarcoinJsonRpcClient {
             committee: committee_summary,
             treasury: treasury_summary,
             bridge_records_id: [0u8; 32], // Default to zero
-            limiter: BridgeLimiterSummary::default(),
+            limiter: BridgeLimiterSummary {
+                transfer_limit,
+                transfer_records,
+            },
             is_frozen,
         })
     }
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error("{0}")]
-pub struct JsonRpcError(String);
+#[error("{message}")]
+pub struct JsonRpcError {
+    message: String,
+    retryable: bool,
+}
+
+impl JsonRpcError {
+    // The node answered, but not with what the bridge expects
+    fn unexpected_response(message: &str) -> Self {
+        JsonRpcError {
+            message: message.to_string(),
+            retryable: false,
+        }
+    }
+}
 
 impl From<anyhow::Error> for JsonRpcError {
     fn from(e: anyhow::Error) -> Self {
-        JsonRpcError(e.to_string())
+        JsonRpcError {
+            retryable: is_retryable_rpc_error(&e),
+            message: e.to_string(),
+        }
     }
 }
 
 impl From<serde_json::Error> for JsonRpcError {
     fn from(e: serde_json::Error) -> Self {
-        JsonRpcError(e.to_string())
+        JsonRpcError::unexpected_response(&e.to_string())
+    }
+}
+
+// Classifies an error of `SimpleStarcoinRpcClient`, prefixed with `context`
+fn rpc_error(context: &str, e: anyhow::Error) -> BridgeError {
+    BridgeError::Rpc {
+        source: format!("{context}: {e}"),
+        retryable: is_retryable_rpc_error(&e),
     }
 }
 
@@ -323,6 +473,13 @@ const MAX_BLOCK_RANGE: u64 = 32;
 impl StarcoinClientInner for StarcoinJsonRpcClient {
     type Error = JsonRpcError;
 
+    fn rpc_error(error: Self::Error) -> BridgeError {
+        BridgeError::Rpc {
+            source: error.message,
+            retryable: error.retryable,
+        }
+    }
+
     fn bridge_address(&self) -> &str {
         self.rpc.bridge_address()
     }
@@ -330,7 +487,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
     async fn query_events(
         &self,
         query: EventFilter,
-        cursor: Option<EventID>,
+        cursor: Option<EventCursor>,
     ) -> Result<EventPage, Self::Error> {
         // Get current block height from chain
         let chain_info = self.rpc.chain_info().await?;
@@ -342,9 +499,9 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
             .unwrap_or(0);
 
         // Apply cursor as from_block if provided
-        // EventID is (block_number, event_index) tuple
+        // The cursor position is a (block_number, event_index) tuple
         let mut filter = query.clone();
-        let from_block = if let Some((block_num, _event_idx)) = cursor {
+        let from_block = if let Some((block_num, _event_idx)) = cursor.map(|c| c.position()) {
             // Start from the next block after cursor (cursor is exclusive)
             block_num.saturating_add(1)
         } else {
@@ -353,10 +510,11 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
 
         // Ensure from_block doesn't exceed current block
         if from_block > current_block {
-            // No new blocks to query
+            // No new blocks to query. Re-issue the cursor so a legacy one is
+            // replaced by the current format.
             return Ok(EventPage {
                 data: vec![],
-                next_cursor: cursor,
+                next_cursor: cursor.map(|c| EventCursor::new(c.position(), &query)),
                 has_next_page: false,
             });
         }
@@ -374,6 +532,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
         if filter.limit.is_none() {
             filter.limit = Some(100);
         }
+        let limit = filter.limit.unwrap_or(100);
 
         tracing::debug!(
             from_block = from_block,
@@ -419,10 +578,29 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
         }
 
         // Determine if there are more blocks to query
-        let has_next_page = to_block < current_block;
+        let mut has_next_page = to_block < current_block;
 
         // Next cursor: use last queried block for next iteration
-        let next_cursor: Option<EventID> = Some((to_block, 0));
+        let mut next_cursor = Some(EventCursor::new((to_block, 0), &query));
+
+        // A full page may have left out events of its last block and of the
+        // blocks after it. Resume from that block rather than past the range,
+        // dropping its events here so they are only returned once. Events
+        // the caller filters out count towards the limit too, so this can
+        // happen with few events of interest.
+        if raw_events.len() >= limit && filter.reverse != Some(true) {
+            if last_block_num > from_block {
+                events.retain(|event| event.id.block_number < last_block_num);
+                next_cursor = Some(EventCursor::new((last_block_num - 1, 0), &query));
+                has_next_page = true;
+            } else {
+                tracing::warn!(
+                    block = last_block_num,
+                    limit,
+                    "Block has more events than fit in a page, the rest are skipped"
+                );
+            }
+        }
 
         Ok(EventPage {
             data: events,
@@ -437,18 +615,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
     ) -> Result<Vec<StarcoinEvent>, Self::Error> {
         let tx_hash = format!("0x{}", hex::encode(tx_digest));
         let raw_events = self.rpc.get_events_by_txn_hash(&tx_hash).await?;
-
-        // Parse each event from RPC response into StarcoinEvent
-        let mut events = Vec::new();
-        for event_value in raw_events {
-            match StarcoinEvent::try_from_rpc_event(&event_value, tx_digest) {
-                Ok(event) => events.push(event),
-                Err(e) => {
-                    tracing::warn!("Failed to parse event: {:?}, error: {}", event_value, e);
-                }
-            }
-        }
-        Ok(events)
+        Ok(events_from_rpc(&raw_events, tx_digest))
     }
 
     async fn get_chain_identifier(&self) -> Result<String, Self::Error> {
@@ -456,7 +623,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
         let chain_id = chain_info
             .get("chain_id")
             .and_then(|v| v.as_u64())
-            .ok_or_else(|| JsonRpcError("Missing chain_id".into()))?;
+            .ok_or_else(|| JsonRpcError::unexpected_response("Missing chain_id"))?;
         Ok(format!("{}", chain_id))
     }
 
@@ -474,7 +641,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
                 n.as_u64()
                     .or_else(|| n.as_str().and_then(|s| s.parse().ok()))
             })
-            .ok_or_else(|| JsonRpcError("Missing block number".into()))?;
+            .ok_or_else(|| JsonRpcError::unexpected_response("Missing block number"))?;
         Ok(block_number)
     }
 
@@ -510,7 +677,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
             .rpc
             .submit_and_wait_transaction(&signed_txn_hex)
             .await
-            .map_err(|e| BridgeError::Generic(format!("Transaction execution failed: {}", e)))?;
+            .map_err(|e| rpc_error("Transaction execution failed", e))?;
 
         // Parse the response into StarcoinTransactionBlockResponse
         let tx_hash = txn_info
@@ -733,7 +900,7 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
     async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
-    ) -> (GasCoin, ObjectRef, Owner) {
+    ) -> Result<(GasCoin, ObjectRef, Owner), BridgeError> {
         // Query account balance for the gas object
         // For Starcoin, gas is STC balance, not a separate object
         // We return a dummy value since Starcoin handles gas differently
@@ -743,21 +910,21 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
         let object_ref = (gas_object_id, 1u64, [0u8; 32]);
         let owner = Owner::AddressOwner(starcoin_bridge_types::base_types::StarcoinAddress::ZERO);
 
-        (gas_coin, object_ref, owner)
+        Ok((gas_coin, object_ref, owner))
     }
 
     async fn get_sequence_number(&self, address: &str) -> Result<u64, BridgeError> {
         self.rpc
             .get_sequence_number(address)
             .await
-            .map_err(|e| BridgeError::Generic(format!("Failed to get sequence number: {}", e)))
+            .map_err(|e| rpc_error("Failed to get sequence number", e))
     }
 
     async fn get_block_timestamp(&self) -> Result<u64, BridgeError> {
         self.rpc
             .get_block_timestamp()
             .await
-            .map_err(|e| BridgeError::Generic(format!("Failed to get block timestamp: {}", e)))
+            .map_err(|e| rpc_error("Failed to get block timestamp", e))
     }
 
     async fn sign_and_submit_transaction(
@@ -768,8 +935,40 @@ impl StarcoinClientInner for StarcoinJsonRpcClient {
         self.rpc
             .sign_and_submit_transaction(key, raw_txn)
             .await
-            .map_err(|e| {
-                BridgeError::Generic(format!("Failed to sign and submit transaction: {}", e))
-            })
+            .map_err(|e| rpc_error("Failed to sign and submit transaction", e))
+    }
+
+    async fn dry_run_transaction(
+        &self,
+        key: &starcoin_bridge_types::crypto::StarcoinKeyPair,
+        raw_txn: starcoin_bridge_types::transaction::RawUserTransaction,
+    ) -> Result<serde_json::Value, BridgeError> {
+        self.rpc
+            .dry_run_raw_transaction(key, raw_txn)
+            .await
+            .map_err(|e| rpc_error("Failed to dry run transaction", e))
+    }
+
+    async fn get_transaction_info(
+        &self,
+        txn_hash: &str,
+    ) -> Result<Option<serde_json::Value>, BridgeError> {
+        let txn_info = self
+            .rpc
+            .get_transaction_info(txn_hash)
+            .await
+            .map_err(|e| rpc_error("Failed to get transaction info", e))?;
+        Ok((!txn_info.is_null()).then_some(txn_info))
+    }
+
+    async fn get_module_bytes(
+        &self,
+        address: &str,
+        module: &str,
+    ) -> Result<Option<Vec<u8>>, BridgeError> {
+        self.rpc
+            .get_module(address, module)
+            .await
+            .map_err(|e| rpc_error(&format!("Failed to get module {address}::{module}"), e))
     }
 }
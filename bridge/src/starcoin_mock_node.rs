@@ -0,0 +1,983 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process Starcoin node serving the JSON-RPC methods
+//! `StarcoinJsonRpcClient` calls, backed by state the test programs. Unlike
+//! `StarcoinMockClient`, which stands in for the client, requests go through
+//! `SimpleStarcoinRpcClient` over HTTP, so the JSON (de)serialization of the
+//! client is exercised too.
+//!
+//! Served methods: `chain.info`, `node.info`, `chain.get_events`,
+//! `chain.get_events_by_txn_hash`, `chain.get_transaction_info`,
+//! `contract.call_v2`, `state.get_resource`, `txpool.next_sequence_number`,
+//! `txpool.gas_price` and `txpool.submit_hex_transaction`. Faults of a
+//! `FaultInjector` are keyed by these method names and answered with a
+//! JSON-RPC error.
+
+use crate::fault_injection::FaultInjector;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use move_core_types::language_storage::StructTag;
+use serde_json::{json, Value};
+use starcoin_bridge_json_rpc_types::{EventFilter, StarcoinEvent};
+use starcoin_bridge_types::base_types::{StarcoinAddress, TransactionDigest};
+use starcoin_bridge_types::bridge::{
+    BridgeChainId, BridgeCommitteeSummary, BridgeLimiterSummary, BridgeSummary,
+    BridgeTreasurySummary,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+// The bridge address `StarcoinMockClient` reports, so events built for one
+// work with the other
+pub const MOCK_NODE_BRIDGE_ADDRESS: &str = "0x0b8e0206e990e41e913a7f03d1c60675";
+
+// Chain id of dev nodes
+const DEV_CHAIN_ID: u8 = 254;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug)]
+struct SubmittedTransaction {
+    sender: StarcoinAddress,
+    sequence_number: u64,
+    info: Value,
+}
+
+#[derive(Debug)]
+struct NodeState {
+    chain_id: u8,
+    head: u64,
+    now_seconds: u64,
+    gas_price: u64,
+    // Events in emission order
+    events: Vec<StarcoinEvent>,
+    bridge_summary: BridgeSummary,
+    // (address, resource type) -> decoded resource view
+    resources: HashMap<(StarcoinAddress, String), Value>,
+    // (module::function, args) -> return values
+    call_results: HashMap<(String, Vec<String>), Value>,
+    sequence_numbers: HashMap<StarcoinAddress, u64>,
+    // Status and gas used of the transactions submitted or dry run from now
+    // on
+    execution_status: Value,
+    gas_used: u64,
+    // Events of the transactions submitted from now on
+    transaction_events: Vec<StarcoinEvent>,
+    transactions: HashMap<String, SubmittedTransaction>,
+    submitted: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StarcoinMockNode {
+    url: String,
+    state: Arc<Mutex<NodeState>>,
+    fault_injector: Arc<Mutex<Option<Arc<FaultInjector>>>>,
+}
+
+impl StarcoinMockNode {
+    // Starts serving on a free local port, as a dev node at block 0 whose
+    // bridge has an empty committee and treasury. The server runs until the
+    // test's runtime shuts down.
+    pub async fn start() -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let node = Self {
+            url,
+            state: Arc::new(Mutex::new(NodeState {
+                chain_id: DEV_CHAIN_ID,
+                head: 0,
+                now_seconds: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+                gas_price: 1,
+                events: vec![],
+                bridge_summary: BridgeSummary {
+                    bridge_version: 1,
+                    message_version: 1,
+                    chain_id: 2,
+                    sequence_nums: vec![],
+                    committee: BridgeCommitteeSummary::default(),
+                    treasury: BridgeTreasurySummary::default(),
+                    bridge_records_id: [0u8; 32],
+                    limiter: BridgeLimiterSummary::default(),
+                    is_frozen: false,
+                },
+                resources: HashMap::new(),
+                call_results: HashMap::new(),
+                sequence_numbers: HashMap::new(),
+                execution_status: json!("Executed"),
+                gas_used: 0,
+                transaction_events: vec![],
+                transactions: HashMap::new(),
+                submitted: vec![],
+            })),
+            fault_injector: Default::default(),
+        };
+        let router = Router::new()
+            .route("/", post(handle_request))
+            .with_state(node.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        node
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn bridge_address(&self) -> &str {
+        MOCK_NODE_BRIDGE_ADDRESS
+    }
+
+    // Makes subsequent requests subject to the injector's faults.
+    pub fn set_fault_injector(&self, injector: Arc<FaultInjector>) {
+        self.fault_injector.lock().unwrap().replace(injector);
+    }
+
+    pub fn set_chain_id(&self, chain_id: u8) {
+        self.state.lock().unwrap().chain_id = chain_id;
+    }
+
+    pub fn set_head(&self, head: u64) {
+        self.state.lock().unwrap().head = head;
+    }
+
+    pub fn set_gas_price(&self, gas_price: u64) {
+        self.state.lock().unwrap().gas_price = gas_price;
+    }
+
+    // Emits `event` in the block and transaction of its id. The head moves up
+    // to the event's block.
+    pub fn add_event(&self, event: StarcoinEvent) {
+        let mut state = self.state.lock().unwrap();
+        state.head = state.head.max(event.id.block_number);
+        state.events.push(event);
+    }
+
+    // Replaces the events of transaction `tx_digest`, keeping their order.
+    // Unlike `add_event`, the ids of `events` are not looked at.
+    pub fn add_events_by_tx_digest(
+        &self,
+        tx_digest: TransactionDigest,
+        events: Vec<StarcoinEvent>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.events.retain(|event| event.id.tx_digest != tx_digest);
+        state
+            .events
+            .extend(events.into_iter().enumerate().map(|(seq, mut event)| {
+                event.id.tx_digest = tx_digest;
+                event.id.event_seq = seq as u64;
+                event
+            }));
+    }
+
+    // The bridge resource is rendered from `summary`, the way
+    // `StarcoinJsonRpcClient` parses it
+    pub fn set_bridge_summary(&self, summary: BridgeSummary) {
+        self.state.lock().unwrap().bridge_summary = summary;
+    }
+
+    pub fn set_bridge_committee(&self, committee: BridgeCommitteeSummary) {
+        self.state.lock().unwrap().bridge_summary.committee = committee;
+    }
+
+    pub fn set_paused_token_ids(&self, token_ids: Vec<u8>) {
+        self.state
+            .lock()
+            .unwrap()
+            .bridge_summary
+            .treasury
+            .paused_token_ids = token_ids;
+    }
+
+    pub fn set_limiter(&self, limiter: BridgeLimiterSummary) {
+        self.state.lock().unwrap().bridge_summary.limiter = limiter;
+    }
+
+    // Sets the decoded view `state.get_resource` returns
+    pub fn set_resource(&self, address: StarcoinAddress, resource_type: &str, resource: Value) {
+        self.state
+            .lock()
+            .unwrap()
+            .resources
+            .insert((address, resource_type.to_string()), resource);
+    }
+
+    // Return values of `contract.call_v2` for `function`, as
+    // `Module::function`, called with `args` such as `["12u8", "0u64"]`.
+    // Calls without a result fail like calls of a missing function.
+    pub fn set_call_result(&self, function: &str, args: &[&str], result: Value) {
+        self.state.lock().unwrap().call_results.insert(
+            (
+                function.to_string(),
+                args.iter().map(|arg| arg.to_string()).collect(),
+            ),
+            result,
+        );
+    }
+
+    // Next sequence number of `address` in the pool. Addresses without one
+    // answer null, and the client falls back to the account resource.
+    pub fn set_sequence_number(&self, address: StarcoinAddress, sequence_number: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .sequence_numbers
+            .insert(address, sequence_number);
+    }
+
+    // Status of the transactions submitted from now on, e.g. "Executed" or
+    // {"MoveAbort": {...}}
+    pub fn set_execution_status(&self, status: Value) {
+        self.state.lock().unwrap().execution_status = status;
+    }
+
+    // Gas used by the transactions submitted or dry run from now on
+    pub fn set_gas_used(&self, gas_used: u64) {
+        self.state.lock().unwrap().gas_used = gas_used;
+    }
+
+    // Events emitted by each transaction submitted from now on, in the
+    // transaction's block. As with `add_events_by_tx_digest`, the ids of
+    // `events` are not looked at.
+    pub fn set_transaction_events(&self, events: Vec<StarcoinEvent>) {
+        self.state.lock().unwrap().transaction_events = events;
+    }
+
+    // Sender and sequence number of the transactions accepted, in submission
+    // order
+    pub fn submitted_transactions(&self) -> Vec<(StarcoinAddress, u64)> {
+        let state = self.state.lock().unwrap();
+        state
+            .submitted
+            .iter()
+            .map(|hash| {
+                let txn = &state.transactions[hash];
+                (txn.sender, txn.sequence_number)
+            })
+            .collect()
+    }
+
+    async fn handle(&self, method: &str, params: &[Value]) -> Result<Value, (i64, String)> {
+        let faults = self.fault_injector.lock().unwrap().clone();
+        if let Some(faults) = &faults {
+            faults
+                .on_call(method)
+                .await
+                .map_err(|e| (SERVER_ERROR, e))?;
+        }
+        let param = |i: usize| {
+            params
+                .get(i)
+                .ok_or_else(|| (INVALID_PARAMS, format!("Missing param {i}")))
+        };
+        let str_param = |i: usize| {
+            param(i)?
+                .as_str()
+                .ok_or_else(|| (INVALID_PARAMS, format!("Param {i} is not a string")))
+        };
+        let mut state = self.state.lock().unwrap();
+        match method {
+            "chain.info" => {
+                let head = faults.map_or(state.head, |faults| faults.head(state.head));
+                Ok(json!({
+                    "chain_id": state.chain_id,
+                    "head": {"number": head.to_string()},
+                }))
+            }
+            "node.info" => Ok(json!({
+                "net": state.chain_id.to_string(),
+                "now_seconds": state.now_seconds,
+            })),
+            "txpool.gas_price" => Ok(json!(state.gas_price.to_string())),
+            "chain.get_events" => {
+                let filter: EventFilter = serde_json::from_value(param(0)?.clone())
+                    .map_err(|e| (INVALID_PARAMS, format!("Invalid event filter: {e}")))?;
+                let mut events = state.get_events(&filter)?;
+                if let Some(faults) = &faults {
+                    faults.reorder(&mut events);
+                }
+                Ok(Value::Array(events))
+            }
+            "chain.get_events_by_txn_hash" => {
+                let tx_digest = parse_hash(str_param(0)?)?;
+                Ok(Value::Array(
+                    state
+                        .events
+                        .iter()
+                        .filter(|event| event.id.tx_digest == tx_digest)
+                        .map(event_view)
+                        .collect(),
+                ))
+            }
+            "state.get_resource" => {
+                let address = parse_address(str_param(0)?)?;
+                let resource_type = str_param(1)?;
+                let bridge_type = format!("{MOCK_NODE_BRIDGE_ADDRESS}::Bridge::Bridge");
+                if address == parse_address(MOCK_NODE_BRIDGE_ADDRESS)?
+                    && resource_type == bridge_type
+                {
+                    return Ok(bridge_resource(&state.bridge_summary));
+                }
+                Ok(state
+                    .resources
+                    .get(&(address, resource_type.to_string()))
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            "contract.call_v2" => {
+                let call = param(0)?;
+                let function_id = call
+                    .get("function_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| (INVALID_PARAMS, "Missing function_id".to_string()))?;
+                // Module::function, without the address
+                let function = function_id
+                    .split_once("::")
+                    .map_or(function_id, |(_, function)| function);
+                let args = call
+                    .get("args")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|arg| arg.as_str().map(str::to_string))
+                    .collect::<Vec<_>>();
+                state
+                    .call_results
+                    .get(&(function.to_string(), args.clone()))
+                    .cloned()
+                    .ok_or_else(|| {
+                        (
+                            SERVER_ERROR,
+                            format!("Function {function_id} not found for args {args:?}"),
+                        )
+                    })
+            }
+            "txpool.next_sequence_number" => {
+                let address = parse_address(str_param(0)?)?;
+                Ok(state
+                    .sequence_numbers
+                    .get(&address)
+                    .map_or(Value::Null, |seq| json!(seq)))
+            }
+            "txpool.submit_hex_transaction" => state.submit(str_param(0)?),
+            "contract.dry_run_raw" => state.dry_run(str_param(0)?),
+            "chain.get_transaction_info" => Ok(state
+                .transactions
+                .get(&str_param(0)?.to_lowercase())
+                .map_or(Value::Null, |txn| txn.info.clone())),
+            _ => Err((METHOD_NOT_FOUND, format!("Method {method} not found"))),
+        }
+    }
+}
+
+impl NodeState {
+    fn get_events(&self, filter: &EventFilter) -> Result<Vec<Value>, (i64, String)> {
+        let addrs = filter
+            .addrs
+            .iter()
+            .flatten()
+            .map(|addr| parse_address(addr))
+            .collect::<Result<Vec<_>, _>>()?;
+        let from_block = filter.from_block.unwrap_or(0);
+        let to_block = filter.to_block.unwrap_or(self.head);
+        let mut events = self
+            .events
+            .iter()
+            .filter(|event| (from_block..=to_block).contains(&event.id.block_number))
+            .filter(|event| filter.addrs.is_none() || addrs.contains(&event.type_.address))
+            .filter(|event| {
+                filter.type_tags.as_ref().is_none_or(|type_tags| {
+                    type_tags
+                        .iter()
+                        .any(|tag| StructTag::from_str(tag).is_ok_and(|tag| tag == event.type_))
+                })
+            })
+            .collect::<Vec<_>>();
+        // Stable, so events of a block keep their emission order
+        events.sort_by_key(|event| event.id.block_number);
+        if filter.reverse == Some(true) {
+            events.reverse();
+        }
+        Ok(events
+            .into_iter()
+            .take(filter.limit.unwrap_or(usize::MAX))
+            .map(event_view)
+            .collect())
+    }
+
+    fn submit(&mut self, signed_txn_hex: &str) -> Result<Value, (i64, String)> {
+        let bytes = hex::decode(signed_txn_hex.trim_start_matches("0x"))
+            .map_err(|e| (INVALID_PARAMS, format!("Invalid transaction hex: {e}")))?;
+        let signed_txn: starcoin_vm_types::transaction::SignedUserTransaction =
+            bcs_ext::from_bytes(&bytes)
+                .map_err(|e| (INVALID_PARAMS, format!("Invalid signed transaction: {e}")))?;
+        let sender = StarcoinAddress::new(signed_txn.sender().into_bytes());
+        let sequence_number = signed_txn.sequence_number();
+        let next_sequence_number = self.sequence_numbers.entry(sender).or_default();
+        if sequence_number < *next_sequence_number {
+            return Err((
+                SERVER_ERROR,
+                format!("SEQUENCE_NUMBER_TOO_OLD: {sequence_number} < {next_sequence_number}"),
+            ));
+        }
+        *next_sequence_number = sequence_number + 1;
+
+        let tx_digest = Sha3_256::digest(&bytes).digest;
+        let txn_hash = format!("0x{}", hex::encode(tx_digest));
+        self.head += 1;
+        let events = self
+            .transaction_events
+            .iter()
+            .enumerate()
+            .map(|(seq, event)| {
+                let mut event = event.clone();
+                event.id.tx_digest = tx_digest;
+                event.id.event_seq = seq as u64;
+                event.id.block_number = self.head;
+                event
+            })
+            .collect::<Vec<_>>();
+        self.events.extend(events);
+        let info = json!({
+            "transaction_hash": txn_hash,
+            "status": self.execution_status,
+            "block_number": self.head.to_string(),
+            "gas_used": self.gas_used.to_string(),
+        });
+        self.transactions.insert(
+            txn_hash.clone(),
+            SubmittedTransaction {
+                sender,
+                sequence_number,
+                info,
+            },
+        );
+        self.submitted.push(txn_hash.clone());
+        Ok(json!(txn_hash))
+    }
+
+    fn dry_run(&self, raw_txn_hex: &str) -> Result<Value, (i64, String)> {
+        let bytes = hex::decode(raw_txn_hex.trim_start_matches("0x"))
+            .map_err(|e| (INVALID_PARAMS, format!("Invalid transaction hex: {e}")))?;
+        let _: starcoin_vm_types::transaction::RawUserTransaction = bcs_ext::from_bytes(&bytes)
+            .map_err(|e| (INVALID_PARAMS, format!("Invalid raw transaction: {e}")))?;
+        Ok(json!({
+            "explained_status": self.execution_status,
+            "txn_output": {
+                "status": self.execution_status,
+                "gas_used": self.gas_used.to_string(),
+            },
+        }))
+    }
+}
+
+async fn handle_request(
+    State(node): State<StarcoinMockNode>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let id = request.get("id").cloned().unwrap_or(json!(0));
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = request
+        .get("params")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    Json(match node.handle(method, &params).await {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": code, "message": message},
+        }),
+    })
+}
+
+fn parse_address(address: &str) -> Result<StarcoinAddress, (i64, String)> {
+    StarcoinAddress::from_hex_literal(address)
+        .map_err(|e| (INVALID_PARAMS, format!("Invalid address {address}: {e}")))
+}
+
+fn parse_hash(hash: &str) -> Result<TransactionDigest, (i64, String)> {
+    hex::decode(hash.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| TransactionDigest::try_from(bytes).ok())
+        .ok_or_else(|| (INVALID_PARAMS, format!("Invalid transaction hash {hash}")))
+}
+
+// Type tags are printed with the full address, as the node does. Bridge
+// events have no type parameters.
+fn type_tag_view(event: &StarcoinEvent) -> String {
+    format!(
+        "0x{}::{}::{}",
+        hex::encode(event.type_.address.as_ref()),
+        event.type_.module,
+        event.type_.name
+    )
+}
+
+fn event_view(event: &StarcoinEvent) -> Value {
+    json!({
+        "block_number": event.id.block_number.to_string(),
+        "transaction_hash": format!("0x{}", hex::encode(event.id.tx_digest)),
+        "event_seq_number": event.id.event_seq.to_string(),
+        "type_tag": type_tag_view(event),
+        "data": format!("0x{}", hex::encode(&event.bcs)),
+    })
+}
+
+// The decoded `Bridge::Bridge` resource `summary` is read from
+fn bridge_resource(summary: &BridgeSummary) -> Value {
+    let vec_map = |entries: Vec<Value>| json!({ "data": entries });
+    let members = summary
+        .committee
+        .members
+        .iter()
+        .map(|(pubkey, member)| {
+            json!({
+                "key": format!("0x{}", hex::encode(pubkey)),
+                "value": {
+                    "bridge_pubkey_bytes": format!("0x{}", hex::encode(&member.bridge_pubkey_bytes)),
+                    "voting_power": member.voting_power,
+                    "starcoin_address": member.starcoin_bridge_address.to_hex_literal(),
+                    "blocklisted": member.blocklisted,
+                    "http_rest_url": format!("0x{}", hex::encode(&member.http_rest_url)),
+                },
+            })
+        })
+        .collect();
+    let supported_tokens = summary
+        .treasury
+        .supported_tokens
+        .iter()
+        .map(|(type_name, metadata)| {
+            json!({
+                "key": type_name,
+                "value": {
+                    "id": metadata.id,
+                    "decimal_multiplier": metadata.decimal_multiplier,
+                    "notional_value": metadata.notional_value,
+                    "native_token": metadata.native_token,
+                },
+            })
+        })
+        .collect();
+    let id_token_type_map = summary
+        .treasury
+        .id_token_type_map
+        .iter()
+        .map(|(id, type_name)| json!({"key": id, "value": type_name}))
+        .collect();
+    let route = |source: BridgeChainId, destination: BridgeChainId| json!({"source": source as u8, "destination": destination as u8});
+    let transfer_limits = summary
+        .limiter
+        .transfer_limit
+        .iter()
+        .map(|(source, destination, limit)| {
+            json!({"key": route(*source, *destination), "value": limit.to_string()})
+        })
+        .collect();
+    let transfer_records = summary
+        .limiter
+        .transfer_records
+        .iter()
+        .map(|(source, destination, record)| {
+            json!({"key": route(*source, *destination), "value": record})
+        })
+        .collect();
+    let sequence_nums = summary
+        .sequence_nums
+        .iter()
+        .map(|(message_type, seq_num)| json!({"key": message_type, "value": seq_num}))
+        .collect();
+    json!({
+        "json": {
+            "inner": {
+                "bridge_version": summary.bridge_version,
+                "message_version": summary.message_version,
+                "chain_id": summary.chain_id,
+                "paused": summary.is_frozen,
+                "committee": {
+                    "members": vec_map(members),
+                    "last_committee_update_epoch": summary.committee.last_committee_update_epoch,
+                },
+                "treasury": {
+                    "supported_tokens": vec_map(supported_tokens),
+                    "id_token_type_map": vec_map(id_token_type_map),
+                    "paused_tokens": summary.treasury.paused_token_ids,
+                },
+                "limiter": {
+                    "transfer_limits": vec_map(transfer_limits),
+                    "transfer_records": vec_map(transfer_records),
+                },
+                "sequence_nums": vec_map(sequence_nums),
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deadline::Deadline;
+    use crate::error::BridgeError;
+    use crate::starcoin_bridge_client::{StarcoinBridgeClient, StarcoinClient};
+    use crate::starcoin_bridge_transaction_builder::{
+        gas_with_margin, GasConfig, StarcoinBridgeTransactionBuilder, DEFAULT_GAS_UNIT_PRICE,
+        MAX_GAS_AMOUNT,
+    };
+    use crate::starcoin_jsonrpc_client::StarcoinJsonRpcClient;
+    use crate::test_utils::StarcoinEventTestExt;
+    use crate::types::{BridgeActionStatus, BridgeActionType};
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use starcoin_bridge_types::base_types::ObjectID;
+    use starcoin_bridge_types::bridge::TOKEN_ID_USDC;
+    use starcoin_bridge_types::crypto::{get_key_pair, StarcoinKeyPair};
+    use starcoin_bridge_types::Identifier;
+
+    fn client(node: &StarcoinMockNode) -> StarcoinBridgeClient {
+        StarcoinClient::new_for_testing(StarcoinJsonRpcClient::new(
+            node.url(),
+            node.bridge_address(),
+        ))
+    }
+
+    fn bridge_package() -> ObjectID {
+        let mut package = [0u8; 32];
+        package[16..].copy_from_slice(parse_address(MOCK_NODE_BRIDGE_ADDRESS).unwrap().as_ref());
+        package
+    }
+
+    fn event(address: &str, module: &str, block_number: u64, event_seq: u64) -> StarcoinEvent {
+        let mut event = StarcoinEvent::random_for_testing();
+        event.type_ = StructTag::from_str(&format!("{address}::{module}::TestEvent")).unwrap();
+        event.id.block_number = block_number;
+        event.id.event_seq = event_seq;
+        event.bcs = vec![block_number as u8, event_seq as u8];
+        event
+    }
+
+    fn blocks(events: &[StarcoinEvent]) -> Vec<u64> {
+        events.iter().map(|event| event.id.block_number).collect()
+    }
+
+    #[tokio::test]
+    async fn test_query_events_by_module_maps_module_name() {
+        telemetry_subscribers::init_for_testing();
+        let node = StarcoinMockNode::start().await;
+        let other_address = "0x00000000000000000000000000000001";
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 11, 0));
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Committee", 12, 0));
+        node.add_event(event(other_address, "Bridge", 13, 0));
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 14, 0));
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "BridgeX", 15, 0));
+        let client = client(&node);
+
+        // The module is `Bridge` on chain, and configs name it `bridge`
+        for module in ["Bridge", "bridge"] {
+            let page = client
+                .query_events_by_module(
+                    bridge_package(),
+                    Identifier::from_str(module).unwrap(),
+                    Some((10, 0)),
+                )
+                .await
+                .unwrap();
+            assert_eq!(blocks(&page.data), vec![11, 14], "{module}");
+            assert_eq!(page.next_cursor.unwrap().position(), (15, 0));
+            assert!(!page.has_next_page);
+        }
+        // Other spellings are other modules
+        for module in ["BRIDGE", "bridgeX"] {
+            let page = client
+                .query_events_by_module(
+                    bridge_package(),
+                    Identifier::from_str(module).unwrap(),
+                    Some((10, 0)),
+                )
+                .await
+                .unwrap();
+            assert!(page.data.is_empty(), "{module}");
+        }
+        let page = client
+            .query_events_by_module(
+                bridge_package(),
+                Identifier::from_str("Limiter").unwrap(),
+                Some((10, 0)),
+            )
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_events_by_module_paginated_over_rpc() {
+        telemetry_subscribers::init_for_testing();
+        let node = StarcoinMockNode::start().await;
+        // More events than fit in a page of 100, then a block past the 32
+        // block range of a query
+        for seq in 0..60 {
+            node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 5, seq));
+            node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 6, seq));
+        }
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 7, 0));
+        node.add_event(event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 90, 0));
+        let client = client(&node);
+        let bridge = Identifier::from_str("Bridge").unwrap();
+
+        let page = client
+            .query_events_by_module_paginated(bridge_package(), bridge.clone(), Some((0, 0)), 1000)
+            .await
+            .unwrap();
+        // Nothing skipped and nothing twice, in chain order
+        let ids = page
+            .data
+            .iter()
+            .map(|event| (event.id.block_number, event.id.event_seq))
+            .collect::<Vec<_>>();
+        let expected = (0..60)
+            .map(|seq| (5, seq))
+            .chain((0..60).map(|seq| (6, seq)))
+            .chain([(7, 0), (90, 0)])
+            .collect::<Vec<_>>();
+        assert_eq!(ids, expected);
+        assert_eq!(page.next_cursor, Some((90, 0)));
+        assert!(!page.has_next_page);
+        // The events' contents made it through the JSON layer
+        assert_eq!(page.data[60].bcs, vec![6, 0]);
+
+        // Stops at the limit on a page boundary
+        let page = client
+            .query_events_by_module_paginated(bridge_package(), bridge, Some((0, 0)), 10)
+            .await
+            .unwrap();
+        assert_eq!(blocks(&page.data), vec![5; 60]);
+        assert_eq!(page.next_cursor, Some((5, 59)));
+        assert!(page.has_next_page);
+    }
+
+    #[tokio::test]
+    async fn test_events_by_tx_digest_and_chain_info() {
+        let node = StarcoinMockNode::start().await;
+        node.set_head(42);
+        node.set_chain_id(251);
+        let client = client(&node);
+        assert_eq!(
+            client
+                .get_latest_checkpoint_sequence_number()
+                .await
+                .unwrap(),
+            42
+        );
+        assert_eq!(client.get_chain_identifier().await.unwrap(), "251");
+        client
+            .describe(BridgeChainId::StarcoinTestnet)
+            .await
+            .unwrap();
+
+        let tx_digest = [7u8; 32];
+        node.add_events_by_tx_digest(
+            tx_digest,
+            vec![
+                event(MOCK_NODE_BRIDGE_ADDRESS, "Bridge", 42, 9),
+                event(MOCK_NODE_BRIDGE_ADDRESS, "Committee", 42, 9),
+            ],
+        );
+        let events = client
+            .starcoin_bridge_client()
+            .rpc()
+            .get_events_by_txn_hash(&format!("0x{}", hex::encode(tx_digest)))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["event_seq_number"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_status_and_signatures() {
+        let node = StarcoinMockNode::start().await;
+        let client = client(&node);
+        node.set_call_result(
+            "Bridge::query_token_transfer_status",
+            &["12u8", "3u64"],
+            json!([1]),
+        );
+        node.set_call_result(
+            "Bridge::query_token_transfer_signatures",
+            &["12u8", "3u64"],
+            json!([{
+                "type": "option",
+                "value": {"type": "vector", "value": [{"value": [1, 2]}, {"value": [3]}]},
+            }]),
+        );
+
+        let deadline = Deadline::none();
+        assert_eq!(
+            client
+                .get_token_transfer_action_onchain_status(12, 3, deadline)
+                .await
+                .unwrap(),
+            BridgeActionStatus::Approved
+        );
+        assert_eq!(
+            client
+                .get_token_transfer_action_onchain_signatures(12, 3, deadline)
+                .await
+                .unwrap(),
+            Some(vec![vec![1, 2], vec![3]])
+        );
+        // Failed calls read as a transfer the bridge doesn't know
+        assert_eq!(
+            client
+                .get_token_transfer_action_onchain_status(12, 4, deadline)
+                .await
+                .unwrap(),
+            BridgeActionStatus::NotFound
+        );
+    }
+
+    fn claim_txn(
+        key: &StarcoinKeyPair,
+        sequence_number: u64,
+    ) -> starcoin_bridge_types::transaction::RawUserTransaction {
+        let sender = key.starcoin_address();
+        StarcoinBridgeTransactionBuilder::build_claim_and_transfer(
+            sender,
+            sender,
+            sequence_number,
+            DEV_CHAIN_ID,
+            0,
+            DEFAULT_GAS_UNIT_PRICE,
+            MAX_GAS_AMOUNT,
+            0,
+            12,
+            1,
+            TOKEN_ID_USDC,
+            &HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_submit_and_wait() {
+        telemetry_subscribers::init_for_testing();
+        let node = StarcoinMockNode::start().await;
+        let client = client(&node);
+        let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Ed25519(kp);
+        // Unknown to the pool, so read from the account resource
+        assert_eq!(
+            client
+                .get_sequence_number(&key.starcoin_address().to_hex_literal())
+                .await
+                .unwrap(),
+            0
+        );
+
+        let receipt = client
+            .sign_and_submit_and_wait_transaction(&key, claim_txn(&key, 0))
+            .await
+            .unwrap();
+        assert_eq!(receipt.status, "Executed");
+        assert_eq!(receipt.block_number, Some(1));
+        assert_eq!(
+            node.submitted_transactions(),
+            vec![(key.starcoin_address(), 0)]
+        );
+
+        node.set_execution_status(json!("MiscellaneousError"));
+        let err = client
+            .sign_and_submit_and_wait_transaction(&key, claim_txn(&key, 1))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, BridgeError::StarcoinTxFailure { ref status, .. } if status == "MiscellaneousError"),
+            "{err:?}"
+        );
+
+        // A reused sequence number is refused
+        client
+            .sign_and_submit_transaction(&key, claim_txn(&key, 1))
+            .await
+            .unwrap_err();
+        assert_eq!(node.submitted_transactions().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas() {
+        let node = StarcoinMockNode::start().await;
+        let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+        let key = StarcoinKeyPair::Ed25519(kp);
+        let client = client(&node).with_gas_config(GasConfig {
+            gas_budget_overrides: HashMap::from([(BridgeActionType::TokenTransfer, 200_000)]),
+            ..Default::default()
+        });
+
+        node.set_gas_used(100_000);
+        assert_eq!(
+            client
+                .estimate_gas(&key, claim_txn(&key, 0), BridgeActionType::TokenTransfer)
+                .await
+                .unwrap(),
+            gas_with_margin(100_000)
+        );
+        // Dry runs are not submitted
+        assert!(node.submitted_transactions().is_empty());
+
+        // Over the max gas of the action type, but not the default one
+        node.set_gas_used(190_000);
+        let err = client
+            .estimate_gas(&key, claim_txn(&key, 0), BridgeActionType::TokenTransfer)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max gas amount 200000"), "{err:?}");
+        client
+            .estimate_gas(&key, claim_txn(&key, 0), BridgeActionType::TokenPause)
+            .await
+            .unwrap();
+
+        node.set_execution_status(json!({"MoveAbort": {"abort_code": "13"}}));
+        let err = client
+            .estimate_gas(&key, claim_txn(&key, 0), BridgeActionType::TokenPause)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, BridgeError::StarcoinTxFailure { ref status, .. } if status == "MoveAbort"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_faults_and_unknown_methods() {
+        let node = StarcoinMockNode::start().await;
+        let faults = FaultInjector::builder(0)
+            .fail_first("chain.info", 1)
+            .stale_head(3)
+            .build();
+        node.set_fault_injector(faults.clone());
+        node.set_head(10);
+        let client = client(&node);
+        let rpc = client.starcoin_bridge_client().rpc();
+
+        let err = rpc.chain_info().await.unwrap_err();
+        assert!(
+            err.to_string().contains("Injected fault in chain.info"),
+            "{err}"
+        );
+        assert_eq!(rpc.chain_info().await.unwrap()["head"]["number"], "3");
+        assert_eq!(faults.calls("chain.info"), 2);
+
+        let err = rpc.dry_run_transaction("0x00").await.unwrap_err();
+        assert!(err.to_string().contains("-32601"), "{err}");
+    }
+}
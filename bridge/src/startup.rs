@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for running independent startup steps concurrently.
+//!
+//! Each step is wrapped with `timed_step` and the steps are driven together
+//! with `tokio::join!`. `finish_steps` then logs how long each step took and,
+//! if any failed, returns the first error with the outcome of every step
+//! attached, so a failure in one RPC does not hide what happened to the rest.
+//! Steps that depend on each other must still be awaited in order.
+
+use anyhow::anyhow;
+use std::fmt::Debug;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::info;
+
+pub struct StepOutcome<T> {
+    name: String,
+    elapsed: Duration,
+    result: anyhow::Result<T>,
+}
+
+pub async fn timed_step<T, E, F>(name: impl Into<String>, fut: F) -> StepOutcome<T>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Debug,
+{
+    let start = Instant::now();
+    // BridgeError does not implement std::error::Error, so render with Debug
+    let result = fut.await.map_err(|e| anyhow!("{:?}", e));
+    StepOutcome {
+        name: name.into(),
+        elapsed: start.elapsed(),
+        result,
+    }
+}
+
+impl<T> StepOutcome<T> {
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn into_result(self) -> anyhow::Result<T> {
+        self.result
+    }
+}
+
+// Type-erased view of a step, so steps with different outputs can be
+// reported together.
+pub trait StepReport {
+    fn name(&self) -> &str;
+    fn elapsed(&self) -> Duration;
+    fn error(&self) -> Option<&anyhow::Error>;
+}
+
+impl<T> StepReport for StepOutcome<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    fn error(&self) -> Option<&anyhow::Error> {
+        self.result.as_ref().err()
+    }
+}
+
+fn describe(step: &dyn StepReport) -> String {
+    match step.error() {
+        None => format!("{}: ok in {:?}", step.name(), step.elapsed()),
+        Some(e) => format!("{}: failed in {:?}: {:#}", step.name(), step.elapsed(), e),
+    }
+}
+
+// Logs the timing breakdown of `steps` and returns the first failure, with the
+// outcome of every step attached as context.
+pub fn finish_steps(phase: &str, steps: &[&dyn StepReport]) -> anyhow::Result<()> {
+    let breakdown = steps
+        .iter()
+        .map(|step| describe(*step))
+        .collect::<Vec<_>>()
+        .join(", ");
+    info!("{} timing: {}", phase, breakdown);
+    match steps
+        .iter()
+        .find_map(|step| Some((step.name(), step.error()?)))
+    {
+        None => Ok(()),
+        Some((name, e)) => Err(anyhow!("{} failed: {:#}", name, e)
+            .context(format!("{} failed. Step outcomes: {}", phase, breakdown))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn step(delay_ms: u64, fail: bool) -> anyhow::Result<u64> {
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        if fail {
+            return Err(anyhow!("boom after {delay_ms}ms"));
+        }
+        Ok(delay_ms)
+    }
+
+    #[tokio::test]
+    async fn test_finish_steps() {
+        let (a, b) = tokio::join!(
+            timed_step("fast", step(10, false)),
+            timed_step("slow", step(50, false))
+        );
+        assert!(a.elapsed() >= Duration::from_millis(10));
+        assert!(b.elapsed() >= Duration::from_millis(50));
+        finish_steps("test", &[&a, &b]).unwrap();
+        assert_eq!(a.into_result().unwrap(), 10);
+
+        let (a, b, c) = tokio::join!(
+            timed_step("first", step(10, false)),
+            timed_step("second", step(20, true)),
+            timed_step("third", step(30, true))
+        );
+        let err = finish_steps("test", &[&a, &b, &c]).unwrap_err();
+        let rendered = format!("{:#}", err);
+        // The first failure is the error, every outcome is in the context
+        assert!(err
+            .root_cause()
+            .to_string()
+            .starts_with("second failed: boom after 20ms"));
+        assert!(rendered.contains("first: ok in"));
+        assert!(rendered.contains("third: failed in"));
+    }
+}
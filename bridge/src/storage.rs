@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use starcoin_bridge_types::bridge::BridgeChainId;
 use starcoin_bridge_types::Identifier;
 use std::collections::HashMap;
 use std::path::Path;
@@ -19,8 +20,17 @@ pub struct BridgeOrchestratorTables {
     pub(crate) pending_actions: DBMap<BridgeActionDigest, BridgeAction>,
     // module identifier to the last processed EventID
     pub(crate) starcoin_bridge_syncer_cursors: DBMap<Identifier, EventID>,
-    // contract address to the last processed block
+    // contract address to the last processed block, written before cursors
+    // were keyed by chain. Moved into `eth_chain_syncer_cursors` on startup.
     pub(crate) eth_syncer_cursors: DBMap<ethers::types::Address, u64>,
+    // (eth chain id, contract address) to the last processed block
+    pub(crate) eth_chain_syncer_cursors: DBMap<(u8, ethers::types::Address), u64>,
+    // token id to the code hash of its Starcoin Move module, recorded when the
+    // token is first seen
+    pub(crate) token_module_hashes: DBMap<u8, [u8; 32]>,
+    // token id to when its module hash was recorded, in unix milliseconds.
+    // Hashes recorded before this was kept have no entry.
+    pub(crate) token_module_first_seen: DBMap<u8, u64>,
 }
 
 impl BridgeOrchestratorTables {
@@ -85,16 +95,20 @@ impl BridgeOrchestratorTables {
 
     pub(crate) fn update_eth_event_cursor(
         &self,
+        chain_id: BridgeChainId,
         contract_address: ethers::types::Address,
         cursor: u64,
     ) -> BridgeResult<()> {
-        let mut batch = self.eth_syncer_cursors.batch();
+        let mut batch = self.eth_chain_syncer_cursors.batch();
 
         batch
-            .insert_batch(&self.eth_syncer_cursors, [(contract_address, cursor)])
+            .insert_batch(
+                &self.eth_chain_syncer_cursors,
+                [((chain_id as u8, contract_address), cursor)],
+            )
             .map_err(|e| {
                 BridgeError::StorageError(format!(
-                    "Coudln't insert into eth_syncer_cursors: {:?}",
+                    "Coudln't insert into eth_chain_syncer_cursors: {:?}",
                     e
                 ))
             })?;
@@ -103,6 +117,96 @@ impl BridgeOrchestratorTables {
             .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
     }
 
+    // Moves cursors written before they were keyed by chain under `chain_id`,
+    // the chain the node watched at the time. A cursor already recorded for
+    // that chain is kept. Returns how many legacy cursors were moved.
+    pub(crate) fn migrate_eth_event_cursors(&self, chain_id: BridgeChainId) -> BridgeResult<usize> {
+        let legacy = self
+            .eth_syncer_cursors
+            .safe_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get eth_syncer_cursors: {:?}", e))
+            })?;
+        if legacy.is_empty() {
+            return Ok(0);
+        }
+        let keys = legacy
+            .iter()
+            .map(|(contract, _)| (chain_id as u8, *contract))
+            .collect::<Vec<_>>();
+        let existing = self
+            .eth_chain_syncer_cursors
+            .multi_get(&keys)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get eth_chain_syncer_cursors: {:?}", e))
+            })?;
+        let mut batch = self.eth_chain_syncer_cursors.batch();
+        batch
+            .insert_batch(
+                &self.eth_chain_syncer_cursors,
+                keys.into_iter()
+                    .zip(legacy.iter())
+                    .zip(existing)
+                    .filter(|(_, existing)| existing.is_none())
+                    .map(|((key, (_, cursor)), _)| (key, *cursor)),
+            )
+            .and_then(|batch| {
+                batch.delete_batch(
+                    &self.eth_syncer_cursors,
+                    legacy.iter().map(|(contract, _)| *contract),
+                )
+            })
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't migrate eth_syncer_cursors: {:?}", e))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(legacy.len())
+    }
+
+    pub(crate) fn record_token_module_hash(
+        &self,
+        token_id: u8,
+        hash: [u8; 32],
+        recorded_at_ms: u64,
+    ) -> BridgeResult<()> {
+        let mut batch = self.token_module_hashes.batch();
+        batch
+            .insert_batch(&self.token_module_hashes, [(token_id, hash)])
+            .and_then(|batch| {
+                batch.insert_batch(&self.token_module_first_seen, [(token_id, recorded_at_ms)])
+            })
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into token_module_hashes: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    pub fn get_token_module_hashes(&self) -> BridgeResult<HashMap<u8, [u8; 32]>> {
+        self.token_module_hashes
+            .safe_iter()
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get token_module_hashes: {:?}", e))
+            })
+    }
+
+    pub fn get_token_module_first_seen(&self) -> BridgeResult<HashMap<u8, u64>> {
+        self.token_module_first_seen
+            .safe_iter()
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get token_module_first_seen: {:?}", e))
+            })
+    }
+
     pub fn get_all_pending_actions(&self) -> HashMap<BridgeActionDigest, BridgeAction> {
         self.pending_actions
             .safe_iter()
@@ -126,16 +230,16 @@ impl BridgeOrchestratorTables {
 
     pub fn get_eth_event_cursors(
         &self,
+        chain_id: BridgeChainId,
         contract_addresses: &[ethers::types::Address],
     ) -> BridgeResult<Vec<Option<u64>>> {
-        self.eth_syncer_cursors
-            .multi_get(contract_addresses)
-            .map_err(|e| {
-                BridgeError::StorageError(format!(
-                    "Couldn't get starcoin_bridge_syncer_cursors: {:?}",
-                    e
-                ))
-            })
+        let keys = contract_addresses
+            .iter()
+            .map(|contract| (chain_id as u8, *contract))
+            .collect::<Vec<_>>();
+        self.eth_chain_syncer_cursors.multi_get(&keys).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't get eth_chain_syncer_cursors: {:?}", e))
+        })
     }
 }
 
@@ -225,19 +329,37 @@ mod tests {
         let eth_contract_address = ethers::types::Address::random();
         let eth_block_num = 199999u64;
         assert!(store
-            .get_eth_event_cursors(&[eth_contract_address])
+            .get_eth_event_cursors(BridgeChainId::EthCustom, &[eth_contract_address])
             .unwrap()[0]
             .is_none());
         store
-            .update_eth_event_cursor(eth_contract_address, eth_block_num)
+            .update_eth_event_cursor(
+                BridgeChainId::EthCustom,
+                eth_contract_address,
+                eth_block_num,
+            )
             .unwrap();
         assert_eq!(
             store
-                .get_eth_event_cursors(&[eth_contract_address])
+                .get_eth_event_cursors(BridgeChainId::EthCustom, &[eth_contract_address])
                 .unwrap()[0]
                 .unwrap(),
             eth_block_num
         );
+        // the same contract address on another chain has its own cursor
+        assert!(store
+            .get_eth_event_cursors(BridgeChainId::EthSepolia, &[eth_contract_address])
+            .unwrap()[0]
+            .is_none());
+        store
+            .update_eth_event_cursor(BridgeChainId::EthSepolia, eth_contract_address, 5)
+            .unwrap();
+        assert_eq!(
+            store
+                .get_eth_event_cursors(BridgeChainId::EthCustom, &[eth_contract_address])
+                .unwrap()[0],
+            Some(eth_block_num)
+        );
 
         // update starcoin event cursor
         let starcoin_bridge_module = Identifier::from_str("test").unwrap();
@@ -260,4 +382,56 @@ mod tests {
             starcoin_bridge_cursor
         );
     }
+
+    #[tokio::test]
+    async fn test_migrate_eth_event_cursors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let contract1 = ethers::types::Address::random();
+        let contract2 = ethers::types::Address::random();
+
+        // nothing to migrate
+        assert_eq!(
+            store
+                .migrate_eth_event_cursors(BridgeChainId::EthSepolia)
+                .unwrap(),
+            0
+        );
+
+        // cursors written before they were keyed by chain
+        store.eth_syncer_cursors.insert(&contract1, &100).unwrap();
+        store.eth_syncer_cursors.insert(&contract2, &200).unwrap();
+        // a cursor the node already recorded for the chain wins
+        store
+            .update_eth_event_cursor(BridgeChainId::EthSepolia, contract2, 250)
+            .unwrap();
+
+        assert_eq!(
+            store
+                .migrate_eth_event_cursors(BridgeChainId::EthSepolia)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            store
+                .get_eth_event_cursors(BridgeChainId::EthSepolia, &[contract1, contract2])
+                .unwrap(),
+            vec![Some(100), Some(250)]
+        );
+        assert_eq!(
+            store
+                .get_eth_event_cursors(BridgeChainId::EthCustom, &[contract1, contract2])
+                .unwrap(),
+            vec![None, None]
+        );
+        assert!(store.eth_syncer_cursors.safe_iter().next().is_none());
+
+        // running it again is a no-op
+        assert_eq!(
+            store
+                .migrate_eth_event_cursors(BridgeChainId::EthSepolia)
+                .unwrap(),
+            0
+        );
+    }
 }
@@ -5,7 +5,7 @@ use crate::abi::EthToStarcoinTokenBridgeV1;
 use crate::eth_mock_provider::EthMockProvider;
 use crate::events::StarcoinBridgeEvent;
 use crate::server::mock_handler::run_mock_server;
-use crate::starcoin_bridge_transaction_builder::build_starcoin_bridge_transaction;
+use crate::starcoin_bridge_transaction_builder::{build_starcoin_bridge_transaction, GasConfig};
 use crate::types::{
     BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
     VerifiedCertifiedBridgeAction,
@@ -542,6 +542,7 @@ pub async fn approve_action_with_validator_secrets(
         bridge_obj_org,
         id_token_map,
         rgp,
+        &GasConfig::default(),
     )
     .unwrap();
     let signed_tx = wallet_context.sign_transaction(&tx_data).await;
@@ -0,0 +1,315 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions of token amounts between decimals. Transfers carry amounts in
+//! bridge units, with the decimals the Starcoin treasury records for the
+//! token, which differ from both the ERC20 decimals and whole tokens. The
+//! conversions are exact: they fail instead of dropping digits or wrapping.
+
+use starcoin_bridge_types::bridge::BridgeTreasurySummary;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TokenMathError {
+    #[error("{amount} with {from_decimals} decimals loses precision at {to_decimals} decimals")]
+    PrecisionLoss {
+        amount: u128,
+        from_decimals: u8,
+        to_decimals: u8,
+    },
+    #[error("{amount} with {from_decimals} decimals overflows at {to_decimals} decimals")]
+    Overflow {
+        amount: u128,
+        from_decimals: u8,
+        to_decimals: u8,
+    },
+    #[error("Token id {0} has no decimals in the bridge treasury")]
+    UnknownToken(u8),
+}
+
+// `amount` with `from_decimals` decimals, rescaled to `to_decimals`
+pub fn convert_units(
+    amount: u128,
+    from_decimals: u8,
+    to_decimals: u8,
+) -> Result<u128, TokenMathError> {
+    if amount == 0 || from_decimals == to_decimals {
+        return Ok(amount);
+    }
+    let pow10 = |exp: u8| 10u128.checked_pow(exp as u32);
+    if to_decimals > from_decimals {
+        pow10(to_decimals - from_decimals)
+            .and_then(|factor| amount.checked_mul(factor))
+            .ok_or(TokenMathError::Overflow {
+                amount,
+                from_decimals,
+                to_decimals,
+            })
+    } else {
+        // A factor past u128 is larger than any amount, so only 0 divides
+        match pow10(from_decimals - to_decimals) {
+            Some(factor) if amount % factor == 0 => Ok(amount / factor),
+            _ => Err(TokenMathError::PrecisionLoss {
+                amount,
+                from_decimals,
+                to_decimals,
+            }),
+        }
+    }
+}
+
+// `amount` with `from_decimals` decimals, e.g. of an ERC20, in bridge units
+pub fn to_bridge_units(
+    amount: u128,
+    from_decimals: u8,
+    bridge_decimals: u8,
+) -> Result<u64, TokenMathError> {
+    let converted = convert_units(amount, from_decimals, bridge_decimals)?;
+    u64::try_from(converted).map_err(|_| TokenMathError::Overflow {
+        amount,
+        from_decimals,
+        to_decimals: bridge_decimals,
+    })
+}
+
+// `amount` in bridge units, with `to_decimals` decimals
+pub fn from_bridge_units(
+    amount: u64,
+    bridge_decimals: u8,
+    to_decimals: u8,
+) -> Result<u128, TokenMathError> {
+    convert_units(amount as u128, bridge_decimals, to_decimals)
+}
+
+// `raw` units of a token with `decimals` decimals as a decimal number without
+// trailing zeros, e.g. 1500000 with 6 decimals is "1.5"
+pub fn format_units(raw: u128, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{raw:0>width$}", width = decimals + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - decimals);
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.is_empty() {
+        int_part.to_string()
+    } else {
+        format!("{int_part}.{frac_part}")
+    }
+}
+
+// Decimals of a treasury decimal multiplier, None unless it is a power of 10
+pub fn decimals_from_multiplier(multiplier: u64) -> Option<u8> {
+    let decimals = multiplier.checked_ilog10()?;
+    (10u64.pow(decimals) == multiplier).then_some(decimals as u8)
+}
+
+// Bridge decimals of each token id, as the Starcoin treasury records them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenDecimals(BTreeMap<u8, u8>);
+
+impl TokenDecimals {
+    pub fn from_treasury(treasury: &BridgeTreasurySummary) -> Self {
+        Self(
+            treasury
+                .id_token_type_map
+                .iter()
+                .filter_map(|(token_id, type_name)| {
+                    let (_, metadata) = treasury
+                        .supported_tokens
+                        .iter()
+                        .find(|(name, _)| name == type_name)?;
+                    Some((
+                        *token_id,
+                        decimals_from_multiplier(metadata.decimal_multiplier)?,
+                    ))
+                })
+                .collect(),
+        )
+    }
+
+    pub fn decimals(&self, token_id: u8) -> Result<u8, TokenMathError> {
+        self.0
+            .get(&token_id)
+            .copied()
+            .ok_or(TokenMathError::UnknownToken(token_id))
+    }
+
+    // `raw` bridge units of `token_id` in whole tokens, e.g. "1.5"
+    pub fn format_amount(&self, token_id: u8, raw: u64) -> Result<String, TokenMathError> {
+        Ok(format_units(raw as u128, self.decimals(token_id)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use starcoin_bridge_types::bridge::BridgeTokenMetadata;
+
+    #[test]
+    fn test_convert_units() {
+        // 1.5 USDC from 6 ERC20 decimals to 8 bridge decimals and back
+        assert_eq!(to_bridge_units(1_500_000, 6, 8).unwrap(), 150_000_000);
+        assert_eq!(from_bridge_units(150_000_000, 8, 6).unwrap(), 1_500_000);
+        // 1 ether from 18 decimals
+        assert_eq!(
+            to_bridge_units(1_000_000_000_000_000_000, 18, 8).unwrap(),
+            100_000_000
+        );
+        // 1 wei is below the bridge's precision
+        assert_eq!(
+            to_bridge_units(1_000_000_000_000_000_001, 18, 8).unwrap_err(),
+            TokenMathError::PrecisionLoss {
+                amount: 1_000_000_000_000_000_001,
+                from_decimals: 18,
+                to_decimals: 8,
+            }
+        );
+        assert_eq!(to_bridge_units(0, 0, 38).unwrap(), 0);
+        assert_eq!(convert_units(7, 3, 3).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_convert_units_boundaries() {
+        let overflow = |amount, from_decimals, to_decimals| TokenMathError::Overflow {
+            amount,
+            from_decimals,
+            to_decimals,
+        };
+        assert_eq!(to_bridge_units(u64::MAX as u128, 8, 8).unwrap(), u64::MAX);
+        assert_eq!(
+            to_bridge_units(u64::MAX as u128 + 1, 8, 8).unwrap_err(),
+            overflow(u64::MAX as u128 + 1, 8, 8)
+        );
+        assert_eq!(
+            to_bridge_units(u64::MAX as u128, 8, 9).unwrap_err(),
+            overflow(u64::MAX as u128, 8, 9)
+        );
+        assert_eq!(
+            from_bridge_units(u64::MAX, 0, 19).unwrap(),
+            u64::MAX as u128 * 10u128.pow(19)
+        );
+        assert_eq!(
+            from_bridge_units(u64::MAX, 0, 20).unwrap_err(),
+            overflow(u64::MAX as u128, 0, 20)
+        );
+        assert_eq!(convert_units(u128::MAX, 0, 0).unwrap(), u128::MAX);
+        assert_eq!(
+            convert_units(u128::MAX, 0, 1).unwrap_err(),
+            overflow(u128::MAX, 0, 1)
+        );
+        // 10^39 doesn't fit u128
+        assert_eq!(convert_units(1, 0, 38).unwrap(), 10u128.pow(38));
+        assert_eq!(convert_units(1, 0, 39).unwrap_err(), overflow(1, 0, 39));
+        assert!(matches!(
+            convert_units(u128::MAX, 39, 0).unwrap_err(),
+            TokenMathError::PrecisionLoss { .. }
+        ));
+        assert_eq!(convert_units(10u128.pow(38), 38, 0).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(1_500_000, 6), "1.5");
+        assert_eq!(format_units(1, 6), "0.000001");
+        assert_eq!(format_units(2_000_000, 6), "2");
+        assert_eq!(format_units(0, 8), "0");
+        assert_eq!(format_units(42, 0), "42");
+        assert_eq!(
+            format_units(u128::MAX, 38),
+            "3.40282366920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn test_token_decimals() {
+        let metadata = |decimal_multiplier| BridgeTokenMetadata {
+            decimal_multiplier,
+            ..Default::default()
+        };
+        let treasury = BridgeTreasurySummary {
+            supported_tokens: vec![
+                ("0x1::usdc::USDC".to_string(), metadata(1_000_000)),
+                ("0x1::eth::ETH".to_string(), metadata(100_000_000)),
+                ("0x1::odd::ODD".to_string(), metadata(3)),
+            ],
+            id_token_type_map: vec![
+                (2, "0x1::eth::ETH".to_string()),
+                (3, "0x1::usdc::USDC".to_string()),
+                (4, "0x1::odd::ODD".to_string()),
+                (5, "0x1::missing::MISSING".to_string()),
+            ],
+            paused_token_ids: vec![],
+        };
+        let decimals = TokenDecimals::from_treasury(&treasury);
+        assert_eq!(decimals.decimals(2).unwrap(), 8);
+        assert_eq!(decimals.format_amount(3, 1_500_000).unwrap(), "1.5");
+        assert_eq!(decimals.format_amount(2, 1).unwrap(), "0.00000001");
+        // Not a power of 10, or not in the treasury
+        for token_id in [4, 5, 6] {
+            assert_eq!(
+                decimals.format_amount(token_id, 1).unwrap_err(),
+                TokenMathError::UnknownToken(token_id)
+            );
+        }
+
+        assert_eq!(decimals_from_multiplier(1), Some(0));
+        assert_eq!(
+            decimals_from_multiplier(10_000_000_000_000_000_000),
+            Some(19)
+        );
+        assert_eq!(decimals_from_multiplier(0), None);
+        assert_eq!(decimals_from_multiplier(20), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_bridge_units_round_trip(
+            amount in any::<u64>(),
+            bridge_decimals in 0u8..=20,
+            to_decimals in 0u8..=40,
+        ) {
+            // Converting out of bridge units and back never changes the amount
+            if let Ok(converted) = from_bridge_units(amount, bridge_decimals, to_decimals) {
+                prop_assert_eq!(
+                    to_bridge_units(converted, to_decimals, bridge_decimals),
+                    Ok(amount)
+                );
+            }
+        }
+
+        #[test]
+        fn test_convert_units_is_exact(
+            amount in any::<u128>(),
+            from_decimals in 0u8..=40,
+            to_decimals in 0u8..=40,
+        ) {
+            match convert_units(amount, from_decimals, to_decimals) {
+                Ok(converted) => prop_assert_eq!(
+                    convert_units(converted, to_decimals, from_decimals),
+                    Ok(amount)
+                ),
+                Err(TokenMathError::Overflow { .. }) => {
+                    prop_assert!(to_decimals > from_decimals)
+                }
+                Err(TokenMathError::PrecisionLoss { .. }) => {
+                    prop_assert!(to_decimals < from_decimals)
+                }
+                Err(e) => prop_assert!(false, "Unexpected error {}", e),
+            }
+        }
+
+        #[test]
+        fn test_format_units_keeps_every_digit(
+            raw in any::<u128>(),
+            decimals in 0u8..=40,
+        ) {
+            let formatted = format_units(raw, decimals);
+            let (int_part, frac_part) = formatted.split_once('.').unwrap_or((&formatted, ""));
+            prop_assert!(frac_part.len() <= decimals as usize);
+            let digits = format!("{int_part}{frac_part:0<width$}", width = decimals as usize);
+            prop_assert_eq!(
+                digits.trim_start_matches('0'),
+                raw.to_string().trim_start_matches('0')
+            );
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detects upgrades of the Starcoin Move modules that define bridged tokens.
+//!
+//! The code hash of each token's module is recorded the first time the node
+//! sees the token and compared on every token map refresh. An upgrade may be
+//! compatible, so a mismatch only warns: it is logged, exported as the
+//! `bridge_token_module_changed` metric and included in the health report.
+//!
+//! The recorded hash is trusted on first use: registration events carry no
+//! code hash, so a module upgraded before the node first saw its token
+//! becomes the baseline. Each change in the health report says when its
+//! baseline was recorded.
+
+use crate::error::BridgeResult;
+use crate::metrics::BridgeMetrics;
+use crate::starcoin_bridge_client::{StarcoinClient, StarcoinClientInner};
+use crate::storage::BridgeOrchestratorTables;
+use arc_swap::ArcSwap;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use serde::{Deserialize, Serialize};
+use starcoin_bridge_types::TypeTag;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::{self, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+pub const TOKEN_MODULE_CHECK_INTERVAL: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenModuleChange {
+    pub token_id: u8,
+    pub token_type: String,
+    // Hex encoded sha3-256 of the module bytecode, as first seen by the node
+    pub recorded_hash: String,
+    // When the node first saw the module, in unix milliseconds. None for
+    // hashes recorded before this was kept.
+    #[serde(default)]
+    pub recorded_at_ms: Option<u64>,
+    pub current_hash: String,
+}
+
+pub fn module_hash(code: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(code).digest
+}
+
+pub fn format_module_hash(hash: &[u8; 32]) -> String {
+    format!("0x{}", Hex::encode(hash))
+}
+
+// Compares the current module of every token against the recorded hash.
+// Tokens seen for the first time are recorded rather than reported, with
+// `now_ms` as the time they were first seen.
+pub async fn check_token_modules<C: StarcoinClientInner>(
+    starcoin_bridge_client: &StarcoinClient<C>,
+    token_types: &HashMap<u8, TypeTag>,
+    store: &BridgeOrchestratorTables,
+    now_ms: u64,
+) -> BridgeResult<Vec<TokenModuleChange>> {
+    let recorded = store.get_token_module_hashes()?;
+    let first_seen = store.get_token_module_first_seen()?;
+    let mut changes = vec![];
+    for (token_id, token_type) in token_types.iter().collect::<BTreeMap<_, _>>() {
+        let Some(code) = starcoin_bridge_client
+            .get_token_module_bytes(token_type)
+            .await?
+        else {
+            warn!(token_id, %token_type, "Token module is not published on Starcoin");
+            continue;
+        };
+        let current = module_hash(&code);
+        match recorded.get(token_id) {
+            None => {
+                info!(
+                    token_id,
+                    %token_type,
+                    hash = format_module_hash(&current),
+                    "Recording token module hash"
+                );
+                store.record_token_module_hash(*token_id, current, now_ms)?;
+            }
+            Some(recorded) if *recorded != current => changes.push(TokenModuleChange {
+                token_id: *token_id,
+                token_type: token_type.to_string(),
+                recorded_hash: format_module_hash(recorded),
+                recorded_at_ms: first_seen.get(token_id).copied(),
+                current_hash: format_module_hash(&current),
+            }),
+            Some(_) => {}
+        }
+    }
+    Ok(changes)
+}
+
+// Periodically refreshes the token map cache from the bridge summary and
+// checks the token modules against their recorded hashes.
+pub struct TokenModuleWatcher<C> {
+    starcoin_bridge_client: Arc<StarcoinClient<C>>,
+    store: Arc<BridgeOrchestratorTables>,
+    starcoin_bridge_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+    token_module_changes: Arc<ArcSwap<Vec<TokenModuleChange>>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> TokenModuleWatcher<C>
+where
+    C: StarcoinClientInner + 'static,
+{
+    pub fn new(
+        starcoin_bridge_client: Arc<StarcoinClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        starcoin_bridge_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+        token_module_changes: Arc<ArcSwap<Vec<TokenModuleChange>>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            starcoin_bridge_client,
+            store,
+            starcoin_bridge_token_type_tags,
+            token_module_changes,
+            metrics,
+        }
+    }
+
+    pub async fn run(self, check_interval: Duration) {
+        let mut interval = time::interval(check_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            self.refresh().await;
+        }
+    }
+
+    async fn refresh(&self) {
+        match self.starcoin_bridge_client.get_token_id_map().await {
+            Ok(token_types) => self
+                .starcoin_bridge_token_type_tags
+                .store(Arc::new(token_types)),
+            Err(e) => warn!("Failed to refresh token map, using cached one: {:?}", e),
+        }
+        let token_types = self.starcoin_bridge_token_type_tags.load_full();
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        let changes = match check_token_modules(
+            &self.starcoin_bridge_client,
+            &token_types,
+            &self.store,
+            now_ms,
+        )
+        .await
+        {
+            Ok(changes) => changes,
+            Err(e) => {
+                warn!("Failed to check token modules: {:?}", e);
+                return;
+            }
+        };
+        for token_id in token_types.keys() {
+            let changed = changes.iter().any(|c| c.token_id == *token_id);
+            self.metrics
+                .token_module_changed
+                .with_label_values(&[&token_id.to_string()])
+                .set(changed as i64);
+        }
+        for change in &changes {
+            error!(
+                token_id = change.token_id,
+                token_type = change.token_type,
+                recorded_hash = change.recorded_hash,
+                recorded_at_ms = change.recorded_at_ms,
+                current_hash = change.current_hash,
+                "Token module was upgraded since the node first saw it, claims for this token may abort"
+            );
+        }
+        self.token_module_changes.store(Arc::new(changes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::starcoin_bridge_mock_client::StarcoinMockClient;
+    use std::str::FromStr;
+
+    const USDC_ADDRESS: &str = "0x0b8e0206e990e41e913a7f03d1c60675";
+
+    fn usdc_type() -> TypeTag {
+        TypeTag::from_str(&format!("{USDC_ADDRESS}::usdc::USDC")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_token_modules() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let mock = StarcoinMockClient::default();
+        let client = StarcoinClient::new_for_testing(mock.clone());
+        let usdc_v1 = vec![0xa1, 0x1c, 0xeb, 0x0b, 0x06, 0x01];
+        let usdc_v2 = vec![0xa1, 0x1c, 0xeb, 0x0b, 0x06, 0x02];
+        let eth_type = TypeTag::from_str(&format!("{USDC_ADDRESS}::eth::ETH")).unwrap();
+        let token_types = HashMap::from([(3, usdc_type()), (2, eth_type)]);
+
+        // First sight records the hash; the unpublished module is skipped
+        mock.set_module_bytes(USDC_ADDRESS, "usdc", usdc_v1.clone());
+        let changes = check_token_modules(&client, &token_types, &store, 1_000)
+            .await
+            .unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(
+            store.get_token_module_hashes().unwrap(),
+            HashMap::from([(3, module_hash(&usdc_v1))])
+        );
+        assert_eq!(
+            store.get_token_module_first_seen().unwrap(),
+            HashMap::from([(3, 1_000)])
+        );
+
+        // Unchanged module
+        let changes = check_token_modules(&client, &token_types, &store, 1_000)
+            .await
+            .unwrap();
+        assert!(changes.is_empty());
+
+        // Upgraded module is reported against the hash first seen, along with
+        // when it was seen
+        mock.set_module_bytes(USDC_ADDRESS, "usdc", usdc_v2.clone());
+        for _ in 0..2 {
+            let changes = check_token_modules(&client, &token_types, &store, 2_000)
+                .await
+                .unwrap();
+            assert_eq!(
+                changes,
+                vec![TokenModuleChange {
+                    token_id: 3,
+                    token_type: usdc_type().to_string(),
+                    recorded_hash: format_module_hash(&module_hash(&usdc_v1)),
+                    recorded_at_ms: Some(1_000),
+                    current_hash: format_module_hash(&module_hash(&usdc_v2)),
+                }]
+            );
+        }
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `TxDigest` is the canonical rendering of transaction digests in logs, CLI
+//! output and error messages: `stc:0x<64 hex>` for Starcoin and
+//! `eth:0x<64 hex>` for Ethereum. The chain tag keeps a single grep pattern
+//! working across both sides of the bridge.
+//!
+//! Parsing also accepts the formats found in older logs: hex with or without
+//! `0x`, and the `[u8; 32]` Debug array used for Starcoin digests.
+
+use crate::error::{BridgeError, BridgeResult};
+use ethers::types::TxHash;
+use fastcrypto::encoding::{Encoding, Hex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starcoin_bridge_types::base_types::TransactionDigest;
+use std::fmt;
+use std::str::FromStr;
+
+const STARCOIN_TAG: &str = "stc:";
+const ETH_TAG: &str = "eth:";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TxChain {
+    Starcoin,
+    Eth,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TxDigest {
+    chain: TxChain,
+    bytes: [u8; 32],
+}
+
+impl TxDigest {
+    pub fn starcoin(digest: TransactionDigest) -> Self {
+        Self {
+            chain: TxChain::Starcoin,
+            bytes: digest,
+        }
+    }
+
+    pub fn eth(hash: TxHash) -> Self {
+        Self {
+            chain: TxChain::Eth,
+            bytes: hash.0,
+        }
+    }
+
+    pub fn chain(&self) -> TxChain {
+        self.chain
+    }
+
+    pub fn bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    // Parses any accepted format when the chain is known from context. A tag,
+    // if present, must match `chain`.
+    pub fn parse_as(chain: TxChain, s: &str) -> BridgeResult<Self> {
+        let s = s.trim();
+        if !s.starts_with(STARCOIN_TAG) && !s.starts_with(ETH_TAG) {
+            return Ok(Self {
+                chain,
+                bytes: parse_digest_bytes(s)?,
+            });
+        }
+        let digest = s.parse::<Self>()?;
+        if digest.chain != chain {
+            return Err(BridgeError::InvalidTxHash);
+        }
+        Ok(digest)
+    }
+}
+
+// Renders a hash string returned by an RPC in the tagged form. Falls back to
+// the raw string if it is not a 32 byte digest, so logs never lose it.
+pub fn tagged(chain: TxChain, hash: &str) -> String {
+    TxDigest::parse_as(chain, hash)
+        .map(|d| d.to_string())
+        .unwrap_or_else(|_| hash.to_string())
+}
+
+fn parse_digest_bytes(s: &str) -> BridgeResult<[u8; 32]> {
+    let s = s.trim();
+    let bytes = if let Some(list) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        list.split(',')
+            .map(|b| b.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| BridgeError::InvalidTxHash)?
+    } else {
+        let hex = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        Hex::decode(hex).map_err(|_| BridgeError::InvalidTxHash)?
+    };
+    bytes.try_into().map_err(|_| BridgeError::InvalidTxHash)
+}
+
+impl fmt::Display for TxDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = match self.chain {
+            TxChain::Starcoin => STARCOIN_TAG,
+            TxChain::Eth => ETH_TAG,
+        };
+        write!(f, "{}0x{}", tag, Hex::encode(self.bytes))
+    }
+}
+
+// Same as Display so `?digest` in tracing fields also renders the tagged form
+impl fmt::Debug for TxDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// Requires a chain tag, except for the Debug array format which was only
+// ever used for Starcoin digests. Use `TxDigest::parse_as` for untagged hex.
+impl FromStr for TxDigest {
+    type Err = BridgeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (chain, rest) = if let Some(rest) = s.strip_prefix(STARCOIN_TAG) {
+            (TxChain::Starcoin, rest)
+        } else if let Some(rest) = s.strip_prefix(ETH_TAG) {
+            (TxChain::Eth, rest)
+        } else if s.starts_with('[') {
+            (TxChain::Starcoin, s)
+        } else {
+            return Err(BridgeError::InvalidTxHash);
+        };
+        Ok(Self {
+            chain,
+            bytes: parse_digest_bytes(rest)?,
+        })
+    }
+}
+
+impl Serialize for TxDigest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxDigest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid tx digest: {s}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_digest_format() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xab;
+        bytes[31] = 0x01;
+        let hex = format!("ab{}01", "00".repeat(30));
+
+        let starcoin = TxDigest::starcoin(bytes);
+        assert_eq!(starcoin.to_string(), format!("stc:0x{hex}"));
+        assert_eq!(format!("{:?}", starcoin), format!("stc:0x{hex}"));
+
+        let eth = TxDigest::eth(TxHash::from(bytes));
+        assert_eq!(eth.to_string(), format!("eth:0x{hex}"));
+        assert_eq!(
+            serde_json::to_string(&eth).unwrap(),
+            format!("\"eth:0x{hex}\"")
+        );
+    }
+
+    #[test]
+    fn test_tx_digest_parse() {
+        let bytes = [7u8; 32];
+        let hex = "07".repeat(32);
+        let starcoin = TxDigest::starcoin(bytes);
+        let eth = TxDigest::eth(TxHash::from(bytes));
+
+        // Tagged forms round-trip
+        assert_eq!(starcoin.to_string().parse::<TxDigest>().unwrap(), starcoin);
+        assert_eq!(eth.to_string().parse::<TxDigest>().unwrap(), eth);
+        assert_eq!(format!("eth:{hex}").parse::<TxDigest>().unwrap(), eth);
+        assert_eq!(
+            serde_json::from_str::<TxDigest>(&format!("\"stc:0x{hex}\"")).unwrap(),
+            starcoin
+        );
+
+        // Legacy Debug array of a Starcoin digest
+        assert_eq!(
+            format!("{:?}", bytes).parse::<TxDigest>().unwrap(),
+            starcoin
+        );
+
+        // Untagged hex is ambiguous without context
+        assert!(format!("0x{hex}").parse::<TxDigest>().is_err());
+        for s in [format!("0x{hex}"), format!("0X{hex}"), hex.clone()] {
+            assert_eq!(TxDigest::parse_as(TxChain::Eth, &s).unwrap(), eth);
+            assert_eq!(TxDigest::parse_as(TxChain::Starcoin, &s).unwrap(), starcoin);
+        }
+        // A tag must agree with the expected chain
+        assert!(TxDigest::parse_as(TxChain::Eth, &starcoin.to_string()).is_err());
+
+        // Wrong lengths and garbage
+        assert!(TxDigest::parse_as(TxChain::Eth, "0x1234").is_err());
+        assert!(TxDigest::parse_as(TxChain::Eth, &format!("0x{hex}00")).is_err());
+        assert!(TxDigest::parse_as(TxChain::Starcoin, "[1, 2, 3]").is_err());
+        assert!("stc:0xzz".parse::<TxDigest>().is_err());
+
+        assert_eq!(tagged(TxChain::Starcoin, &hex), starcoin.to_string());
+        assert_eq!(tagged(TxChain::Starcoin, "pending"), "pending");
+    }
+
+    // Support greps for `stc:0x` / `eth:0x`, so the claim and deposit paths must
+    // log digests through `TxDigest` rather than raw Debug output.
+    #[test]
+    fn test_claim_path_logs_tagged_digests() {
+        let executor = include_str!("action_executor.rs");
+        let orchestrator = include_str!("orchestrator.rs");
+        for (name, source) in [
+            ("action_executor.rs", executor),
+            ("orchestrator.rs", orchestrator),
+        ] {
+            for untagged in ["?tx_digest,", "=?log.tx_hash"] {
+                assert!(
+                    !source.contains(untagged),
+                    "{name} logs an untagged digest: {untagged}"
+                );
+            }
+        }
+        assert!(executor.contains("tagged(TxChain::Starcoin, &txn_hash)"));
+        assert!(executor.contains("tagged(TxChain::Starcoin, &claim_txn_hash)"));
+        assert!(executor.contains("TxDigest::starcoin(tx_digest)"));
+        assert!(orchestrator.contains("TxDigest::eth(log.tx_hash)"));
+    }
+}
@@ -290,7 +290,18 @@ impl CommitteeTrait for BridgeCommittee {
 // NOTE: starcoin_bridge_authority_aggregation::CommitteeTrait implementation removed
 // Starcoin bridge uses a single-member committee, no quorum aggregation needed
 
-#[derive(Serialize, Copy, Clone, PartialEq, Eq, TryFromPrimitive, Hash, Display)]
+#[derive(
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    TryFromPrimitive,
+    Hash,
+    Display,
+)]
 #[repr(u8)]
 pub enum BridgeActionType {
     TokenTransfer = 0,
@@ -301,6 +312,7 @@ pub enum BridgeActionType {
     EvmContractUpgrade = 5,
     AddTokensOnstarcoin = 6,
     AddTokensOnEvm = 7,
+    TokenPause = 8,
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -320,7 +332,7 @@ impl Debug for BridgeActionKey {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, TryFromPrimitive)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, TryFromPrimitive)]
 #[repr(u8)]
 pub enum BridgeActionStatus {
     Pending = 0,
@@ -449,6 +461,16 @@ pub struct AddTokensOnEvmAction {
     pub token_prices: Vec<u64>,
 }
 
+// Pauses or unpauses bridging of a single token on `chain_id`, leaving the
+// rest of the bridge running. Distinct from the global emergency button.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TokenPauseAction {
+    pub nonce: u64,
+    pub chain_id: BridgeChainId,
+    pub token_id: u8,
+    pub pause: bool,
+}
+
 // The type of actions Bridge Committee verify and sign off to execution.
 // Its relationship with BridgeEvent is similar to the relationship between
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -465,6 +487,7 @@ pub enum BridgeAction {
     EvmContractUpgradeAction(EvmContractUpgradeAction),
     AddTokensOnStarcoinAction(AddTokensOnStarcoinAction),
     AddTokensOnEvmAction(AddTokensOnEvmAction),
+    TokenPauseAction(TokenPauseAction),
 }
 
 impl BridgeAction {
@@ -499,6 +522,7 @@ impl BridgeAction {
             BridgeAction::EvmContractUpgradeAction(a) => a.chain_id,
             BridgeAction::AddTokensOnStarcoinAction(a) => a.chain_id,
             BridgeAction::AddTokensOnEvmAction(a) => a.chain_id,
+            BridgeAction::TokenPauseAction(a) => a.chain_id,
         }
     }
 
@@ -512,6 +536,7 @@ impl BridgeAction {
             BridgeActionType::EvmContractUpgrade => true,
             BridgeActionType::AddTokensOnstarcoin => true,
             BridgeActionType::AddTokensOnEvm => true,
+            BridgeActionType::TokenPause => true,
         }
     }
 
@@ -527,6 +552,7 @@ impl BridgeAction {
             BridgeAction::EvmContractUpgradeAction(_) => BridgeActionType::EvmContractUpgrade,
             BridgeAction::AddTokensOnStarcoinAction(_) => BridgeActionType::AddTokensOnstarcoin,
             BridgeAction::AddTokensOnEvmAction(_) => BridgeActionType::AddTokensOnEvm,
+            BridgeAction::TokenPauseAction(_) => BridgeActionType::TokenPause,
         }
     }
 
@@ -542,6 +568,7 @@ impl BridgeAction {
             BridgeAction::EvmContractUpgradeAction(a) => a.nonce,
             BridgeAction::AddTokensOnStarcoinAction(a) => a.nonce,
             BridgeAction::AddTokensOnEvmAction(a) => a.nonce,
+            BridgeAction::TokenPauseAction(a) => a.nonce,
         }
     }
 
@@ -559,6 +586,14 @@ impl BridgeAction {
             BridgeAction::EvmContractUpgradeAction(_) => APPROVAL_THRESHOLD_EVM_CONTRACT_UPGRADE,
             BridgeAction::AddTokensOnStarcoinAction(_) => APPROVAL_THRESHOLD_ADD_TOKENS_ON_STARCOIN,
             BridgeAction::AddTokensOnEvmAction(_) => APPROVAL_THRESHOLD_ADD_TOKENS_ON_EVM,
+            // Same bar as the emergency button: easy to pause, harder to resume
+            BridgeAction::TokenPauseAction(a) => {
+                if a.pause {
+                    APPROVAL_THRESHOLD_EMERGENCY_PAUSE
+                } else {
+                    APPROVAL_THRESHOLD_EMERGENCY_UNPAUSE
+                }
+            }
         }
     }
 }
@@ -715,6 +750,7 @@ mod tests {
     use starcoin_bridge_types::bridge::{BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER, TOKEN_ID_BTC};
     use starcoin_bridge_types::crypto::get_key_pair;
     use std::collections::HashSet;
+    use std::str::FromStr;
 
     use super::*;
 
@@ -831,9 +867,246 @@ mod tests {
             call_data: vec![],
         });
         assert_eq!(action.approval_threshold(), 5001);
+
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 3,
+            chain_id: BridgeChainId::StarcoinCustom,
+            token_id: TOKEN_ID_BTC,
+            pause: true,
+        });
+        assert_eq!(action.approval_threshold(), 450);
+
+        let action = BridgeAction::TokenPauseAction(TokenPauseAction {
+            nonce: 4,
+            chain_id: BridgeChainId::StarcoinCustom,
+            token_id: TOKEN_ID_BTC,
+            pause: false,
+        });
+        assert_eq!(action.approval_threshold(), 5001);
         Ok(())
     }
 
     // NOTE: test_bridge_committee_filter_blocklisted_authorities removed
     // Starcoin bridge uses single-member committee, shuffle_by_stake is only for testing
+
+    const ENCODING_FIXTURES: &str = include_str!("../fixtures/bridge_action_encodings.json");
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct EncodingFixture {
+        name: String,
+        message: String,
+        signing_payload: String,
+        digest: String,
+    }
+
+    // One action per variant, pinned by fixtures/bridge_action_encodings.json.
+    // Committees verify each other's signatures over these bytes, so a change
+    // here breaks signing across versions and must be deliberate.
+    fn golden_actions() -> Vec<(&'static str, BridgeAction)> {
+        let starcoin_bridge_address = StarcoinAddress::new([
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x64,
+        ]);
+        let eth_address = EthAddress::from_low_u64_be(200);
+        let members_to_update = [
+            "02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4",
+            "027f1178ff417fc9f5b8290bd8876f0a157a505a6c52db100a8492203ddd1d4279",
+        ]
+        .iter()
+        .map(|key| {
+            BridgeAuthorityPublicKeyBytes::from_bytes(&Hex::decode(key).unwrap()).unwrap()
+        })
+        .collect();
+        vec![
+            (
+                "starcoin_to_eth_token_transfer",
+                BridgeAction::StarcoinToEthBridgeAction(StarcoinToEthBridgeAction {
+                    starcoin_bridge_tx_digest: [0; 32],
+                    starcoin_bridge_tx_event_index: 1,
+                    starcoin_bridge_event: EmittedStarcoinToEthTokenBridgeV1 {
+                        nonce: 10,
+                        starcoin_bridge_chain_id: BridgeChainId::StarcoinTestnet,
+                        eth_chain_id: BridgeChainId::EthSepolia,
+                        starcoin_bridge_address,
+                        eth_address,
+                        token_id: 3,
+                        amount_starcoin_bridge_adjusted: 12345,
+                    },
+                }),
+            ),
+            (
+                "eth_to_starcoin_token_transfer",
+                BridgeAction::EthToStarcoinBridgeAction(EthToStarcoinBridgeAction {
+                    eth_tx_hash: EthTransactionHash::zero(),
+                    eth_event_index: 1,
+                    eth_bridge_event: EthToStarcoinTokenBridgeV1 {
+                        nonce: 10,
+                        starcoin_bridge_chain_id: BridgeChainId::StarcoinTestnet,
+                        eth_chain_id: BridgeChainId::EthSepolia,
+                        starcoin_bridge_address,
+                        eth_address,
+                        token_id: 3,
+                        starcoin_bridge_adjusted_amount: 12345,
+                    },
+                }),
+            ),
+            (
+                "committee_blocklist",
+                BridgeAction::BlocklistCommitteeAction(BlocklistCommitteeAction {
+                    nonce: 68,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    blocklist_type: BlocklistType::Unblocklist,
+                    members_to_update,
+                }),
+            ),
+            (
+                "emergency_pause",
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 55,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    action_type: EmergencyActionType::Pause,
+                }),
+            ),
+            (
+                "emergency_unpause",
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 56,
+                    chain_id: BridgeChainId::EthSepolia,
+                    action_type: EmergencyActionType::Unpause,
+                }),
+            ),
+            (
+                "limit_update",
+                BridgeAction::LimitUpdateAction(LimitUpdateAction {
+                    nonce: 15,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    sending_chain_id: BridgeChainId::EthCustom,
+                    new_usd_limit: 1_000_000 * USD_MULTIPLIER,
+                }),
+            ),
+            (
+                "asset_price_update",
+                BridgeAction::AssetPriceUpdateAction(AssetPriceUpdateAction {
+                    nonce: 266,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    token_id: TOKEN_ID_BTC,
+                    new_usd_price: 100_000 * USD_MULTIPLIER,
+                }),
+            ),
+            (
+                "evm_contract_upgrade",
+                BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+                    nonce: 123,
+                    chain_id: BridgeChainId::EthCustom,
+                    proxy_address: EthAddress::repeat_byte(6),
+                    new_impl_address: EthAddress::repeat_byte(9),
+                    call_data: Keccak256::digest("initializeV2()").digest[0..4].to_vec(),
+                }),
+            ),
+            (
+                "add_tokens_on_starcoin",
+                BridgeAction::AddTokensOnStarcoinAction(AddTokensOnStarcoinAction {
+                    nonce: 0,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    native: false,
+                    token_ids: vec![1, 2, 3, 4],
+                    token_type_names: [
+                        "0x9b5e13bcd0cb23ff25c07698e89d4805::btc::BTC",
+                        "0x7970d71c03573f540a7157f0d3970e11::eth::ETH",
+                        "0x500e429a24478405d5130222b20f8570::usdc::USDC",
+                        "0x46bfe51da1bd9511919a92eb11541496::usdt::USDT",
+                    ]
+                    .iter()
+                    .map(|name| TypeTag::from_str(name).unwrap())
+                    .collect(),
+                    token_prices: vec![500_000_000, 30_000_000, 1_000, 1_000],
+                }),
+            ),
+            (
+                "add_tokens_on_evm",
+                BridgeAction::AddTokensOnEvmAction(AddTokensOnEvmAction {
+                    nonce: 0,
+                    chain_id: BridgeChainId::EthCustom,
+                    native: true,
+                    token_ids: vec![99, 100, 101],
+                    token_addresses: vec![
+                        EthAddress::from_str("0x6B175474E89094C44Da98b954EedeAC495271d0F").unwrap(),
+                        EthAddress::from_str("0xae7ab96520DE3A18E5e111B5EaAb095312D7fE84").unwrap(),
+                        EthAddress::from_str("0xC18360217D8F7Ab5e7c516566761Ea12Ce7F9D72").unwrap(),
+                    ],
+                    token_starcoin_bridge_decimals: vec![5, 6, 7],
+                    token_prices: vec![1_000_000_000, 2_000_000_000, 3_000_000_000],
+                }),
+            ),
+            (
+                "token_pause",
+                BridgeAction::TokenPauseAction(TokenPauseAction {
+                    nonce: 7,
+                    chain_id: BridgeChainId::StarcoinCustom,
+                    token_id: 3,
+                    pause: true,
+                }),
+            ),
+        ]
+    }
+
+    // Run with UPDATE_BRIDGE_ACTION_FIXTURES=1 to rewrite the fixtures after an
+    // intended encoding change
+    #[test]
+    fn test_bridge_action_encoding_golden() {
+        let actions = golden_actions();
+
+        // Adding a variant fails to compile here until it gets a fixture
+        let variants = actions
+            .iter()
+            .map(|(_, action)| match action {
+                BridgeAction::StarcoinToEthBridgeAction(_) => 0,
+                BridgeAction::EthToStarcoinBridgeAction(_) => 1,
+                BridgeAction::BlocklistCommitteeAction(_) => 2,
+                BridgeAction::EmergencyAction(_) => 3,
+                BridgeAction::LimitUpdateAction(_) => 4,
+                BridgeAction::AssetPriceUpdateAction(_) => 5,
+                BridgeAction::EvmContractUpgradeAction(_) => 6,
+                BridgeAction::AddTokensOnStarcoinAction(_) => 7,
+                BridgeAction::AddTokensOnEvmAction(_) => 8,
+                BridgeAction::TokenPauseAction(_) => 9,
+            })
+            .collect::<BTreeSet<_>>();
+        assert_eq!(variants.len(), 10);
+
+        let encoded = actions
+            .iter()
+            .map(|(name, action)| {
+                let encoded = action.encode().unwrap();
+                assert_eq!(encoded.signing_payload, action.to_bytes().unwrap());
+                assert_eq!(BridgeActionDigest::new(encoded.digest), action.digest());
+                EncodingFixture {
+                    name: name.to_string(),
+                    message: Hex::encode(&encoded.message),
+                    signing_payload: Hex::encode(&encoded.signing_payload),
+                    digest: Hex::encode(encoded.digest),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if std::env::var("UPDATE_BRIDGE_ACTION_FIXTURES").is_ok() {
+            let path = format!(
+                "{}/fixtures/bridge_action_encodings.json",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let json = serde_json::to_string_pretty(&encoded).unwrap();
+            std::fs::write(path, json + "\n").unwrap();
+            return;
+        }
+
+        let fixtures: Vec<EncodingFixture> = serde_json::from_str(ENCODING_FIXTURES).unwrap();
+        assert_eq!(fixtures.len(), encoded.len());
+        for (fixture, encoded) in fixtures.iter().zip(&encoded) {
+            assert_eq!(
+                fixture, encoded,
+                "Encoding of {} changed, which breaks committee signatures",
+                encoded.name
+            );
+        }
+    }
 }
@@ -5,42 +5,189 @@ use crate::abi::{
     EthBridgeCommittee, EthBridgeConfig, EthBridgeLimiter, EthBridgeVault, EthStarcoinBridge,
 };
 use crate::config::{
-    default_ed25519_key_pair, BridgeNodeConfig, EthConfig, MetricsConfig, StarcoinConfig,
-    WatchdogConfig,
+    default_ed25519_key_pair, ActionQueueConfig, BridgeNodeConfig, CommitteeConcentrationConfig,
+    EthConfig, MetricsConfig, StarcoinConfig, StarcoinRpcRetryConfig, WatchdogConfig,
+    DEFAULT_CLIENT_DB_PATH, DEFAULT_METRICS_PORT,
 };
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::error::{BridgeError, BridgeResult};
+use crate::secret::Secret;
 use crate::server::APPLICATION_JSON;
+use crate::starcoin_bridge_transaction_builder::GasConfig;
 use crate::types::BridgeAction;
 use crate::types::BridgeCommittee;
 use anyhow::anyhow;
+use ethers::abi::{Detokenize, Function, ParamType, Token};
+use ethers::contract::ContractCall;
 use ethers::core::k256::ecdsa::SigningKey;
 use ethers::middleware::SignerMiddleware;
 use ethers::prelude::*;
 use ethers::providers::{Http, Provider};
-use ethers::signers::Wallet;
-use ethers::types::Address as EthAddress;
+#[cfg(feature = "eth-wallets")]
+use ethers::signers::coins_bip39::English;
+#[cfg(feature = "eth-wallets")]
+use ethers::signers::{HDPath, Ledger, LedgerError, MnemonicBuilder};
+use ethers::signers::{Wallet, WalletError};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::transaction::eip712::Eip712;
+use ethers::types::{Address as EthAddress, Bytes, TransactionRequest, H160};
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::secp256k1::Secp256k1KeyPair;
+use fastcrypto::secp256r1::Secp256r1KeyPair;
 use fastcrypto::traits::EncodeDecodeBase64;
 use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
 use starcoin_bridge_config::Config;
 use starcoin_bridge_json_rpc_types::StarcoinSystemStateSummary;
+use starcoin_bridge_keys::encrypted;
+use starcoin_bridge_keys::keygen::KeyScheme;
 use starcoin_bridge_keys::keypair_file::read_key;
 use starcoin_bridge_sdk::wallet_context::WalletContext;
-use starcoin_bridge_types::base_types::StarcoinAddress;
 use starcoin_bridge_types::bridge::BridgeChainId;
 use starcoin_bridge_types::committee::StakeUnit;
 use starcoin_bridge_types::crypto::get_key_pair;
 use starcoin_bridge_types::crypto::StarcoinKeyPair;
+use starcoin_bridge_types::crypto::{starcoin_address_from_public_key, SECP256K1_FLAG};
 use starcoin_bridge_types::transaction::ObjectArg;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use tracing::debug;
 
-pub type EthSigner = SignerMiddleware<Provider<Http>, Wallet<SigningKey>>;
+pub type EthSigner = SignerMiddleware<Provider<Http>, EthWallet>;
+
+// Derivation path of the first account of a BIP-44 Ethereum wallet, as used
+// by MetaMask and Ledger Live
+#[cfg(feature = "eth-wallets")]
+pub const DEFAULT_ETH_HD_PATH: &str = "m/44'/60'/0'/0/0";
+
+#[cfg(feature = "eth-wallets")]
+fn default_eth_hd_path() -> String {
+    DEFAULT_ETH_HD_PATH.to_string()
+}
+
+// Where the key that signs Eth transactions comes from. Mnemonics and
+// Ledger devices need the `eth-wallets` feature, which only the CLI enables.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum EthSignerSource {
+    // The raw private key of the configured key file
+    #[default]
+    KeyFile,
+    // A BIP-39 mnemonic phrase, read from `mnemonic-path`
+    #[cfg(feature = "eth-wallets")]
+    #[serde(rename_all = "kebab-case")]
+    Mnemonic {
+        mnemonic_path: PathBuf,
+        #[serde(default = "default_eth_hd_path")]
+        derivation_path: String,
+    },
+    // A Ledger device with the Ethereum app open
+    #[cfg(feature = "eth-wallets")]
+    #[serde(rename_all = "kebab-case")]
+    Ledger {
+        #[serde(default = "default_eth_hd_path")]
+        hd_path: String,
+    },
+}
+
+impl EthSignerSource {
+    pub fn is_key_file(&self) -> bool {
+        matches!(self, Self::KeyFile)
+    }
+}
+
+// The signer behind `EthSigner`: a key held in memory, from a key file or a
+// mnemonic, or a Ledger device that signs on the device. `EthSigner` is
+// cloned per transaction, so the device is shared, and its chain id is kept
+// here since it can't be changed through the `Arc`.
+#[derive(Clone, Debug)]
+pub enum EthWallet {
+    Local(Wallet<SigningKey>),
+    #[cfg(feature = "eth-wallets")]
+    Ledger { device: Arc<Ledger>, chain_id: u64 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EthWalletError {
+    #[error(transparent)]
+    Local(#[from] WalletError),
+    #[cfg(feature = "eth-wallets")]
+    #[error(transparent)]
+    Ledger(#[from] LedgerError),
+}
+
+#[async_trait::async_trait]
+impl Signer for EthWallet {
+    type Error = EthWalletError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        Ok(match self {
+            Self::Local(wallet) => wallet.sign_message(message).await?,
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { device, .. } => device.sign_message(message).await?,
+        })
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        Ok(match self {
+            Self::Local(wallet) => wallet.sign_transaction(tx).await?,
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { device, chain_id } => {
+                let mut tx = tx.clone();
+                if tx.chain_id().is_none() {
+                    tx.set_chain_id(*chain_id);
+                }
+                device.sign_transaction(&tx).await?
+            }
+        })
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        Ok(match self {
+            Self::Local(wallet) => wallet.sign_typed_data(payload).await?,
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { device, .. } => device.sign_typed_data(payload).await?,
+        })
+    }
+
+    fn address(&self) -> EthAddress {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { device, .. } => device.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            Self::Local(wallet) => wallet.chain_id(),
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { chain_id, .. } => *chain_id,
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            Self::Local(wallet) => Self::Local(wallet.with_chain_id(chain_id)),
+            #[cfg(feature = "eth-wallets")]
+            Self::Ledger { device, .. } => Self::Ledger {
+                device,
+                chain_id: chain_id.into(),
+            },
+        }
+    }
+}
 
 pub struct EthBridgeContracts<P> {
     pub bridge: EthStarcoinBridge<Provider<P>>,
@@ -51,8 +198,10 @@ pub struct EthBridgeContracts<P> {
 }
 
 // Generate Bridge Authority key (Secp256k1KeyPair) and write to a file as base64 encoded `privkey`.
+// With `encrypt` the file is encrypted with a passphrase, see `encrypted::read_passphrase`.
 pub fn generate_bridge_authority_key_and_write_to_file(
     path: &PathBuf,
+    encrypt: bool,
 ) -> Result<(), anyhow::Error> {
     use fastcrypto::traits::KeyPair;
     let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
@@ -61,38 +210,40 @@ pub fn generate_bridge_authority_key_and_write_to_file(
         "Corresponding Ethereum address by this ecdsa key: {:?}",
         eth_address
     );
-    // Secp256k1PublicKey doesn't have a direct conversion to StarcoinAddress
-    // For testing, use first 16 bytes of public key
-    let pub_bytes = kp.public().as_bytes();
     let starcoin_bridge_address =
-        StarcoinAddress::from_bytes(&pub_bytes[..16.min(pub_bytes.len())])
-            .unwrap_or(StarcoinAddress::ZERO);
+        starcoin_address_from_public_key(kp.public().as_bytes(), SECP256K1_FLAG);
     println!(
         "Corresponding Starcoin address by this ecdsa key: {:?}",
         starcoin_bridge_address
     );
-    let base64_encoded = kp.encode_base64();
-    std::fs::write(path, base64_encoded)
-        .map_err(|err| anyhow!("Failed to write encoded key to path: {:?}", err))
+    write_key_file(path, kp.encode_base64(), encrypt)
 }
 
-// Generate Bridge Client key (Secp256k1KeyPair or Ed25519KeyPair) and write to a file as base64 encoded `flag || privkey`.
+// Generate Bridge Client key (Ed25519KeyPair, Secp256k1KeyPair or Secp256r1KeyPair) and write to a file as base64 encoded `flag || privkey`.
 pub fn generate_bridge_client_key_and_write_to_file(
     path: &PathBuf,
-    use_ecdsa: bool,
+    key_scheme: KeyScheme,
+    encrypt: bool,
 ) -> Result<(), anyhow::Error> {
     use fastcrypto::traits::KeyPair;
-    let kp = if use_ecdsa {
-        let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
-        let eth_address = BridgeAuthorityPublicKeyBytes::from(kp.public()).to_eth_address();
-        println!(
-            "Corresponding Ethereum address by this ecdsa key: {:?}",
-            eth_address
-        );
-        StarcoinKeyPair::Secp256k1(kp)
-    } else {
-        let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
-        StarcoinKeyPair::Ed25519(kp)
+    let kp = match key_scheme {
+        KeyScheme::Secp256k1 => {
+            let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+            let eth_address = BridgeAuthorityPublicKeyBytes::from(kp.public()).to_eth_address();
+            println!(
+                "Corresponding Ethereum address by this ecdsa key: {:?}",
+                eth_address
+            );
+            StarcoinKeyPair::Secp256k1(kp)
+        }
+        KeyScheme::Secp256r1 => {
+            let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+            StarcoinKeyPair::Secp256r1(kp)
+        }
+        KeyScheme::Ed25519 => {
+            let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+            StarcoinKeyPair::Ed25519(kp)
+        }
     };
     // Use proper Starcoin address derivation (SHA3-256 hash algorithm)
     let starcoin_bridge_address = kp.starcoin_address();
@@ -101,7 +252,16 @@ pub fn generate_bridge_client_key_and_write_to_file(
         starcoin_bridge_address
     );
 
-    let contents = kp.encode_base64();
+    write_key_file(path, kp.encode_base64(), encrypt)
+}
+
+fn write_key_file(path: &PathBuf, contents: String, encrypt: bool) -> Result<(), anyhow::Error> {
+    let contents = if encrypt {
+        let passphrase = encrypted::read_passphrase(true)?;
+        encrypted::encrypt(&contents, &passphrase)?
+    } else {
+        contents
+    };
     std::fs::write(path, contents)
         .map_err(|err| anyhow!("Failed to write encoded key to path: {:?}", err))
 }
@@ -121,17 +281,33 @@ pub async fn get_eth_contract_addresses<P: ethers::providers::JsonRpcClient + 's
     EthAddress,
 )> {
     let starcoin_bridge = EthStarcoinBridge::new(bridge_proxy_address, provider.clone());
-    let committee_address: EthAddress = starcoin_bridge.committee().call().await?;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let committee = batch.add(&starcoin_bridge.committee());
+    let limiter = batch.add(&starcoin_bridge.limiter());
+    let vault = batch.add(&starcoin_bridge.vault());
+    let results = batch.execute().await;
+    let committee_address = committee.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let limiter_address = limiter.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let vault_address = vault.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+
     let committee = EthBridgeCommittee::new(committee_address, provider.clone());
-    let config_address: EthAddress = committee.config().call().await?;
-    let bridge_config = EthBridgeConfig::new(config_address, provider.clone());
-    let limiter_address: EthAddress = starcoin_bridge.limiter().call().await?;
-    let vault_address: EthAddress = starcoin_bridge.vault().call().await?;
     let vault = EthBridgeVault::new(vault_address, provider.clone());
-    let weth_address: EthAddress = vault.w_eth().call().await?;
-    let usdt_address: EthAddress = bridge_config.token_address_of(4).call().await?;
-    let wbtc_address: EthAddress = bridge_config.token_address_of(1).call().await?;
-    let lbtc_address: EthAddress = bridge_config.token_address_of(6).call().await?;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let config = batch.add(&committee.config());
+    let weth = batch.add(&vault.w_eth());
+    let results = batch.execute().await;
+    let config_address = config.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let weth_address = weth.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+
+    let bridge_config = EthBridgeConfig::new(config_address, provider.clone());
+    let mut batch = EthCallBatch::new(provider.clone());
+    let usdt = batch.add(&bridge_config.token_address_of(4));
+    let wbtc = batch.add(&bridge_config.token_address_of(1));
+    let lbtc = batch.add(&bridge_config.token_address_of(6));
+    let results = batch.execute().await;
+    let usdt_address = usdt.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let wbtc_address = wbtc.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let lbtc_address = lbtc.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
 
     Ok((
         committee_address,
@@ -151,9 +327,14 @@ pub async fn get_eth_contracts<P: ethers::providers::JsonRpcClient + 'static>(
     provider: &Arc<Provider<P>>,
 ) -> anyhow::Result<EthBridgeContracts<P>> {
     let starcoin_bridge = EthStarcoinBridge::new(bridge_proxy_address, provider.clone());
-    let committee_address: EthAddress = starcoin_bridge.committee().call().await?;
-    let limiter_address: EthAddress = starcoin_bridge.limiter().call().await?;
-    let vault_address: EthAddress = starcoin_bridge.vault().call().await?;
+    let mut batch = EthCallBatch::new(provider.clone());
+    let committee = batch.add(&starcoin_bridge.committee());
+    let limiter = batch.add(&starcoin_bridge.limiter());
+    let vault = batch.add(&starcoin_bridge.vault());
+    let results = batch.execute().await;
+    let committee_address = committee.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let limiter_address = limiter.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
+    let vault_address = vault.decode(&results).map_err(|e| anyhow!("{:?}", e))?;
     let committee = EthBridgeCommittee::new(committee_address, provider.clone());
     let config_address: EthAddress = committee.config().call().await?;
 
@@ -169,6 +350,166 @@ pub async fn get_eth_contracts<P: ethers::providers::JsonRpcClient + 'static>(
     })
 }
 
+// Canonical Multicall3 deployment, at the same address on every chain that has one.
+pub const MULTICALL3_ADDRESS: EthAddress = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+const AGGREGATE3_SIGNATURE: &str = "aggregate3((address,bool,bytes)[])";
+
+// View calls read in a single round trip through Multicall3 `aggregate3`. On chains
+// without Multicall3 the calls are made one by one instead. Either way each call
+// succeeds or fails on its own, so one bad read does not sink the rest.
+pub struct EthCallBatch<M> {
+    client: Arc<M>,
+    multicall_address: EthAddress,
+    // (target, calldata, function name for error messages)
+    calls: Vec<(EthAddress, Bytes, String)>,
+}
+
+// A call queued in an `EthCallBatch`. Decodes that call's output from the batch results.
+pub struct EthCallHandle<D> {
+    index: usize,
+    function: Function,
+    _output: PhantomData<D>,
+}
+
+impl<D: Detokenize> EthCallHandle<D> {
+    pub fn decode(&self, results: &[BridgeResult<Bytes>]) -> BridgeResult<D> {
+        let output = results.get(self.index).cloned().unwrap_or_else(|| {
+            Err(BridgeError::InternalError(format!(
+                "No result for {} in batch",
+                self.function.name
+            )))
+        })?;
+        let tokens = self.function.decode_output(&output).map_err(|e| {
+            BridgeError::permanent_rpc(format!("Failed to decode {}: {e}", self.function.name))
+        })?;
+        D::from_tokens(tokens).map_err(|e| {
+            BridgeError::permanent_rpc(format!("Failed to decode {}: {e}", self.function.name))
+        })
+    }
+}
+
+impl<M: Middleware> EthCallBatch<M> {
+    pub fn new(client: Arc<M>) -> Self {
+        Self::with_multicall_address(client, MULTICALL3_ADDRESS)
+    }
+
+    pub fn with_multicall_address(client: Arc<M>, multicall_address: EthAddress) -> Self {
+        Self {
+            client,
+            multicall_address,
+            calls: vec![],
+        }
+    }
+
+    pub fn add<D: Detokenize>(&mut self, call: &ContractCall<M, D>) -> EthCallHandle<D> {
+        self.calls.push((
+            call.tx.to_addr().copied().unwrap_or_default(),
+            call.tx.data().cloned().unwrap_or_default(),
+            call.function.name.clone(),
+        ));
+        EthCallHandle {
+            index: self.calls.len() - 1,
+            function: call.function.clone(),
+            _output: PhantomData,
+        }
+    }
+
+    // Returns one result per queued call, in the order they were added.
+    pub async fn execute(&self) -> Vec<BridgeResult<Bytes>> {
+        if self.calls.is_empty() {
+            return vec![];
+        }
+        match self.aggregate3().await {
+            Ok(results) => results,
+            Err(e) => {
+                debug!(
+                    "Multicall3 read failed, falling back to sequential calls: {:?}",
+                    e
+                );
+                self.execute_sequential().await
+            }
+        }
+    }
+
+    pub async fn execute_sequential(&self) -> Vec<BridgeResult<Bytes>> {
+        let mut results = Vec::with_capacity(self.calls.len());
+        for (target, data, name) in &self.calls {
+            let result = eth_call(self.client.as_ref(), *target, data.clone())
+                .await
+                .map_err(|e| BridgeError::transient_rpc(format!("{name} failed: {:?}", e)));
+            results.push(result);
+        }
+        results
+    }
+
+    async fn aggregate3(&self) -> BridgeResult<Vec<BridgeResult<Bytes>>> {
+        let calls = self
+            .calls
+            .iter()
+            .map(|(target, data, _)| {
+                Token::Tuple(vec![
+                    Token::Address(*target),
+                    Token::Bool(true),
+                    Token::Bytes(data.to_vec()),
+                ])
+            })
+            .collect();
+        let mut data = ethers::utils::id(AGGREGATE3_SIGNATURE).to_vec();
+        data.extend(ethers::abi::encode(&[Token::Array(calls)]));
+        // An address without code returns empty output, which fails to decode below
+        let output = eth_call(self.client.as_ref(), self.multicall_address, data.into()).await?;
+        let output_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+            ParamType::Bool,
+            ParamType::Bytes,
+        ])));
+        let Ok(mut tokens) = ethers::abi::decode(&[output_type], &output) else {
+            return Err(BridgeError::permanent_rpc(format!(
+                "Unexpected aggregate3 output from {:?}",
+                self.multicall_address
+            )));
+        };
+        let Some(Token::Array(results)) = tokens.pop() else {
+            return Err(BridgeError::permanent_rpc("Unexpected aggregate3 output"));
+        };
+        if results.len() != self.calls.len() {
+            return Err(BridgeError::permanent_rpc(format!(
+                "aggregate3 returned {} results for {} calls",
+                results.len(),
+                self.calls.len()
+            )));
+        }
+        results
+            .into_iter()
+            .zip(&self.calls)
+            .map(|(result, (_, _, name))| match result {
+                Token::Tuple(fields) => match fields.as_slice() {
+                    [Token::Bool(true), Token::Bytes(output)] => Ok(Ok(output.clone().into())),
+                    [Token::Bool(false), Token::Bytes(revert)] => {
+                        Ok(Err(BridgeError::permanent_rpc(format!(
+                            "{name} reverted: 0x{}",
+                            Hex::encode(revert)
+                        ))))
+                    }
+                    _ => Err(BridgeError::permanent_rpc("Unexpected aggregate3 result")),
+                },
+                _ => Err(BridgeError::permanent_rpc("Unexpected aggregate3 result")),
+            })
+            .collect()
+    }
+}
+
+async fn eth_call<M: Middleware>(client: &M, to: EthAddress, data: Bytes) -> BridgeResult<Bytes> {
+    let tx: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+    client
+        .call(&tx, None)
+        .await
+        .map_err(|e| BridgeError::transient_rpc(format!("eth_call to {:?} failed: {e}", to)))
+}
+
 // Read bridge key from a file and print the corresponding information.
 // If `is_validator_key` is true, the key must be a Secp256k1 key.
 pub fn examine_key(path: &PathBuf, is_validator_key: bool) -> Result<(), anyhow::Error> {
@@ -185,6 +526,10 @@ pub fn examine_key(path: &PathBuf, is_validator_key: bool) -> Result<(), anyhow:
             println!("Ed25519 key:");
             kp.public().as_bytes().to_vec()
         }
+        StarcoinKeyPair::Secp256r1(kp) => {
+            println!("Secp256r1 key:");
+            kp.public().as_bytes().to_vec()
+        }
     };
     // Derive Starcoin address using proper algorithm:
     // SHA3-256(pubkey || scheme_flag), take last 16 bytes
@@ -197,39 +542,346 @@ pub fn examine_key(path: &PathBuf, is_validator_key: bool) -> Result<(), anyhow:
     Ok(())
 }
 
-// Generate Bridge Node Config template and write to a file.
+// Comment written above each field of the bridge node config template, by
+// dotted path. Fields the template leaves unset are written commented out,
+// with the example value given here.
+const NODE_CONFIG_TEMPLATE_DOCS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "server-listen-port",
+        "Port the bridge server listens on for signature requests.",
+        None,
+    ),
+    (
+        "metrics-port",
+        "Port of the Prometheus metrics server.",
+        None,
+    ),
+    (
+        "bridge-authority-key-path",
+        "File with the bridge authority key. Must be Secp256k1, as its signatures\n\
+         are verified on Eth too.",
+        None,
+    ),
+    (
+        "run-client",
+        "Whether to run the client, which relays approved actions to the chains.\n\
+         Requires `db-path` and `eth.eth-contracts-start-block-fallback`.",
+        None,
+    ),
+    (
+        "db-path",
+        "Path of the client storage. Used when `run-client` is true.",
+        None,
+    ),
+    (
+        "approved-governance-actions",
+        "Governance actions this node signs when a client asks for them.",
+        None,
+    ),
+    ("starcoin", "Starcoin side of the bridge.", None),
+    (
+        "starcoin.starcoin-bridge-rpc-url",
+        "Rpc url of the Starcoin fullnode.",
+        None,
+    ),
+    (
+        "starcoin.starcoin-bridge-proxy-address",
+        "Address the bridge Move modules are deployed at, 16 bytes hex.",
+        None,
+    ),
+    (
+        "starcoin.starcoin-bridge-chain-id",
+        "Bridge chain id of the Starcoin side: 0 mainnet, 1 testnet, 2 custom.",
+        None,
+    ),
+    (
+        "starcoin.bridge-client-key-path",
+        "File with the key the client submits transactions with, of any scheme.\n\
+         The authority key is used when unset.",
+        Some("/path/to/your/bridge_client_key"),
+    ),
+    (
+        "starcoin.starcoin-bridge-module-last-processed-event-id-override",
+        "Starcoin event cursor to resume from instead of the stored one, as\n\
+         [block-number, event-seq]. Remove it again after a restart.",
+        Some("[0, 0]"),
+    ),
+    (
+        "starcoin.rpc-retry",
+        "Retries and rate limiting of calls to the fullnode.",
+        None,
+    ),
+    (
+        "starcoin.rpc-retry.max-attempts",
+        "Attempts per call, including the first. 1 disables retries.",
+        None,
+    ),
+    (
+        "starcoin.rpc-retry.initial-backoff-ms",
+        "Delay before the first retry, doubled after each.",
+        None,
+    ),
+    (
+        "starcoin.rpc-retry.max-backoff-ms",
+        "Longest delay between retries.",
+        None,
+    ),
+    (
+        "starcoin.rpc-retry.max-requests-per-second",
+        "Max requests per second to the fullnode. Unlimited when unset.",
+        Some("50"),
+    ),
+    (
+        "starcoin.gas-price-multiplier",
+        "Overrides `gas.gas-unit-price-multiplier`, kept for older configs.",
+        None,
+    ),
+    (
+        "starcoin.gas",
+        "Gas of the transactions the client submits.",
+        None,
+    ),
+    (
+        "starcoin.gas.max-gas-amount",
+        "Max gas of a transaction, unless its action type has an override.",
+        None,
+    ),
+    (
+        "starcoin.gas.gas-unit-price-multiplier",
+        "Transactions bid the reference gas price times this. At least 1.",
+        None,
+    ),
+    (
+        "starcoin.gas.gas-budget-overrides",
+        "Max gas by action type, e.g. TokenTransfer or EmergencyButton.",
+        Some("{ TokenTransfer: 2000000 }"),
+    ),
+    ("eth", "Eth side of the bridge.", None),
+    ("eth.eth-rpc-url", "Rpc url of the Eth fullnode.", None),
+    (
+        "eth.eth-bridge-proxy-address",
+        "Proxy address of the bridge contract, 20 bytes hex.",
+        None,
+    ),
+    (
+        "eth.eth-bridge-chain-id",
+        "Bridge chain id of the Eth side: 10 mainnet, 11 sepolia, 12 custom.",
+        None,
+    ),
+    (
+        "eth.eth-contracts-start-block-fallback",
+        "Block the Eth syncer starts from when storage has no cursor yet, usually\n\
+         the block the bridge contracts were deployed in. Required by the client.",
+        None,
+    ),
+    (
+        "eth.eth-contracts-start-block-override",
+        "Block the Eth syncer starts from instead of the stored cursor, to\n\
+         reprocess events. Remove it again after a restart.",
+        Some("0"),
+    ),
+    (
+        "eth.eth-use-latest-block",
+        "Follow the latest instead of the finalized block, for local Anvil chains.",
+        None,
+    ),
+    (
+        "metrics-key-pair",
+        "Network key metrics are pushed with.",
+        None,
+    ),
+    (
+        "metrics",
+        "Pushing metrics to a proxy. Disabled when unset.",
+        None,
+    ),
+    ("metrics.push-url", "Url metrics are pushed to.", None),
+    (
+        "metrics.push-interval-seconds",
+        "Seconds between pushes.",
+        Some("60"),
+    ),
+    (
+        "metrics.auth-username",
+        "Basic auth username of the push url.",
+        Some("bridge"),
+    ),
+    (
+        "metrics.auth-password",
+        "Basic auth password of the push url.",
+        None,
+    ),
+    (
+        "watchdog-config",
+        "Total supplies to watch on Starcoin, by coin name and type tag.",
+        None,
+    ),
+    (
+        "watchdog-config.total-supplies",
+        "Coin name to coin type tag.",
+        None,
+    ),
+    (
+        "action-queue",
+        "Bounds of the queue between event processing and the action executor.",
+        None,
+    ),
+    (
+        "action-queue.capacity",
+        "Actions buffered for the executor. The syncers pause when it is full.",
+        None,
+    ),
+    (
+        "action-queue.high-water-mark",
+        "Queue depth considered congested.",
+        None,
+    ),
+    (
+        "action-queue.high-water-warn-after-secs",
+        "Warn when the queue stays above the high water mark this long.",
+        None,
+    ),
+    (
+        "scheduled-actions",
+        "Directory of pre-signed governance actions to execute once their time\n\
+         window opens. Requires `run-client`.",
+        Some("{ dir: /path/to/your/scheduled_actions, poll-interval-secs: 10 }"),
+    ),
+    (
+        "committee-concentration",
+        "Alerting on committee members that share infrastructure.",
+        None,
+    ),
+    (
+        "committee-concentration.max-group-voting-power-percent",
+        "Alert when members sharing infrastructure hold more voting power.",
+        None,
+    ),
+    (
+        "committee-concentration.asn-table-path",
+        "File mapping IP prefixes to ASNs, one `<prefix>/<len> <asn>` per line.",
+        Some("/path/to/your/asn_table"),
+    ),
+    (
+        "expose-node-info",
+        "Whether `/info` reports the node version, features and config fingerprint.",
+        None,
+    ),
+];
+
+// Writes `docs` as comments above the keys of `yaml`, as emitted by serde_yaml.
+// Documented keys missing from `yaml` are appended commented out at the end of
+// their parent mapping, if they have an example value.
+fn annotate_yaml(yaml: &str, docs: &[(&str, &str, Option<&str>)]) -> String {
+    let comment = |indent: usize, text: &str| {
+        text.lines()
+            .map(|line| format!("{}# {}", " ".repeat(indent), line))
+            .collect::<Vec<_>>()
+    };
+    let mut lines: Vec<String> = vec![];
+    // (indent, key) of the mappings enclosing the current line
+    let mut parents: Vec<(usize, String)> = vec![];
+    let mut present = HashSet::new();
+    // Line index after the last line of each mapping, by path. "" is the root.
+    let mut mapping_ends: HashMap<String, usize> = HashMap::new();
+    let path_of = |parents: &[(usize, String)]| {
+        parents
+            .iter()
+            .map(|(_, key)| key.as_str())
+            .collect::<Vec<_>>()
+            .join(".")
+    };
+    for line in yaml.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let key = line
+            .trim_start()
+            .split_once(':')
+            .map(|(key, _)| key)
+            .filter(|key| {
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if let Some(key) = key {
+            while parents.last().is_some_and(|(i, _)| *i >= indent) {
+                parents.pop();
+            }
+            parents.push((indent, key.to_string()));
+            let path = path_of(&parents);
+            if let Some(&(_, doc, _)) = docs.iter().find(|(p, _, _)| *p == path) {
+                lines.extend(comment(indent, doc));
+            }
+            present.insert(path);
+        }
+        lines.push(line.to_string());
+        for depth in 0..=parents.len() {
+            mapping_ends.insert(path_of(&parents[..depth]), lines.len());
+        }
+    }
+
+    let mut insertions = docs
+        .iter()
+        .enumerate()
+        .filter(|(_, (path, _, _))| !present.contains(*path))
+        .filter_map(|(order, &(path, doc, example))| {
+            let (parent, key) = path.rsplit_once('.').unwrap_or(("", path));
+            let at = *mapping_ends.get(parent)?;
+            let indent = if parent.is_empty() {
+                0
+            } else {
+                2 * (parent.matches('.').count() + 1)
+            };
+            let mut text = comment(indent, doc);
+            text.push(format!("{}# {}: {}", " ".repeat(indent), key, example?));
+            Some((at, order, text))
+        })
+        .collect::<Vec<_>>();
+    // From the back, so earlier indices stay valid
+    insertions.sort_by(|a, b| (b.0, b.1).cmp(&(a.0, a.1)));
+    for (at, _, text) in insertions {
+        for line in text.into_iter().rev() {
+            lines.insert(at, line);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+// Generate Bridge Node Config template and write to a file. YAML paths get a
+// comment above every field, JSON ones are plain.
 pub fn generate_bridge_node_config_and_write_to_file(
     path: &PathBuf,
     run_client: bool,
 ) -> Result<(), anyhow::Error> {
     let mut config = BridgeNodeConfig {
         server_listen_port: 9191,
-        metrics_port: 9184,
+        metrics_port: DEFAULT_METRICS_PORT,
         bridge_authority_key_path: PathBuf::from("/path/to/your/bridge_authority_key"),
         starcoin: StarcoinConfig {
-            starcoin_bridge_rpc_url: "your_starcoin_bridge_rpc_url".to_string(),
-            starcoin_bridge_proxy_address: "0x0000000000000000000000000000000000000000".to_string(),
+            starcoin_bridge_rpc_url: "http://127.0.0.1:9850".to_string(),
+            starcoin_bridge_proxy_address: "0x00000000000000000000000000000000".to_string(),
             starcoin_bridge_chain_id: BridgeChainId::StarcoinTestnet as u8,
             bridge_client_key_path: None,
             starcoin_bridge_module_last_processed_event_id_override: None,
+            rpc_retry: Some(StarcoinRpcRetryConfig::default()),
+            gas_price_multiplier: None,
+            gas: Some(GasConfig::default()),
         },
         eth: EthConfig {
-            eth_rpc_url: "your_eth_rpc_url".to_string(),
+            eth_rpc_url: "http://127.0.0.1:8545".to_string(),
             eth_bridge_proxy_address: "0x0000000000000000000000000000000000000000".to_string(),
             eth_bridge_chain_id: BridgeChainId::EthSepolia as u8,
             eth_contracts_start_block_fallback: Some(0),
             eth_contracts_start_block_override: None,
             eth_use_latest_block: false,
         },
+        evm_chains: vec![],
         approved_governance_actions: vec![],
         run_client,
-        db_path: None,
-        metrics_key_pair: default_ed25519_key_pair(),
+        db_path: Some(PathBuf::from(DEFAULT_CLIENT_DB_PATH)),
+        metrics_key_pair: Secret::new(default_ed25519_key_pair()),
         metrics: Some(MetricsConfig {
             push_interval_seconds: None, // use default value
-            push_url: "metrics_proxy_url".to_string(),
+            push_url: "https://metrics-proxy.example.com/publish/metrics".to_string(),
             auth_username: None,
-            auth_password: String::new(),
+            auth_password: Secret::default(),
         }),
         watchdog_config: Some(WatchdogConfig {
             total_supplies: BTreeMap::from_iter(vec![(
@@ -238,23 +890,79 @@ pub fn generate_bridge_node_config_and_write_to_file(
                     .to_string(),
             )]),
         }),
+        action_queue: Some(ActionQueueConfig::default()),
+        scheduled_actions: None,
+        committee_concentration: Some(CommitteeConcentrationConfig::default()),
+        expose_node_info: Some(true),
+        external_url: None,
+        refuse_signing_on_registration_mismatch: None,
     };
     if run_client {
         config.starcoin.bridge_client_key_path =
             Some(PathBuf::from("/path/to/your/bridge_client_key"));
-        config.db_path = Some(PathBuf::from("/path/to/your/client_db"));
     }
-    config.save(path)
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => {
+            let yaml = serde_yaml::to_string(&config)?;
+            std::fs::write(path, annotate_yaml(&yaml, NODE_CONFIG_TEMPLATE_DOCS))?;
+            Ok(())
+        }
+        _ => config.save(path),
+    }
 }
 
 pub async fn get_eth_signer_client(url: &str, private_key_hex: &str) -> anyhow::Result<EthSigner> {
+    let wallet = Wallet::from_str(private_key_hex).unwrap();
+    eth_signer_client(url, |_| async { Ok(EthWallet::Local(wallet)) }).await
+}
+
+// The wallet derived from `phrase` at `derivation_path`, e.g. `m/44'/60'/0'/0/0`
+#[cfg(feature = "eth-wallets")]
+pub fn mnemonic_eth_wallet(phrase: &str, derivation_path: &str) -> anyhow::Result<EthWallet> {
+    let wallet = MnemonicBuilder::<English>::default()
+        .phrase(phrase.trim())
+        .derivation_path(derivation_path)?
+        .build()
+        .map_err(|e| anyhow!("Invalid mnemonic or derivation path {derivation_path}: {e}"))?;
+    Ok(EthWallet::Local(wallet))
+}
+
+#[cfg(feature = "eth-wallets")]
+pub async fn get_mnemonic_eth_signer_client(
+    url: &str,
+    phrase: &str,
+    derivation_path: &str,
+) -> anyhow::Result<EthSigner> {
+    let wallet = mnemonic_eth_wallet(phrase, derivation_path)?;
+    eth_signer_client(url, |_| async { Ok(wallet) }).await
+}
+
+// Signs on the Ledger device connected over USB. The Ethereum app must be open.
+#[cfg(feature = "eth-wallets")]
+pub async fn get_ledger_eth_signer_client(url: &str, hd_path: &str) -> anyhow::Result<EthSigner> {
+    eth_signer_client(url, |chain_id| async move {
+        let ledger = Ledger::new(HDPath::Other(hd_path.to_string()), chain_id)
+            .await
+            .map_err(|e| anyhow!("Failed to open the Ledger at {hd_path}: {e}"))?;
+        Ok(EthWallet::Ledger {
+            device: Arc::new(ledger),
+            chain_id,
+        })
+    })
+    .await
+}
+
+// Connects to `url` and builds the wallet for the chain id it reports
+async fn eth_signer_client<F, Fut>(url: &str, wallet: F) -> anyhow::Result<EthSigner>
+where
+    F: FnOnce(u64) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<EthWallet>>,
+{
     let provider = Provider::<Http>::try_from(url)
         .unwrap()
         .interval(std::time::Duration::from_millis(2000));
-    let chain_id = provider.get_chainid().await?;
-    let wallet = Wallet::from_str(private_key_hex)
-        .unwrap()
-        .with_chain_id(chain_id.as_u64());
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let wallet = wallet(chain_id).await?.with_chain_id(chain_id);
     Ok(SignerMiddleware::new(provider, wallet))
 }
 
@@ -347,3 +1055,252 @@ pub async fn get_validator_names_by_pub_keys(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use ethers::providers::{JsonRpcClient, MockError};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    // Answers eth_call from a table of (target, calldata) -> output, and executes
+    // Multicall3 aggregate3 against the same table when `multicall_deployed`.
+    #[derive(Clone, Debug, Default)]
+    struct FakeEvm {
+        outputs: Arc<Mutex<HashMap<(EthAddress, Vec<u8>), Vec<u8>>>>,
+        multicall_deployed: bool,
+        eth_calls: Arc<AtomicUsize>,
+    }
+
+    impl FakeEvm {
+        fn set_output<D>(&self, call: &ContractCall<Provider<FakeEvm>, D>, output: Token) {
+            self.outputs.lock().unwrap().insert(
+                (
+                    *call.tx.to_addr().unwrap(),
+                    call.tx.data().unwrap().to_vec(),
+                ),
+                ethers::abi::encode(&[output]),
+            );
+        }
+
+        fn eth_calls(&self) -> usize {
+            self.eth_calls.load(Ordering::SeqCst)
+        }
+
+        // None means the call reverts
+        fn output(&self, target: EthAddress, data: &[u8]) -> Option<Vec<u8>> {
+            self.outputs
+                .lock()
+                .unwrap()
+                .get(&(target, data.to_vec()))
+                .cloned()
+        }
+
+        fn aggregate3(&self, data: &[u8]) -> Vec<u8> {
+            let input_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Bool,
+                ParamType::Bytes,
+            ])));
+            let Token::Array(calls) = ethers::abi::decode(&[input_type], &data[4..])
+                .unwrap()
+                .remove(0)
+            else {
+                panic!("unexpected aggregate3 input");
+            };
+            let results = calls
+                .into_iter()
+                .map(|call| {
+                    let Token::Tuple(fields) = call else {
+                        panic!("unexpected aggregate3 call");
+                    };
+                    let (Token::Address(target), Token::Bytes(data)) = (&fields[0], &fields[2])
+                    else {
+                        panic!("unexpected aggregate3 call");
+                    };
+                    let output = self.output(*target, data);
+                    Token::Tuple(vec![
+                        Token::Bool(output.is_some()),
+                        Token::Bytes(output.unwrap_or_default()),
+                    ])
+                })
+                .collect();
+            ethers::abi::encode(&[Token::Array(results)])
+        }
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for FakeEvm {
+        type Error = MockError;
+
+        async fn request<P: Serialize + Send + Sync + Debug, R: DeserializeOwned>(
+            &self,
+            method: &str,
+            params: P,
+        ) -> Result<R, MockError> {
+            assert_eq!(method, "eth_call");
+            self.eth_calls.fetch_add(1, Ordering::SeqCst);
+            let params = serde_json::to_value(params)?;
+            let target: EthAddress = serde_json::from_value(params[0]["to"].clone())?;
+            let data: Bytes = serde_json::from_value(params[0]["data"].clone())?;
+            let output = if target == MULTICALL3_ADDRESS {
+                if self.multicall_deployed {
+                    self.aggregate3(&data)
+                } else {
+                    // No code at the address
+                    vec![]
+                }
+            } else {
+                self.output(target, &data)
+                    .ok_or(MockError::EmptyResponses)?
+            };
+            Ok(serde_json::from_value(serde_json::to_value(Bytes::from(
+                output,
+            ))?)?)
+        }
+    }
+
+    async fn assert_batch_matches_sequential(fake: FakeEvm, expected_batch_calls: usize) {
+        let provider = Arc::new(Provider::new(fake.clone()));
+        let bridge = EthStarcoinBridge::new(EthAddress::random(), provider.clone());
+        let config = EthBridgeConfig::new(EthAddress::random(), provider.clone());
+        let committee_address = EthAddress::random();
+
+        let committee_call = bridge.committee();
+        let nonce_call = config.nonces(3);
+        let paused_call = config.is_token_paused(2);
+        // No output registered, so this call reverts
+        let reverting_call = config.nonces(7);
+        fake.set_output(&committee_call, Token::Address(committee_address));
+        fake.set_output(&nonce_call, Token::Uint(42.into()));
+        fake.set_output(&paused_call, Token::Bool(true));
+
+        let mut batch = EthCallBatch::new(provider);
+        let committee = batch.add(&committee_call);
+        let nonce = batch.add(&nonce_call);
+        let reverting = batch.add(&reverting_call);
+        let paused = batch.add(&paused_call);
+
+        let batched = batch.execute().await;
+        assert_eq!(fake.eth_calls(), expected_batch_calls);
+        let sequential = batch.execute_sequential().await;
+        assert_eq!(fake.eth_calls(), expected_batch_calls + 4);
+
+        for results in [&batched, &sequential] {
+            assert_eq!(committee.decode(results).unwrap(), committee_address);
+            assert_eq!(nonce.decode(results).unwrap(), 42);
+            assert!(paused.decode(results).unwrap());
+            // The failed call is reported on its own without affecting its neighbours
+            let BridgeError::Rpc { source: err, .. } = reverting.decode(results).unwrap_err()
+            else {
+                panic!("expected a provider error");
+            };
+            assert!(err.contains("nonces"), "{err}");
+        }
+        for (batched, sequential) in batched.iter().zip(&sequential) {
+            assert_eq!(batched.as_ref().ok(), sequential.as_ref().ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eth_call_batch_uses_one_multicall() {
+        let fake = FakeEvm {
+            multicall_deployed: true,
+            ..Default::default()
+        };
+        assert_batch_matches_sequential(fake, 1).await;
+    }
+
+    #[tokio::test]
+    async fn test_eth_call_batch_falls_back_without_multicall() {
+        // One failed aggregate3 attempt, then one call per read
+        assert_batch_matches_sequential(FakeEvm::default(), 1 + 4).await;
+    }
+
+    #[tokio::test]
+    async fn test_empty_eth_call_batch_makes_no_calls() {
+        let fake = FakeEvm::default();
+        let batch = EthCallBatch::new(Arc::new(Provider::new(fake.clone())));
+        assert!(batch.execute().await.is_empty());
+        assert_eq!(fake.eth_calls(), 0);
+    }
+
+    #[cfg(feature = "eth-wallets")]
+    #[test]
+    fn test_mnemonic_eth_wallet() {
+        // The well known development mnemonic of Hardhat and Anvil
+        let phrase = "test test test test test test test test test test test junk\n";
+        let wallet = mnemonic_eth_wallet(phrase, DEFAULT_ETH_HD_PATH).unwrap();
+        assert_eq!(
+            wallet.address(),
+            EthAddress::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap()
+        );
+        let wallet = mnemonic_eth_wallet(phrase, "m/44'/60'/0'/0/1").unwrap();
+        assert_eq!(
+            wallet.address(),
+            EthAddress::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap()
+        );
+        assert!(mnemonic_eth_wallet("test junk", DEFAULT_ETH_HD_PATH).is_err());
+        assert!(mnemonic_eth_wallet(phrase, "not a path").is_err());
+    }
+
+    // Needs a Ledger connected over USB with the Ethereum app open. Runs only
+    // when BRIDGE_TEST_LEDGER_HD_PATH names the account to sign with.
+    #[cfg(feature = "eth-wallets")]
+    #[tokio::test]
+    async fn test_ledger_eth_wallet() {
+        let Ok(hd_path) = std::env::var("BRIDGE_TEST_LEDGER_HD_PATH") else {
+            return;
+        };
+        let ledger = Ledger::new(HDPath::Other(hd_path), 1).await.unwrap();
+        let wallet = EthWallet::Ledger {
+            device: Arc::new(ledger),
+            chain_id: 1,
+        };
+        // Confirm on the device
+        let signature = wallet.sign_message("starcoin bridge").await.unwrap();
+        signature
+            .verify("starcoin bridge", wallet.address())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_node_config_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node-config.yaml");
+        generate_bridge_node_config_and_write_to_file(&path, true).unwrap();
+        let template = std::fs::read_to_string(&path).unwrap();
+
+        assert!(template.contains("# Port of the Prometheus metrics server.\nmetrics-port: 9184\n"));
+        assert!(template.contains("db-path: bridge-client-db\n"));
+        assert!(template.contains("  # Rpc url of the Eth fullnode.\n  eth-rpc-url: "));
+        // Unset fields are there commented out, under their parent
+        assert!(template.contains("\n  # eth-contracts-start-block-override: 0\n"));
+        assert!(template.contains("\n    # max-requests-per-second: 50\n"));
+        assert!(template.contains("\n# scheduled-actions: { dir: "));
+        assert!(template.contains("\n    # gas-budget-overrides: { TokenTransfer: 2000000 }\n"));
+
+        // Every field has a comment above it, except the watched coins
+        let lines = template.lines().collect::<Vec<_>>();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with('#') || line.starts_with("    eth:") {
+                continue;
+            }
+            assert!(
+                i > 0 && lines[i - 1].trim_start().starts_with('#'),
+                "{line} is not documented"
+            );
+        }
+
+        // Only the placeholder key paths are left to fill in
+        let config = BridgeNodeConfig::load(&path).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(crate::config::ConfigValidationError::MissingKeyFile { .. })
+        ));
+    }
+}
@@ -138,6 +138,61 @@ contract BridgeUtilsTest is BridgeBaseTest {
         );
     }
 
+    // Pins the digests of bridge/fixtures/eth_token_transfer_messages.json,
+    // which the Rust conversion of claimed messages is tested against
+    function testComputeHashStarcoinTokenTransfer() public {
+        bytes32 digest = BridgeUtils.computeHash(
+            BridgeUtils.Message({
+                messageType: BridgeUtils.TOKEN_TRANSFER,
+                version: 1,
+                nonce: 10,
+                chainID: 1,
+                payload: hex"10000000000000000000000000000000640b1400000000000000000000000000000000000000c8030000000000003039"
+            })
+        );
+        assertEq(digest, bytes32(hex"32b738488301ae5dbb627ce54abbd28f91c876f31343f2364d33bbf2ded45a91"));
+    }
+
+    function testComputeHashStarcoinTokenTransferMaxValues() public {
+        bytes32 digest = BridgeUtils.computeHash(
+            BridgeUtils.Message({
+                messageType: BridgeUtils.TOKEN_TRANSFER,
+                version: 1,
+                nonce: type(uint64).max,
+                chainID: 0,
+                payload: hex"100123456789abcdef0123456789abcdef0a14b18f79fe671db47393315ffdb377da4ea1b7af96ffffffffffffffffff"
+            })
+        );
+        assertEq(digest, bytes32(hex"1bad148e3b35b8ecc4279ba03145b720e633b4596b1c743fff0cf069d07eba3f"));
+    }
+
+    function testComputeHashStarcoinTokenTransferPaddedSender() public {
+        // The sender is hashed as 16 bytes however Move rendered it
+        bytes32 digest = BridgeUtils.computeHash(
+            BridgeUtils.Message({
+                messageType: BridgeUtils.TOKEN_TRANSFER,
+                version: 1,
+                nonce: 42,
+                chainID: 2,
+                payload: hex"10fedcba9876543210fedcba98765432100c1468b43fd906c0b8f024a18c56e06744f7c6157c65010000000005f5e100"
+            })
+        );
+        assertEq(digest, bytes32(hex"6c731c3ddf3d5249d86f8ce049b12dfcd21905befeb2587f99ff581e238a9f2a"));
+    }
+
+    function testComputeHashStarcoinTokenTransferVersion2() public {
+        bytes32 digest = BridgeUtils.computeHash(
+            BridgeUtils.Message({
+                messageType: BridgeUtils.TOKEN_TRANSFER,
+                version: 2,
+                nonce: 10,
+                chainID: 1,
+                payload: hex"10000000000000000000000000000000640b1400000000000000000000000000000000000000c8030000000000003039"
+            })
+        );
+        assertEq(digest, bytes32(hex"569554fad021c57138b2fed8d375a7ba948f83af953abf8dd72f12b6416168db"));
+    }
+
     function testDecodeTransferTokenPayload() public {
         // 20: sender length 1 bytes
         // 80ab1ee086210a3a37355300ca24672e81062fcdb5ced6618dab203f6a3b291c: sender 32 bytes
@@ -232,6 +232,15 @@ module Bridge::Bridge {
         committee_registration(bridge, Signer::address_of(&bridge_admin), bridge_pubkey_bytes, http_rest_url);
     }
 
+    /// Update the http rest url of the sender's committee member
+    public entry fun update_committee_member_url(
+        sender: signer,
+        new_url: vector<u8>,
+    ) acquires Bridge {
+        let bridge = borrow_global_mut<Bridge>(@Bridge);
+        update_node_url(bridge, Signer::address_of(&sender), new_url);
+    }
+
     /// Create committee with a single validator (for dev/test)
     public entry fun create_committee(
         bridge_admin: signer,
@@ -335,6 +344,31 @@ module Bridge::Bridge {
         approve_token_transfer(bridge, message, signatures);
     }
 
+    /// Script entry for approving token transfer with any number of signatures
+    public entry fun approve_bridge_token_transfer(
+        _sender: signer,
+        source_chain: u8,
+        seq_num: u64,
+        sender_address: vector<u8>,
+        target_chain: u8,
+        target_address: vector<u8>,
+        token_type: u8,
+        amount: u64,
+        signatures: vector<vector<u8>>,
+    ) acquires Bridge, EventHandlePod {
+        let message = Message::create_token_bridge_message(
+            source_chain,
+            seq_num,
+            sender_address,
+            target_chain,
+            target_address,
+            token_type,
+            amount,
+        );
+        let bridge = borrow_global_mut<Bridge>(@Bridge);
+        approve_token_transfer(bridge, message, signatures);
+    }
+
     /// Script entry for claiming tokens after approval (ETH token)
     public entry fun claim_bridge_eth(
         _sender: signer,
@@ -383,6 +417,19 @@ module Bridge::Bridge {
         claim_and_transfer_token<USDT>(bridge, clock_timestamp_ms, source_chain, bridge_seq_num);
     }
 
+    /// Script entry for claiming tokens after approval, for any token
+    /// registered on the bridge. Tokens added by governance have no dedicated
+    /// entry, so they are claimed through this one.
+    public entry fun claim_bridge_token<T: store>(
+        _sender: signer,
+        clock_timestamp_ms: u64,
+        source_chain: u8,
+        bridge_seq_num: u64,
+    ) acquires Bridge, EventHandlePod {
+        let bridge = borrow_global_mut<Bridge>(@Bridge);
+        claim_and_transfer_token<T>(bridge, clock_timestamp_ms, source_chain, bridge_seq_num);
+    }
+
     /// Script entry for sending ETH tokens to ETH chain
     public entry fun send_bridge_eth(
         sender: signer,
@@ -439,6 +486,20 @@ module Bridge::Bridge {
         send_token<USDT>(bridge, sender_addr, target_chain, target_address, token);
     }
 
+    /// Script entry for sending tokens to ETH chain, for any token registered
+    /// on the bridge
+    public entry fun send_bridge_token<T: store>(
+        sender: signer,
+        target_chain: u8,
+        target_address: vector<u8>,
+        amount: u128,
+    ) acquires Bridge, EventHandlePod {
+        let sender_addr = Signer::address_of(&sender);
+        let token = Account::withdraw<T>(&sender, amount);
+        let bridge = borrow_global_mut<Bridge>(@Bridge);
+        send_token<T>(bridge, sender_addr, target_chain, target_address, token);
+    }
+
     /// Script entry for emergency operations (pause/unpause) with single signature
     public entry fun execute_emergency_op_single(
         bridge_admin: signer,
@@ -25,12 +25,16 @@ module Bridge::Committee {
     const ECommitteeAlreadyInitiated: u64 = 7;
     const EDuplicatePubkey: u64 = 8;
     const ESenderIsNotInBridgeCommittee: u64 = 9;
+    const EUrlTooLong: u64 = 10;
 
     const STARCOIN_MESSAGE_PREFIX: vector<u8> = b"STARCOIN_BRIDGE_MESSAGE";
 
     const ECDSA_COMPRESSED_PUBKEY_LENGTH: u64 = 33;
     const ECDSA_RAW_PUBKEY_LENGTH: u64 = 64;
 
+    // Longest http rest url a member can set, in bytes
+    const MAX_HTTP_REST_URL_LENGTH: u64 = 512;
+
     //////////////////////////////////////////////////////
     // Types
     //
@@ -329,6 +333,7 @@ module Bridge::Committee {
         sender_address: address,
         new_url: vector<u8>,
     ) acquires EventHandlePod {
+        assert!(Vector::length(&new_url) <= MAX_HTTP_REST_URL_LENGTH, EUrlTooLong);
         let eh = borrow_global_mut<EventHandlePod>(@Bridge);
         let idx = 0;
         let member_len = SimpleMap::length(&self.members);
@@ -382,6 +387,10 @@ module Bridge::Committee {
     }
 
     #[test_only]
+    public fun max_http_rest_url_length(): u64 {
+        MAX_HTTP_REST_URL_LENGTH
+    }
+
     public fun http_rest_url(member: &CommitteeMember): vector<u8> {
         member.http_rest_url
     }
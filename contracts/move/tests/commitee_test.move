@@ -13,6 +13,7 @@ module Bridge::CommitteeTest {
     use Bridge::Crypto;
     use Bridge::Message;
     use StarcoinFramework::SimpleMap::{Self, SimpleMap};
+    use StarcoinFramework::Vector;
 
     // This is a token transfer message for testing
     const TEST_MSG: vector<u8> =
@@ -196,6 +197,33 @@ module Bridge::CommitteeTest {
     }
 
 
+    #[test, expected_failure(abort_code = Bridge::Committee::EUrlTooLong)]
+    fun test_update_node_url_too_long() {
+        let committee = Committee::create();
+
+        // validator registration
+        Committee::register(
+            &mut committee,
+            @Bridge,
+            VALIDATOR1_PUBKEY,
+            b"test url 1",
+        );
+
+        let voting_powers = Self::validator_voting_powers_for_testing();
+        Committee::try_create_next_committee(&mut committee, voting_powers, 0, 6000);
+
+        let url = Vector::empty<u8>();
+        while (Vector::length(&url) <= Committee::max_http_rest_url_length()) {
+            Vector::push_back(&mut url, 0x61);
+        };
+        Committee::update_node_url(&mut committee, @Bridge, url);
+
+        Committee::destroy(committee);
+
+        // test should have failed, abort
+        abort 1
+    }
+
     #[test, expected_failure(abort_code = Bridge::Committee::ESenderNotActiveValidator)]
     fun test_init_committee_not_validator() {
         let committee = Committee::create();
@@ -23,6 +23,10 @@ pub mod secp256k1_tests;
 #[path = "tests/secp256k1_recoverable_tests.rs"]
 pub mod secp256k1_recoverable_tests;
 
+#[cfg(test)]
+#[path = "tests/secp256r1_tests.rs"]
+pub mod secp256r1_tests;
+
 #[cfg(test)]
 #[path = "tests/hash_tests.rs"]
 pub mod hash_tests;
@@ -56,6 +60,7 @@ pub mod hash;
 pub mod hmac;
 pub mod private_seed;
 pub mod secp256k1;
+pub mod secp256r1;
 pub mod serde_helpers;
 pub mod traits;
 pub mod utils;
@@ -0,0 +1,377 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains an implementation of the [ECDSA signature scheme](https://en.wikipedia.org/wiki/Elliptic_Curve_Digital_Signature_Algorithm) over the [secp256r1 (NIST P-256) curve](http://www.secg.org/sec2-v2.pdf).
+//!
+//! Messages can be signed and the signature can be verified again:
+//! # Example
+//! ```rust
+//! # use fastcrypto::secp256r1::*;
+//! # use fastcrypto::{traits::{KeyPair, Signer, VerifyingKey}};
+//! use rand::thread_rng;
+//! let kp = Secp256r1KeyPair::generate(&mut thread_rng());
+//! let message: &[u8] = b"Hello, world!";
+//! let signature = kp.sign(message);
+//! assert!(kp.public().verify(message, &signature).is_ok());
+//! ```
+
+use crate::hash::{HashFunction, Sha256};
+use crate::serde_helpers::BytesRepresentation;
+use crate::traits::Signer;
+use crate::{
+    encoding::{Base64, Encoding},
+    error::FastCryptoError,
+    serialize_deserialize_with_to_from_bytes,
+    traits::{
+        AllowedRng, Authenticator, EncodeDecodeBase64, KeyPair, SigningKey, ToFromBytes,
+        VerifyingKey,
+    },
+};
+use crate::{generate_bytes_representation, impl_base64_display_fmt};
+use fastcrypto_derive::{SilentDebug, SilentDisplay};
+use once_cell::sync::OnceCell;
+use p256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use p256::ecdsa::{
+    Signature as ExternalSignature, SigningKey as ExternalSecretKey,
+    VerifyingKey as ExternalPublicKey,
+};
+use std::{
+    fmt::{self, Debug},
+    str::FromStr,
+};
+
+// The length of a compressed public key in bytes.
+pub const SECP256R1_PUBLIC_KEY_LENGTH: usize = 33;
+
+// The length of a private key in bytes.
+pub const SECP256R1_PRIVATE_KEY_LENGTH: usize = 32;
+
+// The length of a signature in bytes.
+pub const SECP256R1_SIGNATURE_LENGTH: usize = 64;
+
+// The key pair bytes length is the same as the private key length. This enforces deserialization to always derive the public key from the private key.
+pub const SECP256R1_KEYPAIR_LENGTH: usize = SECP256R1_PRIVATE_KEY_LENGTH;
+
+// Default hash function used for signing and verifying messages unless another hash function is
+// specified using the `with_hash` functions.
+pub type DefaultHash = Sha256;
+
+// Secp256r1 public key.
+#[readonly::make]
+#[derive(Debug, Clone)]
+pub struct Secp256r1PublicKey {
+    pub pubkey: ExternalPublicKey,
+    pub bytes: OnceCell<[u8; SECP256R1_PUBLIC_KEY_LENGTH]>,
+}
+
+// Secp256r1 private key.
+#[readonly::make]
+#[derive(SilentDebug, SilentDisplay)]
+pub struct Secp256r1PrivateKey {
+    pub privkey: ExternalSecretKey,
+    pub bytes: OnceCell<zeroize::Zeroizing<[u8; SECP256R1_PRIVATE_KEY_LENGTH]>>,
+}
+
+// Secp256r1 ECDSA signature. Signatures are always normalized to a low s value.
+#[readonly::make]
+#[derive(Debug, Clone)]
+pub struct Secp256r1Signature {
+    pub sig: ExternalSignature,
+    pub bytes: OnceCell<[u8; SECP256R1_SIGNATURE_LENGTH]>,
+}
+
+impl std::hash::Hash for Secp256r1PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl PartialOrd for Secp256r1PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Secp256r1PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
+impl PartialEq for Secp256r1PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.pubkey == other.pubkey
+    }
+}
+
+impl Eq for Secp256r1PublicKey {}
+
+impl VerifyingKey for Secp256r1PublicKey {
+    type PrivKey = Secp256r1PrivateKey;
+    type Sig = Secp256r1Signature;
+    const LENGTH: usize = SECP256R1_PUBLIC_KEY_LENGTH;
+
+    fn verify(&self, msg: &[u8], signature: &Secp256r1Signature) -> Result<(), FastCryptoError> {
+        // Sha256 is used by default as digest
+        self.verify_with_hash::<DefaultHash>(msg, signature)
+    }
+}
+
+impl Secp256r1PublicKey {
+    // Verify the signature using the given hash function to hash the message.
+    pub fn verify_with_hash<H: HashFunction<32>>(
+        &self,
+        msg: &[u8],
+        signature: &Secp256r1Signature,
+    ) -> Result<(), FastCryptoError> {
+        // Signatures with a high s value are malleable copies of a valid signature.
+        if signature.sig.normalize_s().is_some() {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        self.pubkey
+            .verify_prehash(H::digest(msg).as_ref(), &signature.sig)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
+impl AsRef<[u8]> for Secp256r1PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.get_or_init::<_>(|| {
+            self.pubkey
+                .to_encoded_point(true)
+                .as_bytes()
+                .try_into()
+                .expect("A compressed secp256r1 point is 33 bytes")
+        })
+    }
+}
+
+impl ToFromBytes for Secp256r1PublicKey {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        if bytes.len() != SECP256R1_PUBLIC_KEY_LENGTH {
+            return Err(FastCryptoError::InputLengthWrong(
+                SECP256R1_PUBLIC_KEY_LENGTH,
+            ));
+        }
+        match ExternalPublicKey::from_sec1_bytes(bytes) {
+            Ok(pubkey) => Ok(Secp256r1PublicKey {
+                pubkey,
+                bytes: OnceCell::new(),
+            }),
+            Err(_) => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+impl_base64_display_fmt!(Secp256r1PublicKey);
+
+serialize_deserialize_with_to_from_bytes!(Secp256r1PublicKey, SECP256R1_PUBLIC_KEY_LENGTH);
+generate_bytes_representation!(
+    Secp256r1PublicKey,
+    SECP256R1_PUBLIC_KEY_LENGTH,
+    Secp256r1PublicKeyAsBytes
+);
+
+impl<'a> From<&'a Secp256r1PrivateKey> for Secp256r1PublicKey {
+    fn from(secret: &'a Secp256r1PrivateKey) -> Self {
+        Secp256r1PublicKey {
+            pubkey: ExternalPublicKey::from(&secret.privkey),
+            bytes: OnceCell::new(),
+        }
+    }
+}
+
+impl SigningKey for Secp256r1PrivateKey {
+    type PubKey = Secp256r1PublicKey;
+    type Sig = Secp256r1Signature;
+    const LENGTH: usize = SECP256R1_PRIVATE_KEY_LENGTH;
+}
+
+impl ToFromBytes for Secp256r1PrivateKey {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        if bytes.len() != SECP256R1_PRIVATE_KEY_LENGTH {
+            return Err(FastCryptoError::InputLengthWrong(
+                SECP256R1_PRIVATE_KEY_LENGTH,
+            ));
+        }
+        // Rejects zero and scalars greater than or equal to the group order.
+        match ExternalSecretKey::from_bytes(bytes) {
+            Ok(privkey) => Ok(Secp256r1PrivateKey {
+                privkey,
+                bytes: OnceCell::new(),
+            }),
+            Err(_) => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+impl PartialEq for Secp256r1PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.privkey == other.privkey
+    }
+}
+
+impl Eq for Secp256r1PrivateKey {}
+
+serialize_deserialize_with_to_from_bytes!(Secp256r1PrivateKey, SECP256R1_PRIVATE_KEY_LENGTH);
+
+impl AsRef<[u8]> for Secp256r1PrivateKey {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+            .get_or_init::<_>(|| zeroize::Zeroizing::new(self.privkey.to_bytes().into()))
+            .as_ref()
+    }
+}
+
+// The external secret key zeroizes itself on drop, and bytes is zeroized via OnceCell.
+impl zeroize::ZeroizeOnDrop for Secp256r1PrivateKey {}
+
+serialize_deserialize_with_to_from_bytes!(Secp256r1Signature, SECP256R1_SIGNATURE_LENGTH);
+generate_bytes_representation!(
+    Secp256r1Signature,
+    SECP256R1_SIGNATURE_LENGTH,
+    Secp256r1SignatureAsBytes
+);
+
+impl_base64_display_fmt!(Secp256r1Signature);
+
+impl ToFromBytes for Secp256r1Signature {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        if bytes.len() != SECP256R1_SIGNATURE_LENGTH {
+            return Err(FastCryptoError::InputLengthWrong(
+                SECP256R1_SIGNATURE_LENGTH,
+            ));
+        }
+        // Fails if r or s is zero.
+        ExternalSignature::try_from(bytes)
+            .map(|sig| Secp256r1Signature {
+                sig,
+                bytes: OnceCell::new(),
+            })
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+impl Authenticator for Secp256r1Signature {
+    type PubKey = Secp256r1PublicKey;
+    type PrivKey = Secp256r1PrivateKey;
+    const LENGTH: usize = SECP256R1_SIGNATURE_LENGTH;
+}
+
+impl AsRef<[u8]> for Secp256r1Signature {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.get_or_init::<_>(|| {
+            self.sig
+                .as_ref()
+                .try_into()
+                .expect("A secp256r1 signature is 64 bytes")
+        })
+    }
+}
+
+impl std::hash::Hash for Secp256r1Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl PartialEq for Secp256r1Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.sig == other.sig
+    }
+}
+
+impl Eq for Secp256r1Signature {}
+
+// Secp256r1 public/private key pair.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Secp256r1KeyPair {
+    pub public: Secp256r1PublicKey,
+    pub secret: Secp256r1PrivateKey,
+}
+
+// The bytes form of the keypair always only contain the private key bytes
+impl ToFromBytes for Secp256r1KeyPair {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        Secp256r1PrivateKey::from_bytes(bytes).map(|secret| secret.into())
+    }
+}
+
+serialize_deserialize_with_to_from_bytes!(Secp256r1KeyPair, SECP256R1_KEYPAIR_LENGTH);
+
+impl AsRef<[u8]> for Secp256r1KeyPair {
+    fn as_ref(&self) -> &[u8] {
+        self.secret.as_ref()
+    }
+}
+
+impl KeyPair for Secp256r1KeyPair {
+    type PubKey = Secp256r1PublicKey;
+    type PrivKey = Secp256r1PrivateKey;
+    type Sig = Secp256r1Signature;
+
+    fn public(&'_ self) -> &'_ Self::PubKey {
+        &self.public
+    }
+
+    fn private(self) -> Self::PrivKey {
+        Secp256r1PrivateKey::from_bytes(self.secret.as_ref()).unwrap()
+    }
+
+    #[cfg(feature = "copy_key")]
+    fn copy(&self) -> Self {
+        Secp256r1KeyPair {
+            public: self.public.clone(),
+            secret: Secp256r1PrivateKey::from_bytes(self.secret.as_ref()).unwrap(),
+        }
+    }
+
+    fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        Secp256r1PrivateKey {
+            privkey: ExternalSecretKey::random(&mut *rng),
+            bytes: OnceCell::new(),
+        }
+        .into()
+    }
+}
+
+impl FromStr for Secp256r1KeyPair {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode_base64(s)
+    }
+}
+
+impl Secp256r1KeyPair {
+    // Create a new signature using the given hash function to hash the message.
+    pub fn sign_with_hash<H: HashFunction<32>>(&self, msg: &[u8]) -> Secp256r1Signature {
+        // Creates a 64-bytes signature of shape [r, s].
+        // Pseudo-random deterministic nonce generation is used according to RFC6979.
+        let sig: ExternalSignature = self
+            .secret
+            .privkey
+            .sign_prehash(H::digest(msg).as_ref())
+            .expect("Signing a 32 byte digest never fails");
+
+        // Normalize s so that each message has a single valid signature.
+        Secp256r1Signature {
+            sig: sig.normalize_s().unwrap_or(sig),
+            bytes: OnceCell::new(),
+        }
+    }
+}
+
+impl Signer<Secp256r1Signature> for Secp256r1KeyPair {
+    fn sign(&self, msg: &[u8]) -> Secp256r1Signature {
+        // Sha256 is used by default
+        self.sign_with_hash::<DefaultHash>(msg)
+    }
+}
+
+impl From<Secp256r1PrivateKey> for Secp256r1KeyPair {
+    fn from(secret: Secp256r1PrivateKey) -> Self {
+        let public = Secp256r1PublicKey::from(&secret);
+        Secp256r1KeyPair { public, secret }
+    }
+}
@@ -1,12 +1,12 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
 use p256::ecdsa::Signature;
 use p256::elliptic_curve::scalar::IsHigh;
-use p256::Scalar;
+use p256::elliptic_curve::{Field, PrimeField};
 use proptest::{prelude::*, strategy::Strategy};
 use rand::{rngs::StdRng, SeedableRng as _};
-use rust_secp256k1::constants::SECRET_KEY_SIZE;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
@@ -15,14 +15,14 @@ use wycheproof::TestResult;
 
 use crate::encoding::{Base64, Encoding};
 use crate::hash::{Blake2b256, Keccak256};
-use crate::secp256r1::recoverable::SECP256R1_RECOVERABLE_SIGNATURE_LENGTH;
 use crate::test_helpers::verify_serialization;
 use crate::traits::Signer;
 use crate::{
     hash::{HashFunction, Sha256},
-    secp256r1::{Secp256r1KeyPair, Secp256r1PrivateKey, Secp256r1PublicKey, Secp256r1Signature},
-    signature_service::SignatureService,
-    test_helpers,
+    secp256r1::{
+        Secp256r1KeyPair, Secp256r1PrivateKey, Secp256r1PublicKey, Secp256r1Signature,
+        SECP256R1_PRIVATE_KEY_LENGTH,
+    },
     traits::{EncodeDecodeBase64, KeyPair, ToFromBytes, VerifyingKey},
 };
 
@@ -60,21 +60,6 @@ fn import_export_public_key() {
     assert_eq!(import.unwrap().as_ref(), public_key.as_ref());
 }
 
-#[test]
-fn test_public_key_recovery_error() {
-    // incorrect length
-    assert!(<Secp256r1Signature as ToFromBytes>::from_bytes(&[0u8; 1]).is_err());
-
-    // invalid recovery id at index 65
-    assert!(<Secp256r1Signature as ToFromBytes>::from_bytes(&[4u8; 65]).is_err());
-
-    // Invalid signature: Zeros in signatures are not allowed
-    assert!(<Secp256r1Signature as ToFromBytes>::from_bytes(
-        &[0u8; SECP256R1_RECOVERABLE_SIGNATURE_LENGTH]
-    )
-    .is_err());
-}
-
 #[test]
 fn public_key_ordering() {
     let pk1 = keys().pop().unwrap().public().clone();
@@ -223,14 +208,14 @@ fn fail_on_r_or_s_zero() {
 
     // Set r to zero
     let mut r_is_zero = [0u8; 64];
-    r_is_zero[0..32].copy_from_slice(&Scalar::ZERO.to_bytes());
+    r_is_zero[0..32].copy_from_slice(&[0u8; 32]);
     r_is_zero[32..64].copy_from_slice(&sig_bytes[32..64]);
     assert!(<Secp256r1Signature as ToFromBytes>::from_bytes(&r_is_zero).is_err());
 
     // Set s to zero
     let mut s_is_zero = [0u8; 64];
     s_is_zero[0..32].copy_from_slice(&sig_bytes[0..32]);
-    s_is_zero[32..64].copy_from_slice(&Scalar::ZERO.to_bytes());
+    s_is_zero[32..64].copy_from_slice(&[0u8; 32]);
     assert!(<Secp256r1Signature as ToFromBytes>::from_bytes(&s_is_zero).is_err());
 }
 
@@ -286,64 +271,6 @@ fn verify_hashed_failed_if_different_hash() {
         .is_err());
 }
 
-fn signature_test_inputs() -> (Vec<u8>, Vec<Secp256r1PublicKey>, Vec<Secp256r1Signature>) {
-    // Make signatures.
-    let message: &[u8] = b"Hello, world!";
-    let digest = Sha256::digest(message);
-    let (pubkeys, signatures): (Vec<Secp256r1PublicKey>, Vec<Secp256r1Signature>) = keys()
-        .into_iter()
-        .take(3)
-        .map(|kp| {
-            let sig = kp.sign(digest.as_ref());
-            (kp.public().clone(), sig)
-        })
-        .unzip();
-
-    (digest.to_vec(), pubkeys, signatures)
-}
-
-#[test]
-fn verify_valid_batch() {
-    let (digest, pubkeys, signatures) = signature_test_inputs();
-
-    let res = Secp256r1PublicKey::verify_batch_empty_fail(&digest[..], &pubkeys, &signatures);
-    assert!(res.is_ok(), "{:?}", res);
-}
-
-#[test]
-fn verify_invalid_batch() {
-    let (digest, pubkeys, mut signatures) = signature_test_inputs();
-    // mangle one signature
-    signatures.swap(0, 1);
-
-    assert!(Secp256r1PublicKey::verify_batch_empty_fail(&digest, &pubkeys, &signatures).is_err())
-}
-
-#[test]
-fn verify_empty_batch() {
-    let (digest, _, _) = signature_test_inputs();
-
-    let res = Secp256r1PublicKey::verify_batch_empty_fail(&digest[..], &[], &[]);
-    assert!(res.is_err(), "{:?}", res);
-}
-
-#[test]
-fn verify_batch_missing_public_keys() {
-    let (digest, pubkeys, signatures) = signature_test_inputs();
-
-    // missing leading public keys
-    let res = Secp256r1PublicKey::verify_batch_empty_fail(&digest, &pubkeys[1..], &signatures);
-    assert!(res.is_err(), "{:?}", res);
-
-    // missing trailing public keys
-    let res = Secp256r1PublicKey::verify_batch_empty_fail(
-        &digest,
-        &pubkeys[..pubkeys.len() - 1],
-        &signatures,
-    );
-    assert!(res.is_err(), "{:?}", res);
-}
-
 #[test]
 fn verify_invalid_signature() {
     // Get a keypair.
@@ -366,29 +293,6 @@ fn verify_invalid_signature() {
     assert!(kp.public().verify(digest.as_ref(), &signature).is_err());
 }
 
-#[test]
-fn verify_valid_batch_different_msg() {
-    let inputs = test_helpers::signature_test_inputs_different_msg::<Secp256r1KeyPair>();
-    let res = Secp256r1PublicKey::verify_batch_empty_fail_different_msg(
-        &inputs.digests,
-        &inputs.pubkeys,
-        &inputs.signatures,
-    );
-    assert!(res.is_ok(), "{:?}", res);
-}
-
-#[test]
-fn verify_invalid_batch_different_msg() {
-    let mut inputs = test_helpers::signature_test_inputs_different_msg::<Secp256r1KeyPair>();
-    inputs.signatures.swap(0, 1);
-    let res = Secp256r1PublicKey::verify_batch_empty_fail_different_msg(
-        &inputs.digests,
-        &inputs.pubkeys,
-        &inputs.signatures,
-    );
-    assert!(res.is_err(), "{:?}", res);
-}
-
 #[test]
 fn fail_to_verify_if_upper_s() {
     // Make signature.
@@ -409,31 +313,41 @@ fn fail_to_verify_if_upper_s() {
     let normalized = sig.sig.normalize_s().unwrap();
 
     // Normalize S to be less than N/2.
-    let normalized_sig = Secp256r1Signature::from_bytes(normalized.to_bytes().as_slice()).unwrap();
+    let normalized_sig = Secp256r1Signature::from_bytes(normalized.as_ref()).unwrap();
 
     // Verify with normalized lower S.
     assert!(pk.verify(&digest.digest, &normalized_sig).is_ok());
 }
 
-#[tokio::test]
-async fn signature_service() {
-    // Get a keypair.
-    let kp = keys().pop().unwrap();
-    let pk = kp.public().clone();
-
-    // Spawn the signature service.
-    let service = SignatureService::new(kp);
-
-    // Request signature from the service.
-    let message: &[u8] = b"Hello, world!";
-    let digest = Sha256::digest(message);
-    let signature = service.request_signature(digest).await;
-
-    //    digest.into()
+#[test]
+fn signatures_have_low_s() {
+    for kp in keys() {
+        let sig = kp.sign(MSG);
+        assert_eq!(sig.sig.s().is_high().unwrap_u8(), 0);
+    }
+}
 
-    // Verify the signature we received.
-    assert!(pk.verify(digest.as_ref(), &signature).is_ok());
-    assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+#[test]
+fn fail_to_verify_malleated_signature() {
+    let digest = Sha256::digest(MSG);
+    for kp in keys() {
+        let sig = kp.sign(MSG);
+
+        // Negating s gives the other valid signature of the same message
+        let mut malleated = sig.as_ref().to_vec();
+        malleated[32..].copy_from_slice(&(-*sig.sig.s().as_ref()).to_repr());
+        let malleated = Secp256r1Signature::from_bytes(&malleated).unwrap();
+        assert_ne!(malleated.sig.s().is_high().unwrap_u8(), 0);
+        assert!(kp
+            .public()
+            .pubkey
+            .verify_prehash(digest.as_ref(), &malleated.sig)
+            .is_ok());
+
+        // Only the low s copy verifies
+        assert!(kp.public().verify(MSG, &malleated).is_err());
+        assert!(kp.public().verify(MSG, &sig).is_ok());
+    }
 }
 
 #[test]
@@ -452,100 +366,39 @@ fn test_sk_zeroization_on_drop() {
         ptr = std::ptr::addr_of!(sk.privkey) as *const u8;
         bytes_ptr = &sk.as_ref()[0] as *const u8;
 
-        let sk_memory: &[u8] = unsafe { std::slice::from_raw_parts(bytes_ptr, SECRET_KEY_SIZE) };
+        let sk_memory: &[u8] =
+            unsafe { std::slice::from_raw_parts(bytes_ptr, SECP256R1_PRIVATE_KEY_LENGTH) };
         // Assert that this is equal to sk_bytes before deletion
         assert_eq!(sk_memory, &sk_bytes[..]);
     }
 
-    // Check that self.privkey is set to ONE_KEY (workaround to all zero SecretKey considered as invalid)
-    unsafe {
-        assert_eq!(*ptr, 1);
-        for i in 1..SECRET_KEY_SIZE {
-            assert_eq!(*ptr.add(i), 0);
-        }
-    }
+    // Check that the p256 key is set to one (zero is not a valid scalar), in
+    // whatever layout p256 stores it
+    let one = p256::NonZeroScalar::new(p256::Scalar::one()).unwrap();
+    let size = std::mem::size_of::<p256::NonZeroScalar>();
+    let one_memory: &[u8] =
+        unsafe { std::slice::from_raw_parts(std::ptr::addr_of!(one) as *const u8, size) };
+    let privkey_memory: &[u8] = unsafe { std::slice::from_raw_parts(ptr, size) };
+    assert_eq!(privkey_memory, one_memory);
 
     // Check that self.bytes is zeroized
-    let sk_memory: &[u8] = unsafe { std::slice::from_raw_parts(bytes_ptr, SECRET_KEY_SIZE) };
+    let sk_memory: &[u8] =
+        unsafe { std::slice::from_raw_parts(bytes_ptr, SECP256R1_PRIVATE_KEY_LENGTH) };
     assert_ne!(sk_memory, &sk_bytes[..]);
 }
 
-// TODO: If we find another crate impl'ing ecdsa secp256r1 with recovery, we could use this test to compare it with our implementation.
-// proptest::proptest! {
-//     #[test]
-//     #[cfg(feature = "copy_key")]
-//     fn test_k256_against_Secp256r1_lib_with_recovery(
-//         r in <[u8; 32]>::arbitrary()
-// ) {
-//         let message: &[u8] = b"hello world!";
-//         let hashed_msg = rust_Secp256r1::Message::from_slice(Keccak256::digest(message).as_ref()).unwrap();
-//
-//         // construct private key with bytes and signs message
-//         let priv_key = <Secp256r1PrivateKey as ToFromBytes>::from_bytes(&r).unwrap();
-//         let key_pair = Secp256r1KeyPair::from(priv_key);
-//         let key_pair_copied = key_pair.copy();
-//         let key_pair_copied_2 = key_pair.copy();
-//         let signature: Secp256r1Signature = key_pair.sign(message);
-//         assert!(key_pair.public().verify(message, &signature).is_ok());
-//
-//         // construct a signature with r, s, v where v is flipped from the original signature.
-//         let bytes = ToFromBytes::as_bytes(&signature);
-//         let mut flipped_bytes = [0u8; 65];
-//         flipped_bytes[..64].copy_from_slice(&bytes[..64]);
-//         if bytes[64] == 0 {
-//             flipped_bytes[64] = 1;
-//         } else {
-//             flipped_bytes[64] = 0;
-//         }
-//         let malleated_signature: Secp256r1Signature = <Secp256r1Signature as signature::Signature>::from_bytes(&flipped_bytes).unwrap();
-//
-//         // malleable(altered) signature with opposite sign fails to verify
-//         assert!(key_pair.public().verify(message, &malleated_signature).is_err());
-//
-//         // use k256 to construct private key with the same bytes and signs the same message
-//         let priv_key_1 = k256::ecdsa::SigningKey::from_bytes(&r).unwrap();
-//         let pub_key_1 = priv_key_1.verifying_key();
-//         let signature_1: k256::ecdsa::recoverable::Signature = priv_key_1.sign(message);
-//         assert!(pub_key_1.verify(message, &signature_1).is_ok());
-//
-//         // two private keys are serialized the same
-//         assert_eq!(key_pair_copied.private().as_bytes(), priv_key_1.to_bytes().as_slice());
-//
-//         // two pubkeys are the same
-//         assert_eq!(
-//             key_pair.public().as_bytes(),
-//             pub_key_1.to_bytes().as_slice()
-//         );
-//
-//         // same recovered pubkey are recovered
-//         let recovered_key = signature.sig.recover(&hashed_msg).unwrap();
-//         let recovered_key_1 = signature_1.recover_verifying_key(message).expect("couldn't recover pubkey");
-//         assert_eq!(recovered_key.serialize(),recovered_key_1.to_bytes().as_slice());
-//
-//         // same signatures produced from both implementations
-//         assert_eq!(signature.as_ref(), ToFromBytes::as_bytes(&signature_1));
-//
-//         // use ffi-implemented keypair to verify sig constructed by k256
-//         let sig_bytes_1 = bincode::serialize(&signature_1.as_ref()).unwrap();
-//         let secp_sig1 = bincode::deserialize::<Secp256r1Signature>(&sig_bytes_1).unwrap();
-//         assert!(key_pair_copied_2.public().verify(message, &secp_sig1).is_ok());
-//
-//         // use k256 keypair to verify sig constructed by ffi-implementation
-//         let typed_sig = k256::ecdsa::recoverable::Signature::try_from(signature.as_ref()).unwrap();
-//         assert!(pub_key_1.verify(message, &typed_sig).is_ok());
-//     }
-// }
-
 #[test]
 fn wycheproof_test_nonrecoverable() {
     let test_set = TestSet::load(EcdsaSecp256r1Sha256).unwrap();
     for test_group in test_set.test_groups {
-        let pk = Secp256r1PublicKey::from_bytes(&test_group.key.key).unwrap();
+        // Wycheproof keys are uncompressed, and only compressed keys are accepted
+        let pk = p256::ecdsa::VerifyingKey::from_sec1_bytes(&test_group.key.key).unwrap();
+        let pk = Secp256r1PublicKey::from_bytes(pk.to_encoded_point(true).as_bytes()).unwrap();
         for test in test_group.tests {
             let signature = match Signature::from_der(&test.sig) {
                 Ok(s) => Secp256r1Signature::from_bytes(
                     // Wycheproof tests do not enforce low s but we do, so we need to normalize
-                    s.normalize_s().unwrap_or(s).to_bytes().as_slice(),
+                    s.normalize_s().unwrap_or(s).as_ref(),
                 )
                 .unwrap(),
                 Err(_) => {
@@ -583,7 +436,6 @@ fn dont_display_secrets() {
     });
 }
 
-// Arbitrary implementations for the proptests
 fn arb_keypair() -> impl Strategy<Value = Secp256r1KeyPair> {
     any::<[u8; 32]>()
         .prop_map(|seed| {
@@ -3,8 +3,48 @@
 
 use anyhow::Result;
 use serde::{de::DeserializeOwned, Serialize};
+use std::ops::{Deref, DerefMut};
 use std::path::Path;
 
+// File format of a config, chosen by the extension of its path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    // `.yaml` / `.yml` and `.toml` files, in any case, and JSON otherwise
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    pub fn serialize<T: ?Sized + Serialize>(self, value: &T) -> Result<String> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value)?,
+            ConfigFormat::Yaml => serde_yaml::to_string(value)?,
+            ConfigFormat::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(self, content: &str) -> Result<T> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+            ConfigFormat::Toml => toml::from_str(content)?,
+        })
+    }
+}
+
 // Config trait compatible with Starcoin's interface
 // Wraps Starcoin's config functionality
 pub trait Config: Serialize + DeserializeOwned {
@@ -15,26 +55,43 @@ pub trait Config: Serialize + DeserializeOwned {
         PersistedConfig {
             inner: self,
             path: path.to_path_buf(),
+            format: ConfigFormat::from_path(path),
         }
     }
 
+    // Loads the config at `path`, and saves it back in the format it was
+    // read with
+    fn load_persisted<P: AsRef<Path>>(path: P) -> Result<PersistedConfig<Self>>
+    where
+        Self: Sized,
+    {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path);
+        Ok(PersistedConfig {
+            inner: Self::load_as(path, format)?,
+            path: path.to_path_buf(),
+            format,
+        })
+    }
+
     fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
+        Self::load_as(path, ConfigFormat::from_path(path))
+    }
+
+    fn load_as<P: AsRef<Path>>(path: P, format: ConfigFormat) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        // Support both YAML and JSON formats
-        let config: Self = if path.extension().and_then(|s| s.to_str()) == Some("yaml")
-            || path.extension().and_then(|s| s.to_str()) == Some("yml")
-        {
-            serde_yaml::from_str(&content)?
-        } else {
-            serde_json::from_str(&content)?
-        };
-        Ok(config)
+        format.deserialize(&content)
     }
 
+    // Saves in the format `load` reads from the same path
     fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path = path.as_ref();
-        let content = serde_json::to_string_pretty(self)?;
+        self.save_as(path, ConfigFormat::from_path(path))
+    }
+
+    fn save_as<P: AsRef<Path>>(&self, path: P, format: ConfigFormat) -> Result<()> {
+        let content = format.serialize(self)?;
         std::fs::write(path, content)?;
         Ok(())
     }
@@ -43,15 +100,34 @@ pub trait Config: Serialize + DeserializeOwned {
 pub struct PersistedConfig<C> {
     inner: C,
     path: std::path::PathBuf,
+    format: ConfigFormat,
 }
 
 impl<C: Config> PersistedConfig<C> {
     pub fn read(&self) -> Result<C> {
-        C::load(&self.path)
+        C::load_as(&self.path, self.format)
     }
 
     pub fn save(&self) -> Result<()> {
-        self.inner.save(&self.path)
+        self.inner.save_as(&self.path, self.format)
+    }
+
+    pub fn format(&self) -> ConfigFormat {
+        self.format
+    }
+}
+
+impl<C> Deref for PersistedConfig<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C> DerefMut for PersistedConfig<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.inner
     }
 }
 
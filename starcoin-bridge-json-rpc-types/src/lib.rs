@@ -275,9 +275,15 @@ pub struct EventFilter {
     /// Type tags to filter events (e.g., "0x1::bridge::TokenDepositedEvent")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub type_tags: Option<Vec<String>>,
+    /// Addresses of the event handles, i.e. the accounts emitting the events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addrs: Option<Vec<String>>,
     /// Maximum number of events to return
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    /// Return events newest first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
 }
 
 impl EventFilter {
@@ -289,6 +295,15 @@ impl EventFilter {
         }
     }
 
+    /// Create a filter for events emitted by a package. Starcoin can't filter
+    /// by module, callers match the module on the returned type tags.
+    pub fn move_event_module(package_address: &str) -> Self {
+        Self {
+            addrs: Some(vec![package_address.to_string()]),
+            ..Default::default()
+        }
+    }
+
     /// Create a filter with block range
     pub fn block_range(from_block: u64, to_block: u64) -> Self {
         Self {
@@ -323,8 +338,174 @@ impl<T, C> Page<T, C> {
     }
 }
 
-// EventPage with (u64, u64) tuple as cursor (block_num, event_idx)
-pub type EventPage = Page<StarcoinEvent, (u64, u64)>;
+// EventPage with an opaque cursor bound to the filter it was issued for
+pub type EventPage = Page<StarcoinEvent, EventCursor>;
+
+/// Version of the `EventCursor` string format. Version 1 cursors were bare
+/// `<block_num>:<event_idx>` positions without a filter fingerprint.
+pub const EVENT_CURSOR_VERSION: u8 = 2;
+
+/// Fingerprint of the normalized form of an `EventFilter`. The block range,
+/// limit and order only control pagination, so they are not part of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FilterFingerprint(u64);
+
+impl std::fmt::Display for FilterFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl EventFilter {
+    pub fn fingerprint(&self) -> FilterFingerprint {
+        let mut normalized = match &self.type_tags {
+            None => "type_tags:*".to_string(),
+            Some(type_tags) => {
+                let mut type_tags = type_tags.clone();
+                type_tags.sort();
+                type_tags.dedup();
+                format!("type_tags:{}", type_tags.join(","))
+            }
+        };
+        // Only appended when set, so fingerprints of filters without
+        // addresses match the ones issued before the field existed
+        if let Some(addrs) = &self.addrs {
+            let mut addrs = addrs.iter().map(|a| a.to_lowercase()).collect::<Vec<_>>();
+            addrs.sort();
+            addrs.dedup();
+            normalized.push_str(&format!(";addrs:{}", addrs.join(",")));
+        }
+        // FNV-1a, so fingerprints stay stable across builds and Rust versions
+        let hash = normalized
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        FilterFingerprint(hash)
+    }
+}
+
+/// Position in the results of an event query, bound to the filter of that
+/// query. Encoded as `2:<fingerprint>:<block_num>:<event_idx>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCursor {
+    position: (u64, u64),
+    // None for cursors persisted before fingerprints were added
+    fingerprint: Option<FilterFingerprint>,
+}
+
+/// A cursor was used with a different filter than the one it was issued for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorFilterMismatch {
+    pub cursor: EventCursor,
+    pub filter: FilterFingerprint,
+}
+
+impl std::fmt::Display for CursorFilterMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cursor {} was issued for a different filter than {}",
+            self.cursor, self.filter
+        )
+    }
+}
+
+impl std::error::Error for CursorFilterMismatch {}
+
+impl EventCursor {
+    pub fn new(position: (u64, u64), filter: &EventFilter) -> Self {
+        Self {
+            position,
+            fingerprint: Some(filter.fingerprint()),
+        }
+    }
+
+    /// A cursor without a fingerprint, as persisted by older versions.
+    pub fn legacy(position: (u64, u64)) -> Self {
+        Self {
+            position,
+            fingerprint: None,
+        }
+    }
+
+    pub fn position(&self) -> (u64, u64) {
+        self.position
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.fingerprint.is_none()
+    }
+
+    /// Returns the position if the cursor may be used with `filter`. Legacy
+    /// cursors are accepted with any filter.
+    pub fn check(&self, filter: &EventFilter) -> Result<(u64, u64), CursorFilterMismatch> {
+        let expected = filter.fingerprint();
+        match self.fingerprint {
+            Some(fingerprint) if fingerprint != expected => Err(CursorFilterMismatch {
+                cursor: *self,
+                filter: expected,
+            }),
+            _ => Ok(self.position),
+        }
+    }
+}
+
+impl std::fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (block_num, event_idx) = self.position;
+        match self.fingerprint {
+            Some(fingerprint) => write!(
+                f,
+                "{}:{}:{}:{}",
+                EVENT_CURSOR_VERSION, fingerprint, block_num, event_idx
+            ),
+            None => write!(f, "{}:{}", block_num, event_idx),
+        }
+    }
+}
+
+impl std::str::FromStr for EventCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let parse_u64 = |part: &str| {
+            part.parse::<u64>()
+                .map_err(|e| anyhow::anyhow!("Invalid event cursor {:?}: {}", s, e))
+        };
+        match parts.as_slice() {
+            [block_num, event_idx] => {
+                Ok(Self::legacy((parse_u64(block_num)?, parse_u64(event_idx)?)))
+            }
+            [version, fingerprint, block_num, event_idx] => {
+                if *version != EVENT_CURSOR_VERSION.to_string() {
+                    anyhow::bail!("Unsupported event cursor version in {:?}", s);
+                }
+                let fingerprint = u64::from_str_radix(fingerprint, 16)
+                    .map_err(|e| anyhow::anyhow!("Invalid event cursor {:?}: {}", s, e))?;
+                Ok(Self {
+                    position: (parse_u64(block_num)?, parse_u64(event_idx)?),
+                    fingerprint: Some(FilterFingerprint(fingerprint)),
+                })
+            }
+            _ => anyhow::bail!("Invalid event cursor {:?}", s),
+        }
+    }
+}
+
+impl Serialize for EventCursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
 
 // Placeholder for StarcoinObjectDataOptions
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -380,6 +561,8 @@ pub struct StarcoinObjectData {
     pub version: u64,
     pub digest: [u8; 32],
     pub owner: Option<Owner>,
+    // Value of the object when it is gas, i.e. an account's STC balance
+    pub gas_balance: Option<u64>,
 }
 
 impl StarcoinObjectData {
@@ -500,3 +683,65 @@ pub struct CoinPage {
     pub next_cursor: Option<String>,
     pub has_next_page: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_filter(module: &str) -> EventFilter {
+        EventFilter::move_event_type(&format!("0x1::{}::Event", module))
+    }
+
+    #[test]
+    fn test_event_cursor_round_trip() {
+        let filter = module_filter("bridge");
+        let cursor = EventCursor::new((42, 3), &filter);
+        let encoded = cursor.to_string();
+        assert!(encoded.starts_with("2:"));
+        assert_eq!(encoded.parse::<EventCursor>().unwrap(), cursor);
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(json, format!("\"{}\"", encoded));
+        assert_eq!(serde_json::from_str::<EventCursor>(&json).unwrap(), cursor);
+
+        // Pagination fields and type tag order are not part of the fingerprint
+        let mut paged = EventFilter {
+            type_tags: Some(vec!["0x1::b::E".to_string(), "0x1::a::E".to_string()]),
+            ..Default::default()
+        };
+        let reference = paged.fingerprint();
+        paged.from_block = Some(10);
+        paged.limit = Some(5);
+        paged.type_tags.as_mut().unwrap().reverse();
+        assert_eq!(paged.fingerprint(), reference);
+        paged.reverse = Some(true);
+        assert_eq!(paged.fingerprint(), reference);
+        paged.addrs = Some(vec!["0x1".to_string()]);
+        assert_ne!(paged.fingerprint(), reference);
+
+        assert!("3:00:1:2".parse::<EventCursor>().is_err());
+        assert!("1:2:3".parse::<EventCursor>().is_err());
+        assert!("a:b".parse::<EventCursor>().is_err());
+    }
+
+    #[test]
+    fn test_event_cursor_filter_mismatch() {
+        let bridge = module_filter("bridge");
+        let committee = module_filter("committee");
+        let cursor = EventCursor::new((42, 3), &bridge);
+        assert_eq!(cursor.check(&bridge).unwrap(), (42, 3));
+        let err = cursor.check(&committee).unwrap_err();
+        assert_eq!(err.cursor, cursor);
+        assert_eq!(err.filter, committee.fingerprint());
+    }
+
+    #[test]
+    fn test_legacy_event_cursor() {
+        let cursor: EventCursor = "42:3".parse().unwrap();
+        assert!(cursor.is_legacy());
+        assert_eq!(cursor, EventCursor::legacy((42, 3)));
+        assert_eq!(cursor.to_string(), "42:3");
+        // Accepted with any filter
+        assert_eq!(cursor.check(&module_filter("bridge")).unwrap(), (42, 3));
+        assert_eq!(cursor.check(&module_filter("committee")).unwrap(), (42, 3));
+    }
+}
@@ -3,6 +3,7 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use starcoin_bridge_keys::keygen::KeyScheme;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -27,8 +28,12 @@ enum Commands {
         #[arg(short, long)]
         output: PathBuf,
 
-        /// Use ECDSA (Secp256k1) instead of Ed25519
-        #[arg(long, default_value = "false")]
+        /// Signature scheme of the key
+        #[arg(long, value_enum, default_value = "ed25519")]
+        key_scheme: KeyScheme,
+
+        /// Use ECDSA (Secp256k1) instead of Ed25519, same as `--key-scheme secp256k1`
+        #[arg(long, default_value = "false", conflicts_with = "key_scheme")]
         ecdsa: bool,
     },
     /// Examine an existing key file
@@ -54,11 +59,15 @@ fn main() -> Result<()> {
             );
             println!("  3. The Ethereum address above is derived from this key and used for bridge operations");
         }
-        Commands::Client { output, ecdsa } => {
-            let key_type = if ecdsa { "Secp256k1" } else { "Ed25519" };
-            println!("Generating bridge client key ({})...", key_type);
+        Commands::Client {
+            output,
+            key_scheme,
+            ecdsa,
+        } => {
+            let key_scheme = if ecdsa { KeyScheme::Secp256k1 } else { key_scheme };
+            println!("Generating bridge client key ({:?})...", key_scheme);
             starcoin_bridge_keys::keygen::generate_bridge_client_key_and_write_to_file(
-                &output, ecdsa,
+                &output, key_scheme,
             )?;
             println!("\n✓ Bridge client key generated successfully!");
             println!("  File: {:?}", output);
@@ -103,6 +112,10 @@ fn examine_key_file(path: &PathBuf) -> Result<()> {
             println!("Key type: Ed25519");
             println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
         }
+        StarcoinKeyPair::Secp256r1(kp) => {
+            println!("Key type: Secp256r1");
+            println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
+        }
     }
 
     Ok(())
@@ -0,0 +1,245 @@
+// Passphrase-encrypted key files. An encrypted file starts with
+// `ENCRYPTED_KEY_HEADER` on its own line, followed by a JSON envelope with
+// the AES-256-GCM ciphertext of the plaintext key file contents. The AES key
+// is derived from the passphrase with scrypt.
+
+use crate::keypair_file::parse_key;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Context, Result};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+pub const ENCRYPTED_KEY_HEADER: &str = "starcoin-bridge-encrypted-key-v1";
+
+// Passphrase of encrypted key files when there is no TTY to prompt on
+pub const PASSPHRASE_ENV_VAR: &str = "BRIDGE_KEY_PASSPHRASE";
+
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Envelope {
+    kdf: KdfParams,
+    // Hex encoded
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+// scrypt cost parameters, stored with the key so they can be raised later
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct KdfParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl Default for KdfParams {
+    // 32 MiB of memory per derivation
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+pub fn is_encrypted(contents: &str) -> bool {
+    contents.trim_start().starts_with(ENCRYPTED_KEY_HEADER)
+}
+
+// Encrypt the contents of a plaintext key file
+pub fn encrypt(plaintext: &str, passphrase: &str) -> Result<String> {
+    encrypt_with_params(plaintext, passphrase, KdfParams::default())
+}
+
+fn encrypt_with_params(plaintext: &str, passphrase: &str, kdf: KdfParams) -> Result<String> {
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let key = derive_key(passphrase, &salt, kdf)?;
+    let ciphertext = Aes256Gcm::new_from_slice(key.as_slice())
+        .map_err(|e| anyhow!("Invalid encryption key: {}", e))?
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|e| anyhow!("Failed to encrypt key: {}", e))?;
+    let envelope = Envelope {
+        kdf,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    };
+    Ok(format!(
+        "{}\n{}\n",
+        ENCRYPTED_KEY_HEADER,
+        serde_json::to_string_pretty(&envelope)?
+    ))
+}
+
+// Decrypt the contents of an encrypted key file back to the plaintext ones
+pub fn decrypt(contents: &str, passphrase: &str) -> Result<Zeroizing<String>> {
+    let envelope = contents
+        .trim_start()
+        .strip_prefix(ENCRYPTED_KEY_HEADER)
+        .ok_or_else(|| anyhow!("Not an encrypted key file"))?;
+    let malformed =
+        |e: &dyn std::fmt::Display| anyhow!("Encrypted key file is truncated or malformed: {}", e);
+    let envelope: Envelope = serde_json::from_str(envelope).map_err(|e| malformed(&e))?;
+    let salt = hex::decode(&envelope.salt).map_err(|e| malformed(&e))?;
+    let nonce = hex::decode(&envelope.nonce).map_err(|e| malformed(&e))?;
+    let ciphertext = hex::decode(&envelope.ciphertext).map_err(|e| malformed(&e))?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(malformed(&format!("nonce is {} bytes", nonce.len())));
+    }
+    let key = derive_key(passphrase, &salt, envelope.kdf)?;
+    // The authentication tag fails both on a wrong passphrase and on a
+    // modified ciphertext, the two can't be told apart
+    let plaintext = Aes256Gcm::new_from_slice(key.as_slice())
+        .map_err(|e| anyhow!("Invalid encryption key: {}", e))?
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Wrong passphrase, or the encrypted key file is corrupted"))?;
+    String::from_utf8(plaintext)
+        .map(Zeroizing::new)
+        .map_err(|_| malformed(&"key is not utf8"))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Zeroizing<[u8; 32]>> {
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+        .map_err(|e| anyhow!("Invalid scrypt parameters {:?}: {}", kdf, e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, key.as_mut_slice())
+        .map_err(|e| anyhow!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+// The passphrase from `PASSPHRASE_ENV_VAR` if set, otherwise prompted for on
+// the TTY. With `confirm`, a prompted passphrase must be entered twice.
+pub fn read_passphrase(confirm: bool) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Key file is encrypted, set {} to its passphrase",
+            PASSPHRASE_ENV_VAR
+        ));
+    }
+    let passphrase = Zeroizing::new(rpassword::prompt_password("Key passphrase: ")?);
+    if confirm {
+        let again = Zeroizing::new(rpassword::prompt_password("Repeat passphrase: ")?);
+        if passphrase != again {
+            return Err(anyhow!("Passphrases don't match"));
+        }
+    }
+    Ok(passphrase)
+}
+
+// Encrypt the plaintext key file at `path` into `out`
+pub fn encrypt_key_file(path: &Path, out: &Path) -> Result<()> {
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?,
+    );
+    if is_encrypted(&contents) {
+        return Err(anyhow!("{:?} is already encrypted", path));
+    }
+    // Refuse to encrypt something that isn't a key
+    parse_key(&contents, false).map_err(|e| anyhow!("{} at {:?}", e, path))?;
+    let passphrase = read_passphrase(true)?;
+    write_new_file(out, &encrypt(&contents, &passphrase)?)
+}
+
+// Decrypt the encrypted key file at `path` into `out`
+pub fn decrypt_key_file(path: &Path, out: &Path) -> Result<()> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    if !is_encrypted(&contents) {
+        return Err(anyhow!("{:?} is not encrypted", path));
+    }
+    let passphrase = read_passphrase(false)?;
+    let plaintext = decrypt(&contents, &passphrase).map_err(|e| anyhow!("{} at {:?}", e, path))?;
+    parse_key(&plaintext, false).map_err(|e| anyhow!("{} in {:?}", e, path))?;
+    write_new_file(out, &plaintext)
+}
+
+// Never overwrite a key file, it may be the only copy of the key
+fn write_new_file(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("Failed to write key to {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StarcoinKeyPair;
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::EncodeDecodeBase64;
+    use starcoin_bridge_types::crypto::get_key_pair;
+
+    // Cheap enough to keep the tests fast
+    const TEST_KDF: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn test_encrypted_key_round_trip() {
+        let keys = [
+            StarcoinKeyPair::Ed25519(get_key_pair::<Ed25519KeyPair>().1),
+            StarcoinKeyPair::Secp256k1(get_key_pair::<Secp256k1KeyPair>().1),
+        ];
+        for key in keys {
+            let plaintext = key.encode_base64();
+            let encrypted = encrypt_with_params(&plaintext, "hunter2", TEST_KDF).unwrap();
+            assert!(is_encrypted(&encrypted));
+            assert!(!encrypted.contains(&plaintext));
+            assert!(!is_encrypted(&plaintext));
+
+            let decrypted = decrypt(&encrypted, "hunter2").unwrap();
+            assert_eq!(*decrypted, plaintext);
+            let decoded = parse_key(&decrypted, false).unwrap();
+            assert_eq!(decoded.public(), key.public());
+        }
+    }
+
+    #[test]
+    fn test_encrypted_key_errors() {
+        let plaintext =
+            StarcoinKeyPair::Ed25519(get_key_pair::<Ed25519KeyPair>().1).encode_base64();
+        let encrypted = encrypt_with_params(&plaintext, "hunter2", TEST_KDF).unwrap();
+
+        let err = decrypt(&encrypted, "hunter3").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"), "{}", err);
+
+        let truncated = &encrypted[..encrypted.len() / 2];
+        let err = decrypt(truncated, "hunter2").unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{}", err);
+
+        let err = decrypt(ENCRYPTED_KEY_HEADER, "hunter2").unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{}", err);
+
+        assert!(decrypt(&plaintext, "hunter2").is_err());
+    }
+
+    #[test]
+    fn test_key_files_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key");
+        write_new_file(&path, "first").unwrap();
+        assert!(write_new_file(&path, "second").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+    }
+}
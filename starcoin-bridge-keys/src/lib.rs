@@ -7,12 +7,23 @@ use std::path::Path;
 // Re-export StarcoinKeyPair from starcoin-bridge-types
 pub use starcoin_bridge_types::crypto::StarcoinKeyPair;
 
+pub mod encrypted;
+
 pub mod keygen {
     use super::*;
     use anyhow::{anyhow, Result};
     use fastcrypto::{secp256k1::Secp256k1KeyPair, traits::EncodeDecodeBase64};
     use std::path::PathBuf;
 
+    /// Signature scheme of a generated bridge client key
+    #[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum KeyScheme {
+        #[default]
+        Ed25519,
+        Secp256k1,
+        Secp256r1,
+    }
+
     /// Generate a new Secp256k1 keypair for bridge authority and write to file
     /// The key is written as base64-encoded `flag || privkey` (StarcoinKeyPair format)
     pub fn generate_bridge_authority_key_and_write_to_file(path: &PathBuf) -> Result<()> {
@@ -70,28 +81,38 @@ pub mod keygen {
         addr
     }
 
-    /// Generate a new StarcoinKeyPair (Ed25519 or Secp256k1) for bridge client and write to file
+    /// Generate a new StarcoinKeyPair of the given scheme for bridge client and write to file
     /// The key is written as base64-encoded `flag || privkey`
     pub fn generate_bridge_client_key_and_write_to_file(
         path: &PathBuf,
-        use_ecdsa: bool,
+        key_scheme: KeyScheme,
     ) -> Result<()> {
         use fastcrypto::ed25519::Ed25519KeyPair;
+        use fastcrypto::secp256r1::Secp256r1KeyPair;
         use fastcrypto::traits::{KeyPair as _, ToFromBytes};
 
-        let kp = if use_ecdsa {
-            let (_, kp): ((), Secp256k1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
-            println!("Generated new Secp256k1 keypair for bridge client");
-            println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
+        let kp = match key_scheme {
+            KeyScheme::Secp256k1 => {
+                let (_, kp): ((), Secp256k1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+                println!("Generated new Secp256k1 keypair for bridge client");
+                println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
 
-            let eth_address = calculate_eth_address(kp.public());
-            println!("Ethereum address: 0x{}", hex::encode(eth_address));
-            StarcoinKeyPair::Secp256k1(kp)
-        } else {
-            let (_, kp): ((), Ed25519KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
-            println!("Generated new Ed25519 keypair for bridge client");
-            println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
-            StarcoinKeyPair::Ed25519(kp)
+                let eth_address = calculate_eth_address(kp.public());
+                println!("Ethereum address: 0x{}", hex::encode(eth_address));
+                StarcoinKeyPair::Secp256k1(kp)
+            }
+            KeyScheme::Secp256r1 => {
+                let (_, kp): ((), Secp256r1KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+                println!("Generated new Secp256r1 keypair for bridge client");
+                println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
+                StarcoinKeyPair::Secp256r1(kp)
+            }
+            KeyScheme::Ed25519 => {
+                let (_, kp): ((), Ed25519KeyPair) = starcoin_bridge_types::crypto::get_key_pair();
+                println!("Generated new Ed25519 keypair for bridge client");
+                println!("Public key (hex): {}", hex::encode(kp.public().as_bytes()));
+                StarcoinKeyPair::Ed25519(kp)
+            }
         };
 
         // Encode the keypair as base64
@@ -109,22 +130,42 @@ pub mod keygen {
 pub mod keypair_file {
     use super::*;
     use anyhow::{anyhow, Result};
-    use fastcrypto::{secp256k1::Secp256k1KeyPair, traits::EncodeDecodeBase64};
+    use fastcrypto::{
+        secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey},
+        traits::{EncodeDecodeBase64, ToFromBytes},
+    };
     use std::path::PathBuf;
 
-    // Read a StarcoinKeyPair from a file
-    // The file should contain Base64 encoded `flag || privkey`
+    // Read a StarcoinKeyPair from a file, in any format `parse_key` accepts
     // If require_secp256k1 is true, only Secp256k1 keys are accepted
+    // Encrypted key files are decrypted with `encrypted::read_passphrase`
     pub fn read_key(path: &PathBuf, require_secp256k1: bool) -> Result<StarcoinKeyPair> {
         if !path.exists() {
             return Err(anyhow!("Key file not found at path: {:?}", path));
         }
 
         let file_contents = std::fs::read_to_string(path)?;
-        let contents = file_contents.as_str().trim();
+        if crate::encrypted::is_encrypted(&file_contents) {
+            let passphrase = crate::encrypted::read_passphrase(false)?;
+            let plaintext = crate::encrypted::decrypt(&file_contents, &passphrase)
+                .map_err(|e| anyhow!("{} at {:?}", e, path))?;
+            return parse_key(&plaintext, require_secp256k1)
+                .map_err(|e| anyhow!("{} at {:?}", e, path));
+        }
+        parse_key(&file_contents, require_secp256k1).map_err(|e| anyhow!("{} at {:?}", e, path))
+    }
+
+    // Parse a StarcoinKeyPair from one of:
+    // - Base64 encoded `flag || privkey`
+    // - Base64 encoded raw Secp256k1 `privkey`
+    // - Hex encoded raw Secp256k1 `privkey`, with or without `0x`
+    // Errors never include the key material.
+    pub fn parse_key(contents: &str, require_secp256k1: bool) -> Result<StarcoinKeyPair> {
+        let contents = contents.trim();
 
         // Try base64 encoded StarcoinKeyPair `flag || privkey`
         if let Ok(key) = StarcoinKeyPair::decode_base64(contents) {
+            // Secp256r1 keys are ECDSA too, but can't sign for Ethereum
             if require_secp256k1 && !matches!(key, StarcoinKeyPair::Secp256k1(_)) {
                 return Err(anyhow!("Key is not Secp256k1"));
             }
@@ -136,10 +177,16 @@ pub mod keypair_file {
             return Ok(StarcoinKeyPair::Secp256k1(key));
         }
 
-        Err(anyhow!(
-            "Invalid key file format. Expected Base64 encoded key at {:?}",
-            path
-        ))
+        // Try hex encoded Raw Secp256k1 key `privkey`
+        let hex_contents = contents.strip_prefix("0x").unwrap_or(contents);
+        if let Some(key) = hex::decode(hex_contents)
+            .ok()
+            .and_then(|bytes| Secp256k1PrivateKey::from_bytes(&bytes).ok())
+        {
+            return Ok(StarcoinKeyPair::Secp256k1(Secp256k1KeyPair::from(key)));
+        }
+
+        Err(anyhow!("Invalid key format. Expected a Base64 or hex encoded key"))
     }
 
     // Read from file as Base64 encoded `flag || privkey` and return a NetworkKeyPair (Ed25519)
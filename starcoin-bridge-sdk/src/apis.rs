@@ -3,16 +3,30 @@
 
 use anyhow::Result;
 use futures::Stream;
-use starcoin_bridge_json_rpc_types::Coin;
+use starcoin_bridge_json_rpc_types::{Coin, CoinPage};
+use starcoin_rpc_api::types::AnnotatedMoveValueView;
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::language_storage::StructTag;
+use std::str::FromStr;
+use std::sync::Arc;
 
-// Coin Read API stub
+// Native STC token type, used when no coin type is given
+const STC_COIN_TYPE: &str = "0x1::STC::STC";
+
+// Coin Read API backed by Starcoin account balance resources.
+//
+// Starcoin has no coin objects: an account holds a single
+// `0x1::Account::Balance<T>` resource per token type. Each balance is
+// surfaced as one synthetic `Coin` so that callers written against the
+// coin-object model keep working.
 pub struct CoinReadApi {
-    // TODO: Implement Starcoin coin/token reading
+    client: Arc<RpcClient>,
 }
 
 impl CoinReadApi {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(client: Arc<RpcClient>) -> Self {
+        Self { client }
     }
 
     // Get total supply for a coin type
@@ -25,20 +39,57 @@ impl CoinReadApi {
         Ok(starcoin_bridge_json_rpc_types::Supply { value: 0 })
     }
 
-    // Get coins for an address
+    // Get the balance of `coin_type` (STC if None) held by an address.
+    // Accounts that never accepted the token have a balance of zero.
+    pub async fn get_balance(&self, address: [u8; 32], coin_type: Option<String>) -> Result<u128> {
+        let coin_type = coin_type.unwrap_or_else(|| STC_COIN_TYPE.to_string());
+        let struct_tag = StructTag::from_str(&format!("0x1::Account::Balance<{}>", coin_type))?;
+        let resource = self
+            .client
+            .get_resource(to_account_address(address), struct_tag)?;
+        let Some(resource) = resource else {
+            return Ok(0);
+        };
+
+        let token = resource
+            .value
+            .iter()
+            .find(|(id, _)| id.as_str() == "token")
+            .map(|(_, v)| v)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'token' field in Balance resource"))?;
+        let token = match token {
+            AnnotatedMoveValueView::Struct(s) => s,
+            _ => anyhow::bail!("Expected 'token' to be a struct, got {:?}", token),
+        };
+        let value = token
+            .value
+            .iter()
+            .find(|(id, _)| id.as_str() == "value")
+            .map(|(_, v)| v)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'value' field in Token"))?;
+        match value {
+            AnnotatedMoveValueView::U128(v) => Ok(v.0),
+            _ => anyhow::bail!("Expected 'value' to be u128, got {:?}", value),
+        }
+    }
+
+    // Get coins for an address. Only STC is returned when no coin type is
+    // given, since account resources can't be enumerated by token type.
     pub async fn get_coins(
         &self,
-        _address: [u8; 32],
-        _coin_type: Option<String>,
-        _cursor: Option<String>,
-        _limit: Option<usize>,
-    ) -> Result<starcoin_bridge_json_rpc_types::CoinPage> {
-        // Return empty page for now
-        Ok(starcoin_bridge_json_rpc_types::CoinPage {
-            data: vec![],
-            next_cursor: None,
-            has_next_page: false,
-        })
+        address: [u8; 32],
+        coin_type: Option<String>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage> {
+        let coin_type = coin_type.unwrap_or_else(|| STC_COIN_TYPE.to_string());
+        let balance = self.get_balance(address, Some(coin_type.clone())).await?;
+        let coins = if balance > 0 {
+            vec![balance_coin(address, coin_type, balance)]
+        } else {
+            vec![]
+        };
+        Ok(paginate(coins, cursor, limit))
     }
 
     // Select coins up to a certain amount
@@ -64,8 +115,87 @@ impl CoinReadApi {
     }
 }
 
-impl Default for CoinReadApi {
-    fn default() -> Self {
-        Self::new()
+// Starcoin addresses are 16 bytes, right-aligned in the 32-byte form
+pub(crate) fn to_account_address(address: [u8; 32]) -> AccountAddress {
+    let mut bytes = [0u8; AccountAddress::LENGTH];
+    bytes.copy_from_slice(&address[32 - AccountAddress::LENGTH..]);
+    AccountAddress::new(bytes)
+}
+
+// Build the synthetic coin standing in for an account's balance of one token.
+// The object id is the owner address, so it is stable across calls.
+fn balance_coin(address: [u8; 32], coin_type: String, balance: u128) -> Coin {
+    Coin {
+        coin_object_id: address,
+        version: 0,
+        digest: [0u8; 32],
+        balance: u64::try_from(balance).unwrap_or(u64::MAX),
+        coin_type,
+        previous_transaction: [0u8; 32],
+    }
+}
+
+// Page through coins ordered by coin type. The cursor is the coin type of
+// the last coin on the previous page.
+fn paginate(mut coins: Vec<Coin>, cursor: Option<String>, limit: Option<usize>) -> CoinPage {
+    coins.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+    if let Some(cursor) = cursor {
+        coins.retain(|c| c.coin_type > cursor);
+    }
+    let limit = limit.unwrap_or(usize::MAX);
+    let has_next_page = coins.len() > limit;
+    coins.truncate(limit);
+    let next_cursor = if has_next_page {
+        coins.last().map(|c| c.coin_type.clone())
+    } else {
+        None
+    };
+    CoinPage {
+        data: coins,
+        next_cursor,
+        has_next_page,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(coin_type: &str) -> Coin {
+        balance_coin([1u8; 32], coin_type.to_string(), 100)
+    }
+
+    #[test]
+    fn test_paginate() {
+        let coins = vec![coin("0x1::C::C"), coin("0x1::A::A"), coin("0x1::B::B")];
+
+        let page = paginate(coins.clone(), None, Some(2));
+        let types: Vec<_> = page.data.iter().map(|c| c.coin_type.as_str()).collect();
+        assert_eq!(types, vec!["0x1::A::A", "0x1::B::B"]);
+        assert!(page.has_next_page);
+        assert_eq!(page.next_cursor.as_deref(), Some("0x1::B::B"));
+
+        let page = paginate(coins.clone(), page.next_cursor, Some(2));
+        let types: Vec<_> = page.data.iter().map(|c| c.coin_type.as_str()).collect();
+        assert_eq!(types, vec!["0x1::C::C"]);
+        assert!(!page.has_next_page);
+        assert_eq!(page.next_cursor, None);
+
+        let page = paginate(coins, None, None);
+        assert_eq!(page.data.len(), 3);
+        assert!(!page.has_next_page);
+    }
+
+    #[test]
+    fn test_balance_coin_saturates() {
+        let coin = balance_coin([0u8; 32], STC_COIN_TYPE.to_string(), u128::MAX);
+        assert_eq!(coin.balance, u64::MAX);
+    }
+
+    #[test]
+    fn test_to_account_address() {
+        let mut bytes = [0u8; 32];
+        bytes[16..].copy_from_slice(&[7u8; 16]);
+        assert_eq!(to_account_address(bytes), AccountAddress::new([7u8; 16]));
     }
 }
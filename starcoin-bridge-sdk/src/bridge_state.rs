@@ -0,0 +1,326 @@
+// Rust mirrors of the Move structs stored in the `Bridge::Bridge` resource.
+// Fields are declared in the order of the Move definitions so that the
+// resource can be BCS-decoded exactly as it is stored on chain. Structs whose
+// layout already matches one of the shared bridge types reuse that type.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_bridge_types::base_types::StarcoinAddress;
+use starcoin_bridge_types::bridge::{
+    BridgeChainId, BridgeCommitteeSummary, BridgeLimiterSummary, BridgeSummary,
+    BridgeTokenMetadata, BridgeTreasurySummary, MoveTypeBridgeMessageKey, MoveTypeBridgeRecord,
+    MoveTypeBridgeRoute, MoveTypeBridgeTransferRecord, MoveTypeCommitteeMember,
+    MoveTypeCommitteeMemberRegistration,
+};
+
+// `StarcoinFramework::SimpleMap::SimpleMap`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimpleMap<K, V> {
+    pub data: Vec<Element<K, V>>,
+}
+
+// `StarcoinFramework::SimpleMap::Element`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Element<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+impl<K, V> SimpleMap<K, V> {
+    fn into_pairs(self) -> impl Iterator<Item = (K, V)> {
+        self.data.into_iter().map(|e| (e.key, e.value))
+    }
+}
+
+// `Bridge::Bridge::Bridge`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveBridge {
+    pub id: StarcoinAddress,
+    pub inner: MoveBridgeInner,
+}
+
+// `Bridge::Bridge::BridgeInner`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveBridgeInner {
+    pub bridge_version: u64,
+    pub message_version: u8,
+    pub chain_id: u8,
+    pub sequence_nums: SimpleMap<u8, u64>,
+    pub committee: MoveBridgeCommittee,
+    pub treasury: MoveBridgeTreasury,
+    pub token_transfer_records: SimpleMap<MoveTypeBridgeMessageKey, MoveTypeBridgeRecord>,
+    pub limiter: MoveTransferLimiter,
+    pub paused: bool,
+}
+
+// `Bridge::Committee::BridgeCommittee`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveBridgeCommittee {
+    pub members: SimpleMap<Vec<u8>, MoveTypeCommitteeMember>,
+    pub member_registrations: SimpleMap<StarcoinAddress, MoveTypeCommitteeMemberRegistration>,
+    pub last_committee_update_epoch: u64,
+}
+
+// `Bridge::Treasury::BridgeTreasury`. Token types are stored as the bytes of
+// their type name.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveBridgeTreasury {
+    pub supported_tokens: SimpleMap<Vec<u8>, BridgeTokenMetadata>,
+    pub id_token_type_map: SimpleMap<u8, Vec<u8>>,
+    pub waiting_room: SimpleMap<Vec<u8>, MoveForeignTokenRegistration>,
+}
+
+// `Bridge::Treasury::ForeignTokenRegistration`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveForeignTokenRegistration {
+    pub type_name: Vec<u8>,
+    pub decimal: u8,
+}
+
+// `Bridge::Limiter::TransferLimiter`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveTransferLimiter {
+    pub transfer_limits: SimpleMap<MoveTypeBridgeRoute, u64>,
+    pub transfer_records: SimpleMap<MoveTypeBridgeRoute, MoveTypeBridgeTransferRecord>,
+}
+
+// Decode the BCS bytes of a `Bridge::Bridge` resource into a summary
+pub fn bridge_summary_from_bcs(bytes: &[u8]) -> Result<BridgeSummary> {
+    let bridge: MoveBridge =
+        bcs::from_bytes(bytes).context("Failed to decode the Bridge resource")?;
+    bridge_summary(bridge.inner)
+}
+
+fn bridge_summary(inner: MoveBridgeInner) -> Result<BridgeSummary> {
+    let committee = BridgeCommitteeSummary {
+        members: inner.committee.members.into_pairs().collect(),
+        member_registration: inner.committee.member_registrations.into_pairs().collect(),
+        last_committee_update_epoch: inner.committee.last_committee_update_epoch,
+    };
+    let treasury = BridgeTreasurySummary {
+        supported_tokens: inner
+            .treasury
+            .supported_tokens
+            .into_pairs()
+            .map(|(type_name, metadata)| Ok((utf8(type_name)?, metadata)))
+            .collect::<Result<_>>()?,
+        id_token_type_map: inner
+            .treasury
+            .id_token_type_map
+            .into_pairs()
+            .map(|(id, type_name)| Ok((id, utf8(type_name)?)))
+            .collect::<Result<_>>()?,
+        // Tokens can only be paused by pausing the whole bridge on Starcoin
+        paused_token_ids: vec![],
+    };
+    let limiter = BridgeLimiterSummary {
+        transfer_limit: inner
+            .limiter
+            .transfer_limits
+            .into_pairs()
+            .map(|(route, limit)| {
+                let (source, destination) = route_chains(&route)?;
+                Ok((source, destination, limit))
+            })
+            .collect::<Result<_>>()?,
+        transfer_records: inner
+            .limiter
+            .transfer_records
+            .into_pairs()
+            .map(|(route, record)| {
+                let (source, destination) = route_chains(&route)?;
+                Ok((source, destination, record))
+            })
+            .collect::<Result<_>>()?,
+    };
+    Ok(BridgeSummary {
+        bridge_version: inner.bridge_version,
+        message_version: inner.message_version,
+        chain_id: inner.chain_id,
+        sequence_nums: inner.sequence_nums.into_pairs().collect(),
+        committee,
+        treasury,
+        // Records live in the resource itself, there is no separate object
+        bridge_records_id: Default::default(),
+        limiter,
+        is_frozen: inner.paused,
+    })
+}
+
+fn utf8(type_name: Vec<u8>) -> Result<String> {
+    String::from_utf8(type_name).map_err(|e| anyhow!("Token type name is not utf8: {}", e))
+}
+
+fn route_chains(route: &MoveTypeBridgeRoute) -> Result<(BridgeChainId, BridgeChainId)> {
+    let chain = |id: u8| {
+        BridgeChainId::try_from(id).map_err(|_| anyhow!("Unknown chain id {} in bridge route", id))
+    };
+    Ok((chain(route.source)?, chain(route.destination)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BCS of a `Bridge::Bridge` resource, assembled by hand from the field
+    // order of the Move structs: one committee member, one token, a limit
+    // and a record for the Eth -> Starcoin route, one approved transfer, not
+    // paused. The keys and addresses are made up. This only checks that the
+    // Rust types decode the layout they assume; it was never compared with a
+    // `state.get_resource` blob of a real node.
+    const BRIDGE_RESOURCE_BCS: &str = concat!(
+        // id
+        "246b237c16c761e9478783dd83f7004a",
+        // bridge_version, message_version, chain_id
+        "0100000000000000",
+        "01",
+        "02",
+        // sequence_nums: {0: 3, 3: 1}
+        "02",
+        "00",
+        "0300000000000000",
+        "03",
+        "0100000000000000",
+        // committee.members: one member
+        "01",
+        "21",
+        "02d5c4f1ad1fa5d8c48ad5b1a0d6e3f4c51e2f8b4c3a1d9e0f7b6a5c4d3e2f1a0b",
+        "d6ac2d9c6ec0d1ffcaf5c7b27f7d8e92",
+        "21",
+        "02d5c4f1ad1fa5d8c48ad5b1a0d6e3f4c51e2f8b4c3a1d9e0f7b6a5c4d3e2f1a0b",
+        "1027000000000000",
+        "15",
+        "687474703a2f2f3132372e302e302e313a39313931",
+        "00",
+        // committee.member_registrations: none, last_committee_update_epoch
+        "00",
+        "0700000000000000",
+        // treasury.supported_tokens: one token
+        "01",
+        "2e",
+        "3078323436623233376331366337363165393437383738336464383366373030",
+        "34613a3a555344543a3a55534454",
+        "03",
+        "40420f0000000000",
+        "1027000000000000",
+        "00",
+        // treasury.id_token_type_map: {3: ".."}
+        "01",
+        "03",
+        "2e",
+        "3078323436623233376331366337363165393437383738336464383366373030",
+        "34613a3a555344543a3a55534454",
+        // treasury.waiting_room: none
+        "00",
+        // token_transfer_records: one approved transfer from EthCustom
+        "01",
+        "0c",
+        "00",
+        "0500000000000000",
+        "00",
+        "01",
+        "0500000000000000",
+        "0c",
+        "03",
+        "aabbcc",
+        "01",
+        "01",
+        "02",
+        "beef",
+        "00",
+        // limiter.transfer_limits: EthCustom -> StarcoinCustom
+        "01",
+        "0c",
+        "02",
+        "00e40b5402000000",
+        // limiter.transfer_records: EthCustom -> StarcoinCustom
+        "01",
+        "0c",
+        "02",
+        "e803000000000000",
+        "e703000000000000",
+        "02",
+        "0a00000000000000",
+        "1400000000000000",
+        "1e00000000000000",
+        // paused
+        "00",
+    );
+
+    #[test]
+    fn test_bridge_summary_from_bcs() {
+        let bytes = hex::decode(BRIDGE_RESOURCE_BCS).unwrap();
+        let summary = bridge_summary_from_bcs(&bytes).unwrap();
+
+        assert_eq!(summary.bridge_version, 1);
+        assert_eq!(summary.message_version, 1);
+        assert_eq!(summary.chain_id, BridgeChainId::StarcoinCustom as u8);
+        assert_eq!(summary.sequence_nums, vec![(0, 3), (3, 1)]);
+        assert!(!summary.is_frozen);
+
+        let (pubkey, member) = &summary.committee.members[0];
+        assert_eq!(summary.committee.members.len(), 1);
+        assert_eq!(pubkey.len(), 33);
+        assert_eq!(&member.bridge_pubkey_bytes, pubkey);
+        assert_eq!(
+            member.starcoin_bridge_address,
+            StarcoinAddress::from_hex_literal("0xd6ac2d9c6ec0d1ffcaf5c7b27f7d8e92").unwrap()
+        );
+        assert_eq!(member.voting_power, 10000);
+        assert_eq!(member.http_rest_url, b"http://127.0.0.1:9191");
+        assert!(!member.blocklisted);
+        assert!(summary.committee.member_registration.is_empty());
+        assert_eq!(summary.committee.last_committee_update_epoch, 7);
+
+        let usdt = "0x246b237c16c761e9478783dd83f7004a::USDT::USDT".to_string();
+        assert_eq!(
+            summary.treasury.supported_tokens,
+            vec![(
+                usdt.clone(),
+                BridgeTokenMetadata {
+                    id: 3,
+                    decimal_multiplier: 1_000_000,
+                    notional_value: 10_000,
+                    native_token: false,
+                }
+            )]
+        );
+        assert_eq!(summary.treasury.id_token_type_map, vec![(3, usdt)]);
+
+        assert_eq!(
+            summary.limiter.transfer_limit,
+            vec![(
+                BridgeChainId::EthCustom,
+                BridgeChainId::StarcoinCustom,
+                10_000_000_000
+            )]
+        );
+        let (source, destination, record) = &summary.limiter.transfer_records[0];
+        assert_eq!(
+            (*source, *destination),
+            (BridgeChainId::EthCustom, BridgeChainId::StarcoinCustom)
+        );
+        assert_eq!(record.rolling_total_amount(1000), 30);
+    }
+
+    #[test]
+    fn test_bridge_summary_from_bcs_errors() {
+        let bytes = hex::decode(BRIDGE_RESOURCE_BCS).unwrap();
+        // Truncated
+        assert!(bridge_summary_from_bcs(&bytes[..bytes.len() - 1]).is_err());
+        // Trailing bytes
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(bridge_summary_from_bcs(&longer).is_err());
+        // A route to a chain the bridge doesn't know
+        let mut unknown_route = bytes;
+        let limits = hex::decode("010c0200e40b5402000000").unwrap();
+        let at = unknown_route
+            .windows(limits.len())
+            .position(|w| w == limits.as_slice())
+            .unwrap();
+        unknown_route[at + 1] = 0x63;
+        let err = bridge_summary_from_bcs(&unknown_route).unwrap_err();
+        assert!(err.to_string().contains("Unknown chain id 99"), "{}", err);
+    }
+}
@@ -0,0 +1,460 @@
+// Read-only execution of bridge move calls for `ReadApi::dev_inspect_transaction_block`.
+//
+// The bridge client phrases its queries as programmable transactions with a
+// single move call. On Starcoin these run through `contract.call_v2`, which
+// takes typed argument strings and returns decoded JSON values. The function
+// ABI from `contract.resolve_function` supplies the types needed to convert
+// the BCS inputs into arguments and the returned values back into BCS.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+use starcoin_bridge_json_rpc_types::StarcoinExecutionResult;
+use starcoin_bridge_types::transaction::{Argument, CallArg, Command, TransactionKind};
+
+// Bridge functions that take the bridge object have public counterparts on
+// Starcoin reading the global `Bridge` resource instead.
+const QUERY_FUNCTIONS: &[(&str, &str)] = &[
+    (
+        "get_token_transfer_action_status",
+        "query_token_transfer_status",
+    ),
+    (
+        "get_token_transfer_action_signatures",
+        "query_token_transfer_signatures",
+    ),
+];
+
+// A read-only call of a bridge module function
+#[derive(Debug, PartialEq)]
+pub(crate) struct BridgeCall {
+    pub function_id: String,
+    pub type_args: Vec<String>,
+    // BCS bytes of the pure inputs, in call order
+    pub args: Vec<Vec<u8>>,
+}
+
+pub(crate) fn bridge_call(tx_kind: &TransactionKind) -> Result<BridgeCall> {
+    let TransactionKind::ProgrammableTransaction(pt) = tx_kind;
+    let [Command::MoveCall(call)] = pt.commands.as_slice() else {
+        bail!(
+            "Only a single move call can be inspected, got {:?}",
+            pt.commands
+        );
+    };
+    // Package ids are Starcoin addresses left padded to 32 bytes
+    let (padding, address) = call.package.split_at(16);
+    if padding.iter().any(|b| *b != 0) {
+        bail!("Invalid package id 0x{}", hex::encode(call.package));
+    }
+    let function = call.function.as_str();
+    let function = QUERY_FUNCTIONS
+        .iter()
+        .find(|(name, _)| *name == function)
+        .map_or(function, |(_, query)| *query);
+    let mut args = vec![];
+    for argument in &call.arguments {
+        let Argument::Input(index) = argument else {
+            bail!("Unsupported argument {:?} for '{}'", argument, function);
+        };
+        match pt.inputs.get(*index as usize) {
+            Some(CallArg::Pure(bytes)) => args.push(bytes.clone()),
+            // The bridge object is the global `Bridge` resource on Starcoin
+            Some(CallArg::Object(_)) => {}
+            None => bail!("Missing input {} for '{}'", index, function),
+        }
+    }
+    Ok(BridgeCall {
+        function_id: format!(
+            "0x{}::{}::{}",
+            hex::encode(address),
+            module_name(call.module.as_str()),
+            function
+        ),
+        type_args: call.type_arguments.iter().map(|t| t.to_string()).collect(),
+        args,
+    })
+}
+
+// Starcoin bridge modules are the camel case forms of the names the client
+// uses, e.g. `bridge` is deployed as `Bridge`.
+fn module_name(module: &str) -> String {
+    let mut chars = module.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+// `contract.call_v2` arguments for the BCS `args`, typed by the function `abi`
+pub(crate) fn encode_args(abi: &Value, args: &[Vec<u8>]) -> Result<Vec<String>> {
+    let params = abi_list(abi, "args")?;
+    if params.len() != args.len() {
+        bail!(
+            "'{}' takes {} arguments, got {}",
+            abi["name"],
+            params.len(),
+            args.len()
+        );
+    }
+    params
+        .iter()
+        .zip(args)
+        .map(|(param, bytes)| transaction_argument(&param["type_tag"], bytes))
+        .collect()
+}
+
+fn transaction_argument(ty: &Value, bytes: &[u8]) -> Result<String> {
+    let arg = match ty.as_str() {
+        Some("bool") => bcs::from_bytes::<bool>(bytes)?.to_string(),
+        Some("u8") => format!("{}u8", bcs::from_bytes::<u8>(bytes)?),
+        Some("u16") => format!("{}u16", bcs::from_bytes::<u16>(bytes)?),
+        Some("u32") => format!("{}u32", bcs::from_bytes::<u32>(bytes)?),
+        Some("u64") => format!("{}u64", bcs::from_bytes::<u64>(bytes)?),
+        Some("u128") => format!("{}u128", bcs::from_bytes::<u128>(bytes)?),
+        Some("address") => format!("0x{}", hex::encode(bcs::from_bytes::<[u8; 16]>(bytes)?)),
+        _ if ty["vector"] == "u8" => {
+            format!("x\"{}\"", hex::encode(bcs::from_bytes::<Vec<u8>>(bytes)?))
+        }
+        _ => bail!("Unsupported argument type {}", ty),
+    };
+    Ok(arg)
+}
+
+// Maps the decoded `values` returned by `contract.call_v2` into BCS bytes and
+// type names, typed by the function `abi`.
+pub(crate) fn execution_result(abi: &Value, values: &Value) -> Result<StarcoinExecutionResult> {
+    let returns = abi_list(abi, "returns")?;
+    let values = values
+        .as_array()
+        .ok_or_else(|| anyhow!("Unexpected call result: {}", values))?;
+    if returns.len() != values.len() {
+        bail!(
+            "'{}' returns {} values, got {}",
+            abi["name"],
+            returns.len(),
+            values.len()
+        );
+    }
+    let return_values = returns
+        .iter()
+        .zip(values)
+        .map(|(ty, value)| {
+            let mut bytes = vec![];
+            encode_value(ty, value, &mut bytes)?;
+            Ok((bytes, type_name(ty)))
+        })
+        .collect::<Result<_>>()?;
+    Ok(StarcoinExecutionResult { return_values })
+}
+
+fn abi_list<'a>(abi: &'a Value, field: &str) -> Result<&'a Vec<Value>> {
+    abi[field]
+        .as_array()
+        .ok_or_else(|| anyhow!("Missing '{}' in function abi: {}", field, abi))
+}
+
+fn encode_value(ty: &Value, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+    match ty.as_str() {
+        Some("bool") => {
+            let b = value
+                .as_bool()
+                .ok_or_else(|| anyhow!("Expected a bool, got {}", value))?;
+            out.push(b as u8);
+        }
+        Some("u8") => out.push(u8::try_from(integer(value)?)?),
+        Some("u16") => out.extend(u16::try_from(integer(value)?)?.to_le_bytes()),
+        Some("u32") => out.extend(u32::try_from(integer(value)?)?.to_le_bytes()),
+        Some("u64") => out.extend(u64::try_from(integer(value)?)?.to_le_bytes()),
+        Some("u128") => out.extend(integer(value)?.to_le_bytes()),
+        Some("address") => {
+            let hex_str = value
+                .as_str()
+                .ok_or_else(|| anyhow!("Expected an address, got {}", value))?;
+            let hex_str = hex_str.trim_start_matches("0x");
+            let bytes = hex::decode(format!("{:0>32}", hex_str))?;
+            if bytes.len() != 16 {
+                bail!("Invalid address {}", value);
+            }
+            out.extend(bytes);
+        }
+        _ if !ty["vector"].is_null() => encode_vector(&ty["vector"], value, out)?,
+        _ if !ty["struct"].is_null() => encode_struct(&ty["struct"], value, out)?,
+        _ => bail!("Unsupported return type {}", ty),
+    }
+    Ok(())
+}
+
+fn encode_vector(elem: &Value, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+    // Byte vectors are returned as hex strings
+    if let (Some("u8"), Some(hex_str)) = (elem.as_str(), value.as_str()) {
+        let bytes = hex::decode(hex_str.trim_start_matches("0x"))?;
+        write_uleb128(bytes.len(), out);
+        out.extend(bytes);
+        return Ok(());
+    }
+    let items = value
+        .as_array()
+        .ok_or_else(|| anyhow!("Expected a vector, got {}", value))?;
+    write_uleb128(items.len(), out);
+    for item in items {
+        encode_value(elem, item, out)?;
+    }
+    Ok(())
+}
+
+fn encode_struct(ty: &Value, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+    let fields = abi_list(ty, "fields")?;
+    // `Option` is a struct wrapping a vector of at most one element. Accept
+    // it as null or the bare value as well.
+    if let [field] = fields.as_slice() {
+        if field["name"] == "vec" && value.get("vec").is_none() {
+            return match value {
+                Value::Null => {
+                    encode_vector(&field["type_abi"]["vector"], &Value::Array(vec![]), out)
+                }
+                some => encode_vector(
+                    &field["type_abi"]["vector"],
+                    &Value::Array(vec![some.clone()]),
+                    out,
+                ),
+            };
+        }
+    }
+    for field in fields {
+        let name = field["name"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Invalid field abi {}", field))?;
+        let field_value = value
+            .get(name)
+            .ok_or_else(|| anyhow!("Missing field '{}' in {}", name, value))?;
+        encode_value(&field["type_abi"], field_value, out)?;
+    }
+    Ok(())
+}
+
+// Integers wider than 53 bits may come back as strings
+fn integer(value: &Value) -> Result<u128> {
+    match value {
+        Value::Number(n) => n.as_u64().map(u128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("Expected an unsigned integer, got {}", value))
+}
+
+fn write_uleb128(mut len: usize, out: &mut Vec<u8>) {
+    while len >= 0x80 {
+        out.push((len & 0x7f) as u8 | 0x80);
+        len >>= 7;
+    }
+    out.push(len as u8);
+}
+
+fn type_name(ty: &Value) -> String {
+    if let Some(primitive) = ty.as_str() {
+        return primitive.to_string();
+    }
+    if !ty["vector"].is_null() {
+        return format!("vector<{}>", type_name(&ty["vector"]));
+    }
+    let ty = &ty["struct"];
+    match (ty["module_name"].as_str(), ty["name"].as_str()) {
+        (Some(module), Some(name)) => format!("{}::{}", module, name),
+        (None, Some(name)) => name.to_string(),
+        _ => ty.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use serde_json::json;
+    use starcoin_bridge_types::transaction::{ObjectArg, ProgrammableTransaction};
+    use starcoin_bridge_types::{BRIDGE_PACKAGE_ID, STARCOIN_BRIDGE_OBJECT_ID};
+    use starcoin_bridge_vm_types::bridge::bridge::{
+        MoveTypeParsedTokenTransferMessage, MoveTypeTokenTransferPayload,
+    };
+
+    // Built the same way as the bridge client's dev inspect queries
+    fn bridge_query(function: &str, source_chain_id: u8, seq_number: u64) -> TransactionKind {
+        TransactionKind::programmable(ProgrammableTransaction {
+            inputs: vec![
+                CallArg::Object(ObjectArg::SharedObject {
+                    id: STARCOIN_BRIDGE_OBJECT_ID,
+                    initial_shared_version: 1,
+                    mutable: true,
+                }),
+                CallArg::Pure(bcs::to_bytes(&source_chain_id).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&seq_number).unwrap()),
+            ],
+            commands: vec![Command::move_call(
+                BRIDGE_PACKAGE_ID,
+                Identifier::new("bridge").unwrap(),
+                Identifier::new(function).unwrap(),
+                vec![],
+                vec![Argument::Input(0), Argument::Input(1), Argument::Input(2)],
+            )],
+        })
+    }
+
+    // Recorded `contract.resolve_function` response
+    fn query_abi(name: &str, returns: Value) -> Value {
+        json!({
+            "name": name,
+            "module_name": "0x0b8e0206e990e41e913a7f03d1c60675::Bridge",
+            "doc": "",
+            "ty_args": [],
+            "args": [
+                {"name": "source_chain", "type_tag": "u8", "doc": ""},
+                {"name": "bridge_seq_num", "type_tag": "u64", "doc": ""}
+            ],
+            "returns": [returns]
+        })
+    }
+
+    fn option_abi(elem: Value) -> Value {
+        json!({"struct": {
+            "name": "Option",
+            "module_name": "0x00000000000000000000000000000001::Option",
+            "type_args": [elem.clone()],
+            "fields": [{"name": "vec", "doc": "", "type_abi": {"vector": elem}}]
+        }})
+    }
+
+    #[test]
+    fn test_token_transfer_action_status() {
+        let call = bridge_query("get_token_transfer_action_status", 12, 5);
+        let call = bridge_call(&call).unwrap();
+        assert_eq!(
+            call,
+            BridgeCall {
+                function_id:
+                    "0x0b8e0206e990e41e913a7f03d1c60675::Bridge::query_token_transfer_status"
+                        .to_string(),
+                type_args: vec![],
+                args: vec![vec![12], 5u64.to_le_bytes().to_vec()],
+            }
+        );
+        let abi = query_abi("query_token_transfer_status", json!("u8"));
+        assert_eq!(encode_args(&abi, &call.args).unwrap(), vec!["12u8", "5u64"]);
+
+        // Recorded `contract.call_v2` response for an approved transfer
+        let result = execution_result(&abi, &json!([1])).unwrap();
+        let (bytes, type_name) = &result.return_values[0];
+        assert_eq!(type_name, "u8");
+        assert_eq!(bcs::from_bytes::<u8>(bytes).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_token_transfer_action_signatures() {
+        let abi = query_abi(
+            "query_token_transfer_signatures",
+            option_abi(json!({"vector": {"vector": "u8"}})),
+        );
+        let result = execution_result(&abi, &json!([{"vec": [["0xaabb", "0xcc"]]}])).unwrap();
+        let (bytes, type_name) = &result.return_values[0];
+        assert_eq!(
+            type_name,
+            "0x00000000000000000000000000000001::Option::Option"
+        );
+        assert_eq!(
+            bcs::from_bytes::<Option<Vec<Vec<u8>>>>(bytes).unwrap(),
+            Some(vec![vec![0xaa, 0xbb], vec![0xcc]])
+        );
+
+        for none in [json!([{"vec": []}]), json!([null])] {
+            let result = execution_result(&abi, &none).unwrap();
+            assert_eq!(
+                bcs::from_bytes::<Option<Vec<Vec<u8>>>>(&result.return_values[0].0).unwrap(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_parsed_token_transfer_message() {
+        let payload_abi = json!({"struct": {
+            "name": "TokenTransferPayload",
+            "module_name": "0x0b8e0206e990e41e913a7f03d1c60675::Message",
+            "type_args": [],
+            "fields": [
+                {"name": "sender_address", "doc": "", "type_abi": {"vector": "u8"}},
+                {"name": "target_chain", "doc": "", "type_abi": "u8"},
+                {"name": "target_address", "doc": "", "type_abi": {"vector": "u8"}},
+                {"name": "token_type", "doc": "", "type_abi": "u8"},
+                {"name": "amount", "doc": "", "type_abi": "u64"}
+            ]
+        }});
+        let message_abi = json!({"struct": {
+            "name": "ParsedTokenTransferMessage",
+            "module_name": "0x0b8e0206e990e41e913a7f03d1c60675::Message",
+            "type_args": [],
+            "fields": [
+                {"name": "message_version", "doc": "", "type_abi": "u8"},
+                {"name": "seq_num", "doc": "", "type_abi": "u64"},
+                {"name": "source_chain", "doc": "", "type_abi": "u8"},
+                {"name": "payload", "doc": "", "type_abi": {"vector": "u8"}},
+                {"name": "parsed_payload", "doc": "", "type_abi": payload_abi}
+            ]
+        }});
+        let abi = query_abi("get_parsed_token_transfer_message", option_abi(message_abi));
+        let values = json!([{"vec": [{
+            "message_version": 1,
+            "seq_num": "7",
+            "source_chain": 2,
+            "payload": "0x0102",
+            "parsed_payload": {
+                "sender_address": "0x00000000000000000000000000000abc",
+                "target_chain": 12,
+                "target_address": [1, 2, 3],
+                "token_type": 3,
+                "amount": 1000
+            }
+        }]}]);
+        let result = execution_result(&abi, &values).unwrap();
+        assert_eq!(
+            bcs::from_bytes::<Option<MoveTypeParsedTokenTransferMessage>>(
+                &result.return_values[0].0
+            )
+            .unwrap(),
+            Some(MoveTypeParsedTokenTransferMessage {
+                message_version: 1,
+                seq_num: 7,
+                source_chain: 2,
+                payload: vec![1, 2],
+                parsed_payload: MoveTypeTokenTransferPayload {
+                    sender_address: hex::decode("00000000000000000000000000000abc").unwrap(),
+                    target_chain: 12,
+                    target_address: vec![1, 2, 3],
+                    token_type: 3,
+                    amount: 1000,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_dev_inspect_errors() {
+        // Only a single move call over inputs can be inspected
+        let TransactionKind::ProgrammableTransaction(mut pt) =
+            bridge_query("get_token_transfer_action_status", 12, 5);
+        pt.commands.push(pt.commands[0].clone());
+        bridge_call(&TransactionKind::programmable(pt.clone())).unwrap_err();
+        pt.commands.pop();
+        let Command::MoveCall(call) = &mut pt.commands[0] else {
+            unreachable!()
+        };
+        call.arguments.push(Argument::Result(0));
+        bridge_call(&TransactionKind::programmable(pt)).unwrap_err();
+
+        let abi = query_abi("query_token_transfer_status", json!("u8"));
+        // Argument count and encoding must match the abi
+        encode_args(&abi, &[vec![12]]).unwrap_err();
+        encode_args(&abi, &[vec![12], vec![5]]).unwrap_err();
+        // So must the returned values
+        execution_result(&abi, &json!([])).unwrap_err();
+        execution_result(&abi, &json!([256])).unwrap_err();
+        execution_result(&abi, &json!({"error": "abort"})).unwrap_err();
+    }
+}
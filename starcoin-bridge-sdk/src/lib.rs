@@ -2,32 +2,36 @@
 #![allow(dead_code, unused_variables, unused_imports)]
 
 use anyhow::Result;
-use starcoin_bridge_types::bridge::{BridgeSummary, BridgeTreasurySummary};
-use starcoin_bridge_vm_types::bridge::bridge::{BridgeCommitteeSummary, MoveTypeCommitteeMember};
+use sha3::{Digest, Sha3_256};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
-use starcoin_types::language_storage::StructTag;
-use std::str::FromStr;
 
-// Bridge module address on Starcoin
+// Default bridge module address on Starcoin, see
+// `StarcoinClientBuilder::bridge_address`
 const BRIDGE_ADDRESS: &str = "0x246b237c16c761e9478783dd83f7004a";
 const BRIDGE_MODULE: &str = "Bridge";
 const BRIDGE_RESOURCE: &str = "Bridge";
 
 // Sub-modules
 pub mod apis;
+pub mod bridge_state;
+mod dev_inspect;
 pub mod error;
+pub mod wallet_context;
 
 // StarcoinClient wraps Starcoin's RpcClient
 // Note: RpcClient doesn't implement Clone, so we wrap it in Arc
 pub struct StarcoinClient {
     client: std::sync::Arc<RpcClient>,
+    // Account the bridge modules and the `Bridge` resource are published at
+    bridge_address: AccountAddress,
 }
 
 impl Clone for StarcoinClient {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            bridge_address: self.bridge_address,
         }
     }
 }
@@ -37,9 +41,17 @@ impl StarcoinClient {
     pub fn new(client: RpcClient) -> Self {
         Self {
             client: std::sync::Arc::new(client),
+            // Unwrap safe: the default address is a valid literal
+            bridge_address: AccountAddress::from_hex_literal(BRIDGE_ADDRESS).unwrap(),
         }
     }
 
+    // Read the bridge state from `bridge_address` instead of the default
+    pub fn with_bridge_address(mut self, bridge_address: AccountAddress) -> Self {
+        self.bridge_address = bridge_address;
+        self
+    }
+
     // Create a new StarcoinClient by connecting to a WebSocket URL
     pub fn connect_websocket(url: &str) -> Result<Self> {
         let client = RpcClient::connect_websocket(url)?;
@@ -50,6 +62,7 @@ impl StarcoinClient {
     pub fn read_api(&self) -> ReadApi {
         ReadApi {
             client: self.client.clone(),
+            bridge_address: self.bridge_address,
         }
     }
 
@@ -57,12 +70,13 @@ impl StarcoinClient {
     pub fn governance_api(&self) -> GovernanceApi {
         GovernanceApi {
             client: self.client.clone(),
+            bridge_address: self.bridge_address,
         }
     }
 
     // Get coin read API interface
     pub fn coin_read_api(&self) -> apis::CoinReadApi {
-        apis::CoinReadApi::new()
+        apis::CoinReadApi::new(self.client.clone())
     }
 
     // Get event API interface
@@ -76,6 +90,7 @@ impl StarcoinClient {
     pub fn http(&self) -> BridgeReadApi {
         BridgeReadApi {
             client: self.client.clone(),
+            bridge_address: self.bridge_address,
         }
     }
 
@@ -101,6 +116,7 @@ impl std::fmt::Debug for StarcoinClient {
 // ReadApi provides read-only access to blockchain data
 pub struct ReadApi {
     client: std::sync::Arc<RpcClient>,
+    bridge_address: AccountAddress,
 }
 
 impl ReadApi {
@@ -111,29 +127,15 @@ impl ReadApi {
 
     // Get the latest checkpoint sequence number (block number in Starcoin)
     pub async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64> {
-        // Query latest block number from Starcoin node
-        let chain_info = self.client.chain_info()?;
-        Ok(chain_info.head.number.0)
+        head_block_number(&chain_info(&self.client).await?)
     }
 
     // Get bridge summary
     pub async fn get_bridge_summary(&self) -> Result<starcoin_bridge_types::bridge::BridgeSummary> {
-        // Query actual bridge state from Starcoin via RPC
-        let bridge_addr = AccountAddress::from_hex_literal(BRIDGE_ADDRESS)?;
-        let resource_type = format!("{}::{}::{}", BRIDGE_ADDRESS, BRIDGE_MODULE, BRIDGE_RESOURCE);
-        let struct_tag = StructTag::from_str(&resource_type)?;
-
-        let resource_opt = self.client.get_resource(bridge_addr, struct_tag)?;
-
-        if let Some(resource) = resource_opt {
-            // Parse the Bridge resource and convert to BridgeSummary
-            parse_bridge_summary_from_resource(resource)
-        } else {
-            anyhow::bail!("Bridge resource not found at address {}", BRIDGE_ADDRESS)
-        }
+        let bytes = bridge_resource(&self.client, self.bridge_address).await?;
+        bridge_state::bridge_summary_from_bcs(&bytes)
     }
 
-    // Dev inspect transaction block
     pub async fn dev_inspect_transaction_block(
         &self,
         sender: [u8; 32],
@@ -141,45 +143,135 @@ impl ReadApi {
         gas_price: Option<u64>,
         epoch: Option<u64>,
     ) -> Result<starcoin_bridge_json_rpc_types::DevInspectResults> {
-        // Starcoin uses contract.dry_run for transaction simulation
-        // Since we don't have the full transaction structure yet, return minimal results
-        // In a full implementation, we would:
-        // 1. Build a proper Starcoin transaction from tx_kind
-        // 2. Call contract.dry_run with the transaction
-        // 3. Parse the results into DevInspectResults
-        log::debug!("dev_inspect_transaction_block called - returning minimal results (full implementation requires transaction building)");
+        // The single bridge move call runs read-only through contract.call_v2,
+        // with the function abi typing its arguments and return values
+        let call = dev_inspect::bridge_call(&tx_kind)?;
+        let abi = self
+            .client
+            .call_raw_api(
+                "contract.resolve_function",
+                starcoin_rpc_client::Params::Array(vec![serde_json::Value::String(
+                    call.function_id.clone(),
+                )]),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve {}: {}", call.function_id, e))?;
+        let args = dev_inspect::encode_args(&abi, &call.args)?;
+        let values = self
+            .client
+            .call_raw_api(
+                "contract.call_v2",
+                starcoin_rpc_client::Params::Array(vec![serde_json::json!({
+                    "function_id": call.function_id,
+                    "type_args": call.type_args,
+                    "args": args,
+                })]),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to call {}: {}", call.function_id, e))?;
+        let result = dev_inspect::execution_result(&abi, &values)?;
         Ok(starcoin_bridge_json_rpc_types::DevInspectResults {
-            results: None,
+            results: Some(vec![result]),
             effects: None,
         })
     }
 
-    // Get chain identifier
+    // Get chain identifier, the node's numeric chain id, e.g. `254` for dev
     pub async fn get_chain_identifier(&self) -> Result<String> {
-        // Query chain info from Starcoin node
-        let node_info = self.client.node_info()?;
-        Ok(node_info.net.to_string())
+        chain_identifier(&chain_info(&self.client).await?)
     }
 
-    // Get object with options
+    // Get object with options. Starcoin has no objects, and the only ones the
+    // bridge asks about are gas, so `object_id` is read as the 32-byte form of
+    // an account address and its object is the account's STC balance, see
+    // `gas_object_data`. Accounts that don't exist have no object.
     pub async fn get_object_with_options(
         &self,
         object_id: [u8; 32],
         options: starcoin_bridge_json_rpc_types::StarcoinObjectDataOptions,
     ) -> Result<starcoin_bridge_json_rpc_types::StarcoinObjectResponse> {
-        let object_id_hex = hex::encode(object_id);
-        let options_json = serde_json::to_value(&options)?;
+        let client = self.client.clone();
+        let address = apis::to_account_address(object_id).to_hex_literal();
+        let (account, balance) = tokio::task::spawn_blocking(move || {
+            let get_resource = |resource_type: &str| {
+                client
+                    .call_raw_api(
+                        "state.get_resource",
+                        starcoin_rpc_client::Params::Array(vec![
+                            serde_json::json!(address),
+                            serde_json::json!(resource_type),
+                            serde_json::json!({ "decode": true }),
+                        ]),
+                    )
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to get {} of {}: {}", resource_type, address, e)
+                    })
+            };
+            Ok::<_, anyhow::Error>((
+                get_resource(ACCOUNT_RESOURCE)?,
+                get_resource(STC_BALANCE_RESOURCE)?,
+            ))
+        })
+        .await??;
+        Ok(starcoin_bridge_json_rpc_types::StarcoinObjectResponse {
+            data: gas_object_data(object_id, &account, &balance)?,
+        })
+    }
+}
+
+const ACCOUNT_RESOURCE: &str = "0x00000000000000000000000000000001::Account::Account";
+const STC_BALANCE_RESOURCE: &str =
+    "0x00000000000000000000000000000001::Account::Balance<0x00000000000000000000000000000001::STC::STC>";
+
+// The gas object of an account from its `Account` and STC `Balance`
+// resources, None if the account doesn't exist. The version is the account's
+// sequence number, which changes with every transaction it pays gas for, and
+// the digest is the SHA3-256 hash of the balance resource bytes. An account
+// without a balance resource has a zero balance, hashed as such.
+fn gas_object_data(
+    object_id: [u8; 32],
+    account: &serde_json::Value,
+    balance: &serde_json::Value,
+) -> Result<Option<starcoin_bridge_json_rpc_types::StarcoinObjectData>> {
+    if account.is_null() {
+        return Ok(None);
+    }
+    let sequence_number = account
+        .get("json")
+        .and_then(|json| json.get("sequence_number"))
+        .and_then(json_u64)
+        .ok_or_else(|| anyhow::anyhow!("No sequence number in account resource: {}", account))?;
+    let balance_bytes = resource_bytes(balance)?.unwrap_or_else(|| 0u128.to_le_bytes().to_vec());
+    // `Balance<STC>` is a `Token<STC>`, whose only field is its u128 value
+    let value: [u8; 16] = balance_bytes.as_slice().try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid STC balance resource: {}",
+            hex::encode(&balance_bytes)
+        )
+    })?;
+    let value = u128::from_le_bytes(value);
+    Ok(Some(starcoin_bridge_json_rpc_types::StarcoinObjectData {
+        object_id,
+        version: sequence_number,
+        digest: Sha3_256::digest(&balance_bytes).into(),
+        owner: Some(starcoin_bridge_json_rpc_types::Owner::AddressOwner(
+            object_id,
+        )),
+        gas_balance: Some(u64::try_from(value).unwrap_or(u64::MAX)),
+    }))
+}
 
-        // TODO: Implement actual Starcoin object query
-        // Starcoin doesn't have a direct object model like Sui
-        // Objects are represented as resources at account addresses
-        Ok(starcoin_bridge_json_rpc_types::StarcoinObjectResponse { data: None })
+// A u64 the node may render as a number or a string
+fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
     }
 }
 
 // GovernanceApi provides governance-related access
 pub struct GovernanceApi {
     client: std::sync::Arc<RpcClient>,
+    bridge_address: AccountAddress,
 }
 
 impl GovernanceApi {
@@ -195,11 +287,38 @@ impl GovernanceApi {
         // Query actual Starcoin epoch and chain info
         let chain_info = self.client.chain_info()?;
 
+        // Starcoin is PoW and has no validator set of its own, so the
+        // non-blocklisted bridge committee stands in for it. Members carry no
+        // on-chain name; their registered URL is used instead.
+        let summary = ReadApi {
+            client: self.client.clone(),
+            bridge_address: self.bridge_address,
+        }
+        .get_bridge_summary()
+        .await?;
+        let active_validators = summary
+            .committee
+            .members
+            .into_iter()
+            .filter(|(_, member)| !member.blocklisted)
+            .map(
+                |(pubkey, member)| starcoin_bridge_json_rpc_types::StarcoinValidatorSummary {
+                    starcoin_bridge_address:
+                        starcoin_bridge_types::base_types::starcoin_bridge_address_to_bytes(
+                            member.starcoin_bridge_address,
+                        ),
+                    protocol_pubkey_bytes: pubkey,
+                    name: String::from_utf8_lossy(&member.http_rest_url).into_owned(),
+                    voting_power: member.voting_power,
+                },
+            )
+            .collect();
+
         Ok(starcoin_bridge_json_rpc_types::StarcoinSystemStateSummary {
             epoch: chain_info.head.number.0, // Use block number as epoch
             protocol_version: 1,             // Starcoin doesn't expose protocol version in same way
             system_state_version: 1,
-            active_validators: vec![],
+            active_validators,
         })
     }
 
@@ -217,14 +336,17 @@ impl GovernanceApi {
         })
     }
 
-    // Get reference gas price
+    // Get reference gas price, the node's `txpool.gas_price` estimate in
+    // nanoSTC per gas unit
     pub async fn get_reference_gas_price(&self) -> Result<u64> {
-        // Query gas price from Starcoin node
-        // In Starcoin, gas price is in nanoSTC
-        let chain_info = self.client.chain_info()?;
-        // Use a default gas price based on the chain if available
-        // For now, return 1 nanoSTC as minimum
-        Ok(1)
+        let client = self.client.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            client
+                .call_raw_api("txpool.gas_price", starcoin_rpc_client::Params::None)
+                .map_err(|e| anyhow::anyhow!("Failed to get gas price: {}", e))
+        })
+        .await??;
+        gas_price(&response)
     }
 }
 
@@ -239,67 +361,215 @@ impl EventApi {
         &self.client
     }
 
-    // Query events
+    // Query events with `chain.get_events`. The node filters by type tag,
+    // emitting address and block range but has no cursor, so each call reads
+    // a window of at most `MAX_EVENT_BLOCK_RANGE` blocks starting at the
+    // cursor's block and drops the events already returned.
     pub async fn query_events(
         &self,
         query: starcoin_bridge_json_rpc_types::EventFilter,
-        cursor: Option<starcoin_bridge_types::event::EventID>,
+        cursor: Option<starcoin_bridge_json_rpc_types::EventCursor>,
         limit: Option<usize>,
         descending: bool,
     ) -> Result<starcoin_bridge_json_rpc_types::EventPage> {
-        // Starcoin event query would use chain.get_events or similar RPC methods
-        // To fully implement this, we need to:
-        // 1. Map EventFilter to Starcoin event query parameters
-        // 2. Query events from Starcoin node via RPC
-        // 3. Convert Starcoin events to bridge event format
-        // 4. Handle pagination with cursor
+        let position = cursor.map(|c| c.check(&query)).transpose()?;
+        let head = self.client.chain_info()?.head.number.0;
+        let Some(window) = event_window(&query, position, head, descending) else {
+            // Nothing past the cursor yet
+            return Ok(starcoin_bridge_json_rpc_types::EventPage {
+                data: vec![],
+                next_cursor: position
+                    .map(|p| starcoin_bridge_json_rpc_types::EventCursor::new(p, &query)),
+                has_next_page: false,
+            });
+        };
+
+        let mut filter = query.clone();
+        filter.from_block = Some(window.from_block);
+        filter.to_block = Some(window.to_block);
+        // The page limit is applied after dropping the events before the
+        // cursor, so the whole window is read
+        filter.limit = None;
+        filter.reverse = descending.then_some(true);
         log::debug!(
-            "query_events called with query: {:?}, cursor: {:?}",
-            query,
-            cursor
+            "Querying events in blocks {}..={}, cursor: {:?}",
+            window.from_block,
+            window.to_block,
+            position
         );
-
-        // For now, return empty results
-        // Full implementation requires mapping between Sui-style event filters and Starcoin events
-        Ok(starcoin_bridge_json_rpc_types::EventPage {
-            data: vec![],
-            next_cursor: None,
-            has_next_page: false,
-        })
+        let result = self.client.call_raw_api(
+            "chain.get_events",
+            starcoin_rpc_client::Params::Array(vec![filter.to_rpc_filter()]),
+        )?;
+        let views = match result {
+            serde_json::Value::Null => vec![],
+            serde_json::Value::Array(views) => views,
+            other => anyhow::bail!("Unexpected chain.get_events response: {}", other),
+        };
+        let events = views
+            .iter()
+            .map(event_from_view)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(page_events(
+            events,
+            &query,
+            position,
+            &window,
+            limit.or(query.limit),
+            descending,
+        ))
     }
 
-    // Get events by transaction digest
-    pub async fn get_events(
-        &self,
-        digest: &[u8; 32],
-    ) -> Result<Vec<starcoin_bridge_types::event::Event>> {
-        // Query transaction events from Starcoin using call_raw_api
-        // This avoids HashValue type conflicts between different crates
+    // Get the events emitted by a transaction, as the raw event views
+    // returned by `chain.get_events_by_txn_hash`. A transaction that is not
+    // found has no events.
+    pub async fn get_events(&self, digest: &[u8; 32]) -> Result<Vec<serde_json::Value>> {
+        // Use the raw API to avoid HashValue type conflicts between different crates
         let tx_hash_hex = format!("0x{}", hex::encode(digest));
-
-        // Use raw API call to get transaction info
-        let params = serde_json::json!([tx_hash_hex]);
         let result = self.client.call_raw_api(
-            "chain.get_transaction_info",
+            "chain.get_events_by_txn_hash",
             starcoin_rpc_client::Params::Array(vec![serde_json::Value::String(tx_hash_hex)]),
         )?;
+        match result {
+            serde_json::Value::Null => {
+                log::debug!("Transaction {} not found", hex::encode(digest));
+                Ok(vec![])
+            }
+            serde_json::Value::Array(events) => Ok(events),
+            other => Err(anyhow::anyhow!(
+                "Unexpected events response for transaction {}: {}",
+                hex::encode(digest),
+                other
+            )),
+        }
+    }
+}
+
+// Maximum block range of a `chain.get_events` query
+const MAX_EVENT_BLOCK_RANGE: u64 = 32;
+
+// Event sequence number of a cursor position meaning every event of the
+// block was returned, used when a window ends without filling the page.
+const WHOLE_BLOCK: u64 = u64::MAX;
 
-        // Check if transaction was found
-        if result.is_null() {
-            log::debug!("Transaction {} not found", hex::encode(digest));
-            return Ok(vec![]);
+// Blocks read by one event query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EventWindow {
+    from_block: u64,
+    to_block: u64,
+    // More blocks of the filter's range remain past the window
+    more_blocks: bool,
+}
+
+// The window to read after `position`, None when the range is exhausted.
+// Ascending windows start at the cursor's block and descending ones end at
+// it, since the rest of that block may not have been returned yet.
+fn event_window(
+    query: &starcoin_bridge_json_rpc_types::EventFilter,
+    position: Option<(u64, u64)>,
+    head: u64,
+    descending: bool,
+) -> Option<EventWindow> {
+    let lowest = query.from_block.unwrap_or(0);
+    let highest = query.to_block.map_or(head, |to| to.min(head));
+    if descending {
+        let to_block = position.map_or(highest, |(block, _)| block.min(highest));
+        if to_block < lowest {
+            return None;
+        }
+        let from_block = to_block
+            .saturating_sub(MAX_EVENT_BLOCK_RANGE - 1)
+            .max(lowest);
+        Some(EventWindow {
+            from_block,
+            to_block,
+            more_blocks: from_block > lowest,
+        })
+    } else {
+        let from_block = position.map_or(lowest, |(block, _)| block.max(lowest));
+        if from_block > highest {
+            return None;
         }
+        let to_block = from_block
+            .saturating_add(MAX_EVENT_BLOCK_RANGE - 1)
+            .min(highest);
+        Some(EventWindow {
+            from_block,
+            to_block,
+            more_blocks: to_block < highest,
+        })
+    }
+}
 
-        // TODO: Parse transaction info and extract events
-        // For now, return empty list as event conversion needs implementation
-        log::debug!(
-            "Found transaction {}, but event conversion not yet implemented",
-            hex::encode(digest)
-        );
-        Ok(vec![])
+// Turn the events of a window, in the order returned by the node, into a
+// page. Events up to and including the cursor are dropped: the ones of
+// earlier blocks, and those of the cursor's block up to the event with the
+// cursor's sequence number (all of them for `WHOLE_BLOCK`).
+fn page_events(
+    mut events: Vec<starcoin_bridge_json_rpc_types::StarcoinEvent>,
+    query: &starcoin_bridge_json_rpc_types::EventFilter,
+    position: Option<(u64, u64)>,
+    window: &EventWindow,
+    limit: Option<usize>,
+    descending: bool,
+) -> starcoin_bridge_json_rpc_types::EventPage {
+    if let Some((block, event_seq)) = position {
+        let seen = events
+            .iter()
+            .position(|e| e.id.block_number == block && e.id.event_seq == event_seq)
+            .map(|idx| idx + 1)
+            .unwrap_or_else(|| {
+                events
+                    .iter()
+                    .take_while(|e| {
+                        if descending {
+                            e.id.block_number >= block
+                        } else {
+                            e.id.block_number <= block
+                        }
+                    })
+                    .count()
+            });
+        events.drain(..seen);
+    }
+
+    let limit = limit.unwrap_or(usize::MAX).max(1);
+    let (next_position, has_next_page) = if events.len() > limit {
+        events.truncate(limit);
+        let last = &events[limit - 1].id;
+        ((last.block_number, last.event_seq), true)
+    } else {
+        let last_block = if descending {
+            window.from_block
+        } else {
+            window.to_block
+        };
+        ((last_block, WHOLE_BLOCK), window.more_blocks)
+    };
+    starcoin_bridge_json_rpc_types::EventPage {
+        data: events,
+        next_cursor: Some(starcoin_bridge_json_rpc_types::EventCursor::new(
+            next_position,
+            query,
+        )),
+        has_next_page,
     }
 }
 
+// Parse an event view returned by `chain.get_events`
+fn event_from_view(
+    view: &serde_json::Value,
+) -> Result<starcoin_bridge_json_rpc_types::StarcoinEvent> {
+    let tx_hash = view
+        .get("transaction_hash")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing transaction_hash in event"))?;
+    let tx_digest: [u8; 32] = hex::decode(tx_hash.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid transaction_hash in event: {}", tx_hash))?;
+    starcoin_bridge_json_rpc_types::StarcoinEvent::try_from_rpc_event(view, tx_digest)
+}
+
 // QuorumDriverApi provides quorum driver access
 pub struct QuorumDriverApi {
     client: std::sync::Arc<RpcClient>,
@@ -346,6 +616,7 @@ impl QuorumDriverApi {
 // BridgeReadApi provides bridge-specific read access
 pub struct BridgeReadApi {
     client: std::sync::Arc<RpcClient>,
+    bridge_address: AccountAddress,
 }
 
 impl BridgeReadApi {
@@ -354,67 +625,134 @@ impl BridgeReadApi {
         &self.client
     }
 
-    // Get chain identifier (returns network name)
+    // Get chain identifier, the node's numeric chain id
     pub async fn bridge_get_chain_identifier(&self) -> Result<String> {
-        // Query chain info from Starcoin node
-        let node_info = self.client.node_info()?;
-        Ok(node_info.net.to_string())
+        chain_identifier(&chain_info(&self.client).await?)
     }
 
     // Get latest checkpoint sequence number (equivalent to block number in Starcoin)
     pub async fn bridge_get_latest_checkpoint_sequence_number(&self) -> Result<u64> {
-        // Query latest block number from Starcoin
-        let chain_info = self.client.chain_info()?;
-        Ok(chain_info.head.number.0)
+        head_block_number(&chain_info(&self.client).await?)
+    }
+}
+
+// Read `chain.info` off the runtime's worker threads, since the RpcClient
+// blocks until the node answers
+async fn chain_info(client: &std::sync::Arc<RpcClient>) -> Result<serde_json::Value> {
+    let client = client.clone();
+    tokio::task::spawn_blocking(move || {
+        client
+            .call_raw_api("chain.info", starcoin_rpc_client::Params::None)
+            .map_err(|e| anyhow::anyhow!("Failed to get chain info: {}", e))
+    })
+    .await?
+}
+
+// The chain id of a `chain.info` response in the string form the bridge
+// uses for chain identifiers, e.g. `1` for main or `254` for dev
+fn chain_identifier(chain_info: &serde_json::Value) -> Result<String> {
+    chain_info
+        .get("chain_id")
+        .and_then(|id| id.as_u64())
+        .and_then(|id| u8::try_from(id).ok())
+        .map(|id| id.to_string())
+        .ok_or_else(|| anyhow::anyhow!("No valid chain_id in chain info: {}", chain_info))
+}
+
+// The head block number of a `chain.info` response. The node sends u64
+// values as strings.
+fn head_block_number(chain_info: &serde_json::Value) -> Result<u64> {
+    let number = chain_info.get("head").and_then(|head| head.get("number"));
+    number
+        .and_then(|n| n.as_u64().or_else(|| n.as_str()?.parse().ok()))
+        .ok_or_else(|| anyhow::anyhow!("No valid head block number in chain info: {:?}", number))
+}
+
+// A `txpool.gas_price` response, sent as a string like other u64 values
+fn gas_price(response: &serde_json::Value) -> Result<u64> {
+    response
+        .as_u64()
+        .or_else(|| response.as_str()?.parse().ok())
+        .filter(|price| *price > 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid gas price: {}", response))
+}
+
+// Read the BCS bytes of the `Bridge` resource published at `bridge_address`,
+// off the runtime's worker threads like `chain_info`
+async fn bridge_resource(
+    client: &std::sync::Arc<RpcClient>,
+    bridge_address: AccountAddress,
+) -> Result<Vec<u8>> {
+    let client = client.clone();
+    let address = bridge_address.to_hex_literal();
+    let resource_type = format!("{}::{}::{}", address, BRIDGE_MODULE, BRIDGE_RESOURCE);
+    let response = tokio::task::spawn_blocking(move || {
+        client
+            .call_raw_api(
+                "state.get_resource",
+                starcoin_rpc_client::Params::Array(vec![
+                    serde_json::json!(address),
+                    serde_json::json!(resource_type),
+                    serde_json::json!({ "decode": false }),
+                ]),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to get the Bridge resource: {}", e))
+    })
+    .await??;
+    resource_bytes(&response)?
+        .ok_or_else(|| anyhow::anyhow!("Bridge resource not found at address {}", bridge_address))
+}
+
+// The raw bytes of a `state.get_resource` response, None if the account has
+// no such resource
+fn resource_bytes(response: &serde_json::Value) -> Result<Option<Vec<u8>>> {
+    if response.is_null() {
+        return Ok(None);
     }
+    let raw = response
+        .get("raw")
+        .and_then(|raw| raw.as_str())
+        .ok_or_else(|| anyhow::anyhow!("No raw bytes in resource: {}", response))?;
+    let bytes = hex::decode(raw.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid raw bytes in resource: {}", e))?;
+    Ok(Some(bytes))
 }
 
 // Implement BridgeReadApiClient for BridgeReadApi
 #[async_trait::async_trait]
 impl starcoin_bridge_json_rpc_api::BridgeReadApiClient for BridgeReadApi {
+    // Starcoin has no shared objects, so the bridge is never re-shared and
+    // its "initial shared version" is always 1. Transactions don't use it.
     async fn get_bridge_object_initial_shared_version(&self) -> Result<u64, eyre::Error> {
-        // Starcoin doesn't have shared objects concept, return a fixed version
         Ok(1)
     }
 
+    // The bridge state as stored in the `Bridge` resource, read fresh from
+    // the node on every call
     async fn get_latest_bridge(
         &self,
     ) -> Result<starcoin_bridge_vm_types::bridge::bridge::BridgeSummary, eyre::Error> {
-        // Get the actual bridge summary from chain
-        let summary = self
-            .starcoin_client()
-            .get_resource(
-                AccountAddress::from_hex_literal(BRIDGE_ADDRESS)
-                    .map_err(|e| eyre::eyre!("Invalid bridge address: {}", e))?,
-                StructTag::from_str(&format!(
-                    "{}::{}::{}",
-                    BRIDGE_ADDRESS, BRIDGE_MODULE, BRIDGE_RESOURCE
-                ))
-                .map_err(|e| eyre::eyre!("Invalid struct tag: {}", e))?,
-            )
-            .map_err(|e| eyre::eyre!("Failed to get bridge resource: {}", e))?;
-
-        if let Some(resource) = summary {
-            parse_bridge_vm_summary_from_resource(resource)
-                .map_err(|e| eyre::eyre!("Failed to parse bridge summary: {}", e))
-        } else {
-            Err(eyre::eyre!(
-                "Bridge resource not found at address {}",
-                BRIDGE_ADDRESS
-            ))
-        }
+        let bytes = bridge_resource(&self.client, self.bridge_address)
+            .await
+            .map_err(|e| eyre::eyre!("{:#}", e))?;
+        bridge_state::bridge_summary_from_bcs(&bytes)
+            .map_err(|e| eyre::eyre!("Failed to parse bridge summary: {:#}", e))
     }
 }
 
 // StarcoinClientBuilder for constructing StarcoinClient instances
 pub struct StarcoinClientBuilder {
     url: Option<String>,
+    bridge_address: Option<String>,
 }
 
 impl StarcoinClientBuilder {
     // Create a new builder
     pub fn new() -> Self {
-        Self { url: None }
+        Self {
+            url: None,
+            bridge_address: None,
+        }
     }
 
     // Set the RPC URL
@@ -423,6 +761,33 @@ impl StarcoinClientBuilder {
         self
     }
 
+    // Set the address the bridge is published at, e.g. the configured
+    // `starcoin-bridge-proxy-address`. Defaults to the dev deployment's.
+    pub fn bridge_address(mut self, address: impl Into<String>) -> Self {
+        self.bridge_address = Some(address.into());
+        self
+    }
+
+    fn parsed_bridge_address(&self) -> Result<Option<AccountAddress>> {
+        self.bridge_address
+            .as_deref()
+            .map(|address| {
+                AccountAddress::from_hex_literal(address)
+                    .map_err(|e| anyhow::anyhow!("Invalid bridge address {}: {}", address, e))
+            })
+            .transpose()
+    }
+
+    fn with_bridge_address(
+        client: StarcoinClient,
+        bridge_address: Option<AccountAddress>,
+    ) -> StarcoinClient {
+        match bridge_address {
+            Some(address) => client.with_bridge_address(address),
+            None => client,
+        }
+    }
+
     // Build the StarcoinClient from a URL string (static method)
     pub fn build_from_url(url: impl AsRef<str>) -> Result<StarcoinClient> {
         let url_str = url.as_ref();
@@ -444,12 +809,17 @@ impl StarcoinClientBuilder {
 
     // Build with configured URL (instance method)
     pub fn build(self) -> Result<StarcoinClient> {
+        let bridge_address = self.parsed_bridge_address()?;
         let url = self.url.ok_or_else(|| anyhow::anyhow!("URL not set"))?;
-        Self::build_from_url(&url)
+        Ok(Self::with_bridge_address(
+            Self::build_from_url(&url)?,
+            bridge_address,
+        ))
     }
 
     // Async build method that properly handles blocking operations
     pub async fn build_async(self) -> Result<StarcoinClient> {
+        let bridge_address = self.parsed_bridge_address()?;
         let url = self.url.ok_or_else(|| anyhow::anyhow!("URL not set"))?;
         // Use Arc<Mutex<Option<Result>>> for thread-safe result sharing
         let result_holder = std::sync::Arc::new(std::sync::Mutex::new(None));
@@ -465,7 +835,7 @@ impl StarcoinClientBuilder {
             tokio::time::sleep(std::time::Duration::from_millis(10)).await;
             let mut guard = result_holder.lock().unwrap();
             if let Some(result) = guard.take() {
-                return result;
+                return result.map(|client| Self::with_bridge_address(client, bridge_address));
             }
         }
     }
@@ -477,419 +847,312 @@ impl Default for StarcoinClientBuilder {
     }
 }
 
-// Wallet context module
-pub mod wallet_context {
+#[cfg(test)]
+mod tests {
     use super::*;
-
-    // WalletContext wraps wallet functionality
-    #[derive(Default)]
-    pub struct WalletContext {
-        client: Option<StarcoinClient>,
-        addresses: Vec<[u8; 32]>,
-    }
-
-    impl WalletContext {
-        // Create a new wallet context
-        pub fn new() -> Result<Self> {
-            Ok(Self {
-                client: None,
-                addresses: Vec::new(),
-            })
-        }
-
-        // Get the client
-        pub fn get_client(&self) -> Result<&StarcoinClient> {
-            self.client
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("Client not set"))
+    use starcoin_bridge_json_rpc_types::{EventCursor, EventFilter, EventPage, StarcoinEvent};
+
+    // `chain.info` of a dev node, trimmed to the fields that are read
+    const CHAIN_INFO: &str = r#"{
+        "chain_id": 254,
+        "genesis_hash": "0x6e6e3be4e4e4d2e0a2b0e3c7c0a1d7a7b1f3d2c6a3b8e1d4c6f0a9b2c3d4e5f6",
+        "head": {
+            "timestamp": "1700000000000",
+            "author": "0x0000000000000000000000000a550c18",
+            "block_hash": "0x80848150abee7e9a3bfe9542a019eb0b8b01f124b63b011f9c338fdb935c417d",
+            "chain_id": 254,
+            "gas_used": "0",
+            "number": "1234",
+            "parent_hash": "0x2a5ed0e9df2e07bb7e3cf0e0de8e5c4b0b4c3b2a19080706050403020100ffee"
+        },
+        "block_info": {
+            "block_hash": "0x80848150abee7e9a3bfe9542a019eb0b8b01f124b63b011f9c338fdb935c417d",
+            "total_difficulty": "0x04d2"
         }
-
-        // Get addresses
-        pub fn get_addresses(&self) -> Vec<[u8; 32]> {
-            self.addresses.clone()
-        }
-
-        // Get one gas object owned by address
-        pub async fn get_one_gas_object_owned_by_address(
-            &self,
-            address: [u8; 32],
-        ) -> Result<Option<([u8; 32], u64, [u8; 32])>> {
-            // In Starcoin, gas is paid from account balance, not gas objects
-            // Return None to indicate gas objects don't exist in Starcoin model
-            log::warn!(
-                "get_one_gas_object_owned_by_address called for {:?} - Starcoin uses account balance for gas",
-                hex::encode(address)
-            );
-            Ok(None)
+    }"#;
+
+    #[test]
+    fn test_chain_info_fields() {
+        let chain_info: serde_json::Value = serde_json::from_str(CHAIN_INFO).unwrap();
+        assert_eq!(chain_identifier(&chain_info).unwrap(), "254");
+        assert_eq!(head_block_number(&chain_info).unwrap(), 1234);
+
+        // Numbers are accepted unquoted too
+        let chain_info = serde_json::json!({"chain_id": 1, "head": {"number": 7}});
+        assert_eq!(chain_identifier(&chain_info).unwrap(), "1");
+        assert_eq!(head_block_number(&chain_info).unwrap(), 7);
+
+        for chain_info in [
+            serde_json::json!({}),
+            serde_json::json!({"chain_id": 300, "head": {"number": "x"}}),
+            serde_json::json!({"chain_id": "dev", "head": {}}),
+        ] {
+            chain_identifier(&chain_info).unwrap_err();
+            head_block_number(&chain_info).unwrap_err();
         }
+    }
 
-        // Sign transaction
-        pub async fn sign_transaction(
-            &self,
-            tx_data: &starcoin_bridge_types::transaction::TransactionData,
-        ) -> Result<starcoin_bridge_types::crypto::Signature> {
-            // For now, return an empty signature
-            // Real implementation would need access to private keys
-            log::warn!(
-                "sign_transaction called - returning empty signature (needs keystore integration)"
-            );
-            Ok(starcoin_bridge_types::crypto::Signature(vec![]))
+    #[test]
+    fn test_gas_price() {
+        assert_eq!(gas_price(&serde_json::json!("1000")).unwrap(), 1000);
+        assert_eq!(gas_price(&serde_json::json!(7)).unwrap(), 7);
+        for response in [
+            serde_json::json!("0"),
+            serde_json::json!("x"),
+            serde_json::json!(-1),
+            serde_json::Value::Null,
+        ] {
+            gas_price(&response).unwrap_err();
         }
     }
-}
-
-//////////////////////////////////////////////////////
-// Helper functions to parse Bridge resources from Starcoin
-//
-
-use starcoin_rpc_api::types::AnnotatedMoveStructView;
-use starcoin_types::identifier::Identifier;
-
-// Helper to get field from Vec<(Identifier, AnnotatedMoveValueView)>
-fn get_field<'a>(
-    fields: &'a [(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    name: &str,
-) -> Option<&'a starcoin_rpc_api::types::AnnotatedMoveValueView> {
-    fields
-        .iter()
-        .find(|(id, _)| id.as_str() == name)
-        .map(|(_, v)| v)
-}
-
-// Parse BridgeSummary from Move resource (for starcoin_bridge_types)
-fn parse_bridge_summary_from_resource(
-    resource: AnnotatedMoveStructView,
-) -> Result<starcoin_bridge_types::bridge::BridgeSummary> {
-    use starcoin_bridge_types::bridge::*;
-
-    // The Bridge resource has an 'inner' field of type BridgeInner
-    let inner = get_field(&resource.value, "inner")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'inner' field in Bridge resource"))?;
-
-    let inner_struct = match inner {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'inner' to be a struct"),
-    };
-
-    // Extract fields from BridgeInner
-    let bridge_version = extract_u64(&inner_struct.value, "bridge_version")?;
-    let message_version = extract_u8(&inner_struct.value, "message_version")?;
-    let chain_id = extract_u8(&inner_struct.value, "chain_id")?;
-    let paused = extract_bool(&inner_struct.value, "paused")?;
-
-    // Parse committee
-    let committee = parse_committee(&inner_struct.value)?;
-
-    // Parse treasury
-    let treasury = BridgeTreasurySummary::default(); // TODO: parse treasury details
-
-    Ok(BridgeSummary {
-        committee,
-        treasury,
-        bridge_version,
-        message_version,
-        chain_id,
-        sequence_nums: Default::default(),
-        bridge_records_id: Default::default(),
-        limiter: Default::default(),
-        is_frozen: paused,
-    })
-}
-
-// Parse BridgeSummary from Move resource (for starcoin_bridge_vm_types)
-fn parse_bridge_vm_summary_from_resource(
-    resource: AnnotatedMoveStructView,
-) -> Result<starcoin_bridge_vm_types::bridge::bridge::BridgeSummary> {
-    use starcoin_bridge_vm_types::bridge::bridge::*;
-
-    let inner = get_field(&resource.value, "inner")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'inner' field in Bridge resource"))?;
-
-    let inner_struct = match inner {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'inner' to be a struct"),
-    };
-
-    let bridge_version = extract_u64(&inner_struct.value, "bridge_version")?;
-    let message_version = extract_u8(&inner_struct.value, "message_version")?;
-    let chain_id = extract_u8(&inner_struct.value, "chain_id")?;
-    let paused = extract_bool(&inner_struct.value, "paused")?;
-
-    let committee = parse_committee_vm(&inner_struct.value)?;
-    let treasury = BridgeTreasurySummary::default();
-
-    Ok(BridgeSummary {
-        committee,
-        treasury,
-        bridge_version,
-        message_version,
-        chain_id,
-        sequence_nums: Default::default(),
-        bridge_records_id: Default::default(),
-        limiter: Default::default(),
-        is_frozen: paused,
-    })
-}
 
-// Parse committee for starcoin_bridge_types
-fn parse_committee(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-) -> Result<starcoin_bridge_types::bridge::BridgeCommitteeSummary> {
-    use starcoin_bridge_types::bridge::*;
-
-    let committee_field = get_field(fields, "committee")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'committee' field"))?;
-
-    let committee_struct = match committee_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'committee' to be a struct"),
-    };
-
-    let last_committee_update_epoch =
-        extract_u64(&committee_struct.value, "last_committee_update_epoch")?;
-
-    // Parse members from SimpleMap - returns Vec<(Vec<u8>, MoveTypeCommitteeMember)>
-    let members = parse_committee_members(&committee_struct.value)?;
-
-    Ok(BridgeCommitteeSummary {
-        members,
-        member_registration: vec![],
-        last_committee_update_epoch,
-    })
-}
-
-// Parse committee for starcoin_bridge_vm_types
-fn parse_committee_vm(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-) -> Result<starcoin_bridge_vm_types::bridge::bridge::BridgeCommitteeSummary> {
-    use starcoin_bridge_vm_types::bridge::bridge::*;
-
-    let committee_field = get_field(fields, "committee")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'committee' field"))?;
-
-    let committee_struct = match committee_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'committee' to be a struct"),
-    };
-
-    let last_committee_update_epoch =
-        extract_u64(&committee_struct.value, "last_committee_update_epoch")?;
-
-    let members = parse_committee_members_vm(&committee_struct.value)?;
-
-    Ok(BridgeCommitteeSummary {
-        members,
-        member_registration: vec![],
-        last_committee_update_epoch,
-    })
-}
-
-// Parse committee members from SimpleMap for starcoin_bridge_types
-fn parse_committee_members(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-) -> Result<
-    Vec<(
-        Vec<u8>,
-        starcoin_bridge_vm_types::bridge::bridge::MoveTypeCommitteeMember,
-    )>,
-> {
-    let members_field =
-        get_field(fields, "members").ok_or_else(|| anyhow::anyhow!("Missing 'members' field"))?;
-
-    // SimpleMap is represented as a struct with 'data' field containing vector of key-value pairs
-    let members_struct = match members_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'members' to be a struct"),
-    };
+    #[test]
+    fn test_resource_bytes() {
+        let response = serde_json::json!({"raw": "0x0a0b", "json": null});
+        assert_eq!(resource_bytes(&response).unwrap(), Some(vec![0x0a, 0x0b]));
+        // Accounts without the resource
+        assert_eq!(resource_bytes(&serde_json::Value::Null).unwrap(), None);
+        resource_bytes(&serde_json::json!({"json": {}})).unwrap_err();
+        resource_bytes(&serde_json::json!({"raw": "0xzz"})).unwrap_err();
+    }
 
-    let data_field = get_field(&members_struct.value, "data")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in SimpleMap"))?;
+    #[test]
+    fn test_gas_object_data() {
+        let object_id = {
+            let mut id = [0u8; 32];
+            id[16..].copy_from_slice(&[0xab; 16]);
+            id
+        };
+        let account = serde_json::json!({"raw": "0x00", "json": {"sequence_number": "7"}});
+        let balance_bytes = 1_500_000_000u128.to_le_bytes();
+        let balance = serde_json::json!({
+            "raw": format!("0x{}", hex::encode(balance_bytes)),
+            "json": {"token": {"value": 1_500_000_000u64}},
+        });
+        let gas = gas_object_data(object_id, &account, &balance)
+            .unwrap()
+            .unwrap();
+        assert_eq!(gas.object_id, object_id);
+        assert_eq!(gas.version, 7);
+        assert_eq!(gas.gas_balance, Some(1_500_000_000));
+        assert_eq!(
+            gas.digest,
+            <[u8; 32]>::from(Sha3_256::digest(balance_bytes))
+        );
+        assert!(matches!(
+            gas.owner,
+            Some(starcoin_bridge_json_rpc_types::Owner::AddressOwner(owner)) if owner == object_id
+        ));
+        // The digest is stable across reads and follows the balance
+        let again = gas_object_data(object_id, &account, &balance)
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.digest, gas.digest);
+    }
 
-    let data_vec = match data_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Vector(v) => v,
-        _ => anyhow::bail!("Expected 'data' to be a vector"),
-    };
+    #[test]
+    fn test_gas_object_data_not_found() {
+        let balance = serde_json::Value::Null;
+        assert!(
+            gas_object_data([1u8; 32], &serde_json::Value::Null, &balance)
+                .unwrap()
+                .is_none()
+        );
+        // An account resource without a sequence number is malformed
+        gas_object_data([1u8; 32], &serde_json::json!({"json": {}}), &balance).unwrap_err();
+    }
 
-    let mut members = Vec::new();
-    for entry in data_vec {
-        if let starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(entry_struct) = entry {
-            // Each entry has 'key' (pubkey bytes) and 'value' (CommitteeMember)
-            let key_field = get_field(&entry_struct.value, "key")
-                .ok_or_else(|| anyhow::anyhow!("Missing 'key' field in map entry"))?;
-            let pubkey = extract_bytes_from_value(key_field)?;
-
-            let value_field = get_field(&entry_struct.value, "value")
-                .ok_or_else(|| anyhow::anyhow!("Missing 'value' field in map entry"))?;
-
-            if let starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(member_struct) =
-                value_field
-            {
-                let member = parse_committee_member_vm(&member_struct.value)?;
-                members.push((pubkey, member));
-            }
-        }
+    #[test]
+    fn test_gas_object_data_zero_balance() {
+        let account = serde_json::json!({"json": {"sequence_number": 0}});
+        // Accounts that never held STC have no balance resource
+        let missing = gas_object_data([2u8; 32], &account, &serde_json::Value::Null)
+            .unwrap()
+            .unwrap();
+        assert_eq!(missing.gas_balance, Some(0));
+        assert_eq!(missing.version, 0);
+        // and hash like an emptied balance
+        let zero = serde_json::json!({"raw": format!("0x{}", hex::encode(0u128.to_le_bytes()))});
+        let emptied = gas_object_data([2u8; 32], &account, &zero)
+            .unwrap()
+            .unwrap();
+        assert_eq!(emptied.gas_balance, Some(0));
+        assert_eq!(emptied.digest, missing.digest);
+
+        gas_object_data([2u8; 32], &account, &serde_json::json!({"raw": "0x01"})).unwrap_err();
     }
 
-    Ok(members)
-}
+    #[test]
+    fn test_builder_bridge_address() {
+        let builder = StarcoinClientBuilder::new().bridge_address("0x1");
+        assert_eq!(
+            builder.parsed_bridge_address().unwrap(),
+            Some(AccountAddress::ONE)
+        );
+        assert_eq!(
+            StarcoinClientBuilder::new()
+                .parsed_bridge_address()
+                .unwrap(),
+            None
+        );
+        StarcoinClientBuilder::new()
+            .bridge_address("bridge")
+            .parsed_bridge_address()
+            .unwrap_err();
+    }
 
-// Parse committee members for starcoin_bridge_vm_types
-fn parse_committee_members_vm(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-) -> Result<
-    Vec<(
-        Vec<u8>,
-        starcoin_bridge_vm_types::bridge::bridge::MoveTypeCommitteeMember,
-    )>,
-> {
-    let members_field =
-        get_field(fields, "members").ok_or_else(|| anyhow::anyhow!("Missing 'members' field"))?;
-
-    let members_struct = match members_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(s) => s,
-        _ => anyhow::bail!("Expected 'members' to be a struct"),
-    };
+    const EVENT_TYPE: &str = "0x246b237c16c761e9478783dd83f7004a::Bridge::TokenDepositedEvent";
 
-    let data_field = get_field(&members_struct.value, "data")
-        .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in SimpleMap"))?;
+    fn view(block_number: u64, event_seq: u64) -> serde_json::Value {
+        serde_json::json!({
+            "block_number": block_number.to_string(),
+            "transaction_hash": format!("0x{}", hex::encode([block_number as u8; 32])),
+            "event_seq_number": event_seq.to_string(),
+            "type_tag": EVENT_TYPE,
+            "data": "0x0102",
+        })
+    }
 
-    let data_vec = match data_field {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Vector(v) => v,
-        _ => anyhow::bail!("Expected 'data' to be a vector"),
-    };
+    // Two events in each of blocks 1, 2, 3 and 40, one in block 41
+    fn chain() -> Vec<StarcoinEvent> {
+        [
+            (1, 0),
+            (1, 1),
+            (2, 2),
+            (2, 3),
+            (3, 4),
+            (3, 5),
+            (40, 6),
+            (40, 7),
+            (41, 8),
+        ]
+        .into_iter()
+        .map(|(block, seq)| event_from_view(&view(block, seq)).unwrap())
+        .collect()
+    }
 
-    let mut members = Vec::new();
-    for entry in data_vec {
-        if let starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(entry_struct) = entry {
-            let key_field = get_field(&entry_struct.value, "key")
-                .ok_or_else(|| anyhow::anyhow!("Missing 'key' field in map entry"))?;
-            let pubkey = extract_bytes_from_value(key_field)?;
-
-            let value_field = get_field(&entry_struct.value, "value")
-                .ok_or_else(|| anyhow::anyhow!("Missing 'value' field in map entry"))?;
-
-            if let starcoin_rpc_api::types::AnnotatedMoveValueView::Struct(member_struct) =
-                value_field
-            {
-                let member = parse_committee_member_vm(&member_struct.value)?;
-                members.push((pubkey, member));
-            }
+    // `EventApi::query_events` against a node holding the events of `chain`
+    fn query(
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: usize,
+        descending: bool,
+    ) -> EventPage {
+        let position = cursor.map(|c| c.check(filter).unwrap());
+        let window = event_window(filter, position, 50, descending).unwrap();
+        let mut events: Vec<_> = chain()
+            .into_iter()
+            .filter(|e| (window.from_block..=window.to_block).contains(&e.id.block_number))
+            .collect();
+        if descending {
+            events.reverse();
         }
+        page_events(events, filter, position, &window, Some(limit), descending)
     }
 
-    Ok(members)
-}
-
-// Parse individual committee member for starcoin_bridge_vm_types
-fn parse_committee_member_vm(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-) -> Result<starcoin_bridge_vm_types::bridge::bridge::MoveTypeCommitteeMember> {
-    use move_core_types::account_address::AccountAddress as MoveAccountAddress;
-    use starcoin_bridge_vm_types::bridge::bridge::*;
-
-    let starcoin_address = extract_address(fields, "starcoin_address")?;
-    let bridge_pubkey_bytes = extract_bytes(fields, "bridge_pubkey_bytes")?;
-    let voting_power = extract_u64(fields, "voting_power")?;
-    let http_rest_url = extract_bytes(fields, "http_rest_url")?;
-    let blocklisted = extract_bool(fields, "blocklisted")?;
-
-    // Convert AccountAddress to MoveAccountAddress (16 bytes)
-    let move_addr = MoveAccountAddress::from_bytes(starcoin_address.as_ref())
-        .unwrap_or(MoveAccountAddress::ZERO);
-
-    Ok(MoveTypeCommitteeMember {
-        starcoin_bridge_address: move_addr,
-        bridge_pubkey_bytes,
-        voting_power,
-        http_rest_url,
-        blocklisted,
-    })
-}
-
-// Extract u64 from Move value
-fn extract_u64(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    field_name: &str,
-) -> Result<u64> {
-    let value = get_field(fields, field_name)
-        .ok_or_else(|| anyhow::anyhow!("Missing '{}' field", field_name))?;
-
-    match value {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::U64(v) => Ok(v.0),
-        _ => anyhow::bail!("Expected '{}' to be u64, got {:?}", field_name, value),
+    fn seqs(page: &EventPage) -> Vec<u64> {
+        page.data.iter().map(|e| e.id.event_seq).collect()
     }
-}
 
-// Extract u8 from Move value
-fn extract_u8(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    field_name: &str,
-) -> Result<u8> {
-    let value = get_field(fields, field_name)
-        .ok_or_else(|| anyhow::anyhow!("Missing '{}' field", field_name))?;
+    #[test]
+    fn test_event_from_view() {
+        let event = event_from_view(&view(7, 3)).unwrap();
+        assert_eq!(event.id.block_number, 7);
+        assert_eq!(event.id.event_seq, 3);
+        assert_eq!(event.id.tx_digest, [7u8; 32]);
+        assert_eq!(event.type_.to_string(), EVENT_TYPE);
+        assert_eq!(event.bcs, vec![1, 2]);
+    }
 
-    match value {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::U8(v) => Ok(*v),
-        _ => anyhow::bail!("Expected '{}' to be u8, got {:?}", field_name, value),
+    #[test]
+    fn test_query_events_pages() {
+        let filter = EventFilter::move_event_type(EVENT_TYPE);
+
+        let page = query(&filter, None, 3, false);
+        assert_eq!(seqs(&page), vec![0, 1, 2]);
+        assert!(page.has_next_page);
+
+        // The second page resumes in the middle of block 2 and ends with
+        // the window, past which there are more blocks
+        let page = query(&filter, page.next_cursor, 3, false);
+        assert_eq!(seqs(&page), vec![3, 4, 5]);
+        assert!(page.has_next_page);
+        assert_eq!(page.next_cursor.unwrap().position(), (33, WHOLE_BLOCK));
+
+        let page = query(&filter, page.next_cursor, 3, false);
+        assert_eq!(seqs(&page), vec![6, 7, 8]);
+        assert!(!page.has_next_page);
+        assert_eq!(page.next_cursor.unwrap().position(), (50, WHOLE_BLOCK));
     }
-}
 
-// Extract bool from Move value
-fn extract_bool(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    field_name: &str,
-) -> Result<bool> {
-    let value = get_field(fields, field_name)
-        .ok_or_else(|| anyhow::anyhow!("Missing '{}' field", field_name))?;
+    #[test]
+    fn test_query_events_descending_pages() {
+        let filter = EventFilter {
+            from_block: Some(1),
+            to_block: Some(3),
+            ..EventFilter::move_event_type(EVENT_TYPE)
+        };
 
-    match value {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Bool(v) => Ok(*v),
-        _ => anyhow::bail!("Expected '{}' to be bool, got {:?}", field_name, value),
-    }
-}
+        let page = query(&filter, None, 2, true);
+        assert_eq!(seqs(&page), vec![5, 4]);
+        assert!(page.has_next_page);
 
-// Extract bytes from Move value by field name
-fn extract_bytes(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    field_name: &str,
-) -> Result<Vec<u8>> {
-    let value = get_field(fields, field_name)
-        .ok_or_else(|| anyhow::anyhow!("Missing '{}' field", field_name))?;
-    extract_bytes_from_value(value)
-}
+        let page = query(&filter, page.next_cursor, 2, true);
+        assert_eq!(seqs(&page), vec![3, 2]);
+        assert!(page.has_next_page);
 
-// Extract bytes from a Move value
-fn extract_bytes_from_value(
-    value: &starcoin_rpc_api::types::AnnotatedMoveValueView,
-) -> Result<Vec<u8>> {
-    match value {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Bytes(v) => Ok(v.0.clone()),
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Vector(v) => {
-            // Vector of u8
-            let mut bytes = Vec::new();
-            for item in v {
-                if let starcoin_rpc_api::types::AnnotatedMoveValueView::U8(byte) = item {
-                    bytes.push(*byte);
-                } else {
-                    anyhow::bail!("Expected vector of u8, got {:?}", item);
-                }
-            }
-            Ok(bytes)
-        }
-        _ => anyhow::bail!("Expected bytes or vector<u8>, got {:?}", value),
+        let page = query(&filter, page.next_cursor, 2, true);
+        assert_eq!(seqs(&page), vec![1, 0]);
+        assert!(!page.has_next_page);
     }
-}
 
-// Extract address from Move value
-fn extract_address(
-    fields: &[(Identifier, starcoin_rpc_api::types::AnnotatedMoveValueView)],
-    field_name: &str,
-) -> Result<AccountAddress> {
-    let value = get_field(fields, field_name)
-        .ok_or_else(|| anyhow::anyhow!("Missing '{}' field", field_name))?;
+    #[test]
+    fn test_event_window() {
+        let filter = EventFilter::default();
+        assert_eq!(
+            event_window(&filter, None, 100, false),
+            Some(EventWindow {
+                from_block: 0,
+                to_block: 31,
+                more_blocks: true
+            })
+        );
+        // Resumes at the cursor's block, bounded by the head
+        assert_eq!(
+            event_window(&filter, Some((90, 1)), 100, false),
+            Some(EventWindow {
+                from_block: 90,
+                to_block: 100,
+                more_blocks: false
+            })
+        );
+        assert_eq!(event_window(&filter, Some((101, 0)), 100, false), None);
+        assert_eq!(
+            event_window(&filter, None, 100, true),
+            Some(EventWindow {
+                from_block: 69,
+                to_block: 100,
+                more_blocks: true
+            })
+        );
+        let bounded = EventFilter::block_range(10, 20);
+        assert_eq!(event_window(&bounded, Some((9, 0)), 100, true), None);
+    }
 
-    match value {
-        starcoin_rpc_api::types::AnnotatedMoveValueView::Address(addr) => Ok(*addr),
-        _ => anyhow::bail!("Expected '{}' to be address, got {:?}", field_name, value),
+    #[test]
+    fn test_query_events_rpc_filter() {
+        let mut filter = EventFilter::move_event_module("0x246b237c16c761e9478783dd83f7004a");
+        filter.from_block = Some(5);
+        filter.to_block = Some(36);
+        filter.reverse = Some(true);
+        assert_eq!(
+            filter.to_rpc_filter(),
+            serde_json::json!({
+                "from_block": 5,
+                "to_block": 36,
+                "addrs": ["0x246b237c16c761e9478783dd83f7004a"],
+                "reverse": true,
+            })
+        );
     }
 }
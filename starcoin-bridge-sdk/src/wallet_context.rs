@@ -0,0 +1,297 @@
+// Keystore-backed wallet. Keys are read from a directory of key files, one
+// key per file, in any format `keypair_file::read_key` accepts.
+
+use crate::StarcoinClient;
+use anyhow::{anyhow, Context, Result};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use starcoin_bridge_keys::keypair_file;
+use starcoin_bridge_types::base_types::starcoin_bridge_address_to_bytes;
+use starcoin_bridge_types::crypto::{Signature, StarcoinKeyPair};
+use starcoin_bridge_types::transaction::{RawUserTransaction, TransactionData};
+use std::path::Path;
+
+// Starcoin signs the BCS bytes of a transaction prefixed by the hash of its
+// type salt, so that signatures can't be replayed as another type
+const RAW_USER_TRANSACTION_SALT: &[u8] = b"STARCOIN::RawUserTransaction";
+
+// The authenticator submitted along with a signed transaction. The Ed25519
+// variant has the BCS layout of Starcoin's own `TransactionAuthenticator`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionAuthenticator {
+    Ed25519 {
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    },
+    Secp256k1 {
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    },
+    Secp256r1 {
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    },
+}
+
+impl TransactionAuthenticator {
+    pub fn public_key(&self) -> &[u8] {
+        match self {
+            Self::Ed25519 { public_key, .. }
+            | Self::Secp256k1 { public_key, .. }
+            | Self::Secp256r1 { public_key, .. } => public_key,
+        }
+    }
+
+    // Check the signature over `raw_txn` against the public key
+    pub fn verify(&self, raw_txn: &RawUserTransaction) -> Result<()> {
+        let message = signing_message(raw_txn)?;
+        let result = match self {
+            Self::Ed25519 {
+                public_key,
+                signature,
+            } => fastcrypto::ed25519::Ed25519PublicKey::from_bytes(public_key).and_then(|pk| {
+                pk.verify(
+                    &message,
+                    &fastcrypto::ed25519::Ed25519Signature::from_bytes(signature)?,
+                )
+            }),
+            Self::Secp256k1 {
+                public_key,
+                signature,
+            } => fastcrypto::secp256k1::Secp256k1PublicKey::from_bytes(public_key).and_then(|pk| {
+                pk.verify(
+                    &message,
+                    &fastcrypto::secp256k1::Secp256k1Signature::from_bytes(signature)?,
+                )
+            }),
+            Self::Secp256r1 {
+                public_key,
+                signature,
+            } => fastcrypto::secp256r1::Secp256r1PublicKey::from_bytes(public_key).and_then(|pk| {
+                pk.verify(
+                    &message,
+                    &fastcrypto::secp256r1::Secp256r1Signature::from_bytes(signature)?,
+                )
+            }),
+        };
+        result.map_err(|e| anyhow!("Invalid transaction signature: {}", e))
+    }
+}
+
+// The bytes a key signs for `raw_txn`
+pub fn signing_message(raw_txn: &RawUserTransaction) -> Result<Vec<u8>> {
+    let mut message = Sha3_256::digest(RAW_USER_TRANSACTION_SALT).to_vec();
+    message.extend(bcs::to_bytes(raw_txn).context("Failed to serialize transaction")?);
+    Ok(message)
+}
+
+// WalletContext wraps wallet functionality
+#[derive(Default)]
+pub struct WalletContext {
+    client: Option<StarcoinClient>,
+    keys: Vec<StarcoinKeyPair>,
+    addresses: Vec<[u8; 32]>,
+}
+
+impl WalletContext {
+    // Create a new wallet context
+    pub fn new() -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    // Create a wallet holding every key in `path`
+    pub fn from_key_dir(path: &Path, client: StarcoinClient) -> Result<Self> {
+        let mut wallet = Self::with_keys(read_key_dir(path)?);
+        wallet.client = Some(client);
+        Ok(wallet)
+    }
+
+    fn with_keys(keys: Vec<StarcoinKeyPair>) -> Self {
+        let addresses = keys
+            .iter()
+            .map(|key| starcoin_bridge_address_to_bytes(key.starcoin_address()))
+            .collect();
+        Self {
+            client: None,
+            keys,
+            addresses,
+        }
+    }
+
+    // Get the client
+    pub fn get_client(&self) -> Result<&StarcoinClient> {
+        self.client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Client not set"))
+    }
+
+    // Get addresses
+    pub fn get_addresses(&self) -> Vec<[u8; 32]> {
+        self.addresses.clone()
+    }
+
+    // Get one gas object owned by address. Starcoin pays gas from the STC
+    // balance of the account, so the account itself stands in for the gas
+    // object when it has any balance.
+    pub async fn get_one_gas_object_owned_by_address(
+        &self,
+        address: [u8; 32],
+    ) -> Result<Option<([u8; 32], u64, [u8; 32])>> {
+        let balance = self
+            .get_client()?
+            .coin_read_api()
+            .get_balance(address, None)
+            .await?;
+        if balance == 0 {
+            return Ok(None);
+        }
+        Ok(Some((
+            address,
+            u64::try_from(balance).unwrap_or(u64::MAX),
+            [0u8; 32],
+        )))
+    }
+
+    // Sign a transaction with the key of its sender
+    pub fn sign_raw_transaction(
+        &self,
+        raw_txn: &RawUserTransaction,
+    ) -> Result<TransactionAuthenticator> {
+        let sender = starcoin_bridge_address_to_bytes(raw_txn.sender);
+        let key = self
+            .addresses
+            .iter()
+            .position(|address| *address == sender)
+            .map(|i| &self.keys[i])
+            .ok_or_else(|| anyhow!("No key for sender 0x{}", hex::encode(sender)))?;
+        let (public_key, signature) = key.sign_message(&signing_message(raw_txn)?);
+        Ok(match key {
+            StarcoinKeyPair::Ed25519(_) => TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            },
+            StarcoinKeyPair::Secp256k1(_) => TransactionAuthenticator::Secp256k1 {
+                public_key,
+                signature,
+            },
+            StarcoinKeyPair::Secp256r1(_) => TransactionAuthenticator::Secp256r1 {
+                public_key,
+                signature,
+            },
+        })
+    }
+
+    // Sign transaction. The signature holds the BCS bytes of the authenticator.
+    pub async fn sign_transaction(&self, tx_data: &TransactionData) -> Result<Signature> {
+        let raw_txn = tx_data
+            .inner
+            .as_ref()
+            .ok_or_else(|| anyhow!("Transaction data has no transaction to sign"))?;
+        let authenticator = self.sign_raw_transaction(raw_txn)?;
+        Ok(Signature(bcs::to_bytes(&authenticator)?))
+    }
+}
+
+// Read every key file in `path`, ordered by file name
+fn read_key_dir(path: &Path) -> Result<Vec<StarcoinKeyPair>> {
+    let mut files = std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read key directory {:?}", path))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    files.retain(|file| file.is_file());
+    files.sort();
+    if files.is_empty() {
+        return Err(anyhow!("No key files in {:?}", path));
+    }
+    files
+        .iter()
+        .map(|file| keypair_file::read_key(file, false))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use fastcrypto::traits::EncodeDecodeBase64;
+    use starcoin_bridge_types::crypto::get_key_pair;
+    use starcoin_bridge_types::transaction::{ChainId, TransactionPayload};
+
+    fn raw_txn(key: &StarcoinKeyPair) -> RawUserTransaction {
+        RawUserTransaction {
+            sender: key.starcoin_address(),
+            sequence_number: 3,
+            payload: TransactionPayload::Script(vec![1, 2, 3]),
+            max_gas_amount: 10_000_000,
+            gas_unit_price: 1,
+            gas_token_code: "0x1::STC::STC".to_string(),
+            expiration_timestamp_secs: 1_000,
+            chain_id: ChainId(254),
+        }
+    }
+
+    // A key directory with an Ed25519 key and a Secp256k1 key
+    fn key_dir() -> (tempfile::TempDir, Vec<StarcoinKeyPair>) {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = vec![
+            StarcoinKeyPair::Ed25519(get_key_pair::<Ed25519KeyPair>().1),
+            StarcoinKeyPair::Secp256k1(get_key_pair::<Secp256k1KeyPair>().1),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            std::fs::write(dir.path().join(format!("{i}.key")), key.encode_base64()).unwrap();
+        }
+        (dir, keys)
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction() {
+        let (dir, keys) = key_dir();
+        let wallet = WalletContext::with_keys(read_key_dir(dir.path()).unwrap());
+        assert_eq!(
+            wallet.get_addresses(),
+            keys.iter()
+                .map(|key| starcoin_bridge_address_to_bytes(key.starcoin_address()))
+                .collect::<Vec<_>>()
+        );
+
+        for key in &keys {
+            let raw_txn = raw_txn(key);
+            let signature = wallet
+                .sign_transaction(&TransactionData {
+                    inner: Some(raw_txn.clone()),
+                })
+                .await
+                .unwrap();
+            let authenticator: TransactionAuthenticator = bcs::from_bytes(&signature.0).unwrap();
+            assert_eq!(authenticator.public_key(), key.public());
+            authenticator.verify(&raw_txn).unwrap();
+
+            // The signature doesn't cover another transaction
+            let mut other = raw_txn.clone();
+            other.sequence_number += 1;
+            assert!(authenticator.verify(&other).is_err());
+        }
+        assert!(matches!(
+            wallet.sign_raw_transaction(&raw_txn(&keys[1])).unwrap(),
+            TransactionAuthenticator::Secp256k1 { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_errors() {
+        let (dir, _) = key_dir();
+        let wallet = WalletContext::with_keys(read_key_dir(dir.path()).unwrap());
+        // No transaction
+        assert!(wallet
+            .sign_transaction(&TransactionData { inner: None })
+            .await
+            .is_err());
+        // No key for the sender
+        let stranger = StarcoinKeyPair::Ed25519(get_key_pair::<Ed25519KeyPair>().1);
+        assert!(wallet.sign_raw_transaction(&raw_txn(&stranger)).is_err());
+        // No keys
+        assert!(read_key_dir(tempfile::tempdir().unwrap().path()).is_err());
+    }
+}
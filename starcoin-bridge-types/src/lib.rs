@@ -107,6 +107,7 @@ pub mod crypto {
         ed25519::Ed25519KeyPair,
         error::FastCryptoError,
         secp256k1::Secp256k1KeyPair,
+        secp256r1::Secp256r1KeyPair,
         traits::{EncodeDecodeBase64, KeyPair as KeypairTraits, ToFromBytes},
     };
     use serde::{Deserialize, Serialize};
@@ -140,14 +141,13 @@ pub mod crypto {
     // Re-export Secp256k1PublicKey for convenience
     pub use fastcrypto::secp256k1::Secp256k1PublicKey;
 
-    // Define StarcoinKeyPair enum (simplified - only Ed25519 and Secp256k1)
+    // Define StarcoinKeyPair enum (simplified - Ed25519, Secp256k1 and Secp256r1)
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(tag = "type")]
     pub enum StarcoinKeyPair {
         Ed25519(Ed25519KeyPair),
         Secp256k1(Secp256k1KeyPair),
-        // TODO: Add Secp256r1 support when fastcrypto adds it
-        // Secp256r1(Secp256r1KeyPair),
+        Secp256r1(Secp256r1KeyPair),
     }
 
     impl StarcoinKeyPair {
@@ -156,38 +156,15 @@ pub mod crypto {
             match self {
                 StarcoinKeyPair::Ed25519(kp) => kp.public().as_bytes().to_vec(),
                 StarcoinKeyPair::Secp256k1(kp) => kp.public().as_bytes().to_vec(),
+                StarcoinKeyPair::Secp256r1(kp) => kp.public().as_bytes().to_vec(),
             }
         }
 
-        /// Derive Starcoin account address from keypair's public key.
-        ///
-        /// The address derivation follows Starcoin's algorithm:
-        /// 1. Create preimage: pubkey_bytes || scheme_flag (0x00 for Ed25519)
-        /// 2. Hash with SHA3-256 to get AuthenticationKey (32 bytes)
-        /// 3. Take the last 16 bytes as the AccountAddress
+        /// Derive Starcoin account address from keypair's public key, see
+        /// `starcoin_address_from_public_key`. Only the address of an Ed25519
+        /// key pair is an account on chain.
         pub fn starcoin_address(&self) -> move_core_types::account_address::AccountAddress {
-            use sha3::{Digest, Sha3_256};
-
-            // Get public key bytes
-            let pubkey_bytes = self.public();
-
-            // Create preimage: pubkey || scheme_flag
-            // Ed25519 = 0x00, Secp256k1 = 0x01
-            let scheme_flag: u8 = match self {
-                StarcoinKeyPair::Ed25519(_) => 0x00,
-                StarcoinKeyPair::Secp256k1(_) => 0x01,
-            };
-            let mut preimage = pubkey_bytes;
-            preimage.push(scheme_flag);
-
-            // Hash with SHA3-256
-            let hash = Sha3_256::digest(&preimage);
-
-            // Take last 16 bytes as address
-            let mut addr_bytes = [0u8; 16];
-            addr_bytes.copy_from_slice(&hash[16..32]);
-
-            move_core_types::account_address::AccountAddress::new(addr_bytes)
+            starcoin_address_from_public_key(&self.public(), self.scheme_flag())
         }
 
         /// Sign a message and return (public_key, signature) bytes
@@ -205,6 +182,10 @@ pub mod crypto {
                     let sig = fastcrypto::traits::Signer::<fastcrypto::secp256k1::Secp256k1Signature>::sign(kp, msg);
                     (kp.public().as_bytes().to_vec(), sig.as_bytes().to_vec())
                 }
+                StarcoinKeyPair::Secp256r1(kp) => {
+                    let sig = fastcrypto::traits::Signer::<fastcrypto::secp256r1::Secp256r1Signature>::sign(kp, msg);
+                    (kp.public().as_bytes().to_vec(), sig.as_bytes().to_vec())
+                }
             }
         }
 
@@ -213,6 +194,7 @@ pub mod crypto {
             match self {
                 StarcoinKeyPair::Ed25519(kp) => kp.as_bytes()[..32].to_vec(), // Ed25519 private key is first 32 bytes
                 StarcoinKeyPair::Secp256k1(kp) => kp.as_bytes().to_vec(),
+                StarcoinKeyPair::Secp256r1(kp) => kp.as_bytes().to_vec(),
             }
         }
     }
@@ -225,21 +207,25 @@ pub mod crypto {
         }
     }
 
-    // Implement starcoin Signer for StarcoinKeyPair
-    impl
-        starcoin_bridge_vm_types::bridge::crypto::Signer<
-            starcoin_bridge_vm_types::bridge::crypto::AuthoritySignature,
-        > for StarcoinKeyPair
-    {
-        fn sign(
+    impl StarcoinKeyPair {
+        /// Sign a message as an authority. Authority signatures are Ed25519, so
+        /// ECDSA keys can't produce one.
+        pub fn sign_authority(
             &self,
-            _msg: &[u8],
-        ) -> starcoin_bridge_vm_types::bridge::crypto::AuthoritySignature {
-            // Stub implementation - returns placeholder signature
-            use fastcrypto::traits::ToFromBytes;
-            // Create a placeholder Ed25519Signature with zeros
-            starcoin_bridge_vm_types::bridge::crypto::AuthoritySignature::from_bytes(&[0u8; 64])
-                .expect("Failed to create placeholder signature")
+            msg: &[u8],
+        ) -> Result<starcoin_bridge_vm_types::bridge::crypto::AuthoritySignature, FastCryptoError>
+        {
+            match self {
+                StarcoinKeyPair::Ed25519(kp) => Ok(fastcrypto::traits::Signer::<
+                    fastcrypto::ed25519::Ed25519Signature,
+                >::sign(kp, msg)),
+                StarcoinKeyPair::Secp256k1(_) => Err(FastCryptoError::GeneralError(
+                    "Secp256k1 keys can't produce Ed25519 authority signatures".to_string(),
+                )),
+                StarcoinKeyPair::Secp256r1(_) => Err(FastCryptoError::GeneralError(
+                    "Secp256r1 keys can't produce Ed25519 authority signatures".to_string(),
+                )),
+            }
         }
     }
 
@@ -258,9 +244,37 @@ pub mod crypto {
         }
     }
 
-    /// Signature scheme flags matching Starcoin's implementation
-    const ED25519_FLAG: u8 = 0x00;
-    const SECP256K1_FLAG: u8 = 0x01;
+    /// Scheme flags of the key pair encoding. Only `ED25519_FLAG` is also a
+    /// Starcoin authentication scheme: Starcoin's scheme 0x01 is MultiEd25519,
+    /// and it has no secp256k1 or secp256r1 scheme.
+    pub const ED25519_FLAG: u8 = 0x00;
+    pub const SECP256K1_FLAG: u8 = 0x01;
+    pub const SECP256R1_FLAG: u8 = 0x02;
+
+    /// Derive the Starcoin account address of a public key.
+    ///
+    /// The address derivation follows Starcoin's algorithm:
+    /// 1. Create preimage: pubkey_bytes || scheme_flag (0x00 for Ed25519)
+    /// 2. Hash with SHA3-256 to get AuthenticationKey (32 bytes)
+    /// 3. Take the last 16 bytes as the AccountAddress
+    ///
+    /// For ECDSA keys the rule is applied with their encoding flag, but no
+    /// Starcoin account can be authenticated by such a key, so their
+    /// addresses only identify the key, e.g. to the mock clients of tests.
+    pub fn starcoin_address_from_public_key(
+        pubkey_bytes: &[u8],
+        scheme_flag: u8,
+    ) -> move_core_types::account_address::AccountAddress {
+        use sha3::{Digest, Sha3_256};
+
+        let mut preimage = pubkey_bytes.to_vec();
+        preimage.push(scheme_flag);
+        let hash = Sha3_256::digest(&preimage);
+
+        let mut addr_bytes = [0u8; 16];
+        addr_bytes.copy_from_slice(&hash[16..32]);
+        move_core_types::account_address::AccountAddress::new(addr_bytes)
+    }
 
     impl StarcoinKeyPair {
         /// Get the scheme flag for this keypair
@@ -268,6 +282,7 @@ pub mod crypto {
             match self {
                 StarcoinKeyPair::Ed25519(_) => ED25519_FLAG,
                 StarcoinKeyPair::Secp256k1(_) => SECP256K1_FLAG,
+                StarcoinKeyPair::Secp256r1(_) => SECP256R1_FLAG,
             }
         }
 
@@ -285,6 +300,9 @@ pub mod crypto {
                 StarcoinKeyPair::Secp256k1(kp) => {
                     bytes.extend_from_slice(kp.as_bytes());
                 }
+                StarcoinKeyPair::Secp256r1(kp) => {
+                    bytes.extend_from_slice(kp.as_bytes());
+                }
             }
             bytes
         }
@@ -304,10 +322,129 @@ pub mod crypto {
                         .map_err(|_| FastCryptoError::InvalidInput)?;
                     Ok(StarcoinKeyPair::Secp256k1(kp))
                 }
+                SECP256R1_FLAG => {
+                    let kp = Secp256r1KeyPair::from_bytes(&bytes[1..])
+                        .map_err(|_| FastCryptoError::InvalidInput)?;
+                    Ok(StarcoinKeyPair::Secp256r1(kp))
+                }
                 _ => Err(FastCryptoError::InvalidInput),
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+        use fastcrypto::secp256k1::Secp256k1Signature;
+        use fastcrypto::secp256r1::{Secp256r1PublicKey, Secp256r1Signature};
+        use fastcrypto::traits::{Signer, VerifyingKey};
+
+        #[test]
+        fn test_sign_authority() {
+            let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Ed25519(kp);
+            let pubkey = Ed25519PublicKey::from_bytes(&kp.public()).unwrap();
+            let sig = kp.sign_authority(b"bridge message").unwrap();
+            pubkey.verify(b"bridge message", &sig).unwrap();
+            pubkey.verify(b"bridge messagf", &sig).unwrap_err();
+
+            let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Secp256k1(kp);
+            assert!(matches!(
+                kp.sign_authority(b"bridge message"),
+                Err(FastCryptoError::GeneralError(_))
+            ));
+
+            let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Secp256r1(kp);
+            assert!(matches!(
+                kp.sign_authority(b"bridge message"),
+                Err(FastCryptoError::GeneralError(_))
+            ));
+        }
+
+        #[test]
+        fn test_sign_signature() {
+            let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Ed25519(kp);
+            let Signature(sig) = kp.sign(b"tx data");
+            let pubkey = Ed25519PublicKey::from_bytes(&kp.public()).unwrap();
+            let sig = Ed25519Signature::from_bytes(&sig).unwrap();
+            pubkey.verify(b"tx data", &sig).unwrap();
+            pubkey.verify(b"tx datb", &sig).unwrap_err();
+
+            let (_, kp): (_, Secp256k1KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Secp256k1(kp);
+            let Signature(sig) = kp.sign(b"tx data");
+            let pubkey = Secp256k1PublicKey::from_bytes(&kp.public()).unwrap();
+            let sig = Secp256k1Signature::from_bytes(&sig).unwrap();
+            pubkey.verify(b"tx data", &sig).unwrap();
+            pubkey.verify(b"tx datb", &sig).unwrap_err();
+
+            let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+            let kp = StarcoinKeyPair::Secp256r1(kp);
+            let Signature(sig) = kp.sign(b"tx data");
+            let pubkey = Secp256r1PublicKey::from_bytes(&kp.public()).unwrap();
+            let sig = Secp256r1Signature::from_bytes(&sig).unwrap();
+            pubkey.verify(b"tx data", &sig).unwrap();
+            pubkey.verify(b"tx datb", &sig).unwrap_err();
+        }
+
+        // The Ed25519 key of RFC 8032's first test vector. Its address is
+        // checked against Starcoin's own `AuthenticationKey` in the bridge
+        // crate, which depends on starcoin-vm-types, see
+        // `test_starcoin_address_matches_authentication_key` there.
+        #[test]
+        fn test_starcoin_address_of_ed25519_key() {
+            use fastcrypto::encoding::{Encoding, Hex};
+
+            let kp = StarcoinKeyPair::Ed25519(
+                Ed25519KeyPair::from_bytes(
+                    &Hex::decode(
+                        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            );
+            assert_eq!(
+                Hex::encode(kp.public()),
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+            );
+            assert_eq!(
+                Hex::encode(kp.starcoin_address().to_vec()),
+                "f4eb152cfd2054c1080fd9d57c48913b"
+            );
+            assert_eq!(
+                starcoin_address_from_public_key(&kp.public(), ED25519_FLAG),
+                kp.starcoin_address()
+            );
+        }
+
+        #[test]
+        fn test_key_pair_base64_roundtrip() {
+            let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+            let (_, k1): (_, Secp256k1KeyPair) = get_key_pair();
+            let (_, r1): (_, Secp256r1KeyPair) = get_key_pair();
+            for (kp, flag) in [
+                (StarcoinKeyPair::Ed25519(kp), ED25519_FLAG),
+                (StarcoinKeyPair::Secp256k1(k1), SECP256K1_FLAG),
+                (StarcoinKeyPair::Secp256r1(r1), SECP256R1_FLAG),
+            ] {
+                assert_eq!(kp.to_bytes()[0], flag);
+                let decoded = StarcoinKeyPair::decode_base64(&kp.encode_base64()).unwrap();
+                assert_eq!(decoded.to_bytes(), kp.to_bytes());
+                assert_eq!(decoded.public(), kp.public());
+                assert_eq!(decoded.starcoin_address(), kp.starcoin_address());
+            }
+
+            // Unknown scheme flag
+            let mut bytes = StarcoinKeyPair::Secp256r1(get_key_pair().1).to_bytes();
+            bytes[0] = 0x03;
+            assert!(StarcoinKeyPair::from_bytes(&bytes).is_err());
+        }
+    }
 }
 
 pub mod message_envelope {
@@ -649,9 +786,11 @@ pub mod transaction {
             Transaction(vec![])
         }
 
-        pub fn digest(&self) -> &super::base_types::TransactionDigest {
-            static DIGEST: super::base_types::TransactionDigest = [0u8; 32];
-            &DIGEST
+        /// The legacy wrapper never holds a signed transaction, so it has no
+        /// digest. Returning a placeholder here used to put an all-zero digest
+        /// in logs and mock lookups.
+        pub fn digest(&self) -> Option<super::base_types::TransactionDigest> {
+            None
         }
     }
 
@@ -926,8 +1065,11 @@ pub mod execution_status {
 }
 
 pub mod programmable_transaction_builder {
+    use super::base_types::StarcoinAddress;
     use super::transaction::*;
 
+    pub const ETH_ADDRESS_LENGTH: usize = 20;
+
     pub struct ProgrammableTransactionBuilder {
         inputs: Vec<CallArg>,
         commands: Vec<Command>,
@@ -949,12 +1091,60 @@ pub mod programmable_transaction_builder {
             }
         }
 
+        /// Prefer the typed `pure_*` helpers below; this is for arguments
+        /// they don't cover, like vectors of signatures.
         pub fn pure<T: serde::Serialize>(&mut self, value: T) -> Result<Argument, String> {
             let bytes = bcs::to_bytes(&value).map_err(|e| e.to_string())?;
+            // Only zero sized types encode to nothing, and no Move argument
+            // is zero sized
+            debug_assert!(!bytes.is_empty(), "pure argument serialized to no bytes");
+            Ok(self.push_pure(bytes))
+        }
+
+        pub fn pure_u8(&mut self, value: u8) -> Argument {
+            self.push_pure(vec![value])
+        }
+
+        pub fn pure_bool(&mut self, value: bool) -> Argument {
+            self.push_pure(vec![value as u8])
+        }
+
+        pub fn pure_u64(&mut self, value: u64) -> Argument {
+            self.push_pure(value.to_le_bytes().to_vec())
+        }
+
+        pub fn pure_u128(&mut self, value: u128) -> Argument {
+            self.push_pure(value.to_le_bytes().to_vec())
+        }
+
+        /// A Move `address`: the 16 address bytes, without a length prefix
+        pub fn pure_address(&mut self, address: StarcoinAddress) -> Argument {
+            self.push_pure(address.to_vec())
+        }
+
+        /// An Eth address as the `vector<u8>` bridge messages carry it
+        pub fn pure_eth_address(&mut self, address: [u8; ETH_ADDRESS_LENGTH]) -> Argument {
+            self.pure_vec_bytes(&address)
+        }
+
+        /// A Move `vector<u8>`: the ULEB128 length followed by the bytes
+        pub fn pure_vec_bytes(&mut self, bytes: &[u8]) -> Argument {
+            let mut encoded = Vec::with_capacity(bytes.len() + 5);
+            let mut len = bytes.len();
+            while len >= 0x80 {
+                encoded.push((len & 0x7f) as u8 | 0x80);
+                len >>= 7;
+            }
+            encoded.push(len as u8);
+            encoded.extend_from_slice(bytes);
+            self.push_pure(encoded)
+        }
+
+        fn push_pure(&mut self, bytes: Vec<u8>) -> Argument {
             let input_idx = self.next_input;
             self.next_input += 1;
             self.inputs.push(CallArg::Pure(bytes));
-            Ok(Argument::Input(input_idx))
+            Argument::Input(input_idx)
         }
 
         pub fn input(&mut self, call_arg: CallArg) -> Result<Argument, String> {
@@ -997,6 +1187,78 @@ pub mod programmable_transaction_builder {
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn pure_inputs(builder: ProgrammableTransactionBuilder) -> Vec<Vec<u8>> {
+            builder
+                .finish()
+                .inputs
+                .into_iter()
+                .map(|input| match input {
+                    CallArg::Pure(bytes) => bytes,
+                    CallArg::Object(_) => panic!("Unexpected object input"),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_pure_helpers_encoding() {
+            let mut address = [0u8; 16];
+            address[15] = 0x01;
+            address[0] = 0xab;
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pure_u8(12);
+            builder.pure_bool(true);
+            builder.pure_u64(0x0102030405060708);
+            builder.pure_u128(1);
+            builder.pure_address(StarcoinAddress::new(address));
+            builder.pure_eth_address([0x11; ETH_ADDRESS_LENGTH]);
+            builder.pure_vec_bytes(&[]);
+            builder.pure_vec_bytes(&[0x42; 200]);
+
+            let inputs = pure_inputs(builder);
+            assert_eq!(inputs[0], vec![12]);
+            assert_eq!(inputs[1], vec![1]);
+            assert_eq!(inputs[2], vec![8, 7, 6, 5, 4, 3, 2, 1]);
+            let mut expected = vec![0u8; 16];
+            expected[0] = 1;
+            assert_eq!(inputs[3], expected);
+            assert_eq!(inputs[4], address.to_vec());
+            let mut expected = vec![20];
+            expected.extend([0x11; 20]);
+            assert_eq!(inputs[5], expected);
+            assert_eq!(inputs[6], vec![0]);
+            // 200 needs a two byte ULEB128 length
+            let mut expected = vec![0xc8, 0x01];
+            expected.extend([0x42; 200]);
+            assert_eq!(inputs[7], expected);
+        }
+
+        #[test]
+        fn test_pure_helpers_match_bcs() {
+            let address = StarcoinAddress::new([7; 16]);
+            let bytes = vec![9u8; 300];
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pure_u8(255);
+            builder.pure(255u8).unwrap();
+            builder.pure_u64(u64::MAX);
+            builder.pure(u64::MAX).unwrap();
+            builder.pure_u128(u128::MAX);
+            builder.pure(u128::MAX).unwrap();
+            builder.pure_address(address);
+            builder.pure(address).unwrap();
+            builder.pure_vec_bytes(&bytes);
+            builder.pure(&bytes).unwrap();
+
+            let inputs = pure_inputs(builder);
+            for pair in inputs.chunks(2) {
+                assert_eq!(pair[0], pair[1]);
+            }
+        }
+    }
 }
 
 pub mod gas_coin {
@@ -98,6 +98,17 @@ impl BridgeChainId {
                 | BridgeChainId::StarcoinCustom
         )
     }
+
+    // The bridge chain of a Starcoin node's chain id, as mapped in
+    // `ChainIDs.move`: main is 1, the test networks are 251 to 253 and any
+    // other network, e.g. dev (254), is custom.
+    pub fn from_starcoin_node_chain_id(chain_id: u8) -> Self {
+        match chain_id {
+            1 => BridgeChainId::StarcoinMainnet,
+            251..=253 => BridgeChainId::StarcoinTestnet,
+            _ => BridgeChainId::StarcoinCustom,
+        }
+    }
 }
 
 pub fn get_bridge_obj_initial_shared_version(
@@ -371,6 +382,8 @@ impl BridgeTrait for BridgeInnerV1 {
             treasury: BridgeTreasurySummary {
                 supported_tokens,
                 id_token_type_map,
+                // Not part of the BCS treasury layout, only reported by the JSON RPC view
+                paused_token_ids: vec![],
             },
             is_frozen: self.frozen,
         })
@@ -442,12 +455,70 @@ pub struct BridgeLimiterSummary {
     pub transfer_records: Vec<(BridgeChainId, BridgeChainId, MoveTypeBridgeTransferRecord)>,
 }
 
+impl BridgeLimiterSummary {
+    /// USD value the route may carry over 24 hours, or None if the route has
+    /// no limit, in which case the limiter refuses its transfers.
+    pub fn transfer_limit(
+        &self,
+        sending_chain: BridgeChainId,
+        receiving_chain: BridgeChainId,
+    ) -> Option<u64> {
+        self.transfer_limit
+            .iter()
+            .find(|(source, destination, _)| {
+                *source == sending_chain && *destination == receiving_chain
+            })
+            .map(|(_, _, limit)| *limit)
+    }
+
+    /// Transfers recorded on the route. A route without a record has no
+    /// transfers yet.
+    pub fn transfer_record(
+        &self,
+        sending_chain: BridgeChainId,
+        receiving_chain: BridgeChainId,
+    ) -> Option<&MoveTypeBridgeTransferRecord> {
+        self.transfer_records
+            .iter()
+            .find(|(source, destination, _)| {
+                *source == sending_chain && *destination == receiving_chain
+            })
+            .map(|(_, _, record)| record)
+    }
+
+    /// The limit of the route minus what it carried over the 24 hours up to
+    /// `current_hour_since_epoch`, or None if the route has no limit.
+    pub fn remaining_transfer_budget(
+        &self,
+        sending_chain: BridgeChainId,
+        receiving_chain: BridgeChainId,
+        current_hour_since_epoch: u64,
+    ) -> Option<u64> {
+        let limit = self.transfer_limit(sending_chain, receiving_chain)?;
+        let used = self
+            .transfer_record(sending_chain, receiving_chain)
+            .map_or(0, |record| {
+                record.rolling_total_amount(current_hour_since_epoch)
+            });
+        Some(limit.saturating_sub(used))
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BridgeTreasurySummary {
     pub supported_tokens: Vec<(String, BridgeTokenMetadata)>,
     pub id_token_type_map: Vec<(u8, String)>,
+    /// Ids of tokens paused individually by governance
+    #[serde(default)]
+    pub paused_token_ids: Vec<u8>,
+}
+
+impl BridgeTreasurySummary {
+    pub fn is_token_paused(&self, token_id: u8) -> bool {
+        self.paused_token_ids.contains(&token_id)
+    }
 }
 
 /// Rust version of the Move committee::CommitteeMember type.
@@ -505,6 +576,54 @@ pub struct MoveTypeBridgeTransferRecord {
     total_amount: u64,
 }
 
+impl MoveTypeBridgeTransferRecord {
+    pub fn new(
+        hour_head: u64,
+        hour_tail: u64,
+        per_hour_amounts: Vec<u64>,
+        total_amount: u64,
+    ) -> Self {
+        Self {
+            hour_head,
+            hour_tail,
+            per_hour_amounts,
+            total_amount,
+        }
+    }
+
+    /// USD value transferred over the 24 hours up to `current_hour_since_epoch`,
+    /// i.e. `total_amount` without the hours the limiter evicts before
+    /// recording the next transfer.
+    pub fn rolling_total_amount(&self, current_hour_since_epoch: u64) -> u64 {
+        let target_tail = current_hour_since_epoch.saturating_sub(23);
+        if self.hour_head < target_tail {
+            return 0;
+        }
+        // `per_hour_amounts[i]` is the amount of hour `hour_tail + i`
+        let expired: u64 = self
+            .per_hour_amounts
+            .iter()
+            .take(target_tail.saturating_sub(self.hour_tail) as usize)
+            .sum();
+        self.total_amount.saturating_sub(expired)
+    }
+
+    /// `(hour since epoch, USD value)` of the hours with transfers among the
+    /// 24 hours up to `current_hour_since_epoch`, oldest first. They add up
+    /// to `rolling_total_amount`.
+    pub fn hourly_amounts(&self, current_hour_since_epoch: u64) -> Vec<(u64, u64)> {
+        let target_tail = current_hour_since_epoch.saturating_sub(23);
+        self.per_hour_amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| (self.hour_tail + i as u64, *amount))
+            .filter(|(hour, amount)| {
+                *amount > 0 && *hour >= target_tail && *hour <= current_hour_since_epoch
+            })
+            .collect()
+    }
+}
+
 /// Rust version of the Move message::BridgeMessage type.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoveTypeBridgeMessage {
@@ -36,18 +36,14 @@ pub fn authority_public_key_bytes(pk: &Ed25519PublicKey) -> AuthorityPublicKeyBy
 pub struct Signature(pub Vec<u8>);
 
 impl Signature {
-    // Create a new secure signature - compatible with starcoin_bridge_types::crypto::Signature
-    pub fn new_secure<T, S>(_intent_msg: &T, _signer: &S) -> Self
+    // Signs the BCS bytes of an intent message
+    pub fn new_secure<T, S>(intent_msg: &T, signer: &S) -> Self
     where
         T: Serialize,
-        S: ?Sized + Signer<AuthoritySignature>,
+        S: ?Sized + SignerTrait<Signature>,
     {
-        // For now, return an empty signature (stub implementation)
-        // In real implementation, this would:
-        // 1. Serialize the intent message
-        // 2. Hash it
-        // 3. Sign with the signer
-        Signature(Vec::new())
+        let msg = bcs::to_bytes(intent_msg).expect("intent message serialization should not fail");
+        signer.sign(&msg)
     }
 }
 